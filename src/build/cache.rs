@@ -0,0 +1,136 @@
+use super::CompiledFile;
+use crate::compile::CompileOptions;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::UNIX_EPOCH;
+
+/// First line of every cache entry; bump when the entry format changes
+const HEADER: &str = "grit-cache 1";
+
+/// On-disk cache of per-file compilation results, keyed by a hash of the source
+///
+/// Entries are content-addressed, so renaming or moving a file keeps its entry, and
+/// any edit (or a different build of Grit) produces a different key.
+#[derive(Debug, Clone)]
+pub struct BuildCache {
+    dir: PathBuf,
+}
+
+impl BuildCache {
+    /// Creates a cache stored in the given directory, which is created on first write
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        BuildCache { dir: dir.into() }
+    }
+
+    /// Returns the directory entries are stored in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the cache key for a source file's contents, as compiled by this build
+    /// of Grit
+    pub fn key(source: &str) -> String {
+        Self::key_with_options(source, &CompileOptions::default())
+    }
+
+    /// Returns the cache key for a source file's contents, as compiled by this build
+    /// of Grit with the given options
+    pub fn key_with_options(source: &str, options: &CompileOptions) -> String {
+        let passes = options
+            .optimize
+            .as_ref()
+            .map_or(String::new(), ToString::to_string);
+        let mut hash = FNV_OFFSET;
+        let parts = [
+            compiler_stamp().as_bytes(),
+            &[0],
+            passes.as_bytes(),
+            &[0],
+            source.as_bytes(),
+        ];
+        for bytes in parts {
+            for byte in bytes {
+                hash ^= u64::from(*byte);
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        }
+        format!("{:016x}", hash)
+    }
+
+    /// Returns the cached result for a key, if there is a readable entry for it
+    pub fn load(&self, key: &str) -> Option<CompiledFile> {
+        let contents = fs::read_to_string(self.entry_path(key)).ok()?;
+        let mut lines = contents.split_inclusive('\n');
+
+        if lines.next()?.trim_end() != HEADER {
+            return None;
+        }
+        let succeeded = match lines.next()?.trim_end() {
+            "ok" => true,
+            "error" => false,
+            _ => return None,
+        };
+
+        let mut messages = Vec::new();
+        for line in lines.by_ref() {
+            match line.trim_end().strip_prefix("! ") {
+                Some(message) => messages.push(message.to_string()),
+                None if line.trim_end() == "---" => break,
+                None => return None,
+            }
+        }
+
+        let code: String = lines.collect();
+        Some(CompiledFile {
+            generated_code: succeeded.then_some(code),
+            messages,
+        })
+    }
+
+    /// Writes the result for a key
+    pub fn store(&self, key: &str, compiled: &CompiledFile) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+
+        let mut contents = format!("{}\n", HEADER);
+        contents.push_str(if compiled.succeeded() {
+            "ok\n"
+        } else {
+            "error\n"
+        });
+        for message in &compiled.messages {
+            contents.push_str(&format!("! {}\n", message.replace('\n', " ")));
+        }
+        contents.push_str("---\n");
+        if let Some(code) = &compiled.generated_code {
+            contents.push_str(code);
+        }
+
+        fs::write(self.entry_path(key), contents)
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.cache", key))
+    }
+}
+
+/// Identifies the running build of Grit: its version, and the size and modification
+/// time of its executable, which change with every rebuild even when the version
+/// does not
+fn compiler_stamp() -> &'static str {
+    static STAMP: OnceLock<String> = OnceLock::new();
+    STAMP.get_or_init(|| {
+        let executable = std::env::current_exe().and_then(fs::metadata).ok();
+        let size = executable.as_ref().map_or(0, |metadata| metadata.len());
+        let modified = executable
+            .and_then(|metadata| metadata.modified().ok())
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |elapsed| elapsed.as_nanos());
+        format!("{} {} {}", env!("CARGO_PKG_VERSION"), size, modified)
+    })
+}
+
+// 64-bit FNV-1a: stable across Rust versions, unlike `DefaultHasher`
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
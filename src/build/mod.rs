@@ -0,0 +1,214 @@
+pub mod cache;
+
+pub use cache::BuildCache;
+
+use crate::compile::{compile_with_options, CompileOptions};
+use crate::optimize::Pipeline;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Settings for `grit build`
+#[derive(Debug, Clone)]
+pub struct BuildOptions {
+    /// Reuse and record results in the cache directory
+    pub use_cache: bool,
+    /// Where cache entries are stored
+    pub cache_dir: PathBuf,
+    /// Where generated Rust files are written
+    pub out_dir: PathBuf,
+    /// The optimization passes run over each file before its code is generated
+    pub optimize: Option<Pipeline>,
+}
+
+impl Default for BuildOptions {
+    fn default() -> Self {
+        BuildOptions {
+            use_cache: true,
+            cache_dir: PathBuf::from(".grit-cache"),
+            out_dir: PathBuf::from("target/grit"),
+            optimize: None,
+        }
+    }
+}
+
+/// The result of parsing, checking, and generating code for one source file
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompiledFile {
+    /// Generated Rust code, or `None` if the file has errors
+    pub generated_code: Option<String>,
+    /// Parse errors and semantic diagnostics, formatted for display
+    pub messages: Vec<String>,
+}
+
+impl CompiledFile {
+    /// Checks if code was generated
+    pub fn succeeded(&self) -> bool {
+        self.generated_code.is_some()
+    }
+}
+
+/// What happened to one file during a build
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileOutcome {
+    pub source: PathBuf,
+    /// Where the generated code was written, if the file compiled
+    pub output: Option<PathBuf>,
+    /// Whether the result came from the cache instead of being recomputed
+    pub cached: bool,
+    pub messages: Vec<String>,
+}
+
+/// Per-file outcomes of a build, in the order the files were processed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BuildReport {
+    pub files: Vec<FileOutcome>,
+}
+
+impl BuildReport {
+    /// Returns the number of files recompiled without errors
+    pub fn compiled(&self) -> usize {
+        self.files
+            .iter()
+            .filter(|file| !file.cached && file.output.is_some())
+            .count()
+    }
+
+    /// Returns the number of files without errors loaded from the cache
+    pub fn cached(&self) -> usize {
+        self.files
+            .iter()
+            .filter(|file| file.cached && file.output.is_some())
+            .count()
+    }
+
+    /// Returns the number of files with errors
+    pub fn failed(&self) -> usize {
+        self.files
+            .iter()
+            .filter(|file| file.output.is_none())
+            .count()
+    }
+}
+
+/// Compiles a single Grit source to Rust
+pub fn compile_source(source: &str) -> CompiledFile {
+    compile_source_with_options(source, &CompileOptions::default())
+}
+
+/// Compiles a single Grit source to Rust, as the options say
+pub fn compile_source_with_options(source: &str, options: &CompileOptions) -> CompiledFile {
+    let result = compile_with_options(source, options);
+    let messages = match &result.ast {
+        Ok(_) => result.diagnostics.iter().map(ToString::to_string).collect(),
+        Err(err) if result.parse_errors.is_empty() => vec![format!("Parse error: {}", err)],
+        Err(_) => result
+            .parse_errors
+            .iter()
+            .map(|err| format!("Parse error: {}", err))
+            .collect(),
+    };
+
+    CompiledFile {
+        generated_code: result.generated_code,
+        messages,
+    }
+}
+
+/// Builds every `.grit` file under the given paths, writing one `.rs` file per source
+///
+/// Directories are searched recursively, skipping hidden directories and `target`.
+/// Unchanged files are loaded from the cache unless caching is disabled; a file
+/// built with other optimization passes counts as changed.
+pub fn build(paths: &[PathBuf], options: &BuildOptions) -> io::Result<BuildReport> {
+    let cache = BuildCache::new(&options.cache_dir);
+    let mut report = BuildReport::default();
+    let compile_options = CompileOptions {
+        optimize: options.optimize.clone(),
+        ..CompileOptions::default()
+    };
+
+    for (source, relative) in collect_sources(paths)? {
+        let contents = fs::read_to_string(&source)?;
+        let key = BuildCache::key_with_options(&contents, &compile_options);
+
+        let cached = if options.use_cache {
+            cache.load(&key)
+        } else {
+            None
+        };
+        let from_cache = cached.is_some();
+        let compiled = match cached {
+            Some(compiled) => compiled,
+            None => {
+                let compiled = compile_source_with_options(&contents, &compile_options);
+                if options.use_cache {
+                    cache.store(&key, &compiled)?;
+                }
+                compiled
+            }
+        };
+
+        let output = match &compiled.generated_code {
+            Some(code) => {
+                let output = options.out_dir.join(relative.with_extension("rs"));
+                if let Some(parent) = output.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(&output, code)?;
+                Some(output)
+            }
+            None => None,
+        };
+
+        report.files.push(FileOutcome {
+            source,
+            output,
+            cached: from_cache,
+            messages: compiled.messages,
+        });
+    }
+
+    Ok(report)
+}
+
+/// Finds the sources to build, each with its path relative to the argument it came from
+pub(crate) fn collect_sources(paths: &[PathBuf]) -> io::Result<Vec<(PathBuf, PathBuf)>> {
+    let mut sources = Vec::new();
+
+    for path in paths {
+        if path.is_dir() {
+            let mut found = Vec::new();
+            find_grit_files(path, &mut found)?;
+            found.sort();
+            for file in found {
+                let relative = file.strip_prefix(path).unwrap_or(&file).to_path_buf();
+                sources.push((file, relative));
+            }
+        } else {
+            let name = path.file_name().map(PathBuf::from).unwrap_or_default();
+            sources.push((path.clone(), name));
+        }
+    }
+
+    Ok(sources)
+}
+
+fn find_grit_files(dir: &Path, found: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("");
+
+        if path.is_dir() {
+            if !name.starts_with('.') && name != "target" {
+                find_grit_files(&path, found)?;
+            }
+        } else if path.extension().is_some_and(|ext| ext == "grit") {
+            found.push(path);
+        }
+    }
+    Ok(())
+}
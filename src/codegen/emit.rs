@@ -0,0 +1,171 @@
+//! Writing generated code out as it is produced
+//!
+//! An `Emitter` writes into any `fmt::Write` and keeps track of how deeply the code
+//! is nested, so a piece of code can be generated once, without indentation, and be
+//! written out at whatever depth it belongs (a static method is a function written
+//! one level into an `impl` block).
+//!
+//! Code with a body is generated the same way: the body is written one level into
+//! the block, so a statement's code only indents its body relative to its first
+//! line, however deeply the statement ends up nested.
+//!
+//! The generator indents by four spaces a level; `Reindent` rewrites that to another
+//! width on the way out.
+
+use std::fmt::{self, Write};
+use std::io;
+
+/// The spaces per level of the indentation the generator writes
+const WIDTH: usize = 4;
+
+/// Writes generated code, indented to the current depth
+pub(super) struct Emitter<'w> {
+    out: &'w mut dyn Write,
+    depth: usize,
+    /// Whether nothing has been written on the current line yet
+    line_start: bool,
+}
+
+impl<'w> Emitter<'w> {
+    pub(super) fn new(out: &'w mut dyn Write) -> Self {
+        Emitter {
+            out,
+            depth: 0,
+            line_start: true,
+        }
+    }
+
+    /// Writes code, indenting each of its lines that has text to the current depth
+    ///
+    /// Lines keep any indentation of their own on top of the depth's.
+    pub(super) fn code(&mut self, code: &str) -> fmt::Result {
+        for line in code.split_inclusive('\n') {
+            if self.line_start && line != "\n" {
+                write!(self.out, "{:1$}", "", self.depth * WIDTH)?;
+            }
+            self.out.write_str(line)?;
+            self.line_start = line.ends_with('\n');
+        }
+        Ok(())
+    }
+
+    /// Writes a statement at the current depth and ends its line
+    pub(super) fn statement(&mut self, code: &str) -> fmt::Result {
+        self.code(code)?;
+        self.newline()
+    }
+
+    /// Ends the current line, or writes an empty one at the start of a line
+    pub(super) fn newline(&mut self) -> fmt::Result {
+        self.line_start = true;
+        self.out.write_char('\n')
+    }
+
+    /// Writes the line opening a block, and nests what follows one level deeper
+    pub(super) fn open(&mut self, line: &str) -> fmt::Result {
+        self.code(line)?;
+        self.newline()?;
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Writes the line closing a block opened with `open`, one level out
+    pub(super) fn close(&mut self, line: &str) -> fmt::Result {
+        self.depth -= 1;
+        self.code(line)?;
+        self.newline()
+    }
+
+    /// Writes a line that closes one block and opens the next, as `} else {` does
+    pub(super) fn reopen(&mut self, line: &str) -> fmt::Result {
+        self.depth -= 1;
+        self.open(line)
+    }
+
+    /// Writes the line closing a block opened with `open`, leaving the line open for
+    /// the code that follows the block
+    pub(super) fn end(&mut self, line: &str) -> fmt::Result {
+        self.depth -= 1;
+        self.code(line)
+    }
+}
+
+/// Passes code through to another writer with its indentation rewritten from four
+/// spaces a level to `width`
+///
+/// Spaces left over from a whole level, as in a continued line, are kept as they are.
+pub(super) struct Reindent<W> {
+    inner: W,
+    width: usize,
+    /// The spaces read so far at the start of the current line, while only spaces
+    /// have been read on it; `None` once it has other text
+    leading: Option<usize>,
+}
+
+impl<W> Reindent<W> {
+    pub(super) fn new(inner: W, width: usize) -> Self {
+        Reindent {
+            inner,
+            width,
+            leading: Some(0),
+        }
+    }
+}
+
+impl<W: Write> Write for Reindent<W> {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        // Text after the indentation is written through in runs
+        let mut run = 0;
+        for (i, c) in text.char_indices() {
+            match (self.leading, c) {
+                (Some(spaces), ' ') => {
+                    self.leading = Some(spaces + 1);
+                    run = i + 1;
+                }
+                (Some(spaces), _) => {
+                    let levels = spaces / WIDTH;
+                    write!(
+                        self.inner,
+                        "{:1$}",
+                        "",
+                        levels * self.width + spaces % WIDTH
+                    )?;
+                    if c == '\n' {
+                        self.inner.write_char('\n')?;
+                        self.leading = Some(0);
+                        run = i + 1;
+                    } else {
+                        self.leading = None;
+                        run = i;
+                    }
+                }
+                (None, '\n') => {
+                    self.inner.write_str(&text[run..=i])?;
+                    self.leading = Some(0);
+                    run = i + 1;
+                }
+                (None, _) => {}
+            }
+        }
+        if self.leading.is_none() {
+            self.inner.write_str(&text[run..])?;
+        }
+        Ok(())
+    }
+}
+
+/// Adapts an `io::Write` to `fmt::Write`, keeping the I/O error that `fmt::Error`
+/// can't carry
+pub(super) struct IoWriter<W> {
+    pub(super) inner: W,
+    pub(super) error: Option<io::Error>,
+}
+
+impl<W: io::Write> Write for IoWriter<W> {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.inner.write_all(text.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
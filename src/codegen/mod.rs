@@ -1,270 +1,1580 @@
-use crate::parser::{BinaryOperator, Expr, Program, Statement};
+mod emit;
+mod mutation;
+pub mod options;
+pub mod runtime;
+pub mod source_map;
+
+use crate::lexer::Span;
+use crate::parser::ast::visit::{walk_body, walk_expr, walk_statement};
+use crate::parser::precedence::UNARY_PRECEDENCE;
+use crate::parser::{
+    Associativity, BinaryOperator, Expr, MatchArm, Pattern, Program, ProgramIndex, Statement,
+    TraitMethod, TypeAnnotation, UnaryOperator, Visitor,
+};
+use crate::semantic::format::{parse_format_parts, FormatPart, FormatSpec, Placeholder};
+use crate::semantic::{bind_self, body_returns, FunctionSymbol, SymbolTable, Type, TypeEnv};
+use emit::{Emitter, IoWriter, Reindent};
+use mutation::MutatingMethods;
+use options::{CodegenOptions, IntType};
+use runtime::RuntimeHelper;
+use source_map::{Extractor, SourceMap};
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::io;
 
 /// Generates Rust source code from Grit ASTs.
-pub struct CodeGenerator;
+pub struct CodeGenerator<'a> {
+    /// Types of the variables in scope, resolved against the program's symbol table
+    env: TypeEnv<'a>,
+    /// The return type of the function being generated, `Unknown` when it returns
+    /// nothing or the type can't be told
+    return_type: Type,
+    /// The methods that assign fields of their instance, which take `&mut self`
+    mutating: MutatingMethods,
+    options: CodegenOptions,
+    /// Variables of the body being generated whose fields it assigns, bound with `let mut`
+    mutated: HashSet<String>,
+    /// The fields each class's struct declares itself, without those it inherits
+    fields: HashMap<String, Vec<String>>,
+    /// The class whose instance method is being generated, whose fields the body may
+    /// name without `self.`
+    self_class: Option<String>,
+    /// The fields of the constructor being generated, when its body is more than
+    /// field assignments, each held in a `self_field` local until `Self` is built
+    constructor_fields: HashMap<String, FieldLocal>,
+    helpers: RefCell<BTreeSet<RuntimeHelper>>,
+    /// Set while building a source map, to mark each statement's code with its span
+    mark_spans: bool,
+}
 
-impl CodeGenerator {
+impl<'a> CodeGenerator<'a> {
     /// Generates a Rust expression string equivalent to the provided AST.
+    ///
+    /// Without a surrounding program no classes are known, so every method call
+    /// is generated as an instance call.
     pub fn generate_expression(ast: &Expr) -> String {
-        Self::generate_expression_with_context(ast, None, false)
+        let symbols = SymbolTable::default();
+        CodeGenerator::new(&symbols).expression(ast)
     }
 
     /// Generates a full Rust program from a Grit Program AST.
     pub fn generate_program(program: &Program) -> String {
+        Self::generate_program_with_options(program, &CodegenOptions::default())
+    }
+
+    /// Generates a full Rust program shaped by the given options
+    pub fn generate_program_with_options(program: &Program, options: &CodegenOptions) -> String {
+        let mut code = String::new();
+        Self::generate_program_to(program, options, &mut code).unwrap();
+        code
+    }
+
+    /// Generates a full Rust program into a writer, writing each item out as soon as
+    /// it is generated rather than building the whole program first
+    ///
+    /// Fails only if the writer does.
+    pub fn generate_program_to<W: fmt::Write>(
+        program: &Program,
+        options: &CodegenOptions,
+        out: &mut W,
+    ) -> fmt::Result {
+        let symbols = SymbolTable::from_program(program);
+        let mut generator = CodeGenerator::for_program(program, &symbols);
+        generator.options = options.clone();
+        generator.emit(out, |generator, out| generator.program(program, out))
+    }
+
+    /// Generates a full Rust program into an `io::Write`, such as a file
+    pub fn write_program<W: io::Write>(
+        program: &Program,
+        options: &CodegenOptions,
+        out: W,
+    ) -> io::Result<()> {
+        let mut writer = IoWriter {
+            inner: out,
+            error: None,
+        };
+        Self::generate_program_to(program, options, &mut writer).map_err(|_| {
+            writer
+                .error
+                .take()
+                .unwrap_or_else(|| io::Error::other(fmt::Error))
+        })
+    }
+
+    /// Generates a Rust program that runs every `test` block in a Grit Program
+    ///
+    /// Classes and functions are kept, top-level statements are not. Each test runs
+    /// in turn and reports one line per test; see [`RuntimeHelper::TestRunner`].
+    pub fn generate_test_harness(program: &Program) -> String {
+        let mut code = String::new();
+        Self::generate_test_harness_to(program, &CodegenOptions::default(), &mut code).unwrap();
+        code
+    }
+
+    /// Generates a test harness into a writer, as `generate_program_to` does a program
+    ///
+    /// The harness has its own `main`, so `wrap_in_main` does not apply.
+    pub fn generate_test_harness_to<W: fmt::Write>(
+        program: &Program,
+        options: &CodegenOptions,
+        out: &mut W,
+    ) -> fmt::Result {
+        let symbols = SymbolTable::from_program(program);
+        let mut generator = CodeGenerator::for_program(program, &symbols);
+        generator.options = options.clone();
+        generator.emit(out, |generator, out| generator.test_harness(program, out))
+    }
+
+    /// Generates a full Rust program along with the Grit statement each line of it
+    /// came from
+    pub fn generate_program_with_source_map(program: &Program) -> (String, SourceMap) {
+        let symbols = SymbolTable::from_program(program);
+        let mut generator = CodeGenerator::for_program(program, &symbols);
+        generator.mark_spans = true;
+        let mut extractor = Extractor::new(String::new());
+        generator
+            .program(program, &mut Emitter::new(&mut extractor))
+            .unwrap();
+        extractor.finish()
+    }
+
+    /// Generates a test harness along with the Grit statement each line of it came
+    /// from
+    pub fn generate_test_harness_with_source_map(program: &Program) -> (String, SourceMap) {
+        let symbols = SymbolTable::from_program(program);
+        let mut generator = CodeGenerator::for_program(program, &symbols);
+        generator.mark_spans = true;
+        let mut extractor = Extractor::new(String::new());
+        generator
+            .test_harness(program, &mut Emitter::new(&mut extractor))
+            .unwrap();
+        extractor.finish()
+    }
+
+    fn new(symbols: &'a SymbolTable) -> Self {
+        CodeGenerator {
+            env: TypeEnv::new(symbols),
+            return_type: Type::Unknown,
+            mutating: MutatingMethods::default(),
+            options: CodegenOptions::default(),
+            mutated: HashSet::new(),
+            fields: HashMap::new(),
+            self_class: None,
+            constructor_fields: HashMap::new(),
+            helpers: RefCell::new(BTreeSet::new()),
+            mark_spans: false,
+        }
+    }
+
+    /// Creates a generator for a program, with its functions' parameters and return
+    /// values typed
+    fn for_program(program: &Program, symbols: &'a SymbolTable) -> Self {
+        let mut generator = CodeGenerator::new(symbols);
+        generator.mutating = MutatingMethods::infer(program);
+        generator
+    }
+
+    /// Runs `write` with an emitter into `out`, indented as the options say
+    fn emit(
+        &mut self,
+        out: &mut dyn fmt::Write,
+        write: impl FnOnce(&mut Self, &mut Emitter) -> fmt::Result,
+    ) -> fmt::Result {
+        match self.options.indent_width {
+            4 => write(self, &mut Emitter::new(out)),
+            width => write(self, &mut Emitter::new(&mut Reindent::new(out, width))),
+        }
+    }
+
+    /// Writes the statements of a body into the block `out` has open
+    fn write_body(&mut self, body: &[Statement], out: &mut Emitter) -> fmt::Result {
+        for stmt in body {
+            self.write_statement(stmt, out)?;
+            out.newline()?;
+        }
+        Ok(())
+    }
+
+    /// Writes a body whose final expression, unless it is a `print`, is its value,
+    /// converted to `ty` when one is given
+    fn write_value_body(
+        &mut self,
+        body: &[Statement],
+        ty: Option<Type>,
+        out: &mut Emitter,
+    ) -> fmt::Result {
+        for (i, stmt) in body.iter().enumerate() {
+            match stmt {
+                Statement::Expression(expr)
+                    if i == body.len() - 1 && !Self::is_print_call(expr) =>
+                {
+                    let value = self.expression(expr);
+                    let value = match &ty {
+                        Some(ty) => self.coerce(expr, value, ty),
+                        None => value,
+                    };
+                    out.statement(&value)?;
+                }
+                _ => {
+                    self.write_statement(stmt, out)?;
+                    out.newline()?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Opens a nested variable scope (function body, block)
+    fn push_scope(&mut self) {
+        self.env.push_scope();
+    }
+
+    /// Closes the innermost variable scope
+    fn pop_scope(&mut self) {
+        self.env.pop_scope();
+    }
+
+    /// Records a variable in the innermost scope, as a `var` if it is mutable
+    fn declare(&mut self, name: &str, ty: Type, mutable: bool) {
+        if mutable {
+            self.env.bind_var(name, ty);
+        } else {
+            self.env.bind(name, ty);
+        }
+    }
+
+    /// Records the variable a binding condition introduces in the innermost scope
+    fn declare_binding(&mut self, condition: &Expr) {
+        if let Expr::Binding { name, value, .. } = condition {
+            // The body never sees a nil
+            let ty = self.env.infer(value).without_nil();
+            self.env.bind(name, ty);
+        }
+    }
+
+    /// Replaces the scopes with a fresh environment holding only the given parameters
+    /// and their types
+    ///
+    /// A variadic last parameter is an array of the extra arguments. Returns the
+    /// previous environment, return type, and mutated variables so the caller can
+    /// restore them with `leave_function` afterwards.
+    fn enter_function(
+        &mut self,
+        params: &[String],
+        param_types: &[Type],
+        variadic: bool,
+        body: &[Statement],
+    ) -> (TypeEnv<'a>, Type, HashSet<String>) {
+        let mut env = TypeEnv::new(self.env.symbols());
+        for (i, param) in params.iter().enumerate() {
+            let ty = param_types.get(i).cloned().unwrap_or(Type::Unknown);
+            if variadic && i == params.len() - 1 {
+                env.bind(param, Type::Array(Box::new(ty)));
+            } else {
+                env.bind(param, ty);
+            }
+        }
+        let mutated = self.mutated_variables(params, body);
+        (
+            std::mem::replace(&mut self.env, env),
+            std::mem::replace(&mut self.return_type, Type::Unknown),
+            std::mem::replace(&mut self.mutated, mutated),
+        )
+    }
+
+    /// Restores the state saved by `enter_function`
+    fn leave_function(
+        &mut self,
+        (env, return_type, mutated): (TypeEnv<'a>, Type, HashSet<String>),
+    ) {
+        self.env = env;
+        self.return_type = return_type;
+        self.mutated = mutated;
+    }
+
+    /// Returns what the body of the function being entered gives back, `None` for
+    /// nothing, and sets it as the type returned values convert to
+    ///
+    /// An annotated return type is used as written; other bodies are inferred with
+    /// the parameters bound, along with the fields of `self` in a method. A body
+    /// returning n-element tuples whose types disagree returns a tuple of unknowns.
+    fn enter_returns(
+        &mut self,
+        annotation: Option<TypeAnnotation>,
+        body: &[Statement],
+    ) -> Option<Type> {
+        let returns = match annotation {
+            Some(_) => Some(Type::annotated(annotation)),
+            None => {
+                let mut env = self.env.clone();
+                if let Some(Type::Class(class_name)) = self.env.lookup("self") {
+                    bind_self(&mut env, &class_name.clone());
+                }
+                match (
+                    body_returns(env, body),
+                    Self::returned_tuple_len(body, true),
+                ) {
+                    (Some(Type::Unknown), Some(len)) => Some(Type::Tuple(vec![Type::Unknown; len])),
+                    (returns, _) => returns,
+                }
+            }
+        };
+        self.return_type = returns.clone().unwrap_or(Type::Unknown);
+        returns
+    }
+
+    /// Returns the path to a class's field from an instance: the field itself, or
+    /// through the embedded parents to an ancestor's (`name` → `parent.name`)
+    fn field_path(&self, class_name: &str, field: &str) -> Option<String> {
+        let symbols = self.env.symbols();
+        let classes = std::iter::once(class_name).chain(
+            symbols
+                .ancestors(class_name)
+                .into_iter()
+                .map(|ancestor| ancestor.name.as_str()),
+        );
+        for (depth, class_name) in classes.enumerate() {
+            let own = self.fields.get(class_name).map_or(&[][..], Vec::as_slice);
+            if own.iter().any(|own| own == field) {
+                return Some(format!("{}{}", "parent.".repeat(depth), field));
+            }
+        }
+        None
+    }
+
+    /// Checks if a variable is visible in the current scope
+    fn is_variable(&self, name: &str) -> bool {
+        self.env.lookup(name).is_some()
+    }
+
+    /// Checks if a method call receiver names a class rather than a value
+    fn is_static_receiver(&self, name: &str) -> bool {
+        !self.is_variable(name) && self.env.symbols().is_class(name)
+    }
+
+    /// Returns the Rust type of integers
+    fn int_type(&self) -> &'static str {
+        self.options.int_type.rust_name()
+    }
+
+    /// Marks a runtime helper as used so it is emitted with the program
+    fn require(&self, helper: RuntimeHelper) {
+        self.helpers.borrow_mut().insert(helper);
+    }
+
+    /// Writes the source of every runtime helper used, after the code that uses them
+    fn write_helpers(&self, out: &mut Emitter) -> fmt::Result {
+        for helper in self.helpers.borrow().iter() {
+            out.newline()?;
+            out.code(helper.source())?;
+        }
+        Ok(())
+    }
+
+    fn expression(&self, ast: &Expr) -> String {
+        self.generate_expression_with_context(ast, None, false)
+    }
+
+    /// Writes a program: its items, then `main` running its other statements, then the
+    /// runtime helpers they use
+    fn program(&mut self, program: &Program, out: &mut Emitter) -> fmt::Result {
+        self.write_preamble(out)?;
+
         // Special case: if there's only one expression statement, evaluate and print it
         if program.statements.len() == 1 {
             if let Statement::Expression(expr) = &program.statements[0] {
                 if !matches!(expr, Expr::FunctionCall { .. }) {
-                    let expression = Self::generate_expression(expr);
-                    return format!(
-                        "fn main() {{\n    let result = {};\n    println!(\"{{}}\", result);\n}}\n",
-                        expression
-                    );
+                    let expression = self.expression(expr);
+                    self.open_main(out)?;
+                    out.statement(&format!("let result = {};", expression))?;
+                    out.statement("println!(\"{}\", result);")?;
+                    self.close_main(out)?;
+                    return self.write_helpers(out);
                 }
             }
         }
 
-        let mut code = String::new();
-        let mut main_body = String::new();
+        self.items(program, out)?;
+        self.mutated = self.mutated_variables(&[], &program.statements);
+
+        self.open_main(out)?;
+        for stmt in &program.statements {
+            if !Self::is_item(stmt) {
+                self.write_statement(stmt, out)?;
+                out.newline()?;
+            }
+        }
+        self.close_main(out)?;
+
+        self.write_helpers(out)
+    }
+
+    /// Writes the `#![allow(...)]` of the lints the options allow, if any
+    fn write_preamble(&self, out: &mut Emitter) -> fmt::Result {
+        if self.options.allow.is_empty() {
+            return Ok(());
+        }
+        out.statement(&format!("#![allow({})]", self.options.allow.join(", ")))?;
+        out.newline()
+    }
+
+    /// Opens `fn main` around the top-level statements, unless the options leave
+    /// them unwrapped
+    fn open_main(&self, out: &mut Emitter) -> fmt::Result {
+        if self.options.wrap_in_main {
+            out.open("fn main() {")?;
+        }
+        Ok(())
+    }
+
+    fn close_main(&self, out: &mut Emitter) -> fmt::Result {
+        if self.options.wrap_in_main {
+            out.close("}")?;
+        }
+        Ok(())
+    }
+
+    /// Writes a test harness: the program's items, a function per test block, and a
+    /// `main` that runs each of them
+    fn test_harness(&mut self, program: &Program, out: &mut Emitter) -> fmt::Result {
+        self.write_preamble(out)?;
+        self.items(program, out)?;
+
+        self.require(RuntimeHelper::TestRunner);
+        let tests = program.statements.iter().filter_map(|stmt| match stmt {
+            Statement::Test { body, .. } => Some(body),
+            _ => None,
+        });
+        let mut count = 0;
+        for (index, body) in tests.enumerate() {
+            let outer = self.enter_function(&[], &[], false, body);
+            out.open(&format!("fn grit_test_{}() {{", index))?;
+            for stmt in body {
+                self.write_statement(stmt, out)?;
+                out.newline()?;
+            }
+            out.close("}")?;
+            out.newline()?;
+            self.leave_function(outer);
+            count += 1;
+        }
+
+        out.open("fn main() {")?;
+        out.statement("// Failures are reported by grit_run_test, not the default panic message")?;
+        out.statement("std::panic::set_hook(Box::new(|_| {}));")?;
+        out.statement("let mut failed = 0;")?;
+        for index in 0..count {
+            out.open(&format!(
+                "if !grit_run_test({}, grit_test_{}) {{",
+                index, index
+            ))?;
+            out.statement("failed += 1;")?;
+            out.close("}")?;
+        }
+        out.open("if failed > 0 {")?;
+        out.statement("std::process::exit(1);")?;
+        out.close("}")?;
+        out.close("}")?;
+
+        self.write_helpers(out)
+    }
 
-        // Collect classes and their methods
-        use std::collections::HashMap;
-        let mut classes: HashMap<String, Vec<&Statement>> = HashMap::new();
+    /// Checks if a statement becomes a top-level Rust item rather than part of `main`
+    fn is_item(stmt: &Statement) -> bool {
+        matches!(
+            stmt,
+            Statement::FunctionDef { .. }
+                | Statement::ClassDef { .. }
+                | Statement::MixinDef { .. }
+                | Statement::TraitDef { .. }
+                | Statement::EnumDef { .. }
+                | Statement::MethodDef { .. }
+                | Statement::ConstDef { .. }
+                | Statement::Global { .. }
+                | Statement::Import { .. }
+                | Statement::Test { .. }
+        )
+    }
 
+    /// Generates the constants, structs, impl blocks, and functions of a program
+    fn items(&mut self, program: &Program, out: &mut Emitter) -> fmt::Result {
+        let mut any_constants = false;
         for stmt in &program.statements {
-            match stmt {
-                Statement::ClassDef { name } => {
-                    classes.entry(name.clone()).or_default();
-                }
-                Statement::MethodDef { class_name, .. } => {
-                    classes.entry(class_name.clone()).or_default().push(stmt);
-                }
-                _ => {}
+            if let Statement::ConstDef { .. } | Statement::Global { .. } = stmt {
+                self.write_statement(stmt, out)?;
+                out.newline()?;
+                any_constants = true;
+            }
+        }
+        if any_constants {
+            out.newline()?;
+        }
+
+        for stmt in &program.statements {
+            if let Statement::TraitDef { .. } = stmt {
+                self.write_statement(stmt, out)?;
+                out.newline()?;
+                out.newline()?;
             }
         }
 
+        for stmt in &program.statements {
+            if let Statement::EnumDef { .. } = stmt {
+                self.write_statement(stmt, out)?;
+                out.newline()?;
+                out.newline()?;
+            }
+        }
+
+        // Classes in order, each with its methods and those of its mixins
+        let index = program.index();
+        let classes: HashMap<&str, Vec<&Statement>> = index
+            .classes()
+            .iter()
+            .map(|&class_name| (class_name, self.class_methods(&index, class_name)))
+            .collect();
+
+        // Declared fields keep their order; otherwise collect them from all methods
+        let mut class_fields: HashMap<&str, Vec<String>> = HashMap::new();
+        for (&class_name, methods) in &classes {
+            let declared = index.declared_fields(class_name);
+            let fields = if declared.is_empty() {
+                let mut fields = std::collections::HashSet::new();
+                for method in methods {
+                    if let Statement::MethodDef { body, .. } = method {
+                        Self::collect_fields(body, &mut fields);
+                    }
+                }
+                fields.into_iter().collect()
+            } else {
+                declared.iter().map(|field| field.name.clone()).collect()
+            };
+            class_fields.insert(class_name, fields);
+        }
+
+        // A subclass embeds its parent as a `parent` field, which holds the inherited
+        // fields, so its own struct keeps only the fields no ancestor has
+        let symbols = self.env.symbols();
+        let own_fields: HashMap<&str, Vec<String>> = class_fields
+            .iter()
+            .map(|(&class_name, fields)| {
+                let inherited: HashSet<&String> = symbols
+                    .ancestors(class_name)
+                    .iter()
+                    .filter_map(|ancestor| class_fields.get(ancestor.name.as_str()))
+                    .flatten()
+                    .collect();
+                let own = fields
+                    .iter()
+                    .filter(|field| !inherited.contains(field))
+                    .cloned()
+                    .collect();
+                (class_name, own)
+            })
+            .collect();
+        self.fields = own_fields
+            .iter()
+            .map(|(&class_name, fields)| (class_name.to_string(), fields.clone()))
+            .collect();
+        let parents: HashSet<&str> = index
+            .classes()
+            .iter()
+            .filter_map(|class_name| symbols.class(class_name)?.parent.as_deref())
+            .collect();
+
         // Generate structs and impl blocks for each class
-        for (class_name, methods) in &classes {
-            // Collect all field names from all methods
-            let mut fields = std::collections::HashSet::new();
-            for method in methods {
-                if let Statement::MethodDef { body, .. } = method {
-                    Self::collect_fields(body, &mut fields);
+        for &class_name in index.classes() {
+            let methods = &classes[class_name];
+            let fields = &own_fields[class_name];
+            let ancestors: Vec<(&str, Vec<String>)> = symbols
+                .ancestors(class_name)
+                .into_iter()
+                .filter_map(|ancestor| {
+                    let fields = own_fields.get(ancestor.name.as_str())?;
+                    Some((ancestor.name.as_str(), fields.clone()))
+                })
+                .collect();
+
+            // Generate struct; fields are integers unless annotated or set from a typed
+            // parameter. Parents also derive `Default`, for the fields a subclass
+            // constructor leaves unset.
+            let mut field_types = self.constructor_field_types(methods);
+            for field in index.declared_fields(class_name) {
+                if let Some(ty) = field.ty {
+                    field_types.insert(&field.name, Type::from(ty));
                 }
             }
-
-            // Generate struct
-            code.push_str(&format!("#[derive(Clone)]\nstruct {} {{\n", class_name));
-            for field in &fields {
-                code.push_str(&format!("    {}: i64,\n", field));
+            let mut derives = self.options.derives.clone();
+            if parents.contains(class_name) && !derives.iter().any(|derive| derive == "Default") {
+                derives.push("Default".to_string());
             }
-            code.push_str("}\n\n");
+            Self::write_doc_comment(index.class_docs(class_name), out)?;
+            if !derives.is_empty() {
+                out.statement(&format!("#[derive({})]", derives.join(", ")))?;
+            }
+            out.open(&format!("struct {} {{", class_name))?;
+            if let Some((parent, _)) = ancestors.first() {
+                out.statement(&format!("parent: {},", parent))?;
+            }
+            for field in fields {
+                let ty = field_types
+                    .get(field.as_str())
+                    .map_or(self.int_type().to_string(), |ty| self.param_type(ty));
+                out.statement(&format!("{}: {},", field, ty))?;
+            }
+            out.close("}")?;
+            out.newline()?;
 
             // Generate impl block
-            code.push_str(&format!("impl {} {{\n", class_name));
+            out.open(&format!("impl {} {{", class_name))?;
             for method in methods {
                 if let Statement::MethodDef {
                     method_name,
-                    params,
-                    body,
+                    is_static,
+                    docs,
+                    span,
                     ..
                 } = method
                 {
-                    code.push_str(&Self::generate_method_impl(method_name, params, body));
+                    let Some(def) = self.definition(method) else {
+                        continue;
+                    };
+                    self.marked(*span, out, |generator, out| {
+                        Self::write_doc_comment(docs, out)?;
+                        if *is_static && method_name != "new" {
+                            // A static method has no `self`, so its body is a plain
+                            // function's
+                            generator.write_function_def(&def, out)
+                        } else if method_name == "new" {
+                            generator.write_constructor(&def, class_name, &ancestors, out)
+                        } else {
+                            generator.self_class = Some(class_name.to_string());
+                            let written = generator.write_method_impl(&def, out);
+                            generator.self_class = None;
+                            written
+                        }
+                    })?;
+                    out.newline()?;
+                    out.newline()?;
                 }
             }
-            code.push_str("}\n\n");
-        }
 
-        // Separate functions from main body statements
-        for stmt in &program.statements {
-            match stmt {
-                Statement::FunctionDef { .. } => {
-                    code.push_str(&Self::generate_statement(stmt));
-                    code.push('\n');
-                }
-                Statement::ClassDef { .. } | Statement::MethodDef { .. } => {
-                    // Already handled above
+            // Inherited methods the class does not define forward to the parent
+            let mut defined: HashSet<&str> = methods
+                .iter()
+                .filter_map(|method| match method {
+                    Statement::MethodDef { method_name, .. } => Some(method_name.as_str()),
+                    _ => None,
+                })
+                .collect();
+            for (ancestor, _) in &ancestors {
+                for method in classes.get(*ancestor).into_iter().flatten() {
+                    if let Statement::MethodDef {
+                        method_name,
+                        is_static,
+                        params,
+                        param_types,
+                        variadic,
+                        return_type,
+                        body,
+                        span,
+                        ..
+                    } = method
+                    {
+                        if method_name != "new" && defined.insert(method_name) {
+                            // Typed as the ancestor generated the method
+                            let types = self.signature_params(*span, param_types);
+                            let outer = self.enter_function(params, &types, *variadic, body);
+                            if !is_static {
+                                self.env.bind("self", Type::Class(ancestor.to_string()));
+                            }
+                            let returns = self.enter_returns(*return_type, body);
+                            let typed = self.typed_params(params, &types, *variadic, body);
+                            self.leave_function(outer);
+                            self.write_delegate(
+                                method_name,
+                                is_static.then_some(*ancestor),
+                                params,
+                                typed,
+                                &returns,
+                                out,
+                            )?;
+                            out.newline()?;
+                            out.newline()?;
+                        }
+                    }
                 }
-                _ => {
-                    main_body.push_str("    ");
-                    main_body.push_str(&Self::generate_statement(stmt));
-                    main_body.push('\n');
+            }
+            out.close("}")?;
+            out.newline()?;
+
+            // Each trait's methods forward to the class's own methods of the same name
+            let class_traits = symbols
+                .class(class_name)
+                .map_or(&[][..], |class| class.traits.as_slice());
+            for trait_name in class_traits {
+                if let Some(methods) = index.trait_methods(trait_name) {
+                    self.write_trait_impl(trait_name, class_name, methods, out)?;
                 }
             }
         }
 
-        // Add main function
-        code.push_str(&format!("fn main() {{\n{}}}\n", main_body));
+        for stmt in &program.statements {
+            if let Statement::FunctionDef { .. } = stmt {
+                self.write_statement(stmt, out)?;
+                out.newline()?;
+                out.newline()?;
+            }
+        }
 
-        code
+        Ok(())
     }
 
-    /// Generates Rust code for a statement.
-    fn generate_statement(stmt: &Statement) -> String {
-        match stmt {
-            Statement::FunctionDef { name, params, body } => {
-                Self::generate_function_def(name, params, body)
+    /// Returns the methods of a class's impl block: its own, then the methods of its
+    /// mixins that neither it nor an earlier mixin defines; constructors stay with the
+    /// class that defines them
+    fn class_methods<'p>(&self, index: &ProgramIndex<'p>, class_name: &str) -> Vec<&'p Statement> {
+        let mut methods = index.methods_of(class_name).to_vec();
+        let Some(class) = self.env.symbols().class(class_name) else {
+            return methods;
+        };
+        let mut defined: HashSet<&str> = class
+            .methods
+            .iter()
+            .map(|method| method.name.as_str())
+            .collect();
+
+        for mixin in class.mixins.iter().filter(|mixin| index.is_mixin(mixin)) {
+            for &method in index.methods_of(mixin) {
+                if let Statement::MethodDef { method_name, .. } = method {
+                    if method_name != "new" && defined.insert(method_name) {
+                        methods.push(method);
+                    }
+                }
             }
-            Statement::ClassDef { name } => {
-                // Class definitions themselves don't generate code
-                // They're used to track class names for struct generation
-                format!("// class {}", name)
+        }
+        methods
+    }
+
+    /// Writes the code of a statement, marked with its span for a source map
+    ///
+    /// The statement's last line is left open, for the caller to end.
+    fn write_statement(&mut self, stmt: &Statement, out: &mut Emitter) -> fmt::Result {
+        self.marked(stmt.span(), out, |generator, out| {
+            generator.statement_code(stmt, out)
+        })
+    }
+
+    /// Runs `write`, marking what it writes with the span it came from when building
+    /// a source map
+    fn marked(
+        &mut self,
+        span: Span,
+        out: &mut Emitter,
+        write: impl FnOnce(&mut Self, &mut Emitter) -> fmt::Result,
+    ) -> fmt::Result {
+        match source_map::start_marker(span).filter(|_| self.mark_spans) {
+            Some(marker) => {
+                out.code(&marker)?;
+                write(self, out)?;
+                out.code(source_map::END_MARKER)
             }
+            None => write(self, out),
+        }
+    }
+
+    /// Returns the parts of a function or method definition, with its parameters
+    /// typed as its calls settle them
+    fn definition<'d>(&self, stmt: &'d Statement) -> Option<Definition<'d>> {
+        let (name, params, param_types, variadic, return_type, body, span) = match stmt {
+            Statement::FunctionDef {
+                name,
+                params,
+                param_types,
+                variadic,
+                return_type,
+                body,
+                span,
+                ..
+            } => (name, params, param_types, variadic, return_type, body, span),
             Statement::MethodDef {
-                class_name,
                 method_name,
                 params,
+                param_types,
+                variadic,
+                return_type,
                 body,
-            } => Self::generate_method_def(class_name, method_name, params, body),
-            Statement::Assignment { name, value } => {
-                format!("let {} = {};", name, Self::generate_expression(value))
+                span,
+                ..
+            } => (
+                method_name,
+                params,
+                param_types,
+                variadic,
+                return_type,
+                body,
+                span,
+            ),
+            _ => return None,
+        };
+        Some(Definition {
+            name,
+            params,
+            param_types: self.signature_params(*span, param_types),
+            variadic: *variadic,
+            return_type: *return_type,
+            body,
+        })
+    }
+
+    /// Writes the Rust code for a statement.
+    fn statement_code(&mut self, stmt: &Statement, out: &mut Emitter) -> fmt::Result {
+        match stmt {
+            Statement::FunctionDef { docs, .. } => {
+                Self::write_doc_comment(docs, out)?;
+                match self.definition(stmt) {
+                    Some(def) => self.write_function_def(&def, out),
+                    None => Ok(()),
+                }
+            }
+            Statement::ClassDef { name, .. } => {
+                // Class definitions themselves don't generate code
+                // They're used to track class names for struct generation
+                out.code(&format!("// class {}", name))
+            }
+            Statement::MixinDef { name, .. } => out.code(&format!("// mixin {}", name)),
+            // Modules are not resolved yet, so an import generates no `use`
+            Statement::Import { .. } => out.code(&format!("// {}", stmt)),
+            Statement::TraitDef { name, methods, .. } => self.write_trait(name, methods, out),
+            Statement::EnumDef { name, variants, .. } => Self::write_enum(name, variants, out),
+            // Methods are written into their class's impl block
+            Statement::MethodDef { .. } => Ok(()),
+            Statement::Assignment { name, value, .. }
+                if name
+                    .strip_prefix("self.")
+                    .is_some_and(|field| self.constructor_fields.contains_key(field)) =>
+            {
+                let code = self.constructor_field_assignment(&name["self.".len()..], value);
+                out.code(&code)
+            }
+            // A field assigned by an instance method outside the constructor
+            Statement::Assignment { name, value, .. } if name.starts_with("self.") => {
+                let field = &name["self.".len()..];
+                let (path, ty) = match &self.self_class {
+                    Some(class_name) => (
+                        self.field_path(class_name, field)
+                            .unwrap_or_else(|| field.to_string()),
+                        self.field_type(class_name, field),
+                    ),
+                    None => (field.to_string(), Type::Unknown),
+                };
+                out.code(&format!(
+                    "self.{} = {};",
+                    path,
+                    self.coerce(value, self.expression(value), &ty)
+                ))
+            }
+            Statement::Assignment { name, value, .. } if self.env.is_global_var(name) => {
+                let value_str = self.global_value(name, value);
+                out.code(&format!(
+                    "{}.with(|grit_cell| *grit_cell.borrow_mut() = {});",
+                    name, value_str
+                ))
+            }
+            // A `var` assigned again, or a variable of an enclosing scope, is updated in
+            // place
+            Statement::Assignment {
+                name,
+                mutable: false,
+                ty,
+                value,
+                ..
+            } if self.env.updates_variable(name, ty.is_some()) => {
+                let declared = self.env.lookup(name).cloned().unwrap_or(Type::Unknown);
+                out.code(&format!(
+                    "{} = {};",
+                    name,
+                    self.coerce(value, self.expression(value), &declared)
+                ))
+            }
+            Statement::Assignment {
+                name,
+                mutable,
+                ty: Some(ty),
+                value,
+                ..
+            } => {
+                let declared = Type::from(*ty);
+                let value_str = self.coerce(value, self.expression(value), &declared);
+                self.declare(name, declared, *mutable);
+                out.code(&format!(
+                    "let {}{}: {} = {};",
+                    self.binding_mode(name, *mutable),
+                    name,
+                    self.rust_type(*ty),
+                    value_str
+                ))
+            }
+            Statement::Assignment {
+                name,
+                mutable,
+                value,
+                ..
+            } => {
+                let value_str = self.expression(value);
+                self.declare(name, self.env.infer(value), *mutable);
+                out.code(&format!(
+                    "let {}{} = {};",
+                    self.binding_mode(name, *mutable),
+                    name,
+                    value_str
+                ))
+            }
+            Statement::FieldAssignment {
+                object,
+                field,
+                value,
+                ..
+            } => out.code(&format!(
+                "{}.{} = {};",
+                self.expression(object),
+                field,
+                self.expression(value)
+            )),
+            // A thread-local cell lets main and every function read and assign it
+            Statement::Global { name, value, .. } => {
+                let ty = match self.env.symbols().global_var(name).map(|global| &global.ty) {
+                    Some(Type::Float) => "f64",
+                    Some(Type::String) => "String",
+                    Some(Type::Bool) => "bool",
+                    _ => self.int_type(),
+                };
+                out.open("thread_local! {")?;
+                out.statement("#[allow(non_upper_case_globals)]")?;
+                out.statement(&format!(
+                    "static {}: std::cell::RefCell<{}> = std::cell::RefCell::new({});",
+                    name,
+                    ty,
+                    self.global_value(name, value)
+                ))?;
+                out.end("}")
+            }
+            Statement::ConstDef { name, value, .. } => {
+                let ty = match self.env.infer(value) {
+                    Type::Float => "f64",
+                    Type::String => "&str",
+                    Type::Bool => "bool",
+                    _ => self.int_type(),
+                };
+                out.code(&format!(
+                    "const {}: {} = {};",
+                    name,
+                    ty,
+                    self.expression(value)
+                ))
+            }
+            // `var`s assigned together are updated in place, still evaluating every
+            // value first
+            Statement::ParallelAssignment { names, values, .. }
+                if names.iter().all(|name| self.env.is_var(name)) =>
+            {
+                let values_str = values
+                    .iter()
+                    .map(|value| self.expression(value))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                if values.len() < names.len() {
+                    out.code(&format!("({}) = {};", names.join(", "), values_str))
+                } else {
+                    out.code(&format!("({}) = ({});", names.join(", "), values_str))
+                }
+            }
+            Statement::ParallelAssignment { names, values, .. } if values.len() < names.len() => {
+                // A single value is a tuple to destructure
+                let types = match self.env.infer(&values[0]) {
+                    Type::Tuple(elements) => elements,
+                    _ => vec![Type::Unknown; names.len()],
+                };
+                let value_str = self.expression(&values[0]);
+                for (name, ty) in names.iter().zip(types) {
+                    self.env.bind(name, ty);
+                }
+                out.code(&format!("let ({}) = {};", names.join(", "), value_str))
+            }
+            Statement::ParallelAssignment { names, values, .. } => {
+                // A tuple evaluates every value before any name is rebound
+                let values_str = values
+                    .iter()
+                    .map(|value| self.expression(value))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let types: Vec<Type> = values.iter().map(|value| self.env.infer(value)).collect();
+                for (name, ty) in names.iter().zip(types) {
+                    self.env.bind(name, ty);
+                }
+                out.code(&format!("let ({}) = ({});", names.join(", "), values_str))
+            }
+            Statement::ArrayDestructuring { names, value, .. } => {
+                let code = self.generate_array_destructuring(names, value);
+                out.code(&code)
             }
             Statement::If {
                 condition,
                 then_branch,
                 elif_branches,
                 else_branch,
-            } => Self::generate_if_statement(condition, then_branch, elif_branches, else_branch),
-            Statement::While { condition, body } => Self::generate_while_statement(condition, body),
+                ..
+            } => self.write_if_statement(condition, then_branch, elif_branches, else_branch, out),
+            Statement::Match { subject, arms, .. } => {
+                self.write_match_statement(subject, arms, out)
+            }
+            Statement::While {
+                condition,
+                body,
+                label,
+                ..
+            } => self.write_while_statement(condition, body, label.as_deref(), out),
+            Statement::Loop { body, label, .. } => {
+                self.write_loop_statement(body, label.as_deref(), out)
+            }
+            Statement::DoWhile {
+                body,
+                condition,
+                label,
+                ..
+            } => self.write_do_while_statement(body, condition, label.as_deref(), out),
+            Statement::Block { body, .. } => self.write_block(body, out),
+            Statement::Break { label, .. } => match label {
+                Some(label) => out.code(&format!("break '{};", label)),
+                None => out.code("break;"),
+            },
+            Statement::Continue { label, .. } => match label {
+                Some(label) => out.code(&format!("continue '{};", label)),
+                None => out.code("continue;"),
+            },
+            Statement::Return { value, .. } => match value {
+                Some(value) => out.code(&format!(
+                    "return {};",
+                    self.coerce(value, self.expression(value), &self.return_type)
+                )),
+                None => out.code("return;"),
+            },
+            // Test blocks only run under `grit test`
+            Statement::Test { name, .. } => out.code(&format!("// test {:?}", name)),
             Statement::Expression(expr) => {
                 match expr {
-                    Expr::FunctionCall { name, args } if name == "print" => {
+                    Expr::FunctionCall { name, args, .. } if name == "print" => {
                         // Generate println! macro call from print function
-                        Self::generate_print_call(args)
-                    }
-                    _ => {
-                        format!("{};", Self::generate_expression(expr))
+                        out.code(&self.generate_print_call(args))
                     }
+                    _ => out.code(&format!("{};", self.expression(expr))),
                 }
             }
         }
     }
 
-    /// Generates Rust code for a function definition.
-    fn generate_function_def(name: &str, params: &[String], body: &[Statement]) -> String {
-        let params_str = params.join(": i64, ");
-        let params_with_types = if params.is_empty() {
-            String::new()
-        } else {
-            format!("{}: i64", params_str)
-        };
-
-        let mut body_code = String::new();
-
-        // Check if the last statement is an expression (implicit return)
-        let has_implicit_return = if let Some(last) = body.last() {
-            matches!(last, Statement::Expression(_))
-        } else {
-            false
-        };
+    /// Writes the lines of a `##` doc comment as `///` doc comments
+    fn write_doc_comment(docs: &[String], out: &mut Emitter) -> fmt::Result {
+        for line in docs {
+            match line.as_str() {
+                "" => out.statement("///")?,
+                line => out.statement(&format!("/// {}", line))?,
+            }
+        }
+        Ok(())
+    }
 
-        for (i, stmt) in body.iter().enumerate() {
-            body_code.push_str("    ");
+    /// Writes the Rust code for a function definition.
+    fn write_function_def(&mut self, def: &Definition, out: &mut Emitter) -> fmt::Result {
+        // Functions only see their own parameters and locals, not variables from main
+        let outer = self.enter_function(def.params, &def.param_types, def.variadic, def.body);
+        let returns = self.enter_returns(def.return_type, def.body);
+        let params_with_types = self
+            .typed_params(def.params, &def.param_types, def.variadic, def.body)
+            .join(", ");
 
-            // If this is the last statement and it's an expression, make it a return
-            if i == body.len() - 1 && has_implicit_return {
-                if let Statement::Expression(expr) = stmt {
-                    body_code.push_str(&Self::generate_expression(expr));
-                } else {
-                    body_code.push_str(&Self::generate_statement(stmt));
-                }
-            } else {
-                body_code.push_str(&Self::generate_statement(stmt));
+        out.open(&format!(
+            "fn {}({}){} {{",
+            def.name,
+            params_with_types,
+            self.return_signature(&returns)
+        ))?;
+        // A final expression is the value the function returns
+        match returns {
+            Some(_) => {
+                let ty = self.return_type.clone();
+                self.write_value_body(def.body, Some(ty), out)?
             }
-            body_code.push('\n');
+            None => self.write_body(def.body, out)?,
         }
+        self.leave_function(outer);
+        out.end("}")
+    }
+
+    /// Generates an array pattern `let`, converting the vector into a fixed-size array
+    ///
+    /// An array literal becomes a Rust array directly; any other value is copied into
+    /// one, and a value of the wrong length panics.
+    fn generate_array_destructuring(&mut self, names: &[String], value: &Expr) -> String {
+        let element = match self.env.infer(value) {
+            Type::Array(element) => *element,
+            _ => Type::Unknown,
+        };
+        let value_str = match value {
+            Expr::Array { elements, .. } => {
+                let elements: Vec<String> = elements
+                    .iter()
+                    .map(|element| self.expression(element))
+                    .collect();
+                format!("[{}]", elements.join(", "))
+            }
+            _ => {
+                let vector = match value {
+                    Expr::FunctionCall { .. } | Expr::MethodCall { .. } => self.expression(value),
+                    _ => format!(
+                        "{}.clone()",
+                        self.generate_expression_with_context(value, Some(UNARY_PRECEDENCE), false)
+                    ),
+                };
+                // Spelled out, since `TryInto` is not in the 2015 edition's prelude
+                format!(
+                    "std::convert::TryInto::try_into({}).unwrap_or_else(|_| panic!(\"expected {} elements\"))",
+                    vector,
+                    names.len()
+                )
+            }
+        };
 
+        let patterns: Vec<String> = names
+            .iter()
+            .map(|name| format!("{}{}", self.binding_mode(name, false), name))
+            .collect();
+        for name in names.iter().filter(|name| *name != "_") {
+            self.env.bind(name, element.clone());
+        }
         format!(
-            "fn {}({}) -> i64 {{\n{}}}\n",
-            name, params_with_types, body_code
+            "let [{}]: [_; {}] = {};",
+            patterns.join(", "),
+            names.len(),
+            value_str
         )
     }
 
-    /// Generates Rust code for an if statement
-    fn generate_if_statement(
+    /// Writes the Rust code for an if statement
+    fn write_if_statement(
+        &mut self,
         condition: &Expr,
         then_branch: &[Statement],
         elif_branches: &[(Expr, Vec<Statement>)],
         else_branch: &Option<Vec<Statement>>,
-    ) -> String {
-        let mut code = format!("if {} {{\n", Self::generate_expression(condition));
+        out: &mut Emitter,
+    ) -> fmt::Result {
+        out.open(&format!("if {} {{", self.expression(condition)))?;
 
         // Generate then branch
-        for stmt in then_branch {
-            code.push_str("        ");
-            code.push_str(&Self::generate_statement(stmt));
-            code.push('\n');
-        }
-
-        code.push_str("    }");
+        self.push_scope();
+        self.declare_binding(condition);
+        self.write_body(then_branch, out)?;
+        self.pop_scope();
 
         // Generate elif branches
         for (elif_condition, elif_body) in elif_branches {
-            code.push_str(&format!(
-                " else if {} {{\n",
-                Self::generate_expression(elif_condition)
-            ));
+            out.reopen(&format!(
+                "}} else if {} {{",
+                self.expression(elif_condition)
+            ))?;
 
-            for stmt in elif_body {
-                code.push_str("        ");
-                code.push_str(&Self::generate_statement(stmt));
-                code.push('\n');
-            }
-
-            code.push_str("    }");
+            self.push_scope();
+            self.declare_binding(elif_condition);
+            self.write_body(elif_body, out)?;
+            self.pop_scope();
         }
 
         // Generate else branch
         if let Some(else_body) = else_branch {
-            code.push_str(" else {\n");
+            out.reopen("} else {")?;
 
-            for stmt in else_body {
-                code.push_str("        ");
-                code.push_str(&Self::generate_statement(stmt));
-                code.push('\n');
-            }
+            self.push_scope();
+            self.write_body(else_body, out)?;
+            self.pop_scope();
+        }
+
+        out.end("}")
+    }
 
-            code.push_str("    }");
+    /// Writes the Rust code for a match statement
+    ///
+    /// A `_ => {}` arm is added unless the arms already cover every value, since
+    /// Rust requires a match to be exhaustive.
+    fn write_match_statement(
+        &mut self,
+        subject: &Expr,
+        arms: &[MatchArm],
+        out: &mut Emitter,
+    ) -> fmt::Result {
+        let subject_ty = self.env.infer(subject);
+        let mut subject_str = self.expression(subject);
+        if subject_ty == Type::String {
+            // String patterns are `&str` literals
+            subject_str = self.string_operand(subject, subject_str);
         }
+        out.open(&format!("match {} {{", subject_str))?;
+        self.write_arms(&subject_ty, arms, out)?;
+        out.end("}")
+    }
 
-        code
+    /// Writes the arms of a match statement on a value of type `subject_ty`
+    fn write_arms(
+        &mut self,
+        subject_ty: &Type,
+        arms: &[MatchArm],
+        out: &mut Emitter,
+    ) -> fmt::Result {
+        let mut covered: HashSet<&str> = HashSet::new();
+        let mut wildcard = false;
+        for arm in arms {
+            let patterns: Vec<String> = arm
+                .patterns
+                .iter()
+                .map(|pattern| match pattern {
+                    Pattern::Variant { enum_name, variant } => {
+                        covered.insert(variant);
+                        format!("{}::{}", enum_name, variant)
+                    }
+                    Pattern::Literal(Expr::String { value, .. }) => format!("{:?}", value),
+                    Pattern::Literal(value) => self.expression(value),
+                    Pattern::Wildcard => {
+                        wildcard = true;
+                        "_".to_string()
+                    }
+                })
+                .collect();
+            out.open(&format!("{} => {{", patterns.join(" | ")))?;
+
+            self.push_scope();
+            self.write_body(&arm.body, out)?;
+            self.pop_scope();
+
+            out.close("}")?;
+        }
+
+        let exhaustive = match subject_ty {
+            Type::Enum(name) => self.env.symbols().enum_def(name).is_some_and(|symbol| {
+                symbol
+                    .variants
+                    .iter()
+                    .all(|variant| covered.contains(variant.as_str()))
+            }),
+            _ => false,
+        };
+        if !wildcard && !exhaustive {
+            out.statement("_ => {}")?;
+        }
+        Ok(())
     }
 
-    /// Generates Rust code for a while loop
-    fn generate_while_statement(condition: &Expr, body: &[Statement]) -> String {
-        let mut code = format!("while {} {{\n", Self::generate_expression(condition));
+    /// Writes the Rust code for a while loop
+    fn write_while_statement(
+        &mut self,
+        condition: &Expr,
+        body: &[Statement],
+        label: Option<&str>,
+        out: &mut Emitter,
+    ) -> fmt::Result {
+        let header = match label {
+            Some(label) => format!("'{}: while {} {{", label, self.expression(condition)),
+            None => format!("while {} {{", self.expression(condition)),
+        };
+        out.open(&header)?;
 
         // Generate body
-        for stmt in body {
-            code.push_str("        ");
-            code.push_str(&Self::generate_statement(stmt));
-            code.push('\n');
+        self.push_scope();
+        self.declare_binding(condition);
+        self.write_body(body, out)?;
+        self.pop_scope();
+
+        out.end("}")
+    }
+
+    /// Writes an infinite loop as a Rust `loop`
+    fn write_loop_statement(
+        &mut self,
+        body: &[Statement],
+        label: Option<&str>,
+        out: &mut Emitter,
+    ) -> fmt::Result {
+        out.open(&Self::loop_header(label))?;
+
+        self.push_scope();
+        self.write_body(body, out)?;
+        self.pop_scope();
+
+        out.end("}")
+    }
+
+    /// Returns the line opening a Rust `loop`, with its label if it has one
+    fn loop_header(label: Option<&str>) -> String {
+        match label {
+            Some(label) => format!("'{}: loop {{", label),
+            None => "loop {".to_string(),
         }
+    }
+
+    /// Writes a post-condition loop as a Rust `loop` that breaks once the condition,
+    /// checked after the body, fails
+    fn write_do_while_statement(
+        &mut self,
+        body: &[Statement],
+        condition: &Expr,
+        label: Option<&str>,
+        out: &mut Emitter,
+    ) -> fmt::Result {
+        out.open(&Self::loop_header(label))?;
 
-        code.push_str("    }");
+        // The condition sees the variables assigned in the body
+        self.push_scope();
+        self.write_body(body, out)?;
+        out.open(&format!("if !({}) {{", self.expression(condition)))?;
+        out.statement("break;")?;
+        out.close("}")?;
+        self.pop_scope();
 
+        out.end("}")
+    }
+
+    /// Writes a Rust block, whose variables go out of scope at its end
+    fn write_block(&mut self, body: &[Statement], out: &mut Emitter) -> fmt::Result {
+        out.open("{")?;
+
+        self.push_scope();
+        self.write_body(body, out)?;
+        self.pop_scope();
+
+        out.end("}")
+    }
+
+    /// Generates a Rust closure for a lambda
+    ///
+    /// The body sees the enclosing variables as well as the parameters. A block body
+    /// returns its final expression, like a function.
+    fn generate_lambda(&self, params: &[String], body: &[Statement]) -> String {
+        let params_str = params
+            .iter()
+            .map(|param| format!("{}: {}", param, self.int_type()))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut env = self.env.clone();
+        env.push_scope();
+        for param in params {
+            env.bind(param, Type::Unknown);
+        }
+        let mut generator = CodeGenerator {
+            env,
+            return_type: Type::Unknown,
+            mutating: self.mutating.clone(),
+            options: self.options.clone(),
+            mutated: self.mutated.clone(),
+            fields: self.fields.clone(),
+            self_class: self.self_class.clone(),
+            constructor_fields: self.constructor_fields.clone(),
+            helpers: RefCell::new(BTreeSet::new()),
+            mark_spans: self.mark_spans,
+        };
+
+        let code = match body {
+            [Statement::Expression(expr)] if !Self::is_print_call(expr) => {
+                format!("|{}| {}", params_str, generator.expression(expr))
+            }
+            _ => {
+                // Writing to a `String` cannot fail
+                let mut code = String::new();
+                let out = &mut Emitter::new(&mut code);
+                out.open(&format!("|{}| {{", params_str)).unwrap();
+                generator.write_value_body(body, None, out).unwrap();
+                out.end("}").unwrap();
+                code
+            }
+        };
+
+        self.helpers
+            .borrow_mut()
+            .extend(generator.helpers.into_inner());
+        code
+    }
+
+    /// Generates a cond expression as a Rust `if`/`else if`/`else` expression
+    ///
+    /// Each branch is a block whose final expression is its value. The branches see
+    /// the enclosing variables, and assign their own only within the branch.
+    fn generate_cond(
+        &self,
+        branches: &[(Expr, Vec<Statement>)],
+        else_branch: &[Statement],
+    ) -> String {
+        let mut generator = CodeGenerator {
+            env: self.env.clone(),
+            return_type: self.return_type.clone(),
+            mutating: self.mutating.clone(),
+            options: self.options.clone(),
+            mutated: self.mutated.clone(),
+            fields: self.fields.clone(),
+            self_class: self.self_class.clone(),
+            constructor_fields: self.constructor_fields.clone(),
+            helpers: RefCell::new(BTreeSet::new()),
+            mark_spans: self.mark_spans,
+        };
+
+        // Writing to a `String` cannot fail
+        let mut code = String::new();
+        generator
+            .write_cond(branches, else_branch, &mut Emitter::new(&mut code))
+            .unwrap();
+
+        self.helpers
+            .borrow_mut()
+            .extend(generator.helpers.into_inner());
         code
     }
 
+    /// Writes the `if`/`else if`/`else` of a cond expression, each branch a block
+    /// whose final expression is its value
+    fn write_cond(
+        &mut self,
+        branches: &[(Expr, Vec<Statement>)],
+        else_branch: &[Statement],
+        out: &mut Emitter,
+    ) -> fmt::Result {
+        for (i, (condition, body)) in branches.iter().enumerate() {
+            let line = format!("if {} {{", self.expression(condition));
+            if i == 0 {
+                out.open(&line)?;
+            } else {
+                out.reopen(&format!("}} else {}", line))?;
+            }
+            self.write_value_block(body, out)?;
+        }
+        if branches.is_empty() {
+            out.open("{")?;
+        } else {
+            out.reopen("} else {")?;
+        }
+        self.write_value_block(else_branch, out)?;
+        out.end("}")
+    }
+
+    /// Writes the statements of a block whose final expression is its value, in a
+    /// scope of their own
+    fn write_value_block(&mut self, body: &[Statement], out: &mut Emitter) -> fmt::Result {
+        self.push_scope();
+        self.write_value_body(body, None, out)?;
+        self.pop_scope();
+        Ok(())
+    }
+
+    fn is_print_call(expr: &Expr) -> bool {
+        matches!(expr, Expr::FunctionCall { name, .. } if name == "print")
+    }
+
+    /// Returns the types the annotations of a parameter list declare
+    fn annotated(param_types: &[Option<TypeAnnotation>]) -> Vec<Type> {
+        param_types.iter().copied().map(Type::annotated).collect()
+    }
+
+    /// Returns the parameter types of the definition with the given header: those
+    /// its calls settle on, or its annotations when the table does not list it
+    fn signature_params(&self, span: Span, param_types: &[Option<TypeAnnotation>]) -> Vec<Type> {
+        match self.env.symbols().signature(span) {
+            Some(signature) => signature.inferred_params.clone(),
+            None => Self::annotated(param_types),
+        }
+    }
+
+    /// Returns the typed parameters of a function or method
+    ///
+    /// Parameters have their given type, or are integers, except that an untyped
+    /// parameter the body calls is a function taking as many integers as it is called
+    /// with. A variadic last parameter is a slice of the extra arguments. Parameters
+    /// the body mutates are declared `mut`.
+    fn typed_params(
+        &self,
+        params: &[String],
+        param_types: &[Type],
+        variadic: bool,
+        body: &[Statement],
+    ) -> Vec<String> {
+        let mutated = self.mutated_variables(params, body);
+        params
+            .iter()
+            .enumerate()
+            .map(|(i, param)| {
+                let ty = param_types.get(i).unwrap_or(&Type::Unknown);
+                let arity = Self::call_arity(param, body);
+                let param = if mutated.contains(param) {
+                    format!("mut {}", param)
+                } else {
+                    param.clone()
+                };
+                match (ty, arity) {
+                    _ if variadic && i == params.len() - 1 => {
+                        format!("{}: &[{}]", param, self.param_type(ty))
+                    }
+                    (Type::Unknown, Some(arity)) => format!(
+                        "{}: impl Fn({}) -> {}",
+                        param,
+                        vec![self.int_type(); arity].join(", "),
+                        self.int_type()
+                    ),
+                    _ => format!("{}: {}", param, self.param_type(ty)),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the Rust type a parameter of the given type is passed as; parameters
+    /// of other types are integers
+    ///
+    /// A tuple is a tuple of its elements' types, a value that may be `nil` an
+    /// `Option`, and an instance of any class implementing a trait a trait object.
+    fn param_type(&self, ty: &Type) -> String {
+        match ty {
+            Type::Float => "f64".to_string(),
+            Type::String => "String".to_string(),
+            Type::Bool => "bool".to_string(),
+            Type::Class(name) => name.clone(),
+            Type::Trait(name) => format!("&dyn {}", name),
+            Type::Tuple(elements) => {
+                let elements: Vec<String> = elements.iter().map(|ty| self.param_type(ty)).collect();
+                format!("({})", elements.join(", "))
+            }
+            Type::Optional(inner) => format!("Option<{}>", self.param_type(inner)),
+            Type::Nil => format!("Option<{}>", self.int_type()),
+            _ => self.int_type().to_string(),
+        }
+    }
+
+    /// Finds how many arguments a body passes when it calls the given name
+    fn call_arity(name: &str, body: &[Statement]) -> Option<usize> {
+        let mut finder = CallArity { name, arity: None };
+        walk_body(&mut finder, body);
+        finder.arity
+    }
+
     /// Generates a println! call from print() arguments.
-    fn generate_print_call(args: &[Expr]) -> String {
+    fn generate_print_call(&self, args: &[Expr]) -> String {
         if args.is_empty() {
             return "println!();".to_string();
         }
 
-        // First argument is the format string
-        let format_str = match &args[0] {
-            Expr::String(s) => {
-                // Convert Grit format specifiers to Rust format specifiers
-                s.replace("%d", "{}").replace("%s", "{}")
-            }
-            _ => "{}".to_string(),
+        // First argument is the format string, the remaining ones are the values;
+        // any other value is printed on its own
+        let (format_str, values) = match &args[0] {
+            Expr::String { value: s, .. } => self.convert_format(s, &args[1..]),
+            value => ("{}".to_string(), vec![self.print_value(value)]),
         };
 
-        // Remaining arguments are the values
-        let values: Vec<String> = args[1..].iter().map(Self::generate_expression).collect();
-
         if values.is_empty() {
             format!("println!(\"{}\");", format_str)
         } else {
@@ -272,31 +1582,244 @@ impl CodeGenerator {
         }
     }
 
+    /// Generates a value to print; nil has no Display, so it prints as a word
+    fn print_value(&self, value: &Expr) -> String {
+        match self.env.infer(value) {
+            Type::Nil => "\"nil\"".to_string(),
+            Type::Optional(_) => format!(
+                "{}.as_ref().map_or(\"nil\".to_string(), |value| value.to_string())",
+                self.generate_expression_with_context(value, Some(UNARY_PRECEDENCE), false)
+            ),
+            _ => self.expression(value),
+        }
+    }
+
+    /// Converts Grit format specifiers to Rust format specifiers, returning the
+    /// format string with the values it formats
+    ///
+    /// Collections and tuples have no `Display` implementation, so their placeholders
+    /// use `{:?}`. A width or precision would apply to each of their elements there,
+    /// so such a value is formatted into a string first and padded as a whole.
+    fn convert_format(&self, format: &str, values: &[Expr]) -> (String, Vec<String>) {
+        let mut values = values.iter();
+        let mut converted = String::new();
+        let mut generated = Vec::new();
+
+        for part in parse_format_parts(format) {
+            match part {
+                FormatPart::Text(text) => converted.push_str(&Self::format_text(&text)),
+                FormatPart::Placeholder(mut placeholder) => {
+                    let Some(value) = values.next() else {
+                        converted.push_str(&Self::rust_placeholder(&placeholder, false));
+                        continue;
+                    };
+                    let ty = self.env.infer(value);
+                    let mut is_debug = ty.is_collection() || matches!(ty, Type::Tuple(_));
+                    if is_debug && (placeholder.width.is_some() || placeholder.precision.is_some())
+                    {
+                        generated.push(format!("format!(\"{{:?}}\", {})", self.expression(value)));
+                        placeholder.spec = FormatSpec::Str;
+                        is_debug = false;
+                    } else {
+                        generated.push(self.print_value(value));
+                    }
+                    converted.push_str(&Self::rust_placeholder(&placeholder, is_debug));
+                }
+            }
+        }
+        generated.extend(values.map(|value| self.print_value(value)));
+
+        (converted, generated)
+    }
+
+    /// Escapes text for a Rust format string: braces are doubled, and quotes,
+    /// backslashes, and control characters escaped as in any string literal
+    fn format_text(text: &str) -> String {
+        let literal = format!("{:?}", text);
+        literal[1..literal.len() - 1]
+            .replace('{', "{{")
+            .replace('}', "}}")
+    }
+
+    /// Returns the Rust placeholder that formats a value the way a Grit one does:
+    /// `%-8s` is `{:<8}`, `%05d` is `{:05}`, `%.2f` is `{:.2}`, and `%x` is `{:x}`
+    ///
+    /// A Grit width right-aligns strings too, which Rust aligns left by default; zeros
+    /// pad only numbers; and `%f` without a precision has six decimals.
+    fn rust_placeholder(placeholder: &Placeholder, is_debug: bool) -> String {
+        let is_number = !is_debug && placeholder.spec != FormatSpec::Str;
+        let mut spec = String::new();
+        if placeholder.left {
+            spec.push('<');
+        } else if placeholder.width.is_some() && !is_number {
+            spec.push('>');
+        } else if placeholder.zero && is_number {
+            spec.push('0');
+        }
+        if let Some(width) = placeholder.width {
+            spec.push_str(&width.to_string());
+        }
+        match (placeholder.precision, placeholder.spec) {
+            (Some(precision), _) => spec.push_str(&format!(".{}", precision)),
+            (None, FormatSpec::Float) => spec.push_str(".6"),
+            (None, _) => {}
+        }
+        if is_debug {
+            spec.push('?');
+        } else if placeholder.spec == FormatSpec::Hex {
+            spec.push('x');
+        }
+
+        if spec.is_empty() {
+            "{}".to_string()
+        } else {
+            format!("{{:{}}}", spec)
+        }
+    }
+
     fn generate_expression_with_context(
+        &self,
         ast: &Expr,
         parent_precedence: Option<u8>,
         is_right_child: bool,
     ) -> String {
+        if let Some(field) = self.constructor_field(ast) {
+            return format!("self_{}", field);
+        }
         match ast {
-            Expr::Integer(value) => value.to_string(),
-            Expr::Float(value) => value.to_string(),
-            Expr::String(s) => format!("\"{}\"", s.replace("\"", "\\\"")),
-            Expr::Identifier(name) => name.clone(),
-            Expr::Grouped(expr) => format!(
+            Expr::Integer { value, .. } => value.to_string(),
+            Expr::Float { value, .. } => Self::float_literal(*value),
+            Expr::Bool { value, .. } => value.to_string(),
+            // Grit values default to integers, so an untyped nil is an empty `Option<i64>`
+            Expr::Nil { .. } => format!("None::<{}>", self.int_type()),
+            Expr::String { value: s, .. } => format!("{:?}", s),
+            Expr::Identifier { name, .. } if self.env.is_global_var(name) => {
+                format!("{}.with(|grit_cell| grit_cell.borrow().clone())", name)
+            }
+            // In a method, a field not hidden by a variable is read from `self`
+            Expr::Identifier { name, .. } => match &self.self_class {
+                Some(class_name) if !self.is_variable(name) => self
+                    .field_path(class_name, name)
+                    .map_or_else(|| name.to_string(), |path| format!("self.{}", path)),
+                _ => name.to_string(),
+            },
+            Expr::Grouped { inner: expr, .. } => format!(
                 "({})",
-                Self::generate_expression_with_context(expr, None, false)
+                self.generate_expression_with_context(expr, None, false)
+            ),
+            Expr::Array { elements, .. } => {
+                let elements_str = elements
+                    .iter()
+                    .map(|element| self.expression(element))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("vec![{}]", elements_str)
+            }
+            Expr::Tuple { elements, .. } => {
+                let elements_str = elements
+                    .iter()
+                    .map(|element| self.expression(element))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("({})", elements_str)
+            }
+            Expr::Lambda { params, body, .. } => self.generate_lambda(params, body),
+            Expr::Call { callee, args, .. } => {
+                // A closure must be parenthesized to be called
+                let callee_str = match &**callee {
+                    Expr::Lambda { .. } => format!("({})", self.expression(callee)),
+                    _ => {
+                        self.generate_expression_with_context(callee, Some(UNARY_PRECEDENCE), false)
+                    }
+                };
+                let args_str = args
+                    .iter()
+                    .map(|arg| self.expression(arg))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("{}({})", callee_str, args_str)
+            }
+            Expr::Cond {
+                branches,
+                else_branch,
+                ..
+            } => self.generate_cond(branches, else_branch),
+            Expr::Map { entries, .. } if entries.is_empty() => {
+                "std::collections::HashMap::new()".to_string()
+            }
+            Expr::Map { entries, .. } => {
+                let entries_str = entries
+                    .iter()
+                    .map(|(key, value)| {
+                        format!("({}, {})", self.expression(key), self.expression(value))
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("std::collections::HashMap::from([{}])", entries_str)
+            }
+            Expr::Index { object, index, .. } => {
+                let element = self.generate_index(object, index);
+                // Reading a collection or string out of a collection copies it; a
+                // character of a string is already a new string
+                let ty = self.env.infer(ast);
+                if self.env.infer(object) == Type::String {
+                    element
+                } else if ty == Type::String || ty.is_collection() {
+                    format!("{}.clone()", element)
+                } else {
+                    element
+                }
+            }
+            Expr::Slice {
+                object, start, end, ..
+            } => self.generate_slice(object, start.as_deref(), end.as_deref()),
+            Expr::BinaryOp {
+                left, op, right, ..
+            } if self.is_nil_comparison(op, left, right) => {
+                self.generate_nil_comparison(op, left, right)
+            }
+            Expr::BinaryOp {
+                left,
+                op: BinaryOperator::In,
+                right,
+                ..
+            } => self.membership(
+                left,
+                self.generate_expression_with_context(left, Some(UNARY_PRECEDENCE), false),
+                right,
+                self.generate_expression_with_context(right, Some(UNARY_PRECEDENCE), false),
             ),
-            Expr::BinaryOp { left, op, right } => {
+            Expr::BinaryOp {
+                left, op, right, ..
+            } => {
                 let precedence = op.precedence();
-                let left_str =
-                    Self::generate_expression_with_context(left, Some(precedence), false);
-                let right_str =
-                    Self::generate_expression_with_context(right, Some(precedence), true);
+                let mut left_str =
+                    self.generate_expression_with_context(left, Some(precedence), false);
+                let mut right_str =
+                    self.generate_expression_with_context(right, Some(precedence), true);
+
+                if op.is_comparison() && self.is_string_comparison(left, right) {
+                    left_str = self.string_operand(left, left_str);
+                    right_str = self.string_operand(right, right_str);
+                }
+                if op.is_comparison() || op.is_arithmetic() {
+                    left_str = self.widened(left, right, left_str);
+                    right_str = self.widened(right, left, right_str);
+                }
+                if matches!(op, BinaryOperator::EqualEqual | BinaryOperator::NotEqual) {
+                    left_str = self.optional_operand(left, right, left_str);
+                    right_str = self.optional_operand(right, left, right_str);
+                }
 
-                let expression = format!("{} {} {}", left_str, Self::op_symbol(op), right_str);
+                let expression = format!("{} {} {}", left_str, op.symbol(), right_str);
 
+                // At equal precedence, only the child on the non-associative side needs parens
                 let needs_parens = parent_precedence.is_some_and(|parent| {
-                    precedence < parent || (precedence == parent && is_right_child)
+                    let against_associativity = match op.associativity() {
+                        Associativity::Left => is_right_child,
+                        Associativity::Right => !is_right_child,
+                    };
+                    precedence < parent || (precedence == parent && against_associativity)
                 });
 
                 if needs_parens {
@@ -305,55 +1828,164 @@ impl CodeGenerator {
                     expression
                 }
             }
-            Expr::FunctionCall { name, args } => {
+            Expr::UnaryOp { op, operand, .. } => {
+                let operand_str =
+                    self.generate_expression_with_context(operand, Some(UNARY_PRECEDENCE), false);
+                Self::unary(op, operand_str)
+            }
+            Expr::FunctionCall { name, args, .. } => {
                 // Handle type conversion functions
                 match name.as_str() {
                     "to_int" if args.len() == 1 => {
-                        let arg = Self::generate_expression_with_context(&args[0], None, false);
-                        format!("({} as i64)", arg)
+                        let arg = self.generate_expression_with_context(&args[0], None, false);
+                        format!("({} as {})", arg, self.int_type())
                     }
                     "to_float" if args.len() == 1 => {
-                        let arg = Self::generate_expression_with_context(&args[0], None, false);
+                        let arg = self.generate_expression_with_context(&args[0], None, false);
                         format!("({} as f64)", arg)
                     }
                     "to_string" if args.len() == 1 => {
-                        let arg = Self::generate_expression_with_context(&args[0], None, false);
+                        let arg = self.generate_expression_with_context(&args[0], None, false);
                         format!("{}.to_string()", arg)
                     }
+                    "time" if args.is_empty() => format!(
+                        "(std::time::SystemTime::now()\
+                        .duration_since(std::time::UNIX_EPOCH)\
+                        .unwrap()\
+                        .as_secs() as {})",
+                        self.int_type()
+                    ),
+                    // The helper counts in `i64`s whatever type integers are
+                    "clock" if args.is_empty() => {
+                        self.require(RuntimeHelper::Clock);
+                        match self.options.int_type {
+                            IntType::I64 => "grit_clock()".to_string(),
+                            int_type => format!("(grit_clock() as {})", int_type.rust_name()),
+                        }
+                    }
+                    // Types known statically become literals; the rest are named at runtime
+                    "typeof" if args.len() == 1 => match self.env.infer(&args[0]) {
+                        Type::Optional(_) => {
+                            self.require(RuntimeHelper::TypeOf);
+                            let arg = self.generate_expression_with_context(
+                                &args[0],
+                                Some(UNARY_PRECEDENCE),
+                                false,
+                            );
+                            format!("{}.as_ref().map_or(\"nil\", grit_typeof)", arg)
+                        }
+                        Type::Unknown => {
+                            self.require(RuntimeHelper::TypeOf);
+                            let arg = self.generate_expression_with_context(&args[0], None, false);
+                            format!("grit_typeof(&{})", arg)
+                        }
+                        ty => format!("\"{}\"", ty),
+                    },
+                    "assert" if args.len() == 1 => {
+                        let condition =
+                            self.generate_expression_with_context(&args[0], None, false);
+                        let message = format!("assertion failed: {}", args[0]);
+                        format!("assert!({}, \"{{}}\", {:?})", condition, message)
+                    }
+                    "assert" if args.len() == 2 => {
+                        let condition =
+                            self.generate_expression_with_context(&args[0], None, false);
+                        let message = self.generate_expression_with_context(&args[1], None, false);
+                        format!("assert!({}, \"{{}}\", {})", condition, message)
+                    }
+                    // An unset variable is nil, or the default when there is one
+                    "env" if args.len() == 1 => {
+                        let key = self.generate_expression_with_context(&args[0], None, false);
+                        format!("std::env::var({}).ok()", key)
+                    }
+                    "env" if args.len() == 2 => {
+                        let key = self.generate_expression_with_context(&args[0], None, false);
+                        let default = self.generate_expression_with_context(&args[1], None, false);
+                        format!(
+                            "std::env::var({}).unwrap_or_else(|_| {}.to_string())",
+                            key, default
+                        )
+                    }
                     _ => {
-                        let args_str = args
-                            .iter()
-                            .map(|arg| Self::generate_expression_with_context(arg, None, false))
-                            .collect::<Vec<_>>()
-                            .join(", ");
-                        format!("{}({})", name, args_str)
+                        // A local variable holding a lambda hides a function of the same name
+                        let function = self
+                            .env
+                            .symbols()
+                            .function(name)
+                            .filter(|_| !self.is_variable(name));
+                        format!("{}({})", name, self.call_args(function, args))
                     }
                 }
             }
-            Expr::FieldAccess { object, field } => {
-                let object_str = Self::generate_expression_with_context(object, None, false);
+            // Only conditions bind, so this follows `if` or `while`: the body runs
+            // unless the value is `false` or `nil`
+            Expr::Binding { name, value, .. } => {
+                let value_str = self.generate_expression_with_context(value, None, false);
+                match self.env.infer(value) {
+                    Type::Bool => format!("let {} @ true = {}", name, value_str),
+                    Type::Nil | Type::Optional(_) => format!("let Some({}) = {}", name, value_str),
+                    _ => format!("let Some({}) = Some({})", name, value_str),
+                }
+            }
+            // Calls put named arguments in place; elsewhere only the value is left
+            Expr::NamedArg { value, .. } => {
+                self.generate_expression_with_context(value, parent_precedence, is_right_child)
+            }
+            Expr::FieldAccess { object, field, .. } => {
+                let object_str = self.generate_expression_with_context(object, None, false);
                 format!("{}.{}", object_str, field)
             }
             Expr::MethodCall {
                 object,
                 method,
                 args,
+                ..
             } => {
-                let object_str = Self::generate_expression_with_context(object, None, false);
-                let args_str = args
-                    .iter()
-                    .map(|arg| Self::generate_expression_with_context(arg, None, false))
-                    .collect::<Vec<_>>()
-                    .join(", ");
+                // An enum variant: Enum::Variant
+                if let Some(enum_name) = self.env.enum_receiver(object) {
+                    return format!("{}::{}", enum_name, method);
+                }
+                let object_str = self.generate_expression_with_context(object, None, false);
+                let class_name = match &**object {
+                    Expr::Identifier { name, .. } if self.is_static_receiver(name) => {
+                        Some(name.to_string())
+                    }
+                    _ => match self.env.infer(object) {
+                        Type::Class(name) => Some(name),
+                        _ => None,
+                    },
+                };
+                let callee = match &class_name {
+                    Some(class_name) => self.env.symbols().resolve_method(class_name, method),
+                    None => self.env.callee(ast),
+                };
+                // An instance of a class without such a method: a field read
+                if let (Some(class_name), None, true) = (&class_name, callee, args.is_empty()) {
+                    if !matches!(&**object, Expr::Identifier { name, .. } if self.is_static_receiver(name))
+                    {
+                        let path = self.field_path(class_name, method);
+                        return format!("{}.{}", object_str, path.as_deref().unwrap_or(method));
+                    }
+                }
+                let args_str = self.call_args(callee, args);
 
-                // Check if this is a static method call (ClassName.method)
-                // If object is an identifier that starts with uppercase, treat as static
-                if let Expr::Identifier(class_name) = &**object {
-                    if class_name.chars().next().is_some_and(|c| c.is_uppercase()) {
+                // Check if this is a static method call (ClassName.method): the receiver
+                // must name a declared class and not be shadowed by a variable
+                if let Expr::Identifier {
+                    name: class_name, ..
+                } = &**object
+                {
+                    if self.is_static_receiver(class_name) {
                         // Static method call: ClassName::method(args)
                         return format!("{}::{}({})", class_name, method, args_str);
                     }
                 }
+                // A static method called through an instance is called on its class
+                if let (Some(class_name), Some(callee)) = (&class_name, callee) {
+                    if callee.is_static {
+                        return format!("{}::{}({})", class_name, method, args_str);
+                    }
+                }
 
                 // Instance method call: obj.method(args)
                 format!("{}.{}({})", object_str, method, args_str)
@@ -361,31 +1993,358 @@ impl CodeGenerator {
         }
     }
 
-    fn op_symbol(op: &BinaryOperator) -> &'static str {
+    /// Generates the arguments of a call
+    ///
+    /// Named arguments move to the position of the parameter they name, so they are
+    /// evaluated in parameter order. When the callee is variadic, the arguments past
+    /// its fixed parameters are passed as one slice. Arguments for typed parameters
+    /// are converted to the parameter's type, annotated or inferred.
+    fn call_args(&self, callee: Option<&FunctionSymbol>, args: &[Expr]) -> String {
+        let Some(callee) = callee else {
+            return args
+                .iter()
+                .map(|arg| self.generate_expression_with_context(arg, None, false))
+                .collect::<Vec<_>>()
+                .join(", ");
+        };
+        let generate = |arg: &Expr, index: usize| {
+            let code = self.generate_expression_with_context(arg, None, false);
+            match callee.inferred_params.get(index) {
+                Some(ty) => self.coerce(arg, code, ty),
+                None => code,
+            }
+        };
+
+        let (named, positional): (Vec<&Expr>, Vec<&Expr>) = args
+            .iter()
+            .partition(|arg| matches!(arg, Expr::NamedArg { .. }));
+        let fixed = callee.required_args();
+        let mut args_str: Vec<String> = positional
+            .iter()
+            .take(fixed)
+            .enumerate()
+            .map(|(i, arg)| generate(arg, i))
+            .collect();
+        for (i, param) in callee
+            .params
+            .iter()
+            .enumerate()
+            .take(fixed)
+            .skip(args_str.len())
+        {
+            let value = named.iter().find_map(|arg| match arg {
+                Expr::NamedArg { name, value, .. } if name == param => Some(value),
+                _ => None,
+            });
+            // The checker reports missing arguments; there is nothing to pass for them
+            if let Some(value) = value {
+                args_str.push(generate(value, i));
+            }
+        }
+
+        if callee.variadic {
+            let rest: Vec<String> = positional
+                .iter()
+                .skip(fixed)
+                .map(|arg| generate(arg, fixed))
+                .collect();
+            args_str.push(format!("&[{}]", rest.join(", ")));
+        }
+
+        args_str.join(", ")
+    }
+
+    /// Renders a float so rustc always types it as f64 (`2.0`, never `2`)
+    /// Generates an index into an array or map, without copying the element out
+    ///
+    /// Map keys are borrowed; array indices are converted to `usize` unless they are
+    /// integer literals, which Rust infers as `usize` already.
+    fn generate_index(&self, object: &Expr, index: &Expr) -> String {
+        let object_str = match object {
+            Expr::Index { object, index, .. } => self.generate_index(object, index),
+            _ => self.generate_expression_with_context(object, Some(UNARY_PRECEDENCE), false),
+        };
+
+        match self.env.infer(object) {
+            Type::Map(_, _) => format!("{}[&{}]", object_str, self.expression(index)),
+            Type::String => format!(
+                "{}.chars().nth({}).expect(\"string index out of range\").to_string()",
+                object_str,
+                self.usize_operand(index)
+            ),
+            _ => format!("{}[{}]", object_str, self.usize_operand(index)),
+        }
+    }
+
+    /// Generates `s[start..end]` as a copy of the characters or elements in range
+    ///
+    /// Taking up to the end before skipping to the start clamps both bounds to the
+    /// length, so a slice past the end is cut short and never panics.
+    fn generate_slice(&self, object: &Expr, start: Option<&Expr>, end: Option<&Expr>) -> String {
+        let object_str =
+            self.generate_expression_with_context(object, Some(UNARY_PRECEDENCE), false);
+        let (items, collect) = match self.env.infer(object) {
+            Type::String => (format!("{}.chars()", object_str), "String"),
+            _ => (format!("{}.iter()", object_str), "Vec<_>"),
+        };
+        let take = end.map_or(String::new(), |end| {
+            format!(".take({})", self.usize_operand(end))
+        });
+        let skip = start.map_or(String::new(), |start| {
+            format!(".skip({})", self.usize_operand(start))
+        });
+        let cloned = if collect == "String" { "" } else { ".cloned()" };
+        format!(
+            "{}{}{}{}.collect::<{}>()",
+            items, take, skip, cloned, collect
+        )
+    }
+
+    /// Generates an integer used as a position, cast to `usize` unless it is a literal
+    fn usize_operand(&self, expr: &Expr) -> String {
+        match expr {
+            Expr::Integer { .. } => self.expression(expr),
+            _ => format!(
+                "{} as usize",
+                self.generate_expression_with_context(expr, Some(UNARY_PRECEDENCE), false)
+            ),
+        }
+    }
+
+    /// Checks if the operands of a comparison are strings: one is statically known to
+    /// be a string, and the other is a string too or of unknown type (such as a field)
+    fn is_string_comparison(&self, left: &Expr, right: &Expr) -> bool {
+        matches!(
+            (self.env.infer(left), self.env.infer(right)),
+            (Type::String, Type::String | Type::Unknown) | (Type::Unknown, Type::String)
+        )
+    }
+
+    /// Checks if an equality comparison has an operand that is statically nil
+    fn is_nil_comparison(&self, op: &BinaryOperator, left: &Expr, right: &Expr) -> bool {
+        matches!(op, BinaryOperator::EqualEqual | BinaryOperator::NotEqual)
+            && (self.env.infer(left) == Type::Nil || self.env.infer(right) == Type::Nil)
+    }
+
+    /// Generates `x == nil` or `x != nil`
+    ///
+    /// A nil-typed value is an `Option` and is tested with `is_none()`/`is_some()`.
+    /// A value of any other type is never nil, so comparing it is a constant.
+    fn generate_nil_comparison(&self, op: &BinaryOperator, left: &Expr, right: &Expr) -> String {
+        let equal = *op == BinaryOperator::EqualEqual;
+        let operand = match (left, right) {
+            (Expr::Nil { .. }, Expr::Nil { .. }) => return equal.to_string(),
+            (operand, Expr::Nil { .. }) | (Expr::Nil { .. }, operand) => operand,
+            _ => {
+                let both_nil = self.env.infer(left) == self.env.infer(right);
+                return (equal == both_nil).to_string();
+            }
+        };
+
+        if !self.env.infer(operand).is_nilable() {
+            return (!equal).to_string();
+        }
+        let operand_str =
+            self.generate_expression_with_context(operand, Some(UNARY_PRECEDENCE), false);
+        let test = if equal { "is_none" } else { "is_some" };
+        format!("{}.{}()", operand_str, test)
+    }
+
+    /// Generates `item in collection` as a `contains` call on the collection
+    ///
+    /// Maps test their keys, and strings a substring, borrowed as `&str`.
+    fn membership(
+        &self,
+        item: &Expr,
+        item_str: String,
+        collection: &Expr,
+        collection_str: String,
+    ) -> String {
+        match self.env.infer(collection) {
+            Type::Map(_, _) => format!("{}.contains_key(&{})", collection_str, item_str),
+            Type::String => format!(
+                "{}.contains({})",
+                collection_str,
+                self.string_operand(item, item_str)
+            ),
+            _ => format!("{}.contains(&{})", collection_str, item_str),
+        }
+    }
+
+    /// Borrows a string operand as `&str`, so `&str` and `String` values compare by content
+    ///
+    /// Literals, including those generated for builtins like `typeof`, are already
+    /// `&str` and are left as they are.
+    fn string_operand(&self, expr: &Expr, code: String) -> String {
+        match expr {
+            Expr::BinaryOp { .. } => format!("&*({})", code),
+            // Borrowing an element needs no copy of it
+            Expr::Index { object, index, .. } => {
+                format!("&*{}", self.generate_index(object, index))
+            }
+            _ if code.starts_with('"') && code.ends_with('"') => code,
+            _ => format!("&*{}", code),
+        }
+    }
+
+    /// Applies a prefix operator to generated operand code
+    ///
+    /// Rust has no unary plus, so `+x` is just `x`; a negated negative is parenthesized,
+    /// since `--x` reads as a decrement.
+    fn unary(op: &UnaryOperator, operand: String) -> String {
         match op {
-            BinaryOperator::Add => "+",
-            BinaryOperator::Subtract => "-",
-            BinaryOperator::Multiply => "*",
-            BinaryOperator::Divide => "/",
-            BinaryOperator::EqualEqual => "==",
-            BinaryOperator::NotEqual => "!=",
-            BinaryOperator::LessThan => "<",
-            BinaryOperator::LessThanOrEqual => "<=",
-            BinaryOperator::GreaterThan => ">",
-            BinaryOperator::GreaterThanOrEqual => ">=",
+            UnaryOperator::Plus => operand,
+            UnaryOperator::Negate if operand.starts_with('-') => format!("-({})", operand),
+            _ => format!("{}{}", op.symbol(), operand),
+        }
+    }
+
+    fn float_literal(value: f64) -> String {
+        // Debug formatting keeps a decimal point or exponent on every finite value
+        format!("{:?}", value)
+    }
+
+    /// Returns the Rust type an annotation stands for
+    fn rust_type(&self, annotation: TypeAnnotation) -> &'static str {
+        match annotation {
+            TypeAnnotation::Int => self.int_type(),
+            TypeAnnotation::Float => "f64",
+            TypeAnnotation::String => "String",
+            TypeAnnotation::Bool => "bool",
+        }
+    }
+
+    /// Converts the generated code for a value to the type it is declared as
+    ///
+    /// Declared strings are owned `String`s, so string values are converted with
+    /// `to_string()`, integers widen where a float is declared, and instances are
+    /// cloned. A tuple literal converts each element, and a value stored where `nil`
+    /// may be is wrapped in `Some`. Other values are left alone.
+    fn coerce(&self, value: &Expr, code: String, declared: &Type) -> String {
+        let operand = match value {
+            Expr::BinaryOp { .. } | Expr::UnaryOp { .. } => format!("({})", code),
+            _ => code.clone(),
+        };
+        match declared {
+            Type::Tuple(types) => match value {
+                Expr::Tuple { elements, .. } if elements.len() == types.len() => {
+                    let elements: Vec<String> = elements
+                        .iter()
+                        .zip(types)
+                        .map(|(element, ty)| self.coerce(element, self.expression(element), ty))
+                        .collect();
+                    format!("({})", elements.join(", "))
+                }
+                _ => code,
+            },
+            Type::Optional(inner) => match self.env.infer(value) {
+                Type::Nil => "None".to_string(),
+                Type::Optional(_) => code,
+                _ => format!("Some({})", self.coerce(value, code, inner)),
+            },
+            Type::String => format!("{}.to_string()", operand),
+            // A trait object is borrowed, so any instance is passed as it is
+            Type::Trait(_) => format!("&{}", operand),
+            Type::Float if self.env.infer(value) == Type::Int => format!("{} as f64", operand),
+            // An instance passed on is copied, leaving the variable it came from usable
+            Type::Class(_)
+                if matches!(
+                    value,
+                    Expr::Identifier { .. } | Expr::FieldAccess { .. } | Expr::Index { .. }
+                ) =>
+            {
+                format!("{}.clone()", code)
+            }
+            _ => code,
+        }
+    }
+
+    /// Converts an integer operand to `f64` when the other operand is a float, since
+    /// Rust does no arithmetic or comparison across the two
+    fn widened(&self, operand: &Expr, other: &Expr, code: String) -> String {
+        if self.env.infer(operand) != Type::Int || self.env.infer(other) != Type::Float {
+            return code;
+        }
+        match operand {
+            Expr::Integer { value, .. } => format!("{}.0", value),
+            _ => format!("({} as f64)", code),
+        }
+    }
+
+    /// Wraps a value compared with one that may be `nil` in `Some`, so
+    /// `found == 3` compares an `Option` with an `Option`
+    fn optional_operand(&self, operand: &Expr, other: &Expr, code: String) -> String {
+        match (self.env.infer(operand), self.env.infer(other)) {
+            (Type::Optional(_) | Type::Nil, _) | (_, Type::Nil) => code,
+            (_, optional @ Type::Optional(_)) => self.coerce(operand, code, &optional),
+            _ => code,
+        }
+    }
+
+    /// Generates a value stored in a global, converted to the global's type
+    fn global_value(&self, name: &str, value: &Expr) -> String {
+        let ty = self
+            .env
+            .symbols()
+            .global_var(name)
+            .map_or(Type::Unknown, |global| global.ty.clone());
+        self.coerce(value, self.expression(value), &ty)
+    }
+
+    /// Finds the fields a constructor sets straight from a typed parameter, with the
+    /// parameter's type, annotated or inferred
+    ///
+    /// A parameter typed by a trait is borrowed, so the field it sets is left alone.
+    fn constructor_field_types<'s>(
+        &self,
+        methods: &[&'s Statement],
+    ) -> std::collections::HashMap<&'s str, Type> {
+        let mut types = std::collections::HashMap::new();
+        for method in methods {
+            let Statement::MethodDef {
+                method_name,
+                params,
+                param_types,
+                body,
+                span,
+                ..
+            } = method
+            else {
+                continue;
+            };
+            if method_name != "new" {
+                continue;
+            }
+            let param_types = self.signature_params(*span, param_types);
+            for stmt in body {
+                if let Statement::Assignment {
+                    name,
+                    value: Expr::Identifier { name: param, .. },
+                    ..
+                } = stmt
+                {
+                    let ty = params
+                        .iter()
+                        .position(|candidate| candidate == param)
+                        .and_then(|i| param_types.get(i))
+                        .filter(|ty| !matches!(ty, Type::Unknown | Type::Trait(_)));
+                    if let (Some(field), Some(ty)) = (name.strip_prefix("self."), ty) {
+                        types.insert(field, ty.clone());
+                    }
+                }
+            }
         }
+        types
     }
 
     /// Collects all field names from self.field assignments
     fn collect_fields(body: &[Statement], fields: &mut std::collections::HashSet<String>) {
         for stmt in body {
             match stmt {
-                Statement::Assignment { name, .. } => {
-                    // Check if this is a self.field assignment (self.field = ...)
-                    if name.starts_with("self.") {
-                        if let Some(field) = name.strip_prefix("self.") {
-                            fields.insert(field.to_string());
-                        }
+                // Check if this is a self.field assignment (self.field = ...)
+                Statement::Assignment { name, .. } if name.starts_with("self.") => {
+                    if let Some(field) = name.strip_prefix("self.") {
+                        fields.insert(field.to_string());
                     }
                 }
                 Statement::If {
@@ -402,11 +2361,19 @@ impl CodeGenerator {
                         Self::collect_fields(else_body, fields);
                     }
                 }
-                Statement::While { body, .. } => {
+                Statement::While { body, .. }
+                | Statement::Loop { body, .. }
+                | Statement::DoWhile { body, .. }
+                | Statement::Block { body, .. } => {
                     Self::collect_fields(body, fields);
                 }
-                Statement::Expression(Expr::FieldAccess { object, field }) => {
-                    if let Expr::Identifier(obj_name) = &**object {
+                Statement::Match { arms, .. } => {
+                    for arm in arms {
+                        Self::collect_fields(&arm.body, fields);
+                    }
+                }
+                Statement::Expression(Expr::FieldAccess { object, field, .. }) => {
+                    if let Expr::Identifier { name: obj_name, .. } = &**object {
                         if obj_name == "self" {
                             fields.insert(field.clone());
                         }
@@ -417,134 +2384,602 @@ impl CodeGenerator {
         }
     }
 
-    /// Generates code for a method definition (not used directly, kept for compatibility)
-    fn generate_method_def(
-        _class_name: &str,
-        _method_name: &str,
-        _params: &[String],
-        _body: &[Statement],
-    ) -> String {
-        // This is not used in the new approach but kept for compatibility
-        String::new()
+    /// Returns `mut ` for a `var` or a variable whose fields are assigned, so the
+    /// binding allows it
+    fn binding_mode(&self, name: &str, mutable: bool) -> &'static str {
+        if mutable || self.mutated.contains(name) {
+            "mut "
+        } else {
+            ""
+        }
     }
 
-    /// Generates code for a method implementation (inside impl block)
-    fn generate_method_impl(method_name: &str, params: &[String], body: &[Statement]) -> String {
-        let mut code = String::new();
-
-        // Special handling for constructor (new method)
-        if method_name == "new" {
-            let params_str = params.join(": i64, ");
-            let params_with_types = if params.is_empty() {
-                String::new()
-            } else {
-                format!("{}: i64", params_str)
+    /// Collects the variables of a body, including its parameters, that must be
+    /// bound with `let mut`: those whose fields it assigns, however deeply nested the
+    /// field is (`p.x = 1`, `line.start.x = 1`, `points[0].x = 1`), those it calls a
+    /// method assigning fields on (`p.shift(1)`), and those a nested scope assigns
+    /// again
+    fn mutated_variables(&self, params: &[String], body: &[Statement]) -> HashSet<String> {
+        /// Walks a scope, given the variables of the scopes enclosing it
+        fn collect(
+            body: &[Statement],
+            outer: &HashSet<String>,
+            mut local: HashSet<String>,
+            mutated: &mut HashSet<String>,
+        ) {
+            let nested = |local: &HashSet<String>| -> HashSet<String> {
+                outer.union(local).cloned().collect()
             };
-
-            code.push_str(&format!(
-                "    fn {}({}) -> Self {{\n",
-                method_name, params_with_types
-            ));
-
-            // Collect field assignments
-            let mut field_assignments = Vec::new();
             for stmt in body {
-                if let Statement::Assignment { name, value } = stmt {
-                    // Check if this is self.field = value
-                    if name.starts_with("self.") {
-                        let field = name.strip_prefix("self.").unwrap();
-                        let value_str = Self::generate_expression(value);
-                        field_assignments.push((field.to_string(), value_str));
+                match stmt {
+                    Statement::Assignment {
+                        name,
+                        mutable: false,
+                        ty: None,
+                        ..
+                    } if outer.contains(name) && !local.contains(name) => {
+                        mutated.insert(name.clone());
+                    }
+                    Statement::Assignment { name, .. } => {
+                        local.insert(name.clone());
+                    }
+                    Statement::ParallelAssignment { names, .. }
+                    | Statement::ArrayDestructuring { names, .. } => {
+                        local.extend(names.iter().cloned());
+                    }
+                    Statement::If {
+                        then_branch,
+                        elif_branches,
+                        else_branch,
+                        ..
+                    } => {
+                        let visible = nested(&local);
+                        collect(then_branch, &visible, HashSet::new(), mutated);
+                        for (_, branch) in elif_branches {
+                            collect(branch, &visible, HashSet::new(), mutated);
+                        }
+                        if let Some(else_body) = else_branch {
+                            collect(else_body, &visible, HashSet::new(), mutated);
+                        }
+                    }
+                    Statement::While { body, .. }
+                    | Statement::Loop { body, .. }
+                    | Statement::DoWhile { body, .. }
+                    | Statement::Block { body, .. } => {
+                        collect(body, &nested(&local), HashSet::new(), mutated)
                     }
+                    Statement::Match { arms, .. } => {
+                        let visible = nested(&local);
+                        for arm in arms {
+                            collect(&arm.body, &visible, HashSet::new(), mutated);
+                        }
+                    }
+                    _ => {}
                 }
             }
+        }
 
-            // Generate Self construction
-            code.push_str("        Self {\n");
-            for (field, value) in &field_assignments {
-                code.push_str(&format!("            {}: {},\n", field, value));
-            }
-            code.push_str("        }\n");
-            code.push_str("    }\n\n");
-        } else {
-            // Regular method
-            let params_str = params.join(": i64, ");
-            let params_with_types = if params.is_empty() {
-                "&self".to_string()
-            } else {
-                format!("&self, {}: i64", params_str)
-            };
+        let mut mutated = self.mutating.receivers(body);
+        collect(
+            body,
+            &HashSet::new(),
+            params.iter().cloned().collect(),
+            &mut mutated,
+        );
+        mutated
+    }
 
-            code.push_str(&format!(
-                "    fn {}({}) -> i64 {{\n",
-                method_name, params_with_types
-            ));
+    /// Returns the Rust return type of a function or method body
+    ///
+    /// An annotated return type is used as written. Otherwise values are integers, so
+    /// a body that returns an n-element tuple returns `(i64, ...)` and any other body
+    /// returns `i64`.
+    fn return_type(&self, annotation: Option<TypeAnnotation>, body: &[Statement]) -> String {
+        if let Some(ty) = annotation {
+            return self.rust_type(ty).to_string();
+        }
+        match Self::returned_tuple_len(body, true) {
+            Some(len) => format!("({})", vec![self.int_type(); len].join(", ")),
+            None => self.int_type().to_string(),
+        }
+    }
 
-            // Check if the last statement is an expression (implicit return)
-            let has_implicit_return = if let Some(last) = body.last() {
-                matches!(last, Statement::Expression(_))
-            } else {
-                false
-            };
+    /// Renders the return type of a function or method signature: ` -> T`, or
+    /// nothing when it returns nothing
+    fn return_signature(&self, returns: &Option<Type>) -> String {
+        match returns {
+            Some(ty) => format!(" -> {}", self.param_type(ty)),
+            None => String::new(),
+        }
+    }
 
-            for (i, stmt) in body.iter().enumerate() {
-                let is_last = i == body.len() - 1;
+    /// Finds the length of a tuple returned by `return` anywhere in a body, or by the
+    /// body's final expression when `is_function_body` is set
+    fn returned_tuple_len(body: &[Statement], is_function_body: bool) -> Option<usize> {
+        if is_function_body {
+            if let Some(Statement::Expression(Expr::Tuple { elements, .. })) = body.last() {
+                return Some(elements.len());
+            }
+        }
 
-                // Skip self.field assignments (they're handled in the constructor)
-                if let Statement::Assignment { name, .. } = stmt {
-                    if name.starts_with("self.") {
-                        continue;
-                    }
-                }
+        body.iter().find_map(|stmt| match stmt {
+            Statement::Return {
+                value: Some(Expr::Tuple { elements, .. }),
+                ..
+            } => Some(elements.len()),
+            Statement::If {
+                then_branch,
+                elif_branches,
+                else_branch,
+                ..
+            } => Self::returned_tuple_len(then_branch, false)
+                .or_else(|| {
+                    elif_branches
+                        .iter()
+                        .find_map(|(_, branch)| Self::returned_tuple_len(branch, false))
+                })
+                .or_else(|| {
+                    else_branch
+                        .as_deref()
+                        .and_then(|branch| Self::returned_tuple_len(branch, false))
+                }),
+            Statement::While { body, .. }
+            | Statement::Loop { body, .. }
+            | Statement::DoWhile { body, .. }
+            | Statement::Block { body, .. } => Self::returned_tuple_len(body, false),
+            Statement::Match { arms, .. } => arms
+                .iter()
+                .find_map(|arm| Self::returned_tuple_len(&arm.body, false)),
+            _ => None,
+        })
+    }
+
+    /// Generates code for a method definition (not used directly, kept for compatibility)
+    /// Writes the constructor (the `new` method) of a class's impl block
+    ///
+    /// The `self.field` assignments become the fields of the returned struct; the
+    /// struct's other fields get their type's default value. Assignments to inherited
+    /// fields initialize the embedded parent, given with its ancestors' own fields.
+    ///
+    /// A body of nothing but one assignment per field puts the values straight into
+    /// the struct. Any other body runs as written, with each field it assigns held in
+    /// a `self_field` local (bound up front, to its default, when it is first
+    /// assigned in a nested scope) that the struct is built from at the end.
+    fn write_constructor(
+        &mut self,
+        def: &Definition,
+        class_name: &str,
+        ancestors: &[(&str, Vec<String>)],
+        out: &mut Emitter,
+    ) -> fmt::Result {
+        let fields = self.fields.get(class_name).cloned().unwrap_or_default();
+        let outer = self.enter_function(def.params, &def.param_types, def.variadic, def.body);
 
-                code.push_str("        ");
+        let params_with_types = self
+            .typed_params(def.params, &def.param_types, def.variadic, def.body)
+            .join(", ");
+        out.open(&format!("fn new({}) -> Self {{", params_with_types))?;
+        self.write_constructor_body(def.body, class_name, ancestors, &fields, out)?;
+        self.leave_function(outer);
+        out.end("}")
+    }
 
-                // Convert field references: a -> self.a, b -> self.b
-                let stmt_code = Self::generate_statement_with_self(stmt);
+    /// Writes the body of a constructor, ending in the struct it returns
+    fn write_constructor_body(
+        &mut self,
+        body: &[Statement],
+        class_name: &str,
+        ancestors: &[(&str, Vec<String>)],
+        fields: &[String],
+        out: &mut Emitter,
+    ) -> fmt::Result {
+        let mut assigned = FieldAssignments::default();
+        walk_body(&mut assigned, body);
+        let only_assignments = body.len() == assigned.order.len()
+            && body.iter().all(|stmt| Self::assigned_field(stmt).is_some());
 
-                if is_last && has_implicit_return {
-                    // Last expression should be returned
-                    if let Statement::Expression(_) = stmt {
-                        code.push_str(stmt_code.trim_end_matches(';'));
-                    } else {
-                        code.push_str(&stmt_code);
+        let mut field_assignments = Vec::new();
+        if only_assignments {
+            for stmt in body {
+                if let Statement::Assignment { name, value, .. } = stmt {
+                    let field = &name["self.".len()..];
+                    let ty = self.field_type(class_name, field);
+                    let value_str = self.coerce(value, self.expression(value), &ty);
+                    field_assignments.push((field.to_string(), value_str));
+                }
+            }
+        } else {
+            // A field first assigned by a statement of the body itself is bound there
+            let mut top_level = HashSet::new();
+            let mut seen = HashSet::new();
+            for stmt in body {
+                if let Some(field) = Self::assigned_field(stmt) {
+                    if seen.insert(field.to_string()) {
+                        top_level.insert(field.to_string());
                     }
                 } else {
-                    code.push_str(&stmt_code);
+                    let mut nested = FieldAssignments::default();
+                    walk_statement(&mut nested, stmt);
+                    seen.extend(nested.order);
                 }
-                code.push('\n');
             }
 
-            code.push_str("    }\n\n");
+            for field in &assigned.order {
+                let bound = !top_level.contains(field);
+                if bound {
+                    out.statement(&format!("let mut self_{} = Default::default();", field))?;
+                }
+                self.constructor_fields.insert(
+                    field.clone(),
+                    FieldLocal {
+                        ty: self.field_type(class_name, field),
+                        bound,
+                        mutable: assigned.counts[field] > 1,
+                    },
+                );
+                field_assignments.push((field.clone(), format!("self_{}", field)));
+            }
+            self.write_body(body, out)?;
+            self.constructor_fields.clear();
+        }
+        let parent =
+            (!ancestors.is_empty()).then(|| Self::parent_literal(ancestors, &field_assignments));
+        field_assignments.retain(|(field, _)| ancestors.is_empty() || fields.contains(field));
+        for field in fields {
+            if !field_assignments
+                .iter()
+                .any(|(assigned, _)| assigned == field)
+            {
+                field_assignments.push((field.clone(), "Default::default()".to_string()));
+            }
         }
 
-        code
+        // Generate Self construction
+        out.open("Self {")?;
+        if let Some(parent) = parent {
+            out.statement(&format!("parent: {},", parent))?;
+        }
+        for (field, value) in &field_assignments {
+            out.statement(&format!("{}: {},", field, value))?;
+        }
+        out.close("}")
+    }
+
+    /// Returns the declared type of a class's field, or of the ancestor's that
+    /// declares it; `Unknown` for an unannotated or undeclared field
+    fn field_type(&self, class_name: &str, field: &str) -> Type {
+        let symbols = self.env.symbols();
+        symbols
+            .class(class_name)
+            .into_iter()
+            .chain(symbols.ancestors(class_name))
+            .find_map(|class| class.field(field))
+            .map_or(Type::Unknown, |field| field.ty.clone())
     }
 
-    /// Generates a statement with self. prefix for field references
-    fn generate_statement_with_self(stmt: &Statement) -> String {
+    /// Returns the field a `self.field = value` statement assigns
+    fn assigned_field(stmt: &Statement) -> Option<&str> {
         match stmt {
-            Statement::Expression(expr) => {
-                format!("{};", Self::generate_expression_with_self(expr))
+            Statement::Assignment { name, .. } => name.strip_prefix("self."),
+            _ => None,
+        }
+    }
+
+    /// Generates an assignment to a field of the constructor being generated, which
+    /// binds the field's local the first time
+    fn constructor_field_assignment(&mut self, field: &str, value: &Expr) -> String {
+        let ty = self
+            .constructor_fields
+            .get(field)
+            .map_or(Type::Unknown, |local| local.ty.clone());
+        let value_str = self.coerce(value, self.expression(value), &ty);
+        match self.constructor_fields.get_mut(field) {
+            Some(local) if !local.bound => {
+                local.bound = true;
+                let mode = if local.mutable { "mut " } else { "" };
+                format!("let {}self_{} = {};", mode, field, value_str)
+            }
+            _ => format!("self_{} = {};", field, value_str),
+        }
+    }
+
+    /// Returns the field a `self.field` read names, when it is a field of the
+    /// constructor being generated
+    fn constructor_field<'e>(&self, expr: &'e Expr) -> Option<&'e str> {
+        let (object, field) = match expr {
+            Expr::FieldAccess { object, field, .. } => (object, field),
+            Expr::MethodCall {
+                object,
+                method,
+                args,
+                ..
+            } if args.is_empty() => (object, method),
+            _ => return None,
+        };
+        match &**object {
+            Expr::Identifier { name, .. }
+                if name == "self" && self.constructor_fields.contains_key(field) =>
+            {
+                Some(field)
+            }
+            _ => None,
+        }
+    }
+
+    /// Builds the struct literal for the parent embedded in a subclass, from the
+    /// parent and its ancestors with their own fields, nearest first
+    ///
+    /// The inherited fields the constructor assigns are set where they live; the
+    /// rest take the parent's defaults.
+    fn parent_literal(
+        ancestors: &[(&str, Vec<String>)],
+        assignments: &[(String, String)],
+    ) -> String {
+        let Some(((parent, own), rest)) = ancestors.split_first() else {
+            return String::new();
+        };
+        let assigns_any = assignments
+            .iter()
+            .any(|(field, _)| ancestors.iter().any(|(_, own)| own.contains(field)));
+        if !assigns_any {
+            return "Default::default()".to_string();
+        }
+
+        let mut parts: Vec<String> = assignments
+            .iter()
+            .filter(|(field, _)| own.contains(field))
+            .map(|(field, value)| format!("{}: {}", field, value))
+            .collect();
+        if !rest.is_empty() {
+            parts.push(format!(
+                "parent: {}",
+                Self::parent_literal(rest, assignments)
+            ));
+        }
+        parts.push("..Default::default()".to_string());
+        format!("{} {{ {} }}", parent, parts.join(", "))
+    }
+
+    /// Writes a Rust enum, displayed as its variant names
+    fn write_enum(name: &str, variants: &[String], out: &mut Emitter) -> fmt::Result {
+        out.statement("#[derive(Debug, Clone, Copy, PartialEq)]")?;
+        out.open(&format!("enum {} {{", name))?;
+        for variant in variants {
+            out.statement(&format!("{},", variant))?;
+        }
+        out.close("}")?;
+        out.newline()?;
+
+        out.open(&format!("impl std::fmt::Display for {} {{", name))?;
+        out.open("fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {")?;
+        out.open("let name = match self {")?;
+        for variant in variants {
+            out.statement(&format!("{}::{} => \"{}\",", name, variant, variant))?;
+        }
+        out.close("};")?;
+        out.statement("write!(f, \"{}\", name)")?;
+        out.close("}")?;
+        out.end("}")
+    }
+
+    /// Writes a Rust trait from a trait definition, with a `&self` method for each
+    /// signature
+    fn write_trait(&self, name: &str, methods: &[TraitMethod], out: &mut Emitter) -> fmt::Result {
+        out.open(&format!("trait {} {{", name))?;
+        for method in methods {
+            out.statement(&format!("fn {};", self.trait_method_header(method)))?;
+        }
+        out.end("}")
+    }
+
+    /// Writes the impl of a trait for a class, forwarding each method to the class's
+    /// inherent method
+    ///
+    /// Inherent methods win method lookup in Rust, so the forwarding call does not
+    /// recurse, and the class keeps calling its methods without the trait in scope.
+    fn write_trait_impl(
+        &self,
+        trait_name: &str,
+        class_name: &str,
+        methods: &[TraitMethod],
+        out: &mut Emitter,
+    ) -> fmt::Result {
+        out.open(&format!("impl {} for {} {{", trait_name, class_name))?;
+        for method in methods {
+            let mut args = vec!["self".to_string()];
+            args.extend(method.params.iter().cloned());
+            out.open(&format!("fn {} {{", self.trait_method_header(method)))?;
+            out.statement(&format!(
+                "{}::{}({})",
+                class_name,
+                method.name,
+                args.join(", ")
+            ))?;
+            out.close("}")?;
+        }
+        out.close("}")?;
+        out.newline()
+    }
+
+    /// Renders a trait method's Rust signature: `area(&self) -> f64`
+    fn trait_method_header(&self, method: &TraitMethod) -> String {
+        let mut params = vec![self.receiver(&method.name).to_string()];
+        params.extend(self.typed_params(
+            &method.params,
+            &Self::annotated(&method.param_types),
+            method.variadic,
+            &[],
+        ));
+        format!(
+            "{}({}) -> {}",
+            method.name,
+            params.join(", "),
+            self.return_type(method.return_type, &[])
+        )
+    }
+
+    /// Writes a method of a subclass that forwards an inherited method to the
+    /// embedded parent
+    ///
+    /// A static method has no parent instance to forward to, so it calls the
+    /// ancestor class that defines it, given as `static_class`, instead. `typed`
+    /// holds the parameters with their types, as the ancestor's method has them.
+    fn write_delegate(
+        &self,
+        method_name: &str,
+        static_class: Option<&str>,
+        params: &[String],
+        typed: Vec<String>,
+        returns: &Option<Type>,
+        out: &mut Emitter,
+    ) -> fmt::Result {
+        let mut params_with_types = Vec::new();
+        if static_class.is_none() {
+            params_with_types.push(self.receiver(method_name).to_string());
+        }
+        params_with_types.extend(typed);
+        let target = match static_class {
+            Some(class_name) => format!("{}::", class_name),
+            None => "self.parent.".to_string(),
+        };
+        out.open(&format!(
+            "fn {}({}){} {{",
+            method_name,
+            params_with_types.join(", "),
+            self.return_signature(returns)
+        ))?;
+        out.statement(&format!("{}{}({})", target, method_name, params.join(", ")))?;
+        out.end("}")
+    }
+
+    /// Returns how an instance method takes its instance: `&mut self` when methods
+    /// of its name assign fields of it
+    fn receiver(&self, method_name: &str) -> &'static str {
+        if self.mutating.contains(method_name) {
+            "&mut self"
+        } else {
+            "&self"
+        }
+    }
+
+    /// Writes a method implementation (inside impl block)
+    ///
+    /// `self` is an instance of `self_class`, which the caller sets.
+    fn write_method_impl(&mut self, def: &Definition, out: &mut Emitter) -> fmt::Result {
+        let outer = self.enter_function(def.params, &def.param_types, def.variadic, def.body);
+        if let Some(class_name) = &self.self_class {
+            self.env.bind("self", Type::Class(class_name.clone()));
+        }
+        let returns = self.enter_returns(def.return_type, def.body);
+
+        let mut params_with_types = vec![self.receiver(def.name).to_string()];
+        params_with_types.extend(self.typed_params(
+            def.params,
+            &def.param_types,
+            def.variadic,
+            def.body,
+        ));
+
+        out.open(&format!(
+            "fn {}({}){} {{",
+            def.name,
+            params_with_types.join(", "),
+            self.return_signature(&returns)
+        ))?;
+        // A final expression is the value the method returns
+        match returns {
+            Some(_) => {
+                let ty = self.return_type.clone();
+                self.write_value_body(def.body, Some(ty), out)?
+            }
+            None => self.write_body(def.body, out)?,
+        }
+        self.leave_function(outer);
+        out.end("}")
+    }
+}
+
+/// A function or method definition, with its parameters typed as its calls settle
+/// them
+struct Definition<'d> {
+    name: &'d str,
+    params: &'d [String],
+    param_types: Vec<Type>,
+    variadic: bool,
+    return_type: Option<TypeAnnotation>,
+    body: &'d [Statement],
+}
+
+/// The local a constructor keeps one of its fields in
+#[derive(Debug, Clone)]
+struct FieldLocal {
+    /// The field's declared type, which assigned values convert to
+    ty: Type,
+    /// Set once the local is declared
+    bound: bool,
+    /// Set when the field is assigned more than once
+    mutable: bool,
+}
+
+/// Counts the `self.field` assignments of a body, in the order the fields are
+/// first assigned
+#[derive(Default)]
+struct FieldAssignments {
+    order: Vec<String>,
+    counts: HashMap<String, usize>,
+}
+
+impl Visitor for FieldAssignments {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        match stmt {
+            Statement::Assignment { name, .. } if name.starts_with("self.") => {
+                let field = &name["self.".len()..];
+                let count = self.counts.entry(field.to_string()).or_insert(0);
+                if *count == 0 {
+                    self.order.push(field.to_string());
+                }
+                *count += 1;
             }
-            _ => Self::generate_statement(stmt),
+            // Nested definitions have a `self` of their own, or none
+            Statement::FunctionDef { .. } | Statement::MethodDef { .. } => {}
+            _ => walk_statement(self, stmt),
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        if !matches!(expr, Expr::Lambda { .. }) {
+            walk_expr(self, expr);
+        }
+    }
+}
+
+/// Finds the first call of a name in a body, and how many arguments it passes
+struct CallArity<'a> {
+    name: &'a str,
+    arity: Option<usize>,
+}
+
+impl Visitor for CallArity<'_> {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        match stmt {
+            _ if self.arity.is_some() => {}
+            // Nested definitions have parameters of their own
+            Statement::FunctionDef { .. }
+            | Statement::MethodDef { .. }
+            | Statement::Test { .. } => {}
+            _ => walk_statement(self, stmt),
         }
     }
 
-    /// Generates an expression with self. prefix for simple identifiers (field references)
-    fn generate_expression_with_self(expr: &Expr) -> String {
+    fn visit_expr(&mut self, expr: &Expr) {
         match expr {
-            Expr::Identifier(name) if name != "self" => format!("self.{}", name),
-            Expr::BinaryOp { left, op, right } => {
-                let left_str = Self::generate_expression_with_self(left);
-                let right_str = Self::generate_expression_with_self(right);
-                format!("{} {} {}", left_str, Self::op_symbol(op), right_str)
-            }
-            Expr::FieldAccess { object, field } => {
-                let object_str = Self::generate_expression_with_self(object);
-                format!("{}.{}", object_str, field)
+            _ if self.arity.is_some() => {}
+            Expr::FunctionCall { name, args, .. } if name == self.name => {
+                self.arity = Some(args.len())
             }
-            _ => Self::generate_expression(expr),
+            // A lambda parameter of the same name hides the outer one
+            Expr::Lambda { params, .. } if params.iter().any(|param| param == self.name) => {}
+            _ => walk_expr(self, expr),
         }
     }
 }
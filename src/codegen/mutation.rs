@@ -0,0 +1,115 @@
+//! Methods that change the instance they are called on
+//!
+//! A method that assigns a field of `self`, however deeply nested the field is
+//! (`self.x = 1`, `self.start.x = 1`), or that calls such a method on `self` or one
+//! of its fields, takes `&mut self`, and a variable it is called on is bound with
+//! `let mut`. Methods are told apart by name alone, as a call's receiver can't
+//! always be typed, so every method of a name changes its instance if one does.
+
+use crate::parser::ast::visit::{walk_body, walk_expr, walk_statement};
+use crate::parser::{Expr, Program, Statement, Visitor};
+use std::collections::HashSet;
+
+/// The names of the methods that change their instance
+#[derive(Debug, Clone, Default)]
+pub(super) struct MutatingMethods {
+    names: HashSet<String>,
+}
+
+impl MutatingMethods {
+    pub(super) fn infer(program: &Program) -> Self {
+        let methods: Vec<(&str, &[Statement])> = program
+            .statements
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Statement::MethodDef {
+                    method_name,
+                    is_static: false,
+                    body,
+                    ..
+                } if method_name != "new" => Some((method_name.as_str(), body.as_slice())),
+                _ => None,
+            })
+            .collect();
+
+        // Each round can only add methods, so a round per method ends it
+        let mut mutating = MutatingMethods::default();
+        for _ in 0..=methods.len() {
+            let mut changed = false;
+            for (name, body) in &methods {
+                if !mutating.contains(name) && mutating.receivers(body).contains("self") {
+                    mutating.names.insert(name.to_string());
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        mutating
+    }
+
+    /// Checks if methods of a name change their instance
+    pub(super) fn contains(&self, method: &str) -> bool {
+        self.names.contains(method)
+    }
+
+    /// Returns the variables of a body, `self` included, whose fields it assigns or
+    /// that it calls a method changing them on
+    pub(super) fn receivers(&self, body: &[Statement]) -> HashSet<String> {
+        let mut receivers = Receivers {
+            methods: self,
+            names: HashSet::new(),
+        };
+        walk_body(&mut receivers, body);
+        receivers.names
+    }
+}
+
+/// Returns the variable an object expression starts from: `p` in `p.start.x`
+fn root(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::Identifier { name, .. } => Some(name),
+        Expr::FieldAccess { object, .. } | Expr::Index { object, .. } => root(object),
+        _ => None,
+    }
+}
+
+/// Collects the variables a body changes through their fields
+struct Receivers<'m> {
+    methods: &'m MutatingMethods,
+    names: HashSet<String>,
+}
+
+impl Visitor for Receivers<'_> {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        match stmt {
+            Statement::Assignment { name, value, .. } => {
+                if name.starts_with("self.") {
+                    self.names.insert("self".to_string());
+                }
+                self.visit_expr(value);
+            }
+            Statement::FieldAssignment { object, .. } => {
+                self.names.extend(root(object).map(str::to_string));
+                walk_statement(self, stmt);
+            }
+            // Nested definitions have a `self` of their own, or none
+            Statement::FunctionDef { .. } | Statement::MethodDef { .. } => {}
+            _ => walk_statement(self, stmt),
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Lambda { .. } => {}
+            Expr::MethodCall { object, method, .. } => {
+                if self.methods.contains(method) {
+                    self.names.extend(root(object).map(str::to_string));
+                }
+                walk_expr(self, expr);
+            }
+            _ => walk_expr(self, expr),
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//! Settings for the shape of generated code
+//!
+//! The Grit program decides what the generated Rust does; `CodegenOptions` decides
+//! choices that are a matter of taste or of where the code is going, such as the
+//! indentation or whether statements are wrapped in `fn main`. The defaults produce
+//! a standalone program that `rustc` compiles as is.
+
+/// The Rust type Grit integers are generated as
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IntType {
+    #[default]
+    I64,
+    I32,
+}
+
+impl IntType {
+    /// Returns the Rust name of the type
+    pub fn rust_name(self) -> &'static str {
+        match self {
+            IntType::I64 => "i64",
+            IntType::I32 => "i32",
+        }
+    }
+}
+
+/// Settings for code generation
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodegenOptions {
+    /// Spaces per level of indentation
+    pub indent_width: usize,
+    /// Traits every class's struct derives; a class that is inherited from also
+    /// derives `Default`, which building its subclasses needs
+    pub derives: Vec<String>,
+    /// The type of integers, including untyped values
+    pub int_type: IntType,
+    /// Whether the top-level statements are wrapped in `fn main`; without it they
+    /// follow the items as they are, to be pasted into a function
+    pub wrap_in_main: bool,
+    /// Lints allowed for the whole generated crate with a `#![allow(...)]` preamble;
+    /// empty for no preamble
+    pub allow: Vec<String>,
+}
+
+impl Default for CodegenOptions {
+    fn default() -> Self {
+        CodegenOptions {
+            indent_width: 4,
+            derives: vec!["Clone".to_string()],
+            int_type: IntType::I64,
+            wrap_in_main: true,
+            allow: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! Support functions emitted into generated programs that need them
+
+/// A helper function the generated code calls at runtime
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RuntimeHelper {
+    /// `grit_clock()`: milliseconds elapsed since the first call
+    Clock,
+    /// `grit_typeof(&value)`: the Grit type name of a value not typed statically
+    ///
+    /// Integer variables may be `i32` in the generated code, since unsuffixed literals
+    /// default to it, so both widths map to `int`.
+    TypeOf,
+    /// `grit_run_test(index, test)`: runs one test block, catching its panic
+    ///
+    /// Prints `grit-test<TAB>ok|failed<TAB>micros<TAB>index<TAB>message` for
+    /// `grit test` to read back, and returns whether the test passed.
+    TestRunner,
+}
+
+impl RuntimeHelper {
+    /// Returns the Rust source of the helper
+    pub fn source(&self) -> &'static str {
+        match self {
+            RuntimeHelper::Clock => {
+                "fn grit_clock() -> i64 {
+    static START: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+    START.get_or_init(std::time::Instant::now).elapsed().as_millis() as i64
+}
+"
+            }
+            RuntimeHelper::TypeOf => {
+                r#"fn grit_typeof<T: ?Sized>(_: &T) -> &'static str {
+    let name = std::any::type_name::<T>();
+    match name {
+        "i32" | "i64" => "int",
+        "f64" => "float",
+        "bool" => "bool",
+        "&str" | "alloc::string::String" => "string",
+        _ => name.rsplit("::").next().unwrap_or(name),
+    }
+}
+"#
+            }
+            RuntimeHelper::TestRunner => {
+                r#"fn grit_run_test(index: usize, test: fn()) -> bool {
+    let start = std::time::Instant::now();
+    let result = std::panic::catch_unwind(test);
+    let micros = start.elapsed().as_micros();
+    let (status, message) = match &result {
+        Ok(()) => ("ok", String::new()),
+        Err(payload) => {
+            let message = match payload.downcast_ref::<&str>() {
+                Some(message) => message.to_string(),
+                None => payload.downcast_ref::<String>().cloned().unwrap_or_default(),
+            };
+            ("failed", message)
+        }
+    };
+    println!("grit-test\t{}\t{}\t{}\t{}", status, micros, index, message.replace(['\t', '\n'], " "));
+    result.is_ok()
+}
+"#
+            }
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Mapping from generated Rust lines back to the Grit source
+//!
+//! While a source map is being built, the generator wraps the code of each statement
+//! (and each method) in markers that carry the statement's span. Once the whole
+//! program is generated, `extract` strips the markers and records the lines between
+//! each pair, so the mapping stays right however the code was indented or moved
+//! around on its way into the output. The markers are stripped as the code is
+//! written out, so the code streams through to its destination.
+
+use crate::lexer::Span;
+use std::fmt::{self, Write};
+use std::ops::RangeInclusive;
+
+/// Opens a marked region; the span follows, up to `SPAN_END`
+const START: char = '\u{E000}';
+const SPAN_END: char = '\u{E001}';
+/// Closes the innermost open region
+const END: char = '\u{E002}';
+
+/// The generated lines produced for one Grit statement
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mapping {
+    /// The span of the statement in the Grit source
+    pub span: Span,
+    /// The 1-based lines of the generated Rust code
+    pub lines: RangeInclusive<usize>,
+}
+
+/// Which Grit statement each generated line came from
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SourceMap {
+    mappings: Vec<Mapping>,
+}
+
+impl SourceMap {
+    /// Returns every mapping, ordered by first generated line; a statement's
+    /// mapping comes before those of the statements nested in it
+    pub fn mappings(&self) -> &[Mapping] {
+        &self.mappings
+    }
+
+    /// Returns the span of the innermost statement that generated a line of Rust,
+    /// such as the line of a rustc error
+    ///
+    /// Lines of scaffolding that no statement produced (`fn main() {`, runtime
+    /// helpers) have no span.
+    pub fn lookup(&self, line: usize) -> Option<Span> {
+        self.mappings
+            .iter()
+            .filter(|mapping| mapping.lines.contains(&line))
+            .min_by_key(|mapping| mapping.lines.end() - mapping.lines.start())
+            .map(|mapping| mapping.span)
+    }
+}
+
+/// Returns the marker opening a region for the code generated from a span, which
+/// `END_MARKER` closes
+///
+/// Statements built without a span are left unmarked.
+pub(super) fn start_marker(span: Span) -> Option<String> {
+    (span != Span::default()).then(|| {
+        format!(
+            "{}{}:{}:{}:{}{}",
+            START, span.start_line, span.start_column, span.end_line, span.end_column, SPAN_END
+        )
+    })
+}
+
+/// Closes the region the last unclosed `start_marker` opened
+pub(super) const END_MARKER: &str = "\u{E002}";
+
+/// Writes marked code through to another writer without its markers, recording
+/// the lines each marked region spans
+pub(super) struct Extractor<W> {
+    out: W,
+    mappings: Vec<Mapping>,
+    /// The regions not closed yet, innermost last, each with its first line
+    open: Vec<(Span, usize)>,
+    /// The 1-based line being written
+    line: usize,
+    /// The text of a span being read, when its marker is split between writes
+    span: Option<String>,
+}
+
+impl<W: Write> Extractor<W> {
+    pub(super) fn new(out: W) -> Self {
+        Extractor {
+            out,
+            mappings: Vec::new(),
+            open: Vec::new(),
+            line: 1,
+            span: None,
+        }
+    }
+
+    /// Returns the writer and the lines of every region written through
+    pub(super) fn finish(self) -> (W, SourceMap) {
+        let mut mappings = self.mappings;
+        // Regions close innermost first; order them outermost first instead
+        mappings.sort_by_key(|mapping| (*mapping.lines.start(), usize::MAX - mapping.lines.end()));
+        (self.out, SourceMap { mappings })
+    }
+}
+
+impl<W: Write> Write for Extractor<W> {
+    fn write_str(&mut self, marked: &str) -> fmt::Result {
+        // Text between markers is written through in runs
+        let mut run = 0;
+        for (i, c) in marked.char_indices() {
+            if let Some(span) = &mut self.span {
+                if c == SPAN_END {
+                    let span = parse_span(span);
+                    self.open.push((span, self.line));
+                    self.span = None;
+                    run = i + c.len_utf8();
+                } else {
+                    span.push(c);
+                }
+                continue;
+            }
+            match c {
+                START | END => {
+                    self.out.write_str(&marked[run..i])?;
+                    run = i + c.len_utf8();
+                    if c == START {
+                        self.span = Some(String::new());
+                    } else if let Some((span, start)) = self.open.pop() {
+                        self.mappings.push(Mapping {
+                            span,
+                            lines: start..=self.line,
+                        });
+                    }
+                }
+                '\n' => self.line += 1,
+                _ => {}
+            }
+        }
+        if self.span.is_none() {
+            self.out.write_str(&marked[run..])?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads back the `line:column:line:column` a marker holds
+fn parse_span(text: &str) -> Span {
+    let mut parts = text.split(':').map(|part| part.parse().unwrap_or(0));
+    let mut next = || parts.next().unwrap_or(0);
+    Span::new(next(), next(), next(), next())
+}
@@ -0,0 +1,149 @@
+//! The whole pipeline in one call, for tools that need every intermediate artifact
+
+use crate::codegen::source_map::SourceMap;
+use crate::codegen::CodeGenerator;
+use crate::hir;
+use crate::lexer::{LexError, Token, Tokenizer};
+use crate::optimize::Pipeline;
+use crate::parser::{ParseError, Parser, Program};
+use crate::semantic::{self, Diagnostic};
+use std::time::{Duration, Instant};
+
+/// How long each compilation phase took
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Timings {
+    pub lexing: Duration,
+    pub parsing: Duration,
+    pub checking: Duration,
+    /// Includes running the optimization passes, if any, and lowering to the HIR
+    /// when the options ask for it
+    pub codegen: Duration,
+}
+
+impl Timings {
+    /// Returns the time spent in all phases together
+    pub fn total(&self) -> Duration {
+        self.lexing + self.parsing + self.checking + self.codegen
+    }
+}
+
+/// Settings for `compile_with_options`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompileOptions {
+    /// The optimization passes run over a program that passes its checks, before
+    /// it is lowered and generated; `None` generates the program as parsed
+    pub optimize: Option<Pipeline>,
+    /// Also lowers the checked program to the typed HIR, for tools that read it;
+    /// the Rust generator works from the AST, so it is skipped by default
+    pub hir: bool,
+}
+
+/// Everything produced by compiling one source
+///
+/// Phases after a failure are skipped: lexer errors are all listed in `lex_errors`
+/// and the first is reported as `ParseError::Lex`, parse errors leave
+/// `diagnostics` empty, and semantic errors leave `hir`, `generated_code`, and
+/// `source_map` unset. Skipped phases take no time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompileResult {
+    /// The tokens, including `Error` tokens; empty if a malformed number stopped lexing
+    pub tokens: Vec<Token>,
+    /// Every lexer error, in source order
+    pub lex_errors: Vec<LexError>,
+    /// The parsed program, or why parsing failed; optimization passes leave it as
+    /// parsed
+    pub ast: Result<Program, ParseError>,
+    /// Every syntax error, in source order, when parsing failed; parsing goes on past
+    /// each error, so the first of these is the one in `ast`
+    pub parse_errors: Vec<ParseError>,
+    /// The typed HIR lowered from the AST, when the options ask for it and the source
+    /// has no errors
+    pub hir: Option<hir::Program>,
+    /// Generated Rust code, or `None` if the source has errors
+    pub generated_code: Option<String>,
+    /// The Grit statement each line of `generated_code` came from
+    pub source_map: Option<SourceMap>,
+    /// Semantic errors and warnings
+    pub diagnostics: Vec<Diagnostic>,
+    pub timings: Timings,
+}
+
+impl CompileResult {
+    /// Checks if code was generated
+    pub fn succeeded(&self) -> bool {
+        self.generated_code.is_some()
+    }
+}
+
+/// Tokenizes, parses, checks, and generates code for a source in a single pass
+pub fn compile(source: &str) -> CompileResult {
+    compile_with_options(source, &CompileOptions::default())
+}
+
+/// Compiles a source, running the optimization passes the options name over the
+/// checked program
+pub fn compile_with_options(source: &str, options: &CompileOptions) -> CompileResult {
+    let mut timings = Timings::default();
+
+    let start = Instant::now();
+    let lexed = Tokenizer::new(source).tokenize();
+    timings.lexing = start.elapsed();
+
+    let mut parse_errors = Vec::new();
+    let (tokens, lex_errors, ast) = match lexed {
+        Ok(tokens) => {
+            let lex_errors: Vec<LexError> =
+                tokens.iter().filter_map(LexError::from_token).collect();
+            let ast = match lex_errors.first() {
+                Some(err) => Err(ParseError::Lex(err.clone())),
+                None => {
+                    let start = Instant::now();
+                    let outcome = Parser::new(tokens.clone()).parse_all();
+                    timings.parsing = start.elapsed();
+                    parse_errors = outcome.errors.clone();
+                    outcome.into_result()
+                }
+            };
+            (tokens, lex_errors, ast)
+        }
+        Err(err) => (Vec::new(), vec![err.clone()], Err(ParseError::Lex(err))),
+    };
+
+    let mut diagnostics = Vec::new();
+    let mut hir = None;
+    let mut generated_code = None;
+    let mut source_map = None;
+    if let Ok(program) = &ast {
+        let start = Instant::now();
+        diagnostics = semantic::check_program(program);
+        timings.checking = start.elapsed();
+
+        if !diagnostics.iter().any(Diagnostic::is_error) {
+            let start = Instant::now();
+            let optimized = options
+                .optimize
+                .as_ref()
+                .map(|pipeline| pipeline.run(program.clone()));
+            let program = optimized.as_ref().unwrap_or(program);
+            if options.hir {
+                hir = Some(hir::lower(program));
+            }
+            let (code, map) = CodeGenerator::generate_program_with_source_map(program);
+            generated_code = Some(code);
+            source_map = Some(map);
+            timings.codegen = start.elapsed();
+        }
+    }
+
+    CompileResult {
+        tokens,
+        lex_errors,
+        ast,
+        parse_errors,
+        hir,
+        generated_code,
+        source_map,
+        diagnostics,
+        timings,
+    }
+}
@@ -0,0 +1,479 @@
+//! `grit diff`: structural differences between two programs
+//!
+//! Declarations (functions, classes, mixins, methods, tests) are matched by name, so
+//! moving one around is not a change. The remaining top-level statements are
+//! compared in order. Source positions are ignored throughout.
+
+use crate::parser::ast::param_list;
+use crate::parser::{FieldDecl, Program, Statement, TraitMethod, TypeAnnotation};
+
+/// How an item differs between the old and new program
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl ChangeKind {
+    /// Returns the marker printed before the item: `+`, `-`, or `~`
+    pub fn marker(&self) -> char {
+        match self {
+            ChangeKind::Added => '+',
+            ChangeKind::Removed => '-',
+            ChangeKind::Changed => '~',
+        }
+    }
+}
+
+/// One added, removed, or changed item
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub kind: ChangeKind,
+    /// The item as written, e.g. `fn add(a, b)`; for changes, the new version
+    pub item: String,
+    /// What changed inside the item, one line per difference
+    pub details: Vec<String>,
+}
+
+/// The structural differences between two programs
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AstDiff {
+    pub changes: Vec<Change>,
+}
+
+impl AstDiff {
+    /// Compares two programs
+    pub fn between(old: &Program, new: &Program) -> Self {
+        let old_declarations: Vec<&Statement> = declarations(old).collect();
+        let new_declarations: Vec<&Statement> = declarations(new).collect();
+        let mut changes = Vec::new();
+
+        for &new_stmt in &new_declarations {
+            let key = declaration_key(new_stmt);
+            match old_declarations
+                .iter()
+                .find(|old_stmt| declaration_key(old_stmt) == key)
+            {
+                None => changes.push(Change {
+                    kind: ChangeKind::Added,
+                    item: new_stmt.to_string(),
+                    details: Vec::new(),
+                }),
+                Some(&old_stmt) => {
+                    let details = declaration_details(old_stmt, new_stmt);
+                    if !details.is_empty() {
+                        changes.push(Change {
+                            kind: ChangeKind::Changed,
+                            item: new_stmt.to_string(),
+                            details,
+                        });
+                    }
+                }
+            }
+        }
+
+        for &old_stmt in &old_declarations {
+            let key = declaration_key(old_stmt);
+            if !new_declarations
+                .iter()
+                .any(|new_stmt| declaration_key(new_stmt) == key)
+            {
+                changes.push(Change {
+                    kind: ChangeKind::Removed,
+                    item: old_stmt.to_string(),
+                    details: Vec::new(),
+                });
+            }
+        }
+
+        let old_statements: Vec<&Statement> = statements(old).collect();
+        let new_statements: Vec<&Statement> = statements(new).collect();
+        for edit in diff_sequence(&old_statements, &new_statements) {
+            changes.push(match edit {
+                Edit::Added(stmt) => Change {
+                    kind: ChangeKind::Added,
+                    item: stmt.to_string(),
+                    details: Vec::new(),
+                },
+                Edit::Removed(stmt) => Change {
+                    kind: ChangeKind::Removed,
+                    item: stmt.to_string(),
+                    details: Vec::new(),
+                },
+                Edit::Changed(old_stmt, new_stmt) => Change {
+                    kind: ChangeKind::Changed,
+                    item: new_stmt.to_string(),
+                    details: vec![replaced(old_stmt, new_stmt)],
+                },
+            });
+        }
+
+        AstDiff { changes }
+    }
+
+    /// Checks if the programs are structurally identical
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl std::fmt::Display for AstDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{} {}", change.kind.marker(), change.item)?;
+            for detail in &change.details {
+                writeln!(f, "    {}", detail)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks if a statement is matched by name rather than position
+fn is_declaration(stmt: &Statement) -> bool {
+    matches!(
+        stmt,
+        Statement::FunctionDef { .. }
+            | Statement::ClassDef { .. }
+            | Statement::MixinDef { .. }
+            | Statement::TraitDef { .. }
+            | Statement::EnumDef { .. }
+            | Statement::MethodDef { .. }
+            | Statement::ConstDef { .. }
+            | Statement::Global { .. }
+            | Statement::Import { .. }
+            | Statement::Test { .. }
+    )
+}
+
+fn declarations(program: &Program) -> impl Iterator<Item = &Statement> {
+    program
+        .statements
+        .iter()
+        .filter(|stmt| is_declaration(stmt))
+}
+
+fn statements(program: &Program) -> impl Iterator<Item = &Statement> {
+    program
+        .statements
+        .iter()
+        .filter(|stmt| !is_declaration(stmt))
+}
+
+/// Returns what identifies a declaration across versions: its kind and name
+fn declaration_key(stmt: &Statement) -> (&'static str, String) {
+    match stmt {
+        Statement::FunctionDef { name, .. } => ("fn", name.clone()),
+        Statement::ClassDef { name, .. } => ("class", name.clone()),
+        Statement::MixinDef { name, .. } => ("mixin", name.clone()),
+        Statement::TraitDef { name, .. } => ("trait", name.clone()),
+        Statement::EnumDef { name, .. } => ("enum", name.clone()),
+        Statement::MethodDef {
+            class_name,
+            method_name,
+            ..
+        } => ("method", format!("{} > {}", class_name, method_name)),
+        Statement::ConstDef { name, .. } => ("const", name.clone()),
+        Statement::Global { name, .. } => ("global", name.clone()),
+        Statement::Import { module, .. } => ("import", module.clone()),
+        Statement::Test { name, .. } => ("test", name.clone()),
+        _ => ("statement", String::new()),
+    }
+}
+
+/// Describes how two versions of the same declaration differ
+fn declaration_details(old: &Statement, new: &Statement) -> Vec<String> {
+    match (old, new) {
+        (
+            Statement::FunctionDef {
+                params: old_params,
+                param_types: old_types,
+                variadic: old_variadic,
+                return_type: old_return,
+                body: old_body,
+                ..
+            }
+            | Statement::MethodDef {
+                params: old_params,
+                param_types: old_types,
+                variadic: old_variadic,
+                return_type: old_return,
+                body: old_body,
+                ..
+            },
+            Statement::FunctionDef {
+                params: new_params,
+                param_types: new_types,
+                variadic: new_variadic,
+                return_type: new_return,
+                body: new_body,
+                ..
+            }
+            | Statement::MethodDef {
+                params: new_params,
+                param_types: new_types,
+                variadic: new_variadic,
+                return_type: new_return,
+                body: new_body,
+                ..
+            },
+        ) => {
+            let mut details = Vec::new();
+            let kind = |stmt: &Statement| match stmt {
+                Statement::MethodDef {
+                    is_static: true, ..
+                } => "static",
+                _ => "instance",
+            };
+            if matches!(old, Statement::MethodDef { .. }) && kind(old) != kind(new) {
+                details.push(format!("kind: {} -> {}", kind(old), kind(new)));
+            }
+            if old_params != new_params || old_types != new_types || old_variadic != new_variadic {
+                details.push(format!(
+                    "params: ({}) -> ({})",
+                    param_list(old_params, old_types, *old_variadic),
+                    param_list(new_params, new_types, *new_variadic)
+                ));
+            }
+            if old_return != new_return {
+                let name = |ty: &Option<TypeAnnotation>| match ty {
+                    Some(ty) => ty.to_string(),
+                    None => "(none)".to_string(),
+                };
+                details.push(format!(
+                    "return type: {} -> {}",
+                    name(old_return),
+                    name(new_return)
+                ));
+            }
+            details.extend(body_details(old_body, new_body));
+            details
+        }
+        (
+            Statement::ClassDef {
+                parent: old_parent,
+                traits: old_traits,
+                mixins: old_mixins,
+                fields: old_fields,
+                ..
+            },
+            Statement::ClassDef {
+                parent: new_parent,
+                traits: new_traits,
+                mixins: new_mixins,
+                fields: new_fields,
+                ..
+            },
+        ) => {
+            let list = |items: Vec<String>| {
+                if items.is_empty() {
+                    "(none)".to_string()
+                } else {
+                    items.join(", ")
+                }
+            };
+            let field_list =
+                |fields: &[FieldDecl]| list(fields.iter().map(FieldDecl::to_string).collect());
+            let mut details = Vec::new();
+            if old_parent != new_parent {
+                details.push(format!(
+                    "parent: {} -> {}",
+                    list(old_parent.iter().cloned().collect()),
+                    list(new_parent.iter().cloned().collect())
+                ));
+            }
+            if old_traits != new_traits {
+                details.push(format!(
+                    "traits: {} -> {}",
+                    list(old_traits.clone()),
+                    list(new_traits.clone())
+                ));
+            }
+            if old_mixins != new_mixins {
+                details.push(format!(
+                    "mixins: {} -> {}",
+                    list(old_mixins.clone()),
+                    list(new_mixins.clone())
+                ));
+            }
+            if field_list(old_fields) != field_list(new_fields) {
+                details.push(format!(
+                    "fields: {} -> {}",
+                    field_list(old_fields),
+                    field_list(new_fields)
+                ));
+            }
+            details
+        }
+        (
+            Statement::ConstDef {
+                value: old_value, ..
+            },
+            Statement::ConstDef {
+                value: new_value, ..
+            },
+        )
+        | (
+            Statement::Global {
+                value: old_value, ..
+            },
+            Statement::Global {
+                value: new_value, ..
+            },
+        ) if old_value.without_spans() != new_value.without_spans() => {
+            vec![format!("value: {} -> {}", old_value, new_value)]
+        }
+        (
+            Statement::Import {
+                names: old_names, ..
+            },
+            Statement::Import {
+                names: new_names, ..
+            },
+        ) if old_names != new_names => {
+            let list = |names: &[String]| {
+                if names.is_empty() {
+                    "(all)".to_string()
+                } else {
+                    names.join(", ")
+                }
+            };
+            vec![format!("names: {} -> {}", list(old_names), list(new_names))]
+        }
+        (Statement::Test { body: old_body, .. }, Statement::Test { body: new_body, .. }) => {
+            body_details(old_body, new_body)
+        }
+        (
+            Statement::TraitDef {
+                methods: old_methods,
+                ..
+            },
+            Statement::TraitDef {
+                methods: new_methods,
+                ..
+            },
+        ) => {
+            let list = |methods: &[TraitMethod]| {
+                if methods.is_empty() {
+                    return "(none)".to_string();
+                }
+                let methods: Vec<String> = methods.iter().map(TraitMethod::to_string).collect();
+                methods.join(", ")
+            };
+            let (old_list, new_list) = (list(old_methods), list(new_methods));
+            if old_list == new_list {
+                return Vec::new();
+            }
+            vec![format!("methods: {} -> {}", old_list, new_list)]
+        }
+        (
+            Statement::EnumDef {
+                variants: old_variants,
+                ..
+            },
+            Statement::EnumDef {
+                variants: new_variants,
+                ..
+            },
+        ) if old_variants != new_variants => {
+            vec![format!(
+                "variants: {} -> {}",
+                old_variants.join(", "),
+                new_variants.join(", ")
+            )]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Describes the statements added, removed, or changed in a body
+fn body_details(old: &[Statement], new: &[Statement]) -> Vec<String> {
+    let old: Vec<&Statement> = old.iter().collect();
+    let new: Vec<&Statement> = new.iter().collect();
+    diff_sequence(&old, &new)
+        .into_iter()
+        .map(|edit| match edit {
+            Edit::Added(stmt) => format!("+ {}", stmt),
+            Edit::Removed(stmt) => format!("- {}", stmt),
+            Edit::Changed(old_stmt, new_stmt) if old_stmt.to_string() == new_stmt.to_string() => {
+                format!("~ {}", replaced(old_stmt, new_stmt))
+            }
+            Edit::Changed(old_stmt, new_stmt) => format!("~ {} -> {}", old_stmt, new_stmt),
+        })
+        .collect()
+}
+
+/// Describes a statement replaced in place
+///
+/// Statements like `if` display only their header, so when the headers match the
+/// difference lies in a nested body.
+fn replaced(old: &Statement, new: &Statement) -> String {
+    if old.to_string() == new.to_string() {
+        format!("{} (body changed)", new)
+    } else {
+        format!("was: {}", old)
+    }
+}
+
+/// A difference between two statement sequences
+enum Edit<'p> {
+    Added(&'p Statement),
+    Removed(&'p Statement),
+    /// A statement replaced in place
+    Changed(&'p Statement, &'p Statement),
+}
+
+/// Aligns two statement sequences by their longest common subsequence
+///
+/// Removals directly followed by additions are paired up as changes.
+fn diff_sequence<'p>(old: &[&'p Statement], new: &[&'p Statement]) -> Vec<Edit<'p>> {
+    let same = |a: &Statement, b: &Statement| a.without_spans() == b.without_spans();
+
+    // lengths[i][j]: common subsequence length of old[i..] and new[j..]
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if same(old[i], new[j]) {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut edits = Vec::new();
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && same(old[i], new[j]) {
+            flush(&mut edits, &mut removed, &mut added);
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || lengths[i][j + 1] >= lengths[i + 1][j]) {
+            added.push(new[j]);
+            j += 1;
+        } else {
+            removed.push(old[i]);
+            i += 1;
+        }
+    }
+    flush(&mut edits, &mut removed, &mut added);
+
+    edits
+}
+
+/// Emits a run of removals and additions, pairing them up as changes
+fn flush<'p>(
+    edits: &mut Vec<Edit<'p>>,
+    removed: &mut Vec<&'p Statement>,
+    added: &mut Vec<&'p Statement>,
+) {
+    let paired = removed.len().min(added.len());
+    for (old, new) in removed.iter().zip(added.iter()) {
+        edits.push(Edit::Changed(old, new));
+    }
+    edits.extend(removed.drain(..).skip(paired).map(Edit::Removed));
+    edits.extend(added.drain(..).skip(paired).map(Edit::Added));
+}
@@ -0,0 +1,696 @@
+use super::{
+    Constant, Expr, ExprKind, Function, Global, MatchArm, Method, Param, Program, Statement, Test,
+};
+use crate::lexer::Span;
+use crate::parser::{self as ast, TypeAnnotation};
+use crate::semantic::{FunctionSymbol, SymbolTable, Type, TypeEnv};
+
+/// Lowers a parsed program to the typed HIR
+///
+/// Types are inferred the way the checker infers them, so the program should be
+/// checked first; an unchecked program still lowers, with `Unknown` wherever its
+/// errors leave a type undetermined.
+pub fn lower(program: &ast::Program) -> Program {
+    let symbols = SymbolTable::from_program(program);
+    let mut lowerer = Lowerer {
+        env: TypeEnv::new(&symbols),
+        depth: 0,
+        constants: Vec::new(),
+        globals: Vec::new(),
+        functions: Vec::new(),
+        methods: Vec::new(),
+        tests: Vec::new(),
+    };
+    let main = lowerer.block(&program.statements);
+
+    let Lowerer {
+        constants,
+        globals,
+        functions,
+        methods,
+        tests,
+        ..
+    } = lowerer;
+    Program {
+        symbols,
+        constants,
+        globals,
+        functions,
+        methods,
+        tests,
+        main,
+    }
+}
+
+/// Walks the AST in the checker's scopes, collecting the program's definitions
+struct Lowerer<'a> {
+    env: TypeEnv<'a>,
+    /// How many blocks or function bodies enclose the current statement
+    depth: usize,
+    constants: Vec<Constant>,
+    globals: Vec<Global>,
+    functions: Vec<Function>,
+    methods: Vec<Method>,
+    tests: Vec<Test>,
+}
+
+impl Lowerer<'_> {
+    /// Lowers the statements of a body; definitions that move out of it are left out
+    fn block(&mut self, statements: &[ast::Statement]) -> Vec<Statement> {
+        statements
+            .iter()
+            .filter_map(|stmt| self.statement(stmt))
+            .collect()
+    }
+
+    /// Lowers a nested block in its own scope
+    fn scoped_block(&mut self, statements: &[ast::Statement]) -> Vec<Statement> {
+        self.env.push_scope();
+        self.depth += 1;
+        let body = self.block(statements);
+        self.depth -= 1;
+        self.env.pop_scope();
+        body
+    }
+
+    /// Lowers a branch or loop body in its own scope, along with the variable its
+    /// condition binds
+    fn guarded_block(
+        &mut self,
+        condition: &ast::Expr,
+        statements: &[ast::Statement],
+    ) -> Vec<Statement> {
+        self.env.push_scope();
+        if let ast::Expr::Binding { name, value, .. } = condition {
+            // The body never sees a nil
+            let ty = self.env.infer(value).without_nil();
+            self.env.bind(name, ty);
+        }
+        self.depth += 1;
+        let body = self.block(statements);
+        self.depth -= 1;
+        self.env.pop_scope();
+        body
+    }
+
+    /// Returns the parameter and return types of the definition at a span, as the
+    /// semantic pass inferred them
+    ///
+    /// A function nested in a block has no inferred signature and takes its
+    /// annotations, giving back a value when it has a return annotation.
+    fn signature(
+        &self,
+        span: Span,
+        param_types: &[Option<TypeAnnotation>],
+        return_type: Option<TypeAnnotation>,
+    ) -> (Vec<Type>, Option<Type>) {
+        match self.env.symbols().signature(span) {
+            Some(symbol) if self.depth == 0 => {
+                (symbol.inferred_params.clone(), symbol.returns.clone())
+            }
+            _ => (
+                param_types.iter().copied().map(Type::annotated).collect(),
+                return_type.map(|ty| Type::annotated(Some(ty))),
+            ),
+        }
+    }
+
+    /// Lowers the parameters and body of a function or method, with only the
+    /// parameters (and a method's `self`) in scope
+    ///
+    /// A variadic last parameter is an array of the extra arguments.
+    fn function_body(
+        &mut self,
+        params: &[String],
+        param_types: &[Type],
+        variadic: bool,
+        body: &[ast::Statement],
+        receiver: Option<&str>,
+    ) -> (Vec<Param>, Vec<Statement>) {
+        let mut env = TypeEnv::new(self.env.symbols());
+        if let Some(class_name) = receiver {
+            env.bind("self", Type::Class(class_name.to_string()));
+        }
+        let params: Vec<Param> = params
+            .iter()
+            .enumerate()
+            .map(|(i, param)| {
+                let mut ty = param_types.get(i).cloned().unwrap_or(Type::Unknown);
+                if variadic && i == params.len() - 1 {
+                    ty = Type::Array(Box::new(ty));
+                }
+                env.bind(param, ty.clone());
+                Param {
+                    name: param.clone(),
+                    ty,
+                }
+            })
+            .collect();
+
+        let outer = std::mem::replace(&mut self.env, env);
+        self.depth += 1;
+        let body = self.block(body);
+        self.depth -= 1;
+        self.env = outer;
+        (params, body)
+    }
+
+    fn statement(&mut self, stmt: &ast::Statement) -> Option<Statement> {
+        let lowered = match stmt {
+            ast::Statement::FunctionDef {
+                name,
+                params,
+                param_types,
+                variadic,
+                return_type,
+                body,
+                docs,
+                span,
+            } => {
+                let (types, returns) = self.signature(*span, param_types, *return_type);
+                let (params, body) = self.function_body(params, &types, *variadic, body, None);
+                let function = Function {
+                    name: name.clone(),
+                    params,
+                    variadic: *variadic,
+                    return_type: returns,
+                    body,
+                    docs: docs.clone(),
+                    span: *span,
+                };
+                if self.depth > 0 {
+                    return Some(Statement::Function(function));
+                }
+                self.functions.push(function);
+                return None;
+            }
+            ast::Statement::MethodDef {
+                class_name,
+                method_name,
+                is_static,
+                params,
+                param_types,
+                variadic,
+                return_type,
+                body,
+                docs,
+                span,
+            } => {
+                let receiver = (!is_static).then_some(class_name.as_str());
+                let (types, returns) = self.signature(*span, param_types, *return_type);
+                let (params, body) = self.function_body(params, &types, *variadic, body, receiver);
+                self.methods.push(Method {
+                    class_name: class_name.clone(),
+                    is_static: *is_static,
+                    function: Function {
+                        name: method_name.clone(),
+                        params,
+                        variadic: *variadic,
+                        return_type: returns,
+                        body,
+                        docs: docs.clone(),
+                        span: *span,
+                    },
+                });
+                return None;
+            }
+            ast::Statement::Test { name, body, span } => {
+                let (_, body) = self.function_body(&[], &[], false, body, None);
+                self.tests.push(Test {
+                    name: name.clone(),
+                    body,
+                    span: *span,
+                });
+                return None;
+            }
+            ast::Statement::ConstDef { name, value, span } => {
+                let value = self.expr(value);
+                self.constants.push(Constant {
+                    name: name.clone(),
+                    value,
+                    span: *span,
+                });
+                return None;
+            }
+            ast::Statement::Global { name, value, span } => {
+                let value = self.expr(value);
+                self.globals.push(Global {
+                    name: name.clone(),
+                    value,
+                    span: *span,
+                });
+                return None;
+            }
+            // Declarations without code are in the symbol table
+            ast::Statement::ClassDef { .. }
+            | ast::Statement::MixinDef { .. }
+            | ast::Statement::TraitDef { .. }
+            | ast::Statement::EnumDef { .. }
+            | ast::Statement::Import { .. } => return None,
+            ast::Statement::Assignment {
+                name,
+                mutable,
+                ty,
+                value,
+                span,
+            } => self.assignment(name, *mutable, *ty, value, *span),
+            ast::Statement::FieldAssignment {
+                object,
+                field,
+                value,
+                span,
+            } => Statement::FieldAssign {
+                object: self.expr(object),
+                field: field.clone(),
+                value: self.expr(value),
+                span: *span,
+            },
+            ast::Statement::ParallelAssignment {
+                names,
+                values,
+                span,
+            } => {
+                let values: Vec<Expr> = values.iter().map(|value| self.expr(value)).collect();
+                // A single value is a tuple destructured into the names
+                let types = match &values[..] {
+                    [value] if names.len() > 1 => match &value.ty {
+                        Type::Tuple(elements) if elements.len() == names.len() => elements.clone(),
+                        _ => vec![Type::Unknown; names.len()],
+                    },
+                    _ => values.iter().map(|value| value.ty.clone()).collect(),
+                };
+                // A `var` assigned again keeps its binding
+                for (name, ty) in names.iter().zip(types) {
+                    if !self.env.is_var(name) {
+                        self.env.bind(name, ty);
+                    }
+                }
+                Statement::ParallelAssign {
+                    names: names.clone(),
+                    values,
+                    span: *span,
+                }
+            }
+            ast::Statement::ArrayDestructuring { names, value, span } => {
+                let value = self.expr(value);
+                let element = match &value.ty {
+                    Type::Array(element) => (**element).clone(),
+                    _ => Type::Unknown,
+                };
+                for name in names.iter().filter(|name| *name != "_") {
+                    if !self.env.is_var(name) {
+                        self.env.bind(name, element.clone());
+                    }
+                }
+                Statement::Destructure {
+                    names: names.clone(),
+                    value,
+                    span: *span,
+                }
+            }
+            ast::Statement::Block { body, .. } => Statement::Block(self.scoped_block(body)),
+            ast::Statement::If {
+                condition,
+                then_branch,
+                elif_branches,
+                else_branch,
+                span,
+            } => {
+                let branches = std::iter::once((condition, then_branch))
+                    .chain(
+                        elif_branches
+                            .iter()
+                            .map(|(condition, body)| (condition, body)),
+                    )
+                    .map(|(condition, body)| {
+                        (self.expr(condition), self.guarded_block(condition, body))
+                    })
+                    .collect();
+                Statement::If {
+                    branches,
+                    else_branch: else_branch.as_ref().map(|body| self.scoped_block(body)),
+                    span: *span,
+                }
+            }
+            ast::Statement::Match {
+                subject,
+                arms,
+                span,
+            } => Statement::Match {
+                subject: self.expr(subject),
+                arms: arms
+                    .iter()
+                    .map(|arm| MatchArm {
+                        patterns: arm.patterns.clone(),
+                        body: self.scoped_block(&arm.body),
+                    })
+                    .collect(),
+                span: *span,
+            },
+            ast::Statement::While {
+                condition,
+                body,
+                label,
+                span,
+            } => Statement::While {
+                condition: self.expr(condition),
+                body: self.guarded_block(condition, body),
+                label: label.clone(),
+                span: *span,
+            },
+            ast::Statement::Loop { body, label, span } => Statement::Loop {
+                body: self.scoped_block(body),
+                label: label.clone(),
+                span: *span,
+            },
+            ast::Statement::DoWhile {
+                body,
+                condition,
+                label,
+                span,
+            } => {
+                // The condition sees the variables assigned in the body
+                self.env.push_scope();
+                self.depth += 1;
+                let body = self.block(body);
+                self.depth -= 1;
+                let condition = self.expr(condition);
+                self.env.pop_scope();
+                Statement::DoWhile {
+                    body,
+                    condition,
+                    label: label.clone(),
+                    span: *span,
+                }
+            }
+            ast::Statement::Break { label, span } => Statement::Break {
+                label: label.clone(),
+                span: *span,
+            },
+            ast::Statement::Continue { label, span } => Statement::Continue {
+                label: label.clone(),
+                span: *span,
+            },
+            ast::Statement::Return { value, span } => Statement::Return {
+                value: value.as_ref().map(|value| self.expr(value)),
+                span: *span,
+            },
+            ast::Statement::Expression(expr) => Statement::Expression(self.expr(expr)),
+        };
+        Some(lowered)
+    }
+
+    /// Lowers an assignment to the declaration or reassignment it is
+    fn assignment(
+        &mut self,
+        name: &str,
+        mutable: bool,
+        annotation: Option<TypeAnnotation>,
+        value: &ast::Expr,
+        span: Span,
+    ) -> Statement {
+        let value = self.expr(value);
+        if let Some(field) = name.strip_prefix("self.") {
+            return Statement::FieldAssign {
+                object: self.name("self", span),
+                field: field.to_string(),
+                value,
+                span,
+            };
+        }
+        // A global, or a `var` or outer variable assigned again, keeps its binding and
+        // its type
+        if self.env.is_global_var(name)
+            || (!mutable && self.env.updates_variable(name, annotation.is_some()))
+        {
+            return Statement::Assign {
+                target: self.name(name, span),
+                value,
+                span,
+            };
+        }
+
+        let ty = annotation.map_or_else(|| value.ty.clone(), Type::from);
+        if mutable {
+            self.env.bind_var(name, ty.clone());
+        } else {
+            self.env.bind(name, ty.clone());
+        }
+        Statement::Let {
+            name: name.to_string(),
+            mutable,
+            ty,
+            value,
+            span,
+        }
+    }
+
+    /// Resolves a name to the variable, constant, or global it refers to
+    fn name(&self, name: &str, span: Span) -> Expr {
+        let symbols = self.env.symbols();
+        let kind = if self.env.is_local(name) {
+            ExprKind::Local(name.to_string())
+        } else if symbols.constant(name).is_some() {
+            ExprKind::Constant(name.to_string())
+        } else if symbols.global_var(name).is_some() {
+            ExprKind::Global(name.to_string())
+        } else {
+            ExprKind::Local(name.to_string())
+        };
+        Expr {
+            kind,
+            ty: self.env.lookup(name).cloned().unwrap_or(Type::Unknown),
+            span,
+        }
+    }
+
+    fn exprs(&mut self, exprs: &[ast::Expr]) -> Vec<Expr> {
+        exprs.iter().map(|expr| self.expr(expr)).collect()
+    }
+
+    fn boxed(&mut self, expr: &ast::Expr) -> Box<Expr> {
+        Box::new(self.expr(expr))
+    }
+
+    fn expr(&mut self, expr: &ast::Expr) -> Expr {
+        let ty = self.env.infer(expr);
+        let span = expr.span();
+        let kind = match expr {
+            ast::Expr::Integer { value, .. } => ExprKind::Integer(*value),
+            ast::Expr::Float { value, .. } => ExprKind::Float(*value),
+            ast::Expr::Bool { value, .. } => ExprKind::Bool(*value),
+            ast::Expr::Nil { .. } => ExprKind::Nil,
+            ast::Expr::String { value, .. } => ExprKind::String(value.clone()),
+            ast::Expr::Identifier { name, .. } => return self.name(name, span),
+            // Parentheses only group; calls put named arguments in place
+            ast::Expr::Grouped { inner, .. } | ast::Expr::NamedArg { value: inner, .. } => {
+                return self.expr(inner)
+            }
+            ast::Expr::BinaryOp {
+                left, op, right, ..
+            } => ExprKind::Binary {
+                op: op.clone(),
+                left: self.boxed(left),
+                right: self.boxed(right),
+            },
+            ast::Expr::UnaryOp { op, operand, .. } => ExprKind::Unary {
+                op: op.clone(),
+                operand: self.boxed(operand),
+            },
+            ast::Expr::Array { elements, .. } => ExprKind::Array(self.exprs(elements)),
+            ast::Expr::Tuple { elements, .. } => ExprKind::Tuple(self.exprs(elements)),
+            ast::Expr::Map { entries, .. } => ExprKind::Map(
+                entries
+                    .iter()
+                    .map(|(key, value)| (self.expr(key), self.expr(value)))
+                    .collect(),
+            ),
+            ast::Expr::Index { object, index, .. } => ExprKind::Index {
+                object: self.boxed(object),
+                index: self.boxed(index),
+            },
+            ast::Expr::Slice {
+                object, start, end, ..
+            } => ExprKind::Slice {
+                object: self.boxed(object),
+                start: start.as_deref().map(|start| self.boxed(start)),
+                end: end.as_deref().map(|end| self.boxed(end)),
+            },
+            // A variable holding a lambda shadows the function
+            ast::Expr::FunctionCall { name, args, .. } if self.env.lookup(name).is_some() => {
+                ExprKind::CallValue {
+                    callee: Box::new(self.name(name, span)),
+                    args: self.exprs(args),
+                }
+            }
+            ast::Expr::FunctionCall { name, args, .. } => ExprKind::Call {
+                function: name.clone(),
+                args: self.call_args(name, args),
+            },
+            ast::Expr::Call { callee, args, .. } => ExprKind::CallValue {
+                callee: self.boxed(callee),
+                args: self.exprs(args),
+            },
+            ast::Expr::Binding { name, value, .. } => ExprKind::Binding {
+                name: name.clone(),
+                value: self.boxed(value),
+            },
+            ast::Expr::FieldAccess { object, field, .. } => ExprKind::FieldAccess {
+                object: self.boxed(object),
+                field: field.clone(),
+            },
+            ast::Expr::MethodCall {
+                object,
+                method,
+                args,
+                ..
+            } => return self.method_call(object, method, args, ty, span),
+            ast::Expr::Cond {
+                branches,
+                else_branch,
+                ..
+            } => ExprKind::Cond {
+                branches: branches
+                    .iter()
+                    .map(|(condition, body)| (self.expr(condition), self.scoped_block(body)))
+                    .collect(),
+                else_branch: self.scoped_block(else_branch),
+            },
+            ast::Expr::Lambda { params, body, .. } => {
+                self.env.push_scope();
+                for param in params {
+                    self.env.bind(param, Type::Unknown);
+                }
+                self.depth += 1;
+                let body = self.block(body);
+                self.depth -= 1;
+                self.env.pop_scope();
+                ExprKind::Lambda {
+                    params: params
+                        .iter()
+                        .map(|param| Param {
+                            name: param.clone(),
+                            ty: Type::Unknown,
+                        })
+                        .collect(),
+                    body,
+                }
+            }
+        };
+        Expr { kind, ty, span }
+    }
+
+    /// Lowers `object.method(args)`: an enum variant, a static call on a class, a
+    /// field read (a method call without arguments on a class that has no such
+    /// method), or an instance method call
+    fn method_call(
+        &mut self,
+        object: &ast::Expr,
+        method: &str,
+        args: &[ast::Expr],
+        ty: Type,
+        span: Span,
+    ) -> Expr {
+        let symbols = self.env.symbols();
+        if let Some(enum_name) = self.env.enum_receiver(object) {
+            return Expr {
+                kind: ExprKind::Variant {
+                    enum_name: enum_name.to_string(),
+                    variant: method.to_string(),
+                },
+                ty,
+                span,
+            };
+        }
+
+        let (object, class_name) = match object {
+            ast::Expr::Identifier { name, span }
+                if self.env.lookup(name).is_none() && symbols.is_class(name) =>
+            {
+                let class = Expr {
+                    kind: ExprKind::Class(name.to_string()),
+                    ty: Type::Unknown,
+                    span: *span,
+                };
+                (class, Some(name.to_string()))
+            }
+            _ => {
+                let object = self.expr(object);
+                let class_name = match &object.ty {
+                    Type::Class(name) => Some(name.clone()),
+                    _ => None,
+                };
+                (object, class_name)
+            }
+        };
+        let callee = class_name
+            .as_deref()
+            .and_then(|class_name| symbols.resolve_method(class_name, method));
+
+        let is_instance = !matches!(object.kind, ExprKind::Class(_));
+        if let (None, Some(class_name), true, true) =
+            (callee, &class_name, is_instance, args.is_empty())
+        {
+            let ty = symbols
+                .class(class_name)
+                .and_then(|class| class.field(method))
+                .map_or(Type::Unknown, |field| field.ty.clone());
+            return Expr {
+                kind: ExprKind::FieldAccess {
+                    object: Box::new(object),
+                    field: method.to_string(),
+                },
+                ty,
+                span,
+            };
+        }
+
+        let args = match callee {
+            Some(callee) => self.ordered_args(callee, args),
+            None => self.exprs(args),
+        };
+        Expr {
+            kind: ExprKind::MethodCall {
+                object: Box::new(object),
+                method: method.to_string(),
+                args,
+            },
+            ty,
+            span,
+        }
+    }
+
+    /// Lowers the arguments of a call to a function by name
+    fn call_args(&mut self, name: &str, args: &[ast::Expr]) -> Vec<Expr> {
+        match self.env.symbols().function(name) {
+            Some(function) => self.ordered_args(function, args),
+            None => self.exprs(args),
+        }
+    }
+
+    /// Lowers the arguments of a call in parameter order: the positional arguments,
+    /// then the named arguments for the remaining fixed parameters, then any extra
+    /// arguments a variadic callee collects
+    fn ordered_args(&mut self, callee: &FunctionSymbol, args: &[ast::Expr]) -> Vec<Expr> {
+        let (named, positional): (Vec<&ast::Expr>, Vec<&ast::Expr>) = args
+            .iter()
+            .partition(|arg| matches!(arg, ast::Expr::NamedArg { .. }));
+        let fixed = callee.required_args().min(positional.len());
+        let mut lowered: Vec<Expr> = positional[..fixed]
+            .iter()
+            .map(|arg| self.expr(arg))
+            .collect();
+        for param in &callee.params[fixed..callee.required_args()] {
+            let value = named.iter().find_map(|arg| match arg {
+                ast::Expr::NamedArg { name, value, .. } if name == param => Some(value),
+                _ => None,
+            });
+            // The checker reports missing arguments; there is nothing to pass for them
+            if let Some(value) = value {
+                lowered.push(self.expr(value));
+            }
+        }
+        lowered.extend(positional[fixed..].iter().map(|arg| self.expr(arg)));
+        lowered
+    }
+}
@@ -0,0 +1,263 @@
+//! Typed high-level IR of a checked program
+//!
+//! `lower` turns a checked `Program` into an `hir::Program`: definitions are
+//! separated from the statements main runs, every expression carries the `Type`
+//! inferred for it, functions and methods carry the signatures the semantic pass
+//! inferred, names are resolved to locals, constants, globals, classes, or enum
+//! variants, named arguments are put in parameter order, and parentheses are gone.
+//! Declarations without code (classes, mixins, traits, enums, imports) live in the
+//! program's symbol table.
+//!
+//! The Rust generator still walks the AST, not the HIR. It takes signatures from
+//! the same symbol table and types expressions with the same `TypeEnv`, so the
+//! types it generates are the ones recorded here.
+
+mod lower;
+
+pub use lower::lower;
+
+use crate::lexer::Span;
+use crate::parser::{BinaryOperator, Pattern, UnaryOperator};
+use crate::semantic::{SymbolTable, Type};
+
+/// A lowered program
+#[derive(Debug, Clone, PartialEq)]
+pub struct Program {
+    /// The classes, functions, enums, constants, and globals the program declares
+    pub symbols: SymbolTable,
+    pub constants: Vec<Constant>,
+    pub globals: Vec<Global>,
+    pub functions: Vec<Function>,
+    pub methods: Vec<Method>,
+    pub tests: Vec<Test>,
+    /// The top-level statements that are not definitions, in order
+    pub main: Vec<Statement>,
+}
+
+/// A `const` declaration
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constant {
+    pub name: String,
+    pub value: Expr,
+    pub span: Span,
+}
+
+/// A `global` declaration, with its initial value
+#[derive(Debug, Clone, PartialEq)]
+pub struct Global {
+    pub name: String,
+    pub value: Expr,
+    pub span: Span,
+}
+
+/// A typed parameter of a function, method, or lambda
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param {
+    pub name: String,
+    /// The annotated or inferred type, `Unknown` when neither settles it; an array
+    /// for a variadic parameter
+    pub ty: Type,
+}
+
+/// A function definition, or the signature and body of a method
+#[derive(Debug, Clone, PartialEq)]
+pub struct Function {
+    pub name: String,
+    pub params: Vec<Param>,
+    /// Set when the last parameter collects any extra arguments
+    pub variadic: bool,
+    /// The annotated or inferred return type, `None` when the body gives back nothing
+    pub return_type: Option<Type>,
+    pub body: Vec<Statement>,
+    pub docs: Vec<String>,
+    pub span: Span,
+}
+
+/// A method definition: `fn Point > move(dx)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Method {
+    pub class_name: String,
+    /// Set for class-level methods, which take no `self`
+    pub is_static: bool,
+    pub function: Function,
+}
+
+/// A `test` block
+#[derive(Debug, Clone, PartialEq)]
+pub struct Test {
+    pub name: String,
+    pub body: Vec<Statement>,
+    pub span: Span,
+}
+
+/// Arm of a match statement
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchArm {
+    pub patterns: Vec<Pattern>,
+    pub body: Vec<Statement>,
+}
+
+/// Statement in a body
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    /// Declaration of a local variable, typed by its annotation or its value
+    Let {
+        name: String,
+        mutable: bool,
+        ty: Type,
+        value: Expr,
+        span: Span,
+    },
+    /// Assignment to a `var` already in scope, or to a global
+    Assign {
+        target: Expr,
+        value: Expr,
+        span: Span,
+    },
+    /// Assignment to a field, including `self.field`
+    FieldAssign {
+        object: Expr,
+        field: String,
+        value: Expr,
+        span: Span,
+    },
+    /// Parallel assignment or tuple destructuring; every value is evaluated first
+    ParallelAssign {
+        names: Vec<String>,
+        values: Vec<Expr>,
+        span: Span,
+    },
+    /// Array destructuring; `_` skips an element
+    Destructure {
+        names: Vec<String>,
+        value: Expr,
+        span: Span,
+    },
+    /// A function defined inside a body
+    Function(Function),
+    Block(Vec<Statement>),
+    /// An if statement; each `elif` is another branch
+    If {
+        branches: Vec<(Expr, Vec<Statement>)>,
+        else_branch: Option<Vec<Statement>>,
+        span: Span,
+    },
+    Match {
+        subject: Expr,
+        arms: Vec<MatchArm>,
+        span: Span,
+    },
+    While {
+        condition: Expr,
+        body: Vec<Statement>,
+        label: Option<String>,
+        span: Span,
+    },
+    Loop {
+        body: Vec<Statement>,
+        label: Option<String>,
+        span: Span,
+    },
+    DoWhile {
+        body: Vec<Statement>,
+        condition: Expr,
+        label: Option<String>,
+        span: Span,
+    },
+    Break {
+        label: Option<String>,
+        span: Span,
+    },
+    Continue {
+        label: Option<String>,
+        span: Span,
+    },
+    Return {
+        value: Option<Expr>,
+        span: Span,
+    },
+    Expression(Expr),
+}
+
+/// A typed expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr {
+    pub kind: ExprKind,
+    /// The inferred type, `Unknown` where it cannot be determined statically
+    pub ty: Type,
+    pub span: Span,
+}
+
+/// The shape of an expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprKind {
+    Integer(i64),
+    Float(f64),
+    Bool(bool),
+    Nil,
+    String(String),
+    /// A parameter or variable in scope, including `self`
+    Local(String),
+    Constant(String),
+    Global(String),
+    /// A class named as the receiver of a static method call
+    Class(String),
+    /// An enum variant: `Color.Red`
+    Variant {
+        enum_name: String,
+        variant: String,
+    },
+    Binary {
+        op: BinaryOperator,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+    Unary {
+        op: UnaryOperator,
+        operand: Box<Expr>,
+    },
+    Array(Vec<Expr>),
+    Tuple(Vec<Expr>),
+    Map(Vec<(Expr, Expr)>),
+    Index {
+        object: Box<Expr>,
+        index: Box<Expr>,
+    },
+    Slice {
+        object: Box<Expr>,
+        start: Option<Box<Expr>>,
+        end: Option<Box<Expr>>,
+    },
+    /// Call of a function or builtin by name, with the arguments in parameter order
+    Call {
+        function: String,
+        args: Vec<Expr>,
+    },
+    /// Call of a value, such as a lambda held in a variable
+    CallValue {
+        callee: Box<Expr>,
+        args: Vec<Expr>,
+    },
+    FieldAccess {
+        object: Box<Expr>,
+        field: String,
+    },
+    MethodCall {
+        object: Box<Expr>,
+        method: String,
+        args: Vec<Expr>,
+    },
+    /// Binding condition of an if, elif, or while
+    Binding {
+        name: String,
+        value: Box<Expr>,
+    },
+    Cond {
+        branches: Vec<(Expr, Vec<Statement>)>,
+        else_branch: Vec<Statement>,
+    },
+    Lambda {
+        params: Vec<Param>,
+        body: Vec<Statement>,
+    },
+}
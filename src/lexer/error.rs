@@ -0,0 +1,50 @@
+use super::token::{Token, TokenType};
+
+/// Lexer errors
+#[derive(Debug, Clone, PartialEq)]
+pub enum LexError {
+    /// A character that cannot start any token
+    UnexpectedCharacter {
+        ch: char,
+        line: usize,
+        column: usize,
+    },
+    /// An underscore in a number literal that is not between two digits, as in
+    /// `1_`, `1__000`, or `3._14`
+    MisplacedUnderscore { line: usize, column: usize },
+}
+
+impl LexError {
+    /// Returns the error an `Error` token stands for, or `None` for any other token
+    pub fn from_token(token: &Token) -> Option<Self> {
+        match token.token_type {
+            TokenType::Error(ch) => Some(LexError::UnexpectedCharacter {
+                ch,
+                line: token.line,
+                column: token.column,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for LexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LexError::UnexpectedCharacter { ch, line, column } => write!(
+                f,
+                "Unexpected character '{}' at line {}, column {}",
+                ch, line, column
+            ),
+            LexError::MisplacedUnderscore { line, column } => write!(
+                f,
+                "Misplaced '_' in number literal at line {}, column {}: underscores must be between digits",
+                line, column
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LexError {}
+
+pub type LexResult<T> = Result<T, LexError>;
@@ -0,0 +1,221 @@
+//! Interning of identifier and string literal text
+//!
+//! Every distinct name is stored once, in a table shared by the whole process, and
+//! stands for a `Symbol`: a `Copy` index into the table. Tokens and identifier
+//! expressions carry symbols instead of owned strings, so copying a token or an
+//! AST node copies an integer, and comparing or hashing names compares integers.
+//! A symbol reads as its text through `Deref`, so it can be used wherever a `&str`
+//! is taken.
+//!
+//! The table only grows: the text of a symbol is kept for the life of the process,
+//! which holds as many strings as the programs compiled have distinct names.
+//!
+//! An `Interner` is a view of the table listing the symbols of one source, in the
+//! order they first appear.
+
+use super::{Token, TokenType};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::ops::Deref;
+use std::sync::{OnceLock, RwLock};
+
+/// The text of every symbol, by index, and the index of every text
+#[derive(Default)]
+struct Table {
+    symbols: HashMap<&'static str, Symbol>,
+    strings: Vec<&'static str>,
+}
+
+fn table() -> &'static RwLock<Table> {
+    static TABLE: OnceLock<RwLock<Table>> = OnceLock::new();
+    TABLE.get_or_init(Default::default)
+}
+
+/// The index of a string in the symbol table
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// Returns the symbol for a string, adding it to the table if it is new
+    pub fn intern(text: &str) -> Symbol {
+        if let Some(&symbol) = table().read().unwrap().symbols.get(text) {
+            return symbol;
+        }
+        let mut table = table().write().unwrap();
+        // Another thread may have added it between the locks
+        if let Some(&symbol) = table.symbols.get(text) {
+            return symbol;
+        }
+        let symbol = Symbol(table.strings.len() as u32);
+        let text: &'static str = Box::leak(text.into());
+        table.strings.push(text);
+        table.symbols.insert(text, symbol);
+        symbol
+    }
+
+    /// Returns the text of the symbol
+    pub fn as_str(self) -> &'static str {
+        table().read().unwrap().strings[self.index()]
+    }
+
+    /// Returns the position of the symbol's text in the table
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl Deref for Symbol {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Shows the text, as a string would be shown
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl Default for Symbol {
+    /// The empty name
+    fn default() -> Self {
+        Symbol::intern("")
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(text: &str) -> Self {
+        Symbol::intern(text)
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(text: String) -> Self {
+        Symbol::intern(&text)
+    }
+}
+
+impl From<&String> for Symbol {
+    fn from(text: &String) -> Self {
+        Symbol::intern(text)
+    }
+}
+
+impl From<Symbol> for String {
+    fn from(symbol: Symbol) -> Self {
+        symbol.as_str().to_string()
+    }
+}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for Symbol {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<Symbol> for str {
+    fn eq(&self, other: &Symbol) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialEq<Symbol> for &str {
+    fn eq(&self, other: &Symbol) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl PartialEq<Symbol> for String {
+    fn eq(&self, other: &Symbol) -> bool {
+        self == other.as_str()
+    }
+}
+
+/// The symbols of one source, each listed once
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    seen: HashSet<Symbol>,
+    symbols: Vec<Symbol>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lists the symbol of every identifier and string literal in a token list, in
+    /// the order they first appear
+    pub fn from_tokens(tokens: &[Token]) -> Self {
+        let mut interner = Self::new();
+        for token in tokens {
+            if let TokenType::Identifier(symbol) | TokenType::String(symbol) = token.token_type {
+                interner.add(symbol);
+            }
+        }
+        interner
+    }
+
+    /// Returns the symbol for a string, listing it if it is new here
+    pub fn intern(&mut self, text: &str) -> Symbol {
+        let symbol = Symbol::intern(text);
+        self.add(symbol);
+        symbol
+    }
+
+    fn add(&mut self, symbol: Symbol) {
+        if self.seen.insert(symbol) {
+            self.symbols.push(symbol);
+        }
+    }
+
+    /// Returns the symbol for a string listed here
+    pub fn get(&self, text: &str) -> Option<Symbol> {
+        self.symbols.iter().copied().find(|symbol| *symbol == text)
+    }
+
+    /// Returns the text of a symbol
+    pub fn resolve(&self, symbol: Symbol) -> &'static str {
+        symbol.as_str()
+    }
+
+    /// Returns the number of distinct strings
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// Returns each symbol with its text, in the order they were listed
+    pub fn iter(&self) -> impl Iterator<Item = (Symbol, &str)> {
+        self.symbols.iter().map(|&symbol| (symbol, symbol.as_str()))
+    }
+}
@@ -1,5 +1,11 @@
+pub mod error;
+pub mod intern;
+pub mod stream;
 pub mod token;
 pub mod tokenizer;
 
-pub use token::{Token, TokenType};
+pub use error::{LexError, LexResult};
+pub use intern::{Interner, Symbol};
+pub use stream::{TokenSource, TokenStream};
+pub use token::{Span, Token, TokenType, TriviaToken};
 pub use tokenizer::Tokenizer;
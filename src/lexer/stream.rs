@@ -0,0 +1,182 @@
+use super::error::LexError;
+use super::token::{Span, Token, TokenType};
+use super::tokenizer::Tokenizer;
+use std::collections::VecDeque;
+
+/// A source of tokens that a `TokenStream` pulls from on demand
+///
+/// Implemented for owned token vectors, borrowed token slices, and the
+/// `Tokenizer` itself, so the parser can run over pre-lexed tokens or lex lazily.
+pub trait TokenSource {
+    /// Returns the next token, or `None` once the source is exhausted
+    fn pull(&mut self) -> Option<Token>;
+
+    /// Returns the error that ended the source early, if any
+    ///
+    /// Called once `pull` has returned `None`.
+    fn take_error(&mut self) -> Option<LexError> {
+        None
+    }
+}
+
+impl TokenSource for std::vec::IntoIter<Token> {
+    fn pull(&mut self) -> Option<Token> {
+        self.next()
+    }
+}
+
+impl TokenSource for &[Token] {
+    fn pull(&mut self) -> Option<Token> {
+        let (first, rest) = self.split_first()?;
+        *self = rest;
+        Some(first.clone())
+    }
+}
+
+/// A tokenizer source stops at the first lexer error, which it then reports through
+/// `take_error`
+impl TokenSource for Tokenizer<'_> {
+    fn pull(&mut self) -> Option<Token> {
+        match self.next_token() {
+            Ok(token) => Some(token),
+            Err(err) => {
+                self.pending_error = Some(err);
+                None
+            }
+        }
+    }
+
+    fn take_error(&mut self) -> Option<LexError> {
+        self.pending_error.take()
+    }
+}
+
+/// Cursor over a sequence of tokens with lookahead and span tracking
+///
+/// `TokenStream` is what the parser uses to navigate tokens, and it is public so
+/// that tooling (highlighters, formatters, linters) can walk tokens the same way.
+/// Tokens are pulled from the underlying source only as far as lookahead requires,
+/// and nothing is pulled after the EOF token.
+#[derive(Debug, Clone)]
+pub struct TokenStream<S: TokenSource = std::vec::IntoIter<Token>> {
+    source: S,
+    buffer: VecDeque<Token>,
+    exhausted: bool,
+    error: Option<LexError>,
+    position: usize,
+    last_span: Option<Span>,
+}
+
+impl TokenStream {
+    /// Creates a new stream over the given tokens
+    pub fn new(tokens: Vec<Token>) -> Self {
+        TokenStream::from_source(tokens.into_iter())
+    }
+}
+
+impl<S: TokenSource> TokenStream<S> {
+    /// Creates a new stream that pulls tokens from the given source
+    pub fn from_source(source: S) -> Self {
+        let mut stream = TokenStream {
+            source,
+            buffer: VecDeque::new(),
+            exhausted: false,
+            error: None,
+            position: 0,
+            last_span: None,
+        };
+        stream.fill(1);
+        stream
+    }
+
+    /// Pulls tokens from the source until at least `count` are buffered
+    fn fill(&mut self, count: usize) {
+        while self.buffer.len() < count && !self.exhausted {
+            match self.source.pull() {
+                Some(token) => {
+                    if self.error.is_none() {
+                        self.error = LexError::from_token(&token);
+                    }
+                    self.exhausted = token.token_type == TokenType::Eof;
+                    self.buffer.push_back(token);
+                }
+                None => {
+                    self.exhausted = true;
+                    self.error = self.error.take().or_else(|| self.source.take_error());
+                }
+            }
+        }
+    }
+
+    /// Returns the first lexer error met so far: an `Error` token pulled from the
+    /// source, or the error that cut the source short
+    pub fn lex_error(&self) -> Option<&LexError> {
+        self.error.as_ref()
+    }
+
+    /// Returns the next token without consuming it
+    pub fn peek(&self) -> Option<&Token> {
+        self.buffer.front()
+    }
+
+    /// Returns the token `n` positions ahead without consuming anything
+    ///
+    /// `peek_nth(0)` is the same as `peek()`.
+    pub fn peek_nth(&mut self, n: usize) -> Option<&Token> {
+        self.fill(n + 1);
+        self.buffer.get(n)
+    }
+
+    /// Returns the number of tokens consumed so far
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Checks if the stream is exhausted or positioned at EOF
+    pub fn is_at_end(&self) -> bool {
+        match self.peek() {
+            Some(token) => token.token_type == TokenType::Eof,
+            None => true,
+        }
+    }
+
+    /// Returns the span of the next token, or of the last consumed token at the end of input
+    pub fn current_span(&self) -> Span {
+        match self.peek() {
+            Some(token) => Span::of(token),
+            None => self.last_span.unwrap_or_default(),
+        }
+    }
+
+    /// Returns the span of the most recently consumed token
+    pub fn last_span(&self) -> Option<Span> {
+        self.last_span
+    }
+
+    /// Returns a span from the start of `start` to the end of the most recently
+    /// consumed token
+    pub fn span_from(&self, start: Span) -> Span {
+        match self.last_span {
+            Some(last) => Span::new(
+                start.start_line,
+                start.start_column,
+                last.end_line,
+                last.end_column,
+            ),
+            None => start,
+        }
+    }
+}
+
+impl<S: TokenSource> Iterator for TokenStream<S> {
+    type Item = Token;
+
+    /// Consumes and returns the next token
+    fn next(&mut self) -> Option<Token> {
+        let token = self.buffer.pop_front()?;
+        self.position += 1;
+        self.last_span = Some(Span::of(&token));
+        self.fill(1);
+        Some(token)
+    }
+}
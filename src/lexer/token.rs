@@ -1,11 +1,14 @@
+use super::Symbol;
+
 /// Represents the different types of tokens in the Grit language
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenType {
     // Literals
     Integer(i64),
     Float(f64),
-    String(String),
-    Identifier(String),
+    Bool(bool),
+    String(Symbol),
+    Identifier(Symbol),
 
     // Operators
     Plus,
@@ -22,14 +25,36 @@ pub enum TokenType {
     GreaterThan,
     GreaterThanOrEqual,
 
+    // Logical operators
+    DoubleAmpersand,
+    DoublePipe,
+    Bang,
+
+    // Bitwise operators
+    Ampersand,
+    Pipe,
+    Caret,
+    ShiftLeft,
+    ShiftRight,
+
     // Delimiters
     LeftParen,
     RightParen,
     LeftBrace,
     RightBrace,
+    LeftBracket,
+    RightBracket,
     Comma,
+    Colon,
+    /// Separates statements on one line
+    Semicolon,
+    Arrow,
+    FatArrow,
     Newline,
     Dot,
+    DotDot,
+    DotDotEqual,
+    DotDotDot,
 
     // Keywords
     Fn,
@@ -37,10 +62,35 @@ pub enum TokenType {
     Elif,
     Else,
     While,
+    Do,
+    Loop,
+    For,
+    In,
+    Match,
+    Cond,
+    Break,
+    Continue,
+    Return,
     Class,
+    Mixin,
+    Trait,
+    Enum,
+    With,
+    Import,
+    Const,
+    Global,
+    Var,
     Self_,
+    And,
+    Or,
+    Not,
+    Nil,
 
     // Special
+    /// A `##` doc comment line, without the `##` and the space after it
+    DocComment(String),
+    /// A character that cannot start any token, kept so lexing can continue past it
+    Error(char),
     Eof,
 }
 
@@ -50,15 +100,71 @@ pub struct Token {
     pub token_type: TokenType,
     pub line: usize,
     pub column: usize,
+    /// Where the token's last character is; the same as its start for a token
+    /// that takes up no more than one character
+    pub end_line: usize,
+    pub end_column: usize,
 }
 
 impl Token {
-    /// Creates a new token
+    /// Creates a new token that ends where it starts
     pub fn new(token_type: TokenType, line: usize, column: usize) -> Self {
         Token {
             token_type,
             line,
             column,
+            end_line: line,
+            end_column: column,
+        }
+    }
+
+    /// Sets where the token's last character is
+    pub fn ending_at(mut self, line: usize, column: usize) -> Self {
+        self.end_line = line;
+        self.end_column = column;
+        self
+    }
+}
+
+/// A token together with the exact source text it was lexed from
+///
+/// Produced by [`Tokenizer::tokenize_with_trivia`](super::Tokenizer::tokenize_with_trivia)
+/// for tools that must reproduce a source byte for byte, such as a formatter.
+/// Concatenating `leading` and `text` over every token, EOF included, gives the
+/// source back unchanged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriviaToken<'a> {
+    pub token: Token,
+    /// Whitespace and `#` comments skipped before the token, including newlines
+    /// inside `(` or `[`
+    pub leading: &'a str,
+    /// The token as written, before escapes or digit separators are processed
+    pub text: &'a str,
+}
+
+/// A region of source code, from the first token of a construct to the last
+/// character of its last token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Span {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl Span {
+    /// Creates a new span between two positions
+    pub fn new(start_line: usize, start_column: usize, end_line: usize, end_column: usize) -> Self {
+        Span {
+            start_line,
+            start_column,
+            end_line,
+            end_column,
         }
     }
+
+    /// Creates a span covering a single token, from its first character to its last
+    pub fn of(token: &Token) -> Self {
+        Span::new(token.line, token.column, token.end_line, token.end_column)
+    }
 }
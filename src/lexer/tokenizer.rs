@@ -1,41 +1,51 @@
-use super::token::{Token, TokenType};
+use super::error::{LexError, LexResult};
+use super::token::{Token, TokenType, TriviaToken};
+use super::Symbol;
+use std::borrow::Cow;
 
 /// Tokenizer for the Grit language
-pub struct Tokenizer {
-    input: Vec<char>,
+///
+/// Lexes directly over the borrowed source text: identifiers, numbers, and strings
+/// are sliced out of it rather than built up a character at a time.
+pub struct Tokenizer<'a> {
+    input: &'a str,
+    /// Byte offset of the current character
     position: usize,
     line: usize,
     column: usize,
+    /// Line and column of the character consumed last
+    previous: (usize, usize),
+    /// Error hit while lexing on demand as a `TokenSource`
+    pub(super) pending_error: Option<LexError>,
+    /// Set once iteration has yielded the EOF token
+    finished: bool,
+    /// Brackets opened and not yet closed, innermost last
+    open_delimiters: Vec<char>,
 }
 
-impl Tokenizer {
+impl<'a> Tokenizer<'a> {
     /// Creates a new tokenizer from the given input string
-    pub fn new(input: &str) -> Self {
+    pub fn new(input: &'a str) -> Self {
         Tokenizer {
-            input: input.chars().collect(),
+            input,
             position: 0,
             line: 1,
             column: 1,
+            previous: (1, 1),
+            pending_error: None,
+            finished: false,
+            open_delimiters: Vec::new(),
         }
     }
 
     /// Returns the current character without consuming it
     fn current_char(&self) -> Option<char> {
-        if self.position < self.input.len() {
-            Some(self.input[self.position])
-        } else {
-            None
-        }
+        self.input[self.position..].chars().next()
     }
 
     /// Peeks ahead at a character without consuming it
     fn peek_char(&self, offset: usize) -> Option<char> {
-        let peek_pos = self.position + offset;
-        if peek_pos < self.input.len() {
-            Some(self.input[peek_pos])
-        } else {
-            None
-        }
+        self.input[self.position..].chars().nth(offset)
     }
 
     /// Advances to the next character and returns it
@@ -45,8 +55,11 @@ impl Tokenizer {
     /// Always call current_char() first to check if there are more characters.
     /// Calling this method when position >= input.len() will cause a panic.
     fn advance(&mut self) -> char {
-        let ch = self.input[self.position];
-        self.position += 1;
+        let ch = self
+            .current_char()
+            .expect("advance() called at end of input");
+        self.position += ch.len_utf8();
+        self.previous = (self.line, self.column);
 
         if ch == '\n' {
             self.line += 1;
@@ -58,43 +71,99 @@ impl Tokenizer {
         ch
     }
 
-    /// Skips whitespace characters (excluding newlines)
+    /// Skips whitespace characters and `#` comments
+    ///
+    /// Newlines are significant, except directly inside `(` or `[`, so an expression
+    /// can wrap across lines until its brackets are closed. Inside a `{` they count
+    /// again, even when the brace itself sits inside parentheses. A `##` doc comment
+    /// is a token rather than whitespace, but only where it documents a definition.
     fn skip_whitespace(&mut self) {
+        let newlines_significant = !matches!(self.open_delimiters.last(), Some('(' | '['));
         while let Some(ch) = self.current_char() {
-            if ch.is_whitespace() && ch != '\n' {
+            if ch.is_whitespace() && (ch != '\n' || !newlines_significant) {
                 self.advance();
+            } else if ch == '#' && !(newlines_significant && self.at_doc_comment()) {
+                self.read_to_end_of_line();
             } else {
                 break;
             }
         }
     }
 
+    /// Checks if the `#` at the current position starts a doc comment
+    ///
+    /// A `##` line documents the next line that is neither blank nor a comment, so
+    /// it only counts when it starts its own line and that next line opens a
+    /// function, method, or class. Anywhere else it is an ordinary comment.
+    fn at_doc_comment(&self) -> bool {
+        if self.peek_char(1) != Some('#') {
+            return false;
+        }
+
+        let before = &self.input[..self.position];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        if !before[line_start..].trim().is_empty() {
+            return false;
+        }
+
+        self.input[self.position..]
+            .lines()
+            .skip(1)
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('#'))
+            .is_some_and(|line| {
+                ["fn", "class"].iter().any(|keyword| {
+                    line.strip_prefix(keyword).is_some_and(|rest| {
+                        !rest.starts_with(|ch: char| ch.is_alphanumeric() || ch == '_')
+                    })
+                })
+            })
+    }
+
+    /// Reads up to, but not including, the next newline
+    fn read_to_end_of_line(&mut self) -> &'a str {
+        let start = self.position;
+
+        while let Some(ch) = self.current_char() {
+            if ch == '\n' {
+                break;
+            }
+            self.advance();
+        }
+
+        &self.input[start..self.position]
+    }
+
     /// Reads an identifier or keyword from the input
-    fn read_identifier(&mut self) -> String {
-        let mut identifier = String::new();
+    fn read_identifier(&mut self) -> &'a str {
+        let start = self.position;
 
         while let Some(ch) = self.current_char() {
             if ch.is_alphanumeric() || ch == '_' {
-                identifier.push(ch);
                 self.advance();
             } else {
                 break;
             }
         }
 
-        identifier
+        &self.input[start..self.position]
     }
 
     /// Reads a string literal from the input (single-quoted)
+    ///
+    /// Text between escapes is copied a run at a time.
     fn read_string(&mut self) -> String {
         let mut string = String::new();
         self.advance(); // consume opening quote
+        let mut run = self.position;
 
         while let Some(ch) = self.current_char() {
             if ch == '\'' {
+                string.push_str(&self.input[run..self.position]);
                 self.advance(); // consume closing quote
-                break;
+                return string;
             } else if ch == '\\' {
+                string.push_str(&self.input[run..self.position]);
                 self.advance();
                 if let Some(escaped) = self.current_char() {
                     match escaped {
@@ -110,30 +179,47 @@ impl Tokenizer {
                     }
                     self.advance();
                 }
+                run = self.position;
             } else {
-                string.push(ch);
                 self.advance();
             }
         }
 
+        // Unterminated: the string runs to the end of the input
+        string.push_str(&self.input[run..]);
         string
     }
 
     /// Reads a number (integer or float) from the input
-    fn read_number(&mut self) -> TokenType {
-        let mut number = String::new();
+    ///
+    /// Underscores may separate digits (`1_000_000`, `3.141_592`) and are dropped
+    /// from the value.
+    fn read_number(&mut self) -> LexResult<TokenType> {
+        let start = self.position;
         let mut is_float = false;
 
         while let Some(ch) = self.current_char() {
             if ch.is_ascii_digit() {
-                number.push(ch);
+                self.advance();
+            } else if ch == '_' {
+                if !self.peek_char(1).is_some_and(|next| next.is_ascii_digit()) {
+                    return Err(LexError::MisplacedUnderscore {
+                        line: self.line,
+                        column: self.column,
+                    });
+                }
                 self.advance();
             } else if ch == '.' && !is_float {
                 // Check if next character is a digit (to distinguish from method calls)
                 if let Some(next_ch) = self.peek_char(1) {
+                    if next_ch == '_' {
+                        return Err(LexError::MisplacedUnderscore {
+                            line: self.line,
+                            column: self.column + 1,
+                        });
+                    }
                     if next_ch.is_ascii_digit() {
                         is_float = true;
-                        number.push(ch);
                         self.advance();
                     } else {
                         break;
@@ -146,56 +232,102 @@ impl Tokenizer {
             }
         }
 
-        if is_float {
+        let text = &self.input[start..self.position];
+        let number = if text.contains('_') {
+            Cow::Owned(text.replace('_', ""))
+        } else {
+            Cow::Borrowed(text)
+        };
+
+        Ok(if is_float {
             TokenType::Float(number.parse().unwrap_or(0.0))
         } else {
             TokenType::Integer(number.parse().unwrap_or(0))
-        }
+        })
     }
 
-    /// Returns the next token from the input
-    pub fn next_token(&mut self) -> Token {
+    /// Returns the next token from the input, or an error for a malformed number
+    ///
+    /// A character that cannot start a token comes back as a `TokenType::Error` token,
+    /// so every bad character in a source can be reported in one pass.
+    pub fn next_token(&mut self) -> LexResult<Token> {
         self.skip_whitespace();
 
         let line = self.line;
         let column = self.column;
 
-        match self.current_char() {
+        let token = match self.current_char() {
             None => Token::new(TokenType::Eof, line, column),
             Some(ch) => {
                 if ch.is_ascii_digit() {
-                    let token_type = self.read_number();
+                    let token_type = self.read_number()?;
                     Token::new(token_type, line, column)
                 } else if ch.is_alphabetic() || ch == '_' {
                     let identifier = self.read_identifier();
-                    let token_type = match identifier.as_str() {
+                    let token_type = match identifier {
                         "fn" => TokenType::Fn,
                         "if" => TokenType::If,
                         "elif" => TokenType::Elif,
                         "else" => TokenType::Else,
                         "while" => TokenType::While,
+                        "do" => TokenType::Do,
+                        "loop" => TokenType::Loop,
+                        "for" => TokenType::For,
+                        "in" => TokenType::In,
+                        "match" => TokenType::Match,
+                        "cond" => TokenType::Cond,
+                        "break" => TokenType::Break,
+                        "continue" => TokenType::Continue,
+                        "return" => TokenType::Return,
                         "class" => TokenType::Class,
+                        "mixin" => TokenType::Mixin,
+                        "trait" => TokenType::Trait,
+                        "enum" => TokenType::Enum,
+                        "with" => TokenType::With,
+                        "import" => TokenType::Import,
+                        "const" => TokenType::Const,
+                        "global" => TokenType::Global,
+                        "var" => TokenType::Var,
                         "self" => TokenType::Self_,
-                        _ => TokenType::Identifier(identifier),
+                        "and" => TokenType::And,
+                        "or" => TokenType::Or,
+                        "not" => TokenType::Not,
+                        "true" => TokenType::Bool(true),
+                        "false" => TokenType::Bool(false),
+                        "nil" => TokenType::Nil,
+                        _ => TokenType::Identifier(Symbol::intern(identifier)),
                     };
                     Token::new(token_type, line, column)
                 } else if ch == '\'' {
                     let string = self.read_string();
-                    Token::new(TokenType::String(string), line, column)
+                    Token::new(TokenType::String(Symbol::intern(&string)), line, column)
                 } else {
                     self.advance();
                     let token_type = match ch {
                         '+' => TokenType::Plus,
-                        '-' => TokenType::Minus,
+                        '-' => {
+                            // Check for ->
+                            if self.current_char() == Some('>') {
+                                self.advance();
+                                TokenType::Arrow
+                            } else {
+                                TokenType::Minus
+                            }
+                        }
                         '*' => TokenType::Multiply,
                         '/' => TokenType::Divide,
                         '=' => {
-                            // Check for ==
-                            if self.current_char() == Some('=') {
-                                self.advance();
-                                TokenType::EqualEqual
-                            } else {
-                                TokenType::Equals
+                            // Check for == and =>
+                            match self.current_char() {
+                                Some('=') => {
+                                    self.advance();
+                                    TokenType::EqualEqual
+                                }
+                                Some('>') => {
+                                    self.advance();
+                                    TokenType::FatArrow
+                                }
+                                _ => TokenType::Equals,
                             }
                         }
                         '!' => {
@@ -204,56 +336,123 @@ impl Tokenizer {
                                 self.advance();
                                 TokenType::NotEqual
                             } else {
-                                panic!(
-                                    "Unexpected character '{}' at line {}, column {}",
-                                    ch, line, column
-                                )
+                                TokenType::Bang
                             }
                         }
-                        '<' => {
-                            // Check for <=
-                            if self.current_char() == Some('=') {
+                        '&' => {
+                            // Check for &&
+                            if self.current_char() == Some('&') {
                                 self.advance();
-                                TokenType::LessThanOrEqual
+                                TokenType::DoubleAmpersand
                             } else {
-                                TokenType::LessThan
+                                TokenType::Ampersand
                             }
                         }
-                        '>' => {
-                            // Check for >=
-                            if self.current_char() == Some('=') {
+                        '|' => {
+                            // Check for ||
+                            if self.current_char() == Some('|') {
                                 self.advance();
-                                TokenType::GreaterThanOrEqual
+                                TokenType::DoublePipe
                             } else {
-                                TokenType::GreaterThan
+                                TokenType::Pipe
+                            }
+                        }
+                        '^' => TokenType::Caret,
+                        '<' => {
+                            // Check for <= and <<
+                            match self.current_char() {
+                                Some('=') => {
+                                    self.advance();
+                                    TokenType::LessThanOrEqual
+                                }
+                                Some('<') => {
+                                    self.advance();
+                                    TokenType::ShiftLeft
+                                }
+                                _ => TokenType::LessThan,
+                            }
+                        }
+                        '>' => {
+                            // Check for >= and >>
+                            match self.current_char() {
+                                Some('=') => {
+                                    self.advance();
+                                    TokenType::GreaterThanOrEqual
+                                }
+                                Some('>') => {
+                                    self.advance();
+                                    TokenType::ShiftRight
+                                }
+                                _ => TokenType::GreaterThan,
+                            }
+                        }
+                        '(' | '{' | '[' => {
+                            self.open_delimiters.push(ch);
+                            match ch {
+                                '(' => TokenType::LeftParen,
+                                '{' => TokenType::LeftBrace,
+                                _ => TokenType::LeftBracket,
+                            }
+                        }
+                        ')' | '}' | ']' => {
+                            self.open_delimiters.pop();
+                            match ch {
+                                ')' => TokenType::RightParen,
+                                '}' => TokenType::RightBrace,
+                                _ => TokenType::RightBracket,
                             }
                         }
-                        '(' => TokenType::LeftParen,
-                        ')' => TokenType::RightParen,
-                        '{' => TokenType::LeftBrace,
-                        '}' => TokenType::RightBrace,
                         ',' => TokenType::Comma,
-                        '.' => TokenType::Dot,
+                        ':' => TokenType::Colon,
+                        ';' => TokenType::Semicolon,
+                        '.' => {
+                            // Check for .., ..=, and ...
+                            if self.current_char() == Some('.') {
+                                self.advance();
+                                match self.current_char() {
+                                    Some('=') => {
+                                        self.advance();
+                                        TokenType::DotDotEqual
+                                    }
+                                    Some('.') => {
+                                        self.advance();
+                                        TokenType::DotDotDot
+                                    }
+                                    _ => TokenType::DotDot,
+                                }
+                            } else {
+                                TokenType::Dot
+                            }
+                        }
                         '\n' => TokenType::Newline,
-                        _ => {
-                            panic!(
-                                "Unexpected character '{}' at line {}, column {}",
-                                ch, line, column
-                            )
+                        '#' => {
+                            self.advance(); // consume the second '#'
+                            let text = self.read_to_end_of_line();
+                            let text = text.strip_prefix(' ').unwrap_or(text);
+                            TokenType::DocComment(text.trim_end().to_string())
                         }
+                        _ => TokenType::Error(ch),
                     };
                     Token::new(token_type, line, column)
                 }
             }
-        }
+        };
+
+        // Only EOF consumes nothing, and it ends where it starts
+        Ok(match token.token_type {
+            TokenType::Eof => token,
+            _ => token.ending_at(self.previous.0, self.previous.1),
+        })
     }
 
-    /// Tokenizes the entire input and returns a vector of tokens
-    pub fn tokenize(&mut self) -> Vec<Token> {
+    /// Tokenizes the entire input, stopping at the first malformed number
+    ///
+    /// Unexpected characters do not stop it; see [`LexError::from_token`].
+    pub fn tokenize(&mut self) -> LexResult<Vec<Token>> {
         let mut tokens = Vec::new();
 
         loop {
-            let token = self.next_token();
+            let token = self.next_token()?;
             let is_eof = token.token_type == TokenType::Eof;
             tokens.push(token);
 
@@ -262,6 +461,58 @@ impl Tokenizer {
             }
         }
 
-        tokens
+        Ok(tokens)
+    }
+
+    /// Returns the next token along with the whitespace before it and its source text
+    pub fn next_token_with_trivia(&mut self) -> LexResult<TriviaToken<'a>> {
+        let start = self.position;
+        self.skip_whitespace();
+        let token_start = self.position;
+        let token = self.next_token()?;
+
+        Ok(TriviaToken {
+            token,
+            leading: &self.input[start..token_start],
+            text: &self.input[token_start..self.position],
+        })
+    }
+
+    /// Tokenizes the entire input, keeping the trivia needed to rebuild it exactly
+    ///
+    /// Stops at the first malformed number, like `tokenize()`.
+    pub fn tokenize_with_trivia(&mut self) -> LexResult<Vec<TriviaToken<'a>>> {
+        let mut tokens = Vec::new();
+
+        loop {
+            let token = self.next_token_with_trivia()?;
+            let is_eof = token.token.token_type == TokenType::Eof;
+            tokens.push(token);
+
+            if is_eof {
+                break;
+            }
+        }
+
+        Ok(tokens)
+    }
+}
+
+/// Yields tokens lazily, one `next_token()` call at a time, ending after the EOF token
+///
+/// Lexer errors are yielded in place and iteration continues past them, so
+/// `collect::<LexResult<Vec<Token>>>()` stops at the first error like `tokenize()`.
+impl Iterator for Tokenizer<'_> {
+    type Item = LexResult<Token>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let result = self.next_token();
+        if let Ok(token) = &result {
+            self.finished = token.token_type == TokenType::Eof;
+        }
+        Some(result)
     }
 }
@@ -1,63 +1,330 @@
+pub mod build;
 pub mod codegen;
+pub mod compile;
+pub mod diff;
+pub mod hir;
 pub mod lexer;
+pub mod optimize;
 pub mod parser;
+pub mod semantic;
+pub mod testing;
 
-use codegen::CodeGenerator;
+pub use compile::{compile, compile_with_options, CompileOptions, CompileResult, Timings};
 use lexer::Tokenizer;
+use optimize::Pipeline;
 use parser::Parser;
 use std::fs;
 use std::io::Write;
+use std::path::PathBuf;
 
 /// Run the tokenizer and parser on the given arguments and write output to the given writer
 /// Returns Ok(()) on success, Err with exit code on failure
 pub fn run<W: Write>(args: &[String], output: &mut W) -> Result<(), i32> {
     if args.len() < 2 {
-        eprintln!("Usage: {} <file.grit>", args[0]);
+        eprintln!("Usage: {} [--optimize[=<passes>]] <file.grit>", args[0]);
+        eprintln!(
+            "       {} build [--no-cache] [--cache-dir <dir>] [--out-dir <dir>] [--optimize[=<passes>]] [paths...]",
+            args[0]
+        );
+        eprintln!("       {} test [paths...]", args[0]);
+        eprintln!("       {} diff <old.grit> <new.grit>", args[0]);
+        eprintln!("       {} --dump-symbols[=json] <file.grit>", args[0]);
+        eprintln!("       {} --dot <file.grit>", args[0]);
         return Err(1);
     }
 
-    let filename = &args[1];
+    match args[1].as_str() {
+        "build" => run_build(&args[2..], output),
+        "test" => run_test(&args[2..], output),
+        "diff" => match &args[2..] {
+            [old, new] => run_diff(old, new, output),
+            _ => {
+                eprintln!("Usage: {} diff <old.grit> <new.grit>", args[0]);
+                Err(1)
+            }
+        },
+        "--dump-symbols" | "--dump-symbols=json" => {
+            let Some(filename) = args.get(2) else {
+                eprintln!("Missing file after '{}'", args[1]);
+                return Err(1);
+            };
+            run_dump_symbols(filename, args[1].ends_with("=json"), output)
+        }
+        "--dot" => {
+            let Some(filename) = args.get(2) else {
+                eprintln!("Missing file after '{}'", args[1]);
+                return Err(1);
+            };
+            run_dot(filename, output)
+        }
+        flag if is_optimize_flag(flag) => {
+            let Some(filename) = args.get(2) else {
+                eprintln!("Missing file after '{}'", args[1]);
+                return Err(1);
+            };
+            let options = CompileOptions {
+                optimize: Some(optimize_flag(flag)?),
+                ..CompileOptions::default()
+            };
+            run_file(filename, &options, output)
+        }
+        filename => run_file(filename, &CompileOptions::default(), output),
+    }
+}
+
+/// Checks if an argument is `--optimize` or `--optimize=<passes>`
+fn is_optimize_flag(arg: &str) -> bool {
+    arg == "--optimize" || arg.starts_with("--optimize=")
+}
+
+/// Returns the pipeline an optimize flag asks for: the default one for a bare
+/// `--optimize`, or the comma-separated passes after `--optimize=` in that order
+fn optimize_flag(flag: &str) -> Result<Pipeline, i32> {
+    match flag.strip_prefix("--optimize=") {
+        Some(names) => Pipeline::from_names(names).map_err(|name| {
+            eprintln!("Unknown optimization pass '{}'", name);
+            1
+        }),
+        None => Ok(Pipeline::default()),
+    }
+}
+
+/// Builds a project: `grit build [--no-cache] [--cache-dir <dir>] [--out-dir <dir>]
+/// [--optimize[=<passes>]] [paths...]`
+///
+/// Paths default to the current directory.
+fn run_build<W: Write>(args: &[String], output: &mut W) -> Result<(), i32> {
+    let mut options = build::BuildOptions::default();
+    let mut paths = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--no-cache" => options.use_cache = false,
+            "--cache-dir" | "--out-dir" => {
+                let dir = args.next().ok_or_else(|| {
+                    eprintln!("Missing directory after '{}'", arg);
+                    1
+                })?;
+                if arg == "--cache-dir" {
+                    options.cache_dir = PathBuf::from(dir);
+                } else {
+                    options.out_dir = PathBuf::from(dir);
+                }
+            }
+            flag if is_optimize_flag(flag) => options.optimize = Some(optimize_flag(flag)?),
+            flag if flag.starts_with("--") => {
+                eprintln!("Unknown build option '{}'", flag);
+                return Err(1);
+            }
+            path => paths.push(PathBuf::from(path)),
+        }
+    }
+    if paths.is_empty() {
+        paths.push(PathBuf::from("."));
+    }
+
+    let report = build::build(&paths, &options).map_err(|err| {
+        eprintln!("Build failed: {}", err);
+        1
+    })?;
+
+    for file in &report.files {
+        let status = match (&file.output, file.cached) {
+            (None, _) => "failed",
+            (Some(_), true) => "cached",
+            (Some(_), false) => "compiled",
+        };
+        writeln!(output, "  {:<8} {}", status, file.source.display()).unwrap();
+        for message in &file.messages {
+            eprintln!("{}: {}", file.source.display(), message);
+        }
+    }
+    writeln!(
+        output,
+        "Built {} file(s): {} compiled, {} cached, {} failed",
+        report.files.len(),
+        report.compiled(),
+        report.cached(),
+        report.failed()
+    )
+    .unwrap();
+
+    if report.failed() > 0 {
+        return Err(1);
+    }
+    Ok(())
+}
+
+/// Runs Grit tests: `grit test [paths...]`
+///
+/// Paths default to `tests` if it exists, otherwise the current directory.
+fn run_test<W: Write>(args: &[String], output: &mut W) -> Result<(), i32> {
+    let paths: Vec<PathBuf> = if args.is_empty() {
+        testing::default_paths()
+    } else {
+        args.iter().map(PathBuf::from).collect()
+    };
+
+    let report = testing::run_tests(&paths).map_err(|err| {
+        eprintln!("Test run failed: {}", err);
+        1
+    })?;
+
+    for result in &report.results {
+        let status = if result.passed { "ok" } else { "FAILED" };
+        writeln!(
+            output,
+            "test {} > {} ... {} ({:.2}ms)",
+            result.file.display(),
+            result.name,
+            status,
+            result.duration.as_secs_f64() * 1000.0
+        )
+        .unwrap();
+        if let Some(message) = &result.message {
+            writeln!(output, "    {}", message).unwrap();
+        }
+    }
+    writeln!(
+        output,
+        "\ntest result: {}. {} passed; {} failed",
+        if report.failed() == 0 { "ok" } else { "FAILED" },
+        report.passed(),
+        report.failed()
+    )
+    .unwrap();
+
+    if report.failed() > 0 {
+        return Err(1);
+    }
+    Ok(())
+}
+
+/// Prints the structural differences between two files: `grit diff old.grit new.grit`
+fn run_diff<W: Write>(old: &str, new: &str, output: &mut W) -> Result<(), i32> {
+    let parse_file = |filename: &str| {
+        let source = fs::read_to_string(filename).map_err(|err| {
+            eprintln!("Error reading file '{}': {}", filename, err);
+            1
+        })?;
+        Parser::from_source(Tokenizer::new(&source))
+            .parse()
+            .map_err(|err| {
+                eprintln!("Parse error in '{}': {}", filename, err);
+                1
+            })
+    };
+    let old_program = parse_file(old)?;
+    let new_program = parse_file(new)?;
+
+    let diff = diff::AstDiff::between(&old_program, &new_program);
+    if diff.is_empty() {
+        writeln!(output, "No structural differences").unwrap();
+    } else {
+        write!(output, "{}", diff).unwrap();
+    }
+    Ok(())
+}
+
+/// Prints every declared symbol of a file with its inferred type and location
+///
+/// Semantic errors are reported but do not stop the listing, since it is meant for
+/// debugging exactly those programs.
+fn run_dump_symbols<W: Write>(filename: &str, json: bool, output: &mut W) -> Result<(), i32> {
     let source = fs::read_to_string(filename).map_err(|err| {
         eprintln!("Error reading file '{}': {}", filename, err);
         1
     })?;
 
-    // Tokenize
-    let mut tokenizer = Tokenizer::new(&source);
-    let tokens = tokenizer.tokenize();
+    let program = Parser::from_source(Tokenizer::new(&source))
+        .parse()
+        .map_err(|err| {
+            eprintln!("Parse error: {}", err);
+            1
+        })?;
+    for diagnostic in semantic::check_program(&program) {
+        eprintln!("{}", diagnostic);
+    }
+
+    let dump = semantic::SymbolDump::from_program(&program);
+    let listing = if json {
+        dump.to_json()
+    } else {
+        dump.to_table()
+    };
+    write!(output, "{}", listing).unwrap();
+    Ok(())
+}
+
+/// Prints a Graphviz DOT graph of a file's AST: `grit --dot file.grit | dot -Tsvg`
+fn run_dot<W: Write>(filename: &str, output: &mut W) -> Result<(), i32> {
+    let source = fs::read_to_string(filename).map_err(|err| {
+        eprintln!("Error reading file '{}': {}", filename, err);
+        1
+    })?;
+
+    let program = Parser::from_source(Tokenizer::new(&source))
+        .parse()
+        .map_err(|err| {
+            eprintln!("Parse error: {}", err);
+            1
+        })?;
+    write!(output, "{}", program.to_dot()).unwrap();
+    Ok(())
+}
+
+/// Prints the tokens, AST, and generated Rust code for a single file
+///
+/// The AST is printed as parsed; the code is generated from the program the
+/// options' optimization passes leave.
+fn run_file<W: Write>(filename: &str, options: &CompileOptions, output: &mut W) -> Result<(), i32> {
+    let source = fs::read_to_string(filename).map_err(|err| {
+        eprintln!("Error reading file '{}': {}", filename, err);
+        1
+    })?;
+
+    let result = compile_with_options(&source, options);
+    if !result.lex_errors.is_empty() {
+        for err in &result.lex_errors {
+            eprintln!("Lex error: {}", err);
+        }
+        return Err(1);
+    }
 
     writeln!(output, "Tokens:").unwrap();
-    for token in &tokens {
+    for token in &result.tokens {
         writeln!(output, "  {:?}", token).unwrap();
     }
     writeln!(output).unwrap();
 
-    // Parse (skip if input is empty)
+    // Skip the remaining output if input is empty
     if source.trim().is_empty() {
         writeln!(output, "Empty input - nothing to parse").unwrap();
-    } else {
-        let mut parser = Parser::new(tokens);
-        match parser.parse() {
-            Ok(program) => {
-                writeln!(output, "AST:").unwrap();
-                writeln!(output, "  {}", program).unwrap();
-                writeln!(output).unwrap();
-                writeln!(output, "Debug AST:").unwrap();
-                writeln!(output, "  {:?}", program).unwrap();
-                writeln!(output).unwrap();
-
-                // Generate Rust code
-                let rust_code = CodeGenerator::generate_program(&program);
-                writeln!(output, "Generated Rust code:").unwrap();
-                for line in rust_code.trim_end().lines() {
-                    writeln!(output, "  {}", line).unwrap();
-                }
-            }
-            Err(err) => {
-                eprintln!("Parse error: {}", err);
-                return Err(1);
-            }
-        }
+        return Ok(());
+    }
+
+    // Lexer errors returned above, so every parse error is in `parse_errors`
+    for err in &result.parse_errors {
+        eprintln!("Parse error: {}", err);
+    }
+    let program = result.ast.map_err(|_| 1)?;
+    writeln!(output, "AST:").unwrap();
+    for line in format!("{:#}", program).lines() {
+        writeln!(output, "  {}", line).unwrap();
+    }
+    writeln!(output).unwrap();
+    writeln!(output, "Debug AST:").unwrap();
+    writeln!(output, "  {:?}", program).unwrap();
+    writeln!(output).unwrap();
+
+    for diagnostic in &result.diagnostics {
+        eprintln!("{}", diagnostic);
+    }
+    let rust_code = result.generated_code.ok_or(1)?;
+    writeln!(output, "Generated Rust code:").unwrap();
+    for line in rust_code.trim_end().lines() {
+        writeln!(output, "  {}", line).unwrap();
     }
 
     Ok(())
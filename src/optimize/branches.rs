@@ -0,0 +1,88 @@
+//! Removal of `if` branches whose conditions are constant
+//!
+//! A condition built only from literals, operators, and top-level constants has the
+//! same value on every run. A branch whose condition is `false` never runs and is
+//! dropped; a branch whose condition is `true` always runs when it is reached, so
+//! it becomes the `else` and the branches after it are dropped. An `if` left with
+//! only its `else` becomes a block, which keeps the variables assigned in it local,
+//! and one left with nothing is removed.
+
+use crate::parser::ast::eval::{eval_with, Value};
+use crate::parser::ast::fold::walk_statement;
+use crate::parser::{Expr, Fold, Program, Statement};
+use std::collections::HashMap;
+
+pub(super) struct ConstantBranches {
+    constants: HashMap<String, Value>,
+}
+
+impl ConstantBranches {
+    /// Collects the values of the program's constants, for conditions that use them
+    pub(super) fn new(program: &Program) -> Self {
+        let mut constants = HashMap::new();
+        for stmt in &program.statements {
+            if let Statement::ConstDef { name, value, .. } = stmt {
+                if let Ok(value) = eval_with(value, &|name| constants.get(name).cloned()) {
+                    constants.insert(name.clone(), value);
+                }
+            }
+        }
+        ConstantBranches { constants }
+    }
+
+    /// Returns the value of a condition that is the same on every run
+    fn constant(&self, condition: &Expr) -> Option<bool> {
+        match eval_with(condition, &|name| self.constants.get(name).cloned()) {
+            Ok(Value::Bool(value)) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl Fold for ConstantBranches {
+    fn fold_body(&mut self, body: Vec<Statement>) -> Vec<Statement> {
+        let mut folded = Vec::with_capacity(body.len());
+        for stmt in body {
+            match walk_statement(self, stmt) {
+                Statement::If {
+                    condition,
+                    then_branch,
+                    elif_branches,
+                    mut else_branch,
+                    span,
+                } => {
+                    let mut branches = Vec::new();
+                    for (condition, branch) in
+                        std::iter::once((condition, then_branch)).chain(elif_branches)
+                    {
+                        match self.constant(&condition) {
+                            Some(false) => {}
+                            Some(true) => {
+                                else_branch = Some(branch);
+                                break;
+                            }
+                            None => branches.push((condition, branch)),
+                        }
+                    }
+
+                    let mut branches = branches.into_iter();
+                    match (branches.next(), else_branch) {
+                        (Some((condition, then_branch)), else_branch) => {
+                            folded.push(Statement::If {
+                                condition,
+                                then_branch,
+                                elif_branches: branches.collect(),
+                                else_branch,
+                                span,
+                            })
+                        }
+                        (None, Some(body)) => folded.push(Statement::Block { body, span }),
+                        (None, None) => {}
+                    }
+                }
+                stmt => folded.push(stmt),
+            }
+        }
+        folded
+    }
+}
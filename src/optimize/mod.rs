@@ -0,0 +1,126 @@
+//! Optimization passes over the AST
+//!
+//! Each pass rewrites a parsed program into a smaller one that runs the same way:
+//!
+//! - `ConstantBranches` drops the branches of an `if` whose condition is a constant
+//!   `false`, and keeps only the branch whose condition is a constant `true`
+//! - `UnreachableCode` drops the statements after a `return`, `break`, or `continue`
+//!   that always runs
+//! - `UnusedFunctions` drops the top-level functions that nothing calls or refers to
+//!
+//! A `Pipeline` runs passes in the order it is given, and each pass sees what the
+//! ones before it left: removing `if false { debug() }` first lets `UnusedFunctions`
+//! remove `debug` too. Dropping unused functions assumes the program is run on its
+//! own, so a file that other files import from is better left out of that pass.
+//!
+//! `compile_with_options` and `grit build` run a pipeline over each checked program
+//! when their options name one, as `--optimize` does on the command line.
+
+mod branches;
+mod unreachable;
+mod unused;
+
+use crate::parser::{Fold, Program};
+use std::fmt;
+
+/// A single optimization pass
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pass {
+    ConstantBranches,
+    UnreachableCode,
+    UnusedFunctions,
+}
+
+impl Pass {
+    /// Returns the pass's name, as `from_name` takes it: `constant-branches`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Pass::ConstantBranches => "constant-branches",
+            Pass::UnreachableCode => "unreachable-code",
+            Pass::UnusedFunctions => "unused-functions",
+        }
+    }
+
+    /// Returns the pass with a name, or `None` if there is none
+    pub fn from_name(name: &str) -> Option<Pass> {
+        [
+            Pass::ConstantBranches,
+            Pass::UnreachableCode,
+            Pass::UnusedFunctions,
+        ]
+        .into_iter()
+        .find(|pass| pass.name() == name)
+    }
+
+    /// Runs the pass over a program
+    pub fn run(&self, program: Program) -> Program {
+        match self {
+            Pass::ConstantBranches => {
+                branches::ConstantBranches::new(&program).fold_program(program)
+            }
+            Pass::UnreachableCode => unreachable::UnreachableCode.fold_program(program),
+            Pass::UnusedFunctions => unused::remove_unused(program),
+        }
+    }
+}
+
+/// Passes run one after another
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pipeline {
+    passes: Vec<Pass>,
+}
+
+impl Pipeline {
+    /// Creates a pipeline running the passes in the order given
+    pub fn new(passes: Vec<Pass>) -> Self {
+        Pipeline { passes }
+    }
+
+    /// Creates a pipeline from comma-separated pass names:
+    /// `constant-branches,unused-functions`
+    ///
+    /// Returns the first name that is not a pass's as the error.
+    pub fn from_names(names: &str) -> Result<Self, String> {
+        names
+            .split(',')
+            .map(|name| Pass::from_name(name.trim()).ok_or_else(|| name.trim().to_string()))
+            .collect::<Result<_, _>>()
+            .map(Pipeline::new)
+    }
+
+    /// Returns the passes in the order they run
+    pub fn passes(&self) -> &[Pass] {
+        &self.passes
+    }
+
+    /// Runs every pass over a program, each on the result of the one before
+    pub fn run(&self, program: Program) -> Program {
+        self.passes
+            .iter()
+            .fold(program, |program, pass| pass.run(program))
+    }
+}
+
+impl Default for Pipeline {
+    /// Constant branches first, so the code and functions only they reached go too
+    fn default() -> Self {
+        Pipeline::new(vec![
+            Pass::ConstantBranches,
+            Pass::UnreachableCode,
+            Pass::UnusedFunctions,
+        ])
+    }
+}
+
+/// Lists the pass names, as `from_names` takes them
+impl fmt::Display for Pipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = self.passes.iter().map(Pass::name).collect();
+        f.write_str(&names.join(","))
+    }
+}
+
+/// Runs the default pipeline over a program
+pub fn optimize(program: Program) -> Program {
+    Pipeline::default().run(program)
+}
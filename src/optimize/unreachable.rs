@@ -0,0 +1,44 @@
+//! Removal of statements that can never run
+//!
+//! Once a `return`, `break`, or `continue` runs, the rest of its body is skipped, so
+//! the statements after one that always runs are dropped. An `if` ends its body the
+//! same way when it has an `else` and every branch ends with one, and a block does
+//! when its own body does.
+
+use crate::parser::ast::fold::walk_statement;
+use crate::parser::{Fold, Statement};
+
+pub(super) struct UnreachableCode;
+
+impl Fold for UnreachableCode {
+    fn fold_body(&mut self, body: Vec<Statement>) -> Vec<Statement> {
+        let mut folded = Vec::with_capacity(body.len());
+        for stmt in body {
+            let stmt = walk_statement(self, stmt);
+            let ends = ends_body(&stmt);
+            folded.push(stmt);
+            if ends {
+                break;
+            }
+        }
+        folded
+    }
+}
+
+/// Checks if a statement always leaves the body it is in
+fn ends_body(stmt: &Statement) -> bool {
+    match stmt {
+        Statement::Return { .. } | Statement::Break { .. } | Statement::Continue { .. } => true,
+        Statement::Block { body, .. } => body.last().is_some_and(ends_body),
+        Statement::If {
+            then_branch,
+            elif_branches,
+            else_branch: Some(else_branch),
+            ..
+        } => std::iter::once(then_branch)
+            .chain(elif_branches.iter().map(|(_, branch)| branch))
+            .chain(std::iter::once(else_branch))
+            .all(|branch| branch.last().is_some_and(ends_body)),
+        _ => false,
+    }
+}
@@ -0,0 +1,65 @@
+//! Removal of top-level functions that are never used
+//!
+//! A function is used when a statement that runs refers to it, by calling it or by
+//! naming it as a value: the top-level statements main runs, methods, tests, and
+//! the bodies of functions that are used themselves. Nested functions stay, since
+//! only their enclosing function can use them.
+
+use crate::parser::ast::visit::walk_expr;
+use crate::parser::{Expr, Program, Statement, Visitor};
+use std::collections::{HashMap, HashSet};
+
+/// Drops the top-level functions nothing that runs refers to
+pub(super) fn remove_unused(mut program: Program) -> Program {
+    let functions: HashMap<&str, &[Statement]> = program
+        .statements
+        .iter()
+        .filter_map(|stmt| match stmt {
+            Statement::FunctionDef { name, body, .. } => Some((name.as_str(), body.as_slice())),
+            _ => None,
+        })
+        .collect();
+
+    let mut names = Names::default();
+    for stmt in &program.statements {
+        if !matches!(stmt, Statement::FunctionDef { .. }) {
+            names.visit_statement(stmt);
+        }
+    }
+
+    // Each function reached brings in the ones its own body names
+    let mut used: HashSet<String> = HashSet::new();
+    let mut pending = names.0;
+    while let Some(name) = pending.pop() {
+        if let Some(body) = functions.get(name.as_str()) {
+            if used.insert(name) {
+                let mut names = Names::default();
+                for stmt in body.iter() {
+                    names.visit_statement(stmt);
+                }
+                pending.extend(names.0);
+            }
+        }
+    }
+
+    program.statements.retain(|stmt| match stmt {
+        Statement::FunctionDef { name, .. } => used.contains(name),
+        _ => true,
+    });
+    program
+}
+
+/// Collects every name called as a function or read as a variable
+#[derive(Default)]
+struct Names(Vec<String>);
+
+impl Visitor for Names {
+    fn visit_expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::FunctionCall { name, .. } => self.0.push(name.clone()),
+            Expr::Identifier { name, .. } => self.0.push(name.to_string()),
+            _ => {}
+        }
+        walk_expr(self, expr);
+    }
+}
@@ -0,0 +1,347 @@
+//! Shorthand constructors for AST nodes
+//!
+//! `assign("x", binop(int(1), Add, int(2)))` builds the statement `x = 1 + 2`. Every
+//! node gets `Span::default()`, as if written by hand rather than parsed, so a tree
+//! built here equals a parsed one after `without_spans()`.
+
+use super::{BinaryOperator, Expr, MatchArm, Pattern, Program, Statement, UnaryOperator};
+use crate::lexer::{Span, Symbol};
+
+/// A program of the given statements
+pub fn program(statements: Vec<Statement>) -> Program {
+    Program { statements }
+}
+
+/// An integer literal
+pub fn int(value: i64) -> Expr {
+    Expr::Integer {
+        value,
+        span: Span::default(),
+    }
+}
+
+/// A float literal
+pub fn float(value: f64) -> Expr {
+    Expr::Float {
+        value,
+        span: Span::default(),
+    }
+}
+
+/// `true` or `false`
+pub fn boolean(value: bool) -> Expr {
+    Expr::Bool {
+        value,
+        span: Span::default(),
+    }
+}
+
+/// `nil`
+pub fn nil() -> Expr {
+    Expr::Nil {
+        span: Span::default(),
+    }
+}
+
+/// A string literal
+pub fn string(value: impl Into<String>) -> Expr {
+    Expr::String {
+        value: value.into(),
+        span: Span::default(),
+    }
+}
+
+/// A variable reference
+pub fn ident(name: impl Into<Symbol>) -> Expr {
+    Expr::Identifier {
+        name: name.into(),
+        span: Span::default(),
+    }
+}
+
+/// A binary operation: `binop(ident("a"), Add, int(1))`
+pub fn binop(left: Expr, op: BinaryOperator, right: Expr) -> Expr {
+    Expr::BinaryOp {
+        left: Box::new(left),
+        op,
+        right: Box::new(right),
+        span: Span::default(),
+    }
+}
+
+/// A prefix operation: `unop(Negate, ident("x"))`
+pub fn unop(op: UnaryOperator, operand: Expr) -> Expr {
+    Expr::UnaryOp {
+        op,
+        operand: Box::new(operand),
+        span: Span::default(),
+    }
+}
+
+/// An expression in parentheses
+pub fn grouped(inner: Expr) -> Expr {
+    Expr::Grouped {
+        inner: Box::new(inner),
+        span: Span::default(),
+    }
+}
+
+/// An array literal
+pub fn array(elements: Vec<Expr>) -> Expr {
+    Expr::Array {
+        elements,
+        span: Span::default(),
+    }
+}
+
+/// A tuple literal
+pub fn tuple(elements: Vec<Expr>) -> Expr {
+    Expr::Tuple {
+        elements,
+        span: Span::default(),
+    }
+}
+
+/// A map literal
+pub fn map(entries: Vec<(Expr, Expr)>) -> Expr {
+    Expr::Map {
+        entries,
+        span: Span::default(),
+    }
+}
+
+/// Indexing: `object[index]`
+pub fn index(object: Expr, index: Expr) -> Expr {
+    Expr::Index {
+        object: Box::new(object),
+        index: Box::new(index),
+        span: Span::default(),
+    }
+}
+
+/// Slicing: `object[start..end]`
+pub fn slice(object: Expr, start: Option<Expr>, end: Option<Expr>) -> Expr {
+    Expr::Slice {
+        object: Box::new(object),
+        start: start.map(Box::new),
+        end: end.map(Box::new),
+        span: Span::default(),
+    }
+}
+
+/// A call of a function by name
+pub fn call(name: impl Into<String>, args: Vec<Expr>) -> Expr {
+    Expr::FunctionCall {
+        name: name.into(),
+        args,
+        span: Span::default(),
+    }
+}
+
+/// A call of the value of an expression
+pub fn call_value(callee: Expr, args: Vec<Expr>) -> Expr {
+    Expr::Call {
+        callee: Box::new(callee),
+        args,
+        span: Span::default(),
+    }
+}
+
+/// A named argument: `name = value`
+pub fn named(name: impl Into<String>, value: Expr) -> Expr {
+    Expr::NamedArg {
+        name: name.into(),
+        value: Box::new(value),
+        span: Span::default(),
+    }
+}
+
+/// A binding condition: `name = value`
+pub fn binding(name: impl Into<String>, value: Expr) -> Expr {
+    Expr::Binding {
+        name: name.into(),
+        value: Box::new(value),
+        span: Span::default(),
+    }
+}
+
+/// A field read, as an assignment target sees it
+pub fn field(object: Expr, field: impl Into<String>) -> Expr {
+    Expr::FieldAccess {
+        object: Box::new(object),
+        field: field.into(),
+        span: Span::default(),
+    }
+}
+
+/// A method call: `object.method(args)`
+pub fn method(object: Expr, method: impl Into<String>, args: Vec<Expr>) -> Expr {
+    Expr::MethodCall {
+        object: Box::new(object),
+        method: method.into(),
+        args,
+        span: Span::default(),
+    }
+}
+
+/// A cond expression
+pub fn cond(branches: Vec<(Expr, Vec<Statement>)>, else_branch: Vec<Statement>) -> Expr {
+    Expr::Cond {
+        branches,
+        else_branch,
+        span: Span::default(),
+    }
+}
+
+/// An anonymous function
+pub fn lambda(params: &[&str], body: Vec<Statement>) -> Expr {
+    Expr::Lambda {
+        params: names(params),
+        body,
+        span: Span::default(),
+    }
+}
+
+/// An assignment: `name = value`
+pub fn assign(name: impl Into<String>, value: Expr) -> Statement {
+    Statement::Assignment {
+        name: name.into(),
+        mutable: false,
+        ty: None,
+        value,
+        span: Span::default(),
+    }
+}
+
+/// A `var` declaration: `var name = value`
+pub fn var(name: impl Into<String>, value: Expr) -> Statement {
+    Statement::Assignment {
+        name: name.into(),
+        mutable: true,
+        ty: None,
+        value,
+        span: Span::default(),
+    }
+}
+
+/// A field assignment through an object: `object.field = value`
+pub fn field_assign(object: Expr, field: impl Into<String>, value: Expr) -> Statement {
+    Statement::FieldAssignment {
+        object,
+        field: field.into(),
+        value,
+        span: Span::default(),
+    }
+}
+
+/// An expression statement
+pub fn expr_stmt(expr: Expr) -> Statement {
+    Statement::Expression(expr)
+}
+
+/// `return` or `return value`
+pub fn ret(value: Option<Expr>) -> Statement {
+    Statement::Return {
+        value,
+        span: Span::default(),
+    }
+}
+
+/// A function definition without type annotations
+pub fn function(name: impl Into<String>, params: &[&str], body: Vec<Statement>) -> Statement {
+    Statement::FunctionDef {
+        name: name.into(),
+        params: names(params),
+        param_types: vec![None; params.len()],
+        variadic: false,
+        return_type: None,
+        body,
+        docs: Vec::new(),
+        span: Span::default(),
+    }
+}
+
+/// An instance method definition without type annotations
+pub fn method_def(
+    class_name: impl Into<String>,
+    method_name: impl Into<String>,
+    params: &[&str],
+    body: Vec<Statement>,
+) -> Statement {
+    Statement::MethodDef {
+        class_name: class_name.into(),
+        method_name: method_name.into(),
+        is_static: false,
+        params: names(params),
+        param_types: vec![None; params.len()],
+        variadic: false,
+        return_type: None,
+        body,
+        docs: Vec::new(),
+        span: Span::default(),
+    }
+}
+
+/// A class definition with no parent, traits, mixins, or field declarations
+pub fn class(name: impl Into<String>) -> Statement {
+    Statement::ClassDef {
+        name: name.into(),
+        parent: None,
+        traits: Vec::new(),
+        mixins: Vec::new(),
+        fields: Vec::new(),
+        docs: Vec::new(),
+        span: Span::default(),
+    }
+}
+
+/// An if statement without elif branches
+pub fn if_else(
+    condition: Expr,
+    then_branch: Vec<Statement>,
+    else_branch: Option<Vec<Statement>>,
+) -> Statement {
+    Statement::If {
+        condition,
+        then_branch,
+        elif_branches: Vec::new(),
+        else_branch,
+        span: Span::default(),
+    }
+}
+
+/// A match statement
+pub fn match_on(subject: Expr, arms: Vec<MatchArm>) -> Statement {
+    Statement::Match {
+        subject,
+        arms,
+        span: Span::default(),
+    }
+}
+
+/// A match arm
+pub fn arm(patterns: Vec<Pattern>, body: Vec<Statement>) -> MatchArm {
+    MatchArm { patterns, body }
+}
+
+/// An unlabeled while loop
+pub fn while_loop(condition: Expr, body: Vec<Statement>) -> Statement {
+    Statement::While {
+        condition,
+        body,
+        label: None,
+        span: Span::default(),
+    }
+}
+
+/// A block statement
+pub fn block(body: Vec<Statement>) -> Statement {
+    Statement::Block {
+        body,
+        span: Span::default(),
+    }
+}
+
+fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
@@ -0,0 +1,220 @@
+//! Graphviz rendering of the AST
+//!
+//! `Program::to_dot` draws the tree as a DOT digraph: statements are boxes,
+//! expressions ellipses, variant and `_` patterns diamonds, and children hang below
+//! their parent in source order. Each branch of an `if` or a `cond` gets a node of
+//! its own (`then`, `elif`, `when`, `else`) so that the bodies can be told apart.
+//! Render it with `dot -Tsvg ast.dot`.
+
+use super::visit::{walk_body, walk_expr, walk_match_arm, walk_statement, Visitor};
+use super::{Expr, MatchArm, Pattern, Program, Statement};
+use std::fmt::Write;
+
+/// Returns the DOT source for a program's tree
+pub(super) fn to_dot(program: &Program) -> String {
+    let mut writer = DotWriter {
+        out: String::from(
+            "digraph AST {\n  graph [ordering=out];\n  node [fontname=\"monospace\"];\n",
+        ),
+        nodes: 0,
+        parents: Vec::new(),
+    };
+    writer.enter("program", "box", |writer| {
+        walk_body(writer, &program.statements)
+    });
+    writer.out.push_str("}\n");
+    writer.out
+}
+
+/// Writes a node per visited node, with an edge from the node it is nested in
+struct DotWriter {
+    out: String,
+    /// Nodes written so far, which numbers the next one
+    nodes: usize,
+    /// The nodes enclosing the current one, innermost last
+    parents: Vec<usize>,
+}
+
+impl DotWriter {
+    /// Writes a node and its edge from the enclosing node, then its children
+    fn enter(&mut self, label: &str, shape: &str, children: impl FnOnce(&mut Self)) {
+        let id = self.nodes;
+        self.nodes += 1;
+        writeln!(
+            self.out,
+            "  n{} [label=\"{}\", shape={}];",
+            id,
+            escape(label),
+            shape
+        )
+        .unwrap();
+        if let Some(parent) = self.parents.last() {
+            writeln!(self.out, "  n{} -> n{};", parent, id).unwrap();
+        }
+
+        self.parents.push(id);
+        children(self);
+        self.parents.pop();
+    }
+
+    /// Writes a `then`, `elif`, or `else` node holding a branch's condition and body
+    fn branch(&mut self, label: &str, condition: Option<&Expr>, body: &[Statement]) {
+        self.enter(label, "plain", |writer| {
+            if let Some(condition) = condition {
+                writer.visit_expr(condition);
+            }
+            walk_body(writer, body);
+        });
+    }
+}
+
+impl Visitor for DotWriter {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        match stmt {
+            // An expression statement is drawn as its expression
+            Statement::Expression(expr) => self.visit_expr(expr),
+            Statement::If {
+                condition,
+                then_branch,
+                elif_branches,
+                else_branch,
+                ..
+            } => self.enter("if", "box", |writer| {
+                writer.visit_expr(condition);
+                writer.branch("then", None, then_branch);
+                for (condition, body) in elif_branches {
+                    writer.branch("elif", Some(condition), body);
+                }
+                if let Some(body) = else_branch {
+                    writer.branch("else", None, body);
+                }
+            }),
+            _ => self.enter(&statement_label(stmt), "box", |writer| {
+                walk_statement(writer, stmt)
+            }),
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Cond {
+                branches,
+                else_branch,
+                ..
+            } => self.enter("cond", "ellipse", |writer| {
+                for (condition, body) in branches {
+                    writer.branch("when", Some(condition), body);
+                }
+                writer.branch("else", None, else_branch);
+            }),
+            _ => self.enter(&expr_label(expr), "ellipse", |writer| {
+                walk_expr(writer, expr)
+            }),
+        }
+    }
+
+    fn visit_match_arm(&mut self, arm: &MatchArm) {
+        self.enter("=>", "plain", |writer| walk_match_arm(writer, arm));
+    }
+
+    fn visit_pattern(&mut self, pattern: &Pattern) {
+        match pattern {
+            Pattern::Literal(value) => self.visit_expr(value),
+            _ => self.enter(&pattern.to_string(), "diamond", |_| {}),
+        }
+    }
+}
+
+/// The node label of a statement: its keyword and the names it declares or assigns
+fn statement_label(stmt: &Statement) -> String {
+    let labeled = |label: &Option<String>, keyword: &str| match label {
+        Some(label) => format!("{}: {}", label, keyword),
+        None => keyword.to_string(),
+    };
+
+    match stmt {
+        Statement::FunctionDef { name, params, .. } => {
+            format!("fn {}({})", name, params.join(", "))
+        }
+        Statement::MethodDef {
+            class_name,
+            method_name,
+            is_static,
+            params,
+            ..
+        } => format!(
+            "fn {} {} {}({})",
+            class_name,
+            if *is_static { ">>" } else { ">" },
+            method_name,
+            params.join(", ")
+        ),
+        Statement::ClassDef { name, .. } => format!("class {}", name),
+        Statement::MixinDef { name, .. } => format!("mixin {}", name),
+        Statement::TraitDef { name, .. } => format!("trait {}", name),
+        Statement::EnumDef { name, variants, .. } => {
+            format!("enum {} {{ {} }}", name, variants.join(", "))
+        }
+        Statement::Assignment { name, mutable, .. } => {
+            format!("{}{} =", if *mutable { "var " } else { "" }, name)
+        }
+        Statement::FieldAssignment { field, .. } => format!(".{} =", field),
+        Statement::ConstDef { name, .. } => format!("const {} =", name),
+        Statement::Global { name, .. } => format!("global {} =", name),
+        Statement::Import { module, .. } => format!("import {}", module),
+        Statement::ParallelAssignment { names, .. } => format!("{} =", names.join(", ")),
+        Statement::ArrayDestructuring { names, .. } => format!("[{}] =", names.join(", ")),
+        Statement::Block { .. } => "block".to_string(),
+        Statement::If { .. } => "if".to_string(),
+        Statement::Match { .. } => "match".to_string(),
+        Statement::While { label, .. } => labeled(label, "while"),
+        Statement::Loop { label, .. } => labeled(label, "loop"),
+        Statement::DoWhile { label, .. } => labeled(label, "do while"),
+        Statement::Break { label, .. } => match label {
+            Some(label) => format!("break {}", label),
+            None => "break".to_string(),
+        },
+        Statement::Continue { label, .. } => match label {
+            Some(label) => format!("continue {}", label),
+            None => "continue".to_string(),
+        },
+        Statement::Return { .. } => "return".to_string(),
+        Statement::Test { name, .. } => format!("test '{}'", name),
+        Statement::Expression(_) => "expression".to_string(),
+    }
+}
+
+/// The node label of an expression: a literal's value, a name, or an operator
+fn expr_label(expr: &Expr) -> String {
+    match expr {
+        Expr::Integer { .. }
+        | Expr::Float { .. }
+        | Expr::Bool { .. }
+        | Expr::Nil { .. }
+        | Expr::String { .. }
+        | Expr::Identifier { .. } => expr.to_string(),
+        Expr::BinaryOp { op, .. } => op.to_string(),
+        Expr::UnaryOp { op, .. } => op.to_string(),
+        Expr::Grouped { .. } => "( )".to_string(),
+        Expr::Array { .. } => "[ ]".to_string(),
+        Expr::Tuple { .. } => "tuple".to_string(),
+        Expr::Map { .. } => "{ }".to_string(),
+        Expr::Index { .. } => "index".to_string(),
+        Expr::Slice { .. } => "slice".to_string(),
+        Expr::FunctionCall { name, .. } => format!("{}()", name),
+        Expr::Call { .. } => "call".to_string(),
+        Expr::NamedArg { name, .. } | Expr::Binding { name, .. } => format!("{} =", name),
+        Expr::FieldAccess { field, .. } => format!(".{}", field),
+        Expr::MethodCall { method, .. } => format!(".{}()", method),
+        Expr::Cond { .. } => "cond".to_string(),
+        Expr::Lambda { params, .. } => format!("|{}|", params.join(", ")),
+    }
+}
+
+/// Escapes a label for a quoted DOT string
+fn escape(label: &str) -> String {
+    label
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,222 @@
+//! Evaluation of constant expressions
+//!
+//! `eval` computes the value of an expression built from literals, operators, and
+//! named constants, the way the generated Rust program would: integer arithmetic
+//! that overflows `i64`, integer division by zero, and shifts by a negative amount
+//! or by 64 or more are errors rather than wrapping, while float arithmetic follows
+//! IEEE 754. An integer mixed with a float is widened to a float first.
+
+use super::{BinaryOperator, Expr, UnaryOperator};
+use std::cmp::Ordering;
+
+/// The value of a constant expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Int(i64),
+    Float(f64),
+    String(String),
+    Bool(bool),
+}
+
+impl Value {
+    /// Returns the name of the value's Grit type
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Int(_) => "int",
+            Value::Float(_) => "float",
+            Value::String(_) => "string",
+            Value::Bool(_) => "bool",
+        }
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Int(value) => write!(f, "{}", value),
+            Value::Float(value) => write!(f, "{:?}", value),
+            Value::String(value) => write!(f, "'{}'", value),
+            Value::Bool(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// Why a constant expression has no value
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalError {
+    /// The expression uses something other than literals, operators, and constants,
+    /// such as a variable, a call, or a collection
+    NotConstant,
+    /// Integer arithmetic or a shift leaves the range of `i64`
+    Overflow,
+    /// Integer division by zero
+    DivisionByZero,
+    /// An operator applied to values it does not take: `1 + true`
+    InvalidOperands {
+        op: String,
+        left: &'static str,
+        right: Option<&'static str>,
+    },
+}
+
+impl std::fmt::Display for EvalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EvalError::NotConstant => write!(f, "not a constant expression"),
+            EvalError::Overflow => write!(f, "arithmetic overflow"),
+            EvalError::DivisionByZero => write!(f, "division by zero"),
+            EvalError::InvalidOperands {
+                op,
+                left,
+                right: Some(right),
+            } => write!(f, "cannot apply '{}' to {} and {}", op, left, right),
+            EvalError::InvalidOperands { op, left, .. } => {
+                write!(f, "cannot apply '{}' to {}", op, left)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+/// Evaluates an expression of literals and operators alone
+pub fn eval(expr: &Expr) -> Result<Value, EvalError> {
+    eval_with(expr, &|_| None)
+}
+
+/// Evaluates an expression whose names are the constants `lookup` returns values for
+pub fn eval_with(expr: &Expr, lookup: &dyn Fn(&str) -> Option<Value>) -> Result<Value, EvalError> {
+    match expr {
+        Expr::Integer { value, .. } => Ok(Value::Int(*value)),
+        Expr::Float { value, .. } => Ok(Value::Float(*value)),
+        Expr::String { value, .. } => Ok(Value::String(value.clone())),
+        Expr::Bool { value, .. } => Ok(Value::Bool(*value)),
+        Expr::Identifier { name, .. } => lookup(name).ok_or(EvalError::NotConstant),
+        Expr::Grouped { inner, .. } => eval_with(inner, lookup),
+        Expr::UnaryOp { op, operand, .. } => eval_unary(op, eval_with(operand, lookup)?),
+        // `&&` and `||` skip their right operand, as at run time
+        Expr::BinaryOp {
+            left,
+            op: op @ (BinaryOperator::And | BinaryOperator::Or),
+            right,
+            ..
+        } => match (eval_with(left, lookup)?, op) {
+            (Value::Bool(false), BinaryOperator::And) => Ok(Value::Bool(false)),
+            (Value::Bool(true), BinaryOperator::Or) => Ok(Value::Bool(true)),
+            (Value::Bool(_), _) => match eval_with(right, lookup)? {
+                right @ Value::Bool(_) => Ok(right),
+                right => Err(invalid(op, &Value::Bool(true), Some(&right))),
+            },
+            (left, _) => Err(invalid(op, &left, None)),
+        },
+        Expr::BinaryOp {
+            left, op, right, ..
+        } => eval_binary(op, eval_with(left, lookup)?, eval_with(right, lookup)?),
+        _ => Err(EvalError::NotConstant),
+    }
+}
+
+fn invalid(op: &impl std::fmt::Display, left: &Value, right: Option<&Value>) -> EvalError {
+    EvalError::InvalidOperands {
+        op: op.to_string(),
+        left: left.type_name(),
+        right: right.map(Value::type_name),
+    }
+}
+
+fn eval_unary(op: &UnaryOperator, operand: Value) -> Result<Value, EvalError> {
+    match (op, operand) {
+        (UnaryOperator::Not, Value::Bool(value)) => Ok(Value::Bool(!value)),
+        (UnaryOperator::Negate, Value::Int(value)) => value
+            .checked_neg()
+            .map(Value::Int)
+            .ok_or(EvalError::Overflow),
+        (UnaryOperator::Negate, Value::Float(value)) => Ok(Value::Float(-value)),
+        (UnaryOperator::Plus, value @ (Value::Int(_) | Value::Float(_))) => Ok(value),
+        (op, operand) => Err(invalid(op, &operand, None)),
+    }
+}
+
+fn eval_binary(op: &BinaryOperator, left: Value, right: Value) -> Result<Value, EvalError> {
+    use BinaryOperator::*;
+
+    match (op, &left, &right) {
+        (Add, Value::String(a), Value::String(b)) => Ok(Value::String(format!("{}{}", a, b))),
+        (Add | Subtract | Multiply | Divide, Value::Int(a), Value::Int(b)) => {
+            let result = match op {
+                Add => a.checked_add(*b),
+                Subtract => a.checked_sub(*b),
+                Multiply => a.checked_mul(*b),
+                _ if *b == 0 => return Err(EvalError::DivisionByZero),
+                _ => a.checked_div(*b),
+            };
+            result.map(Value::Int).ok_or(EvalError::Overflow)
+        }
+        (Add | Subtract | Multiply | Divide, _, _) => {
+            let (Some(a), Some(b)) = (as_float(&left), as_float(&right)) else {
+                return Err(invalid(op, &left, Some(&right)));
+            };
+            Ok(Value::Float(match op {
+                Add => a + b,
+                Subtract => a - b,
+                Multiply => a * b,
+                _ => a / b,
+            }))
+        }
+        (
+            EqualEqual | NotEqual | LessThan | LessThanOrEqual | GreaterThan | GreaterThanOrEqual,
+            ..,
+        ) => {
+            let ordering =
+                compare(&left, &right).ok_or_else(|| invalid(op, &left, Some(&right)))?;
+            Ok(Value::Bool(match op {
+                EqualEqual => ordering == Some(Ordering::Equal),
+                NotEqual => ordering != Some(Ordering::Equal),
+                LessThan => ordering == Some(Ordering::Less),
+                LessThanOrEqual => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+                GreaterThan => ordering == Some(Ordering::Greater),
+                _ => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+            }))
+        }
+        (BitAnd | BitOr | BitXor, Value::Bool(a), Value::Bool(b)) => Ok(Value::Bool(match op {
+            BitAnd => a & b,
+            BitOr => a | b,
+            _ => a ^ b,
+        })),
+        (BitAnd | BitOr | BitXor, Value::Int(a), Value::Int(b)) => Ok(Value::Int(match op {
+            BitAnd => a & b,
+            BitOr => a | b,
+            _ => a ^ b,
+        })),
+        (ShiftLeft | ShiftRight, Value::Int(a), Value::Int(b)) => {
+            let amount = u32::try_from(*b).map_err(|_| EvalError::Overflow)?;
+            let result = match op {
+                ShiftLeft => a.checked_shl(amount),
+                _ => a.checked_shr(amount),
+            };
+            result.map(Value::Int).ok_or(EvalError::Overflow)
+        }
+        // Membership needs a collection, which is never constant
+        (In, ..) => Err(EvalError::NotConstant),
+        _ => Err(invalid(op, &left, Some(&right))),
+    }
+}
+
+/// Returns a number as a float, widening an integer
+fn as_float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Int(value) => Some(*value as f64),
+        Value::Float(value) => Some(*value),
+        _ => None,
+    }
+}
+
+/// Orders two values of comparable types; the inner `None` is a comparison with NaN
+fn compare(left: &Value, right: &Value) -> Option<Option<Ordering>> {
+    match (left, right) {
+        (Value::Int(a), Value::Int(b)) => Some(Some(a.cmp(b))),
+        (Value::String(a), Value::String(b)) => Some(Some(a.cmp(b))),
+        (Value::Bool(a), Value::Bool(b)) => Some(Some(a.cmp(b))),
+        _ => Some(as_float(left)?.partial_cmp(&as_float(right)?)),
+    }
+}
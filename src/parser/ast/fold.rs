@@ -0,0 +1,473 @@
+//! Rewriting of the AST
+//!
+//! A pass implements `Fold` and overrides only the methods for the nodes it rewrites.
+//! Each method takes a node by value and returns its replacement; the default calls
+//! the matching `walk_*` function, which folds the node's children in source order
+//! and rebuilds it around them. An override usually folds the children first (by
+//! calling the `walk_*` function) and then rewrites the result, so a desugaring
+//! written once applies at every depth.
+//!
+//! `fold_body` may return more or fewer statements than it was given, and
+//! `fold_span` sees the span of every node.
+
+use super::{Expr, FieldDecl, MatchArm, Pattern, Program, Statement, TraitMethod};
+use crate::lexer::Span;
+
+/// Rewriting of a program into a new one
+pub trait Fold {
+    fn fold_program(&mut self, program: Program) -> Program {
+        walk_program(self, program)
+    }
+
+    fn fold_body(&mut self, body: Vec<Statement>) -> Vec<Statement> {
+        walk_body(self, body)
+    }
+
+    fn fold_statement(&mut self, stmt: Statement) -> Statement {
+        walk_statement(self, stmt)
+    }
+
+    fn fold_expr(&mut self, expr: Expr) -> Expr {
+        walk_expr(self, expr)
+    }
+
+    fn fold_match_arm(&mut self, arm: MatchArm) -> MatchArm {
+        walk_match_arm(self, arm)
+    }
+
+    fn fold_pattern(&mut self, pattern: Pattern) -> Pattern {
+        walk_pattern(self, pattern)
+    }
+
+    fn fold_span(&mut self, span: Span) -> Span {
+        span
+    }
+}
+
+/// Folds the top-level statements of a program
+pub fn walk_program<F: Fold + ?Sized>(folder: &mut F, program: Program) -> Program {
+    Program {
+        statements: folder.fold_body(program.statements),
+    }
+}
+
+/// Folds each statement of a body, in order
+pub fn walk_body<F: Fold + ?Sized>(folder: &mut F, body: Vec<Statement>) -> Vec<Statement> {
+    body.into_iter()
+        .map(|stmt| folder.fold_statement(stmt))
+        .collect()
+}
+
+/// Folds the expressions, nested bodies, and spans of a statement
+pub fn walk_statement<F: Fold + ?Sized>(folder: &mut F, stmt: Statement) -> Statement {
+    match stmt {
+        Statement::FunctionDef {
+            name,
+            params,
+            param_types,
+            variadic,
+            return_type,
+            body,
+            docs,
+            span,
+        } => Statement::FunctionDef {
+            name,
+            params,
+            param_types,
+            variadic,
+            return_type,
+            body: folder.fold_body(body),
+            docs,
+            span: folder.fold_span(span),
+        },
+        Statement::ClassDef {
+            name,
+            parent,
+            traits,
+            mixins,
+            fields,
+            docs,
+            span,
+        } => Statement::ClassDef {
+            name,
+            parent,
+            traits,
+            mixins,
+            fields: fields
+                .into_iter()
+                .map(|field| FieldDecl {
+                    span: folder.fold_span(field.span),
+                    ..field
+                })
+                .collect(),
+            docs,
+            span: folder.fold_span(span),
+        },
+        Statement::MixinDef { name, span } => Statement::MixinDef {
+            name,
+            span: folder.fold_span(span),
+        },
+        Statement::TraitDef {
+            name,
+            methods,
+            span,
+        } => Statement::TraitDef {
+            name,
+            methods: methods
+                .into_iter()
+                .map(|method| TraitMethod {
+                    span: folder.fold_span(method.span),
+                    ..method
+                })
+                .collect(),
+            span: folder.fold_span(span),
+        },
+        Statement::EnumDef {
+            name,
+            variants,
+            span,
+        } => Statement::EnumDef {
+            name,
+            variants,
+            span: folder.fold_span(span),
+        },
+        Statement::MethodDef {
+            class_name,
+            method_name,
+            is_static,
+            params,
+            param_types,
+            variadic,
+            return_type,
+            body,
+            docs,
+            span,
+        } => Statement::MethodDef {
+            class_name,
+            method_name,
+            is_static,
+            params,
+            param_types,
+            variadic,
+            return_type,
+            body: folder.fold_body(body),
+            docs,
+            span: folder.fold_span(span),
+        },
+        Statement::Assignment {
+            name,
+            mutable,
+            ty,
+            value,
+            span,
+        } => Statement::Assignment {
+            name,
+            mutable,
+            ty,
+            value: folder.fold_expr(value),
+            span: folder.fold_span(span),
+        },
+        Statement::FieldAssignment {
+            object,
+            field,
+            value,
+            span,
+        } => Statement::FieldAssignment {
+            object: folder.fold_expr(object),
+            field,
+            value: folder.fold_expr(value),
+            span: folder.fold_span(span),
+        },
+        Statement::ConstDef { name, value, span } => Statement::ConstDef {
+            name,
+            value: folder.fold_expr(value),
+            span: folder.fold_span(span),
+        },
+        Statement::Global { name, value, span } => Statement::Global {
+            name,
+            value: folder.fold_expr(value),
+            span: folder.fold_span(span),
+        },
+        Statement::Import {
+            module,
+            names,
+            span,
+        } => Statement::Import {
+            module,
+            names,
+            span: folder.fold_span(span),
+        },
+        Statement::ParallelAssignment {
+            names,
+            values,
+            span,
+        } => Statement::ParallelAssignment {
+            names,
+            values: fold_all(folder, values),
+            span: folder.fold_span(span),
+        },
+        Statement::ArrayDestructuring { names, value, span } => Statement::ArrayDestructuring {
+            names,
+            value: folder.fold_expr(value),
+            span: folder.fold_span(span),
+        },
+        Statement::Block { body, span } => Statement::Block {
+            body: folder.fold_body(body),
+            span: folder.fold_span(span),
+        },
+        Statement::If {
+            condition,
+            then_branch,
+            elif_branches,
+            else_branch,
+            span,
+        } => Statement::If {
+            condition: folder.fold_expr(condition),
+            then_branch: folder.fold_body(then_branch),
+            elif_branches: elif_branches
+                .into_iter()
+                .map(|(condition, branch)| (folder.fold_expr(condition), folder.fold_body(branch)))
+                .collect(),
+            else_branch: else_branch.map(|branch| folder.fold_body(branch)),
+            span: folder.fold_span(span),
+        },
+        Statement::Match {
+            subject,
+            arms,
+            span,
+        } => Statement::Match {
+            subject: folder.fold_expr(subject),
+            arms: arms
+                .into_iter()
+                .map(|arm| folder.fold_match_arm(arm))
+                .collect(),
+            span: folder.fold_span(span),
+        },
+        Statement::While {
+            condition,
+            body,
+            label,
+            span,
+        } => Statement::While {
+            condition: folder.fold_expr(condition),
+            body: folder.fold_body(body),
+            label,
+            span: folder.fold_span(span),
+        },
+        Statement::Loop { body, label, span } => Statement::Loop {
+            body: folder.fold_body(body),
+            label,
+            span: folder.fold_span(span),
+        },
+        Statement::DoWhile {
+            body,
+            condition,
+            label,
+            span,
+        } => Statement::DoWhile {
+            body: folder.fold_body(body),
+            condition: folder.fold_expr(condition),
+            label,
+            span: folder.fold_span(span),
+        },
+        Statement::Break { label, span } => Statement::Break {
+            label,
+            span: folder.fold_span(span),
+        },
+        Statement::Continue { label, span } => Statement::Continue {
+            label,
+            span: folder.fold_span(span),
+        },
+        Statement::Return { value, span } => Statement::Return {
+            value: value.map(|value| folder.fold_expr(value)),
+            span: folder.fold_span(span),
+        },
+        Statement::Test { name, body, span } => Statement::Test {
+            name,
+            body: folder.fold_body(body),
+            span: folder.fold_span(span),
+        },
+        Statement::Expression(expr) => Statement::Expression(folder.fold_expr(expr)),
+    }
+}
+
+/// Folds the subexpressions, nested bodies, and span of an expression
+pub fn walk_expr<F: Fold + ?Sized>(folder: &mut F, expr: Expr) -> Expr {
+    match expr {
+        Expr::Integer { value, span } => Expr::Integer {
+            value,
+            span: folder.fold_span(span),
+        },
+        Expr::Float { value, span } => Expr::Float {
+            value,
+            span: folder.fold_span(span),
+        },
+        Expr::Bool { value, span } => Expr::Bool {
+            value,
+            span: folder.fold_span(span),
+        },
+        Expr::Nil { span } => Expr::Nil {
+            span: folder.fold_span(span),
+        },
+        Expr::String { value, span } => Expr::String {
+            value,
+            span: folder.fold_span(span),
+        },
+        Expr::Identifier { name, span } => Expr::Identifier {
+            name,
+            span: folder.fold_span(span),
+        },
+        Expr::BinaryOp {
+            left,
+            op,
+            right,
+            span,
+        } => Expr::BinaryOp {
+            left: fold_boxed(folder, *left),
+            op,
+            right: fold_boxed(folder, *right),
+            span: folder.fold_span(span),
+        },
+        Expr::UnaryOp { op, operand, span } => Expr::UnaryOp {
+            op,
+            operand: fold_boxed(folder, *operand),
+            span: folder.fold_span(span),
+        },
+        Expr::Grouped { inner, span } => Expr::Grouped {
+            inner: fold_boxed(folder, *inner),
+            span: folder.fold_span(span),
+        },
+        Expr::Array { elements, span } => Expr::Array {
+            elements: fold_all(folder, elements),
+            span: folder.fold_span(span),
+        },
+        Expr::Tuple { elements, span } => Expr::Tuple {
+            elements: fold_all(folder, elements),
+            span: folder.fold_span(span),
+        },
+        Expr::Map { entries, span } => Expr::Map {
+            entries: entries
+                .into_iter()
+                .map(|(key, value)| (folder.fold_expr(key), folder.fold_expr(value)))
+                .collect(),
+            span: folder.fold_span(span),
+        },
+        Expr::Index {
+            object,
+            index,
+            span,
+        } => Expr::Index {
+            object: fold_boxed(folder, *object),
+            index: fold_boxed(folder, *index),
+            span: folder.fold_span(span),
+        },
+        Expr::Slice {
+            object,
+            start,
+            end,
+            span,
+        } => Expr::Slice {
+            object: fold_boxed(folder, *object),
+            start: start.map(|start| fold_boxed(folder, *start)),
+            end: end.map(|end| fold_boxed(folder, *end)),
+            span: folder.fold_span(span),
+        },
+        Expr::FunctionCall { name, args, span } => Expr::FunctionCall {
+            name,
+            args: fold_all(folder, args),
+            span: folder.fold_span(span),
+        },
+        Expr::Call { callee, args, span } => Expr::Call {
+            callee: fold_boxed(folder, *callee),
+            args: fold_all(folder, args),
+            span: folder.fold_span(span),
+        },
+        Expr::NamedArg { name, value, span } => Expr::NamedArg {
+            name,
+            value: fold_boxed(folder, *value),
+            span: folder.fold_span(span),
+        },
+        Expr::Binding { name, value, span } => Expr::Binding {
+            name,
+            value: fold_boxed(folder, *value),
+            span: folder.fold_span(span),
+        },
+        Expr::FieldAccess {
+            object,
+            field,
+            span,
+        } => Expr::FieldAccess {
+            object: fold_boxed(folder, *object),
+            field,
+            span: folder.fold_span(span),
+        },
+        Expr::MethodCall {
+            object,
+            method,
+            args,
+            span,
+        } => Expr::MethodCall {
+            object: fold_boxed(folder, *object),
+            method,
+            args: fold_all(folder, args),
+            span: folder.fold_span(span),
+        },
+        Expr::Cond {
+            branches,
+            else_branch,
+            span,
+        } => Expr::Cond {
+            branches: branches
+                .into_iter()
+                .map(|(condition, body)| (folder.fold_expr(condition), folder.fold_body(body)))
+                .collect(),
+            else_branch: folder.fold_body(else_branch),
+            span: folder.fold_span(span),
+        },
+        Expr::Lambda { params, body, span } => Expr::Lambda {
+            params,
+            body: folder.fold_body(body),
+            span: folder.fold_span(span),
+        },
+    }
+}
+
+/// Folds the patterns of a match arm, then its body
+pub fn walk_match_arm<F: Fold + ?Sized>(folder: &mut F, arm: MatchArm) -> MatchArm {
+    MatchArm {
+        patterns: arm
+            .patterns
+            .into_iter()
+            .map(|pattern| folder.fold_pattern(pattern))
+            .collect(),
+        body: folder.fold_body(arm.body),
+    }
+}
+
+/// Folds the literal of a pattern
+pub fn walk_pattern<F: Fold + ?Sized>(folder: &mut F, pattern: Pattern) -> Pattern {
+    match pattern {
+        Pattern::Literal(value) => Pattern::Literal(folder.fold_expr(value)),
+        pattern => pattern,
+    }
+}
+
+fn fold_boxed<F: Fold + ?Sized>(folder: &mut F, expr: Expr) -> Box<Expr> {
+    Box::new(folder.fold_expr(expr))
+}
+
+fn fold_all<F: Fold + ?Sized>(folder: &mut F, exprs: Vec<Expr>) -> Vec<Expr> {
+    exprs
+        .into_iter()
+        .map(|expr| folder.fold_expr(expr))
+        .collect()
+}
+
+/// Resets every span to `Span::default()`
+pub(super) struct ClearSpans;
+
+impl Fold for ClearSpans {
+    fn fold_span(&mut self, _: Span) -> Span {
+        Span::default()
+    }
+}
@@ -0,0 +1,129 @@
+//! Lookup of a program's top-level definitions
+//!
+//! `Program::index` walks the top-level statements once and keeps references to the
+//! definitions by name, so passes that need "the methods of this class" or "the
+//! declaration of that trait" look them up instead of each scanning the program.
+//! Everything is kept in source order.
+
+use super::{FieldDecl, Program, Statement, TraitMethod};
+use std::collections::HashMap;
+
+/// The top-level definitions of a program, by name
+#[derive(Debug, Clone, Default)]
+pub struct ProgramIndex<'p> {
+    functions: Vec<&'p Statement>,
+    function_names: HashMap<&'p str, &'p Statement>,
+    classes: Vec<&'p str>,
+    class_defs: HashMap<&'p str, &'p Statement>,
+    methods: HashMap<&'p str, Vec<&'p Statement>>,
+    mixins: Vec<&'p str>,
+    traits: HashMap<&'p str, &'p Statement>,
+    enums: Vec<&'p str>,
+}
+
+impl<'p> ProgramIndex<'p> {
+    /// Indexes the top-level statements of a program
+    pub fn new(program: &'p Program) -> Self {
+        let mut index = ProgramIndex::default();
+
+        for stmt in &program.statements {
+            match stmt {
+                Statement::MixinDef { name, .. } => index.mixins.push(name),
+                Statement::TraitDef { name, .. } => {
+                    index.traits.insert(name, stmt);
+                }
+                Statement::EnumDef { name, .. } => index.enums.push(name),
+                _ => {}
+            }
+        }
+
+        for stmt in &program.statements {
+            match stmt {
+                Statement::FunctionDef { name, .. } => {
+                    index.functions.push(stmt);
+                    index.function_names.entry(name).or_insert(stmt);
+                }
+                Statement::ClassDef { name, .. } => {
+                    index.add_class(name);
+                    index.class_defs.entry(name).or_insert(stmt);
+                }
+                Statement::MethodDef { class_name, .. } => {
+                    if !index.is_mixin(class_name)
+                        && !index.traits.contains_key(class_name.as_str())
+                        && !index.enums.contains(&class_name.as_str())
+                    {
+                        index.add_class(class_name);
+                    }
+                    index.methods.entry(class_name).or_default().push(stmt);
+                }
+                _ => {}
+            }
+        }
+
+        index
+    }
+
+    fn add_class(&mut self, name: &'p str) {
+        if !self.classes.contains(&name) {
+            self.classes.push(name);
+        }
+    }
+
+    /// Returns the top-level function definitions, in order
+    pub fn functions(&self) -> &[&'p Statement] {
+        &self.functions
+    }
+
+    /// Returns the first top-level definition of a function
+    pub fn function(&self, name: &str) -> Option<&'p Statement> {
+        self.function_names.get(name).copied()
+    }
+
+    /// Returns the names of the classes, declared with `class` or implied by a
+    /// method definition, in order of first appearance
+    pub fn classes(&self) -> &[&'p str] {
+        &self.classes
+    }
+
+    /// Returns the `class` declaration of a class, if it has one
+    pub fn class_def(&self, name: &str) -> Option<&'p Statement> {
+        self.class_defs.get(name).copied()
+    }
+
+    /// Returns the fields declared in a class's body; empty without a body
+    pub fn declared_fields(&self, class_name: &str) -> &'p [FieldDecl] {
+        match self.class_def(class_name) {
+            Some(Statement::ClassDef { fields, .. }) => fields,
+            _ => &[],
+        }
+    }
+
+    /// Returns the lines of a class's doc comment; empty without one
+    pub fn class_docs(&self, class_name: &str) -> &'p [String] {
+        match self.class_def(class_name) {
+            Some(Statement::ClassDef { docs, .. }) => docs,
+            _ => &[],
+        }
+    }
+
+    /// Returns the method definitions of a class, mixin, or other type name, in order
+    ///
+    /// Only the methods defined on the name itself; inherited and mixed-in methods
+    /// are not included.
+    pub fn methods_of(&self, class_name: &str) -> &[&'p Statement] {
+        self.methods.get(class_name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Checks if a name is declared with `mixin`
+    pub fn is_mixin(&self, name: &str) -> bool {
+        self.mixins.contains(&name)
+    }
+
+    /// Returns the method signatures a trait declares
+    pub fn trait_methods(&self, name: &str) -> Option<&'p [TraitMethod]> {
+        match self.traits.get(name) {
+            Some(Statement::TraitDef { methods, .. }) => Some(methods),
+            _ => None,
+        }
+    }
+}
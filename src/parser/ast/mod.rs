@@ -0,0 +1,1121 @@
+pub mod build;
+mod dot;
+pub mod eval;
+pub mod fold;
+pub mod index;
+mod render;
+mod structural;
+pub mod visit;
+
+use super::precedence::{operator_info, Associativity};
+use crate::lexer::{Span, Symbol};
+
+use fold::ClearSpans;
+use structural::Normalize;
+
+pub use fold::Fold;
+pub use index::ProgramIndex;
+pub use visit::{Visitor, VisitorMut};
+
+/// Statement in the program
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    /// Function definition: fn name(params) { body }
+    ///
+    /// The span covers the header from `fn` to the function name.
+    FunctionDef {
+        name: String,
+        params: Vec<String>,
+        /// The annotated type of each parameter: `fn add(a: int, b: int)`
+        param_types: Vec<Option<TypeAnnotation>>,
+        /// Set when the last parameter collects any extra arguments: `fn sum(nums...)`
+        variadic: bool,
+        /// The annotated return type: `fn add(a, b) -> int`
+        return_type: Option<TypeAnnotation>,
+        body: Vec<Statement>,
+        /// The lines of the `##` doc comment above the definition
+        docs: Vec<String>,
+        span: Span,
+    },
+
+    /// Class definition: class Name, class Name < Parent, class Name : Trait, ..., or
+    /// class Name with Mixin, ...
+    ///
+    /// A body declares the class's fields: `class Point { x: float, y: float }`.
+    /// Without one, the fields are the `self.field` assignments in its methods.
+    ClassDef {
+        name: String,
+        /// The class inherited from: `class Dog < Animal`
+        parent: Option<String>,
+        /// The traits the class implements: `class Circle : Shape, Named`
+        traits: Vec<String>,
+        mixins: Vec<String>,
+        fields: Vec<FieldDecl>,
+        /// The lines of the `##` doc comment above the definition
+        docs: Vec<String>,
+        span: Span,
+    },
+
+    /// Mixin definition: mixin Name
+    ///
+    /// Methods defined with `fn Name > method` are copied into every class that
+    /// includes the mixin.
+    MixinDef { name: String, span: Span },
+
+    /// Trait definition: trait Name { fn method(params) -> type }
+    ///
+    /// The body lists the signatures of the methods every implementing class must
+    /// define. The span covers `trait Name`.
+    TraitDef {
+        name: String,
+        methods: Vec<TraitMethod>,
+        span: Span,
+    },
+
+    /// Enum definition: enum Name { Variant, ... }
+    ///
+    /// A variant is written `Name.Variant`. The span covers `enum Name`.
+    EnumDef {
+        name: String,
+        variants: Vec<String>,
+        span: Span,
+    },
+
+    /// Method definition: fn ClassName > methodName(params) { body }
+    ///
+    /// A static method is defined with `>>` (`fn Point >> origin()`): it belongs to
+    /// the class rather than an instance, takes no `self`, and is called as
+    /// `Point.origin()`. The span covers the header from `fn` to the method name.
+    MethodDef {
+        class_name: String,
+        method_name: String,
+        /// Set for class-level methods, defined with `>>`
+        is_static: bool,
+        params: Vec<String>,
+        /// The annotated type of each parameter
+        param_types: Vec<Option<TypeAnnotation>>,
+        /// Set when the last parameter collects any extra arguments
+        variadic: bool,
+        return_type: Option<TypeAnnotation>,
+        body: Vec<Statement>,
+        /// The lines of the `##` doc comment above the definition
+        docs: Vec<String>,
+        span: Span,
+    },
+
+    /// Variable assignment: identifier = expression
+    ///
+    /// Field assignments are named `self.field`; the span covers the target, with
+    /// its `var`.
+    Assignment {
+        name: String,
+        /// Declared with `var`, so later assignments may change it: `var x = 0`
+        mutable: bool,
+        /// The annotated type of the variable: `x: float = 1.0`
+        ty: Option<TypeAnnotation>,
+        value: Expr,
+        span: Span,
+    },
+
+    /// Field assignment through an object: `p.x = 5`, `shapes[0].width = 2`
+    ///
+    /// Assignments to `self.field` are `Assignment`s instead. The span covers the
+    /// target.
+    FieldAssignment {
+        object: Expr,
+        field: String,
+        value: Expr,
+        span: Span,
+    },
+
+    /// Constant declaration: const NAME = expression
+    ///
+    /// Generated as a module-level Rust `const`. The span covers `const` and the name.
+    ConstDef {
+        name: String,
+        value: Expr,
+        span: Span,
+    },
+
+    /// Global variable declaration: global name = expression
+    ///
+    /// Declares a variable that main and every function share, so a function can
+    /// read and assign it. The span covers `global` and the name.
+    Global {
+        name: String,
+        value: Expr,
+        span: Span,
+    },
+
+    /// Module import: import math, or import math { sqrt, pow } for selected names
+    ///
+    /// Modules are not resolved yet. The span covers `import` and the module name.
+    Import {
+        module: String,
+        /// The names listed in braces; empty when the whole module is imported
+        names: Vec<String>,
+        span: Span,
+    },
+
+    /// Parallel assignment: a, b = b, a
+    ///
+    /// Every value is evaluated before any name is bound, so swaps need no temporary.
+    /// A single value destructures a tuple instead: `x, y = point()`. The span
+    /// covers the names.
+    ParallelAssignment {
+        names: Vec<String>,
+        values: Vec<Expr>,
+        span: Span,
+    },
+
+    /// Array destructuring: [a, b, c] = values
+    ///
+    /// The array must have exactly one element per name; `_` skips an element. The
+    /// span covers the names.
+    ArrayDestructuring {
+        names: Vec<String>,
+        value: Expr,
+        span: Span,
+    },
+
+    /// Block statement: { body }, a nested scope for the variables assigned in it
+    ///
+    /// The span covers the opening brace.
+    Block { body: Vec<Statement>, span: Span },
+
+    /// If statement with optional elif and else branches
+    ///
+    /// The span covers `if` and the condition.
+    If {
+        condition: Expr,
+        then_branch: Vec<Statement>,
+        elif_branches: Vec<(Expr, Vec<Statement>)>,
+        else_branch: Option<Vec<Statement>>,
+        span: Span,
+    },
+
+    /// Match statement: match subject { pattern => body ... }
+    ///
+    /// The first arm with a matching pattern runs. The span covers `match` and the
+    /// subject.
+    Match {
+        subject: Expr,
+        arms: Vec<MatchArm>,
+        span: Span,
+    },
+
+    /// While loop, optionally labeled: `outer: while condition { body }`
+    ///
+    /// The span covers the label, `while`, and the condition.
+    While {
+        condition: Expr,
+        body: Vec<Statement>,
+        label: Option<String>,
+        span: Span,
+    },
+
+    /// Infinite loop, optionally labeled: `outer: loop { body }`
+    ///
+    /// Only `break` (or a `return`) leaves it. The span covers the label and `loop`.
+    Loop {
+        body: Vec<Statement>,
+        label: Option<String>,
+        span: Span,
+    },
+
+    /// Post-condition loop, optionally labeled: `do { body } while condition`
+    ///
+    /// The body runs once before the condition is first checked. The span covers the
+    /// whole loop.
+    DoWhile {
+        body: Vec<Statement>,
+        condition: Expr,
+        label: Option<String>,
+        span: Span,
+    },
+
+    /// Loop exit: `break` or `break label`
+    Break { label: Option<String>, span: Span },
+
+    /// Skip to the next iteration: `continue` or `continue label`
+    Continue { label: Option<String>, span: Span },
+
+    /// Early exit from the enclosing function: `return` or `return expression`
+    Return { value: Option<Expr>, span: Span },
+
+    /// Test block: test 'name' { body }
+    ///
+    /// Run by `grit test`; ordinary code generation leaves test blocks out.
+    Test {
+        name: String,
+        body: Vec<Statement>,
+        span: Span,
+    },
+
+    /// Expression statement, spanning its expression
+    Expression(Expr),
+}
+
+impl Statement {
+    /// Returns the span of source the statement was parsed from
+    ///
+    /// Definitions and loops span their header rather than their whole body.
+    pub fn span(&self) -> Span {
+        match self {
+            Statement::FunctionDef { span, .. }
+            | Statement::ClassDef { span, .. }
+            | Statement::MixinDef { span, .. }
+            | Statement::TraitDef { span, .. }
+            | Statement::EnumDef { span, .. }
+            | Statement::MethodDef { span, .. }
+            | Statement::Assignment { span, .. }
+            | Statement::FieldAssignment { span, .. }
+            | Statement::ConstDef { span, .. }
+            | Statement::Global { span, .. }
+            | Statement::Import { span, .. }
+            | Statement::ParallelAssignment { span, .. }
+            | Statement::ArrayDestructuring { span, .. }
+            | Statement::Block { span, .. }
+            | Statement::If { span, .. }
+            | Statement::Match { span, .. }
+            | Statement::While { span, .. }
+            | Statement::Loop { span, .. }
+            | Statement::DoWhile { span, .. }
+            | Statement::Break { span, .. }
+            | Statement::Continue { span, .. }
+            | Statement::Return { span, .. }
+            | Statement::Test { span, .. } => *span,
+            Statement::Expression(expr) => expr.span(),
+        }
+    }
+
+    /// Returns a copy of the statement with every span reset to `Span::default()`, for
+    /// comparing statements by structure alone
+    pub fn without_spans(&self) -> Statement {
+        ClearSpans.fold_statement(self.clone())
+    }
+
+    /// Checks if two statements are the same apart from their spans
+    pub fn structurally_eq(&self, other: &Statement) -> bool {
+        self.without_spans() == other.without_spans()
+    }
+
+    /// Returns a hash of the statement that ignores spans, stable across runs; equal
+    /// for statements that are `structurally_eq`
+    pub fn structural_hash(&self) -> u64 {
+        structural::hash(&Normalize.fold_statement(self.clone()))
+    }
+}
+
+/// Abstract Syntax Tree node for expressions
+///
+/// Every expression carries the span of the source it was parsed from, from its
+/// first token to its last. Expressions built by hand use `Span::default()`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// Integer literal
+    Integer { value: i64, span: Span },
+
+    /// Float literal
+    Float { value: f64, span: Span },
+
+    /// Boolean literal: true or false
+    Bool { value: bool, span: Span },
+
+    /// The absence of a value: nil
+    Nil { span: Span },
+
+    /// String literal
+    String { value: String, span: Span },
+
+    /// Variable reference
+    Identifier { name: Symbol, span: Span },
+
+    /// Binary operation (left operand, operator, right operand)
+    BinaryOp {
+        left: Box<Expr>,
+        op: BinaryOperator,
+        right: Box<Expr>,
+        span: Span,
+    },
+
+    /// Unary operation (operator, operand)
+    UnaryOp {
+        op: UnaryOperator,
+        operand: Box<Expr>,
+        span: Span,
+    },
+
+    /// Grouped expression (parentheses)
+    Grouped { inner: Box<Expr>, span: Span },
+
+    /// Array literal: [1, 2, 3]
+    Array { elements: Vec<Expr>, span: Span },
+
+    /// Tuple literal: (a, b)
+    ///
+    /// Always has at least two elements; `(a)` is a grouped expression.
+    Tuple { elements: Vec<Expr>, span: Span },
+
+    /// Map literal: {'a': 1, 'b': 2}
+    Map {
+        entries: Vec<(Expr, Expr)>,
+        span: Span,
+    },
+
+    /// Indexing: collection[index]
+    Index {
+        object: Box<Expr>,
+        index: Box<Expr>,
+        span: Span,
+    },
+
+    /// Slicing: collection[start..end], where either bound may be left out
+    ///
+    /// The end is exclusive, and bounds past the end of the string or array are
+    /// clamped to it.
+    Slice {
+        object: Box<Expr>,
+        start: Option<Box<Expr>>,
+        end: Option<Box<Expr>>,
+        span: Span,
+    },
+
+    /// Function call: function_name(arg1, arg2, ...)
+    ///
+    /// The span runs from the function name to the closing parenthesis.
+    FunctionCall {
+        name: String,
+        args: Vec<Expr>,
+        span: Span,
+    },
+
+    /// Call of the value of any other expression: (make_adder(2))(5), handlers[0](1)
+    ///
+    /// A call of a name is a `FunctionCall`. The span runs from the start of the
+    /// callee to the closing parenthesis.
+    Call {
+        callee: Box<Expr>,
+        args: Vec<Expr>,
+        span: Span,
+    },
+
+    /// Named argument in a call: `rect(width = 3, height = 4)`
+    ///
+    /// Only appears among a function call's arguments; code generation passes it in
+    /// the position of the parameter it names.
+    NamedArg {
+        name: String,
+        value: Box<Expr>,
+        span: Span,
+    },
+
+    /// Binding condition of an if, elif, or while: `if n = parse(s) { ... }`
+    ///
+    /// Binds `name` to the value in the branch or loop body it guards, which runs
+    /// unless the value is `false` or `nil`. Only parsed in condition position.
+    Binding {
+        name: String,
+        value: Box<Expr>,
+        span: Span,
+    },
+
+    /// Field access: object.field or self.field
+    FieldAccess {
+        object: Box<Expr>,
+        field: String,
+        span: Span,
+    },
+
+    /// Method call: object.method(args) or ClassName.method(args)
+    MethodCall {
+        object: Box<Expr>,
+        method: String,
+        args: Vec<Expr>,
+        span: Span,
+    },
+
+    /// Multi-branch conditional expression:
+    /// `cond { n < 10 { 'small' } n < 100 { 'medium' } else { 'large' } }`
+    ///
+    /// The first branch whose condition holds gives its body's last expression as
+    /// the value. The `else` branch is required, so there always is one.
+    Cond {
+        branches: Vec<(Expr, Vec<Statement>)>,
+        else_branch: Vec<Statement>,
+        span: Span,
+    },
+
+    /// Anonymous function: |x| x * 2, or fn(x) { body }
+    ///
+    /// The short form's body is a single expression statement.
+    Lambda {
+        params: Vec<String>,
+        body: Vec<Statement>,
+        span: Span,
+    },
+}
+
+impl Expr {
+    /// Returns the span of source the expression was parsed from
+    pub fn span(&self) -> Span {
+        match self {
+            Expr::Integer { span, .. }
+            | Expr::Float { span, .. }
+            | Expr::Bool { span, .. }
+            | Expr::Nil { span }
+            | Expr::String { span, .. }
+            | Expr::Identifier { span, .. }
+            | Expr::BinaryOp { span, .. }
+            | Expr::UnaryOp { span, .. }
+            | Expr::Grouped { span, .. }
+            | Expr::Array { span, .. }
+            | Expr::Tuple { span, .. }
+            | Expr::Map { span, .. }
+            | Expr::Index { span, .. }
+            | Expr::Slice { span, .. }
+            | Expr::FunctionCall { span, .. }
+            | Expr::Call { span, .. }
+            | Expr::NamedArg { span, .. }
+            | Expr::Binding { span, .. }
+            | Expr::FieldAccess { span, .. }
+            | Expr::MethodCall { span, .. }
+            | Expr::Cond { span, .. }
+            | Expr::Lambda { span, .. } => *span,
+        }
+    }
+
+    /// Returns a copy of the expression with every span reset to `Span::default()`
+    pub fn without_spans(&self) -> Expr {
+        ClearSpans.fold_expr(self.clone())
+    }
+
+    /// Checks if two expressions are the same apart from their spans
+    pub fn structurally_eq(&self, other: &Expr) -> bool {
+        self.without_spans() == other.without_spans()
+    }
+
+    /// Returns a hash of the expression that ignores spans, stable across runs; equal
+    /// for expressions that are `structurally_eq`
+    pub fn structural_hash(&self) -> u64 {
+        structural::hash(&Normalize.fold_expr(self.clone()))
+    }
+}
+
+/// Program is a list of statements
+#[derive(Debug, Clone, PartialEq)]
+pub struct Program {
+    pub statements: Vec<Statement>,
+}
+
+impl Program {
+    /// Indexes the program's top-level functions, classes, methods, and traits by name
+    pub fn index(&self) -> ProgramIndex<'_> {
+        ProgramIndex::new(self)
+    }
+
+    /// Returns a Graphviz DOT digraph of the program's tree, for `dot -Tsvg`
+    pub fn to_dot(&self) -> String {
+        dot::to_dot(self)
+    }
+
+    /// Returns a copy of the program with every span reset to `Span::default()`
+    pub fn without_spans(&self) -> Program {
+        ClearSpans.fold_program(self.clone())
+    }
+
+    /// Checks if two programs are the same apart from their spans, as when one is
+    /// the other with lines moved or re-indented
+    pub fn structurally_eq(&self, other: &Program) -> bool {
+        self.without_spans() == other.without_spans()
+    }
+
+    /// Returns a hash of the program that ignores spans, stable across runs; equal for
+    /// programs that are `structurally_eq`
+    pub fn structural_hash(&self) -> u64 {
+        structural::hash(&Normalize.fold_program(self.clone()))
+    }
+}
+
+/// Binary operators
+#[derive(Debug, Clone, PartialEq)]
+pub enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    // Comparison operators
+    EqualEqual,
+    NotEqual,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    /// Membership test: `x in xs`, for an element of an array, a key of a map, or a
+    /// substring of a string
+    In,
+    // Logical operators
+    And,
+    Or,
+    // Bitwise operators
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
+}
+
+/// Unary (prefix) operators
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnaryOperator {
+    /// Logical negation: `!x` or `not x`
+    Not,
+    /// Arithmetic negation: `-x`
+    Negate,
+    /// Unary plus, which leaves a number unchanged: `+x`
+    Plus,
+}
+
+impl UnaryOperator {
+    /// Returns the operator's source symbol
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            UnaryOperator::Not => "!",
+            UnaryOperator::Negate => "-",
+            UnaryOperator::Plus => "+",
+        }
+    }
+}
+
+impl std::fmt::Display for UnaryOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+impl BinaryOperator {
+    /// Returns the precedence of the operator (higher = binds tighter)
+    pub fn precedence(&self) -> u8 {
+        operator_info(self).precedence
+    }
+
+    /// Returns how chains of this operator group
+    pub fn associativity(&self) -> Associativity {
+        operator_info(self).associativity
+    }
+
+    /// Returns the operator's source symbol
+    pub fn symbol(&self) -> &'static str {
+        operator_info(self).symbol
+    }
+
+    /// Checks if the operator is arithmetic (`+`, `-`, `*`, `/`)
+    pub fn is_arithmetic(&self) -> bool {
+        matches!(
+            self,
+            BinaryOperator::Add
+                | BinaryOperator::Subtract
+                | BinaryOperator::Multiply
+                | BinaryOperator::Divide
+        )
+    }
+
+    /// Checks if the operator compares its operands (`==`, `!=`, `<`, `<=`, `>`, `>=`)
+    pub fn is_comparison(&self) -> bool {
+        matches!(
+            self,
+            BinaryOperator::EqualEqual
+                | BinaryOperator::NotEqual
+                | BinaryOperator::LessThan
+                | BinaryOperator::LessThanOrEqual
+                | BinaryOperator::GreaterThan
+                | BinaryOperator::GreaterThanOrEqual
+        )
+    }
+
+    /// Checks if the operator works on the bits of integers (`&`, `|`, `^`, `<<`, `>>`)
+    pub fn is_bitwise(&self) -> bool {
+        matches!(
+            self,
+            BinaryOperator::BitAnd
+                | BinaryOperator::BitOr
+                | BinaryOperator::BitXor
+                | BinaryOperator::ShiftLeft
+                | BinaryOperator::ShiftRight
+        )
+    }
+}
+
+impl std::fmt::Display for BinaryOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+/// Field declared in a class body: `x`, or `x: float`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDecl {
+    pub name: String,
+    pub ty: Option<TypeAnnotation>,
+    /// The field name
+    pub span: Span,
+}
+
+impl std::fmt::Display for FieldDecl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.ty {
+            Some(ty) => write!(f, "{}: {}", self.name, ty),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// Arm of a match statement: `Color.Red, Color.Blue => body`
+///
+/// It runs when any of its patterns matches. A body written without braces is a
+/// single statement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchArm {
+    pub patterns: Vec<Pattern>,
+    pub body: Vec<Statement>,
+}
+
+/// Pattern a match arm compares its subject with
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    /// An enum variant: `Color.Red`
+    Variant { enum_name: String, variant: String },
+    /// An integer, string, or bool literal
+    Literal(Expr),
+    /// `_`, which matches anything
+    Wildcard,
+}
+
+impl Pattern {
+    /// Returns a copy of the pattern with the span of a literal reset
+    pub fn without_spans(&self) -> Pattern {
+        ClearSpans.fold_pattern(self.clone())
+    }
+}
+
+impl std::fmt::Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pattern::Variant { enum_name, variant } => write!(f, "{}.{}", enum_name, variant),
+            Pattern::Literal(value) => write!(f, "{}", value),
+            Pattern::Wildcard => write!(f, "_"),
+        }
+    }
+}
+
+/// Method signature in a trait body: `fn area() -> float`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraitMethod {
+    pub name: String,
+    pub params: Vec<String>,
+    pub param_types: Vec<Option<TypeAnnotation>>,
+    pub variadic: bool,
+    pub return_type: Option<TypeAnnotation>,
+    /// The signature from `fn` to the method name
+    pub span: Span,
+}
+
+impl std::fmt::Display for TraitMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "fn {}({}){}",
+            self.name,
+            param_list(&self.params, &self.param_types, self.variadic),
+            return_suffix(&self.return_type)
+        )
+    }
+}
+
+/// Type written after a parameter, variable, or function header: `int`, `float`,
+/// `string`, or `bool`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeAnnotation {
+    Int,
+    Float,
+    String,
+    Bool,
+}
+
+impl TypeAnnotation {
+    /// Returns the annotation spelled by a type name, if it is one
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "int" => Some(TypeAnnotation::Int),
+            "float" => Some(TypeAnnotation::Float),
+            "string" => Some(TypeAnnotation::String),
+            "bool" => Some(TypeAnnotation::Bool),
+            _ => None,
+        }
+    }
+
+    /// Returns the type name as written in source
+    pub fn name(&self) -> &'static str {
+        match self {
+            TypeAnnotation::Int => "int",
+            TypeAnnotation::Float => "float",
+            TypeAnnotation::String => "string",
+            TypeAnnotation::Bool => "bool",
+        }
+    }
+}
+
+impl std::fmt::Display for TypeAnnotation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Renders a parameter list as written, with annotations and `...` after a variadic
+/// last parameter
+pub fn param_list(
+    params: &[String],
+    param_types: &[Option<TypeAnnotation>],
+    variadic: bool,
+) -> String {
+    let list = params
+        .iter()
+        .enumerate()
+        .map(|(i, param)| match param_types.get(i).copied().flatten() {
+            Some(ty) => format!("{}: {}", param, ty),
+            None => param.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    if variadic {
+        format!("{}...", list)
+    } else {
+        list
+    }
+}
+
+/// Renders the ` -> type` suffix of a function header, if it has one
+pub fn return_suffix(return_type: &Option<TypeAnnotation>) -> String {
+    return_type
+        .map(|ty| format!(" -> {}", ty))
+        .unwrap_or_default()
+}
+
+/// One line per statement; the alternate form (`{:#}`) writes out every body,
+/// indented
+impl std::fmt::Display for Statement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            return render::statement(f, self, 0);
+        }
+        match self {
+            Statement::FunctionDef {
+                name,
+                params,
+                param_types,
+                variadic,
+                return_type,
+                ..
+            } => write!(
+                f,
+                "fn {}({}){}",
+                name,
+                param_list(params, param_types, *variadic),
+                return_suffix(return_type)
+            ),
+            Statement::ClassDef {
+                name,
+                parent,
+                traits,
+                mixins,
+                fields,
+                ..
+            } => {
+                write!(f, "class {}", name)?;
+                if let Some(parent) = parent {
+                    write!(f, " < {}", parent)?;
+                }
+                if !traits.is_empty() {
+                    write!(f, " : {}", traits.join(", "))?;
+                }
+                if !mixins.is_empty() {
+                    write!(f, " with {}", mixins.join(", "))?;
+                }
+                if !fields.is_empty() {
+                    let fields: Vec<String> = fields.iter().map(FieldDecl::to_string).collect();
+                    write!(f, " {{ {} }}", fields.join(", "))?;
+                }
+                Ok(())
+            }
+            Statement::MixinDef { name, .. } => write!(f, "mixin {}", name),
+            Statement::EnumDef { name, variants, .. } => {
+                write!(f, "enum {} {{ {} }}", name, variants.join(", "))
+            }
+            Statement::Match { subject, arms, .. } => {
+                write!(f, "match {} + {} arm(s)", subject, arms.len())
+            }
+            Statement::TraitDef { name, methods, .. } => {
+                write!(f, "trait {}", name)?;
+                if !methods.is_empty() {
+                    let methods: Vec<String> = methods.iter().map(TraitMethod::to_string).collect();
+                    write!(f, " {{ {} }}", methods.join(", "))?;
+                }
+                Ok(())
+            }
+            Statement::MethodDef {
+                class_name,
+                method_name,
+                is_static,
+                params,
+                param_types,
+                variadic,
+                return_type,
+                ..
+            } => {
+                write!(
+                    f,
+                    "fn {} {} {}({}){}",
+                    class_name,
+                    if *is_static { ">>" } else { ">" },
+                    method_name,
+                    param_list(params, param_types, *variadic),
+                    return_suffix(return_type)
+                )
+            }
+            Statement::Assignment {
+                name,
+                mutable,
+                ty,
+                value,
+                ..
+            } => {
+                if *mutable {
+                    write!(f, "var ")?;
+                }
+                match ty {
+                    Some(ty) => write!(f, "{}: {} = {}", name, ty, value),
+                    None => write!(f, "{} = {}", name, value),
+                }
+            }
+            Statement::FieldAssignment {
+                object,
+                field,
+                value,
+                ..
+            } => write!(f, "{}.{} = {}", object, field, value),
+            Statement::ConstDef { name, value, .. } => write!(f, "const {} = {}", name, value),
+            Statement::Global { name, value, .. } => write!(f, "global {} = {}", name, value),
+            Statement::Import { module, names, .. } if names.is_empty() => {
+                write!(f, "import {}", module)
+            }
+            Statement::Import { module, names, .. } => {
+                write!(f, "import {} {{ {} }}", module, names.join(", "))
+            }
+            Statement::ParallelAssignment { names, values, .. } => {
+                write!(f, "{} = ", names.join(", "))?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                Ok(())
+            }
+            Statement::ArrayDestructuring { names, value, .. } => {
+                write!(f, "[{}] = {}", names.join(", "), value)
+            }
+            Statement::If {
+                condition,
+                elif_branches,
+                else_branch,
+                ..
+            } => {
+                write!(f, "if {}", condition)?;
+                if !elif_branches.is_empty() {
+                    write!(f, " + {} elif(s)", elif_branches.len())?;
+                }
+                if else_branch.is_some() {
+                    write!(f, " + else")?;
+                }
+                Ok(())
+            }
+            Statement::While {
+                condition, label, ..
+            } => match label {
+                Some(label) => write!(f, "{}: while {}", label, condition),
+                None => write!(f, "while {}", condition),
+            },
+            Statement::Loop { label, .. } => match label {
+                Some(label) => write!(f, "{}: loop", label),
+                None => write!(f, "loop"),
+            },
+            Statement::DoWhile {
+                condition, label, ..
+            } => match label {
+                Some(label) => write!(f, "{}: do ... while {}", label, condition),
+                None => write!(f, "do ... while {}", condition),
+            },
+            Statement::Block { .. } => write!(f, "{{ ... }}"),
+            Statement::Break { label, .. } => match label {
+                Some(label) => write!(f, "break {}", label),
+                None => write!(f, "break"),
+            },
+            Statement::Continue { label, .. } => match label {
+                Some(label) => write!(f, "continue {}", label),
+                None => write!(f, "continue"),
+            },
+            Statement::Return { value, .. } => match value {
+                Some(value) => write!(f, "return {}", value),
+                None => write!(f, "return"),
+            },
+            Statement::Test { name, .. } => write!(f, "test '{}'", name),
+            Statement::Expression(expr) => write!(f, "{}", expr),
+        }
+    }
+}
+
+impl std::fmt::Display for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expr::Integer { value, .. } => write!(f, "{}", value),
+            Expr::Float { value, .. } => write!(f, "{:?}", value),
+            Expr::Bool { value, .. } => write!(f, "{}", value),
+            Expr::Nil { .. } => write!(f, "nil"),
+            Expr::String { value, .. } => write!(f, "'{}'", value),
+            Expr::Identifier { name, .. } => write!(f, "{}", name),
+            Expr::BinaryOp {
+                left, op, right, ..
+            } => {
+                write!(f, "({} {} {})", left, op, right)
+            }
+            Expr::UnaryOp { op, operand, .. } => write!(f, "({}{})", op, operand),
+            Expr::Grouped { inner, .. } => write!(f, "({})", inner),
+            Expr::Array { elements, .. } => {
+                write!(f, "[")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                write!(f, "]")
+            }
+            Expr::Tuple { elements, .. } => {
+                write!(f, "(")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                write!(f, ")")
+            }
+            Expr::Map { entries, .. } => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", key, value)?;
+                }
+                write!(f, "}}")
+            }
+            Expr::Index { object, index, .. } => write!(f, "{}[{}]", object, index),
+            Expr::Slice {
+                object, start, end, ..
+            } => {
+                write!(f, "{}[", object)?;
+                if let Some(start) = start {
+                    write!(f, "{}", start)?;
+                }
+                write!(f, "..")?;
+                if let Some(end) = end {
+                    write!(f, "{}", end)?;
+                }
+                write!(f, "]")
+            }
+            Expr::FunctionCall { name, args, .. } => {
+                write!(f, "{}(", name)?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                write!(f, ")")
+            }
+            Expr::Call { callee, args, .. } => {
+                write!(f, "{}(", callee)?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                write!(f, ")")
+            }
+            Expr::NamedArg { name, value, .. } | Expr::Binding { name, value, .. } => {
+                write!(f, "{} = {}", name, value)
+            }
+            Expr::FieldAccess { object, field, .. } => write!(f, "{}.{}", object, field),
+            Expr::MethodCall {
+                object,
+                method,
+                args,
+                ..
+            } => {
+                write!(f, "{}.{}(", object, method)?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                write!(f, ")")
+            }
+            Expr::Cond {
+                branches,
+                else_branch,
+                ..
+            } => {
+                let block = |body: &[Statement]| {
+                    let statements: Vec<String> = body.iter().map(Statement::to_string).collect();
+                    format!("{{ {} }}", statements.join("; "))
+                };
+                write!(f, "cond {{ ")?;
+                for (condition, body) in branches {
+                    write!(f, "{} {} ", condition, block(body))?;
+                }
+                write!(f, "else {} }}", block(else_branch))
+            }
+            Expr::Lambda { params, body, .. } => {
+                write!(f, "|{}| ", params.join(", "))?;
+                match body.as_slice() {
+                    [Statement::Expression(expr)] => write!(f, "{}", expr),
+                    _ => {
+                        let statements: Vec<String> =
+                            body.iter().map(Statement::to_string).collect();
+                        write!(f, "{{ {} }}", statements.join("; "))
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// One line per top-level statement; the alternate form (`{:#}`) writes out every
+/// body, indented
+impl std::fmt::Display for Program {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            return render::program(f, self);
+        }
+        for (i, stmt) in self.statements.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", stmt)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+//! The alternate (`{:#}`) rendering of statements and programs
+//!
+//! Plain `Display` shows one line per statement, with only the header of anything
+//! that has a body (`if (x > 1) + 1 elif(s)`). The alternate form writes every body
+//! out, indented two spaces per level, so nested programs can be read as a tree.
+//! Expressions keep their `Display` form, with every operation parenthesized, which
+//! shows how they were grouped; the canonical source form is `printer`'s job.
+
+use super::{MatchArm, Program, Statement};
+use std::fmt::{self, Formatter};
+
+/// Writes a program one statement after another
+pub(super) fn program(f: &mut Formatter<'_>, program: &Program) -> fmt::Result {
+    for (i, stmt) in program.statements.iter().enumerate() {
+        if i > 0 {
+            writeln!(f)?;
+        }
+        statement(f, stmt, 0)?;
+    }
+    Ok(())
+}
+
+/// Writes a statement with its bodies, each nested line indented one more level
+/// than `depth`; the first line is not indented
+pub(super) fn statement(f: &mut Formatter<'_>, stmt: &Statement, depth: usize) -> fmt::Result {
+    match stmt {
+        Statement::FunctionDef { body, .. }
+        | Statement::MethodDef { body, .. }
+        | Statement::While { body, .. }
+        | Statement::Loop { body, .. }
+        | Statement::Test { body, .. } => {
+            write!(f, "{} ", stmt)?;
+            block(f, body, depth)
+        }
+        Statement::Block { body, .. } => block(f, body, depth),
+        Statement::If {
+            condition,
+            then_branch,
+            elif_branches,
+            else_branch,
+            ..
+        } => {
+            write!(f, "if {} ", condition)?;
+            block(f, then_branch, depth)?;
+            for (condition, body) in elif_branches {
+                write!(f, " elif {} ", condition)?;
+                block(f, body, depth)?;
+            }
+            if let Some(body) = else_branch {
+                write!(f, " else ")?;
+                block(f, body, depth)?;
+            }
+            Ok(())
+        }
+        Statement::DoWhile {
+            body,
+            condition,
+            label,
+            ..
+        } => {
+            if let Some(label) = label {
+                write!(f, "{}: ", label)?;
+            }
+            write!(f, "do ")?;
+            block(f, body, depth)?;
+            write!(f, " while {}", condition)
+        }
+        Statement::Match { subject, arms, .. } => {
+            writeln!(f, "match {} {{", subject)?;
+            for arm in arms {
+                indent(f, depth + 1)?;
+                match_arm(f, arm, depth + 1)?;
+                writeln!(f)?;
+            }
+            indent(f, depth)?;
+            write!(f, "}}")
+        }
+        _ => write!(f, "{}", stmt),
+    }
+}
+
+/// Writes `pattern, ... => ` and the arm's body as a block
+fn match_arm(f: &mut Formatter<'_>, arm: &MatchArm, depth: usize) -> fmt::Result {
+    let patterns: Vec<String> = arm
+        .patterns
+        .iter()
+        .map(|pattern| pattern.to_string())
+        .collect();
+    write!(f, "{} => ", patterns.join(", "))?;
+    block(f, &arm.body, depth)
+}
+
+/// Writes `{`, the body one level deeper, and `}` at `depth`
+fn block(f: &mut Formatter<'_>, body: &[Statement], depth: usize) -> fmt::Result {
+    writeln!(f, "{{")?;
+    for stmt in body {
+        indent(f, depth + 1)?;
+        statement(f, stmt, depth + 1)?;
+        writeln!(f)?;
+    }
+    indent(f, depth)?;
+    write!(f, "}}")
+}
+
+fn indent(f: &mut Formatter<'_>, depth: usize) -> fmt::Result {
+    write!(f, "{:width$}", "", width = depth * 2)
+}
@@ -0,0 +1,51 @@
+//! Comparison and hashing of trees by structure alone
+//!
+//! Two trees are structurally equal when they differ at most in their spans, so the
+//! same program laid out differently (moved down a line, re-indented) compares
+//! equal. The structural hash agrees with that equality and, unlike `DefaultHasher`,
+//! does not change from run to run, so it can key an on-disk cache of results.
+
+use super::fold::{walk_expr, Fold};
+use super::Expr;
+use crate::lexer::Span;
+use std::fmt::{self, Write};
+
+/// Clears every span and turns `-0.0` into `0.0`, which it equals (and which the
+/// pattern `0.0` matches), so that equal trees print the same
+pub(super) struct Normalize;
+
+impl Fold for Normalize {
+    fn fold_expr(&mut self, expr: Expr) -> Expr {
+        match walk_expr(self, expr) {
+            Expr::Float { value: 0.0, span } => Expr::Float { value: 0.0, span },
+            expr => expr,
+        }
+    }
+
+    fn fold_span(&mut self, _: Span) -> Span {
+        Span::default()
+    }
+}
+
+/// Hashes a node already folded by `Normalize`
+pub(super) fn hash(normalized: &impl fmt::Debug) -> u64 {
+    let mut hasher = Fnv(FNV_OFFSET);
+    write!(hasher, "{:?}", normalized).unwrap();
+    hasher.0
+}
+
+// 64-bit FNV-1a over the tree's debug form
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+struct Fnv(u64);
+
+impl Write for Fnv {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.bytes() {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+        Ok(())
+    }
+}
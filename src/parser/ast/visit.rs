@@ -0,0 +1,391 @@
+//! Traversal of the AST
+//!
+//! A pass implements `Visitor` (or `VisitorMut` to change the tree in place) and
+//! overrides only the methods for the nodes it cares about. Each default method
+//! calls the matching `walk_*` function, which visits the node's children in source
+//! order; an override calls it too to keep descending, or leaves it out to skip the
+//! node's children.
+
+use super::{Expr, MatchArm, Pattern, Program, Statement};
+
+/// Read-only traversal of a program
+pub trait Visitor {
+    fn visit_program(&mut self, program: &Program) {
+        walk_program(self, program);
+    }
+
+    fn visit_statement(&mut self, stmt: &Statement) {
+        walk_statement(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        walk_expr(self, expr);
+    }
+
+    fn visit_match_arm(&mut self, arm: &MatchArm) {
+        walk_match_arm(self, arm);
+    }
+
+    fn visit_pattern(&mut self, pattern: &Pattern) {
+        walk_pattern(self, pattern);
+    }
+}
+
+/// Visits every top-level statement of a program
+pub fn walk_program<V: Visitor + ?Sized>(visitor: &mut V, program: &Program) {
+    walk_body(visitor, &program.statements);
+}
+
+/// Visits the statements of a body, in order
+pub fn walk_body<V: Visitor + ?Sized>(visitor: &mut V, body: &[Statement]) {
+    for stmt in body {
+        visitor.visit_statement(stmt);
+    }
+}
+
+/// Visits the expressions and nested bodies of a statement
+pub fn walk_statement<V: Visitor + ?Sized>(visitor: &mut V, stmt: &Statement) {
+    match stmt {
+        Statement::FunctionDef { body, .. }
+        | Statement::MethodDef { body, .. }
+        | Statement::Block { body, .. }
+        | Statement::Loop { body, .. }
+        | Statement::Test { body, .. } => walk_body(visitor, body),
+        Statement::Assignment { value, .. }
+        | Statement::ConstDef { value, .. }
+        | Statement::Global { value, .. }
+        | Statement::ArrayDestructuring { value, .. }
+        | Statement::Return {
+            value: Some(value), ..
+        }
+        | Statement::Expression(value) => visitor.visit_expr(value),
+        Statement::FieldAssignment { object, value, .. } => {
+            visitor.visit_expr(object);
+            visitor.visit_expr(value);
+        }
+        Statement::ParallelAssignment { values, .. } => {
+            for value in values {
+                visitor.visit_expr(value);
+            }
+        }
+        Statement::If {
+            condition,
+            then_branch,
+            elif_branches,
+            else_branch,
+            ..
+        } => {
+            visitor.visit_expr(condition);
+            walk_body(visitor, then_branch);
+            for (condition, branch) in elif_branches {
+                visitor.visit_expr(condition);
+                walk_body(visitor, branch);
+            }
+            if let Some(branch) = else_branch {
+                walk_body(visitor, branch);
+            }
+        }
+        Statement::Match { subject, arms, .. } => {
+            visitor.visit_expr(subject);
+            for arm in arms {
+                visitor.visit_match_arm(arm);
+            }
+        }
+        Statement::While {
+            condition, body, ..
+        } => {
+            visitor.visit_expr(condition);
+            walk_body(visitor, body);
+        }
+        Statement::DoWhile {
+            body, condition, ..
+        } => {
+            walk_body(visitor, body);
+            visitor.visit_expr(condition);
+        }
+        Statement::ClassDef { .. }
+        | Statement::MixinDef { .. }
+        | Statement::TraitDef { .. }
+        | Statement::EnumDef { .. }
+        | Statement::Import { .. }
+        | Statement::Break { .. }
+        | Statement::Continue { .. }
+        | Statement::Return { value: None, .. } => {}
+    }
+}
+
+/// Visits the subexpressions and nested bodies of an expression
+pub fn walk_expr<V: Visitor + ?Sized>(visitor: &mut V, expr: &Expr) {
+    match expr {
+        Expr::BinaryOp { left, right, .. } => {
+            visitor.visit_expr(left);
+            visitor.visit_expr(right);
+        }
+        Expr::UnaryOp { operand: inner, .. }
+        | Expr::Grouped { inner, .. }
+        | Expr::NamedArg { value: inner, .. }
+        | Expr::Binding { value: inner, .. }
+        | Expr::FieldAccess { object: inner, .. } => visitor.visit_expr(inner),
+        Expr::Array { elements, .. }
+        | Expr::Tuple { elements, .. }
+        | Expr::FunctionCall { args: elements, .. } => {
+            for element in elements {
+                visitor.visit_expr(element);
+            }
+        }
+        Expr::Map { entries, .. } => {
+            for (key, value) in entries {
+                visitor.visit_expr(key);
+                visitor.visit_expr(value);
+            }
+        }
+        Expr::Index { object, index, .. } => {
+            visitor.visit_expr(object);
+            visitor.visit_expr(index);
+        }
+        Expr::Slice {
+            object, start, end, ..
+        } => {
+            visitor.visit_expr(object);
+            for bound in start.iter().chain(end) {
+                visitor.visit_expr(bound);
+            }
+        }
+        Expr::Call {
+            callee: object,
+            args,
+            ..
+        }
+        | Expr::MethodCall { object, args, .. } => {
+            visitor.visit_expr(object);
+            for arg in args {
+                visitor.visit_expr(arg);
+            }
+        }
+        Expr::Cond {
+            branches,
+            else_branch,
+            ..
+        } => {
+            for (condition, body) in branches {
+                visitor.visit_expr(condition);
+                walk_body(visitor, body);
+            }
+            walk_body(visitor, else_branch);
+        }
+        Expr::Lambda { body, .. } => walk_body(visitor, body),
+        Expr::Integer { .. }
+        | Expr::Float { .. }
+        | Expr::Bool { .. }
+        | Expr::Nil { .. }
+        | Expr::String { .. }
+        | Expr::Identifier { .. } => {}
+    }
+}
+
+/// Visits the patterns of a match arm, then its body
+pub fn walk_match_arm<V: Visitor + ?Sized>(visitor: &mut V, arm: &MatchArm) {
+    for pattern in &arm.patterns {
+        visitor.visit_pattern(pattern);
+    }
+    walk_body(visitor, &arm.body);
+}
+
+/// Visits the literal of a pattern
+pub fn walk_pattern<V: Visitor + ?Sized>(visitor: &mut V, pattern: &Pattern) {
+    match pattern {
+        Pattern::Literal(value) => visitor.visit_expr(value),
+        Pattern::Variant { .. } | Pattern::Wildcard => {}
+    }
+}
+
+/// Traversal of a program that may change it in place
+pub trait VisitorMut {
+    fn visit_program_mut(&mut self, program: &mut Program) {
+        walk_program_mut(self, program);
+    }
+
+    fn visit_statement_mut(&mut self, stmt: &mut Statement) {
+        walk_statement_mut(self, stmt);
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        walk_expr_mut(self, expr);
+    }
+
+    fn visit_match_arm_mut(&mut self, arm: &mut MatchArm) {
+        walk_match_arm_mut(self, arm);
+    }
+
+    fn visit_pattern_mut(&mut self, pattern: &mut Pattern) {
+        walk_pattern_mut(self, pattern);
+    }
+}
+
+/// Visits every top-level statement of a program
+pub fn walk_program_mut<V: VisitorMut + ?Sized>(visitor: &mut V, program: &mut Program) {
+    walk_body_mut(visitor, &mut program.statements);
+}
+
+/// Visits the statements of a body, in order
+pub fn walk_body_mut<V: VisitorMut + ?Sized>(visitor: &mut V, body: &mut [Statement]) {
+    for stmt in body {
+        visitor.visit_statement_mut(stmt);
+    }
+}
+
+/// Visits the expressions and nested bodies of a statement
+pub fn walk_statement_mut<V: VisitorMut + ?Sized>(visitor: &mut V, stmt: &mut Statement) {
+    match stmt {
+        Statement::FunctionDef { body, .. }
+        | Statement::MethodDef { body, .. }
+        | Statement::Block { body, .. }
+        | Statement::Loop { body, .. }
+        | Statement::Test { body, .. } => walk_body_mut(visitor, body),
+        Statement::Assignment { value, .. }
+        | Statement::ConstDef { value, .. }
+        | Statement::Global { value, .. }
+        | Statement::ArrayDestructuring { value, .. }
+        | Statement::Return {
+            value: Some(value), ..
+        }
+        | Statement::Expression(value) => visitor.visit_expr_mut(value),
+        Statement::FieldAssignment { object, value, .. } => {
+            visitor.visit_expr_mut(object);
+            visitor.visit_expr_mut(value);
+        }
+        Statement::ParallelAssignment { values, .. } => {
+            for value in values {
+                visitor.visit_expr_mut(value);
+            }
+        }
+        Statement::If {
+            condition,
+            then_branch,
+            elif_branches,
+            else_branch,
+            ..
+        } => {
+            visitor.visit_expr_mut(condition);
+            walk_body_mut(visitor, then_branch);
+            for (condition, branch) in elif_branches {
+                visitor.visit_expr_mut(condition);
+                walk_body_mut(visitor, branch);
+            }
+            if let Some(branch) = else_branch {
+                walk_body_mut(visitor, branch);
+            }
+        }
+        Statement::Match { subject, arms, .. } => {
+            visitor.visit_expr_mut(subject);
+            for arm in arms {
+                visitor.visit_match_arm_mut(arm);
+            }
+        }
+        Statement::While {
+            condition, body, ..
+        } => {
+            visitor.visit_expr_mut(condition);
+            walk_body_mut(visitor, body);
+        }
+        Statement::DoWhile {
+            body, condition, ..
+        } => {
+            walk_body_mut(visitor, body);
+            visitor.visit_expr_mut(condition);
+        }
+        Statement::ClassDef { .. }
+        | Statement::MixinDef { .. }
+        | Statement::TraitDef { .. }
+        | Statement::EnumDef { .. }
+        | Statement::Import { .. }
+        | Statement::Break { .. }
+        | Statement::Continue { .. }
+        | Statement::Return { value: None, .. } => {}
+    }
+}
+
+/// Visits the subexpressions and nested bodies of an expression
+pub fn walk_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, expr: &mut Expr) {
+    match expr {
+        Expr::BinaryOp { left, right, .. } => {
+            visitor.visit_expr_mut(left);
+            visitor.visit_expr_mut(right);
+        }
+        Expr::UnaryOp { operand: inner, .. }
+        | Expr::Grouped { inner, .. }
+        | Expr::NamedArg { value: inner, .. }
+        | Expr::Binding { value: inner, .. }
+        | Expr::FieldAccess { object: inner, .. } => visitor.visit_expr_mut(inner),
+        Expr::Array { elements, .. }
+        | Expr::Tuple { elements, .. }
+        | Expr::FunctionCall { args: elements, .. } => {
+            for element in elements {
+                visitor.visit_expr_mut(element);
+            }
+        }
+        Expr::Map { entries, .. } => {
+            for (key, value) in entries {
+                visitor.visit_expr_mut(key);
+                visitor.visit_expr_mut(value);
+            }
+        }
+        Expr::Index { object, index, .. } => {
+            visitor.visit_expr_mut(object);
+            visitor.visit_expr_mut(index);
+        }
+        Expr::Slice {
+            object, start, end, ..
+        } => {
+            visitor.visit_expr_mut(object);
+            for bound in start.iter_mut().chain(end) {
+                visitor.visit_expr_mut(bound);
+            }
+        }
+        Expr::Call {
+            callee: object,
+            args,
+            ..
+        }
+        | Expr::MethodCall { object, args, .. } => {
+            visitor.visit_expr_mut(object);
+            for arg in args {
+                visitor.visit_expr_mut(arg);
+            }
+        }
+        Expr::Cond {
+            branches,
+            else_branch,
+            ..
+        } => {
+            for (condition, body) in branches {
+                visitor.visit_expr_mut(condition);
+                walk_body_mut(visitor, body);
+            }
+            walk_body_mut(visitor, else_branch);
+        }
+        Expr::Lambda { body, .. } => walk_body_mut(visitor, body),
+        Expr::Integer { .. }
+        | Expr::Float { .. }
+        | Expr::Bool { .. }
+        | Expr::Nil { .. }
+        | Expr::String { .. }
+        | Expr::Identifier { .. } => {}
+    }
+}
+
+/// Visits the patterns of a match arm, then its body
+pub fn walk_match_arm_mut<V: VisitorMut + ?Sized>(visitor: &mut V, arm: &mut MatchArm) {
+    for pattern in &mut arm.patterns {
+        visitor.visit_pattern_mut(pattern);
+    }
+    walk_body_mut(visitor, &mut arm.body);
+}
+
+/// Visits the literal of a pattern
+pub fn walk_pattern_mut<V: VisitorMut + ?Sized>(visitor: &mut V, pattern: &mut Pattern) {
+    match pattern {
+        Pattern::Literal(value) => visitor.visit_expr_mut(value),
+        Pattern::Variant { .. } | Pattern::Wildcard => {}
+    }
+}
@@ -1,5 +1,11 @@
 pub mod ast;
 pub mod parse;
+pub mod precedence;
+pub mod printer;
 
-pub use ast::{BinaryOperator, Expr, Program, Statement};
-pub use parse::{ParseError, ParseResult, Parser};
+pub use ast::{
+    BinaryOperator, Expr, FieldDecl, Fold, MatchArm, Pattern, Program, ProgramIndex, Statement,
+    TraitMethod, TypeAnnotation, UnaryOperator, Visitor, VisitorMut,
+};
+pub use parse::{ParseError, ParseOutcome, ParseResult, Parser, DEFAULT_MAX_DEPTH};
+pub use precedence::{Associativity, OperatorInfo};
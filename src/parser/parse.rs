@@ -1,18 +1,51 @@
-use super::ast::{BinaryOperator, Expr, Program, Statement};
-use crate::lexer::{Token, TokenType};
+use super::ast::{
+    BinaryOperator, Expr, FieldDecl, MatchArm, Pattern, Program, Statement, TraitMethod,
+    TypeAnnotation, UnaryOperator,
+};
+use super::precedence::{Associativity, UNARY_PRECEDENCE};
+use crate::lexer::{LexError, Span, Token, TokenSource, TokenStream, TokenType};
 
 /// Parser for the Grit language
-pub struct Parser {
-    tokens: Vec<Token>,
-    position: usize,
+///
+/// The parser reads from any `TokenSource`: an owned `Vec<Token>` (the default),
+/// a borrowed `&[Token]`, or a `Tokenizer` that lexes on demand.
+pub struct Parser<S: TokenSource = std::vec::IntoIter<Token>> {
+    tokens: TokenStream<S>,
+    /// `{`s consumed but not yet closed, so recovery can skip to the end of a block
+    open_braces: isize,
+    /// Statements and expressions currently being parsed inside one another
+    depth: usize,
+    max_depth: usize,
 }
 
+/// How deeply statements and expressions may nest before parsing gives up with
+/// [`ParseError::TooDeep`] rather than overflowing the stack
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
 /// Parser errors
 #[derive(Debug, Clone, PartialEq)]
 pub enum ParseError {
-    UnexpectedToken { expected: String, found: Token },
-    UnexpectedEof { expected: String },
-    InvalidExpression { token: Token },
+    UnexpectedToken {
+        expected: String,
+        found: Token,
+    },
+    UnexpectedEof {
+        expected: String,
+    },
+    InvalidExpression {
+        token: Token,
+    },
+    /// An `else` or `elif` that does not follow an `if` statement's branches
+    DanglingElse {
+        token: Token,
+    },
+    /// Statements or expressions nested more than `limit` levels deep
+    TooDeep {
+        limit: usize,
+        span: Span,
+    },
+    /// The tokens could not be produced, when parsing from a `Tokenizer`
+    Lex(LexError),
 }
 
 impl std::fmt::Display for ParseError {
@@ -35,41 +68,155 @@ impl std::fmt::Display for ParseError {
                     token.line, token.column
                 )
             }
+            ParseError::DanglingElse { token } => {
+                let keyword = match token.token_type {
+                    TokenType::Elif => "elif",
+                    _ => "else",
+                };
+                write!(
+                    f,
+                    "'{}' without a preceding 'if' at line {}, column {}",
+                    keyword, token.line, token.column
+                )
+            }
+            ParseError::TooDeep { limit, span } => {
+                write!(
+                    f,
+                    "Nesting deeper than {} levels at line {}, column {}",
+                    limit, span.start_line, span.start_column
+                )
+            }
+            ParseError::Lex(err) => write!(f, "{}", err),
         }
     }
 }
 
 impl std::error::Error for ParseError {}
 
+impl From<LexError> for ParseError {
+    fn from(err: LexError) -> Self {
+        ParseError::Lex(err)
+    }
+}
+
 pub type ParseResult<T> = Result<T, ParseError>;
 
+/// A program parsed past its syntax errors, from [`Parser::parse_all`]
+///
+/// A statement with an error is left out of the program.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseOutcome {
+    pub program: Program,
+    /// Every syntax error, in source order
+    pub errors: Vec<ParseError>,
+}
+
+impl ParseOutcome {
+    /// Checks if the source parsed without errors
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns the program, or the first error if there were any
+    pub fn into_result(self) -> ParseResult<Program> {
+        match self.errors.into_iter().next() {
+            Some(err) => Err(err),
+            None => Ok(self.program),
+        }
+    }
+}
+
+/// The parameters, return type, and body shared by functions, methods, and lambdas
+#[derive(Default)]
+struct FunctionParts {
+    params: Vec<String>,
+    param_types: Vec<Option<TypeAnnotation>>,
+    variadic: bool,
+    return_type: Option<TypeAnnotation>,
+    body: Vec<Statement>,
+}
+
 impl Parser {
     /// Creates a new parser from a vector of tokens
     pub fn new(tokens: Vec<Token>) -> Self {
         Parser {
-            tokens,
-            position: 0,
+            tokens: TokenStream::new(tokens),
+            open_braces: 0,
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
         }
     }
+}
+
+impl<S: TokenSource> Parser<S> {
+    /// Creates a new parser that pulls tokens from the given source
+    pub fn from_source(source: S) -> Self {
+        Parser {
+            tokens: TokenStream::from_source(source),
+            open_braces: 0,
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Sets how deeply statements and expressions may nest, [`DEFAULT_MAX_DEPTH`] by default
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Returns the nesting limit
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Returns the underlying token stream
+    pub fn tokens(&self) -> &TokenStream<S> {
+        &self.tokens
+    }
 
     /// Returns the current token without consuming it
     fn current_token(&self) -> Option<&Token> {
-        self.tokens.get(self.position)
+        self.tokens.peek()
+    }
+
+    /// Returns the type of the token `n` places ahead without consuming anything
+    ///
+    /// `peek(0)` is the current token.
+    fn peek(&mut self, n: usize) -> Option<&TokenType> {
+        self.tokens.peek_nth(n).map(|token| &token.token_type)
+    }
+
+    /// Checks if the current token has the given type
+    fn check(&self, token_type: &TokenType) -> bool {
+        self.current_token()
+            .is_some_and(|token| &token.token_type == token_type)
     }
 
     /// Advances to the next token
     fn advance(&mut self) {
-        if self.position < self.tokens.len() {
-            self.position += 1;
+        match self.current_token().map(|token| &token.token_type) {
+            Some(TokenType::LeftBrace) => self.open_braces += 1,
+            Some(TokenType::RightBrace) => self.open_braces -= 1,
+            _ => {}
         }
+        self.tokens.next();
     }
 
     /// Checks if the current token is EOF
     fn is_at_end(&self) -> bool {
-        matches!(
-            self.current_token(),
-            Some(token) if token.token_type == TokenType::Eof
-        ) || self.current_token().is_none()
+        self.tokens.is_at_end()
+    }
+
+    /// Skips the newlines and `;`s that separate statements
+    fn skip_separators(&mut self) {
+        while let Some(token) = self.current_token() {
+            if matches!(token.token_type, TokenType::Newline | TokenType::Semicolon) {
+                self.advance();
+            } else {
+                break;
+            }
+        }
     }
 
     /// Skips newline tokens
@@ -85,26 +232,147 @@ impl Parser {
 
     /// Parses the tokens into a program
     pub fn parse(&mut self) -> ParseResult<Program> {
+        let result = self.parse_program();
+        self.lex_error_or(result)
+    }
+
+    /// Parses the whole input, continuing past syntax errors to report them all
+    ///
+    /// After an error the parser skips to the end of the line, or past the end of a
+    /// block the failed statement opened, and carries on with the next statement. A
+    /// lexer error is the only error reported, since the tokens after it are missing.
+    pub fn parse_all(&mut self) -> ParseOutcome {
         let mut statements = Vec::new();
+        let mut errors = Vec::new();
 
-        self.skip_newlines();
+        self.skip_separators();
+        while !self.is_at_end() {
+            self.open_braces = 0;
+            match self.parse_statement() {
+                Ok(stmt) => statements.push(stmt),
+                Err(err) => {
+                    errors.push(err);
+                    self.synchronize();
+                }
+            }
+            self.skip_separators();
+        }
+
+        if let Some(err) = self.tokens.lex_error() {
+            errors = vec![ParseError::Lex(err.clone())];
+        }
+        ParseOutcome {
+            program: Program { statements },
+            errors,
+        }
+    }
+
+    /// Skips the rest of a statement that failed to parse: up to the next newline or
+    /// `;` outside the blocks it opened
+    fn synchronize(&mut self) {
+        while let Some(token) = self.current_token() {
+            let at_boundary = matches!(token.token_type, TokenType::Newline | TokenType::Semicolon)
+                && self.open_braces <= 0;
+            if at_boundary || token.token_type == TokenType::Eof {
+                break;
+            }
+            self.advance();
+        }
+    }
+
+    fn parse_program(&mut self) -> ParseResult<Program> {
+        let mut statements = Vec::new();
+
+        self.skip_separators();
 
         while !self.is_at_end() {
             let stmt = self.parse_statement()?;
             statements.push(stmt);
-            self.skip_newlines();
+            self.skip_separators();
         }
 
         Ok(Program { statements })
     }
 
+    /// Replaces a result with the lexer error that cut the tokens short, if any
+    ///
+    /// Whatever the parser made of truncated input is beside the point then.
+    fn lex_error_or<T>(&self, result: ParseResult<T>) -> ParseResult<T> {
+        match self.tokens.lex_error() {
+            Some(err) => Err(ParseError::Lex(err.clone())),
+            None => result,
+        }
+    }
+
+    /// Runs `parse` one nesting level deeper, failing once the limit is reached
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> ParseResult<T>) -> ParseResult<T> {
+        if self.depth >= self.max_depth {
+            return Err(ParseError::TooDeep {
+                limit: self.max_depth,
+                span: self.tokens.current_span(),
+            });
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Counts one more operator, call, or index applied to an operand, failing once
+    /// they nest it past the limit
+    ///
+    /// The parser builds a chain like `1 + 1 + 1` in a loop, but the tree it makes is
+    /// nested one level per link, and the checker and generator recurse through it.
+    fn chain_link(&self, links: &mut usize) -> ParseResult<()> {
+        *links += 1;
+        if self.depth + *links > self.max_depth {
+            return Err(ParseError::TooDeep {
+                limit: self.max_depth,
+                span: self.tokens.current_span(),
+            });
+        }
+        Ok(())
+    }
+
     /// Parses a single statement
     fn parse_statement(&mut self) -> ParseResult<Statement> {
+        self.nested(Self::parse_statement_kind)
+    }
+
+    /// Parses a statement, dispatching on its first token
+    fn parse_statement_kind(&mut self) -> ParseResult<Statement> {
+        if let Some(TokenType::DocComment(_)) = self.peek(0) {
+            return self.parse_documented_def();
+        }
         // Check if this is a class definition
         if let Some(token) = self.current_token() {
             if token.token_type == TokenType::Class {
                 return self.parse_class_def();
             }
+            if token.token_type == TokenType::Mixin {
+                return self.parse_mixin_def();
+            }
+            if token.token_type == TokenType::Trait {
+                return self.parse_trait_def();
+            }
+            if token.token_type == TokenType::Enum {
+                return self.parse_enum_def();
+            }
+            if token.token_type == TokenType::Const {
+                return self.parse_const_def();
+            }
+            if token.token_type == TokenType::Global {
+                return self.parse_global();
+            }
+            if token.token_type == TokenType::Var {
+                let start = self.tokens.current_span();
+                self.advance(); // consume 'var'
+                let name = self.expect_name("variable name")?;
+                return self.parse_assignment(name, start, true);
+            }
+            if token.token_type == TokenType::Import {
+                return self.parse_import();
+            }
         }
         // Check if this is a function definition
         if let Some(token) = self.current_token() {
@@ -115,134 +383,840 @@ impl Parser {
             if token.token_type == TokenType::If {
                 return self.parse_if_statement();
             }
+            if token.token_type == TokenType::Match {
+                return self.parse_match_statement();
+            }
+            // Branches of an if are consumed with it, so one here has no if to extend
+            if token.token_type == TokenType::Else || token.token_type == TokenType::Elif {
+                return Err(ParseError::DanglingElse {
+                    token: token.clone(),
+                });
+            }
             // Check if this is a while loop
             if token.token_type == TokenType::While {
-                return self.parse_while_statement();
+                return self.parse_while_statement(None, self.tokens.current_span());
+            }
+            if token.token_type == TokenType::Loop {
+                return self.parse_loop_statement(None, self.tokens.current_span());
+            }
+            if token.token_type == TokenType::Do {
+                return self.parse_do_while_statement(None, self.tokens.current_span());
+            }
+            if token.token_type == TokenType::Break || token.token_type == TokenType::Continue {
+                return self.parse_loop_control();
+            }
+            if token.token_type == TokenType::Return {
+                return self.parse_return();
+            }
+            // A brace starting a statement opens a block, not a map literal
+            if token.token_type == TokenType::LeftBrace {
+                let span = self.tokens.current_span();
+                let body = self.parse_function_body()?;
+                return Ok(Statement::Block { body, span });
+            }
+            if token.token_type == TokenType::LeftBracket && self.is_array_destructuring() {
+                return self.parse_array_destructuring();
             }
         }
 
         // Check if this is an assignment (identifier = expression or self.field = expression)
-        if let Some(token) = self.current_token() {
-            // Handle simple identifier assignment
-            if let TokenType::Identifier(name) = &token.token_type {
-                let name = name.clone();
-                // Look ahead to see if there's an equals sign
-                if self.position + 1 < self.tokens.len() {
-                    if let Some(next_token) = self.tokens.get(self.position + 1) {
-                        if next_token.token_type == TokenType::Equals {
-                            // This is an assignment
-                            self.advance(); // consume identifier
-                            self.advance(); // consume '='
-                            let value = self.parse_expression(0)?;
-
-                            // Consume optional newline or require EOF
-                            if let Some(token) = self.current_token() {
-                                if token.token_type == TokenType::Newline {
-                                    self.advance();
-                                }
-                            }
-
-                            return Ok(Statement::Assignment { name, value });
-                        }
-                    }
+        if let Some(TokenType::Identifier(name)) = self.peek(0).cloned() {
+            let name = name.to_string();
+            // `test` is only a keyword in front of a test name, so it stays usable
+            // as an identifier
+            if name == "test" {
+                if let Some(TokenType::String(test_name)) = self.peek(1).cloned() {
+                    return self.parse_test_block(test_name.to_string());
                 }
             }
+            if self.peek(1) == Some(&TokenType::Comma) {
+                return self.parse_parallel_assignment();
+            }
+            // `name: type = value` annotates a variable; anything else after the
+            // colon is a loop label
+            let has_colon = self.peek(1) == Some(&TokenType::Colon);
+            let is_annotated = has_colon
+                && matches!(
+                    self.peek(2),
+                    Some(TokenType::Identifier(ty)) if TypeAnnotation::from_name(ty).is_some()
+                );
+            if has_colon && !is_annotated {
+                return self.parse_labeled_loop(name);
+            }
+            if is_annotated || self.peek(1) == Some(&TokenType::Equals) {
+                let start = self.tokens.current_span();
+                self.advance(); // consume identifier
+                return self.parse_assignment(name, start, false);
+            }
+        }
 
-            // Handle self.field assignment
-            if token.token_type == TokenType::Self_ {
-                // Check if we have self.field = value
-                if self.position + 1 < self.tokens.len() {
-                    if let Some(dot_token) = self.tokens.get(self.position + 1) {
-                        if dot_token.token_type == TokenType::Dot {
-                            if let Some(field_token) = self.tokens.get(self.position + 2) {
-                                if let TokenType::Identifier(field) = &field_token.token_type {
-                                    let field = field.clone();
-                                    if let Some(equals_token) = self.tokens.get(self.position + 3) {
-                                        if equals_token.token_type == TokenType::Equals {
-                                            // This is a self.field assignment
-                                            self.advance(); // consume 'self'
-                                            self.advance(); // consume '.'
-                                            self.advance(); // consume field name
-                                            self.advance(); // consume '='
-                                            let value = self.parse_expression(0)?;
-
-                                            // Consume optional newline
-                                            if let Some(token) = self.current_token() {
-                                                if token.token_type == TokenType::Newline {
-                                                    self.advance();
-                                                }
-                                            }
-
-                                            return Ok(Statement::Assignment {
-                                                name: format!("self.{}", field),
-                                                value,
-                                            });
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
+        // Handle self.field = value
+        if self.check(&TokenType::Self_)
+            && self.peek(1) == Some(&TokenType::Dot)
+            && self.peek(3) == Some(&TokenType::Equals)
+        {
+            if let Some(TokenType::Identifier(field)) = self.peek(2).cloned() {
+                let start = self.tokens.current_span();
+                self.advance(); // consume 'self'
+                self.advance(); // consume '.'
+                self.advance(); // consume field name
+                let span = self.tokens.span_from(start);
+                self.advance(); // consume '='
+                let value = self.parse_expression(0)?;
+
+                // Consume optional newline
+                if self.check(&TokenType::Newline) {
+                    self.advance();
                 }
+
+                return Ok(Statement::Assignment {
+                    name: format!("self.{}", field),
+                    mutable: false,
+                    ty: None,
+                    value,
+                    span,
+                });
             }
         }
 
         // Otherwise, parse as expression statement
+        let start = self.tokens.current_span();
         let expr = self.parse_expression(0)?;
 
+        // A field read followed by '=' is the target of a field assignment
+        let is_assignment = self.check(&TokenType::Equals);
+        let stmt = match expr {
+            Expr::MethodCall {
+                object,
+                method,
+                args,
+                ..
+            } if is_assignment && args.is_empty() => {
+                let span = self.tokens.span_from(start);
+                self.advance(); // consume '='
+                Statement::FieldAssignment {
+                    object: Self::field_place(*object),
+                    field: method,
+                    value: self.parse_expression(0)?,
+                    span,
+                }
+            }
+            expr => Statement::Expression(expr),
+        };
+
         // Consume optional newline
-        if let Some(token) = self.current_token() {
-            if token.token_type == TokenType::Newline {
-                self.advance();
+        if self.check(&TokenType::Newline) {
+            self.advance();
+        }
+
+        Ok(stmt)
+    }
+
+    /// Turns the object of an assignment target into the place it names: the
+    /// argument-less method calls `a.b` parses to are field reads there
+    fn field_place(object: Expr) -> Expr {
+        match object {
+            Expr::MethodCall {
+                object,
+                method,
+                args,
+                span,
+            } if args.is_empty() => Expr::FieldAccess {
+                object: Box::new(Self::field_place(*object)),
+                field: method,
+                span,
+            },
+            Expr::Index {
+                object,
+                index,
+                span,
+            } => Expr::Index {
+                object: Box::new(Self::field_place(*object)),
+                index,
+                span,
+            },
+            object => object,
+        }
+    }
+
+    /// Parses a test block: test 'name' { body }
+    fn parse_test_block(&mut self, name: String) -> ParseResult<Statement> {
+        let start = self.tokens.current_span();
+        self.advance(); // consume 'test'
+        self.advance(); // consume name
+        let span = self.tokens.span_from(start);
+
+        self.skip_newlines();
+        let body = self.parse_function_body()?;
+
+        Ok(Statement::Test { name, body, span })
+    }
+
+    /// Parses a parallel assignment: a, b = b, a, or a destructuring one: a, b = pair
+    fn parse_parallel_assignment(&mut self) -> ParseResult<Statement> {
+        let start = self.tokens.current_span();
+        let mut names = Vec::new();
+
+        let span = loop {
+            let token = self
+                .current_token()
+                .ok_or_else(|| ParseError::UnexpectedEof {
+                    expected: "variable name".to_string(),
+                })?
+                .clone();
+            match token.token_type {
+                TokenType::Identifier(name) => names.push(name.to_string()),
+                _ => {
+                    return Err(ParseError::UnexpectedToken {
+                        expected: "variable name".to_string(),
+                        found: token,
+                    })
+                }
+            }
+            self.advance();
+
+            match self.current_token() {
+                Some(token) if token.token_type == TokenType::Comma => self.advance(),
+                Some(token) if token.token_type == TokenType::Equals => {
+                    let span = self.tokens.span_from(start);
+                    self.advance();
+                    break span;
+                }
+                Some(token) => {
+                    return Err(ParseError::UnexpectedToken {
+                        expected: "',' or '='".to_string(),
+                        found: token.clone(),
+                    })
+                }
+                None => {
+                    return Err(ParseError::UnexpectedEof {
+                        expected: "',' or '='".to_string(),
+                    })
+                }
             }
+        };
+
+        // Exactly one value per name, or a single tuple to destructure
+        let mut values = vec![self.parse_expression(0)?];
+        let destructures = !matches!(
+            self.current_token(),
+            Some(Token {
+                token_type: TokenType::Comma,
+                ..
+            })
+        );
+        while !destructures && values.len() < names.len() {
+            if !values.is_empty() {
+                match self.current_token() {
+                    Some(token) if token.token_type == TokenType::Comma => self.advance(),
+                    Some(token) => {
+                        return Err(ParseError::UnexpectedToken {
+                            expected: format!("{} values", names.len()),
+                            found: token.clone(),
+                        })
+                    }
+                    None => {
+                        return Err(ParseError::UnexpectedEof {
+                            expected: format!("{} values", names.len()),
+                        })
+                    }
+                }
+            }
+            values.push(self.parse_expression(0)?);
+        }
+
+        match self.current_token() {
+            Some(token) if token.token_type == TokenType::Newline => self.advance(),
+            Some(token) if token.token_type == TokenType::Comma => {
+                return Err(ParseError::UnexpectedToken {
+                    expected: format!("{} values", names.len()),
+                    found: token.clone(),
+                })
+            }
+            _ => {}
         }
 
-        Ok(Statement::Expression(expr))
+        Ok(Statement::ParallelAssignment {
+            names,
+            values,
+            span,
+        })
+    }
+
+    /// Checks if the tokens ahead are `[name, ...] =`, rather than an array literal
+    fn is_array_destructuring(&mut self) -> bool {
+        let mut offset = 1;
+        loop {
+            let is_name = matches!(self.peek(offset), Some(TokenType::Identifier(_)));
+            if !is_name {
+                return false;
+            }
+            match self.peek(offset + 1) {
+                Some(TokenType::Comma) => offset += 2,
+                Some(TokenType::RightBracket) => {
+                    return matches!(self.peek(offset + 2), Some(TokenType::Equals))
+                }
+                _ => return false,
+            }
+        }
     }
 
-    /// Parses a class definition: class Name
+    /// Parses an array destructuring: [a, b, c] = values
+    fn parse_array_destructuring(&mut self) -> ParseResult<Statement> {
+        let start = self.tokens.current_span();
+        self.advance(); // consume '['
+
+        let mut names = vec![self.expect_name("variable name")?];
+        while self.check(&TokenType::Comma) {
+            self.advance(); // consume ','
+            names.push(self.expect_name("variable name")?);
+        }
+        self.advance(); // consume ']'
+        let span = self.tokens.span_from(start);
+        self.advance(); // consume '='
+
+        let value = self.parse_expression(0)?;
+
+        // Consume optional newline
+        if self.check(&TokenType::Newline) {
+            self.advance();
+        }
+
+        Ok(Statement::ArrayDestructuring { names, value, span })
+    }
+
+    /// Parses the lines of a `##` doc comment and the function, method, or class
+    /// definition they document
+    fn parse_documented_def(&mut self) -> ParseResult<Statement> {
+        let mut lines = Vec::new();
+        while let Some(TokenType::DocComment(line)) = self.peek(0).cloned() {
+            lines.push(line);
+            self.advance();
+            self.skip_newlines();
+        }
+
+        let mut def = match self.current_token() {
+            Some(token) if token.token_type == TokenType::Fn => {
+                self.parse_function_or_method_def()?
+            }
+            Some(token) if token.token_type == TokenType::Class => self.parse_class_def()?,
+            Some(token) if token.token_type != TokenType::Eof => {
+                return Err(ParseError::UnexpectedToken {
+                    expected: "a function, method, or class after a doc comment".to_string(),
+                    found: token.clone(),
+                })
+            }
+            _ => {
+                return Err(ParseError::UnexpectedEof {
+                    expected: "a function, method, or class after a doc comment".to_string(),
+                })
+            }
+        };
+        if let Statement::FunctionDef { docs, .. }
+        | Statement::ClassDef { docs, .. }
+        | Statement::MethodDef { docs, .. } = &mut def
+        {
+            *docs = lines;
+        }
+        Ok(def)
+    }
+
+    /// Parses a class definition: class Name, optionally followed by < Parent, then
+    /// with Mixin, ..., then a body of field declarations
     fn parse_class_def(&mut self) -> ParseResult<Statement> {
+        let start = self.tokens.current_span();
+
         // Consume 'class' keyword
         self.advance();
 
         // Parse class name
-        let name = if let Some(token) = self.current_token() {
-            if let TokenType::Identifier(name) = &token.token_type {
-                let name = name.clone();
+        let name = self.expect_name("class name")?;
+
+        // Parse optional parent class
+        let parent = match self.current_token() {
+            Some(token) if token.token_type == TokenType::LessThan => {
+                self.advance(); // consume '<'
+                Some(self.expect_name("parent class name")?)
+            }
+            _ => None,
+        };
+
+        // Parse optional trait list
+        let traits = match self.current_token() {
+            Some(token) if token.token_type == TokenType::Colon => {
+                self.advance(); // consume ':'
+                self.parse_name_list("trait name")?
+            }
+            _ => Vec::new(),
+        };
+
+        // Parse optional mixin list
+        let mixins = match self.current_token() {
+            Some(token) if token.token_type == TokenType::With => {
+                self.advance(); // consume 'with'
+                self.parse_name_list("mixin name")?
+            }
+            _ => Vec::new(),
+        };
+        let span = self.tokens.span_from(start);
+
+        let fields = match self.current_token() {
+            Some(token) if token.token_type == TokenType::LeftBrace => self.parse_class_fields()?,
+            _ => Vec::new(),
+        };
+
+        // Consume optional newline after class definition
+        if self.check(&TokenType::Newline) {
+            self.advance();
+        }
+
+        Ok(Statement::ClassDef {
+            name,
+            parent,
+            traits,
+            mixins,
+            fields,
+            docs: Vec::new(),
+            span,
+        })
+    }
+
+    /// Parses the field declarations of a class body: { x, y: float }
+    ///
+    /// Fields are separated by commas or newlines.
+    fn parse_class_fields(&mut self) -> ParseResult<Vec<FieldDecl>> {
+        self.advance(); // consume '{'
+        let mut fields = Vec::new();
+
+        self.skip_newlines();
+        while !self.check_closing(TokenType::RightBrace, "'}'")? {
+            let start = self.tokens.current_span();
+            let name = self.expect_name("field name")?;
+            let span = self.tokens.span_from(start);
+            let ty = match self.current_token() {
+                Some(token) if token.token_type == TokenType::Colon => {
+                    self.advance(); // consume ':'
+                    Some(self.parse_type_annotation()?)
+                }
+                _ => None,
+            };
+            fields.push(FieldDecl { name, ty, span });
+
+            if self.check(&TokenType::Newline) {
+                self.skip_newlines();
+            } else {
+                self.expect_separator(TokenType::RightBrace, "',' or '}'")?;
+            }
+        }
+
+        self.advance(); // consume '}'
+        Ok(fields)
+    }
+
+    /// Parses a mixin definition: mixin Name
+    fn parse_mixin_def(&mut self) -> ParseResult<Statement> {
+        let start = self.tokens.current_span();
+
+        // Consume 'mixin' keyword
+        self.advance();
+
+        let name = self.expect_name("mixin name")?;
+        let span = self.tokens.span_from(start);
+
+        // Consume optional newline after mixin definition
+        if self.check(&TokenType::Newline) {
+            self.advance();
+        }
+
+        Ok(Statement::MixinDef { name, span })
+    }
+
+    /// Parses a trait definition: trait Name { fn method(params) -> type }
+    ///
+    /// The method signatures are separated by commas or newlines.
+    fn parse_trait_def(&mut self) -> ParseResult<Statement> {
+        let start = self.tokens.current_span();
+
+        // Consume 'trait' keyword
+        self.advance();
+
+        let name = self.expect_name("trait name")?;
+        let span = self.tokens.span_from(start);
+
+        self.skip_newlines();
+        match self.current_token() {
+            Some(token) if token.token_type == TokenType::LeftBrace => self.advance(),
+            Some(token) => {
+                return Err(ParseError::UnexpectedToken {
+                    expected: "'{'".to_string(),
+                    found: token.clone(),
+                })
+            }
+            None => {
+                return Err(ParseError::UnexpectedEof {
+                    expected: "'{'".to_string(),
+                })
+            }
+        }
+
+        let mut methods = Vec::new();
+        self.skip_newlines();
+        while !self.check_closing(TokenType::RightBrace, "'}'")? {
+            let start = self.tokens.current_span();
+            match self.current_token() {
+                Some(token) if token.token_type == TokenType::Fn => self.advance(),
+                Some(token) => {
+                    return Err(ParseError::UnexpectedToken {
+                        expected: "'fn' or '}'".to_string(),
+                        found: token.clone(),
+                    })
+                }
+                None => {
+                    return Err(ParseError::UnexpectedEof {
+                        expected: "'}'".to_string(),
+                    })
+                }
+            }
+            let name = self.expect_name("method name")?;
+            let span = self.tokens.span_from(start);
+            let parts = self.parse_function_signature(true)?;
+            methods.push(TraitMethod {
+                name,
+                params: parts.params,
+                param_types: parts.param_types,
+                variadic: parts.variadic,
+                return_type: parts.return_type,
+                span,
+            });
+
+            if self.check(&TokenType::Comma) {
                 self.advance();
-                name
+            }
+            self.skip_newlines();
+        }
+        self.advance(); // consume '}'
+
+        // Consume optional newline after trait definition
+        if self.check(&TokenType::Newline) {
+            self.advance();
+        }
+
+        Ok(Statement::TraitDef {
+            name,
+            methods,
+            span,
+        })
+    }
+
+    /// Parses an enum definition: enum Name { Variant, ... }
+    ///
+    /// Variants are separated by commas or newlines; an enum has at least one.
+    fn parse_enum_def(&mut self) -> ParseResult<Statement> {
+        let start = self.tokens.current_span();
+
+        // Consume 'enum' keyword
+        self.advance();
+
+        let name = self.expect_name("enum name")?;
+        let span = self.tokens.span_from(start);
+
+        self.skip_newlines();
+        self.expect(TokenType::LeftBrace, "'{'")?;
+
+        let mut variants = Vec::new();
+        self.skip_newlines();
+        loop {
+            variants.push(self.expect_name("variant name")?);
+            if self.check(&TokenType::Newline) {
+                self.skip_newlines();
             } else {
+                self.expect_separator(TokenType::RightBrace, "',' or '}'")?;
+            }
+            if self.check_closing(TokenType::RightBrace, "'}'")? {
+                break;
+            }
+        }
+        self.advance(); // consume '}'
+
+        // Consume optional newline after enum definition
+        if self.check(&TokenType::Newline) {
+            self.advance();
+        }
+
+        Ok(Statement::EnumDef {
+            name,
+            variants,
+            span,
+        })
+    }
+
+    /// Parses an import: import module, or import module { name, ... }
+    ///
+    /// The braces must start on the same line as the module name.
+    fn parse_import(&mut self) -> ParseResult<Statement> {
+        let start = self.tokens.current_span();
+        self.advance(); // consume 'import'
+
+        let module = self.expect_name("module name")?;
+        let span = self.tokens.span_from(start);
+
+        let mut names = Vec::new();
+        if self.check(&TokenType::LeftBrace) {
+            self.advance(); // consume '{'
+            self.skip_newlines();
+            loop {
+                names.push(self.expect_name("imported name")?);
+                if self.check(&TokenType::Newline) {
+                    self.skip_newlines();
+                } else {
+                    self.expect_separator(TokenType::RightBrace, "',' or '}'")?;
+                }
+                if self.check_closing(TokenType::RightBrace, "'}'")? {
+                    break;
+                }
+            }
+            self.advance(); // consume '}'
+        }
+
+        // Consume optional newline after the import
+        if self.check(&TokenType::Newline) {
+            self.advance();
+        }
+
+        Ok(Statement::Import {
+            module,
+            names,
+            span,
+        })
+    }
+
+    /// Consumes a token of the given type, or reports what was expected
+    fn expect(&mut self, token_type: TokenType, expected: &str) -> ParseResult<()> {
+        match self.current_token() {
+            Some(token) if token.token_type == token_type => {
+                self.advance();
+                Ok(())
+            }
+            Some(token) if token.token_type != TokenType::Eof => Err(ParseError::UnexpectedToken {
+                expected: expected.to_string(),
+                found: token.clone(),
+            }),
+            _ => Err(ParseError::UnexpectedEof {
+                expected: expected.to_string(),
+            }),
+        }
+    }
+
+    /// Parses a match statement: match subject { pattern, ... => body ... }
+    ///
+    /// An arm's body is a braced block or a single statement on the same line.
+    fn parse_match_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.tokens.current_span();
+        self.advance(); // consume 'match'
+
+        let subject = self.parse_expression(0)?;
+        let span = self.tokens.span_from(start);
+
+        self.skip_newlines();
+        self.expect(TokenType::LeftBrace, "'{'")?;
+
+        let mut arms = Vec::new();
+        self.skip_newlines();
+        while !self.check_closing(TokenType::RightBrace, "'}'")? {
+            let mut patterns = vec![self.parse_pattern()?];
+            while self.check(&TokenType::Comma) {
+                self.advance(); // consume ','
+                self.skip_newlines();
+                patterns.push(self.parse_pattern()?);
+            }
+            self.expect(TokenType::FatArrow, "'=>'")?;
+
+            let body = match self.current_token() {
+                Some(token) if token.token_type == TokenType::LeftBrace => {
+                    self.parse_function_body()?
+                }
+                _ => vec![self.parse_statement()?],
+            };
+            arms.push(MatchArm { patterns, body });
+            self.skip_separators();
+        }
+        self.advance(); // consume '}'
+
+        Ok(Statement::Match {
+            subject,
+            arms,
+            span,
+        })
+    }
+
+    /// Parses a match pattern: `Enum.Variant`, an integer, string, or bool literal,
+    /// or `_`
+    fn parse_pattern(&mut self) -> ParseResult<Pattern> {
+        const EXPECTED: &str = "pattern ('Enum.Variant', a literal, or '_')";
+        let token = self
+            .current_token()
+            .ok_or_else(|| ParseError::UnexpectedEof {
+                expected: EXPECTED.to_string(),
+            })?
+            .clone();
+        let pattern = match &token.token_type {
+            TokenType::Identifier(name) if name == "_" => Pattern::Wildcard,
+            TokenType::Identifier(name) => {
+                let enum_name = name.to_string();
+                self.advance();
+                self.expect(TokenType::Dot, "'.' after enum name")?;
+                let variant = self.expect_name("variant name")?;
+                return Ok(Pattern::Variant { enum_name, variant });
+            }
+            TokenType::Integer(value) => Pattern::Literal(Expr::Integer {
+                value: *value,
+                span: Span::of(&token),
+            }),
+            TokenType::String(value) => Pattern::Literal(Expr::String {
+                value: value.to_string(),
+                span: Span::of(&token),
+            }),
+            TokenType::Bool(value) => Pattern::Literal(Expr::Bool {
+                value: *value,
+                span: Span::of(&token),
+            }),
+            _ => {
+                return Err(ParseError::UnexpectedToken {
+                    expected: EXPECTED.to_string(),
+                    found: token,
+                })
+            }
+        };
+        self.advance();
+        Ok(pattern)
+    }
+
+    /// Parses a comma-separated list of names, as in `with Clickable, Drawable`
+    fn parse_name_list(&mut self, expected: &str) -> ParseResult<Vec<String>> {
+        let mut names = vec![self.expect_name(expected)?];
+        while let Some(token) = self.current_token() {
+            if token.token_type != TokenType::Comma {
+                break;
+            }
+            self.advance(); // consume ','
+            names.push(self.expect_name(expected)?);
+        }
+        Ok(names)
+    }
+
+    /// Parses a constant declaration: const NAME = expression
+    fn parse_const_def(&mut self) -> ParseResult<Statement> {
+        let start = self.tokens.current_span();
+        self.advance(); // consume 'const'
+
+        let name = self.expect_name("constant name")?;
+        let span = self.tokens.span_from(start);
+
+        match self.current_token() {
+            Some(token) if token.token_type == TokenType::Equals => self.advance(),
+            Some(token) => {
                 return Err(ParseError::UnexpectedToken {
-                    expected: "class name".to_string(),
+                    expected: "'=' after constant name".to_string(),
                     found: token.clone(),
-                });
+                })
             }
+            None => {
+                return Err(ParseError::UnexpectedEof {
+                    expected: "'=' after constant name".to_string(),
+                })
+            }
+        }
+        let value = self.parse_expression(0)?;
+
+        // Consume optional newline
+        if self.check(&TokenType::Newline) {
+            self.advance();
+        }
+
+        Ok(Statement::ConstDef { name, value, span })
+    }
+
+    /// Parses a global variable declaration: global name = expression
+    fn parse_global(&mut self) -> ParseResult<Statement> {
+        let start = self.tokens.current_span();
+        self.advance(); // consume 'global'
+
+        let name = self.expect_name("variable name")?;
+        let span = self.tokens.span_from(start);
+        self.expect(TokenType::Equals, "'=' after global variable name")?;
+        let value = self.parse_expression(0)?;
+
+        // Consume optional newline
+        if self.check(&TokenType::Newline) {
+            self.advance();
+        }
+
+        Ok(Statement::Global { name, value, span })
+    }
+
+    /// Parses the rest of a variable assignment after its name: an optional
+    /// `: type` annotation, then `= value`
+    fn parse_assignment(
+        &mut self,
+        name: String,
+        start: Span,
+        mutable: bool,
+    ) -> ParseResult<Statement> {
+        let span = self.tokens.span_from(start);
+        let ty = if self.check(&TokenType::Colon) {
+            self.advance(); // consume ':'
+            Some(self.parse_type_annotation()?)
         } else {
-            return Err(ParseError::UnexpectedEof {
-                expected: "class name".to_string(),
-            });
+            None
         };
+        self.expect(TokenType::Equals, "'='")?;
+        let value = self.parse_expression(0)?;
 
-        // Consume optional newline after class definition
+        // Consume optional newline or require EOF
+        if self.check(&TokenType::Newline) {
+            self.advance();
+        }
+
+        Ok(Statement::Assignment {
+            name,
+            mutable,
+            ty,
+            value,
+            span,
+        })
+    }
+
+    /// Consumes an identifier and returns its name
+    fn expect_name(&mut self, expected: &str) -> ParseResult<String> {
         if let Some(token) = self.current_token() {
-            if token.token_type == TokenType::Newline {
+            if let TokenType::Identifier(name) = &token.token_type {
+                let name = name.to_string();
                 self.advance();
+                Ok(name)
+            } else {
+                Err(ParseError::UnexpectedToken {
+                    expected: expected.to_string(),
+                    found: token.clone(),
+                })
             }
+        } else {
+            Err(ParseError::UnexpectedEof {
+                expected: expected.to_string(),
+            })
         }
-
-        Ok(Statement::ClassDef { name })
     }
 
     /// Parses a function or method definition
     /// fn name(params) { body } or fn ClassName > methodName(params) { body }
     fn parse_function_or_method_def(&mut self) -> ParseResult<Statement> {
+        let start = self.tokens.current_span();
         // Consume 'fn' keyword
         self.advance();
 
         // Parse first identifier (function name or class name)
         let first_name = if let Some(token) = self.current_token() {
             if let TokenType::Identifier(name) = &token.token_type {
-                let name = name.clone();
+                let name = name.to_string();
                 self.advance();
                 name
             } else {
@@ -256,17 +1230,26 @@ impl Parser {
                 expected: "function or class name".to_string(),
             });
         };
+        let name_span = self.tokens.span_from(start);
 
-        // Check if this is a method definition (look for '>')
+        // The header may continue on the next line: a '>', '(', or '{' always follows
+        self.skip_newlines();
+
+        // Check if this is a method definition (look for '>', or '>>' for a static one)
         if let Some(token) = self.current_token() {
-            if token.token_type == TokenType::GreaterThan {
+            if matches!(
+                token.token_type,
+                TokenType::GreaterThan | TokenType::ShiftRight
+            ) {
                 // This is a method definition (using > as arrow)
-                self.advance(); // consume '>'
+                let is_static = token.token_type == TokenType::ShiftRight;
+                self.advance(); // consume '>' or '>>'
+                self.skip_newlines();
 
                 // Parse method name
                 let method_name = if let Some(token) = self.current_token() {
                     if let TokenType::Identifier(name) = &token.token_type {
-                        let name = name.clone();
+                        let name = name.to_string();
                         self.advance();
                         name
                     } else {
@@ -282,33 +1265,75 @@ impl Parser {
                 };
 
                 let class_name = first_name;
-                let (params, body) = self.parse_function_params_and_body()?;
+                let span = self.tokens.span_from(start);
+                let parts = self.parse_function_params_and_body(true)?;
 
                 return Ok(Statement::MethodDef {
                     class_name,
                     method_name,
-                    params,
-                    body,
+                    is_static,
+                    params: parts.params,
+                    param_types: parts.param_types,
+                    variadic: parts.variadic,
+                    return_type: parts.return_type,
+                    body: parts.body,
+                    docs: Vec::new(),
+                    span,
                 });
             }
         }
 
         // This is a regular function definition
         let name = first_name;
-        let (params, body) = self.parse_function_params_and_body()?;
+        let span = name_span;
+        let parts = self.parse_function_params_and_body(true)?;
+
+        Ok(Statement::FunctionDef {
+            name,
+            params: parts.params,
+            param_types: parts.param_types,
+            variadic: parts.variadic,
+            return_type: parts.return_type,
+            body: parts.body,
+            docs: Vec::new(),
+            span,
+        })
+    }
 
-        Ok(Statement::FunctionDef { name, params, body })
+    /// Parses function parameters and body (shared by functions, methods, and lambdas)
+    ///
+    /// Definitions (`is_definition`) may also annotate their parameters and return
+    /// type and end with a variadic parameter (`nums...`); lambdas may not.
+    fn parse_function_params_and_body(
+        &mut self,
+        is_definition: bool,
+    ) -> ParseResult<FunctionParts> {
+        let mut parts = self.parse_function_signature(is_definition)?;
+
+        // `fn double(x) = x * 2` is short for a body holding just the expression
+        if is_definition && self.check(&TokenType::Equals) {
+            self.advance(); // consume '='
+            parts.body = vec![Statement::Expression(self.parse_expression(0)?)];
+            return Ok(parts);
+        }
+
+        // Skip newlines before '{'
+        self.skip_newlines();
+
+        parts.body = self.parse_function_body()?;
+
+        Ok(parts)
     }
 
-    /// Parses function parameters and body (shared by functions and methods)
-    fn parse_function_params_and_body(&mut self) -> ParseResult<(Vec<String>, Vec<Statement>)> {
+    /// Parses the parameter list and return type of a function header, both optional
+    fn parse_function_signature(&mut self, is_definition: bool) -> ParseResult<FunctionParts> {
+        let mut parts = FunctionParts::default();
+        self.skip_newlines();
+
         // Check if there's a '(' - if not, skip parameter parsing
         if let Some(token) = self.current_token() {
             if token.token_type != TokenType::LeftParen {
-                // No parameters, skip to body parsing
-                self.skip_newlines();
-                let body = self.parse_function_body()?;
-                return Ok((Vec::new(), body));
+                return Ok(parts);
             }
         }
 
@@ -328,7 +1353,6 @@ impl Parser {
         }
 
         // Parse parameters
-        let mut params = Vec::new();
         loop {
             // Skip newlines
             self.skip_newlines();
@@ -340,9 +1364,41 @@ impl Parser {
                 }
 
                 if let TokenType::Identifier(param) = &token.token_type {
-                    params.push(param.clone());
+                    parts.params.push(param.to_string());
                     self.advance();
 
+                    let ty = if is_definition && self.check(&TokenType::Colon) {
+                        self.advance(); // consume ':'
+                        Some(self.parse_type_annotation()?)
+                    } else {
+                        None
+                    };
+                    parts.param_types.push(ty);
+
+                    // Check for `...`, which only the last parameter may have
+                    if is_definition && self.check(&TokenType::DotDotDot) {
+                        self.advance();
+                        parts.variadic = true;
+                        self.skip_newlines();
+                        match self.current_token() {
+                            Some(token) if token.token_type == TokenType::RightParen => {
+                                self.advance();
+                                break;
+                            }
+                            Some(token) => {
+                                return Err(ParseError::UnexpectedToken {
+                                    expected: "')' after variadic parameter".to_string(),
+                                    found: token.clone(),
+                                })
+                            }
+                            None => {
+                                return Err(ParseError::UnexpectedEof {
+                                    expected: "')'".to_string(),
+                                })
+                            }
+                        }
+                    }
+
                     // Check for comma or right paren
                     self.skip_newlines();
                     if let Some(token) = self.current_token() {
@@ -375,12 +1431,38 @@ impl Parser {
             }
         }
 
-        // Skip newlines before '{'
+        // Optional return type: -> type, which may start on the next line
         self.skip_newlines();
+        if is_definition && self.check(&TokenType::Arrow) {
+            self.advance(); // consume '->'
+            parts.return_type = Some(self.parse_type_annotation()?);
+        }
 
-        let body = self.parse_function_body()?;
+        Ok(parts)
+    }
 
-        Ok((params, body))
+    /// Parses a type name: int, float, string, or bool
+    fn parse_type_annotation(&mut self) -> ParseResult<TypeAnnotation> {
+        const EXPECTED: &str = "type ('int', 'float', 'string', or 'bool')";
+        let token = self
+            .current_token()
+            .ok_or_else(|| ParseError::UnexpectedEof {
+                expected: EXPECTED.to_string(),
+            })?;
+        let ty = match &token.token_type {
+            TokenType::Identifier(name) => TypeAnnotation::from_name(name),
+            _ => None,
+        };
+        match ty {
+            Some(ty) => {
+                self.advance();
+                Ok(ty)
+            }
+            None => Err(ParseError::UnexpectedToken {
+                expected: EXPECTED.to_string(),
+                found: token.clone(),
+            }),
+        }
     }
 
     /// Parses a function body (the statements between { and })
@@ -413,7 +1495,7 @@ impl Parser {
 
                 let stmt = self.parse_statement()?;
                 body.push(stmt);
-                self.skip_newlines();
+                self.skip_separators();
             } else {
                 return Err(ParseError::UnexpectedEof {
                     expected: "'}'".to_string(),
@@ -422,22 +1504,42 @@ impl Parser {
         }
 
         // Consume optional newline after function
-        if let Some(token) = self.current_token() {
-            if token.token_type == TokenType::Newline {
-                self.advance();
-            }
+        if self.check(&TokenType::Newline) {
+            self.advance();
         }
 
         Ok(body)
     }
 
+    /// Parses the condition of an if, elif, or while, which may bind a name to its
+    /// value: `if n = parse(s)`
+    fn parse_condition(&mut self) -> ParseResult<Expr> {
+        if !matches!(self.peek(0), Some(TokenType::Identifier(_)))
+            || self.peek(1) != Some(&TokenType::Equals)
+        {
+            return self.parse_expression(0);
+        }
+
+        let start = self.tokens.current_span();
+        let name = self.expect_name("variable name")?;
+        self.advance(); // consume '='
+        let value = self.parse_expression(0)?;
+        Ok(Expr::Binding {
+            name,
+            value: Box::new(value),
+            span: self.tokens.span_from(start),
+        })
+    }
+
     /// Parses an if statement with optional elif and else branches
     fn parse_if_statement(&mut self) -> ParseResult<Statement> {
         // Consume 'if'
+        let start = self.tokens.current_span();
         self.advance();
 
         // Parse condition
-        let condition = self.parse_expression(0)?;
+        let condition = self.parse_condition()?;
+        let span = self.tokens.span_from(start);
 
         // Skip newlines before '{'
         self.skip_newlines();
@@ -470,7 +1572,7 @@ impl Parser {
 
                 let stmt = self.parse_statement()?;
                 then_branch.push(stmt);
-                self.skip_newlines();
+                self.skip_separators();
             } else {
                 return Err(ParseError::UnexpectedEof {
                     expected: "'}'".to_string(),
@@ -482,12 +1584,17 @@ impl Parser {
         let mut elif_branches = Vec::new();
         self.skip_newlines();
 
-        while let Some(token) = self.current_token() {
-            if token.token_type == TokenType::Elif {
+        while let Some(token_type) = self.current_token().map(|token| token.token_type.clone()) {
+            // `else if` is another spelling of `elif`
+            let is_else_if = token_type == TokenType::Else && self.peek(1) == Some(&TokenType::If);
+            if token_type == TokenType::Elif || is_else_if {
+                if is_else_if {
+                    self.advance(); // consume 'else'
+                }
                 self.advance();
 
                 // Parse elif condition
-                let elif_condition = self.parse_expression(0)?;
+                let elif_condition = self.parse_condition()?;
 
                 // Skip newlines before '{'
                 self.skip_newlines();
@@ -520,7 +1627,7 @@ impl Parser {
 
                         let stmt = self.parse_statement()?;
                         elif_body.push(stmt);
-                        self.skip_newlines();
+                        self.skip_separators();
                     } else {
                         return Err(ParseError::UnexpectedEof {
                             expected: "'}'".to_string(),
@@ -571,7 +1678,7 @@ impl Parser {
 
                         let stmt = self.parse_statement()?;
                         else_body.push(stmt);
-                        self.skip_newlines();
+                        self.skip_separators();
                     } else {
                         return Err(ParseError::UnexpectedEof {
                             expected: "'}'".to_string(),
@@ -587,28 +1694,131 @@ impl Parser {
             None
         };
 
-        // Consume optional newline after if statement
-        if let Some(token) = self.current_token() {
-            if token.token_type == TokenType::Newline {
-                self.advance();
-            }
+        // Consume optional newline after if statement
+        if self.check(&TokenType::Newline) {
+            self.advance();
+        }
+
+        Ok(Statement::If {
+            condition,
+            then_branch,
+            elif_branches,
+            else_branch,
+            span,
+        })
+    }
+
+    /// Parses a labeled loop: label: while condition { body }, label: loop { body }, or
+    /// label: do { body } while condition
+    fn parse_labeled_loop(&mut self, label: String) -> ParseResult<Statement> {
+        let start = self.tokens.current_span();
+        self.advance(); // consume label
+        self.advance(); // consume ':'
+
+        match self.current_token() {
+            Some(token) if token.token_type == TokenType::While => {
+                self.parse_while_statement(Some(label), start)
+            }
+            Some(token) if token.token_type == TokenType::Loop => {
+                self.parse_loop_statement(Some(label), start)
+            }
+            Some(token) if token.token_type == TokenType::Do => {
+                self.parse_do_while_statement(Some(label), start)
+            }
+            Some(token) => Err(ParseError::UnexpectedToken {
+                expected: "loop after label".to_string(),
+                found: token.clone(),
+            }),
+            None => Err(ParseError::UnexpectedEof {
+                expected: "loop after label".to_string(),
+            }),
+        }
+    }
+
+    /// Parses `break` or `continue`, with an optional loop label on the same line
+    fn parse_loop_control(&mut self) -> ParseResult<Statement> {
+        let keyword = self
+            .current_token()
+            .map(|token| token.token_type.clone())
+            .unwrap_or(TokenType::Eof);
+        let start = self.tokens.current_span();
+        self.advance();
+
+        let label = match self.current_token().map(|token| &token.token_type) {
+            Some(TokenType::Identifier(label)) => {
+                let label = label.to_string();
+                self.advance();
+                Some(label)
+            }
+            _ => None,
+        };
+        let span = self.tokens.span_from(start);
+
+        // Consume optional newline
+        if self.check(&TokenType::Newline) {
+            self.advance();
+        }
+
+        if keyword == TokenType::Break {
+            Ok(Statement::Break { label, span })
+        } else {
+            Ok(Statement::Continue { label, span })
+        }
+    }
+
+    /// Parses `return`, with an optional value on the same line
+    ///
+    /// Several comma-separated values are returned as a tuple: `return a, b`.
+    fn parse_return(&mut self) -> ParseResult<Statement> {
+        let start = self.tokens.current_span();
+        self.advance(); // consume 'return'
+
+        let value = match self.current_token().map(|token| &token.token_type) {
+            None
+            | Some(
+                TokenType::Newline | TokenType::Semicolon | TokenType::RightBrace | TokenType::Eof,
+            ) => None,
+            Some(_) => {
+                let mut values = vec![self.parse_expression(0)?];
+                while let Some(TokenType::Comma) =
+                    self.current_token().map(|token| &token.token_type)
+                {
+                    self.advance(); // consume ','
+                    values.push(self.parse_expression(0)?);
+                }
+                match values.len() {
+                    1 => values.pop(),
+                    _ => Some(Expr::Tuple {
+                        span: self.tokens.span_from(values[0].span()),
+                        elements: values,
+                    }),
+                }
+            }
+        };
+        let span = self.tokens.span_from(start);
+
+        // Consume optional newline
+        if self.check(&TokenType::Newline) {
+            self.advance();
         }
 
-        Ok(Statement::If {
-            condition,
-            then_branch,
-            elif_branches,
-            else_branch,
-        })
+        Ok(Statement::Return { value, span })
     }
 
     /// Parses a while loop
-    fn parse_while_statement(&mut self) -> ParseResult<Statement> {
+    ///
+    /// `start` is the span of the label, or of the keyword when there is none
+    fn parse_while_statement(
+        &mut self,
+        label: Option<String>,
+        start: Span,
+    ) -> ParseResult<Statement> {
         // Consume 'while'
         self.advance();
 
         // Parse condition
-        let condition = self.parse_expression(0)?;
+        let condition = self.parse_condition()?;
+        let span = self.tokens.span_from(start);
 
         // Skip newlines before '{'
         self.skip_newlines();
@@ -641,7 +1851,7 @@ impl Parser {
 
                 let stmt = self.parse_statement()?;
                 body.push(stmt);
-                self.skip_newlines();
+                self.skip_separators();
             } else {
                 return Err(ParseError::UnexpectedEof {
                     expected: "'}'".to_string(),
@@ -650,22 +1860,92 @@ impl Parser {
         }
 
         // Consume optional newline after while statement
-        if let Some(token) = self.current_token() {
-            if token.token_type == TokenType::Newline {
-                self.advance();
+        if self.check(&TokenType::Newline) {
+            self.advance();
+        }
+
+        Ok(Statement::While {
+            condition,
+            body,
+            label,
+            span,
+        })
+    }
+
+    /// Parses an infinite loop: loop { body }
+    ///
+    /// `start` is the span of the label, or of the keyword when there is none
+    fn parse_loop_statement(
+        &mut self,
+        label: Option<String>,
+        start: Span,
+    ) -> ParseResult<Statement> {
+        self.advance(); // consume 'loop'
+        let span = self.tokens.span_from(start);
+        self.skip_newlines();
+        let body = self.parse_function_body()?;
+
+        // Consume optional newline
+        if self.check(&TokenType::Newline) {
+            self.advance();
+        }
+
+        Ok(Statement::Loop { body, label, span })
+    }
+
+    /// Parses a post-condition loop: do { body } while condition
+    ///
+    /// `start` is the span of the label, or of the keyword when there is none
+    fn parse_do_while_statement(
+        &mut self,
+        label: Option<String>,
+        start: Span,
+    ) -> ParseResult<Statement> {
+        self.advance(); // consume 'do'
+        self.skip_newlines();
+        let body = self.parse_function_body()?;
+
+        // The condition may start on the line after the closing brace
+        self.skip_newlines();
+        match self.current_token() {
+            Some(token) if token.token_type == TokenType::While => self.advance(),
+            Some(token) => {
+                return Err(ParseError::UnexpectedToken {
+                    expected: "'while' after do-loop body".to_string(),
+                    found: token.clone(),
+                })
+            }
+            None => {
+                return Err(ParseError::UnexpectedEof {
+                    expected: "'while' after do-loop body".to_string(),
+                })
             }
         }
+        let condition = self.parse_expression(0)?;
+        let span = self.tokens.span_from(start);
+
+        // Consume optional newline
+        if self.check(&TokenType::Newline) {
+            self.advance();
+        }
 
-        Ok(Statement::While { condition, body })
+        Ok(Statement::DoWhile {
+            body,
+            condition,
+            label,
+            span,
+        })
     }
 
     /// Legacy method for parsing a single expression (for backwards compatibility)
     pub fn parse_expression_only(&mut self) -> ParseResult<Expr> {
-        self.parse_expression(0)
+        let result = self.parse_expression(0);
+        self.lex_error_or(result)
     }
 
-    /// Parses a primary expression (integer, string, identifier, function call, or grouped expression)
+    /// Parses a primary expression (literal, identifier, function call, unary operation, or grouped expression)
     fn parse_primary(&mut self) -> ParseResult<Expr> {
+        let start = self.tokens.current_span();
         let token = self
             .current_token()
             .ok_or_else(|| ParseError::UnexpectedEof {
@@ -676,85 +1956,89 @@ impl Parser {
             TokenType::Integer(n) => {
                 let value = *n;
                 self.advance();
-                Ok(Expr::Integer(value))
+                Ok(Expr::Integer { value, span: start })
             }
             TokenType::Float(n) => {
                 let value = *n;
                 self.advance();
-                Ok(Expr::Float(value))
+                Ok(Expr::Float { value, span: start })
+            }
+            TokenType::Bool(b) => {
+                let value = *b;
+                self.advance();
+                Ok(Expr::Bool { value, span: start })
+            }
+            TokenType::Nil => {
+                self.advance();
+                Ok(Expr::Nil { span: start })
             }
             TokenType::String(s) => {
-                let value = s.clone();
+                let value = s.to_string();
                 self.advance();
-                Ok(Expr::String(value))
+                Ok(Expr::String { value, span: start })
             }
             TokenType::Self_ => {
                 self.advance();
-                Ok(Expr::Identifier("self".to_string()))
+                Ok(Expr::Identifier {
+                    name: "self".into(),
+                    span: start,
+                })
             }
             TokenType::Identifier(name) => {
-                let name = name.clone();
+                let name = *name;
                 self.advance();
 
                 // Check if this is a function call
                 if let Some(token) = self.current_token() {
                     if token.token_type == TokenType::LeftParen {
-                        self.advance(); // consume '('
-
-                        let mut args = Vec::new();
-
-                        // Parse arguments
-                        if let Some(token) = self.current_token() {
-                            if token.token_type != TokenType::RightParen {
-                                loop {
-                                    args.push(self.parse_expression(0)?);
-
-                                    if let Some(token) = self.current_token() {
-                                        if token.token_type == TokenType::Comma {
-                                            self.advance(); // consume ','
-                                            continue;
-                                        } else if token.token_type == TokenType::RightParen {
-                                            break;
-                                        } else {
-                                            return Err(ParseError::UnexpectedToken {
-                                                expected: "',' or ')'".to_string(),
-                                                found: token.clone(),
-                                            });
-                                        }
-                                    } else {
-                                        return Err(ParseError::UnexpectedEof {
-                                            expected: "')'".to_string(),
-                                        });
-                                    }
-                                }
-                            }
-                        }
-
-                        let token =
-                            self.current_token()
-                                .ok_or_else(|| ParseError::UnexpectedEof {
-                                    expected: "')'".to_string(),
-                                })?;
-
-                        if token.token_type != TokenType::RightParen {
-                            return Err(ParseError::UnexpectedToken {
-                                expected: "')'".to_string(),
-                                found: token.clone(),
-                            });
-                        }
-
-                        self.advance(); // consume ')'
-                        return Ok(Expr::FunctionCall { name, args });
+                        let args = self.parse_call_args(true)?;
+                        let span = self.tokens.span_from(start);
+                        return Ok(Expr::FunctionCall {
+                            name: name.to_string(),
+                            args,
+                            span,
+                        });
                     }
                 }
 
                 // Otherwise, it's just an identifier
-                Ok(Expr::Identifier(name))
+                Ok(Expr::Identifier { name, span: start })
+            }
+            TokenType::Bang | TokenType::Not | TokenType::Minus | TokenType::Plus => {
+                let op = match token.token_type {
+                    TokenType::Minus => UnaryOperator::Negate,
+                    TokenType::Plus => UnaryOperator::Plus,
+                    _ => UnaryOperator::Not,
+                };
+                self.advance(); // consume the operator
+                let operand = self.parse_expression(UNARY_PRECEDENCE)?;
+                Ok(Expr::UnaryOp {
+                    op,
+                    operand: Box::new(operand),
+                    span: self.tokens.span_from(start),
+                })
+            }
+            TokenType::Pipe | TokenType::DoublePipe => self.parse_lambda(),
+            TokenType::Fn => {
+                self.advance(); // consume 'fn'
+                let parts = self.parse_function_params_and_body(false)?;
+                Ok(Expr::Lambda {
+                    params: parts.params,
+                    body: parts.body,
+                    span: self.tokens.span_from(start),
+                })
             }
+            TokenType::LeftBracket => self.parse_array_literal(),
+            TokenType::Cond => self.parse_cond(),
+            TokenType::LeftBrace => self.parse_map_literal(),
             TokenType::LeftParen => {
                 self.advance(); // consume '('
                 let expr = self.parse_expression(0)?;
 
+                if self.check(&TokenType::Comma) {
+                    return self.parse_tuple_literal(expr, start);
+                }
+
                 let token = self
                     .current_token()
                     .ok_or_else(|| ParseError::UnexpectedEof {
@@ -769,7 +2053,10 @@ impl Parser {
                 }
 
                 self.advance(); // consume ')'
-                Ok(Expr::Grouped(Box::new(expr)))
+                Ok(Expr::Grouped {
+                    inner: Box::new(expr),
+                    span: self.tokens.span_from(start),
+                })
             }
             _ => Err(ParseError::InvalidExpression {
                 token: token.clone(),
@@ -777,6 +2064,315 @@ impl Parser {
         }
     }
 
+    /// Checks if the current token is a run of newlines followed by a '.', which
+    /// continues a method chain rather than ending the statement
+    fn continues_chain(&mut self) -> bool {
+        let mut n = 0;
+        while let Some(token_type) = self.peek(n) {
+            match token_type {
+                TokenType::Newline => n += 1,
+                TokenType::Dot => return n > 0,
+                _ => return false,
+            }
+        }
+        false
+    }
+
+    /// Parses the parenthesized arguments of a call: (arg1, arg2, ...)
+    ///
+    /// Only function calls take named arguments; method arguments are positional.
+    fn parse_call_args(&mut self, allow_named: bool) -> ParseResult<Vec<Expr>> {
+        self.advance(); // consume '('
+
+        let mut args = Vec::new();
+
+        // Parse arguments
+        if let Some(token) = self.current_token() {
+            if token.token_type != TokenType::RightParen {
+                loop {
+                    args.push(if allow_named {
+                        self.parse_argument()?
+                    } else {
+                        self.parse_expression(0)?
+                    });
+
+                    if let Some(token) = self.current_token() {
+                        if token.token_type == TokenType::Comma {
+                            self.advance(); // consume ','
+
+                            // A trailing comma may come before the ')'
+                            if self.check(&TokenType::RightParen) {
+                                break;
+                            }
+                            continue;
+                        } else if token.token_type == TokenType::RightParen {
+                            break;
+                        } else {
+                            return Err(ParseError::UnexpectedToken {
+                                expected: "',' or ')'".to_string(),
+                                found: token.clone(),
+                            });
+                        }
+                    } else {
+                        return Err(ParseError::UnexpectedEof {
+                            expected: "')'".to_string(),
+                        });
+                    }
+                }
+            }
+        }
+
+        let token = self
+            .current_token()
+            .ok_or_else(|| ParseError::UnexpectedEof {
+                expected: "')'".to_string(),
+            })?;
+
+        if token.token_type != TokenType::RightParen {
+            return Err(ParseError::UnexpectedToken {
+                expected: "')'".to_string(),
+                found: token.clone(),
+            });
+        }
+
+        self.advance(); // consume ')'
+        Ok(args)
+    }
+
+    /// Parses one argument of a function call, which may name its parameter: `width = 3`
+    fn parse_argument(&mut self) -> ParseResult<Expr> {
+        let is_named = self.peek(1) == Some(&TokenType::Equals);
+        if let Some(TokenType::Identifier(name)) =
+            self.current_token().map(|token| token.token_type.clone())
+        {
+            if is_named {
+                let start = self.tokens.current_span();
+                self.advance(); // consume the name
+                self.advance(); // consume '='
+                let value = self.parse_expression(0)?;
+                return Ok(Expr::NamedArg {
+                    name: name.to_string(),
+                    value: Box::new(value),
+                    span: self.tokens.span_from(start),
+                });
+            }
+        }
+
+        self.parse_expression(0)
+    }
+
+    /// Parses a short lambda: |params| expression, or || expression without parameters
+    fn parse_lambda(&mut self) -> ParseResult<Expr> {
+        let start = self.tokens.current_span();
+        let mut params = Vec::new();
+
+        // `||` lexes as a single token, and is a lambda without parameters
+        let no_params = matches!(
+            self.current_token(),
+            Some(Token {
+                token_type: TokenType::DoublePipe,
+                ..
+            })
+        );
+        self.advance(); // consume '|' or '||'
+
+        if !no_params {
+            loop {
+                let token = self
+                    .current_token()
+                    .ok_or_else(|| ParseError::UnexpectedEof {
+                        expected: "'|'".to_string(),
+                    })?
+                    .clone();
+                match token.token_type {
+                    // No parameters at all, or a trailing comma after the last one
+                    TokenType::Pipe => break,
+                    TokenType::Identifier(param) => params.push(param.to_string()),
+                    _ => {
+                        return Err(ParseError::UnexpectedToken {
+                            expected: "parameter name".to_string(),
+                            found: token,
+                        })
+                    }
+                }
+                self.advance();
+
+                match self.current_token() {
+                    Some(token) if token.token_type == TokenType::Comma => self.advance(),
+                    Some(token) if token.token_type == TokenType::Pipe => break,
+                    Some(token) => {
+                        return Err(ParseError::UnexpectedToken {
+                            expected: "',' or '|'".to_string(),
+                            found: token.clone(),
+                        })
+                    }
+                    None => {
+                        return Err(ParseError::UnexpectedEof {
+                            expected: "',' or '|'".to_string(),
+                        })
+                    }
+                }
+            }
+            self.advance(); // consume closing '|'
+        }
+
+        let body = self.parse_expression(0)?;
+        Ok(Expr::Lambda {
+            params,
+            body: vec![Statement::Expression(body)],
+            span: self.tokens.span_from(start),
+        })
+    }
+
+    /// Parses an array literal: [element, ...]
+    ///
+    /// Newlines are allowed between elements.
+    fn parse_array_literal(&mut self) -> ParseResult<Expr> {
+        let start = self.tokens.current_span();
+        self.advance(); // consume '['
+        let mut elements = Vec::new();
+
+        self.skip_newlines();
+        while !self.check_closing(TokenType::RightBracket, "']'")? {
+            elements.push(self.parse_expression(0)?);
+            self.skip_newlines();
+            self.expect_separator(TokenType::RightBracket, "',' or ']'")?;
+        }
+
+        self.advance(); // consume ']'
+        Ok(Expr::Array {
+            elements,
+            span: self.tokens.span_from(start),
+        })
+    }
+
+    /// Parses the rest of a tuple literal, after its first element: (first, element, ...)
+    ///
+    /// `start` is the span of the opening parenthesis.
+    fn parse_tuple_literal(&mut self, first: Expr, start: Span) -> ParseResult<Expr> {
+        let mut elements = vec![first];
+
+        self.expect_separator(TokenType::RightParen, "',' or ')'")?;
+        while !self.check_closing(TokenType::RightParen, "')'")? {
+            elements.push(self.parse_expression(0)?);
+            self.expect_separator(TokenType::RightParen, "',' or ')'")?;
+        }
+
+        self.advance(); // consume ')'
+        Ok(Expr::Tuple {
+            elements,
+            span: self.tokens.span_from(start),
+        })
+    }
+
+    /// Parses a map literal: {key: value, ...}
+    ///
+    /// Newlines are allowed between entries.
+    fn parse_map_literal(&mut self) -> ParseResult<Expr> {
+        let start = self.tokens.current_span();
+        self.advance(); // consume '{'
+        let mut entries = Vec::new();
+
+        self.skip_newlines();
+        while !self.check_closing(TokenType::RightBrace, "'}'")? {
+            let key = self.parse_expression(0)?;
+
+            match self.current_token() {
+                Some(token) if token.token_type == TokenType::Colon => self.advance(),
+                Some(token) => {
+                    return Err(ParseError::UnexpectedToken {
+                        expected: "':'".to_string(),
+                        found: token.clone(),
+                    })
+                }
+                None => {
+                    return Err(ParseError::UnexpectedEof {
+                        expected: "':'".to_string(),
+                    })
+                }
+            }
+
+            self.skip_newlines();
+            let value = self.parse_expression(0)?;
+            entries.push((key, value));
+            self.skip_newlines();
+            self.expect_separator(TokenType::RightBrace, "',' or '}'")?;
+        }
+
+        self.advance(); // consume '}'
+        Ok(Expr::Map {
+            entries,
+            span: self.tokens.span_from(start),
+        })
+    }
+
+    /// Parses a cond expression: cond { condition { body } ... else { body } }
+    ///
+    /// Each branch starts on its own line, and the `else` branch comes last.
+    fn parse_cond(&mut self) -> ParseResult<Expr> {
+        let start = self.tokens.current_span();
+        self.advance(); // consume 'cond'
+        self.expect(TokenType::LeftBrace, "'{' after 'cond'")?;
+        self.skip_newlines();
+
+        let mut branches = Vec::new();
+        loop {
+            if self.check(&TokenType::Else) {
+                self.advance(); // consume 'else'
+                let else_branch = self.parse_function_body()?;
+                self.skip_newlines();
+                self.expect(TokenType::RightBrace, "'}' after the 'else' branch")?;
+                return Ok(Expr::Cond {
+                    branches,
+                    else_branch,
+                    span: self.tokens.span_from(start),
+                });
+            }
+            if let Some(token) = self
+                .current_token()
+                .filter(|token| token.token_type == TokenType::RightBrace)
+            {
+                return Err(ParseError::UnexpectedToken {
+                    expected: "an 'else' branch".to_string(),
+                    found: token.clone(),
+                });
+            }
+            let condition = self.parse_expression(0)?;
+            let body = self.parse_function_body()?;
+            branches.push((condition, body));
+            self.skip_newlines();
+        }
+    }
+
+    /// Checks if the current token closes a collection literal
+    fn check_closing(&self, closing: TokenType, expected: &str) -> ParseResult<bool> {
+        match self.current_token() {
+            Some(token) if token.token_type != TokenType::Eof => Ok(token.token_type == closing),
+            _ => Err(ParseError::UnexpectedEof {
+                expected: expected.to_string(),
+            }),
+        }
+    }
+
+    /// Consumes the ',' after a collection element, unless the collection closes next
+    fn expect_separator(&mut self, closing: TokenType, expected: &str) -> ParseResult<()> {
+        match self.current_token() {
+            Some(token) if token.token_type == TokenType::Comma => {
+                self.advance();
+                self.skip_newlines();
+                Ok(())
+            }
+            Some(token) if token.token_type == closing => Ok(()),
+            Some(token) if token.token_type != TokenType::Eof => Err(ParseError::UnexpectedToken {
+                expected: expected.to_string(),
+                found: token.clone(),
+            }),
+            _ => Err(ParseError::UnexpectedEof {
+                expected: expected.to_string(),
+            }),
+        }
+    }
+
     /// Converts a token type to a binary operator
     fn token_to_operator(token_type: &TokenType) -> Option<BinaryOperator> {
         match token_type {
@@ -788,17 +2384,38 @@ impl Parser {
             TokenType::NotEqual => Some(BinaryOperator::NotEqual),
             TokenType::LessThan => Some(BinaryOperator::LessThan),
             TokenType::LessThanOrEqual => Some(BinaryOperator::LessThanOrEqual),
+            TokenType::In => Some(BinaryOperator::In),
             TokenType::GreaterThan => Some(BinaryOperator::GreaterThan),
             TokenType::GreaterThanOrEqual => Some(BinaryOperator::GreaterThanOrEqual),
+            TokenType::DoubleAmpersand | TokenType::And => Some(BinaryOperator::And),
+            TokenType::DoublePipe | TokenType::Or => Some(BinaryOperator::Or),
+            TokenType::Ampersand => Some(BinaryOperator::BitAnd),
+            TokenType::Pipe => Some(BinaryOperator::BitOr),
+            TokenType::Caret => Some(BinaryOperator::BitXor),
+            TokenType::ShiftLeft => Some(BinaryOperator::ShiftLeft),
+            TokenType::ShiftRight => Some(BinaryOperator::ShiftRight),
             _ => None,
         }
     }
 
     /// Parses an expression using precedence climbing
     fn parse_expression(&mut self, min_precedence: u8) -> ParseResult<Expr> {
+        self.nested(|parser| parser.parse_operators(min_precedence))
+    }
+
+    /// Parses a primary expression and the operators that follow it
+    fn parse_operators(&mut self, min_precedence: u8) -> ParseResult<Expr> {
         let mut left = self.parse_primary()?;
+        let mut links = 0;
 
-        while let Some(token) = self.current_token() {
+        loop {
+            // A method chain may continue on the next line: `items\n  .first()`
+            if self.continues_chain() {
+                self.skip_newlines();
+            }
+            let Some(token) = self.current_token() else {
+                break;
+            };
             if self.is_at_end() {
                 break;
             }
@@ -811,14 +2428,28 @@ impl Parser {
                 break;
             }
 
+            // Call the value of any other expression, such as a grouped lambda or the
+            // function another call returns: (make_adder(2))(5)
+            if token.token_type == TokenType::LeftParen {
+                self.chain_link(&mut links)?;
+                let args = self.parse_call_args(false)?;
+                left = Expr::Call {
+                    span: self.tokens.span_from(left.span()),
+                    callee: Box::new(left),
+                    args,
+                };
+                continue;
+            }
+
             // Handle dot operator for field access and method calls (highest precedence)
             if token.token_type == TokenType::Dot {
+                self.chain_link(&mut links)?;
                 self.advance(); // consume '.'
 
                 // Parse the field or method name
                 let field = if let Some(token) = self.current_token() {
                     if let TokenType::Identifier(name) = &token.token_type {
-                        let name = name.clone();
+                        let name = name.to_string();
                         self.advance();
                         name
                     } else {
@@ -834,57 +2465,16 @@ impl Parser {
                 };
 
                 // Check if this is a method call (has parentheses)
-                let mut args = Vec::new();
-                if let Some(token) = self.current_token() {
-                    if token.token_type == TokenType::LeftParen {
-                        self.advance(); // consume '('
-
-                        // Parse arguments
-                        if let Some(token) = self.current_token() {
-                            if token.token_type != TokenType::RightParen {
-                                loop {
-                                    args.push(self.parse_expression(0)?);
-
-                                    if let Some(token) = self.current_token() {
-                                        if token.token_type == TokenType::Comma {
-                                            self.advance(); // consume ','
-                                            continue;
-                                        } else if token.token_type == TokenType::RightParen {
-                                            break;
-                                        } else {
-                                            return Err(ParseError::UnexpectedToken {
-                                                expected: "',' or ')'".to_string(),
-                                                found: token.clone(),
-                                            });
-                                        }
-                                    } else {
-                                        return Err(ParseError::UnexpectedEof {
-                                            expected: "')'".to_string(),
-                                        });
-                                    }
-                                }
-                            }
-                        }
-
-                        let token =
-                            self.current_token()
-                                .ok_or_else(|| ParseError::UnexpectedEof {
-                                    expected: "')'".to_string(),
-                                })?;
-
-                        if token.token_type != TokenType::RightParen {
-                            return Err(ParseError::UnexpectedToken {
-                                expected: "')'".to_string(),
-                                found: token.clone(),
-                            });
-                        }
-
-                        self.advance(); // consume ')'
+                let args = match self.current_token() {
+                    Some(token) if token.token_type == TokenType::LeftParen => {
+                        self.parse_call_args(false)?
                     }
-                }
+                    _ => Vec::new(),
+                };
 
                 // In Grit, obj.method is always a method call (with or without parens)
                 left = Expr::MethodCall {
+                    span: self.tokens.span_from(left.span()),
                     object: Box::new(left),
                     method: field,
                     args,
@@ -892,6 +2482,56 @@ impl Parser {
                 continue;
             }
 
+            // Handle indexing and slicing, which bind as tightly as the dot operator
+            if token.token_type == TokenType::LeftBracket {
+                self.chain_link(&mut links)?;
+                self.advance(); // consume '['
+                let start = if self.check(&TokenType::DotDot) {
+                    None
+                } else {
+                    Some(Box::new(self.parse_expression(0)?))
+                };
+                let is_slice = start.is_none() || self.check(&TokenType::DotDot);
+                let mut end = None;
+                if is_slice {
+                    self.advance(); // consume '..'
+                    if !self.check(&TokenType::RightBracket) {
+                        end = Some(Box::new(self.parse_expression(0)?));
+                    }
+                }
+
+                match self.current_token() {
+                    Some(token) if token.token_type == TokenType::RightBracket => self.advance(),
+                    Some(token) => {
+                        return Err(ParseError::UnexpectedToken {
+                            expected: "']'".to_string(),
+                            found: token.clone(),
+                        })
+                    }
+                    None => {
+                        return Err(ParseError::UnexpectedEof {
+                            expected: "']'".to_string(),
+                        })
+                    }
+                }
+
+                let span = self.tokens.span_from(left.span());
+                left = match start {
+                    Some(index) if !is_slice => Expr::Index {
+                        object: Box::new(left),
+                        index,
+                        span,
+                    },
+                    start => Expr::Slice {
+                        object: Box::new(left),
+                        start,
+                        end,
+                        span,
+                    },
+                };
+                continue;
+            }
+
             let op = match Self::token_to_operator(&token.token_type) {
                 Some(op) => op,
                 None => break,
@@ -902,11 +2542,18 @@ impl Parser {
                 break;
             }
 
+            self.chain_link(&mut links)?;
             self.advance(); // consume operator
 
-            let right = self.parse_expression(precedence + 1)?;
+            // Left-associative operators only accept tighter-binding operators on the right
+            let next_precedence = match op.associativity() {
+                Associativity::Left => precedence + 1,
+                Associativity::Right => precedence,
+            };
+            let right = self.parse_expression(next_precedence)?;
 
             left = Expr::BinaryOp {
+                span: self.tokens.span_from(left.span()),
                 left: Box::new(left),
                 op,
                 right: Box::new(right),
@@ -0,0 +1,166 @@
+use super::ast::BinaryOperator;
+
+/// How a chain of operators with the same precedence groups
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Associativity {
+    /// `a - b - c` groups as `(a - b) - c`
+    Left,
+    /// `a op b op c` groups as `a op (b op c)`; every binary operator Grit has so
+    /// far is left-associative
+    Right,
+}
+
+/// Parsing and rendering data for a single binary operator
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperatorInfo {
+    pub operator: BinaryOperator,
+    pub symbol: &'static str,
+    pub precedence: u8,
+    pub associativity: Associativity,
+}
+
+/// The operator table used by the parser and code generator, from loosest to tightest binding
+///
+/// Tools that format, highlight, or document Grit source should read operator
+/// data from here rather than keeping their own copy.
+pub const OPERATORS: &[OperatorInfo] = &[
+    OperatorInfo {
+        operator: BinaryOperator::Or,
+        symbol: "||",
+        precedence: 0,
+        associativity: Associativity::Left,
+    },
+    OperatorInfo {
+        operator: BinaryOperator::And,
+        symbol: "&&",
+        precedence: 1,
+        associativity: Associativity::Left,
+    },
+    OperatorInfo {
+        operator: BinaryOperator::EqualEqual,
+        symbol: "==",
+        precedence: 2,
+        associativity: Associativity::Left,
+    },
+    OperatorInfo {
+        operator: BinaryOperator::NotEqual,
+        symbol: "!=",
+        precedence: 2,
+        associativity: Associativity::Left,
+    },
+    OperatorInfo {
+        operator: BinaryOperator::LessThan,
+        symbol: "<",
+        precedence: 2,
+        associativity: Associativity::Left,
+    },
+    OperatorInfo {
+        operator: BinaryOperator::LessThanOrEqual,
+        symbol: "<=",
+        precedence: 2,
+        associativity: Associativity::Left,
+    },
+    OperatorInfo {
+        operator: BinaryOperator::GreaterThan,
+        symbol: ">",
+        precedence: 2,
+        associativity: Associativity::Left,
+    },
+    OperatorInfo {
+        operator: BinaryOperator::GreaterThanOrEqual,
+        symbol: ">=",
+        precedence: 2,
+        associativity: Associativity::Left,
+    },
+    OperatorInfo {
+        operator: BinaryOperator::In,
+        symbol: "in",
+        precedence: 2,
+        associativity: Associativity::Left,
+    },
+    OperatorInfo {
+        operator: BinaryOperator::BitOr,
+        symbol: "|",
+        precedence: 3,
+        associativity: Associativity::Left,
+    },
+    OperatorInfo {
+        operator: BinaryOperator::BitXor,
+        symbol: "^",
+        precedence: 4,
+        associativity: Associativity::Left,
+    },
+    OperatorInfo {
+        operator: BinaryOperator::BitAnd,
+        symbol: "&",
+        precedence: 5,
+        associativity: Associativity::Left,
+    },
+    OperatorInfo {
+        operator: BinaryOperator::ShiftLeft,
+        symbol: "<<",
+        precedence: 6,
+        associativity: Associativity::Left,
+    },
+    OperatorInfo {
+        operator: BinaryOperator::ShiftRight,
+        symbol: ">>",
+        precedence: 6,
+        associativity: Associativity::Left,
+    },
+    OperatorInfo {
+        operator: BinaryOperator::Add,
+        symbol: "+",
+        precedence: 7,
+        associativity: Associativity::Left,
+    },
+    OperatorInfo {
+        operator: BinaryOperator::Subtract,
+        symbol: "-",
+        precedence: 7,
+        associativity: Associativity::Left,
+    },
+    OperatorInfo {
+        operator: BinaryOperator::Multiply,
+        symbol: "*",
+        precedence: 8,
+        associativity: Associativity::Left,
+    },
+    OperatorInfo {
+        operator: BinaryOperator::Divide,
+        symbol: "/",
+        precedence: 8,
+        associativity: Associativity::Left,
+    },
+];
+
+/// Precedence of prefix operators such as `!`, which bind tighter than any binary operator
+pub const UNARY_PRECEDENCE: u8 = 9;
+
+/// Returns the table entry for an operator
+pub fn operator_info(operator: &BinaryOperator) -> &'static OperatorInfo {
+    OPERATORS
+        .iter()
+        .find(|info| info.operator == *operator)
+        .expect("every binary operator has an entry in OPERATORS")
+}
+
+/// Looks up an operator by its source symbol (e.g. `"<="`)
+pub fn find_by_symbol(symbol: &str) -> Option<&'static OperatorInfo> {
+    OPERATORS.iter().find(|info| info.symbol == symbol)
+}
+
+/// Returns all operators sharing the given precedence level
+pub fn operators_at(precedence: u8) -> impl Iterator<Item = &'static OperatorInfo> {
+    OPERATORS
+        .iter()
+        .filter(move |info| info.precedence == precedence)
+}
+
+/// Returns the distinct precedence levels in ascending order
+pub fn precedence_levels() -> Vec<u8> {
+    let mut levels: Vec<u8> = OPERATORS.iter().map(|info| info.precedence).collect();
+    levels.sort_unstable();
+    levels.dedup();
+    levels
+}
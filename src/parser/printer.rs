@@ -0,0 +1,499 @@
+//! Canonical Grit source for an AST
+//!
+//! Unlike `Display`, which summarizes a node for messages, the printer writes
+//! complete source that parses back into the same tree: bodies are indented by two
+//! spaces, definitions are separated by blank lines, and parentheses appear only
+//! where the tree has a grouped expression or precedence requires them.
+
+use super::ast::{param_list, return_suffix};
+use super::precedence::{Associativity, UNARY_PRECEDENCE};
+use super::{Expr, MatchArm, Pattern, Program, Statement, UnaryOperator};
+
+/// Indentation of each nested body
+const INDENT: &str = "  ";
+
+/// Prints a whole program, ending with a newline
+pub fn print_program(program: &Program) -> String {
+    let mut printer = Printer::default();
+    for (i, stmt) in program.statements.iter().enumerate() {
+        if i > 0 && (is_definition(stmt) || is_definition(&program.statements[i - 1])) {
+            printer.out.push('\n');
+        }
+        printer.statement(stmt);
+    }
+    printer.out
+}
+
+/// Prints a single statement, with any nested bodies, ending with a newline
+pub fn print_statement(stmt: &Statement) -> String {
+    let mut printer = Printer::default();
+    printer.statement(stmt);
+    printer.out
+}
+
+/// Prints an expression
+pub fn print_expr(expr: &Expr) -> String {
+    Printer::default().expr(expr, 0)
+}
+
+/// Checks if a statement is a definition, which blank lines set apart
+fn is_definition(stmt: &Statement) -> bool {
+    matches!(
+        stmt,
+        Statement::FunctionDef { .. }
+            | Statement::ClassDef { .. }
+            | Statement::MixinDef { .. }
+            | Statement::TraitDef { .. }
+            | Statement::EnumDef { .. }
+            | Statement::MethodDef { .. }
+            | Statement::Test { .. }
+    )
+}
+
+/// How tightly an expression binds; a subexpression binding more loosely than its
+/// position needs is wrapped in parentheses
+fn binding_power(expr: &Expr) -> u8 {
+    match expr {
+        // These run to the end of the expression, so only a list position holds them
+        Expr::Lambda { .. } | Expr::Binding { .. } | Expr::NamedArg { .. } => 0,
+        Expr::BinaryOp { op, .. } => op.precedence() + 1,
+        Expr::UnaryOp { .. } => UNARY_PRECEDENCE + 1,
+        _ => UNARY_PRECEDENCE + 2,
+    }
+}
+
+/// Writes a string literal, escaping what the lexer unescapes
+fn string_literal(value: &str) -> String {
+    let mut literal = String::from("'");
+    for ch in value.chars() {
+        match ch {
+            '\'' => literal.push_str("\\'"),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\t' => literal.push_str("\\t"),
+            '\r' => literal.push_str("\\r"),
+            _ => literal.push(ch),
+        }
+    }
+    literal.push('\'');
+    literal
+}
+
+#[derive(Default)]
+struct Printer {
+    out: String,
+    depth: usize,
+}
+
+impl Printer {
+    /// Starts a new line at the current indentation
+    fn line(&mut self, text: &str) {
+        for _ in 0..self.depth {
+            self.out.push_str(INDENT);
+        }
+        self.out.push_str(text);
+    }
+
+    /// Writes `{`, the body one level deeper, and the closing `}`, which is left
+    /// open so an `elif`, `else`, or `while` can follow it
+    fn body(&mut self, body: &[Statement]) {
+        if body.is_empty() {
+            self.out.push_str("{}");
+            return;
+        }
+        self.out.push_str("{\n");
+        self.depth += 1;
+        for stmt in body {
+            self.statement(stmt);
+        }
+        self.depth -= 1;
+        self.line("}");
+    }
+
+    fn statement(&mut self, stmt: &Statement) {
+        match stmt {
+            Statement::FunctionDef {
+                name,
+                params,
+                param_types,
+                variadic,
+                return_type,
+                body,
+                docs,
+                ..
+            } => {
+                self.docs(docs);
+                self.line(&format!(
+                    "fn {}({}){} ",
+                    name,
+                    param_list(params, param_types, *variadic),
+                    return_suffix(return_type)
+                ));
+                self.body(body);
+            }
+            Statement::MethodDef {
+                class_name,
+                method_name,
+                is_static,
+                params,
+                param_types,
+                variadic,
+                return_type,
+                body,
+                docs,
+                ..
+            } => {
+                self.docs(docs);
+                self.line(&format!(
+                    "fn {} {} {}({}){} ",
+                    class_name,
+                    if *is_static { ">>" } else { ">" },
+                    method_name,
+                    param_list(params, param_types, *variadic),
+                    return_suffix(return_type)
+                ));
+                self.body(body);
+            }
+            Statement::ClassDef { docs, .. } => {
+                self.docs(docs);
+                self.line(&stmt.to_string());
+            }
+            Statement::TraitDef { name, methods, .. } => {
+                self.line(&format!("trait {} ", name));
+                if methods.is_empty() {
+                    self.out.push_str("{}");
+                } else {
+                    self.out.push_str("{\n");
+                    self.depth += 1;
+                    for method in methods {
+                        self.line(&format!("{}\n", method));
+                    }
+                    self.depth -= 1;
+                    self.line("}");
+                }
+            }
+            Statement::MixinDef { .. }
+            | Statement::EnumDef { .. }
+            | Statement::Import { .. }
+            | Statement::Break { .. }
+            | Statement::Continue { .. }
+            | Statement::Return { value: None, .. } => self.line(&stmt.to_string()),
+            Statement::Assignment {
+                name,
+                mutable,
+                ty,
+                value,
+                ..
+            } => {
+                let keyword = if *mutable { "var " } else { "" };
+                let annotation = ty.map(|ty| format!(": {}", ty)).unwrap_or_default();
+                let value = self.expr(value, 0);
+                self.line(&format!("{}{}{} = {}", keyword, name, annotation, value));
+            }
+            Statement::FieldAssignment {
+                object,
+                field,
+                value,
+                ..
+            } => {
+                let object = self.expr(object, UNARY_PRECEDENCE + 2);
+                let value = self.expr(value, 0);
+                self.line(&format!("{}.{} = {}", object, field, value));
+            }
+            Statement::ConstDef { name, value, .. } => {
+                let value = self.expr(value, 0);
+                self.line(&format!("const {} = {}", name, value));
+            }
+            Statement::Global { name, value, .. } => {
+                let value = self.expr(value, 0);
+                self.line(&format!("global {} = {}", name, value));
+            }
+            Statement::ParallelAssignment { names, values, .. } => {
+                let values = self.list(values);
+                self.line(&format!("{} = {}", names.join(", "), values));
+            }
+            Statement::ArrayDestructuring { names, value, .. } => {
+                let value = self.expr(value, 0);
+                self.line(&format!("[{}] = {}", names.join(", "), value));
+            }
+            Statement::Block { body, .. } => {
+                self.line("");
+                self.body(body);
+            }
+            Statement::If {
+                condition,
+                then_branch,
+                elif_branches,
+                else_branch,
+                ..
+            } => {
+                let condition = self.expr(condition, 0);
+                self.line(&format!("if {} ", condition));
+                self.body(then_branch);
+                for (condition, branch) in elif_branches {
+                    let condition = self.expr(condition, 0);
+                    self.out.push_str(&format!(" elif {} ", condition));
+                    self.body(branch);
+                }
+                if let Some(branch) = else_branch {
+                    self.out.push_str(" else ");
+                    self.body(branch);
+                }
+            }
+            Statement::Match { subject, arms, .. } => {
+                let subject = self.expr(subject, 0);
+                self.line(&format!("match {} {{\n", subject));
+                self.depth += 1;
+                for arm in arms {
+                    self.match_arm(arm);
+                }
+                self.depth -= 1;
+                self.line("}");
+            }
+            Statement::While {
+                condition,
+                body,
+                label,
+                ..
+            } => {
+                let condition = self.expr(condition, 0);
+                self.line(&format!("{}while {} ", label_prefix(label), condition));
+                self.body(body);
+            }
+            Statement::Loop { body, label, .. } => {
+                self.line(&format!("{}loop ", label_prefix(label)));
+                self.body(body);
+            }
+            Statement::DoWhile {
+                body,
+                condition,
+                label,
+                ..
+            } => {
+                self.line(&format!("{}do ", label_prefix(label)));
+                self.body(body);
+                let condition = self.expr(condition, 0);
+                self.out.push_str(&format!(" while {}", condition));
+            }
+            Statement::Return {
+                value: Some(value), ..
+            } => {
+                let value = self.expr(value, 0);
+                self.line(&format!("return {}", value));
+            }
+            Statement::Test { name, body, .. } => {
+                self.line(&format!("test {} ", string_literal(name)));
+                self.body(body);
+            }
+            Statement::Expression(expr) => {
+                let expr = self.expr(expr, 0);
+                self.line(&expr);
+            }
+        }
+        self.out.push('\n');
+    }
+
+    /// Writes the `##` lines documenting a definition
+    fn docs(&mut self, docs: &[String]) {
+        for doc in docs {
+            if doc.is_empty() {
+                self.line("##\n");
+            } else {
+                self.line(&format!("## {}\n", doc));
+            }
+        }
+    }
+
+    /// Writes a match arm, keeping a lone statement on the arm's line
+    fn match_arm(&mut self, arm: &MatchArm) {
+        let patterns: Vec<String> = arm
+            .patterns
+            .iter()
+            .map(|pattern| match pattern {
+                Pattern::Literal(value) => self.expr(value, 0),
+                pattern => pattern.to_string(),
+            })
+            .collect();
+        self.line(&format!("{} => ", patterns.join(", ")));
+        match arm.body.as_slice() {
+            [stmt] if !matches!(stmt, Statement::Block { .. }) => {
+                // The statement starts mid-line, so it skips its own indentation
+                let printed = print_nested(stmt, self.depth);
+                self.out.push_str(printed.trim_start());
+            }
+            body => {
+                self.body(body);
+                self.out.push('\n');
+            }
+        }
+    }
+
+    /// Writes the values of a list, separated by commas
+    fn list(&self, exprs: &[Expr]) -> String {
+        exprs
+            .iter()
+            .map(|expr| self.expr(expr, 0))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Writes an expression, in parentheses if it binds more loosely than `min`
+    fn expr(&self, expr: &Expr, min: u8) -> String {
+        let code = self.bare_expr(expr);
+        if binding_power(expr) < min {
+            format!("({})", code)
+        } else {
+            code
+        }
+    }
+
+    fn bare_expr(&self, expr: &Expr) -> String {
+        // The object of a field access, method call, index, or call binds tightest
+        let postfix = UNARY_PRECEDENCE + 2;
+        match expr {
+            Expr::Integer { value, .. } => value.to_string(),
+            Expr::Float { value, .. } => format!("{:?}", value),
+            Expr::Bool { value, .. } => value.to_string(),
+            Expr::Nil { .. } => "nil".to_string(),
+            Expr::String { value, .. } => string_literal(value),
+            Expr::Identifier { name, .. } => name.to_string(),
+            Expr::BinaryOp {
+                left, op, right, ..
+            } => {
+                let power = op.precedence() + 1;
+                let (left_min, right_min) = match op.associativity() {
+                    Associativity::Left => (power, power + 1),
+                    Associativity::Right => (power + 1, power),
+                };
+                format!(
+                    "{} {} {}",
+                    self.expr(left, left_min),
+                    op,
+                    self.expr(right, right_min)
+                )
+            }
+            Expr::UnaryOp { op, operand, .. } => {
+                let operand = self.expr(operand, UNARY_PRECEDENCE + 1);
+                // `- -x` rather than `--x`
+                let space = if *op != UnaryOperator::Not && operand.starts_with(op.symbol()) {
+                    " "
+                } else {
+                    ""
+                };
+                format!("{}{}{}", op, space, operand)
+            }
+            Expr::Grouped { inner, .. } => format!("({})", self.expr(inner, 0)),
+            Expr::Array { elements, .. } => format!("[{}]", self.list(elements)),
+            Expr::Tuple { elements, .. } => format!("({})", self.list(elements)),
+            Expr::Map { entries, .. } => {
+                let entries: Vec<String> = entries
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", self.expr(key, 0), self.expr(value, 0)))
+                    .collect();
+                format!("{{{}}}", entries.join(", "))
+            }
+            Expr::Index { object, index, .. } => {
+                format!("{}[{}]", self.expr(object, postfix), self.expr(index, 0))
+            }
+            Expr::Slice {
+                object, start, end, ..
+            } => {
+                let bound = |bound: &Option<Box<Expr>>| {
+                    bound
+                        .as_ref()
+                        .map(|bound| self.expr(bound, 0))
+                        .unwrap_or_default()
+                };
+                format!(
+                    "{}[{}..{}]",
+                    self.expr(object, postfix),
+                    bound(start),
+                    bound(end)
+                )
+            }
+            Expr::FunctionCall { name, args, .. } => format!("{}({})", name, self.list(args)),
+            Expr::Call { callee, args, .. } => {
+                format!("{}({})", self.expr(callee, postfix), self.list(args))
+            }
+            Expr::NamedArg { name, value, .. } | Expr::Binding { name, value, .. } => {
+                format!("{} = {}", name, self.expr(value, 0))
+            }
+            Expr::FieldAccess { object, field, .. } => {
+                format!("{}.{}", self.expr(object, postfix), field)
+            }
+            Expr::MethodCall {
+                object,
+                method,
+                args,
+                ..
+            } => format!(
+                "{}.{}({})",
+                self.expr(object, postfix),
+                method,
+                self.list(args)
+            ),
+            Expr::Cond {
+                branches,
+                else_branch,
+                ..
+            } => {
+                let mut code = String::from("cond {\n");
+                let indent = INDENT.repeat(self.depth + 1);
+                for (condition, body) in branches {
+                    code.push_str(&format!(
+                        "{}{} {}\n",
+                        indent,
+                        self.expr(condition, 0),
+                        self.nested_body(body, self.depth + 1)
+                    ));
+                }
+                code.push_str(&format!(
+                    "{}else {}\n{}}}",
+                    indent,
+                    self.nested_body(else_branch, self.depth + 1),
+                    INDENT.repeat(self.depth)
+                ));
+                code
+            }
+            Expr::Lambda { params, body, .. } => match body.as_slice() {
+                [Statement::Expression(expr)] => {
+                    format!("|{}| {}", params.join(", "), self.expr(expr, 0))
+                }
+                body => format!(
+                    "fn({}) {}",
+                    params.join(", "),
+                    self.nested_body(body, self.depth)
+                ),
+            },
+        }
+    }
+
+    /// Writes a body inside an expression, indented as if it started at `depth`
+    fn nested_body(&self, body: &[Statement], depth: usize) -> String {
+        let mut printer = Printer {
+            out: String::new(),
+            depth,
+        };
+        printer.body(body);
+        printer.out
+    }
+}
+
+/// Prints a statement at the given depth, for statements nested in an expression
+/// or a match arm
+fn print_nested(stmt: &Statement, depth: usize) -> String {
+    let mut printer = Printer {
+        out: String::new(),
+        depth,
+    };
+    printer.statement(stmt);
+    printer.out
+}
+
+/// Writes a loop label and its colon, if there is one
+fn label_prefix(label: &Option<String>) -> String {
+    label
+        .as_ref()
+        .map(|label| format!("{}: ", label))
+        .unwrap_or_default()
+}
@@ -0,0 +1,1321 @@
+use super::diagnostics::Diagnostic;
+use super::format::check_print_call;
+use super::symbols::SymbolTable;
+use super::types::{Type, TypeEnv};
+use crate::lexer::Span;
+use crate::parser::ast::eval::{eval_with, EvalError, Value};
+use crate::parser::{
+    BinaryOperator, Expr, FieldDecl, MatchArm, Pattern, Program, Statement, TypeAnnotation,
+};
+use std::collections::HashMap;
+
+/// Runs the semantic checks over a program and returns everything they report
+pub fn check_program(program: &Program) -> Vec<Diagnostic> {
+    let symbols = SymbolTable::from_program(program);
+    let mut checker = Checker {
+        env: TypeEnv::new(&symbols),
+        diagnostics: Vec::new(),
+        loops: Vec::new(),
+        depth: 0,
+        consts: Vec::new(),
+        const_values: HashMap::new(),
+        class: None,
+        static_method: false,
+        in_function: false,
+    };
+
+    checker.check_block(&program.statements);
+    checker.diagnostics
+}
+
+/// Walks statements while tracking variable types, collecting diagnostics
+struct Checker<'a> {
+    env: TypeEnv<'a>,
+    diagnostics: Vec<Diagnostic>,
+    /// Enclosing loops, innermost last
+    loops: Vec<EnclosingLoop>,
+    /// How many blocks or function bodies enclose the current statement
+    depth: usize,
+    /// Constants declared so far
+    consts: Vec<String>,
+    /// The values of the constants declared so far that evaluated successfully
+    const_values: HashMap<String, Value>,
+    /// The class or mixin whose method is being checked
+    class: Option<String>,
+    /// Set while checking a static method, which has no `self`
+    static_method: bool,
+    /// Set while checking a function, method, or test body
+    in_function: bool,
+}
+
+/// A loop around the statement being checked
+struct EnclosingLoop {
+    label: Option<String>,
+    /// Set for `do ... while`, whose condition a `continue` would skip
+    post_condition: bool,
+}
+
+impl Checker<'_> {
+    fn check_block(&mut self, statements: &[Statement]) {
+        for stmt in statements {
+            self.check_statement(stmt);
+        }
+    }
+
+    /// Checks a nested block in its own scope
+    fn check_scoped_block(&mut self, statements: &[Statement]) {
+        self.env.push_scope();
+        self.depth += 1;
+        self.check_block(statements);
+        self.depth -= 1;
+        self.env.pop_scope();
+    }
+
+    /// Checks the statements of a function, method, or lambda body, whose last
+    /// expression statement is its return value
+    fn check_body(&mut self, body: &[Statement]) {
+        match body.split_last() {
+            Some((Statement::Expression(value), rest)) => {
+                self.check_block(rest);
+                self.check_expression(value);
+            }
+            _ => self.check_block(body),
+        }
+    }
+
+    /// Checks a branch of a cond expression in its own scope; its last expression
+    /// statement is the value the branch gives
+    fn check_value_block(&mut self, body: &[Statement], span: Span) {
+        self.env.push_scope();
+        self.depth += 1;
+        self.check_body(body);
+        self.depth -= 1;
+        self.env.pop_scope();
+
+        if !matches!(body.last(), Some(Statement::Expression(_))) {
+            self.diagnostics.push(Diagnostic::error(
+                "each branch of a cond must end in an expression giving its value",
+                span,
+            ));
+        }
+    }
+
+    /// Checks a branch or loop body in its own scope, along with the variable its
+    /// condition binds
+    fn check_guarded_block(&mut self, condition: &Expr, statements: &[Statement]) {
+        self.env.push_scope();
+        if let Expr::Binding { name, value, .. } = condition {
+            // The body never sees a nil
+            let ty = self.env.infer(value).without_nil();
+            self.env.bind(name, ty);
+        }
+        self.depth += 1;
+        self.check_block(statements);
+        self.depth -= 1;
+        self.env.pop_scope();
+    }
+
+    /// Checks a function or method body with only its parameters in scope
+    ///
+    /// Parameters have the given types, and a variadic last parameter is an array of
+    /// the extra arguments.
+    fn check_function_body(
+        &mut self,
+        params: &[String],
+        param_types: &[Type],
+        variadic: bool,
+        body: &[Statement],
+    ) {
+        let mut env = TypeEnv::new(self.env.symbols());
+        for (i, param) in params.iter().enumerate() {
+            let ty = param_types.get(i).cloned().unwrap_or(Type::Unknown);
+            if variadic && i == params.len() - 1 {
+                env.bind(param, Type::Array(Box::new(ty)));
+            } else {
+                env.bind(param, ty);
+            }
+        }
+
+        let outer = std::mem::replace(&mut self.env, env);
+        let outer_loops = std::mem::take(&mut self.loops);
+        let outer_function = std::mem::replace(&mut self.in_function, true);
+        self.depth += 1;
+        self.check_body(body);
+        self.depth -= 1;
+        self.env = outer;
+        self.loops = outer_loops;
+        self.in_function = outer_function;
+    }
+
+    fn check_statement(&mut self, stmt: &Statement) {
+        match stmt {
+            Statement::FunctionDef {
+                name,
+                params,
+                param_types,
+                variadic,
+                body,
+                span,
+                ..
+            } => {
+                // A top-level function's parameters take the types its calls pass
+                let types = match self.env.symbols().function(name) {
+                    Some(function) if self.depth == 0 && function.span == *span => {
+                        function.inferred_params.clone()
+                    }
+                    _ => annotated(param_types),
+                };
+                self.check_function_body(params, &types, *variadic, body);
+            }
+            Statement::MethodDef {
+                class_name,
+                is_static,
+                params,
+                param_types,
+                variadic,
+                body,
+                span,
+                ..
+            } => {
+                if self.env.symbols().is_trait(class_name) {
+                    self.diagnostics.push(Diagnostic::error(
+                        format!(
+                            "'{}' is a trait; declare its methods in the trait body",
+                            class_name
+                        ),
+                        *span,
+                    ));
+                } else if self.env.symbols().is_enum(class_name) {
+                    self.diagnostics.push(Diagnostic::error(
+                        format!("'{}' is an enum and cannot define methods", class_name),
+                        *span,
+                    ));
+                }
+                self.class = Some(class_name.clone());
+                self.static_method = *is_static;
+                // Its parameters take the types its calls pass
+                let types = match self.env.symbols().signature(*span) {
+                    Some(method) => method.inferred_params.clone(),
+                    None => annotated(param_types),
+                };
+                self.check_function_body(params, &types, *variadic, body);
+                self.class = None;
+                self.static_method = false;
+            }
+            Statement::ClassDef {
+                name,
+                parent,
+                traits,
+                mixins,
+                fields,
+                span,
+                ..
+            } => {
+                if let Some(parent) = parent {
+                    self.check_parent(name, parent, *span);
+                }
+                self.check_traits(name, traits, *span);
+                self.check_mixins(name, mixins, *span);
+                self.check_fields(name, fields);
+            }
+            Statement::TraitDef {
+                name,
+                methods,
+                span,
+            } => {
+                let symbols = self.env.symbols();
+                let other = if symbols.is_class(name) {
+                    Some("class")
+                } else if symbols.is_mixin(name) {
+                    Some("mixin")
+                } else {
+                    None
+                };
+                if let Some(other) = other {
+                    self.diagnostics.push(Diagnostic::error(
+                        format!("'{}' is declared as both a {} and a trait", name, other),
+                        *span,
+                    ));
+                }
+                for (i, method) in methods.iter().enumerate() {
+                    if methods[..i]
+                        .iter()
+                        .any(|earlier| earlier.name == method.name)
+                    {
+                        self.diagnostics.push(Diagnostic::error(
+                            format!(
+                                "method '{}' is declared more than once in trait '{}'",
+                                method.name, name
+                            ),
+                            method.span,
+                        ));
+                    }
+                }
+            }
+            Statement::EnumDef {
+                name,
+                variants,
+                span,
+            } => {
+                let symbols = self.env.symbols();
+                let other = if symbols.is_class(name) {
+                    Some("class")
+                } else if symbols.is_mixin(name) {
+                    Some("mixin")
+                } else if symbols.is_trait(name) {
+                    Some("trait")
+                } else {
+                    None
+                };
+                if let Some(other) = other {
+                    self.diagnostics.push(Diagnostic::error(
+                        format!("'{}' is declared as both a {} and an enum", name, other),
+                        *span,
+                    ));
+                }
+                for (i, variant) in variants.iter().enumerate() {
+                    if variants[..i].contains(variant) {
+                        self.diagnostics.push(Diagnostic::error(
+                            format!(
+                                "variant '{}' is declared more than once in enum '{}'",
+                                variant, name
+                            ),
+                            *span,
+                        ));
+                    }
+                }
+            }
+            Statement::MixinDef { name, span } if self.env.symbols().is_class(name) => {
+                self.diagnostics.push(Diagnostic::error(
+                    format!("'{}' is declared as both a class and a mixin", name),
+                    *span,
+                ));
+            }
+            Statement::MixinDef { .. } => {}
+            Statement::ConstDef { name, value, span } => self.check_const(name, value, *span),
+            Statement::Import {
+                module,
+                names,
+                span,
+            } => self.check_import(module, names, *span),
+            Statement::Global { name, value, span } => self.check_global(name, value, *span),
+            Statement::Assignment {
+                name,
+                mutable,
+                ty,
+                value,
+                span,
+            } => {
+                self.check_not_constant(name, *span);
+                self.check_expression(value);
+                let mut value_ty = self.env.infer(value);
+                let global = self.env.is_global_var(name);
+                if *mutable && global {
+                    self.diagnostics.push(Diagnostic::error(
+                        format!("global '{}' cannot be redeclared with 'var'", name),
+                        *span,
+                    ));
+                } else if !mutable && !name.starts_with("self.") {
+                    let updates = self.env.updates_variable(name, ty.is_some());
+                    self.check_reassignable(name, updates, *span);
+                }
+                // Assigning a `var` or an outer variable again keeps it, with its type
+                let reassigned = !mutable && self.env.updates_variable(name, ty.is_some());
+                let declared = match name.strip_prefix("self.") {
+                    Some(_) if self.static_method => {
+                        self.diagnostics.push(Diagnostic::error(
+                            format!("a static method has no 'self' to assign '{}'", name),
+                            *span,
+                        ));
+                        None
+                    }
+                    Some(field) => self.declared_field_type(field, *span),
+                    None if global => self
+                        .env
+                        .symbols()
+                        .global_var(name)
+                        .map(|global| global.ty.clone()),
+                    None if reassigned => ty
+                        .map(Type::from)
+                        .or_else(|| self.env.lookup(name).cloned()),
+                    None => ty.map(Type::from),
+                };
+                if let Some(declared) = declared {
+                    if !declared.accepts(&value_ty) {
+                        self.diagnostics.push(Diagnostic::error(
+                            format!(
+                                "'{}' is declared {} but assigned {}",
+                                name, declared, value_ty
+                            ),
+                            *span,
+                        ));
+                    }
+                    value_ty = declared;
+                }
+                // A global keeps its declared type and stays visible as the global
+                if name.starts_with("self.") || global || reassigned {
+                    return;
+                }
+                if *mutable {
+                    self.env.bind_var(name, value_ty);
+                } else {
+                    self.env.bind(name, value_ty);
+                }
+            }
+            Statement::FieldAssignment { object, value, .. } => {
+                self.check_expression(object);
+                self.check_expression(value);
+            }
+            Statement::ParallelAssignment {
+                names,
+                values,
+                span,
+            } => {
+                for name in names {
+                    self.check_not_constant(name, *span);
+                    self.check_not_global(name, *span);
+                    self.check_reassignable(name, false, *span);
+                }
+                for value in values {
+                    self.check_expression(value);
+                }
+                // Bind only after inferring every value, matching the evaluation order;
+                // a `var` assigned again keeps its binding
+                let types = self.assigned_types(names, values, *span);
+                for (name, ty) in names.iter().zip(types) {
+                    if !self.env.is_var(name) {
+                        self.env.bind(name, ty);
+                    }
+                }
+            }
+            Statement::ArrayDestructuring { names, value, span } => {
+                for name in names.iter().filter(|name| *name != "_") {
+                    self.check_not_constant(name, *span);
+                    self.check_not_global(name, *span);
+                    self.check_reassignable(name, false, *span);
+                }
+                self.check_expression(value);
+                let element = match (value, self.env.infer(value)) {
+                    (Expr::Array { elements, .. }, _) if elements.len() != names.len() => {
+                        self.diagnostics.push(Diagnostic::error(
+                            format!(
+                                "cannot destructure {} values into {} names",
+                                elements.len(),
+                                names.len()
+                            ),
+                            *span,
+                        ));
+                        Type::Unknown
+                    }
+                    (_, Type::Array(element)) => *element,
+                    (_, Type::Unknown) => Type::Unknown,
+                    (_, ty) => {
+                        self.diagnostics.push(Diagnostic::error(
+                            format!("cannot destructure {} into {} names", ty, names.len()),
+                            *span,
+                        ));
+                        Type::Unknown
+                    }
+                };
+                for name in names.iter().filter(|name| *name != "_") {
+                    if !self.env.is_var(name) {
+                        self.env.bind(name, element.clone());
+                    }
+                }
+            }
+            Statement::If {
+                condition,
+                then_branch,
+                elif_branches,
+                else_branch,
+                ..
+            } => {
+                self.check_expression(condition);
+                self.check_guarded_block(condition, then_branch);
+                for (elif_condition, elif_body) in elif_branches {
+                    self.check_expression(elif_condition);
+                    self.check_guarded_block(elif_condition, elif_body);
+                }
+                if let Some(else_body) = else_branch {
+                    self.check_scoped_block(else_body);
+                }
+            }
+            Statement::Match {
+                subject,
+                arms,
+                span,
+            } => {
+                self.check_expression(subject);
+                self.check_match_arms(subject, arms, *span);
+                for arm in arms {
+                    self.check_scoped_block(&arm.body);
+                }
+            }
+            Statement::While {
+                condition,
+                body,
+                label,
+                ..
+            } => {
+                self.check_expression(condition);
+                self.loops.push(EnclosingLoop {
+                    label: label.clone(),
+                    post_condition: false,
+                });
+                self.check_guarded_block(condition, body);
+                self.loops.pop();
+            }
+            Statement::Loop { body, label, .. } => {
+                self.loops.push(EnclosingLoop {
+                    label: label.clone(),
+                    post_condition: false,
+                });
+                self.check_scoped_block(body);
+                self.loops.pop();
+            }
+            Statement::DoWhile {
+                body,
+                condition,
+                label,
+                ..
+            } => {
+                self.loops.push(EnclosingLoop {
+                    label: label.clone(),
+                    post_condition: true,
+                });
+                // The condition sees the variables assigned in the body
+                self.env.push_scope();
+                self.depth += 1;
+                self.check_block(body);
+                self.depth -= 1;
+                self.check_expression(condition);
+                self.env.pop_scope();
+                self.loops.pop();
+            }
+            Statement::Block { body, .. } => self.check_scoped_block(body),
+            Statement::Break { label, span } => self.check_loop_control("break", label, *span),
+            Statement::Continue { label, span } => {
+                self.check_loop_control("continue", label, *span)
+            }
+            Statement::Return { value, .. } => {
+                if let Some(value) = value {
+                    self.check_expression(value);
+                }
+            }
+            Statement::Test { body, .. } => self.check_function_body(&[], &[], false, body),
+            Statement::Expression(expr) => {
+                // A top-level expression may be the value the program prints
+                if self.in_function && !has_side_effects(expr) {
+                    self.diagnostics.push(Diagnostic::warning(
+                        "expression value is never used",
+                        expr.span(),
+                    ));
+                }
+                self.check_expression(expr)
+            }
+        }
+    }
+
+    /// Checks that a match's patterns name variants of the subject's enum, and
+    /// warns about variants no arm covers when there is no `_` arm
+    fn check_match_arms(&mut self, subject: &Expr, arms: &[MatchArm], span: Span) {
+        let symbols = self.env.symbols();
+        let subject_ty = self.env.infer(subject);
+        let mut covered: Vec<&str> = Vec::new();
+        let mut enum_name = match &subject_ty {
+            Type::Enum(name) => Some(name.as_str()),
+            _ => None,
+        };
+
+        for pattern in arms.iter().flat_map(|arm| &arm.patterns) {
+            let message = match pattern {
+                Pattern::Variant {
+                    enum_name: name,
+                    variant,
+                } => match symbols.enum_def(name) {
+                    None => Some(format!("unknown enum '{}'", name)),
+                    Some(symbol) if !symbol.has_variant(variant) => {
+                        Some(format!("enum '{}' has no variant '{}'", name, variant))
+                    }
+                    Some(_) if enum_name.is_some_and(|expected| expected != name) => Some(format!(
+                        "pattern '{}' does not match {}",
+                        pattern,
+                        enum_name.unwrap()
+                    )),
+                    Some(_) => {
+                        enum_name = Some(name);
+                        covered.push(variant);
+                        None
+                    }
+                },
+                Pattern::Literal(value) => {
+                    let ty = self.env.infer(value);
+                    match enum_name {
+                        Some(name) => Some(format!("pattern {} does not match {}", pattern, name)),
+                        None if !ty.accepts(&subject_ty) => {
+                            Some(format!("pattern {} does not match {}", pattern, subject_ty))
+                        }
+                        None => None,
+                    }
+                }
+                Pattern::Wildcard => None,
+            };
+            if let Some(message) = message {
+                self.diagnostics.push(Diagnostic::error(message, span));
+            }
+        }
+
+        let wildcard = arms
+            .iter()
+            .any(|arm| arm.patterns.contains(&Pattern::Wildcard));
+        let Some(symbol) = enum_name
+            .filter(|_| !wildcard)
+            .and_then(|name| symbols.enum_def(name))
+        else {
+            return;
+        };
+        for variant in &symbol.variants {
+            if !covered.contains(&variant.as_str()) {
+                self.diagnostics.push(Diagnostic::warning(
+                    format!(
+                        "match on enum '{}' does not cover variant '{}'",
+                        symbol.name, variant
+                    ),
+                    span,
+                ));
+            }
+        }
+    }
+
+    /// Checks a constant declaration: top-level, declared once, and computed at
+    /// compile time from literals and earlier constants into a scalar type
+    fn check_const(&mut self, name: &str, value: &Expr, span: Span) {
+        self.check_expression(value);
+
+        let message = if self.depth > 0 {
+            format!("constant '{}' must be declared at the top level", name)
+        } else if self.consts.iter().any(|constant| constant == name) {
+            format!("constant '{}' is already declared", name)
+        } else if !self.is_const_expression(value) {
+            format!(
+                "constant '{}' must be computed from literals and other constants",
+                name
+            )
+        } else {
+            self.consts.push(name.to_string());
+            match self.env.infer(value) {
+                Type::Int | Type::Float | Type::String | Type::Bool => {
+                    match eval_with(value, &|name| self.const_values.get(name).cloned()) {
+                        Ok(result) => {
+                            self.const_values.insert(name.to_string(), result);
+                            return;
+                        }
+                        Err(error @ (EvalError::Overflow | EvalError::DivisionByZero)) => {
+                            format!("constant '{}' cannot be computed: {}", name, error)
+                        }
+                        // Operand types are reported by the expression check, and a
+                        // constant that failed leaves the ones built on it unevaluated
+                        Err(_) => return,
+                    }
+                }
+                ty => format!(
+                    "constant '{}' must be an int, float, string, or bool, found {}",
+                    name, ty
+                ),
+            }
+        };
+        self.diagnostics.push(Diagnostic::error(message, span));
+    }
+
+    /// Checks that a global is declared once, at the top level, with a value of a
+    /// type it can hold
+    fn check_global(&mut self, name: &str, value: &Expr, span: Span) {
+        self.check_expression(value);
+
+        let message = if self.depth > 0 {
+            format!("global '{}' must be declared at the top level", name)
+        } else if self.env.symbols().constant(name).is_some() {
+            format!("'{}' is declared as both a constant and a global", name)
+        } else {
+            match self.env.symbols().global_var(name) {
+                Some(global) if global.span != span => {
+                    format!("global '{}' is already declared", name)
+                }
+                Some(global) => match &global.ty {
+                    Type::Int | Type::Float | Type::String | Type::Bool => return,
+                    ty => format!(
+                        "global '{}' must be an int, float, string, or bool, found {}",
+                        name, ty
+                    ),
+                },
+                None => return,
+            }
+        };
+        self.diagnostics.push(Diagnostic::error(message, span));
+    }
+
+    /// Reports a global assigned along with other names, which only a plain
+    /// assignment can do
+    fn check_not_global(&mut self, name: &str, span: Span) {
+        if self.env.is_global_var(name) {
+            self.diagnostics.push(Diagnostic::error(
+                format!("global '{}' must be assigned on its own", name),
+                span,
+            ));
+        }
+    }
+
+    /// Checks that a variable was declared with `var` before it is assigned again,
+    /// whether from its own scope or, when the assignment `updates` it, from a scope
+    /// nested in the one that declared it
+    fn check_reassignable(&mut self, name: &str, updates: bool, span: Span) {
+        if (updates || self.env.is_local_to_scope(name)) && !self.env.is_var(name) {
+            self.diagnostics.push(Diagnostic::error(
+                format!(
+                    "cannot reassign '{}'; declare it with 'var' to allow it",
+                    name
+                ),
+                span,
+            ));
+        }
+    }
+
+    /// Checks that an import is at the top level and names each item once
+    fn check_import(&mut self, module: &str, names: &[String], span: Span) {
+        if self.depth > 0 {
+            self.diagnostics.push(Diagnostic::error(
+                format!("import of '{}' must be at the top level", module),
+                span,
+            ));
+        }
+        for (i, name) in names.iter().enumerate() {
+            // Report each repeated name once, at its second mention
+            if names[..i].iter().filter(|earlier| *earlier == name).count() == 1 {
+                self.diagnostics.push(Diagnostic::error(
+                    format!("'{}' is imported from '{}' more than once", name, module),
+                    span,
+                ));
+            }
+        }
+    }
+
+    /// Checks if an expression can be evaluated when the Rust program is compiled
+    ///
+    /// Strings can only be literals, since Rust cannot concatenate them in a `const`.
+    fn is_const_expression(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Integer { .. } | Expr::Float { .. } | Expr::Bool { .. } | Expr::String { .. } => {
+                true
+            }
+            Expr::Identifier { name, .. } => self.consts.iter().any(|constant| constant == name),
+            Expr::Grouped { inner, .. } | Expr::UnaryOp { operand: inner, .. } => {
+                self.is_const_expression(inner)
+            }
+            Expr::BinaryOp { left, right, .. } => {
+                self.env.infer(expr) != Type::String
+                    && self.is_const_expression(left)
+                    && self.is_const_expression(right)
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the type each name of a parallel assignment receives
+    ///
+    /// A single value is destructured, so it must be a tuple with one element per name
+    /// (or of unknown type, such as a function's result).
+    fn assigned_types(&mut self, names: &[String], values: &[Expr], span: Span) -> Vec<Type> {
+        if values.len() == names.len() {
+            return values.iter().map(|value| self.env.infer(value)).collect();
+        }
+
+        match self.env.infer(&values[0]) {
+            Type::Tuple(elements) if elements.len() == names.len() => elements,
+            Type::Unknown => vec![Type::Unknown; names.len()],
+            ty => {
+                self.diagnostics.push(Diagnostic::error(
+                    format!("cannot destructure {} into {} names", ty, names.len()),
+                    span,
+                ));
+                vec![Type::Unknown; names.len()]
+            }
+        }
+    }
+
+    /// Reports an assignment to a declared constant
+    fn check_not_constant(&mut self, name: &str, span: Span) {
+        if self.env.symbols().constant(name).is_some() {
+            self.diagnostics.push(Diagnostic::error(
+                format!("cannot assign to constant '{}'", name),
+                span,
+            ));
+        }
+    }
+
+    /// Checks that every mixin in a class's `with` clause is declared, once
+    fn check_mixins(&mut self, class_name: &str, mixins: &[String], span: Span) {
+        for (i, mixin) in mixins.iter().enumerate() {
+            let message = if mixins[..i].contains(mixin) {
+                format!(
+                    "class '{}' includes mixin '{}' more than once",
+                    class_name, mixin
+                )
+            } else if self.env.symbols().is_mixin(mixin) {
+                continue;
+            } else if self.env.symbols().is_class(mixin) {
+                format!("'{}' is a class, not a mixin", mixin)
+            } else {
+                format!("unknown mixin '{}'", mixin)
+            };
+            self.diagnostics.push(Diagnostic::error(message, span));
+        }
+    }
+
+    /// Checks that every trait a class lists is declared, once, and that the class
+    /// responds to each of the trait's methods with a matching instance method
+    fn check_traits(&mut self, class_name: &str, traits: &[String], span: Span) {
+        let symbols = self.env.symbols();
+        for (i, trait_name) in traits.iter().enumerate() {
+            let Some(trait_def) = symbols.trait_def(trait_name) else {
+                let message = if symbols.is_class(trait_name) {
+                    format!("'{}' is a class, not a trait", trait_name)
+                } else if symbols.is_mixin(trait_name) {
+                    format!(
+                        "'{}' is a mixin, not a trait; include it with 'with'",
+                        trait_name
+                    )
+                } else {
+                    format!("unknown trait '{}'", trait_name)
+                };
+                self.diagnostics.push(Diagnostic::error(message, span));
+                continue;
+            };
+            if traits[..i].contains(trait_name) {
+                self.diagnostics.push(Diagnostic::error(
+                    format!(
+                        "class '{}' implements trait '{}' more than once",
+                        class_name, trait_name
+                    ),
+                    span,
+                ));
+                continue;
+            }
+
+            for required in &trait_def.methods {
+                let message = match symbols.resolve_method(class_name, &required.name) {
+                    None => format!(
+                        "class '{}' does not define method '{}' required by trait '{}'",
+                        class_name, required.name, trait_name
+                    ),
+                    Some(method)
+                        if method.is_static
+                            || method.params.len() != required.params.len()
+                            || method.param_types != required.param_types
+                            || method.variadic != required.variadic
+                            || method.return_type != required.return_type =>
+                    {
+                        format!(
+                            "method '{}' of class '{}' does not match its declaration in trait '{}'",
+                            required.name, class_name, trait_name
+                        )
+                    }
+                    Some(_) => continue,
+                };
+                self.diagnostics.push(Diagnostic::error(message, span));
+            }
+        }
+    }
+
+    /// Checks that a class inherits from a declared class, and not from itself
+    fn check_parent(&mut self, class_name: &str, parent: &str, span: Span) {
+        let symbols = self.env.symbols();
+        let message = if symbols.is_class(parent) {
+            let mut ancestors = symbols.ancestors(class_name).into_iter();
+            if parent != class_name
+                && ancestors.all(|ancestor| ancestor.parent.as_deref() != Some(class_name))
+            {
+                return;
+            }
+            format!("class '{}' inherits from itself", class_name)
+        } else if symbols.is_mixin(parent) {
+            format!(
+                "'{}' is a mixin, not a class; include it with 'with'",
+                parent
+            )
+        } else {
+            format!("unknown parent class '{}'", parent)
+        };
+        self.diagnostics.push(Diagnostic::error(message, span));
+    }
+
+    /// Checks that no field is declared twice in a class body
+    fn check_fields(&mut self, class_name: &str, fields: &[FieldDecl]) {
+        for (i, field) in fields.iter().enumerate() {
+            if fields[..i].iter().any(|earlier| earlier.name == field.name) {
+                self.diagnostics.push(Diagnostic::error(
+                    format!(
+                        "field '{}' is declared more than once in class '{}'",
+                        field.name, class_name
+                    ),
+                    field.span,
+                ));
+            }
+        }
+    }
+
+    /// Returns the type of a field assigned in a method of a class that declares its
+    /// fields, reporting a field neither the class nor its ancestors declare
+    ///
+    /// Classes without declared fields, and unannotated fields, accept any value.
+    fn declared_field_type(&mut self, field: &str, span: Span) -> Option<Type> {
+        let symbols = self.env.symbols();
+        let class = self
+            .class
+            .as_deref()
+            .and_then(|name| symbols.class(name))
+            .filter(|class| !class.fields.is_empty())?;
+        let declared = std::iter::once(class)
+            .chain(symbols.ancestors(&class.name))
+            .find_map(|class| class.field(field));
+        match declared {
+            Some(declared) if declared.ty == Type::Unknown => None,
+            Some(declared) => Some(declared.ty.clone()),
+            None => {
+                self.diagnostics.push(Diagnostic::error(
+                    format!("class '{}' declares no field '{}'", class.name, field),
+                    span,
+                ));
+                None
+            }
+        }
+    }
+
+    /// Checks that `break` or `continue` is inside a loop with the given label, and
+    /// that `continue` does not skip the condition of a do-while loop
+    fn check_loop_control(&mut self, keyword: &str, label: &Option<String>, span: Span) {
+        let target = match label {
+            Some(label) => self
+                .loops
+                .iter()
+                .rfind(|l| l.label.as_deref() == Some(label)),
+            None => self.loops.last(),
+        };
+        let message = match (label, target) {
+            _ if self.loops.is_empty() => format!("'{}' outside of a loop", keyword),
+            (Some(label), None) => format!(
+                "'{} {}' does not match an enclosing loop label",
+                keyword, label
+            ),
+            // The generated loop checks the condition at the end of the body
+            (_, Some(target)) if keyword == "continue" && target.post_condition => {
+                "'continue' cannot target a do-while loop, whose condition it would skip"
+                    .to_string()
+            }
+            _ => return,
+        };
+        self.diagnostics.push(Diagnostic::error(message, span));
+    }
+
+    /// Checks an `env(name)` or `env(name, default)` call
+    fn check_env_call(&mut self, args: &[Expr], span: Span) {
+        if !(1..=2).contains(&args.len()) {
+            self.diagnostics.push(Diagnostic::error(
+                format!("env() expects 1 or 2 arguments, found {}", args.len()),
+                span,
+            ));
+            return;
+        }
+
+        for arg in args {
+            let ty = self.env.infer(arg);
+            if !matches!(ty, Type::String | Type::Unknown) {
+                self.diagnostics.push(Diagnostic::error(
+                    format!("env() expects string arguments, found {}", ty),
+                    span,
+                ));
+            }
+        }
+    }
+
+    fn check_expression(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Integer { .. }
+            | Expr::Float { .. }
+            | Expr::Bool { .. }
+            | Expr::Nil { .. }
+            | Expr::String { .. }
+            | Expr::Identifier { .. } => {}
+            Expr::Grouped { inner, .. } => self.check_expression(inner),
+            Expr::Array { elements, .. } | Expr::Tuple { elements, .. } => {
+                for element in elements {
+                    self.check_expression(element);
+                }
+            }
+            Expr::Map { entries, .. } => {
+                for (key, value) in entries {
+                    self.check_expression(key);
+                    self.check_expression(value);
+                }
+            }
+            Expr::Index { object, index, .. } => {
+                self.check_expression(object);
+                self.check_expression(index);
+            }
+            Expr::Cond {
+                branches,
+                else_branch,
+                span,
+            } => {
+                for (condition, body) in branches {
+                    self.check_expression(condition);
+                    self.check_value_block(body, *span);
+                }
+                self.check_value_block(else_branch, *span);
+            }
+            Expr::Slice {
+                object,
+                start,
+                end,
+                span,
+            } => {
+                self.check_expression(object);
+                let ty = self.env.infer(object);
+                if !matches!(ty, Type::String | Type::Array(_) | Type::Unknown) {
+                    self.diagnostics.push(Diagnostic::error(
+                        format!("cannot slice a value of type {}", ty),
+                        *span,
+                    ));
+                }
+                for bound in start.iter().chain(end) {
+                    self.check_expression(bound);
+                    let ty = self.env.infer(bound);
+                    if !matches!(ty, Type::Int | Type::Unknown) {
+                        self.diagnostics.push(Diagnostic::error(
+                            format!("slice bounds must be integers, found {}", ty),
+                            bound.span(),
+                        ));
+                    }
+                }
+            }
+            Expr::UnaryOp { operand, .. } => self.check_expression(operand),
+            Expr::BinaryOp {
+                left,
+                op: BinaryOperator::In,
+                right,
+                span,
+            } => {
+                self.check_expression(left);
+                self.check_expression(right);
+                match (self.env.infer(left), self.env.infer(right)) {
+                    (_, Type::Array(_) | Type::Map(_, _) | Type::Unknown)
+                    | (Type::String | Type::Unknown, Type::String) => {}
+                    (item, Type::String) => self.diagnostics.push(Diagnostic::error(
+                        format!("only a string can be in a string, found {}", item),
+                        *span,
+                    )),
+                    (_, ty) => self.diagnostics.push(Diagnostic::error(
+                        format!("cannot test membership in a value of type {}", ty),
+                        *span,
+                    )),
+                }
+            }
+            Expr::BinaryOp {
+                left,
+                op,
+                right,
+                span,
+            } => {
+                self.check_expression(left);
+                self.check_expression(right);
+                self.check_arithmetic(op, left, right, *span);
+            }
+            Expr::FunctionCall { name, args, span } => {
+                for arg in args {
+                    self.check_expression(arg);
+                }
+                if args.iter().any(|arg| matches!(arg, Expr::NamedArg { .. })) {
+                    self.check_named_args(name, args, *span);
+                }
+                match name.as_str() {
+                    "print" => {
+                        let diagnostics = check_print_call(args, &self.env, *span);
+                        self.diagnostics.extend(diagnostics);
+                    }
+                    "env" => self.check_env_call(args, *span),
+                    "assert" if !(1..=2).contains(&args.len()) => {
+                        self.diagnostics.push(Diagnostic::error(
+                            format!("assert() expects 1 or 2 arguments, found {}", args.len()),
+                            *span,
+                        ));
+                    }
+                    "typeof" if args.len() != 1 => {
+                        self.diagnostics.push(Diagnostic::error(
+                            format!("typeof() expects 1 argument, found {}", args.len()),
+                            *span,
+                        ));
+                    }
+                    "time" | "clock" if !args.is_empty() => {
+                        self.diagnostics.push(Diagnostic::error(
+                            format!("{}() takes no arguments, found {}", name, args.len()),
+                            *span,
+                        ));
+                    }
+                    _ => {
+                        self.check_variadic_call(name, args.len(), *span);
+                        self.check_argument_types(name, args, *span);
+                    }
+                }
+            }
+            Expr::NamedArg { value, .. } => self.check_expression(value),
+            Expr::Binding { name, value, span } => {
+                self.check_not_constant(name, *span);
+                self.check_expression(value);
+            }
+            Expr::FieldAccess { object, .. } => self.check_expression(object),
+            Expr::MethodCall { object, args, .. } => {
+                self.check_expression(object);
+                for arg in args {
+                    self.check_expression(arg);
+                }
+            }
+            Expr::Call { callee, args, span } => {
+                self.check_expression(callee);
+                for arg in args {
+                    self.check_expression(arg);
+                }
+                // Lambdas and the results of calls are of unknown type
+                let ty = self.env.infer(callee);
+                if ty != Type::Unknown {
+                    self.diagnostics.push(Diagnostic::error(
+                        format!("cannot call a value of type {}", ty),
+                        *span,
+                    ));
+                }
+            }
+            Expr::Lambda { params, body, .. } => self.check_lambda(params, body),
+        }
+    }
+
+    /// Checks that arithmetic is done on numbers, or that `+` joins a string, and
+    /// never on a value that may be `nil`
+    fn check_arithmetic(&mut self, op: &BinaryOperator, left: &Expr, right: &Expr, span: Span) {
+        if !op.is_arithmetic() {
+            return;
+        }
+        let operands = [self.env.infer(left), self.env.infer(right)];
+        if let Some(ty) = operands.iter().find(|ty| ty.is_nilable()) {
+            self.diagnostics.push(Diagnostic::error(
+                format!(
+                    "operator '{}' cannot use a value that may be nil, found {}; \
+                     bind it with `if name = ...` or give it a default",
+                    op.symbol(),
+                    ty
+                ),
+                span,
+            ));
+            return;
+        }
+        if *op == BinaryOperator::Add && operands.contains(&Type::String) {
+            return;
+        }
+        if let Some(ty) = operands
+            .iter()
+            .find(|ty| !ty.is_numeric() && **ty != Type::Unknown)
+        {
+            self.diagnostics.push(Diagnostic::error(
+                format!("operator '{}' expects numbers, found {}", op.symbol(), ty),
+                span,
+            ));
+        }
+    }
+
+    /// Checks that the named arguments of a call match the function's parameters, so
+    /// every parameter before a variadic one receives exactly one argument
+    fn check_named_args(&mut self, name: &str, args: &[Expr], span: Span) {
+        let function = self
+            .env
+            .symbols()
+            .function(name)
+            .filter(|_| self.env.lookup(name).is_none());
+        let Some(function) = function else {
+            self.diagnostics.push(Diagnostic::error(
+                format!("{}() does not take named arguments", name),
+                span,
+            ));
+            return;
+        };
+
+        let fixed = &function.params[..function.required_args()];
+        let mut given: Vec<&str> = Vec::new();
+        let mut messages = Vec::new();
+        let mut seen_named = false;
+        for (i, arg) in args.iter().enumerate() {
+            match arg {
+                Expr::NamedArg { name: arg_name, .. } => {
+                    seen_named = true;
+                    if !fixed.contains(arg_name) {
+                        messages.push(format!("{}() has no parameter named '{}'", name, arg_name));
+                    } else if given.contains(&arg_name.as_str()) {
+                        messages.push(format!(
+                            "{}() is given argument '{}' more than once",
+                            name, arg_name
+                        ));
+                    } else {
+                        given.push(arg_name);
+                    }
+                }
+                _ if seen_named => {
+                    messages.push(format!(
+                        "positional argument after named arguments in call to {}()",
+                        name
+                    ));
+                }
+                _ => {
+                    if let Some(param) = fixed.get(i) {
+                        given.push(param);
+                    }
+                }
+            }
+        }
+        // Parameters only count as missing once the named arguments make sense
+        if messages.is_empty() {
+            for param in fixed {
+                if !given.contains(&param.as_str()) {
+                    messages.push(format!("{}() is missing argument '{}'", name, param));
+                }
+            }
+        }
+
+        for message in messages {
+            self.diagnostics.push(Diagnostic::error(message, span));
+        }
+    }
+
+    /// Checks that a call to a variadic function passes every parameter before the
+    /// variadic one
+    fn check_variadic_call(&mut self, name: &str, arg_count: usize, span: Span) {
+        if self.env.lookup(name).is_some() {
+            return;
+        }
+        if let Some(function) = self.env.symbols().function(name) {
+            let required = function.required_args();
+            if function.variadic && arg_count < required {
+                let noun = if required == 1 {
+                    "argument"
+                } else {
+                    "arguments"
+                };
+                self.diagnostics.push(Diagnostic::error(
+                    format!(
+                        "{}() expects at least {} {}, found {}",
+                        name, required, noun, arg_count
+                    ),
+                    span,
+                ));
+            }
+        }
+    }
+
+    /// Checks that the arguments of a call fit the types its parameters are annotated
+    /// with
+    fn check_argument_types(&mut self, name: &str, args: &[Expr], span: Span) {
+        if self.env.lookup(name).is_some() {
+            return;
+        }
+        let Some(function) = self.env.symbols().function(name) else {
+            return;
+        };
+
+        let mut messages = Vec::new();
+        for (i, arg) in args.iter().enumerate() {
+            let (index, value) = match arg {
+                Expr::NamedArg { name, value, .. } => {
+                    match function.params.iter().position(|param| param == name) {
+                        Some(index) => (index, &**value),
+                        None => continue,
+                    }
+                }
+                // Extra arguments all go to the variadic parameter
+                _ if function.variadic => (i.min(function.params.len() - 1), arg),
+                _ => (i, arg),
+            };
+            let Some(expected) = function.param_types.get(index) else {
+                continue;
+            };
+            let actual = self.env.infer(value);
+            if !expected.accepts(&actual) {
+                messages.push(format!(
+                    "argument '{}' of {}() must be {}, found {}",
+                    function.params[index], name, expected, actual
+                ));
+            }
+        }
+
+        for message in messages {
+            self.diagnostics.push(Diagnostic::error(message, span));
+        }
+    }
+
+    /// Checks a lambda body, which sees the enclosing variables as well as its
+    /// parameters, but not the enclosing loops
+    fn check_lambda(&mut self, params: &[String], body: &[Statement]) {
+        self.env.push_scope();
+        for param in params {
+            self.env.bind(param, Type::Unknown);
+        }
+
+        let outer_loops = std::mem::take(&mut self.loops);
+        self.depth += 1;
+        self.check_body(body);
+        self.depth -= 1;
+        self.loops = outer_loops;
+        self.env.pop_scope();
+    }
+}
+
+/// Returns the types parameters are annotated with, `Unknown` where they are not
+fn annotated(param_types: &[Option<TypeAnnotation>]) -> Vec<Type> {
+    param_types.iter().copied().map(Type::annotated).collect()
+}
+
+/// Checks if evaluating an expression could do more than compute its value
+///
+/// A field read counts, since `obj.name` may call a method with no arguments.
+fn has_side_effects(expr: &Expr) -> bool {
+    match expr {
+        Expr::Integer { .. }
+        | Expr::Float { .. }
+        | Expr::Bool { .. }
+        | Expr::Nil { .. }
+        | Expr::String { .. }
+        | Expr::Identifier { .. }
+        | Expr::Lambda { .. } => false,
+        Expr::Grouped { inner, .. } => has_side_effects(inner),
+        Expr::UnaryOp { operand, .. } => has_side_effects(operand),
+        Expr::BinaryOp { left, right, .. }
+        | Expr::Index {
+            object: left,
+            index: right,
+            ..
+        } => has_side_effects(left) || has_side_effects(right),
+        Expr::Slice {
+            object, start, end, ..
+        } => {
+            has_side_effects(object) || start.iter().chain(end).any(|bound| has_side_effects(bound))
+        }
+        Expr::Array { elements, .. } | Expr::Tuple { elements, .. } => {
+            elements.iter().any(has_side_effects)
+        }
+        Expr::Map { entries, .. } => entries
+            .iter()
+            .any(|(key, value)| has_side_effects(key) || has_side_effects(value)),
+        // A cond's branches run statements, like an if
+        Expr::Cond { .. }
+        | Expr::FunctionCall { .. }
+        | Expr::Call { .. }
+        | Expr::MethodCall { .. }
+        | Expr::FieldAccess { .. }
+        | Expr::NamedArg { .. }
+        | Expr::Binding { .. } => true,
+    }
+}
@@ -0,0 +1,477 @@
+//! Listings of a program's declarations for `--dump-symbols`, as a table or as JSON
+
+use super::symbols::SymbolTable;
+use super::types::{Type, TypeEnv};
+use crate::lexer::Span;
+use crate::parser::{Program, Statement};
+use std::collections::HashMap;
+
+/// What kind of declaration a symbol is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    Function,
+    Class,
+    Mixin,
+    Trait,
+    Enum,
+    Method,
+    Field,
+    Const,
+    /// A variable declared with `global`, shared with functions
+    Global,
+    /// A variable assigned at the top level of the program, local to it
+    Variable,
+}
+
+impl SymbolKind {
+    /// Returns the lowercase name used in listings
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SymbolKind::Function => "function",
+            SymbolKind::Class => "class",
+            SymbolKind::Mixin => "mixin",
+            SymbolKind::Trait => "trait",
+            SymbolKind::Enum => "enum",
+            SymbolKind::Method => "method",
+            SymbolKind::Field => "field",
+            SymbolKind::Const => "const",
+            SymbolKind::Global => "global",
+            SymbolKind::Variable => "variable",
+        }
+    }
+}
+
+/// One declared name with its inferred type and where it is defined
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolEntry {
+    pub kind: SymbolKind,
+    pub name: String,
+    /// The class, mixin, or trait a method or field belongs to
+    pub owner: Option<String>,
+    /// Parameters of a function or method
+    pub params: Vec<String>,
+    /// The value's type: a function's return type, a variable's or field's assigned
+    /// type, a class's instance type; `None` for mixins and traits, which have no
+    /// instances, and for functions that give back nothing
+    pub ty: Option<Type>,
+    /// The definition, or the first assignment of a variable or field
+    pub span: Span,
+}
+
+impl SymbolEntry {
+    /// Returns the name as written in a listing: `add(a, b)`, `Point > new(x)`, `Point.x`
+    pub fn display_name(&self) -> String {
+        match (self.kind, &self.owner) {
+            (SymbolKind::Method, Some(owner)) => {
+                format!("{} > {}({})", owner, self.name, self.params.join(", "))
+            }
+            (SymbolKind::Field, Some(owner)) => format!("{}.{}", owner, self.name),
+            (SymbolKind::Function, _) => format!("{}({})", self.name, self.params.join(", ")),
+            _ => self.name.clone(),
+        }
+    }
+}
+
+/// Every declaration of a program, in source order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SymbolDump {
+    pub entries: Vec<SymbolEntry>,
+}
+
+impl SymbolDump {
+    /// Collects the declarations of a program, inferring the type of each
+    pub fn from_program(program: &Program) -> Self {
+        let symbols = SymbolTable::from_program(program);
+        let mut entries = Vec::new();
+
+        for function in symbols.functions() {
+            entries.push(SymbolEntry {
+                kind: SymbolKind::Function,
+                name: function.name.clone(),
+                owner: None,
+                params: function.params.clone(),
+                ty: function.returns.clone(),
+                span: function.span,
+            });
+        }
+
+        let owners = symbols
+            .classes()
+            .into_iter()
+            .map(|class| (class, SymbolKind::Class))
+            .chain(
+                symbols
+                    .mixins()
+                    .into_iter()
+                    .map(|mixin| (mixin, SymbolKind::Mixin)),
+            )
+            .chain(
+                symbols
+                    .traits()
+                    .into_iter()
+                    .map(|trait_def| (trait_def, SymbolKind::Trait)),
+            );
+        for (owner, kind) in owners {
+            entries.push(SymbolEntry {
+                kind,
+                name: owner.name.clone(),
+                owner: None,
+                params: Vec::new(),
+                ty: (kind == SymbolKind::Class).then(|| Type::Class(owner.name.clone())),
+                span: owner.span,
+            });
+            for method in &owner.methods {
+                let ty = if method.name == "new" && kind == SymbolKind::Class {
+                    Some(Type::Class(owner.name.clone()))
+                } else {
+                    method.returns.clone()
+                };
+                entries.push(SymbolEntry {
+                    kind: SymbolKind::Method,
+                    name: method.name.clone(),
+                    owner: Some(owner.name.clone()),
+                    params: method.params.clone(),
+                    ty,
+                    span: method.span,
+                });
+            }
+        }
+
+        entries.extend(symbols.enums().into_iter().map(|symbol| SymbolEntry {
+            kind: SymbolKind::Enum,
+            name: symbol.name.clone(),
+            owner: None,
+            params: Vec::new(),
+            ty: Some(Type::Enum(symbol.name.clone())),
+            span: symbol.span,
+        }));
+
+        entries.extend(symbols.consts().iter().map(|constant| SymbolEntry {
+            kind: SymbolKind::Const,
+            name: constant.name.clone(),
+            owner: None,
+            params: Vec::new(),
+            ty: Some(constant.ty.clone()),
+            span: constant.span,
+        }));
+        entries.extend(Self::fields(program, &symbols));
+        entries.extend(Self::globals(program, &symbols));
+
+        entries.sort_by_key(|entry| (entry.span.start_line, entry.span.start_column));
+        SymbolDump { entries }
+    }
+
+    /// Collects the fields declared in class bodies and the `self.field` assignments
+    /// from every method, unifying the types assigned to fields without an annotation
+    ///
+    /// A field is listed once, on the class that declares it or, without a
+    /// declaration, on the furthest ancestor whose methods assign it.
+    fn fields(program: &Program, symbols: &SymbolTable) -> Vec<SymbolEntry> {
+        let mut fields: Vec<SymbolEntry> = Vec::new();
+        let mut index: HashMap<(String, String), usize> = HashMap::new();
+        // Annotated fields keep their type; the rest start untyped until assigned
+        let mut annotated = Vec::new();
+
+        for class in symbols.classes() {
+            for field in &class.fields {
+                index.insert((class.name.clone(), field.name.clone()), fields.len());
+                annotated.push(field.ty != Type::Unknown);
+                fields.push(SymbolEntry {
+                    kind: SymbolKind::Field,
+                    name: field.name.clone(),
+                    owner: Some(class.name.clone()),
+                    params: Vec::new(),
+                    ty: (field.ty != Type::Unknown).then(|| field.ty.clone()),
+                    span: field.span,
+                });
+            }
+        }
+
+        // Ancestors' methods first, so a field they assign is theirs
+        let mut methods: Vec<_> = program
+            .statements
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Statement::MethodDef {
+                    class_name,
+                    params,
+                    param_types,
+                    body,
+                    ..
+                } => Some((class_name, params, param_types, body)),
+                _ => None,
+            })
+            .collect();
+        methods.sort_by_key(|(class_name, ..)| symbols.ancestors(class_name).len());
+
+        for (class_name, params, param_types, body) in methods {
+            let owners: Vec<String> = std::iter::once(class_name.clone())
+                .chain(
+                    symbols
+                        .ancestors(class_name)
+                        .into_iter()
+                        .map(|ancestor| ancestor.name.clone()),
+                )
+                .collect();
+            let mut env = TypeEnv::new(symbols);
+            for (param, ty) in params.iter().zip(param_types) {
+                env.bind(param, Type::annotated(*ty));
+            }
+            walk_assignments(body, &mut env, &mut |name, ty, span| {
+                let Some(field) = name.strip_prefix("self.") else {
+                    return;
+                };
+                let defined = owners
+                    .iter()
+                    .find_map(|owner| index.get(&(owner.clone(), field.to_string())));
+                match defined {
+                    Some(&i) if annotated[i] => {}
+                    Some(&i) => {
+                        fields[i].ty = Some(match fields[i].ty.take() {
+                            Some(previous) => Type::unify([previous, ty]),
+                            None => ty,
+                        });
+                    }
+                    None => {
+                        index.insert((class_name.clone(), field.to_string()), fields.len());
+                        annotated.push(false);
+                        fields.push(SymbolEntry {
+                            kind: SymbolKind::Field,
+                            name: field.to_string(),
+                            owner: Some(class_name.clone()),
+                            params: Vec::new(),
+                            ty: Some(ty),
+                            span,
+                        });
+                    }
+                }
+            });
+        }
+
+        // A declared field that is never assigned has no type to infer
+        for field in &mut fields {
+            field.ty.get_or_insert(Type::Unknown);
+        }
+        fields
+    }
+
+    /// Collects `global` declarations and top-level variables, unifying the types
+    /// assigned to each
+    fn globals(program: &Program, symbols: &SymbolTable) -> Vec<SymbolEntry> {
+        let mut globals: Vec<SymbolEntry> = Vec::new();
+        let mut env = TypeEnv::new(symbols);
+
+        for stmt in &program.statements {
+            if let Statement::Global { name, span, .. } = stmt {
+                if !globals.iter().any(|global| global.name == *name) {
+                    globals.push(SymbolEntry {
+                        kind: SymbolKind::Global,
+                        name: name.clone(),
+                        owner: None,
+                        params: Vec::new(),
+                        ty: symbols.global_var(name).map(|global| global.ty.clone()),
+                        span: *span,
+                    });
+                }
+                continue;
+            }
+            if !matches!(
+                stmt,
+                Statement::Assignment { .. }
+                    | Statement::ParallelAssignment { .. }
+                    | Statement::ArrayDestructuring { .. }
+            ) {
+                continue;
+            }
+            walk_assignments(
+                std::slice::from_ref(stmt),
+                &mut env,
+                &mut |name, ty, span| match globals.iter_mut().find(|global| global.name == name) {
+                    Some(global) => {
+                        let previous = global.ty.take().unwrap_or(Type::Unknown);
+                        global.ty = Some(Type::unify([previous, ty]));
+                    }
+                    None => globals.push(SymbolEntry {
+                        kind: SymbolKind::Variable,
+                        name: name.to_string(),
+                        owner: None,
+                        params: Vec::new(),
+                        ty: Some(ty),
+                        span,
+                    }),
+                },
+            );
+        }
+
+        globals
+    }
+
+    /// Formats the entries as an aligned table
+    pub fn to_table(&self) -> String {
+        let rows: Vec<[String; 4]> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                [
+                    entry.kind.as_str().to_string(),
+                    entry.display_name(),
+                    entry.ty.as_ref().map_or("-".to_string(), Type::to_string),
+                    format!("{}:{}", entry.span.start_line, entry.span.start_column),
+                ]
+            })
+            .collect();
+
+        let header = ["KIND", "NAME", "TYPE", "LOCATION"].map(String::from);
+        let mut widths = header.clone().map(|column| column.len());
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+
+        let mut table = String::new();
+        for row in std::iter::once(&header).chain(&rows) {
+            let line = format!(
+                "{:<w0$}  {:<w1$}  {:<w2$}  {}",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2],
+            );
+            table.push_str(line.trim_end());
+            table.push('\n');
+        }
+        table
+    }
+
+    /// Formats the entries as a JSON array of objects
+    pub fn to_json(&self) -> String {
+        let objects: Vec<String> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let mut fields = vec![
+                    format!("\"kind\": {}", json_string(entry.kind.as_str())),
+                    format!("\"name\": {}", json_string(&entry.name)),
+                ];
+                if let Some(owner) = &entry.owner {
+                    fields.push(format!("\"owner\": {}", json_string(owner)));
+                }
+                if matches!(entry.kind, SymbolKind::Function | SymbolKind::Method) {
+                    let params: Vec<String> = entry
+                        .params
+                        .iter()
+                        .map(|param| json_string(param))
+                        .collect();
+                    fields.push(format!("\"params\": [{}]", params.join(", ")));
+                }
+                fields.push(match &entry.ty {
+                    Some(ty) => format!("\"type\": {}", json_string(&ty.to_string())),
+                    None => "\"type\": null".to_string(),
+                });
+                fields.push(format!("\"line\": {}", entry.span.start_line));
+                fields.push(format!("\"column\": {}", entry.span.start_column));
+                format!("  {{{}}}", fields.join(", "))
+            })
+            .collect();
+
+        if objects.is_empty() {
+            "[]\n".to_string()
+        } else {
+            format!("[\n{}\n]\n", objects.join(",\n"))
+        }
+    }
+}
+
+/// Walks assignments in statement order, binding each variable's inferred type
+///
+/// Calls `visit` with each assigned name, its value's type, and the target's span.
+/// Branches and loop bodies are walked too.
+fn walk_assignments(
+    statements: &[Statement],
+    env: &mut TypeEnv,
+    visit: &mut dyn FnMut(&str, Type, Span),
+) {
+    for stmt in statements {
+        match stmt {
+            Statement::Assignment {
+                name,
+                ty,
+                value,
+                span,
+                ..
+            } => {
+                let ty = ty.map_or_else(|| env.infer(value), Type::from);
+                if !name.starts_with("self.") {
+                    env.bind(name, ty.clone());
+                }
+                visit(name, ty, *span);
+            }
+            Statement::ParallelAssignment {
+                names,
+                values,
+                span,
+            } => {
+                let types: Vec<Type> = values.iter().map(|value| env.infer(value)).collect();
+                for (name, ty) in names.iter().zip(types) {
+                    env.bind(name, ty.clone());
+                    visit(name, ty, *span);
+                }
+            }
+            Statement::ArrayDestructuring { names, value, span } => {
+                let element = match env.infer(value) {
+                    Type::Array(element) => *element,
+                    _ => Type::Unknown,
+                };
+                for name in names.iter().filter(|name| *name != "_") {
+                    env.bind(name, element.clone());
+                    visit(name, element.clone(), *span);
+                }
+            }
+            Statement::If {
+                then_branch,
+                elif_branches,
+                else_branch,
+                ..
+            } => {
+                walk_assignments(then_branch, env, visit);
+                for (_, branch) in elif_branches {
+                    walk_assignments(branch, env, visit);
+                }
+                if let Some(branch) = else_branch {
+                    walk_assignments(branch, env, visit);
+                }
+            }
+            Statement::While { body, .. }
+            | Statement::Loop { body, .. }
+            | Statement::DoWhile { body, .. }
+            | Statement::Block { body, .. } => walk_assignments(body, env, visit),
+            Statement::Match { arms, .. } => {
+                for arm in arms {
+                    walk_assignments(&arm.body, env, visit);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Quotes a string as a JSON string literal
+fn json_string(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
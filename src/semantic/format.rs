@@ -0,0 +1,239 @@
+//! `print` format strings
+//!
+//! A placeholder is `%`, then optional flags (`-` to align left, `0` to pad with
+//! zeros), an optional width, an optional `.` and precision, and a conversion: `d`
+//! for an integer, `f` for a float (six decimals unless a precision is given), `x`
+//! for an integer in hexadecimal, or `s` for any value. `%%` is a literal `%`, and a
+//! `%` that starts no placeholder is kept as text.
+
+use super::diagnostics::Diagnostic;
+use super::types::{Type, TypeEnv};
+use crate::lexer::Span;
+use crate::parser::Expr;
+use std::fmt;
+
+/// A conversion specifier in a `print` format string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatSpec {
+    /// `%d`: an integer
+    Int,
+    /// `%s`: any displayable value
+    Str,
+    /// `%f`: a float
+    Float,
+    /// `%x`: an integer, in lowercase hexadecimal
+    Hex,
+}
+
+impl FormatSpec {
+    /// Checks if a value of the given type can be formatted with this specifier
+    ///
+    /// Unknown types are accepted, since they cannot be ruled out statically.
+    pub fn accepts(&self, ty: &Type) -> bool {
+        match self {
+            FormatSpec::Int | FormatSpec::Hex => matches!(ty, Type::Int | Type::Unknown),
+            FormatSpec::Float => matches!(ty, Type::Float | Type::Unknown),
+            FormatSpec::Str => !matches!(ty, Type::Class(_)),
+        }
+    }
+
+    /// Returns the specifier as written in Grit source
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FormatSpec::Int => "%d",
+            FormatSpec::Str => "%s",
+            FormatSpec::Float => "%f",
+            FormatSpec::Hex => "%x",
+        }
+    }
+
+    fn from_conversion(conversion: char) -> Option<FormatSpec> {
+        match conversion {
+            'd' => Some(FormatSpec::Int),
+            's' => Some(FormatSpec::Str),
+            'f' => Some(FormatSpec::Float),
+            'x' => Some(FormatSpec::Hex),
+            _ => None,
+        }
+    }
+}
+
+/// A placeholder in a `print` format string: `%-8s`, `%05d`, `%.2f`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placeholder {
+    pub spec: FormatSpec,
+    /// `-`: the value is aligned left within the width
+    pub left: bool,
+    /// `0`: the width is padded with zeros instead of spaces
+    pub zero: bool,
+    pub width: Option<usize>,
+    pub precision: Option<usize>,
+}
+
+impl fmt::Display for Placeholder {
+    /// Writes the placeholder as written in Grit source
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "%")?;
+        if self.left {
+            write!(f, "-")?;
+        }
+        if self.zero {
+            write!(f, "0")?;
+        }
+        if let Some(width) = self.width {
+            write!(f, "{}", width)?;
+        }
+        if let Some(precision) = self.precision {
+            write!(f, ".{}", precision)?;
+        }
+        write!(f, "{}", &self.spec.as_str()[1..])
+    }
+}
+
+/// A piece of a `print` format string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatPart {
+    /// Text printed as it is, with `%%` already turned into `%`
+    Text(String),
+    Placeholder(Placeholder),
+}
+
+/// Splits a format string into its text and placeholders, in order
+pub fn parse_format_parts(format: &str) -> Vec<FormatPart> {
+    let mut parts = Vec::new();
+    let mut text = String::new();
+    let mut rest = format;
+
+    while let Some(start) = rest.find('%') {
+        text.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        if let Some(after) = after.strip_prefix('%') {
+            text.push('%');
+            rest = after;
+        } else if let Some((placeholder, after)) = read_placeholder(after) {
+            if !text.is_empty() {
+                parts.push(FormatPart::Text(std::mem::take(&mut text)));
+            }
+            parts.push(FormatPart::Placeholder(placeholder));
+            rest = after;
+        } else {
+            text.push('%');
+            rest = after;
+        }
+    }
+    text.push_str(rest);
+    if !text.is_empty() {
+        parts.push(FormatPart::Text(text));
+    }
+
+    parts
+}
+
+/// Reads the placeholder that follows a `%`, returning it with the text after it
+///
+/// A `.` without digits is a precision of zero, as in C.
+fn read_placeholder(text: &str) -> Option<(Placeholder, &str)> {
+    let digits = |text: &str| {
+        text.find(|ch: char| !ch.is_ascii_digit())
+            .unwrap_or(text.len())
+    };
+
+    let left = text.starts_with('-');
+    let rest = text.strip_prefix('-').unwrap_or(text);
+    let zero = rest.starts_with('0');
+    let rest = rest.strip_prefix('0').unwrap_or(rest);
+
+    let (width, rest) = rest.split_at(digits(rest));
+    let (precision, rest) = match rest.strip_prefix('.') {
+        Some(rest) => {
+            let (precision, rest) = rest.split_at(digits(rest));
+            (Some(precision.parse().unwrap_or(0)), rest)
+        }
+        None => (None, rest),
+    };
+
+    let mut chars = rest.chars();
+    let spec = FormatSpec::from_conversion(chars.next()?)?;
+    let placeholder = Placeholder {
+        spec,
+        left,
+        zero,
+        width: width.parse().ok(),
+        precision,
+    };
+    Some((placeholder, chars.as_str()))
+}
+
+/// Returns the placeholders of a format string in order of appearance
+pub fn parse_placeholders(format: &str) -> Vec<Placeholder> {
+    parse_format_parts(format)
+        .into_iter()
+        .filter_map(|part| match part {
+            FormatPart::Placeholder(placeholder) => Some(placeholder),
+            FormatPart::Text(_) => None,
+        })
+        .collect()
+}
+
+/// Returns the specifiers of a format string in order of appearance
+pub fn parse_format(format: &str) -> Vec<FormatSpec> {
+    parse_placeholders(format)
+        .into_iter()
+        .map(|placeholder| placeholder.spec)
+        .collect()
+}
+
+/// Checks a `print(format, args...)` call against its format string
+///
+/// Calls whose first argument is not a string literal are not checked.
+pub fn check_print_call(args: &[Expr], env: &TypeEnv, span: Span) -> Vec<Diagnostic> {
+    let format = match args.first() {
+        Some(Expr::String { value: format, .. }) => format,
+        // A lone value prints on its own; with values after it, its placeholders
+        // can't be known before the program runs
+        Some(_) if args.len() > 1 => {
+            return vec![Diagnostic::error(
+                "print() formats its values with a string literal",
+                span,
+            )]
+        }
+        _ => return Vec::new(),
+    };
+
+    let specs = parse_placeholders(format);
+    let values = &args[1..];
+
+    if specs.len() != values.len() {
+        return vec![Diagnostic::error(
+            format!(
+                "format string '{}' expects {} argument(s), found {}",
+                format,
+                specs.len(),
+                values.len()
+            ),
+            span,
+        )];
+    }
+
+    specs
+        .iter()
+        .zip(values)
+        .enumerate()
+        .filter_map(|(i, (spec, value))| {
+            let ty = env.infer(value);
+            if spec.spec.accepts(&ty) {
+                None
+            } else {
+                Some(Diagnostic::error(
+                    format!(
+                        "format specifier {} (argument {}) does not accept a value of type {}",
+                        spec,
+                        i + 1,
+                        ty
+                    ),
+                    span,
+                ))
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,16 @@
+pub mod checker;
+pub mod diagnostics;
+pub mod dump;
+pub mod format;
+pub mod signatures;
+pub mod symbols;
+pub mod types;
+
+pub use checker::check_program;
+pub use diagnostics::{Diagnostic, Severity};
+pub use dump::{SymbolDump, SymbolEntry, SymbolKind};
+pub use signatures::{bind_self, body_returns};
+pub use symbols::{
+    ClassSymbol, ConstSymbol, EnumSymbol, FieldSymbol, FunctionSymbol, GlobalSymbol, SymbolTable,
+};
+pub use types::{Type, TypeEnv};
@@ -0,0 +1,506 @@
+//! Types of unannotated parameters and return values, inferred from the program
+//!
+//! A parameter of a top-level function or a method without an annotation takes the
+//! type of the arguments passed to it: `greet('Bob')` makes `name` in
+//! `fn greet(name)` a string, and `P.new('Bob')` does the same for `fn P > new(name)`.
+//! A method's calls are those made on its class by name and on instances of the
+//! class. A parameter called with values of different types, or never called,
+//! stays unknown, which the generator takes as an integer.
+//!
+//! A function or method body gives back the values of its `return` statements and
+//! of its final expression, unless that is a `print`. When there are none, it
+//! returns nothing. Otherwise the values settle on one type the way parameters do,
+//! and a body that gives back `nil` on some paths gives back an optional value.
+//!
+//! Only integers, floats, strings, booleans, class instances, and tuples of them
+//! are inferred. A parameter passed instances of different classes takes the first
+//! trait they all implement, so one function can be called with each of them.
+//! A field declared without an annotation that a constructor sets straight from a
+//! parameter takes the parameter's type.
+//!
+//! Calls are typed with what is known where they are made, including the
+//! signatures already inferred, so types spread through functions that pass a
+//! parameter on or return another call's result; the pass repeats until no
+//! signature changes.
+
+use super::symbols::{FunctionSymbol, SymbolTable};
+use super::types::{Type, TypeEnv};
+use crate::lexer::Span;
+use crate::parser::ast::visit::{walk_body, walk_expr, walk_statement};
+use crate::parser::{Expr, Program, Statement, Visitor};
+use std::collections::HashMap;
+
+/// The inferred signature of one definition
+struct Signature {
+    /// The definition's header, which tells it apart from others of the same name
+    span: Span,
+    params: Vec<Type>,
+    returns: Option<Type>,
+}
+
+/// Infers the signatures of the program's functions and methods into its table
+pub(super) fn infer(program: &Program, table: &mut SymbolTable) {
+    let definitions = program
+        .statements
+        .iter()
+        .filter(|stmt| {
+            matches!(
+                stmt,
+                Statement::FunctionDef { .. } | Statement::MethodDef { .. }
+            )
+        })
+        .count();
+
+    // Each round can only settle more signatures, so a round per definition ends it
+    for _ in 0..=definitions {
+        let signatures = infer_round(program, table);
+        let mut changed = false;
+        for signature in signatures {
+            if let Some(symbol) = table.signature_mut(signature.span) {
+                if symbol.inferred_params != signature.params || symbol.returns != signature.returns
+                {
+                    symbol.inferred_params = signature.params;
+                    symbol.returns = signature.returns;
+                    changed = true;
+                }
+            }
+        }
+        changed |= settle_fields(program, table);
+        if !changed {
+            break;
+        }
+    }
+}
+
+/// Infers every signature once, from what the table knows so far
+fn infer_round(program: &Program, symbols: &SymbolTable) -> Vec<Signature> {
+    let mut calls = CallSites {
+        env: TypeEnv::new(symbols),
+        args: HashMap::new(),
+    };
+    walk_body(&mut calls, &program.statements);
+
+    let mut signatures = Vec::new();
+    for stmt in &program.statements {
+        match stmt {
+            Statement::FunctionDef {
+                name,
+                params,
+                variadic,
+                return_type,
+                body,
+                span,
+                ..
+            } => {
+                let Some(function) = symbols.function(name).filter(|f| f.span == *span) else {
+                    continue;
+                };
+                let types = calls.param_types(*span, &function.param_types, *variadic);
+                let returns = match return_type {
+                    Some(_) => Some(Type::annotated(*return_type)),
+                    None => {
+                        let env = bound_params(symbols, params, &types, *variadic);
+                        body_returns(env, body)
+                    }
+                };
+                signatures.push(Signature {
+                    span: *span,
+                    params: types,
+                    returns,
+                });
+            }
+            Statement::MethodDef {
+                class_name,
+                is_static,
+                params,
+                variadic,
+                return_type,
+                body,
+                span,
+                ..
+            } => {
+                let Some(method) = symbols.signature(*span) else {
+                    continue;
+                };
+                let types = calls.param_types(*span, &method.param_types, *variadic);
+                let returns = match return_type {
+                    Some(_) => Some(Type::annotated(*return_type)),
+                    None => {
+                        let mut env = bound_params(symbols, params, &types, *variadic);
+                        if !is_static {
+                            bind_self(&mut env, class_name);
+                        }
+                        body_returns(env, body)
+                    }
+                };
+                signatures.push(Signature {
+                    span: *span,
+                    params: types,
+                    returns,
+                });
+            }
+            _ => {}
+        }
+    }
+    signatures
+}
+
+/// Types each unannotated field a constructor sets straight from a parameter with
+/// the parameter's type, returning whether any field changed
+///
+/// A parameter typed by a trait is borrowed, so the field it sets is left alone.
+fn settle_fields(program: &Program, table: &mut SymbolTable) -> bool {
+    let unannotated: Vec<(&str, &str)> = program
+        .statements
+        .iter()
+        .filter_map(|stmt| match stmt {
+            Statement::ClassDef { name, fields, .. } => Some((name, fields)),
+            _ => None,
+        })
+        .flat_map(|(class_name, fields)| {
+            fields
+                .iter()
+                .filter(|field| field.ty.is_none())
+                .map(move |field| (class_name.as_str(), field.name.as_str()))
+        })
+        .collect();
+
+    let mut changed = false;
+    for stmt in &program.statements {
+        let Statement::MethodDef {
+            class_name,
+            method_name,
+            params,
+            body,
+            span,
+            ..
+        } = stmt
+        else {
+            continue;
+        };
+        if method_name != "new" {
+            continue;
+        }
+        let Some(types) = table
+            .signature(*span)
+            .map(|new| new.inferred_params.clone())
+        else {
+            continue;
+        };
+        for stmt in body {
+            let Statement::Assignment {
+                name,
+                value: Expr::Identifier { name: param, .. },
+                ..
+            } = stmt
+            else {
+                continue;
+            };
+            let Some(field) = name.strip_prefix("self.") else {
+                continue;
+            };
+            let ty = params
+                .iter()
+                .position(|candidate| candidate == param)
+                .and_then(|i| types.get(i))
+                .filter(|ty| !matches!(ty, Type::Unknown | Type::Trait(_)));
+            if !unannotated.contains(&(class_name.as_str(), field)) {
+                continue;
+            }
+            let symbol = table
+                .class_mut(class_name)
+                .and_then(|class| class.fields.iter_mut().find(|symbol| symbol.name == field));
+            if let (Some(symbol), Some(ty)) = (symbol, ty) {
+                if symbol.ty != *ty {
+                    symbol.ty = ty.clone();
+                    changed = true;
+                }
+            }
+        }
+    }
+    changed
+}
+
+/// Creates an environment holding only a definition's parameters
+///
+/// A variadic last parameter is an array of the extra arguments.
+fn bound_params<'a>(
+    symbols: &'a SymbolTable,
+    params: &[String],
+    types: &[Type],
+    variadic: bool,
+) -> TypeEnv<'a> {
+    let mut env = TypeEnv::new(symbols);
+    for (i, param) in params.iter().enumerate() {
+        let ty = types.get(i).cloned().unwrap_or(Type::Unknown);
+        if variadic && i == params.len() - 1 {
+            env.bind(param, Type::Array(Box::new(ty)));
+        } else {
+            env.bind(param, ty);
+        }
+    }
+    env
+}
+
+/// Binds `self` to an instance of a class, along with the fields of the class and
+/// its ancestors that a method body may name without `self.`
+pub fn bind_self(env: &mut TypeEnv, class_name: &str) {
+    let symbols = env.symbols();
+    let classes = symbols
+        .class(class_name)
+        .into_iter()
+        .chain(symbols.ancestors(class_name));
+    for field in classes.flat_map(|class| &class.fields) {
+        if env.lookup(&field.name).is_none() {
+            env.bind(&field.name, field.ty.clone());
+        }
+    }
+    env.bind("self", Type::Class(class_name.to_string()));
+}
+
+/// Settles the values a body gives back on one type, `None` if it gives back none
+///
+/// `env` holds the body's parameters.
+pub fn body_returns(env: TypeEnv, body: &[Statement]) -> Option<Type> {
+    let mut returned = Returned {
+        env,
+        values: Vec::new(),
+    };
+    walk_body(&mut returned, body);
+    if let Some(Statement::Expression(expr)) = body.last() {
+        if !matches!(expr, Expr::FunctionCall { name, .. } if name == "print") {
+            let value = returned.value_type(expr);
+            returned.values.push(value);
+        }
+    }
+
+    let (nils, values): (Vec<Type>, Vec<Type>) = returned
+        .values
+        .into_iter()
+        .flatten()
+        .partition(|ty| *ty == Type::Nil);
+    if values.is_empty() {
+        return nils.first().cloned();
+    }
+    // Returning `nil` on some paths makes the value optional
+    Some(match settled(Type::unify(values)) {
+        ty if nils.is_empty() || ty == Type::Unknown => ty,
+        ty => Type::Optional(Box::new(ty)),
+    })
+}
+
+/// Keeps the types that are inferred, and makes any other unknown; a tuple keeps
+/// the inferred types of its elements
+fn settled(ty: Type) -> Type {
+    match ty {
+        Type::Int | Type::Float | Type::String | Type::Bool | Type::Class(_) | Type::Trait(_) => ty,
+        Type::Tuple(elements) => Type::Tuple(elements.into_iter().map(settled).collect()),
+        _ => Type::Unknown,
+    }
+}
+
+/// Records the types of the arguments of every call to a top-level function or a
+/// method
+struct CallSites<'a> {
+    env: TypeEnv<'a>,
+    /// The argument types of each call, by the callee's header and parameter position
+    args: HashMap<Span, Vec<Vec<Type>>>,
+}
+
+impl<'a> CallSites<'a> {
+    /// Settles each unannotated parameter of a definition on the type every call
+    /// agrees on; a variadic parameter collects whatever is passed
+    fn param_types(&self, span: Span, annotated: &[Type], variadic: bool) -> Vec<Type> {
+        let calls = self.args.get(&span);
+        annotated
+            .iter()
+            .enumerate()
+            .map(|(i, ty)| match (ty, calls) {
+                (Type::Unknown, Some(calls)) if !(variadic && i == annotated.len() - 1) => {
+                    let args: Vec<Type> = calls.iter().map(|call| call[i].clone()).collect();
+                    match settled(Type::unify(args.clone())) {
+                        Type::Unknown => self.shared_trait(&args).unwrap_or(Type::Unknown),
+                        ty => ty,
+                    }
+                }
+                _ => ty.clone(),
+            })
+            .collect()
+    }
+
+    /// Returns the first trait, in the order the first class lists them, that every
+    /// argument's class implements, when every argument is an instance of a class
+    fn shared_trait(&self, args: &[Type]) -> Option<Type> {
+        let symbols = self.env.symbols();
+        let classes: Vec<_> = args
+            .iter()
+            .map(|arg| match arg {
+                Type::Class(name) => symbols.class(name),
+                _ => None,
+            })
+            .collect::<Option<_>>()?;
+        let (first, rest) = classes.split_first()?;
+        first
+            .traits
+            .iter()
+            .find(|name| rest.iter().all(|class| class.traits.contains(name)))
+            .map(|name| Type::Trait(name.clone()))
+    }
+
+    /// Returns the method a call on an object runs: a method of the class the object
+    /// names, or of the class the object is an instance of
+    fn method_callee(&self, object: &Expr, method: &str) -> Option<&'a FunctionSymbol> {
+        let symbols = self.env.symbols();
+        match self.env.class_receiver(object) {
+            Some(class_name) => symbols.resolve_method(class_name, method),
+            None => match self.env.infer(object) {
+                Type::Class(class_name) => symbols.resolve_method(&class_name, method),
+                _ => None,
+            },
+        }
+    }
+
+    /// Records the type of each argument of a call, by the callee's parameter
+    /// position
+    fn record(&mut self, callee: &FunctionSymbol, args: &[Expr]) {
+        let mut types = vec![Type::Unknown; callee.params.len()];
+        let mut position = 0;
+        for arg in args {
+            let index = match arg {
+                Expr::NamedArg { name, .. } => callee.params.iter().position(|param| param == name),
+                _ => {
+                    position += 1;
+                    Some(position - 1)
+                }
+            };
+            if let Some(slot) = index.and_then(|index| types.get_mut(index)) {
+                *slot = self.env.infer(arg);
+            }
+        }
+        self.args.entry(callee.span).or_default().push(types);
+    }
+
+    /// Types a body with only the given parameters in scope, then restores the scope
+    fn function_body(&mut self, params: &[String], types: &[Type], body: &[Statement]) {
+        let mut env = TypeEnv::new(self.env.symbols());
+        for (param, ty) in params.iter().zip(types) {
+            env.bind(param, ty.clone());
+        }
+        let outer = std::mem::replace(&mut self.env, env);
+        walk_body(self, body);
+        self.env = outer;
+    }
+}
+
+impl Visitor for CallSites<'_> {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        let symbols = self.env.symbols();
+        match stmt {
+            Statement::FunctionDef {
+                name, params, body, ..
+            } => {
+                let types = symbols
+                    .function(name)
+                    .map_or(&[][..], |function| &function.inferred_params);
+                self.function_body(params, types, body);
+            }
+            Statement::MethodDef {
+                class_name,
+                is_static,
+                params,
+                param_types,
+                body,
+                span,
+                ..
+            } => {
+                let mut params = params.clone();
+                let mut types: Vec<Type> = match symbols.signature(*span) {
+                    Some(method) => method.inferred_params.clone(),
+                    None => param_types.iter().copied().map(Type::annotated).collect(),
+                };
+                types.resize(params.len(), Type::Unknown);
+                if !is_static {
+                    params.push("self".to_string());
+                    types.push(Type::Class(class_name.clone()));
+                }
+                self.function_body(&params, &types, body);
+            }
+            Statement::Test { body, .. } => self.function_body(&[], &[], body),
+            Statement::Assignment { name, value, .. } => {
+                self.visit_expr(value);
+                let ty = self.env.infer(value);
+                self.env.bind(name, ty);
+            }
+            _ => walk_statement(self, stmt),
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        let call = match expr {
+            // A variable holding a lambda hides the function
+            Expr::FunctionCall { name, args, .. } if self.env.lookup(name).is_none() => self
+                .env
+                .symbols()
+                .function(name)
+                .map(|function| (function, args)),
+            Expr::MethodCall {
+                object,
+                method,
+                args,
+                ..
+            } => self
+                .method_callee(object, method)
+                .map(|method| (method, args)),
+            _ => None,
+        };
+        if let Some((callee, args)) = call {
+            self.record(callee, args);
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Records the type of every value a body returns
+struct Returned<'a> {
+    env: TypeEnv<'a>,
+    /// The type of each `return`'s value, `None` for a bare `return` or a value
+    /// that is nothing
+    values: Vec<Option<Type>>,
+}
+
+impl Returned<'_> {
+    /// Types a value, taking a call as what its callee gives back
+    fn value_type(&self, value: &Expr) -> Option<Type> {
+        match self.env.callee(value) {
+            Some(callee) => callee.returns.clone(),
+            None => Some(self.env.infer(value)),
+        }
+    }
+}
+
+impl Visitor for Returned<'_> {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        match stmt {
+            // What nested definitions return is their own
+            Statement::FunctionDef { .. }
+            | Statement::MethodDef { .. }
+            | Statement::Test { .. } => {}
+            Statement::Assignment { name, value, .. } => {
+                self.visit_expr(value);
+                let ty = self.value_type(value).unwrap_or(Type::Unknown);
+                self.env.bind(name, ty);
+            }
+            Statement::Return { value, .. } => {
+                let ty = value.as_ref().and_then(|value| self.value_type(value));
+                self.values.push(ty);
+            }
+            _ => walk_statement(self, stmt),
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        if !matches!(expr, Expr::Lambda { .. }) {
+            walk_expr(self, expr);
+        }
+    }
+}
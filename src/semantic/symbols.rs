@@ -0,0 +1,553 @@
+use super::signatures;
+use super::types::{Type, TypeEnv};
+use crate::lexer::Span;
+use crate::parser::{Program, Statement, TypeAnnotation};
+use std::collections::HashMap;
+
+/// A class declared with `class Name` or implied by a `fn Name > method` definition
+///
+/// Mixins and traits are recorded the same way; a trait's methods are the
+/// signatures in its body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassSymbol {
+    pub name: String,
+    pub methods: Vec<FunctionSymbol>,
+    /// The class inherited from
+    pub parent: Option<String>,
+    /// Traits listed after the class name's `:`, in order
+    pub traits: Vec<String>,
+    /// Mixins listed in the class's `with` clause, in order
+    pub mixins: Vec<String>,
+    /// Fields declared in the class body, in order; empty without a body
+    pub fields: Vec<FieldSymbol>,
+    /// Where the class is declared, or its first method for an implied class
+    pub span: Span,
+}
+
+impl ClassSymbol {
+    /// Returns the method with the given name, if the class defines it
+    pub fn method(&self, name: &str) -> Option<&FunctionSymbol> {
+        self.methods.iter().find(|method| method.name == name)
+    }
+
+    /// Returns the declared field with the given name
+    pub fn field(&self, name: &str) -> Option<&FieldSymbol> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+/// A field declared in a class body
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldSymbol {
+    pub name: String,
+    /// The annotated type, `Unknown` without one
+    pub ty: Type,
+    pub span: Span,
+}
+
+/// A top-level function or a method
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionSymbol {
+    pub name: String,
+    pub params: Vec<String>,
+    /// The annotated type of each parameter, `Unknown` where there is none
+    pub param_types: Vec<Type>,
+    /// Set when the last parameter collects any extra arguments into an array
+    pub variadic: bool,
+    /// The annotated return type, `Unknown` without one
+    pub return_type: Type,
+    /// The type of each parameter as the body sees it: the annotation, or the type
+    /// every call passes
+    pub inferred_params: Vec<Type>,
+    /// What a call gives back: the annotated type, or the type inferred from the
+    /// body; `None` when it gives back nothing
+    pub returns: Option<Type>,
+    /// Set for static methods, which are called on the class; never set for functions
+    pub is_static: bool,
+    /// The definition's header
+    pub span: Span,
+}
+
+impl FunctionSymbol {
+    /// Records a definition's signature as annotated, before anything is inferred
+    fn declared(
+        name: &str,
+        params: &[String],
+        param_types: &[Option<TypeAnnotation>],
+        variadic: bool,
+        return_type: Option<TypeAnnotation>,
+        is_static: bool,
+        span: Span,
+    ) -> Self {
+        let param_types: Vec<Type> = param_types.iter().copied().map(Type::annotated).collect();
+        FunctionSymbol {
+            name: name.to_string(),
+            params: params.to_vec(),
+            inferred_params: param_types.clone(),
+            param_types,
+            variadic,
+            return_type: Type::annotated(return_type),
+            returns: return_type.map(Type::from),
+            is_static,
+            span,
+        }
+    }
+
+    /// Returns the number of arguments that must always be passed
+    pub fn required_args(&self) -> usize {
+        if self.variadic {
+            self.params.len() - 1
+        } else {
+            self.params.len()
+        }
+    }
+}
+
+/// An enum declared with `enum Name { Variant, ... }`
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumSymbol {
+    pub name: String,
+    /// The variant names, in order
+    pub variants: Vec<String>,
+    pub span: Span,
+}
+
+impl EnumSymbol {
+    /// Checks if the enum declares a variant with the given name
+    pub fn has_variant(&self, name: &str) -> bool {
+        self.variants.iter().any(|variant| variant == name)
+    }
+}
+
+/// A constant declared with `const NAME = value`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstSymbol {
+    pub name: String,
+    /// The type of the value, inferred from literals and earlier constants
+    pub ty: Type,
+    pub span: Span,
+}
+
+/// A variable declared with `global name = value`, shared by main and every function
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlobalSymbol {
+    pub name: String,
+    /// The type of the initial value
+    pub ty: Type,
+    pub span: Span,
+}
+
+/// Program-wide table of declared classes, functions, constants, and global variables
+///
+/// Built once from a parsed `Program` and consulted by later passes (such as code
+/// generation) to resolve names instead of guessing from their spelling.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SymbolTable {
+    classes: HashMap<String, ClassSymbol>,
+    mixins: HashMap<String, ClassSymbol>,
+    traits: HashMap<String, ClassSymbol>,
+    enums: HashMap<String, EnumSymbol>,
+    functions: HashMap<String, FunctionSymbol>,
+    consts: Vec<ConstSymbol>,
+    globals: Vec<String>,
+    global_vars: Vec<GlobalSymbol>,
+}
+
+impl SymbolTable {
+    /// Collects the declarations of a program
+    pub fn from_program(program: &Program) -> Self {
+        let mut table = SymbolTable::default();
+        // Constant values are typed from literals and earlier constants only
+        let no_symbols = SymbolTable::default();
+        let mut const_types = TypeEnv::new(&no_symbols);
+
+        // Mixins, traits, and enums first, so methods defined before their mixin
+        // declaration land on it
+        for stmt in &program.statements {
+            if let Statement::MixinDef { name, span } = stmt {
+                table.mixins.insert(
+                    name.clone(),
+                    ClassSymbol {
+                        name: name.clone(),
+                        methods: Vec::new(),
+                        parent: None,
+                        traits: Vec::new(),
+                        mixins: Vec::new(),
+                        fields: Vec::new(),
+                        span: *span,
+                    },
+                );
+            }
+            if let Statement::TraitDef {
+                name,
+                methods,
+                span,
+            } = stmt
+            {
+                // A trait's methods have no body to infer from
+                let methods = methods
+                    .iter()
+                    .map(|method| {
+                        let param_types: Vec<Type> = method
+                            .param_types
+                            .iter()
+                            .copied()
+                            .map(Type::annotated)
+                            .collect();
+                        FunctionSymbol {
+                            name: method.name.clone(),
+                            params: method.params.clone(),
+                            inferred_params: param_types.clone(),
+                            param_types,
+                            variadic: method.variadic,
+                            return_type: Type::annotated(method.return_type),
+                            returns: Some(Type::annotated(method.return_type)),
+                            is_static: false,
+                            span: method.span,
+                        }
+                    })
+                    .collect();
+                table.traits.insert(
+                    name.clone(),
+                    ClassSymbol {
+                        name: name.clone(),
+                        methods,
+                        parent: None,
+                        traits: Vec::new(),
+                        mixins: Vec::new(),
+                        fields: Vec::new(),
+                        span: *span,
+                    },
+                );
+            }
+            if let Statement::EnumDef {
+                name,
+                variants,
+                span,
+            } = stmt
+            {
+                table
+                    .enums
+                    .entry(name.clone())
+                    .or_insert_with(|| EnumSymbol {
+                        name: name.clone(),
+                        variants: variants.clone(),
+                        span: *span,
+                    });
+            }
+        }
+
+        for stmt in &program.statements {
+            match stmt {
+                Statement::ClassDef {
+                    name,
+                    parent,
+                    traits,
+                    mixins,
+                    fields,
+                    span,
+                    ..
+                } => {
+                    let class = table.declare_class(name, *span);
+                    class.parent = parent.clone();
+                    class.traits.extend(traits.clone());
+                    class.mixins.extend(mixins.clone());
+                    class.fields.extend(fields.iter().map(|field| FieldSymbol {
+                        name: field.name.clone(),
+                        ty: Type::annotated(field.ty),
+                        span: field.span,
+                    }));
+                    // An explicit declaration outranks a method seen before it
+                    class.span = *span;
+                }
+                Statement::MethodDef {
+                    class_name,
+                    method_name,
+                    is_static,
+                    params,
+                    param_types,
+                    variadic,
+                    return_type,
+                    span,
+                    ..
+                } => {
+                    let method = FunctionSymbol::declared(
+                        method_name,
+                        params,
+                        param_types,
+                        *variadic,
+                        *return_type,
+                        *is_static,
+                        *span,
+                    );
+                    // A trait's methods are declared in its body, and an enum has
+                    // none, so a definition on either declares nothing
+                    if let Some(mixin) = table.mixins.get_mut(class_name) {
+                        mixin.methods.push(method);
+                    } else if !table.is_trait(class_name) && !table.is_enum(class_name) {
+                        table.declare_class(class_name, *span).methods.push(method);
+                    }
+                }
+                Statement::FunctionDef {
+                    name,
+                    params,
+                    param_types,
+                    variadic,
+                    return_type,
+                    span,
+                    ..
+                } => {
+                    table.functions.insert(
+                        name.clone(),
+                        FunctionSymbol::declared(
+                            name,
+                            params,
+                            param_types,
+                            *variadic,
+                            *return_type,
+                            false,
+                            *span,
+                        ),
+                    );
+                }
+                Statement::ConstDef { name, value, span } => {
+                    let ty = const_types.infer(value);
+                    const_types.bind(name, ty.clone());
+                    table.consts.push(ConstSymbol {
+                        name: name.clone(),
+                        ty,
+                        span: *span,
+                    });
+                }
+                Statement::Assignment { name, .. } | Statement::Global { name, .. }
+                    if !table.is_global(name) =>
+                {
+                    table.globals.push(name.clone());
+                }
+                Statement::ParallelAssignment { names, .. }
+                | Statement::ArrayDestructuring { names, .. } => {
+                    for name in names.iter().filter(|name| *name != "_") {
+                        if !table.is_global(name) {
+                            table.globals.push(name.clone());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        signatures::infer(program, &mut table);
+
+        // Initial values may call functions, so they are typed once those are known
+        let mut global_vars = Vec::new();
+        let mut global_types = TypeEnv::new(&table);
+        for stmt in &program.statements {
+            if let Statement::Global { name, value, span } = stmt {
+                let ty = global_types.infer(value);
+                global_types.bind(name, ty.clone());
+                global_vars.push(GlobalSymbol {
+                    name: name.clone(),
+                    ty,
+                    span: *span,
+                });
+            }
+        }
+        table.global_vars = global_vars;
+
+        table
+    }
+
+    fn declare_class(&mut self, name: &str, span: Span) -> &mut ClassSymbol {
+        self.classes
+            .entry(name.to_string())
+            .or_insert_with(|| ClassSymbol {
+                name: name.to_string(),
+                methods: Vec::new(),
+                parent: None,
+                traits: Vec::new(),
+                mixins: Vec::new(),
+                fields: Vec::new(),
+                span,
+            })
+    }
+
+    /// Returns the function or method defined with the given header
+    /// Returns the function or method defined at a span, with its inferred signature
+    pub fn signature(&self, span: Span) -> Option<&FunctionSymbol> {
+        let methods = self
+            .classes
+            .values()
+            .chain(self.mixins.values())
+            .flat_map(|class| &class.methods);
+        self.functions
+            .values()
+            .chain(methods)
+            .find(|function| function.span == span)
+    }
+
+    pub(super) fn class_mut(&mut self, name: &str) -> Option<&mut ClassSymbol> {
+        self.classes.get_mut(name)
+    }
+
+    pub(super) fn signature_mut(&mut self, span: Span) -> Option<&mut FunctionSymbol> {
+        let methods = self
+            .classes
+            .values_mut()
+            .chain(self.mixins.values_mut())
+            .flat_map(|class| class.methods.iter_mut());
+        self.functions
+            .values_mut()
+            .chain(methods)
+            .find(|function| function.span == span)
+    }
+
+    /// Checks if a class with the given name is declared
+    pub fn is_class(&self, name: &str) -> bool {
+        self.classes.contains_key(name)
+    }
+
+    /// Returns the class with the given name
+    pub fn class(&self, name: &str) -> Option<&ClassSymbol> {
+        self.classes.get(name)
+    }
+
+    /// Checks if a mixin with the given name is declared
+    pub fn is_mixin(&self, name: &str) -> bool {
+        self.mixins.contains_key(name)
+    }
+
+    /// Returns the mixin with the given name
+    pub fn mixin(&self, name: &str) -> Option<&ClassSymbol> {
+        self.mixins.get(name)
+    }
+
+    /// Checks if a trait with the given name is declared
+    pub fn is_trait(&self, name: &str) -> bool {
+        self.traits.contains_key(name)
+    }
+
+    /// Returns the trait with the given name, its methods being the signatures the
+    /// trait declares
+    pub fn trait_def(&self, name: &str) -> Option<&ClassSymbol> {
+        self.traits.get(name)
+    }
+
+    /// Returns the method a class responds to, whether defined on the class itself,
+    /// copied from one of its mixins, or inherited
+    ///
+    /// The class's own methods win, then mixins in the order they are listed, then
+    /// the parent class's methods, resolved the same way.
+    pub fn resolve_method(&self, class_name: &str, method_name: &str) -> Option<&FunctionSymbol> {
+        std::iter::once(self.class(class_name)?)
+            .chain(self.ancestors(class_name))
+            .find_map(|class| {
+                class.method(method_name).or_else(|| {
+                    class
+                        .mixins
+                        .iter()
+                        .filter_map(|mixin| self.mixin(mixin))
+                        .find_map(|mixin| mixin.method(method_name))
+                })
+            })
+    }
+
+    /// Returns the classes a class inherits from, nearest first
+    ///
+    /// Stops at a parent that is not a declared class, or that would repeat one
+    /// already listed.
+    pub fn ancestors(&self, class_name: &str) -> Vec<&ClassSymbol> {
+        let mut ancestors: Vec<&ClassSymbol> = Vec::new();
+        let mut current = self.class(class_name);
+        while let Some(parent) = current
+            .and_then(|class| class.parent.as_deref())
+            .and_then(|parent| self.class(parent))
+        {
+            if parent.name == class_name || ancestors.iter().any(|seen| seen.name == parent.name) {
+                break;
+            }
+            ancestors.push(parent);
+            current = Some(parent);
+        }
+        ancestors
+    }
+
+    /// Returns the top-level function with the given name
+    pub fn function(&self, name: &str) -> Option<&FunctionSymbol> {
+        self.functions.get(name)
+    }
+
+    /// Returns every class, ordered by declaration
+    pub fn classes(&self) -> Vec<&ClassSymbol> {
+        Self::by_position(self.classes.values())
+    }
+
+    /// Returns every mixin, ordered by declaration
+    pub fn mixins(&self) -> Vec<&ClassSymbol> {
+        Self::by_position(self.mixins.values())
+    }
+
+    /// Returns every trait, ordered by declaration
+    pub fn traits(&self) -> Vec<&ClassSymbol> {
+        Self::by_position(self.traits.values())
+    }
+
+    /// Checks if an enum with the given name is declared
+    pub fn is_enum(&self, name: &str) -> bool {
+        self.enums.contains_key(name)
+    }
+
+    /// Returns the enum with the given name
+    pub fn enum_def(&self, name: &str) -> Option<&EnumSymbol> {
+        self.enums.get(name)
+    }
+
+    /// Returns every enum, ordered by declaration
+    pub fn enums(&self) -> Vec<&EnumSymbol> {
+        let mut enums: Vec<_> = self.enums.values().collect();
+        enums.sort_by_key(|symbol| (symbol.span.start_line, symbol.span.start_column));
+        enums
+    }
+
+    /// Returns every top-level function, ordered by definition
+    pub fn functions(&self) -> Vec<&FunctionSymbol> {
+        let mut functions: Vec<_> = self.functions.values().collect();
+        functions.sort_by_key(|function| (function.span.start_line, function.span.start_column));
+        functions
+    }
+
+    fn by_position<'t>(classes: impl Iterator<Item = &'t ClassSymbol>) -> Vec<&'t ClassSymbol> {
+        let mut classes: Vec<_> = classes.collect();
+        classes.sort_by_key(|class| (class.span.start_line, class.span.start_column));
+        classes
+    }
+
+    /// Returns the first constant declared with the given name
+    pub fn constant(&self, name: &str) -> Option<&ConstSymbol> {
+        self.consts.iter().find(|constant| constant.name == name)
+    }
+
+    /// Returns the constants in order of declaration
+    pub fn consts(&self) -> &[ConstSymbol] {
+        &self.consts
+    }
+
+    /// Checks if a variable with the given name is assigned at the top level
+    pub fn is_global(&self, name: &str) -> bool {
+        self.globals.iter().any(|global| global == name)
+    }
+
+    /// Returns the top-level variables in order of first assignment
+    pub fn globals(&self) -> &[String] {
+        &self.globals
+    }
+
+    /// Returns the first `global` declaration of the given name
+    pub fn global_var(&self, name: &str) -> Option<&GlobalSymbol> {
+        self.global_vars.iter().find(|global| global.name == name)
+    }
+
+    /// Returns the `global` declarations in order
+    pub fn global_vars(&self) -> &[GlobalSymbol] {
+        &self.global_vars
+    }
+}
@@ -0,0 +1,418 @@
+use super::symbols::{FunctionSymbol, SymbolTable};
+use crate::parser::{BinaryOperator, Expr, Statement, TypeAnnotation, UnaryOperator};
+use std::collections::{HashMap, HashSet};
+
+/// Static type of a Grit value, as far as it can be determined without running the program
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    Int,
+    Float,
+    String,
+    Bool,
+    /// The type of `nil`, which has no other values
+    Nil,
+    /// Array with elements of the given type
+    Array(Box<Type>),
+    /// Map from keys of the first type to values of the second
+    Map(Box<Type>, Box<Type>),
+    /// Fixed-size group of values, each with its own type
+    Tuple(Vec<Type>),
+    /// Instance of a user-defined class
+    Class(String),
+    /// Instance of any class that implements a trait, such as a parameter passed
+    /// instances of different classes that share it
+    Trait(String),
+    /// Variant of a user-defined enum
+    Enum(String),
+    /// A value of the given type, or `nil`, such as what a function gives back
+    /// when some of its paths return `nil`
+    Optional(Box<Type>),
+    /// Not determinable statically (e.g. an untyped parameter)
+    Unknown,
+}
+
+impl Type {
+    /// Checks if the type is `Int` or `Float`
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, Type::Int | Type::Float)
+    }
+
+    /// Checks if the type is an array or a map
+    pub fn is_collection(&self) -> bool {
+        matches!(self, Type::Array(_) | Type::Map(_, _))
+    }
+
+    /// Returns the type an optional annotation declares, `Unknown` without one
+    pub fn annotated(annotation: Option<TypeAnnotation>) -> Type {
+        annotation.map_or(Type::Unknown, Type::from)
+    }
+
+    /// Checks if a value of type `actual` may be stored where `self` is declared
+    ///
+    /// Unknown types are assumed to fit, integers widen to floats, an optional type
+    /// takes `nil` or a value of its own type, and a trait takes an instance of a
+    /// class, which is assumed to implement it.
+    pub fn accepts(&self, actual: &Type) -> bool {
+        match self {
+            Type::Optional(inner) => {
+                matches!(actual, Type::Nil | Type::Unknown)
+                    || self == actual
+                    || inner.accepts(actual)
+            }
+            Type::Trait(_) if matches!(actual, Type::Class(_)) => true,
+            _ => {
+                *self == Type::Unknown
+                    || *actual == Type::Unknown
+                    || self == actual
+                    || (*self == Type::Float && *actual == Type::Int)
+            }
+        }
+    }
+
+    /// Returns the type of a value known not to be `nil`, as in the body of an
+    /// `if x = value` that only runs when the value is set
+    pub fn without_nil(&self) -> Type {
+        match self {
+            Type::Nil => Type::Unknown,
+            Type::Optional(inner) => (**inner).clone(),
+            ty => ty.clone(),
+        }
+    }
+
+    /// Checks if a value of the type may be `nil`
+    pub fn is_nilable(&self) -> bool {
+        matches!(self, Type::Nil | Type::Optional(_))
+    }
+
+    /// Returns the common type of a collection's elements
+    ///
+    /// Integers mixed with floats widen to `Float`; any other mix, or no elements
+    /// at all, is `Unknown`.
+    pub fn unify(types: impl IntoIterator<Item = Type>) -> Type {
+        let mut types = types.into_iter();
+        let first = match types.next() {
+            Some(ty) => ty,
+            None => return Type::Unknown,
+        };
+
+        types.fold(first, |common, ty| match (common, ty) {
+            (a, b) if a == b => a,
+            (Type::Int, Type::Float) | (Type::Float, Type::Int) => Type::Float,
+            _ => Type::Unknown,
+        })
+    }
+}
+
+impl std::fmt::Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::Int => write!(f, "int"),
+            Type::Float => write!(f, "float"),
+            Type::String => write!(f, "string"),
+            Type::Bool => write!(f, "bool"),
+            Type::Nil => write!(f, "nil"),
+            Type::Array(element) => write!(f, "array<{}>", element),
+            Type::Map(key, value) => write!(f, "map<{}, {}>", key, value),
+            Type::Tuple(elements) => {
+                let elements: Vec<String> = elements.iter().map(Type::to_string).collect();
+                write!(f, "({})", elements.join(", "))
+            }
+            Type::Class(name) | Type::Trait(name) | Type::Enum(name) => write!(f, "{}", name),
+            Type::Optional(inner) => write!(f, "{} or nil", inner),
+            Type::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl From<TypeAnnotation> for Type {
+    fn from(annotation: TypeAnnotation) -> Self {
+        match annotation {
+            TypeAnnotation::Int => Type::Int,
+            TypeAnnotation::Float => Type::Float,
+            TypeAnnotation::String => Type::String,
+            TypeAnnotation::Bool => Type::Bool,
+        }
+    }
+}
+
+/// Scoped map from variable names to their inferred types
+#[derive(Debug, Clone)]
+pub struct TypeEnv<'a> {
+    symbols: &'a SymbolTable,
+    scopes: Vec<HashMap<String, Type>>,
+    /// The variables of each scope declared with `var`
+    vars: Vec<HashSet<String>>,
+}
+
+impl<'a> TypeEnv<'a> {
+    /// Creates an environment with a single, empty scope
+    pub fn new(symbols: &'a SymbolTable) -> Self {
+        TypeEnv {
+            symbols,
+            scopes: vec![HashMap::new()],
+            vars: vec![HashSet::new()],
+        }
+    }
+
+    /// Returns the symbol table the environment resolves names against
+    pub fn symbols(&self) -> &'a SymbolTable {
+        self.symbols
+    }
+
+    /// Opens a nested scope
+    pub fn push_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+        self.vars.push(HashSet::new());
+    }
+
+    /// Closes the innermost scope
+    pub fn pop_scope(&mut self) {
+        self.scopes.pop();
+        self.vars.pop();
+    }
+
+    /// Records the type of a variable in the innermost scope
+    pub fn bind(&mut self, name: &str, ty: Type) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), ty);
+        }
+        if let Some(vars) = self.vars.last_mut() {
+            vars.remove(name);
+        }
+    }
+
+    /// Records the type of a `var` in the innermost scope, which may be reassigned
+    pub fn bind_var(&mut self, name: &str, ty: Type) {
+        self.bind(name, ty);
+        if let Some(vars) = self.vars.last_mut() {
+            vars.insert(name.to_string());
+        }
+    }
+
+    /// Checks if a name is a variable in scope, rather than a constant or global
+    pub fn is_local(&self, name: &str) -> bool {
+        self.scopes.iter().any(|scope| scope.contains_key(name))
+    }
+
+    /// Checks if a name is a variable of the innermost scope
+    pub fn is_local_to_scope(&self, name: &str) -> bool {
+        self.scopes
+            .last()
+            .is_some_and(|scope| scope.contains_key(name))
+    }
+
+    /// Checks if the variable in scope under a name was declared with `var`
+    pub fn is_var(&self, name: &str) -> bool {
+        self.scopes
+            .iter()
+            .zip(&self.vars)
+            .rev()
+            .find(|(scope, _)| scope.contains_key(name))
+            .is_some_and(|(_, vars)| vars.contains(name))
+    }
+
+    /// Checks if an assignment (without `var`) updates a variable in scope rather than
+    /// declaring a new one
+    ///
+    /// A variable is updated when it is assigned from a scope nested in the one that
+    /// declared it, unless the assignment is annotated and so declares its own; a
+    /// `var` is updated wherever it is assigned. The checker rejects updating a
+    /// variable that is not a `var`.
+    pub fn updates_variable(&self, name: &str, annotated: bool) -> bool {
+        self.is_var(name) || (!annotated && self.is_local(name) && !self.is_local_to_scope(name))
+    }
+
+    /// Returns the type of a variable, searching from the innermost scope outwards,
+    /// then the program's constants and global variables
+    pub fn lookup(&self, name: &str) -> Option<&Type> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .or_else(|| self.symbols.constant(name).map(|constant| &constant.ty))
+            .or_else(|| self.symbols.global_var(name).map(|global| &global.ty))
+    }
+
+    /// Checks if a name refers to a `global` variable, not hidden by one in scope
+    pub fn is_global_var(&self, name: &str) -> bool {
+        self.symbols.global_var(name).is_some() && !self.is_local(name)
+    }
+
+    /// Infers the type of an expression
+    pub fn infer(&self, expr: &Expr) -> Type {
+        match expr {
+            Expr::Integer { .. } => Type::Int,
+            Expr::Float { .. } => Type::Float,
+            Expr::Bool { .. } => Type::Bool,
+            Expr::Nil { .. } => Type::Nil,
+            Expr::String { .. } => Type::String,
+            Expr::Identifier { name, .. } => self.lookup(name).cloned().unwrap_or(Type::Unknown),
+            Expr::Grouped { inner, .. } | Expr::NamedArg { value: inner, .. } => self.infer(inner),
+            Expr::Array { elements, .. } => Type::Array(Box::new(Type::unify(
+                elements.iter().map(|element| self.infer(element)),
+            ))),
+            Expr::Tuple { elements, .. } => {
+                Type::Tuple(elements.iter().map(|element| self.infer(element)).collect())
+            }
+            Expr::Map { entries, .. } => Type::Map(
+                Box::new(Type::unify(entries.iter().map(|(key, _)| self.infer(key)))),
+                Box::new(Type::unify(
+                    entries.iter().map(|(_, value)| self.infer(value)),
+                )),
+            ),
+            Expr::Index { object, .. } => match self.infer(object) {
+                Type::Array(element) => *element,
+                Type::Map(_, value) => *value,
+                // A character of a string is a string of its own
+                Type::String => Type::String,
+                _ => Type::Unknown,
+            },
+            // Variables assigned inside a branch are out of scope here, so values
+            // computed from them are unknown
+            Expr::Cond {
+                branches,
+                else_branch,
+                ..
+            } => Type::unify(
+                branches
+                    .iter()
+                    .map(|(_, body)| body)
+                    .chain(std::iter::once(else_branch))
+                    .map(|body| match body.last() {
+                        Some(Statement::Expression(value)) => self.infer(value),
+                        _ => Type::Unknown,
+                    }),
+            ),
+            Expr::Slice { object, .. } => match self.infer(object) {
+                ty @ (Type::String | Type::Array(_)) => ty,
+                _ => Type::Unknown,
+            },
+            Expr::UnaryOp { op, operand, .. } => match op {
+                UnaryOperator::Not => Type::Bool,
+                UnaryOperator::Negate | UnaryOperator::Plus => match self.infer(operand) {
+                    ty if ty.is_numeric() => ty,
+                    _ => Type::Unknown,
+                },
+            },
+            Expr::BinaryOp {
+                left, op, right, ..
+            } => Self::infer_binary(op, self.infer(left), self.infer(right)),
+            Expr::FunctionCall { name, args, .. } => match name.as_str() {
+                "to_int" | "time" | "clock" => Type::Int,
+                "env" if args.len() == 1 => Type::Optional(Box::new(Type::String)),
+                "to_float" => Type::Float,
+                "to_string" | "env" | "typeof" => Type::String,
+                _ => self.returned_by(expr),
+            },
+            Expr::MethodCall { object, .. } if self.enum_receiver(object).is_some() => {
+                Type::Enum(self.enum_receiver(object).unwrap().to_string())
+            }
+            Expr::MethodCall { object, method, .. } if method == "new" => {
+                match self.class_receiver(object) {
+                    Some(class_name) => Type::Class(class_name.to_string()),
+                    None => self.returned_by(expr),
+                }
+            }
+            Expr::MethodCall { .. } => self.returned_by(expr),
+            Expr::Binding { .. } => Type::Bool,
+            Expr::FieldAccess { .. } | Expr::Call { .. } | Expr::Lambda { .. } => Type::Unknown,
+        }
+    }
+
+    /// Returns the function or method a call runs, when it can be told statically
+    ///
+    /// That is a top-level function not hidden by a variable, a static method
+    /// called on its class, or a method of a class instance.
+    pub fn callee(&self, expr: &Expr) -> Option<&'a FunctionSymbol> {
+        match expr {
+            Expr::FunctionCall { name, .. } if self.lookup(name).is_none() => {
+                self.symbols.function(name)
+            }
+            Expr::MethodCall { object, method, .. } => match self.class_receiver(object) {
+                Some(class_name) => self
+                    .symbols
+                    .resolve_method(class_name, method)
+                    .filter(|callee| callee.is_static && method != "new"),
+                None => match self.infer(object) {
+                    Type::Class(class_name) => self.symbols.resolve_method(&class_name, method),
+                    Type::Trait(trait_name) => self
+                        .symbols
+                        .trait_def(&trait_name)
+                        .and_then(|trait_def| trait_def.method(method)),
+                    _ => None,
+                },
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the type of what a call gives back, `Unknown` when it can't be told
+    /// or the callee gives back nothing
+    fn returned_by(&self, call: &Expr) -> Type {
+        self.callee(call)
+            .and_then(|callee| callee.returns.clone())
+            .unwrap_or(Type::Unknown)
+    }
+
+    /// Returns the class a static method call's receiver names, unless a variable
+    /// shadows it
+    pub fn class_receiver<'e>(&self, object: &'e Expr) -> Option<&'e str> {
+        match object {
+            Expr::Identifier { name, .. }
+                if self.lookup(name).is_none() && self.symbols.is_class(name) =>
+            {
+                Some(name)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the enum an `Enum.Variant` receiver names, unless a variable shadows it
+    pub fn enum_receiver<'e>(&self, object: &'e Expr) -> Option<&'e str> {
+        match object {
+            Expr::Identifier { name, .. }
+                if self.lookup(name).is_none() && self.symbols.is_enum(name) =>
+            {
+                Some(name)
+            }
+            _ => None,
+        }
+    }
+
+    fn infer_binary(op: &BinaryOperator, left: Type, right: Type) -> Type {
+        match op {
+            BinaryOperator::EqualEqual
+            | BinaryOperator::NotEqual
+            | BinaryOperator::LessThan
+            | BinaryOperator::LessThanOrEqual
+            | BinaryOperator::GreaterThan
+            | BinaryOperator::GreaterThanOrEqual
+            | BinaryOperator::In
+            | BinaryOperator::And
+            | BinaryOperator::Or => Type::Bool,
+            // Bitwise operators apply to integers, and `&`, `|`, `^` to booleans as well
+            BinaryOperator::BitAnd | BinaryOperator::BitOr | BinaryOperator::BitXor
+                if left == Type::Bool && right == Type::Bool =>
+            {
+                Type::Bool
+            }
+            BinaryOperator::BitAnd
+            | BinaryOperator::BitOr
+            | BinaryOperator::BitXor
+            | BinaryOperator::ShiftLeft
+            | BinaryOperator::ShiftRight => match (left, right) {
+                (Type::Int, Type::Int) => Type::Int,
+                _ => Type::Unknown,
+            },
+            BinaryOperator::Add if left == Type::String || right == Type::String => Type::String,
+            BinaryOperator::Add
+            | BinaryOperator::Subtract
+            | BinaryOperator::Multiply
+            | BinaryOperator::Divide => match (left, right) {
+                (Type::Int, Type::Int) => Type::Int,
+                (Type::Float, other) | (other, Type::Float) if other.is_numeric() => Type::Float,
+                _ => Type::Unknown,
+            },
+        }
+    }
+}
@@ -0,0 +1,303 @@
+//! `grit test`: compiles `test` blocks into a Rust harness, runs it, and collects results
+//!
+//! `check_source` and `check_rust` only type-check the generated Rust, for tests that
+//! make sure the generator's output compiles.
+
+use crate::build;
+use crate::codegen::source_map::SourceMap;
+use crate::codegen::CodeGenerator;
+use crate::lexer::Tokenizer;
+use crate::parser::{Parser, Program, Statement};
+use crate::semantic;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// The outcome of one test
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestResult {
+    /// The file the test came from
+    pub file: PathBuf,
+    /// The test block's name, or the file stem for a file run as a whole
+    pub name: String,
+    pub passed: bool,
+    pub duration: Duration,
+    /// Why the test failed
+    pub message: Option<String>,
+}
+
+/// Results of a test run, in the order the tests were run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TestReport {
+    pub results: Vec<TestResult>,
+}
+
+impl TestReport {
+    /// Returns the number of tests that passed
+    pub fn passed(&self) -> usize {
+        self.results.iter().filter(|result| result.passed).count()
+    }
+
+    /// Returns the number of tests that failed
+    pub fn failed(&self) -> usize {
+        self.results.iter().filter(|result| !result.passed).count()
+    }
+}
+
+/// Returns the paths searched when none are given: `tests` if it exists, else `.`
+pub fn default_paths() -> Vec<PathBuf> {
+    let tests = PathBuf::from("tests");
+    if tests.is_dir() {
+        vec![tests]
+    } else {
+        vec![PathBuf::from(".")]
+    }
+}
+
+/// Runs the tests in every `.grit` file under the given paths
+///
+/// Each file's `test` blocks run as separate tests. A file with no test blocks that
+/// lives in a `tests` directory, or is named explicitly, runs as a single test that
+/// passes if the program exits successfully; other files are skipped.
+pub fn run_tests(paths: &[PathBuf]) -> io::Result<TestReport> {
+    let mut report = TestReport::default();
+    let scratch = scratch_dir()?;
+
+    for path in paths {
+        let explicit = !path.is_dir();
+        for (source, _) in build::collect_sources(std::slice::from_ref(path))? {
+            let whole_file = explicit || in_tests_dir(&source);
+            report
+                .results
+                .extend(run_file(&source, whole_file, &scratch)?);
+        }
+    }
+
+    let _ = fs::remove_dir_all(&scratch);
+    Ok(report)
+}
+
+/// Checks that a Grit source parses, passes the semantic checks, and generates Rust
+/// that compiles
+///
+/// Returns the first problem found: a parse or semantic error, or rustc's first
+/// error, as `check_rust` reports it.
+pub fn check_source(source: &str) -> io::Result<Result<(), String>> {
+    let program = match parse_and_check(source) {
+        Ok(program) => program,
+        Err(message) => return Ok(Err(message)),
+    };
+    let (code, source_map) = CodeGenerator::generate_program_with_source_map(&program);
+    check_rust(&code, &source_map)
+}
+
+/// Checks that generated Rust code compiles, without building a binary
+///
+/// Runs `rustc --edition 2021 --emit=metadata`, which type-checks and borrow-checks
+/// the code but stops before code generation. The error names the Grit line the
+/// failing Rust came from, when the source map knows it.
+pub fn check_rust(code: &str, source_map: &SourceMap) -> io::Result<Result<(), String>> {
+    let scratch = scratch_dir()?;
+    let metadata = scratch.join("check.rmeta");
+    let checked = rustc(code, source_map, &scratch.join("check.rs"), |command| {
+        command.arg("--emit=metadata").arg("-o").arg(&metadata);
+    });
+    let _ = fs::remove_dir_all(&scratch);
+    checked
+}
+
+/// Creates an empty directory for the files of one run
+fn scratch_dir() -> io::Result<PathBuf> {
+    // Runs in the same process (such as parallel test threads) get separate directories
+    static RUN: AtomicUsize = AtomicUsize::new(0);
+    let run = RUN.fetch_add(1, Ordering::Relaxed);
+    let scratch = std::env::temp_dir().join(format!("grit_test_{}_{}", std::process::id(), run));
+    fs::create_dir_all(&scratch)?;
+    Ok(scratch)
+}
+
+/// Runs the tests of one file, compiling it in the scratch directory
+fn run_file(source: &Path, whole_file: bool, scratch: &Path) -> io::Result<Vec<TestResult>> {
+    let contents = fs::read_to_string(source)?;
+    let file_name = source
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let failure = |message: String| TestResult {
+        file: source.to_path_buf(),
+        name: file_name.clone(),
+        passed: false,
+        duration: Duration::ZERO,
+        message: Some(message),
+    };
+
+    let program = match parse_and_check(&contents) {
+        Ok(program) => program,
+        Err(message) => return Ok(vec![failure(message)]),
+    };
+
+    let names: Vec<&String> = program
+        .statements
+        .iter()
+        .filter_map(|stmt| match stmt {
+            Statement::Test { name, .. } => Some(name),
+            _ => None,
+        })
+        .collect();
+
+    let ((code, source_map), harness) = if !names.is_empty() {
+        (
+            CodeGenerator::generate_test_harness_with_source_map(&program),
+            true,
+        )
+    } else if whole_file {
+        (
+            CodeGenerator::generate_program_with_source_map(&program),
+            false,
+        )
+    } else {
+        return Ok(Vec::new());
+    };
+
+    let binary = match compile(&code, &source_map, &file_name, scratch)? {
+        Ok(binary) => binary,
+        Err(message) => return Ok(vec![failure(message)]),
+    };
+
+    let start = Instant::now();
+    let output = Command::new(&binary).output()?;
+    let elapsed = start.elapsed();
+    let _ = fs::remove_file(&binary);
+
+    if !harness {
+        let message = (!output.status.success()).then(|| {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            match stderr.lines().find(|line| !line.trim().is_empty()) {
+                Some(line) => line.trim().to_string(),
+                None => format!("program exited with {}", output.status),
+            }
+        });
+        return Ok(vec![TestResult {
+            file: source.to_path_buf(),
+            name: file_name,
+            passed: output.status.success(),
+            duration: elapsed,
+            message,
+        }]);
+    }
+
+    let mut results: Vec<TestResult> = names
+        .iter()
+        .map(|name| TestResult {
+            file: source.to_path_buf(),
+            name: name.to_string(),
+            passed: false,
+            duration: Duration::ZERO,
+            message: Some("test did not report a result".to_string()),
+        })
+        .collect();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for line in stdout.lines() {
+        let mut fields = line.splitn(5, '\t');
+        if fields.next() != Some("grit-test") {
+            continue;
+        }
+        let status = fields.next().unwrap_or("");
+        let micros = fields.next().and_then(|field| field.parse().ok());
+        let index = fields.next().and_then(|field| field.parse::<usize>().ok());
+        let message = fields.next().unwrap_or("");
+
+        if let (Some(micros), Some(result)) = (micros, index.and_then(|i| results.get_mut(i))) {
+            result.passed = status == "ok";
+            result.duration = Duration::from_micros(micros);
+            result.message = (!result.passed).then(|| message.to_string());
+        }
+    }
+
+    Ok(results)
+}
+
+/// Parses a source and runs the semantic checks, returning the first problem found
+fn parse_and_check(source: &str) -> Result<Program, String> {
+    let program = Parser::from_source(Tokenizer::new(source))
+        .parse()
+        .map_err(|err| format!("Parse error: {}", err))?;
+
+    let diagnostics = semantic::check_program(&program);
+    match diagnostics.iter().find(|diagnostic| diagnostic.is_error()) {
+        Some(diagnostic) => Err(diagnostic.to_string()),
+        None => Ok(program),
+    }
+}
+
+/// Compiles generated Rust code with `rustc`, returning the binary or rustc's first error
+fn compile(
+    code: &str,
+    source_map: &SourceMap,
+    name: &str,
+    scratch: &Path,
+) -> io::Result<Result<PathBuf, String>> {
+    let binary = scratch.join(name);
+    let compiled = rustc(
+        code,
+        source_map,
+        &scratch.join(format!("{}.rs", name)),
+        |command| {
+            command.arg("-o").arg(&binary);
+        },
+    )?;
+    Ok(compiled.map(|()| binary))
+}
+
+/// Writes generated Rust code to `source` and runs `rustc` on it, with the
+/// arguments `configure` adds, returning rustc's first error
+///
+/// The error names the Grit line the failing Rust came from, when the source map
+/// knows it. The `RUSTC` environment variable overrides which compiler is run.
+fn rustc(
+    code: &str,
+    source_map: &SourceMap,
+    source: &Path,
+    configure: impl FnOnce(&mut Command),
+) -> io::Result<Result<(), String>> {
+    fs::write(source, code)?;
+
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let mut command = Command::new(rustc);
+    command.args(["--edition", "2021", "-A", "warnings"]);
+    configure(&mut command);
+    let output = command.arg(source).output()?;
+    let _ = fs::remove_file(source);
+
+    if output.status.success() {
+        return Ok(Ok(()));
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let mut lines = stderr.lines().skip_while(|line| !line.starts_with("error"));
+    let message = lines
+        .next()
+        .unwrap_or("rustc failed to compile the generated code");
+    // rustc points at the failing code on the next line: ` --> name.rs:12:5`
+    let span = lines
+        .next()
+        .and_then(|line| line.trim().strip_prefix("--> "))
+        .and_then(|location| location.rsplit(':').nth(1)?.parse().ok())
+        .and_then(|line| source_map.lookup(line));
+    Ok(Err(match span {
+        Some(span) => format!(
+            "generated Rust failed to compile: {} (from line {})",
+            message, span.start_line
+        ),
+        None => format!("generated Rust failed to compile: {}", message),
+    }))
+}
+
+/// Checks if a file sits inside a directory named `tests`
+fn in_tests_dir(path: &Path) -> bool {
+    path.parent()
+        .is_some_and(|parent| parent.components().any(|part| part.as_os_str() == "tests"))
+}
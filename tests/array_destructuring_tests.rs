@@ -0,0 +1,100 @@
+mod common;
+
+use common::{generate, messages, parse, try_parse};
+use grit::diff::AstDiff;
+use grit::lexer::Span;
+use grit::parser::{Expr, Statement};
+use grit::semantic::{SymbolDump, SymbolTable, Type};
+
+#[test]
+fn test_parse_array_destructuring() {
+    let program = parse("[a, b, c] = values");
+
+    assert_eq!(
+        program.statements,
+        vec![Statement::ArrayDestructuring {
+            names: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            value: Expr::Identifier {
+                name: "values".into(),
+                span: Span::new(1, 13, 1, 18)
+            },
+            span: Span::new(1, 1, 1, 9),
+        }]
+    );
+    assert_eq!(program.statements[0].to_string(), "[a, b, c] = values");
+}
+
+#[test]
+fn test_array_literal_statement_is_not_destructuring() {
+    let program = parse("[a, b].len()\n[1, 2] == x\n[first, _] = [1, 2]");
+
+    assert!(matches!(program.statements[0], Statement::Expression(_)));
+    assert!(matches!(program.statements[1], Statement::Expression(_)));
+    assert_eq!(program.statements[2].to_string(), "[first, _] = [1, 2]");
+}
+
+#[test]
+fn test_missing_value_is_an_error() {
+    assert!(try_parse("[a, b] =").is_err());
+    assert!(try_parse("[a, b] = \nx").is_err());
+}
+
+#[test]
+fn test_check_array_destructuring() {
+    assert!(messages("values = [1, 2]\n[a, b] = values\nprint('%d', a + b)").is_empty());
+    assert_eq!(
+        messages("[a, b] = [1, 2, 3]"),
+        vec!["cannot destructure 3 values into 2 names"]
+    );
+    assert_eq!(
+        messages("n = 1\n[a, b] = n"),
+        vec!["cannot destructure int into 2 names"]
+    );
+    assert_eq!(
+        messages("const A = 1\n[A, b] = [1, 2]"),
+        vec!["cannot assign to constant 'A'"]
+    );
+}
+
+#[test]
+fn test_destructured_names_take_the_element_type() {
+    assert_eq!(
+        messages("[a, b] = ['x', 'y']\nprint('%d', a)"),
+        vec!["format specifier %d (argument 1) does not accept a value of type string"]
+    );
+}
+
+#[test]
+fn test_symbols_and_dump_record_destructured_names() {
+    let program = parse("[a, _, c] = [1.5, 2.5, 3.5]");
+
+    assert_eq!(SymbolTable::from_program(&program).globals(), &["a", "c"]);
+    let dump = SymbolDump::from_program(&program);
+    assert_eq!(dump.entries.len(), 2);
+    assert_eq!(dump.entries[1].ty, Some(Type::Float));
+}
+
+#[test]
+fn test_generate_array_literal_destructuring() {
+    let code = generate("[x, _] = [10, 20]\nprint('%d', x)");
+
+    assert!(code.contains("let [x, _]: [_; 2] = [10, 20];"));
+}
+
+#[test]
+fn test_generate_vector_destructuring() {
+    let code = generate("values = [1, 2, 3]\n[a, b, c] = values\nprint('%d', a + b + c)");
+
+    assert!(code.contains(
+        "let [a, b, c]: [_; 3] = std::convert::TryInto::try_into(values.clone())\
+         .unwrap_or_else(|_| panic!(\"expected 3 elements\"));"
+    ));
+}
+
+#[test]
+fn test_diff_ignores_destructuring_spans() {
+    let old = parse("[a, b] = v");
+    let new = parse("[a,   b] = v");
+
+    assert!(AstDiff::between(&old, &new).is_empty());
+}
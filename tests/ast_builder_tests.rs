@@ -0,0 +1,109 @@
+mod common;
+
+use common::parse;
+use grit::lexer::Span;
+use grit::parser::ast::build::*;
+use grit::parser::{BinaryOperator::*, Pattern, Statement, UnaryOperator::*};
+
+fn parse_statement(input: &str) -> Statement {
+    parse(input).statements.remove(0).without_spans()
+}
+
+#[test]
+fn test_built_expressions_match_parsed_ones() {
+    assert_eq!(
+        parse_statement("x = (1 + 2) * -y"),
+        assign(
+            "x",
+            binop(
+                grouped(binop(int(1), Add, int(2))),
+                Multiply,
+                unop(Negate, ident("y"))
+            )
+        )
+    );
+    assert_eq!(
+        parse_statement("r = f(a[0], xs[1..], g = 'h', nil, true, 2.5)"),
+        assign(
+            "r",
+            call(
+                "f",
+                vec![
+                    index(ident("a"), int(0)),
+                    slice(ident("xs"), Some(int(1)), None),
+                    named("g", string("h")),
+                    nil(),
+                    boolean(true),
+                    float(2.5),
+                ]
+            )
+        )
+    );
+    assert_eq!(
+        parse_statement("m = {'a': (1, [2])}"),
+        assign(
+            "m",
+            map(vec![(
+                string("a"),
+                tuple(vec![int(1), array(vec![int(2)])])
+            )])
+        )
+    );
+    assert_eq!(
+        parse_statement("p.norm(1).x = |v| v"),
+        field_assign(
+            method(ident("p"), "norm", vec![int(1)]),
+            "x",
+            lambda(&["v"], vec![expr_stmt(ident("v"))])
+        )
+    );
+}
+
+#[test]
+fn test_built_statements_match_parsed_ones() {
+    let source = "fn add(a, b) {\n  return a + b\n}\nclass P\nfn P > get() {\n  1\n}\n\
+                  var n = 0\nif n > 0 {\n  n = 1\n} else {\n  {\n    n = 2\n  }\n}\n\
+                  while n < 3 {\n  n = n + 1\n}\nmatch n {\n  1 => y = 1\n  _ => y = 2\n}";
+    let built = program(vec![
+        function(
+            "add",
+            &["a", "b"],
+            vec![ret(Some(binop(ident("a"), Add, ident("b"))))],
+        ),
+        class("P"),
+        method_def("P", "get", &[], vec![expr_stmt(int(1))]),
+        var("n", int(0)),
+        if_else(
+            binop(ident("n"), GreaterThan, int(0)),
+            vec![assign("n", int(1))],
+            Some(vec![block(vec![assign("n", int(2))])]),
+        ),
+        while_loop(
+            binop(ident("n"), LessThan, int(3)),
+            vec![assign("n", binop(ident("n"), Add, int(1)))],
+        ),
+        match_on(
+            ident("n"),
+            vec![
+                arm(vec![Pattern::Literal(int(1))], vec![assign("y", int(1))]),
+                arm(vec![Pattern::Wildcard], vec![assign("y", int(2))]),
+            ],
+        ),
+    ]);
+
+    let parsed: Vec<Statement> = parse(source)
+        .statements
+        .iter()
+        .map(Statement::without_spans)
+        .collect();
+    assert_eq!(parsed, built.statements);
+}
+
+#[test]
+fn test_built_nodes_have_default_spans() {
+    let stmt = assign("x", cond(vec![(binding("y", ident("z")), vec![])], vec![]));
+
+    assert_eq!(stmt.span(), Span::default());
+    assert_eq!(stmt, stmt.without_spans());
+    assert_eq!(call_value(ident("f"), vec![]).to_string(), "f()");
+}
@@ -1,24 +1,31 @@
 // Tests for src/parser/ast.rs
+use grit::lexer::Span;
 use grit::parser::{BinaryOperator, Expr, Program, Statement};
 
 // BinaryOperator tests
 
 #[test]
 fn test_binary_operator_precedence_arithmetic() {
-    assert_eq!(BinaryOperator::Add.precedence(), 1);
-    assert_eq!(BinaryOperator::Subtract.precedence(), 1);
-    assert_eq!(BinaryOperator::Multiply.precedence(), 2);
-    assert_eq!(BinaryOperator::Divide.precedence(), 2);
+    assert_eq!(BinaryOperator::Add.precedence(), 7);
+    assert_eq!(BinaryOperator::Subtract.precedence(), 7);
+    assert_eq!(BinaryOperator::Multiply.precedence(), 8);
+    assert_eq!(BinaryOperator::Divide.precedence(), 8);
 }
 
 #[test]
 fn test_binary_operator_precedence_comparison() {
-    assert_eq!(BinaryOperator::EqualEqual.precedence(), 0);
-    assert_eq!(BinaryOperator::NotEqual.precedence(), 0);
-    assert_eq!(BinaryOperator::LessThan.precedence(), 0);
-    assert_eq!(BinaryOperator::LessThanOrEqual.precedence(), 0);
-    assert_eq!(BinaryOperator::GreaterThan.precedence(), 0);
-    assert_eq!(BinaryOperator::GreaterThanOrEqual.precedence(), 0);
+    assert_eq!(BinaryOperator::EqualEqual.precedence(), 2);
+    assert_eq!(BinaryOperator::NotEqual.precedence(), 2);
+    assert_eq!(BinaryOperator::LessThan.precedence(), 2);
+    assert_eq!(BinaryOperator::LessThanOrEqual.precedence(), 2);
+    assert_eq!(BinaryOperator::GreaterThan.precedence(), 2);
+    assert_eq!(BinaryOperator::GreaterThanOrEqual.precedence(), 2);
+}
+
+#[test]
+fn test_binary_operator_precedence_logical() {
+    assert_eq!(BinaryOperator::Or.precedence(), 0);
+    assert_eq!(BinaryOperator::And.precedence(), 1);
 }
 
 #[test]
@@ -43,35 +50,81 @@ fn test_binary_operator_display_comparison() {
 
 #[test]
 fn test_expr_display_integer() {
-    let expr = Expr::Integer(42);
+    let expr = Expr::Integer {
+        value: 42,
+        span: Span::default(),
+    };
     assert_eq!(format!("{}", expr), "42");
 }
 
+#[test]
+fn test_expr_display_float() {
+    assert_eq!(
+        format!(
+            "{}",
+            Expr::Float {
+                value: 2.5,
+                span: Span::default()
+            }
+        ),
+        "2.5"
+    );
+    assert_eq!(
+        format!(
+            "{}",
+            Expr::Float {
+                value: 2.0,
+                span: Span::default()
+            }
+        ),
+        "2.0"
+    );
+}
+
 #[test]
 fn test_expr_display_string() {
-    let expr = Expr::String("hello".to_string());
+    let expr = Expr::String {
+        value: "hello".to_string(),
+        span: Span::default(),
+    };
     assert_eq!(format!("{}", expr), "'hello'");
 }
 
 #[test]
 fn test_expr_display_identifier() {
-    let expr = Expr::Identifier("x".to_string());
+    let expr = Expr::Identifier {
+        name: "x".into(),
+        span: Span::default(),
+    };
     assert_eq!(format!("{}", expr), "x");
 }
 
 #[test]
 fn test_expr_display_binary_op() {
     let expr = Expr::BinaryOp {
-        left: Box::new(Expr::Integer(1)),
+        left: Box::new(Expr::Integer {
+            value: 1,
+            span: Span::default(),
+        }),
         op: BinaryOperator::Add,
-        right: Box::new(Expr::Integer(2)),
+        right: Box::new(Expr::Integer {
+            value: 2,
+            span: Span::default(),
+        }),
+        span: Span::default(),
     };
     assert_eq!(format!("{}", expr), "(1 + 2)");
 }
 
 #[test]
 fn test_expr_display_grouped() {
-    let expr = Expr::Grouped(Box::new(Expr::Integer(42)));
+    let expr = Expr::Grouped {
+        inner: Box::new(Expr::Integer {
+            value: 42,
+            span: Span::default(),
+        }),
+        span: Span::default(),
+    };
     assert_eq!(format!("{}", expr), "(42)");
 }
 
@@ -80,6 +133,7 @@ fn test_expr_display_function_call_no_args() {
     let expr = Expr::FunctionCall {
         name: "foo".to_string(),
         args: vec![],
+        span: Span::default(),
     };
     assert_eq!(format!("{}", expr), "foo()");
 }
@@ -88,7 +142,21 @@ fn test_expr_display_function_call_no_args() {
 fn test_expr_display_function_call_with_args() {
     let expr = Expr::FunctionCall {
         name: "add".to_string(),
-        args: vec![Expr::Integer(1), Expr::Integer(2), Expr::Integer(3)],
+        args: vec![
+            Expr::Integer {
+                value: 1,
+                span: Span::default(),
+            },
+            Expr::Integer {
+                value: 2,
+                span: Span::default(),
+            },
+            Expr::Integer {
+                value: 3,
+                span: Span::default(),
+            },
+        ],
+        span: Span::default(),
     };
     assert_eq!(format!("{}", expr), "add(1, 2, 3)");
 }
@@ -96,8 +164,12 @@ fn test_expr_display_function_call_with_args() {
 #[test]
 fn test_expr_display_field_access() {
     let expr = Expr::FieldAccess {
-        object: Box::new(Expr::Identifier("obj".to_string())),
+        object: Box::new(Expr::Identifier {
+            name: "obj".into(),
+            span: Span::default(),
+        }),
         field: "field".to_string(),
+        span: Span::default(),
     };
     assert_eq!(format!("{}", expr), "obj.field");
 }
@@ -105,9 +177,13 @@ fn test_expr_display_field_access() {
 #[test]
 fn test_expr_display_method_call_no_args() {
     let expr = Expr::MethodCall {
-        object: Box::new(Expr::Identifier("obj".to_string())),
+        object: Box::new(Expr::Identifier {
+            name: "obj".into(),
+            span: Span::default(),
+        }),
         method: "method".to_string(),
         args: vec![],
+        span: Span::default(),
     };
     assert_eq!(format!("{}", expr), "obj.method()");
 }
@@ -115,9 +191,22 @@ fn test_expr_display_method_call_no_args() {
 #[test]
 fn test_expr_display_method_call_with_args() {
     let expr = Expr::MethodCall {
-        object: Box::new(Expr::Identifier("Point".to_string())),
+        object: Box::new(Expr::Identifier {
+            name: "Point".into(),
+            span: Span::default(),
+        }),
         method: "new".to_string(),
-        args: vec![Expr::Integer(3), Expr::Integer(4)],
+        args: vec![
+            Expr::Integer {
+                value: 3,
+                span: Span::default(),
+            },
+            Expr::Integer {
+                value: 4,
+                span: Span::default(),
+            },
+        ],
+        span: Span::default(),
     };
     assert_eq!(format!("{}", expr), "Point.new(3, 4)");
 }
@@ -126,12 +215,23 @@ fn test_expr_display_method_call_with_args() {
 fn test_expr_display_complex() {
     let expr = Expr::BinaryOp {
         left: Box::new(Expr::BinaryOp {
-            left: Box::new(Expr::Integer(1)),
+            left: Box::new(Expr::Integer {
+                value: 1,
+                span: Span::default(),
+            }),
             op: BinaryOperator::Add,
-            right: Box::new(Expr::Integer(2)),
+            right: Box::new(Expr::Integer {
+                value: 2,
+                span: Span::default(),
+            }),
+            span: Span::default(),
         }),
         op: BinaryOperator::Multiply,
-        right: Box::new(Expr::Integer(3)),
+        right: Box::new(Expr::Integer {
+            value: 3,
+            span: Span::default(),
+        }),
+        span: Span::default(),
     };
     assert_eq!(format!("{}", expr), "((1 + 2) * 3)");
 }
@@ -143,7 +243,12 @@ fn test_statement_display_function_def_no_params() {
     let stmt = Statement::FunctionDef {
         name: "main".to_string(),
         params: vec![],
+        param_types: vec![],
+        variadic: false,
+        return_type: None,
         body: vec![],
+        docs: Vec::new(),
+        span: Span::default(),
     };
     assert_eq!(format!("{}", stmt), "fn main()");
 }
@@ -153,7 +258,12 @@ fn test_statement_display_function_def_with_params() {
     let stmt = Statement::FunctionDef {
         name: "add".to_string(),
         params: vec!["x".to_string(), "y".to_string()],
+        param_types: vec![None; 2],
+        variadic: false,
+        return_type: None,
         body: vec![],
+        docs: Vec::new(),
+        span: Span::default(),
     };
     assert_eq!(format!("{}", stmt), "fn add(x, y)");
 }
@@ -162,6 +272,12 @@ fn test_statement_display_function_def_with_params() {
 fn test_statement_display_class_def() {
     let stmt = Statement::ClassDef {
         name: "Point".to_string(),
+        mixins: vec![],
+        fields: vec![],
+        parent: None,
+        traits: vec![],
+        docs: Vec::new(),
+        span: Span::default(),
     };
     assert_eq!(format!("{}", stmt), "class Point");
 }
@@ -171,8 +287,14 @@ fn test_statement_display_method_def_no_params() {
     let stmt = Statement::MethodDef {
         class_name: "Foo".to_string(),
         method_name: "new".to_string(),
+        is_static: false,
         params: vec![],
+        param_types: vec![],
+        variadic: false,
+        return_type: None,
         body: vec![],
+        docs: Vec::new(),
+        span: Span::default(),
     };
     assert_eq!(format!("{}", stmt), "fn Foo > new()");
 }
@@ -182,8 +304,14 @@ fn test_statement_display_method_def_with_params() {
     let stmt = Statement::MethodDef {
         class_name: "Point".to_string(),
         method_name: "new".to_string(),
+        is_static: false,
         params: vec!["x".to_string(), "y".to_string()],
+        param_types: vec![None; 2],
+        variadic: false,
+        return_type: None,
         body: vec![],
+        docs: Vec::new(),
+        span: Span::default(),
     };
     assert_eq!(format!("{}", stmt), "fn Point > new(x, y)");
 }
@@ -192,7 +320,13 @@ fn test_statement_display_method_def_with_params() {
 fn test_statement_display_assignment() {
     let stmt = Statement::Assignment {
         name: "x".to_string(),
-        value: Expr::Integer(42),
+        mutable: false,
+        ty: None,
+        value: Expr::Integer {
+            value: 42,
+            span: Span::default(),
+        },
+        span: Span::default(),
     };
     assert_eq!(format!("{}", stmt), "x = 42");
 }
@@ -200,10 +334,14 @@ fn test_statement_display_assignment() {
 #[test]
 fn test_statement_display_if_simple() {
     let stmt = Statement::If {
-        condition: Expr::Identifier("x".to_string()),
+        condition: Expr::Identifier {
+            name: "x".into(),
+            span: Span::default(),
+        },
         then_branch: vec![],
         elif_branches: vec![],
         else_branch: None,
+        span: Span::default(),
     };
     assert_eq!(format!("{}", stmt), "if x");
 }
@@ -211,10 +349,20 @@ fn test_statement_display_if_simple() {
 #[test]
 fn test_statement_display_if_with_elif() {
     let stmt = Statement::If {
-        condition: Expr::Identifier("x".to_string()),
+        condition: Expr::Identifier {
+            name: "x".into(),
+            span: Span::default(),
+        },
         then_branch: vec![],
-        elif_branches: vec![(Expr::Identifier("y".to_string()), vec![])],
+        elif_branches: vec![(
+            Expr::Identifier {
+                name: "y".into(),
+                span: Span::default(),
+            },
+            vec![],
+        )],
         else_branch: None,
+        span: Span::default(),
     };
     assert_eq!(format!("{}", stmt), "if x + 1 elif(s)");
 }
@@ -222,13 +370,29 @@ fn test_statement_display_if_with_elif() {
 #[test]
 fn test_statement_display_if_with_multiple_elif() {
     let stmt = Statement::If {
-        condition: Expr::Identifier("x".to_string()),
+        condition: Expr::Identifier {
+            name: "x".into(),
+            span: Span::default(),
+        },
         then_branch: vec![],
         elif_branches: vec![
-            (Expr::Identifier("y".to_string()), vec![]),
-            (Expr::Identifier("z".to_string()), vec![]),
+            (
+                Expr::Identifier {
+                    name: "y".into(),
+                    span: Span::default(),
+                },
+                vec![],
+            ),
+            (
+                Expr::Identifier {
+                    name: "z".into(),
+                    span: Span::default(),
+                },
+                vec![],
+            ),
         ],
         else_branch: None,
+        span: Span::default(),
     };
     assert_eq!(format!("{}", stmt), "if x + 2 elif(s)");
 }
@@ -236,10 +400,14 @@ fn test_statement_display_if_with_multiple_elif() {
 #[test]
 fn test_statement_display_if_with_else() {
     let stmt = Statement::If {
-        condition: Expr::Identifier("x".to_string()),
+        condition: Expr::Identifier {
+            name: "x".into(),
+            span: Span::default(),
+        },
         then_branch: vec![],
         elif_branches: vec![],
         else_branch: Some(vec![]),
+        span: Span::default(),
     };
     assert_eq!(format!("{}", stmt), "if x + else");
 }
@@ -247,10 +415,20 @@ fn test_statement_display_if_with_else() {
 #[test]
 fn test_statement_display_if_with_elif_and_else() {
     let stmt = Statement::If {
-        condition: Expr::Identifier("x".to_string()),
+        condition: Expr::Identifier {
+            name: "x".into(),
+            span: Span::default(),
+        },
         then_branch: vec![],
-        elif_branches: vec![(Expr::Identifier("y".to_string()), vec![])],
+        elif_branches: vec![(
+            Expr::Identifier {
+                name: "y".into(),
+                span: Span::default(),
+            },
+            vec![],
+        )],
         else_branch: Some(vec![]),
+        span: Span::default(),
     };
     assert_eq!(format!("{}", stmt), "if x + 1 elif(s) + else");
 }
@@ -259,18 +437,30 @@ fn test_statement_display_if_with_elif_and_else() {
 fn test_statement_display_while() {
     let stmt = Statement::While {
         condition: Expr::BinaryOp {
-            left: Box::new(Expr::Identifier("x".to_string())),
+            left: Box::new(Expr::Identifier {
+                name: "x".into(),
+                span: Span::default(),
+            }),
             op: BinaryOperator::LessThan,
-            right: Box::new(Expr::Integer(10)),
+            right: Box::new(Expr::Integer {
+                value: 10,
+                span: Span::default(),
+            }),
+            span: Span::default(),
         },
         body: vec![],
+        label: None,
+        span: Span::default(),
     };
     assert_eq!(format!("{}", stmt), "while (x < 10)");
 }
 
 #[test]
 fn test_statement_display_expression() {
-    let stmt = Statement::Expression(Expr::Integer(42));
+    let stmt = Statement::Expression(Expr::Integer {
+        value: 42,
+        span: Span::default(),
+    });
     assert_eq!(format!("{}", stmt), "42");
 }
 
@@ -287,7 +477,13 @@ fn test_program_display_single_statement() {
     let program = Program {
         statements: vec![Statement::Assignment {
             name: "x".to_string(),
-            value: Expr::Integer(42),
+            mutable: false,
+            ty: None,
+            value: Expr::Integer {
+                value: 42,
+                span: Span::default(),
+            },
+            span: Span::default(),
         }],
     };
     assert_eq!(format!("{}", program), "x = 42");
@@ -299,16 +495,35 @@ fn test_program_display_multiple_statements() {
         statements: vec![
             Statement::Assignment {
                 name: "x".to_string(),
-                value: Expr::Integer(1),
+                mutable: false,
+                ty: None,
+                value: Expr::Integer {
+                    value: 1,
+                    span: Span::default(),
+                },
+                span: Span::default(),
             },
             Statement::Assignment {
                 name: "y".to_string(),
-                value: Expr::Integer(2),
+                mutable: false,
+                ty: None,
+                value: Expr::Integer {
+                    value: 2,
+                    span: Span::default(),
+                },
+                span: Span::default(),
             },
             Statement::Expression(Expr::BinaryOp {
-                left: Box::new(Expr::Identifier("x".to_string())),
+                left: Box::new(Expr::Identifier {
+                    name: "x".into(),
+                    span: Span::default(),
+                }),
                 op: BinaryOperator::Add,
-                right: Box::new(Expr::Identifier("y".to_string())),
+                right: Box::new(Expr::Identifier {
+                    name: "y".into(),
+                    span: Span::default(),
+                }),
+                span: Span::default(),
             }),
         ],
     };
@@ -326,7 +541,10 @@ fn test_binary_operator_clone() {
 
 #[test]
 fn test_expr_clone() {
-    let expr = Expr::Integer(42);
+    let expr = Expr::Integer {
+        value: 42,
+        span: Span::default(),
+    };
     let cloned = expr.clone();
     assert_eq!(expr, cloned);
 }
@@ -335,7 +553,13 @@ fn test_expr_clone() {
 fn test_statement_clone() {
     let stmt = Statement::Assignment {
         name: "x".to_string(),
-        value: Expr::Integer(42),
+        mutable: false,
+        ty: None,
+        value: Expr::Integer {
+            value: 42,
+            span: Span::default(),
+        },
+        span: Span::default(),
     };
     let cloned = stmt.clone();
     assert_eq!(stmt, cloned);
@@ -344,7 +568,10 @@ fn test_statement_clone() {
 #[test]
 fn test_program_clone() {
     let program = Program {
-        statements: vec![Statement::Expression(Expr::Integer(42))],
+        statements: vec![Statement::Expression(Expr::Integer {
+            value: 42,
+            span: Span::default(),
+        })],
     };
     let cloned = program.clone();
     assert_eq!(program, cloned);
@@ -352,8 +579,14 @@ fn test_program_clone() {
 
 #[test]
 fn test_expr_partial_eq_different() {
-    let expr1 = Expr::Integer(1);
-    let expr2 = Expr::Integer(2);
+    let expr1 = Expr::Integer {
+        value: 1,
+        span: Span::default(),
+    };
+    let expr2 = Expr::Integer {
+        value: 2,
+        span: Span::default(),
+    };
     assert_ne!(expr1, expr2);
 }
 
@@ -361,11 +594,58 @@ fn test_expr_partial_eq_different() {
 fn test_statement_partial_eq_different() {
     let stmt1 = Statement::Assignment {
         name: "x".to_string(),
-        value: Expr::Integer(1),
+        mutable: false,
+        ty: None,
+        value: Expr::Integer {
+            value: 1,
+            span: Span::default(),
+        },
+        span: Span::default(),
     };
     let stmt2 = Statement::Assignment {
         name: "x".to_string(),
-        value: Expr::Integer(2),
+        mutable: false,
+        ty: None,
+        value: Expr::Integer {
+            value: 2,
+            span: Span::default(),
+        },
+        span: Span::default(),
     };
     assert_ne!(stmt1, stmt2);
 }
+
+// Alternate Display tests
+
+fn parse(input: &str) -> Program {
+    let tokens = grit::lexer::Tokenizer::new(input).tokenize().unwrap();
+    grit::parser::Parser::new(tokens).parse().unwrap()
+}
+
+#[test]
+fn test_alternate_display_renders_nested_bodies() {
+    let program = parse(
+        "fn f(a) {\n  if a > 1 {\n    print('big')\n  } elif a < 0 {\n    x = 1\n  } else {\n    \
+         outer: while a > 0 {\n      a = a - 1\n    }\n  }\n  return a\n}\ndo {\n  z = 1\n} while false",
+    );
+
+    assert_eq!(
+        format!("{:#}", program),
+        "fn f(a) {\n  if (a > 1) {\n    print('big')\n  } elif (a < 0) {\n    x = 1\n  } else {\n    \
+         outer: while (a > 0) {\n      a = (a - 1)\n    }\n  }\n  return a\n}\ndo {\n  z = 1\n} while false"
+    );
+    // The plain form still shows one line per statement
+    assert_eq!(program.to_string(), "fn f(a)\ndo ... while false");
+}
+
+#[test]
+fn test_alternate_display_renders_match_arms_and_blocks() {
+    let program =
+        parse("match n {\n  1, 2 => print('x')\n  _ => {\n    {\n      y = 2\n    }\n  }\n}");
+
+    assert_eq!(
+        format!("{:#}", program.statements[0]),
+        "match n {\n  1, 2 => {\n    print('x')\n  }\n  _ => {\n    {\n      y = 2\n    }\n  }\n}"
+    );
+    assert_eq!(format!("{:#}", parse("x = 1").statements[0]), "x = 1");
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use common::{errors, generate, parse, try_parse};
+use grit::lexer::Span;
+use grit::parser::{Expr, Statement};
+
+#[test]
+fn test_parse_binding_condition() {
+    let program = parse("if n = half(x) {\n  print('%d', n)\n}");
+
+    match &program.statements[0] {
+        Statement::If { condition, .. } => match condition {
+            Expr::Binding { name, value, span } => {
+                assert_eq!(name, "n");
+                assert_eq!(value.to_string(), "half(x)");
+                assert_eq!(*span, Span::new(1, 4, 1, 14));
+            }
+            other => panic!("expected a binding, got {:?}", other),
+        },
+        other => panic!("expected an if, got {:?}", other),
+    }
+    assert_eq!(program.statements[0].to_string(), "if n = half(x)");
+}
+
+#[test]
+fn test_parse_binding_in_elif_and_while() {
+    let program = try_parse(
+        "if a {\n  x = 1\n} elif b = a > 1 {\n  x = 2\n}\nwhile line = next() {\n  x = 3\n}",
+    )
+    .unwrap();
+
+    match &program.statements[0] {
+        Statement::If { elif_branches, .. } => {
+            assert!(matches!(elif_branches[0].0, Expr::Binding { .. }))
+        }
+        other => panic!("expected an if, got {:?}", other),
+    }
+    assert_eq!(program.statements[1].to_string(), "while line = next()");
+}
+
+#[test]
+fn test_binding_needs_a_value() {
+    assert!(try_parse("if n = {\n  x = 1\n}").is_err());
+    assert!(try_parse("do {\n  x = 1\n} while n = next()").is_err());
+    // A comparison is still a comparison
+    assert!(matches!(
+        &parse("if n == 1 {\n  x = 1\n}").statements[0],
+        Statement::If {
+            condition: Expr::BinaryOp { .. },
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_check_binding_scope() {
+    assert!(errors("if n = 1 + 2 {\n  print('%d', n)\n}").is_empty());
+    assert_eq!(
+        errors("if s = 'a' {\n  print('%d', s)\n}"),
+        vec!["format specifier %d (argument 1) does not accept a value of type string"]
+    );
+    assert_eq!(
+        errors("const N = 1\nif N = 2 {\n  x = 1\n}"),
+        vec!["cannot assign to constant 'N'"]
+    );
+}
+
+#[test]
+fn test_generate_binding_conditions() {
+    let code = generate(
+        "x = 5\nif big = x > 3 {\n  print('%s', big)\n} elif h = x / 2 {\n  print('%d', h)\n}\n\
+         if m = nil {\n  print('%d', m)\n}\nwhile ok = x > 9 {\n  print('%s', ok)\n}",
+    );
+
+    assert!(code.contains("if let big @ true = x > 3 {"));
+    assert!(code.contains("} else if let Some(h) = Some(x / 2) {"));
+    assert!(code.contains("if let Some(m) = None::<i64> {"));
+    assert!(code.contains("while let ok @ true = x > 9 {"));
+}
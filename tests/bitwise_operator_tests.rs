@@ -0,0 +1,93 @@
+mod common;
+
+use common::parse;
+use grit::codegen::CodeGenerator;
+use grit::lexer::{TokenType, Tokenizer};
+use grit::parser::{BinaryOperator, Expr, Statement};
+
+fn parse_expr(input: &str) -> Expr {
+    match parse(input).statements.remove(0) {
+        Statement::Expression(expr) => expr,
+        other => panic!("Expected expression, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_tokenize_bitwise_operators() {
+    let tokens = Tokenizer::new("& | ^ << >> && || <= >=")
+        .tokenize()
+        .unwrap();
+    let types: Vec<TokenType> = tokens.into_iter().map(|token| token.token_type).collect();
+    assert_eq!(
+        types,
+        vec![
+            TokenType::Ampersand,
+            TokenType::Pipe,
+            TokenType::Caret,
+            TokenType::ShiftLeft,
+            TokenType::ShiftRight,
+            TokenType::DoubleAmpersand,
+            TokenType::DoublePipe,
+            TokenType::LessThanOrEqual,
+            TokenType::GreaterThanOrEqual,
+            TokenType::Eof,
+        ]
+    );
+}
+
+#[test]
+fn test_bitwise_precedence_tiers() {
+    // | < ^ < & < shifts < additive, all tighter than comparisons
+    assert_eq!(
+        parse_expr("a | b ^ c & d << 1 + 2").to_string(),
+        "(a | (b ^ (c & (d << (1 + 2)))))"
+    );
+    assert_eq!(parse_expr("x & 4 == 4").to_string(), "((x & 4) == 4)");
+    assert_eq!(parse_expr("a >> 1 >> 2").to_string(), "((a >> 1) >> 2)");
+}
+
+#[test]
+fn test_bitwise_operators_are_left_associative() {
+    for op in [
+        BinaryOperator::BitAnd,
+        BinaryOperator::BitOr,
+        BinaryOperator::BitXor,
+        BinaryOperator::ShiftLeft,
+        BinaryOperator::ShiftRight,
+    ] {
+        assert!(op.is_bitwise());
+        assert!(op.precedence() > BinaryOperator::EqualEqual.precedence());
+        assert!(op.precedence() < BinaryOperator::Add.precedence());
+    }
+    assert!(!BinaryOperator::And.is_bitwise());
+}
+
+#[test]
+fn test_generate_bitwise_operators() {
+    let code = CodeGenerator::generate_program(&parse(
+        "flags = 6\nmask = 1 << 2 | 1\nlow = flags & mask ^ 2\nhalf = flags >> 1",
+    ));
+    assert!(code.contains("let mask = 1 << 2 | 1;"));
+    assert!(code.contains("let low = flags & mask ^ 2;"));
+    assert!(code.contains("let half = flags >> 1;"));
+}
+
+#[test]
+fn test_generate_bitwise_keeps_needed_parens() {
+    assert_eq!(
+        CodeGenerator::generate_expression(&parse_expr("(a | b) & c")),
+        "(a | b) & c"
+    );
+    assert_eq!(
+        CodeGenerator::generate_expression(&parse_expr("a << (b + 1)")),
+        "a << (b + 1)"
+    );
+}
+
+#[test]
+fn test_bitwise_types() {
+    let code =
+        CodeGenerator::generate_program(&parse("a = typeof(6 & 3)\nb = typeof(true | false)"));
+    assert!(code.contains("let a = \"int\";"));
+    assert!(code.contains("let b = \"bool\";"));
+}
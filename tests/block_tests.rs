@@ -0,0 +1,84 @@
+mod common;
+
+use common::{generate, messages, parse, try_parse};
+use grit::lexer::Span;
+use grit::parser::{Expr, Statement};
+
+#[test]
+fn test_parse_block() {
+    let program = parse("{\n  x = 1\n  print('%d', x)\n}\ny = 2");
+
+    assert_eq!(program.statements.len(), 2);
+    match &program.statements[0] {
+        Statement::Block { body, .. } => {
+            assert_eq!(body.len(), 2);
+            assert!(matches!(body[0], Statement::Assignment { .. }));
+        }
+        other => panic!("expected a block, got {:?}", other),
+    }
+    assert_eq!(program.statements[0].to_string(), "{ ... }");
+}
+
+#[test]
+fn test_parse_empty_and_nested_blocks() {
+    let program = parse("{}\n{\n  {\n    x = 1\n  }\n}");
+
+    assert_eq!(
+        program.statements[0],
+        Statement::Block {
+            body: vec![],
+            span: Span::new(1, 1, 1, 1)
+        }
+    );
+    match &program.statements[1] {
+        Statement::Block { body, .. } => assert!(matches!(body[0], Statement::Block { .. })),
+        other => panic!("expected a block, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_map_literal_still_parses_in_expressions() {
+    let program = parse("m = {'a': 1}");
+
+    assert!(matches!(
+        program.statements[0],
+        Statement::Assignment {
+            value: Expr::Map { .. },
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_unclosed_block_is_error() {
+    assert!(try_parse("{\n  x = 1\n").is_err());
+}
+
+#[test]
+fn test_generate_block() {
+    let code = generate("x = 1\n{\n  x = 2\n  y = 3\n  print('%d', x + y)\n}\nprint('%d', x)");
+
+    // The block updates the outer `x` and declares its own `y`
+    assert!(code.contains(
+        "let mut x = 1;\n    {\n        x = 2;\n        let y = 3;\n        println!(\"{}\", x + y);\n    }\n"
+    ));
+}
+
+#[test]
+fn test_generate_block_in_function() {
+    let code = generate("fn f() {\n  {\n    y = 1\n  }\n  2\n}");
+
+    assert!(code.contains("let y = 1;"));
+    assert!(code.contains("    }\n    2\n}"));
+}
+
+#[test]
+fn test_block_variables_are_scoped() {
+    // A variable the block declares ends with it
+    assert!(messages("{\n  z = 'a'\n}\nz = 1\nprint('%d', z)").is_empty());
+    // Assigning the outer `y` keeps its type
+    assert_eq!(
+        messages("var y = 1\n{\n  y = 'a'\n}\nprint('%d', y)"),
+        vec!["'y' is declared int but assigned string"]
+    );
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use common::parse;
+use grit::codegen::CodeGenerator;
+use grit::lexer::{Span, TokenType, Tokenizer};
+use grit::parser::{Expr, Statement};
+use grit::semantic::check_program;
+
+#[test]
+fn test_tokenize_bool_keywords() {
+    let tokens = Tokenizer::new("true false truth").tokenize().unwrap();
+    assert_eq!(tokens[0].token_type, TokenType::Bool(true));
+    assert_eq!(tokens[1].token_type, TokenType::Bool(false));
+    assert_eq!(tokens[2].token_type, TokenType::Identifier("truth".into()));
+}
+
+#[test]
+fn test_parse_bool_literal() {
+    let program = parse("flag = true");
+    match &program.statements[0] {
+        Statement::Assignment { value, .. } => assert_eq!(
+            value,
+            &Expr::Bool {
+                value: true,
+                span: Span::new(1, 8, 1, 11)
+            }
+        ),
+        other => panic!("Expected assignment, got {:?}", other),
+    }
+    assert_eq!(program.to_string(), "flag = true");
+}
+
+#[test]
+fn test_bool_in_conditions() {
+    let code = CodeGenerator::generate_program(&parse(
+        "running = true\nwhile running {\n  running = false\n}\nif !false and true {\n  print('%s', running)\n}",
+    ));
+    assert!(code.contains("let mut running = true;"));
+    assert!(code.contains("while running {\n        running = false;"));
+    assert!(code.contains("if !false && true {"));
+}
+
+#[test]
+fn test_bool_generates_rust_bool() {
+    assert_eq!(
+        CodeGenerator::generate_expression(&Expr::Bool {
+            value: false,
+            span: Span::default()
+        }),
+        "false"
+    );
+}
+
+#[test]
+fn test_bool_type_is_inferred() {
+    let code = CodeGenerator::generate_program(&parse("t = typeof(false)"));
+    assert!(code.contains("let t = \"bool\";"));
+
+    assert!(check_program(&parse("print('%s', true)")).is_empty());
+    let diagnostics = check_program(&parse("print('%d', true)"));
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("type bool"));
+}
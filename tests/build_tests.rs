@@ -0,0 +1,292 @@
+use grit::build::{build, compile_source, BuildCache, BuildOptions, CompiledFile};
+use grit::optimize::Pipeline;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Creates an empty scratch directory for one test
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("grit_build_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn options(dir: &Path) -> BuildOptions {
+    BuildOptions {
+        use_cache: true,
+        cache_dir: dir.join("cache"),
+        out_dir: dir.join("out"),
+        optimize: None,
+    }
+}
+
+#[test]
+fn test_compile_source_success() {
+    let compiled = compile_source("x = 1\nprint('%d', x)");
+    assert!(compiled.succeeded());
+    assert!(compiled.generated_code.unwrap().contains("let x = 1;"));
+    assert!(compiled.messages.is_empty());
+}
+
+#[test]
+fn test_compile_source_errors() {
+    let compiled = compile_source("print('%d')");
+    assert!(!compiled.succeeded());
+    assert_eq!(compiled.messages.len(), 1);
+
+    let compiled = compile_source("fn (");
+    assert!(!compiled.succeeded());
+    assert!(compiled.messages[0].starts_with("Parse error:"));
+}
+
+#[test]
+fn test_cache_key_depends_on_content() {
+    assert_eq!(BuildCache::key("x = 1"), BuildCache::key("x = 1"));
+    assert_ne!(BuildCache::key("x = 1"), BuildCache::key("x = 2"));
+    assert_eq!(BuildCache::key("").len(), 16);
+}
+
+#[test]
+fn test_cache_round_trip() {
+    let dir = scratch_dir("round_trip");
+    let cache = BuildCache::new(dir.join("cache"));
+
+    let ok = compile_source("x = 1");
+    cache.store("ok", &ok).unwrap();
+    assert_eq!(cache.load("ok"), Some(ok));
+
+    let failed = CompiledFile {
+        generated_code: None,
+        messages: vec!["Error: one".to_string(), "Error: two".to_string()],
+    };
+    cache.store("failed", &failed).unwrap();
+    assert_eq!(cache.load("failed"), Some(failed));
+
+    assert_eq!(cache.load("missing"), None);
+    fs::write(dir.join("cache").join("corrupt.cache"), "garbage").unwrap();
+    assert_eq!(cache.load("corrupt"), None);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_build_only_recompiles_changed_files() {
+    let dir = scratch_dir("incremental");
+    let src = dir.join("src");
+    fs::create_dir_all(src.join("nested")).unwrap();
+    fs::write(src.join("a.grit"), "x = 1").unwrap();
+    fs::write(src.join("nested").join("b.grit"), "y = 2").unwrap();
+    fs::write(src.join("notes.txt"), "not grit").unwrap();
+    let options = options(&dir);
+
+    let first = build(std::slice::from_ref(&src), &options).unwrap();
+    assert_eq!(first.files.len(), 2);
+    assert_eq!((first.compiled(), first.cached()), (2, 0));
+    assert!(dir.join("out").join("a.rs").exists());
+    assert!(dir.join("out").join("nested").join("b.rs").exists());
+
+    let second = build(std::slice::from_ref(&src), &options).unwrap();
+    assert_eq!((second.compiled(), second.cached()), (0, 2));
+
+    fs::write(src.join("a.grit"), "x = 3").unwrap();
+    let third = build(std::slice::from_ref(&src), &options).unwrap();
+    assert_eq!((third.compiled(), third.cached()), (1, 1));
+    assert!(!third.files[0].cached);
+    let generated = fs::read_to_string(dir.join("out").join("a.rs")).unwrap();
+    assert!(generated.contains("let x = 3;"));
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_build_with_optimize_recompiles_and_drops_dead_code() {
+    let dir = scratch_dir("optimize");
+    let file = dir.join("main.grit");
+    fs::write(
+        &file,
+        "fn debug() {\n  print('debug')\n}\nif false {\n  debug()\n}\nprint('done')",
+    )
+    .unwrap();
+    let plain = options(&dir);
+    let optimized = BuildOptions {
+        optimize: Some(Pipeline::default()),
+        ..options(&dir)
+    };
+
+    build(std::slice::from_ref(&file), &plain).unwrap();
+    assert!(fs::read_to_string(dir.join("out").join("main.rs"))
+        .unwrap()
+        .contains("fn debug()"));
+
+    // Other passes make another cache entry
+    let report = build(std::slice::from_ref(&file), &optimized).unwrap();
+    assert_eq!((report.compiled(), report.cached()), (1, 0));
+    let generated = fs::read_to_string(dir.join("out").join("main.rs")).unwrap();
+    assert!(!generated.contains("debug"));
+
+    let report = build(std::slice::from_ref(&file), &optimized).unwrap();
+    assert_eq!((report.compiled(), report.cached()), (0, 1));
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_build_without_cache() {
+    let dir = scratch_dir("no_cache");
+    let file = dir.join("main.grit");
+    fs::write(&file, "x = 1").unwrap();
+    let options = BuildOptions {
+        use_cache: false,
+        ..options(&dir)
+    };
+
+    build(std::slice::from_ref(&file), &options).unwrap();
+    let report = build(std::slice::from_ref(&file), &options).unwrap();
+
+    assert_eq!(report.cached(), 0);
+    assert!(!dir.join("cache").exists());
+    assert!(dir.join("out").join("main.rs").exists());
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_build_reports_failures_from_cache() {
+    let dir = scratch_dir("failures");
+    let file = dir.join("bad.grit");
+    fs::write(&file, "print('%d %d', 1)").unwrap();
+    let options = options(&dir);
+
+    let first = build(std::slice::from_ref(&file), &options).unwrap();
+    let second = build(std::slice::from_ref(&file), &options).unwrap();
+
+    assert_eq!(first.failed(), 1);
+    assert_eq!(second.failed(), 1);
+    assert!(second.files[0].cached);
+    assert_eq!(first.files[0].messages, second.files[0].messages);
+    assert!(!dir.join("out").join("bad.rs").exists());
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_run_build_command() {
+    let dir = scratch_dir("run");
+    fs::write(dir.join("main.grit"), "x = 1").unwrap();
+    let arg = |s: &str| s.to_string();
+    let args = vec![
+        arg("grit"),
+        arg("build"),
+        arg("--cache-dir"),
+        dir.join("cache").display().to_string(),
+        arg("--out-dir"),
+        dir.join("out").display().to_string(),
+        dir.join("main.grit").display().to_string(),
+    ];
+
+    let mut output = Vec::new();
+    assert!(grit::run(&args, &mut output).is_ok());
+    let output_str = String::from_utf8(output).unwrap();
+    assert!(output_str.contains("compiled"));
+    assert!(output_str.contains("Built 1 file(s): 1 compiled, 0 cached, 0 failed"));
+
+    let mut output = Vec::new();
+    assert!(grit::run(&args, &mut output).is_ok());
+    let output_str = String::from_utf8(output).unwrap();
+    assert!(output_str.contains("Built 1 file(s): 0 compiled, 1 cached, 0 failed"));
+
+    let mut no_cache = args.clone();
+    no_cache.insert(2, arg("--no-cache"));
+    let mut output = Vec::new();
+    assert!(grit::run(&no_cache, &mut output).is_ok());
+    let output_str = String::from_utf8(output).unwrap();
+    assert!(output_str.contains("1 compiled, 0 cached"));
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_run_build_optimize_option() {
+    let dir = scratch_dir("run_optimize");
+    fs::write(
+        dir.join("main.grit"),
+        "fn unused() {\n  1\n}\nprint('done')",
+    )
+    .unwrap();
+    let args = |optimize: &str| {
+        vec![
+            "grit".to_string(),
+            "build".to_string(),
+            "--no-cache".to_string(),
+            optimize.to_string(),
+            "--out-dir".to_string(),
+            dir.join("out").display().to_string(),
+            dir.join("main.grit").display().to_string(),
+        ]
+    };
+
+    for optimize in ["--optimize", "--optimize=unused-functions"] {
+        let mut output = Vec::new();
+        assert!(grit::run(&args(optimize), &mut output).is_ok());
+        let generated = fs::read_to_string(dir.join("out").join("main.rs")).unwrap();
+        assert!(!generated.contains("fn unused"), "{}", optimize);
+    }
+
+    let mut output = Vec::new();
+    assert_eq!(
+        grit::run(&args("--optimize=unreachable-code,inline"), &mut output),
+        Err(1)
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_run_build_rejects_unknown_option() {
+    let args = vec![
+        "grit".to_string(),
+        "build".to_string(),
+        "--fast".to_string(),
+    ];
+    let mut output = Vec::new();
+    assert_eq!(grit::run(&args, &mut output), Err(1));
+}
+
+#[test]
+fn test_run_build_fails_on_errors() {
+    let dir = scratch_dir("run_errors");
+    fs::write(dir.join("bad.grit"), "print('%d')").unwrap();
+    let args = vec![
+        "grit".to_string(),
+        "build".to_string(),
+        "--no-cache".to_string(),
+        "--out-dir".to_string(),
+        dir.join("out").display().to_string(),
+        dir.display().to_string(),
+    ];
+
+    let mut output = Vec::new();
+    assert_eq!(grit::run(&args, &mut output), Err(1));
+    assert!(String::from_utf8(output).unwrap().contains("1 failed"));
+
+    // A failed file counts as failed only, whether compiled or loaded from the cache
+    fs::write(dir.join("good.grit"), "x = 1").unwrap();
+    let mut cached = args.clone();
+    cached.splice(
+        2..3,
+        [
+            "--cache-dir".to_string(),
+            dir.join("cache").display().to_string(),
+        ],
+    );
+    for summary in [
+        "Built 2 file(s): 1 compiled, 0 cached, 1 failed",
+        "Built 2 file(s): 0 compiled, 1 cached, 1 failed",
+    ] {
+        let mut output = Vec::new();
+        assert_eq!(grit::run(&cached, &mut output), Err(1));
+        assert!(String::from_utf8(output).unwrap().contains(summary));
+    }
+
+    let _ = fs::remove_dir_all(&dir);
+}
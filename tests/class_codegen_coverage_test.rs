@@ -1,6 +1,7 @@
 // Integration test specifically designed to hit uncovered class codegen paths
 
 use grit::codegen::CodeGenerator;
+use grit::lexer::Span;
 use grit::parser::{Program, Statement};
 
 #[test]
@@ -10,31 +11,68 @@ fn test_class_with_multiple_fields() {
         statements: vec![
             Statement::ClassDef {
                 name: "Point".to_string(),
+                mixins: vec![],
+                fields: vec![],
+                parent: None,
+                traits: vec![],
+                docs: Vec::new(),
+                span: Span::default(),
             },
             Statement::MethodDef {
                 class_name: "Point".to_string(),
                 method_name: "new".to_string(),
+                is_static: false,
                 params: vec!["x".to_string(), "y".to_string()],
+                param_types: vec![None; 2],
+                variadic: false,
+                return_type: None,
                 body: vec![
                     Statement::Assignment {
                         name: "self.x".to_string(),
-                        value: grit::parser::Expr::Identifier("x".to_string()),
+                        mutable: false,
+                        ty: None,
+                        value: grit::parser::Expr::Identifier {
+                            name: "x".into(),
+                            span: Span::default(),
+                        },
+                        span: Span::default(),
                     },
                     Statement::Assignment {
                         name: "self.y".to_string(),
-                        value: grit::parser::Expr::Identifier("y".to_string()),
+                        mutable: false,
+                        ty: None,
+                        value: grit::parser::Expr::Identifier {
+                            name: "y".into(),
+                            span: Span::default(),
+                        },
+                        span: Span::default(),
                     },
                 ],
+                docs: Vec::new(),
+                span: Span::default(),
             },
             Statement::MethodDef {
                 class_name: "Point".to_string(),
                 method_name: "sum".to_string(),
+                is_static: false,
                 params: vec![],
+                param_types: vec![],
+                variadic: false,
+                return_type: None,
                 body: vec![Statement::Expression(grit::parser::Expr::BinaryOp {
-                    left: Box::new(grit::parser::Expr::Identifier("x".to_string())),
+                    left: Box::new(grit::parser::Expr::Identifier {
+                        name: "x".into(),
+                        span: Span::default(),
+                    }),
                     op: grit::parser::BinaryOperator::Add,
-                    right: Box::new(grit::parser::Expr::Identifier("y".to_string())),
+                    right: Box::new(grit::parser::Expr::Identifier {
+                        name: "y".into(),
+                        span: Span::default(),
+                    }),
+                    span: Span::default(),
                 })],
+                docs: Vec::new(),
+                span: Span::default(),
             },
         ],
     };
@@ -66,12 +104,27 @@ fn test_class_with_no_fields() {
         statements: vec![
             Statement::ClassDef {
                 name: "Helper".to_string(),
+                mixins: vec![],
+                fields: vec![],
+                parent: None,
+                traits: vec![],
+                docs: Vec::new(),
+                span: Span::default(),
             },
             Statement::MethodDef {
                 class_name: "Helper".to_string(),
                 method_name: "constant".to_string(),
+                is_static: false,
                 params: vec![],
-                body: vec![Statement::Expression(grit::parser::Expr::Integer(42))],
+                param_types: vec![],
+                variadic: false,
+                return_type: None,
+                body: vec![Statement::Expression(grit::parser::Expr::Integer {
+                    value: 42,
+                    span: Span::default(),
+                })],
+                docs: Vec::new(),
+                span: Span::default(),
             },
         ],
     };
@@ -91,25 +144,51 @@ fn test_multiple_classes() {
         statements: vec![
             Statement::ClassDef {
                 name: "Foo".to_string(),
+                mixins: vec![],
+                fields: vec![],
+                parent: None,
+                traits: vec![],
+                docs: Vec::new(),
+                span: Span::default(),
             },
             Statement::MethodDef {
                 class_name: "Foo".to_string(),
                 method_name: "get_a".to_string(),
+                is_static: false,
                 params: vec![],
-                body: vec![Statement::Expression(grit::parser::Expr::Identifier(
-                    "a".to_string(),
-                ))],
+                param_types: vec![],
+                variadic: false,
+                return_type: None,
+                body: vec![Statement::Expression(grit::parser::Expr::Identifier {
+                    name: "a".into(),
+                    span: Span::default(),
+                })],
+                docs: Vec::new(),
+                span: Span::default(),
             },
             Statement::ClassDef {
                 name: "Bar".to_string(),
+                mixins: vec![],
+                fields: vec![],
+                parent: None,
+                traits: vec![],
+                docs: Vec::new(),
+                span: Span::default(),
             },
             Statement::MethodDef {
                 class_name: "Bar".to_string(),
                 method_name: "get_b".to_string(),
+                is_static: false,
                 params: vec![],
-                body: vec![Statement::Expression(grit::parser::Expr::Identifier(
-                    "b".to_string(),
-                ))],
+                param_types: vec![],
+                variadic: false,
+                return_type: None,
+                body: vec![Statement::Expression(grit::parser::Expr::Identifier {
+                    name: "b".into(),
+                    span: Span::default(),
+                })],
+                docs: Vec::new(),
+                span: Span::default(),
             },
         ],
     };
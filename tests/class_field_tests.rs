@@ -0,0 +1,177 @@
+mod common;
+
+use common::{generate, messages, parse, try_parse};
+use grit::diff::AstDiff;
+use grit::lexer::Span;
+use grit::parser::{FieldDecl, ParseError, Statement, TypeAnnotation};
+use grit::semantic::{SymbolDump, SymbolKind, SymbolTable, Type};
+
+const POINT: &str = "class Point { x: float, y: float, label }\n\
+                     fn Point > new(x: float, y: float) {\n  self.x = x\n  self.y = y\n}\n";
+
+#[test]
+fn test_parse_inline_field_declarations() {
+    let program = parse("class Point { x, y: float }");
+
+    match &program.statements[0] {
+        Statement::ClassDef { fields, span, .. } => {
+            assert_eq!(
+                fields,
+                &[
+                    FieldDecl {
+                        name: "x".to_string(),
+                        ty: None,
+                        span: Span::new(1, 15, 1, 15),
+                    },
+                    FieldDecl {
+                        name: "y".to_string(),
+                        ty: Some(TypeAnnotation::Float),
+                        span: Span::new(1, 18, 1, 18),
+                    },
+                ]
+            );
+            // The header span stops before the body
+            assert_eq!(*span, Span::new(1, 1, 1, 11));
+        }
+        other => panic!("expected a class, got {:?}", other),
+    }
+    assert_eq!(
+        program.statements[0].to_string(),
+        "class Point { x, y: float }"
+    );
+}
+
+#[test]
+fn test_parse_fields_on_separate_lines() {
+    let program =
+        try_parse("mixin Named\nclass Point with Named {\n  x: int\n  y: int,\n  z\n}\np = 1")
+            .unwrap();
+
+    assert_eq!(program.statements.len(), 3);
+    assert_eq!(
+        program.statements[1].to_string(),
+        "class Point with Named { x: int, y: int, z }"
+    );
+}
+
+#[test]
+fn test_class_without_body_has_no_fields() {
+    let program = parse("class Point\n{\n  x = 1\n}");
+
+    assert!(matches!(
+        &program.statements[0],
+        Statement::ClassDef { fields, .. } if fields.is_empty()
+    ));
+    assert!(matches!(program.statements[1], Statement::Block { .. }));
+}
+
+#[test]
+fn test_malformed_field_declarations_are_errors() {
+    assert!(try_parse("class Point { x y }").is_err());
+    assert!(try_parse("class Point { x: number }").is_err());
+    assert!(try_parse("class Point { 1 }").is_err());
+    assert!(matches!(
+        try_parse("class Point { x,"),
+        Err(ParseError::UnexpectedEof { .. })
+    ));
+}
+
+#[test]
+fn test_symbols_record_declared_fields() {
+    let symbols = SymbolTable::from_program(&parse(POINT));
+    let class = symbols.class("Point").unwrap();
+
+    let fields: Vec<(&str, &Type)> = class
+        .fields
+        .iter()
+        .map(|field| (field.name.as_str(), &field.ty))
+        .collect();
+    assert_eq!(
+        fields,
+        vec![
+            ("x", &Type::Float),
+            ("y", &Type::Float),
+            ("label", &Type::Unknown)
+        ]
+    );
+    assert!(class.field("z").is_none());
+}
+
+#[test]
+fn test_generate_struct_from_declared_fields() {
+    let code = generate(POINT);
+
+    assert!(code.contains("struct Point {\n    x: f64,\n    y: f64,\n    label: i64,\n}"));
+    // Fields the constructor leaves unset start at their default
+    assert!(code.contains("            label: Default::default(),\n"));
+}
+
+#[test]
+fn test_declared_fields_replace_scraped_ones() {
+    let code = generate(
+        "class Counter { count }\nfn Counter > new() {\n  self.count = 0\n}\n\
+         fn Counter > get() {\n  count\n}",
+    );
+
+    assert!(code.contains("struct Counter {\n    count: i64,\n}"));
+}
+
+#[test]
+fn test_check_undeclared_field() {
+    assert_eq!(
+        messages(&format!("{}fn Point > move() {{\n  self.z = 1\n}}", POINT)),
+        vec!["class 'Point' declares no field 'z'"]
+    );
+    // Without a declaration any field may be assigned
+    assert!(messages("fn Point > new() {\n  self.z = 1\n}").is_empty());
+}
+
+#[test]
+fn test_check_field_type() {
+    assert_eq!(
+        messages(&format!(
+            "{}fn Point > rename() {{\n  self.x = 'a'\n  self.label = 'b'\n}}",
+            POINT
+        )),
+        vec!["'self.x' is declared float but assigned string"]
+    );
+}
+
+#[test]
+fn test_check_duplicate_field() {
+    assert_eq!(
+        messages("class Point { x, y, x }"),
+        vec!["field 'x' is declared more than once in class 'Point'"]
+    );
+}
+
+#[test]
+fn test_dump_lists_declared_fields() {
+    let dump = SymbolDump::from_program(&parse(POINT));
+    let fields: Vec<(String, String)> = dump
+        .entries
+        .iter()
+        .filter(|entry| entry.kind == SymbolKind::Field)
+        .map(|entry| (entry.display_name(), entry.ty.as_ref().unwrap().to_string()))
+        .collect();
+
+    assert_eq!(
+        fields,
+        vec![
+            ("Point.x".to_string(), "float".to_string()),
+            ("Point.y".to_string(), "float".to_string()),
+            ("Point.label".to_string(), "unknown".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_diff_reports_field_changes() {
+    let old = parse("class Point { x }");
+    let new = parse("class Point { x, y: int }");
+
+    assert_eq!(
+        AstDiff::between(&old, &new).changes[0].details,
+        vec!["fields: x -> x, y: int"]
+    );
+}
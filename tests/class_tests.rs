@@ -1,34 +1,34 @@
 use grit::codegen::CodeGenerator;
-use grit::lexer::Tokenizer;
+use grit::lexer::{Span, Tokenizer};
 use grit::parser::{Expr, Parser, Statement};
 
 #[test]
 fn test_tokenize_class() {
     let mut tokenizer = Tokenizer::new("class Foo");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     assert_eq!(tokens[0].token_type, grit::lexer::TokenType::Class);
     assert_eq!(
         tokens[1].token_type,
-        grit::lexer::TokenType::Identifier("Foo".to_string())
+        grit::lexer::TokenType::Identifier("Foo".into())
     );
 }
 
 #[test]
 fn test_tokenize_self() {
     let mut tokenizer = Tokenizer::new("self");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     assert_eq!(tokens[0].token_type, grit::lexer::TokenType::Self_);
 }
 
 #[test]
 fn test_tokenize_dot() {
     let mut tokenizer = Tokenizer::new("self.field");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     assert_eq!(tokens[0].token_type, grit::lexer::TokenType::Self_);
     assert_eq!(tokens[1].token_type, grit::lexer::TokenType::Dot);
     assert_eq!(
         tokens[2].token_type,
-        grit::lexer::TokenType::Identifier("field".to_string())
+        grit::lexer::TokenType::Identifier("field".into())
     );
 }
 
@@ -36,13 +36,13 @@ fn test_tokenize_dot() {
 fn test_parse_class_def() {
     let input = "class Foo";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
     assert_eq!(program.statements.len(), 1);
     match &program.statements[0] {
-        Statement::ClassDef { name } => {
+        Statement::ClassDef { name, .. } => {
             assert_eq!(name, "Foo");
         }
         _ => panic!("Expected ClassDef"),
@@ -53,7 +53,7 @@ fn test_parse_class_def() {
 fn test_parse_method_def() {
     let input = "fn Foo > new { self.a = 1 }";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
@@ -64,6 +64,7 @@ fn test_parse_method_def() {
             method_name,
             params,
             body,
+            ..
         } => {
             assert_eq!(class_name, "Foo");
             assert_eq!(method_name, "new");
@@ -78,7 +79,7 @@ fn test_parse_method_def() {
 fn test_parse_method_with_params() {
     let input = "fn Bar > new(x, y) { self.x = x }";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
@@ -104,15 +105,21 @@ fn test_parse_method_with_params() {
 fn test_parse_self_field_assignment() {
     let input = "self.a = 1";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
     assert_eq!(program.statements.len(), 1);
     match &program.statements[0] {
-        Statement::Assignment { name, value } => {
+        Statement::Assignment { name, value, .. } => {
             assert_eq!(name, "self.a");
-            assert_eq!(*value, Expr::Integer(1));
+            assert_eq!(
+                *value,
+                Expr::Integer {
+                    value: 1,
+                    span: Span::new(1, 10, 1, 10)
+                }
+            );
         }
         _ => panic!("Expected Assignment"),
     }
@@ -122,7 +129,7 @@ fn test_parse_self_field_assignment() {
 fn test_parse_method_call() {
     let input = "Foo.new";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
@@ -133,8 +140,9 @@ fn test_parse_method_call() {
                 object,
                 method,
                 args,
+                ..
             } => {
-                assert!(matches!(**object, Expr::Identifier(ref s) if s == "Foo"));
+                assert!(matches!(**object, Expr::Identifier { name: ref s, .. } if s == "Foo"));
                 assert_eq!(method, "new");
                 assert_eq!(args.len(), 0);
             }
@@ -148,7 +156,7 @@ fn test_parse_method_call() {
 fn test_generate_simple_class() {
     let input = "class Foo\nfn Foo > new { self.a = 1 }\nf = Foo.new";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
@@ -166,7 +174,7 @@ fn test_generate_simple_class() {
 fn test_generate_class_with_method() {
     let input = "class Foo\nfn Foo > new { self.a = 1 }\nfn Foo > get { a }";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
@@ -178,3 +186,137 @@ fn test_generate_class_with_method() {
     assert!(code.contains("fn get(&self) -> i64"));
     assert!(code.contains("self.a"));
 }
+
+fn generate(input: &str) -> String {
+    let tokens = Tokenizer::new(input).tokenize().unwrap();
+    CodeGenerator::generate_program(&Parser::new(tokens).parse().unwrap())
+}
+
+#[test]
+fn test_method_params_and_locals_hide_fields() {
+    let code = generate(
+        "class Counter {\n  count: int\n  step: int\n}\n\
+         fn Counter > advance(count) {\n  next = count + step\n  next\n}",
+    );
+
+    assert!(code.contains("let next = count + self.step;"));
+    assert!(code.contains("        next\n"));
+}
+
+#[test]
+fn test_self_field_read_is_not_a_call() {
+    let code = generate(
+        "class Point {\n  x: int\n  y: int\n}\nfn Point > sum() {\n  self.x + self.y\n}\n\
+         fn Point > double() {\n  self.sum() * 2\n}\np = Point.new()\nprint('%d', p.x)",
+    );
+
+    assert!(code.contains("self.x + self.y"));
+    assert!(code.contains("self.sum() * 2"));
+    assert!(code.contains("println!(\"{}\", p.x);"));
+}
+
+#[test]
+fn test_inherited_field_read_goes_through_parent() {
+    let code = generate(
+        "class Animal {\n  legs: int\n}\nclass Dog < Animal {\n  tricks: int\n}\n\
+         fn Dog > total() {\n  legs + self.legs + tricks\n}\nd = Dog.new()\nprint('%d', d.legs)",
+    );
+
+    assert!(code.contains("self.parent.legs + self.parent.legs + self.tricks"));
+    assert!(code.contains("println!(\"{}\", d.parent.legs);"));
+}
+
+#[test]
+fn test_constructor_with_locals_and_conditionals() {
+    let code = generate(
+        "class Account {\n  balance: int\n  tier: string\n}\n\
+         fn Account > new(deposit) {\n  bonus = deposit / 10\n  self.balance = deposit + bonus\n  \
+         if self.balance > 100 {\n    self.tier = 'gold'\n  } else {\n    self.tier = 'basic'\n  }\n}",
+    );
+
+    assert!(code.contains(
+        "    fn new(deposit: i64) -> Self {\n        let mut self_tier = Default::default();\n        \
+         let bonus = deposit / 10;\n        let self_balance = deposit + bonus;\n        if self_balance > 100 {"
+    ));
+    assert!(code.contains("self_tier = \"gold\".to_string();"));
+    assert!(code.contains("            balance: self_balance,\n            tier: self_tier,\n"));
+}
+
+#[test]
+fn test_constructor_field_assigned_twice_is_mut() {
+    let code = generate(
+        "class Counter\nfn Counter > new(n) {\n  self.count = n\n  self.count = self.count * 2\n}",
+    );
+
+    assert!(code.contains("let mut self_count = n;\n        self_count = self_count * 2;"));
+    assert!(code.contains("count: self_count,"));
+}
+
+#[test]
+fn test_constructor_of_assignments_builds_struct_directly() {
+    let code =
+        generate("class Named {\n  name: string\n}\nfn Named > new(name) {\n  self.name = 'x'\n}");
+
+    assert!(code.contains("Self {\n            name: \"x\".to_string(),\n        }"));
+    assert!(!code.contains("self_name"));
+}
+
+const POINT: &str = "class Point {\n  x: int\n  y: int\n}\n\
+    fn Point > new(x: int, y: int) {\n  self.x = x\n  self.y = y\n}\n";
+
+#[test]
+fn test_method_assigning_field_takes_mut_self() {
+    let code = generate(&format!(
+        "{}fn Point > shift(dx) {{\n  self.x = x + dx\n}}\n\
+         fn Point > clamp(limit) {{\n  if x > limit {{\n    self.x = limit\n  }}\n}}\n\
+         fn Point > norm() {{\n  x + y\n}}\n\
+         p = Point.new(1, 2)\np.shift(3)\nprint('%d', p.norm())",
+        POINT
+    ));
+
+    assert!(code.contains("fn shift(&mut self, dx: i64) {\n        self.x = self.x + dx;\n    }"));
+    assert!(code.contains(
+        "fn clamp(&mut self, limit: i64) {\n        if self.x > limit {\n            self.x = limit;\n        }\n    }"
+    ));
+    assert!(code.contains("fn norm(&self) -> i64"));
+    assert!(code.contains("let mut p = Point::new(1, 2);"));
+}
+
+#[test]
+fn test_method_calling_mutating_method_takes_mut_self() {
+    let code = generate(&format!(
+        "{}fn Point > shift(dx) {{\n  self.x = x + dx\n}}\n\
+         fn Point > reset() {{\n  self.shift(-x)\n  self.y = 0\n}}",
+        POINT
+    ));
+
+    assert!(code.contains("fn reset(&mut self) {\n        self.shift(-self.x);"));
+}
+
+#[test]
+fn test_setters_run() {
+    let dir = std::env::temp_dir().join(format!("grit_class_setters_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("point.grit"),
+        format!(
+            "{}fn Point > set_x(value) {{\n  self.x = value\n}}\n\
+             fn Point > shift(dx) {{\n  if dx != 0 {{\n    self.x = x + dx\n  }}\n}}\n\
+             class Point3 < Point {{\n  z: int\n}}\n\
+             fn Point3 > new(x: int, y: int, z: int) {{\n  self.x = x\n  self.y = y\n  self.z = z\n}}\n\
+             fn Point3 > lift(dz) {{\n  self.z = z + dz\n  self.y = y + 1\n}}\n\
+             test 'setter' {{\n  p = Point.new(1, 2)\n  p.set_x(7)\n  p.shift(3)\n  assert(p.x == 10)\n}}\n\
+             test 'inherited' {{\n  q = Point3.new(1, 2, 3)\n  q.lift(4)\n  q.shift(2)\n  \
+             assert(q.x == 3 && q.y == 3 && q.z == 7)\n}}\n",
+            POINT
+        ),
+    )
+    .unwrap();
+
+    let report = grit::testing::run_tests(std::slice::from_ref(&dir)).unwrap();
+    assert_eq!(report.results.len(), 2);
+    assert_eq!(report.failed(), 0, "{:?}", report.results);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -26,7 +26,7 @@ fn cleanup_test_file(path: &str) {
 
 #[test]
 fn test_cli_no_arguments() {
-    let output = Command::new(&get_binary_path())
+    let output = Command::new(get_binary_path())
         .output()
         .expect("Failed to execute command");
 
@@ -38,7 +38,7 @@ fn test_cli_no_arguments() {
 
 #[test]
 fn test_cli_file_not_found() {
-    let output = Command::new(&get_binary_path())
+    let output = Command::new(get_binary_path())
         .arg("nonexistent_file.grit")
         .output()
         .expect("Failed to execute command");
@@ -53,7 +53,7 @@ fn test_cli_file_not_found() {
 fn test_cli_simple_expression() {
     let test_file = create_test_file("test_simple.grit", "1 + 2");
 
-    let output = Command::new(&get_binary_path())
+    let output = Command::new(get_binary_path())
         .arg(&test_file)
         .output()
         .expect("Failed to execute command");
@@ -78,7 +78,7 @@ fn test_cli_simple_expression() {
 fn test_cli_complex_expression() {
     let test_file = create_test_file("test_complex.grit", "(10 + 20) * 3");
 
-    let output = Command::new(&get_binary_path())
+    let output = Command::new(get_binary_path())
         .arg(&test_file)
         .output()
         .expect("Failed to execute command");
@@ -106,7 +106,7 @@ fn test_cli_complex_expression() {
 fn test_cli_empty_file() {
     let test_file = create_test_file("test_empty.grit", "");
 
-    let output = Command::new(&get_binary_path())
+    let output = Command::new(get_binary_path())
         .arg(&test_file)
         .output()
         .expect("Failed to execute command");
@@ -124,7 +124,7 @@ fn test_cli_empty_file() {
 fn test_cli_multiline_expression() {
     let test_file = create_test_file("test_multiline.grit", "1 + 2\n3 * 4");
 
-    let output = Command::new(&get_binary_path())
+    let output = Command::new(get_binary_path())
         .arg(&test_file)
         .output()
         .expect("Failed to execute command");
@@ -144,7 +144,7 @@ fn test_cli_multiline_expression() {
 fn test_cli_all_operators() {
     let test_file = create_test_file("test_operators.grit", "1 + 2 - 3 * 4 / 5");
 
-    let output = Command::new(&get_binary_path())
+    let output = Command::new(get_binary_path())
         .arg(&test_file)
         .output()
         .expect("Failed to execute command");
@@ -164,7 +164,7 @@ fn test_cli_all_operators() {
 fn test_cli_invalid_character() {
     let test_file = create_test_file("test_invalid.grit", "1 + @");
 
-    let output = Command::new(&get_binary_path())
+    let output = Command::new(get_binary_path())
         .arg(&test_file)
         .output()
         .expect("Failed to execute command");
@@ -174,3 +174,32 @@ fn test_cli_invalid_character() {
     // Should fail due to unexpected character
     assert!(!output.status.success());
 }
+
+#[test]
+fn test_cli_optimize_flag() {
+    let test_file = create_test_file(
+        "test_optimize.grit",
+        "fn debug() {\n  print('debug')\n}\nif false {\n  debug()\n}\nprint('done')",
+    );
+
+    let optimized = Command::new(get_binary_path())
+        .args(["--optimize", &test_file])
+        .output()
+        .expect("Failed to execute command");
+    let unknown = Command::new(get_binary_path())
+        .args(["--optimize=inline", &test_file])
+        .output()
+        .expect("Failed to execute command");
+
+    cleanup_test_file(&test_file);
+
+    assert!(optimized.status.success());
+    let stdout = String::from_utf8_lossy(&optimized.stdout);
+    let code = stdout.split("Generated Rust code:").nth(1).unwrap();
+    assert!(!code.contains("debug"));
+    assert!(code.contains("println!(\"done\");"));
+
+    assert!(!unknown.status.success());
+    let stderr = String::from_utf8_lossy(&unknown.stderr);
+    assert!(stderr.contains("Unknown optimization pass 'inline'"));
+}
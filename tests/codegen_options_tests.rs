@@ -0,0 +1,110 @@
+mod common;
+
+use common::parse;
+use grit::codegen::options::{CodegenOptions, IntType};
+use grit::codegen::source_map::SourceMap;
+use grit::codegen::CodeGenerator;
+use grit::testing::check_rust;
+
+fn generate(input: &str, options: CodegenOptions) -> String {
+    CodeGenerator::generate_program_with_options(&parse(input), &options)
+}
+
+#[test]
+fn test_default_options_match_generate_program() {
+    let program = parse("class Point {\n  x: int\n}\nfn Point > new(x: int) {\n  self.x = x\n}\np = Point.new(1)\nprint('%d', p.x)");
+
+    assert_eq!(
+        CodeGenerator::generate_program_with_options(&program, &CodegenOptions::default()),
+        CodeGenerator::generate_program(&program)
+    );
+}
+
+#[test]
+fn test_indent_width() {
+    let options = CodegenOptions {
+        indent_width: 2,
+        ..CodegenOptions::default()
+    };
+    let code = generate(
+        "fn sign(n) {\n  if n < 0 {\n    return -1\n  }\n  1\n}\nprint('%d', sign(-5))",
+        options,
+    );
+
+    assert!(code.contains("fn sign(n: i64) -> i64 {\n  if n < 0 {\n    return -1;\n  }\n  1\n}"));
+    assert!(code.contains("fn main() {\n  println!(\"{}\", sign(-5));\n}"));
+    assert_eq!(check_rust(&code, &SourceMap::default()).unwrap(), Ok(()));
+}
+
+#[test]
+fn test_struct_derives() {
+    let options = CodegenOptions {
+        derives: vec!["Clone".to_string(), "Debug".to_string()],
+        ..CodegenOptions::default()
+    };
+    let code = generate(
+        "class Animal {\n  legs: int\n}\nclass Dog < Animal {\n  name: string\n}",
+        options,
+    );
+
+    assert!(code.contains("#[derive(Clone, Debug, Default)]\nstruct Animal {"));
+    assert!(code.contains("#[derive(Clone, Debug)]\nstruct Dog {"));
+}
+
+#[test]
+fn test_no_derives_leaves_out_attribute() {
+    let options = CodegenOptions {
+        derives: Vec::new(),
+        ..CodegenOptions::default()
+    };
+    let code = generate("class Point {\n  x: int\n}", options);
+
+    assert!(!code.contains("#[derive"));
+    assert!(code.contains("struct Point {\n    x: i64,\n}"));
+}
+
+#[test]
+fn test_int_type_i32() {
+    let options = CodegenOptions {
+        int_type: IntType::I32,
+        ..CodegenOptions::default()
+    };
+    let code = generate(
+        "const MAX = 10\nfn add(a: int, b) {\n  a + b\n}\nx = to_int(2.5)\nstart = clock()\nprint('%d %d %d', add(x, MAX), start, time())",
+        options,
+    );
+
+    assert!(code.contains("const MAX: i32 = 10;"));
+    assert!(code.contains("fn add(a: i32, b: i32) -> i32 {"));
+    assert!(code.contains("let x = (2.5 as i32);"));
+    assert!(code.contains("let start = (grit_clock() as i32);"));
+    assert!(code.contains(".as_secs() as i32)"));
+    assert!(!code.contains("i64") || code.contains("fn grit_clock() -> i64"));
+    assert_eq!(check_rust(&code, &SourceMap::default()).unwrap(), Ok(()));
+}
+
+#[test]
+fn test_statements_without_main() {
+    let options = CodegenOptions {
+        wrap_in_main: false,
+        ..CodegenOptions::default()
+    };
+    let code = generate(
+        "fn double(n) {\n  n * 2\n}\nx = double(4)\nprint('%d', x)",
+        options,
+    );
+
+    assert!(!code.contains("fn main"));
+    assert!(code.ends_with("}\n\nlet x = double(4);\nprintln!(\"{}\", x);\n"));
+}
+
+#[test]
+fn test_allow_preamble() {
+    let options = CodegenOptions {
+        allow: vec!["unused_variables".to_string(), "dead_code".to_string()],
+        ..CodegenOptions::default()
+    };
+    let code = generate("x = 1", options);
+
+    assert!(code.starts_with("#![allow(unused_variables, dead_code)]\n\nfn main() {\n"));
+}
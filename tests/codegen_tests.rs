@@ -1,14 +1,13 @@
+#![allow(clippy::approx_constant)]
+
 use grit::codegen::CodeGenerator;
+use grit::lexer::Span;
+use grit::parser::ast::build::{assign, binop, ident, int, program};
 use grit::parser::{BinaryOperator, Expr, Program, Statement};
 
 #[test]
 fn test_generate_assignment() {
-    let program = Program {
-        statements: vec![Statement::Assignment {
-            name: "x".to_string(),
-            value: Expr::Integer(42),
-        }],
-    };
+    let program = program(vec![assign("x", int(42))]);
 
     let code = CodeGenerator::generate_program(&program);
     assert!(code.contains("let x = 42;"));
@@ -16,18 +15,7 @@ fn test_generate_assignment() {
 
 #[test]
 fn test_generate_multiple_assignments() {
-    let program = Program {
-        statements: vec![
-            Statement::Assignment {
-                name: "a".to_string(),
-                value: Expr::Integer(1),
-            },
-            Statement::Assignment {
-                name: "b".to_string(),
-                value: Expr::Integer(2),
-            },
-        ],
-    };
+    let program = program(vec![assign("a", int(1)), assign("b", int(2))]);
 
     let code = CodeGenerator::generate_program(&program);
     assert!(code.contains("let a = 1;"));
@@ -36,16 +24,10 @@ fn test_generate_multiple_assignments() {
 
 #[test]
 fn test_generate_assignment_with_expression() {
-    let program = Program {
-        statements: vec![Statement::Assignment {
-            name: "result".to_string(),
-            value: Expr::BinaryOp {
-                left: Box::new(Expr::Integer(1)),
-                op: BinaryOperator::Add,
-                right: Box::new(Expr::Integer(2)),
-            },
-        }],
-    };
+    let program = program(vec![assign(
+        "result",
+        binop(int(1), BinaryOperator::Add, int(2)),
+    )]);
 
     let code = CodeGenerator::generate_program(&program);
     assert!(code.contains("let result = 1 + 2;"));
@@ -53,12 +35,7 @@ fn test_generate_assignment_with_expression() {
 
 #[test]
 fn test_generate_assignment_with_identifier() {
-    let program = Program {
-        statements: vec![Statement::Assignment {
-            name: "x".to_string(),
-            value: Expr::Identifier("y".to_string()),
-        }],
-    };
+    let program = program(vec![assign("x", ident("y"))]);
 
     let code = CodeGenerator::generate_program(&program);
     assert!(code.contains("let x = y;"));
@@ -70,6 +47,7 @@ fn test_generate_print_no_args() {
         statements: vec![Statement::Expression(Expr::FunctionCall {
             name: "print".to_string(),
             args: vec![],
+            span: Span::default(),
         })],
     };
 
@@ -82,7 +60,11 @@ fn test_generate_print_string_only() {
     let program = Program {
         statements: vec![Statement::Expression(Expr::FunctionCall {
             name: "print".to_string(),
-            args: vec![Expr::String("hello".to_string())],
+            args: vec![Expr::String {
+                value: "hello".to_string(),
+                span: Span::default(),
+            }],
+            span: Span::default(),
         })],
     };
 
@@ -95,7 +77,17 @@ fn test_generate_print_with_format_d() {
     let program = Program {
         statements: vec![Statement::Expression(Expr::FunctionCall {
             name: "print".to_string(),
-            args: vec![Expr::String("value: %d".to_string()), Expr::Integer(42)],
+            args: vec![
+                Expr::String {
+                    value: "value: %d".to_string(),
+                    span: Span::default(),
+                },
+                Expr::Integer {
+                    value: 42,
+                    span: Span::default(),
+                },
+            ],
+            span: Span::default(),
         })],
     };
 
@@ -109,9 +101,16 @@ fn test_generate_print_with_format_s() {
         statements: vec![Statement::Expression(Expr::FunctionCall {
             name: "print".to_string(),
             args: vec![
-                Expr::String("name: %s".to_string()),
-                Expr::String("Alice".to_string()),
+                Expr::String {
+                    value: "name: %s".to_string(),
+                    span: Span::default(),
+                },
+                Expr::String {
+                    value: "Alice".to_string(),
+                    span: Span::default(),
+                },
             ],
+            span: Span::default(),
         })],
     };
 
@@ -125,9 +124,16 @@ fn test_generate_print_with_variable() {
         statements: vec![Statement::Expression(Expr::FunctionCall {
             name: "print".to_string(),
             args: vec![
-                Expr::String("x: %d".to_string()),
-                Expr::Identifier("x".to_string()),
+                Expr::String {
+                    value: "x: %d".to_string(),
+                    span: Span::default(),
+                },
+                Expr::Identifier {
+                    name: "x".into(),
+                    span: Span::default(),
+                },
             ],
+            span: Span::default(),
         })],
     };
 
@@ -141,10 +147,20 @@ fn test_generate_print_multiple_args() {
         statements: vec![Statement::Expression(Expr::FunctionCall {
             name: "print".to_string(),
             args: vec![
-                Expr::String("a=%d b=%d".to_string()),
-                Expr::Integer(1),
-                Expr::Integer(2),
+                Expr::String {
+                    value: "a=%d b=%d".to_string(),
+                    span: Span::default(),
+                },
+                Expr::Integer {
+                    value: 1,
+                    span: Span::default(),
+                },
+                Expr::Integer {
+                    value: 2,
+                    span: Span::default(),
+                },
             ],
+            span: Span::default(),
         })],
     };
 
@@ -157,7 +173,13 @@ fn test_generate_string_expression() {
     let program = Program {
         statements: vec![Statement::Assignment {
             name: "msg".to_string(),
-            value: Expr::String("hello world".to_string()),
+            mutable: false,
+            ty: None,
+            value: Expr::String {
+                value: "hello world".to_string(),
+                span: Span::default(),
+            },
+            span: Span::default(),
         }],
     };
 
@@ -172,7 +194,13 @@ fn test_generate_string_with_quote() {
     let program = Program {
         statements: vec![Statement::Assignment {
             name: "msg".to_string(),
-            value: Expr::String("say \"hi\"".to_string()),
+            mutable: false,
+            ty: None,
+            value: Expr::String {
+                value: "say \"hi\"".to_string(),
+                span: Span::default(),
+            },
+            span: Span::default(),
         }],
     };
 
@@ -186,10 +214,23 @@ fn test_generate_function_call_in_expression() {
     let program = Program {
         statements: vec![Statement::Assignment {
             name: "result".to_string(),
+            mutable: false,
+            ty: None,
             value: Expr::FunctionCall {
                 name: "foo".to_string(),
-                args: vec![Expr::Integer(1), Expr::Integer(2)],
+                args: vec![
+                    Expr::Integer {
+                        value: 1,
+                        span: Span::default(),
+                    },
+                    Expr::Integer {
+                        value: 2,
+                        span: Span::default(),
+                    },
+                ],
+                span: Span::default(),
             },
+            span: Span::default(),
         }],
     };
 
@@ -203,26 +244,55 @@ fn test_generate_complete_program() {
         statements: vec![
             Statement::Assignment {
                 name: "a".to_string(),
-                value: Expr::Integer(1),
+                mutable: false,
+                ty: None,
+                value: Expr::Integer {
+                    value: 1,
+                    span: Span::default(),
+                },
+                span: Span::default(),
             },
             Statement::Assignment {
                 name: "b".to_string(),
-                value: Expr::Integer(2),
+                mutable: false,
+                ty: None,
+                value: Expr::Integer {
+                    value: 2,
+                    span: Span::default(),
+                },
+                span: Span::default(),
             },
             Statement::Assignment {
                 name: "c".to_string(),
+                mutable: false,
+                ty: None,
                 value: Expr::BinaryOp {
-                    left: Box::new(Expr::Identifier("a".to_string())),
+                    left: Box::new(Expr::Identifier {
+                        name: "a".into(),
+                        span: Span::default(),
+                    }),
                     op: BinaryOperator::Add,
-                    right: Box::new(Expr::Identifier("b".to_string())),
+                    right: Box::new(Expr::Identifier {
+                        name: "b".into(),
+                        span: Span::default(),
+                    }),
+                    span: Span::default(),
                 },
+                span: Span::default(),
             },
             Statement::Expression(Expr::FunctionCall {
                 name: "print".to_string(),
                 args: vec![
-                    Expr::String("c: %d".to_string()),
-                    Expr::Identifier("c".to_string()),
+                    Expr::String {
+                        value: "c: %d".to_string(),
+                        span: Span::default(),
+                    },
+                    Expr::Identifier {
+                        name: "c".into(),
+                        span: Span::default(),
+                    },
                 ],
+                span: Span::default(),
             }),
         ],
     };
@@ -240,7 +310,11 @@ fn test_generate_non_print_function_call() {
     let program = Program {
         statements: vec![Statement::Expression(Expr::FunctionCall {
             name: "other_func".to_string(),
-            args: vec![Expr::Integer(42)],
+            args: vec![Expr::Integer {
+                value: 42,
+                span: Span::default(),
+            }],
+            span: Span::default(),
         })],
     };
 
@@ -254,13 +328,17 @@ fn test_generate_print_with_non_string_format() {
     let program = Program {
         statements: vec![Statement::Expression(Expr::FunctionCall {
             name: "print".to_string(),
-            args: vec![Expr::Integer(42)],
+            args: vec![Expr::Integer {
+                value: 42,
+                span: Span::default(),
+            }],
+            span: Span::default(),
         })],
     };
 
     let code = CodeGenerator::generate_program(&program);
-    // When first arg is not a string, it becomes the format, with no values to print
-    assert!(code.contains("println!(\"{}\");"));
+    // When first arg is not a string, it is printed on its own
+    assert!(code.contains("println!(\"{}\", 42);"));
 }
 
 // Tests moved from src/codegen/mod.rs
@@ -272,7 +350,13 @@ fn assert_expression(expected: &str, expr: Expr) {
 
 #[test]
 fn test_generate_integer_expression() {
-    assert_expression("42", Expr::Integer(42));
+    assert_expression(
+        "42",
+        Expr::Integer {
+            value: 42,
+            span: Span::default(),
+        },
+    );
 }
 
 #[test]
@@ -280,9 +364,16 @@ fn test_generate_addition_expression() {
     assert_expression(
         "1 + 2",
         Expr::BinaryOp {
-            left: Box::new(Expr::Integer(1)),
+            left: Box::new(Expr::Integer {
+                value: 1,
+                span: Span::default(),
+            }),
             op: BinaryOperator::Add,
-            right: Box::new(Expr::Integer(2)),
+            right: Box::new(Expr::Integer {
+                value: 2,
+                span: Span::default(),
+            }),
+            span: Span::default(),
         },
     );
 }
@@ -292,9 +383,16 @@ fn test_generate_multiplication_expression() {
     assert_expression(
         "3 * 4",
         Expr::BinaryOp {
-            left: Box::new(Expr::Integer(3)),
+            left: Box::new(Expr::Integer {
+                value: 3,
+                span: Span::default(),
+            }),
             op: BinaryOperator::Multiply,
-            right: Box::new(Expr::Integer(4)),
+            right: Box::new(Expr::Integer {
+                value: 4,
+                span: Span::default(),
+            }),
+            span: Span::default(),
         },
     );
 }
@@ -304,13 +402,24 @@ fn test_generate_expression_respects_precedence() {
     assert_expression(
         "1 + 2 * 3",
         Expr::BinaryOp {
-            left: Box::new(Expr::Integer(1)),
+            left: Box::new(Expr::Integer {
+                value: 1,
+                span: Span::default(),
+            }),
             op: BinaryOperator::Add,
             right: Box::new(Expr::BinaryOp {
-                left: Box::new(Expr::Integer(2)),
+                left: Box::new(Expr::Integer {
+                    value: 2,
+                    span: Span::default(),
+                }),
                 op: BinaryOperator::Multiply,
-                right: Box::new(Expr::Integer(3)),
+                right: Box::new(Expr::Integer {
+                    value: 3,
+                    span: Span::default(),
+                }),
+                span: Span::default(),
             }),
+            span: Span::default(),
         },
     );
 }
@@ -320,13 +429,24 @@ fn test_generate_expression_respects_associativity() {
     assert_expression(
         "1 - (2 - 3)",
         Expr::BinaryOp {
-            left: Box::new(Expr::Integer(1)),
+            left: Box::new(Expr::Integer {
+                value: 1,
+                span: Span::default(),
+            }),
             op: BinaryOperator::Subtract,
             right: Box::new(Expr::BinaryOp {
-                left: Box::new(Expr::Integer(2)),
+                left: Box::new(Expr::Integer {
+                    value: 2,
+                    span: Span::default(),
+                }),
                 op: BinaryOperator::Subtract,
-                right: Box::new(Expr::Integer(3)),
+                right: Box::new(Expr::Integer {
+                    value: 3,
+                    span: Span::default(),
+                }),
+                span: Span::default(),
             }),
+            span: Span::default(),
         },
     );
 }
@@ -336,13 +456,27 @@ fn test_generate_expression_with_grouping() {
     assert_expression(
         "(1 + 2) * 3",
         Expr::BinaryOp {
-            left: Box::new(Expr::Grouped(Box::new(Expr::BinaryOp {
-                left: Box::new(Expr::Integer(1)),
-                op: BinaryOperator::Add,
-                right: Box::new(Expr::Integer(2)),
-            }))),
+            left: Box::new(Expr::Grouped {
+                inner: Box::new(Expr::BinaryOp {
+                    left: Box::new(Expr::Integer {
+                        value: 1,
+                        span: Span::default(),
+                    }),
+                    op: BinaryOperator::Add,
+                    right: Box::new(Expr::Integer {
+                        value: 2,
+                        span: Span::default(),
+                    }),
+                    span: Span::default(),
+                }),
+                span: Span::default(),
+            }),
             op: BinaryOperator::Multiply,
-            right: Box::new(Expr::Integer(3)),
+            right: Box::new(Expr::Integer {
+                value: 3,
+                span: Span::default(),
+            }),
+            span: Span::default(),
         },
     );
 }
@@ -353,12 +487,23 @@ fn test_generate_expression_parenthesizes_lower_precedence_left_child() {
         "(1 + 2) * 3",
         Expr::BinaryOp {
             left: Box::new(Expr::BinaryOp {
-                left: Box::new(Expr::Integer(1)),
+                left: Box::new(Expr::Integer {
+                    value: 1,
+                    span: Span::default(),
+                }),
                 op: BinaryOperator::Add,
-                right: Box::new(Expr::Integer(2)),
+                right: Box::new(Expr::Integer {
+                    value: 2,
+                    span: Span::default(),
+                }),
+                span: Span::default(),
             }),
             op: BinaryOperator::Multiply,
-            right: Box::new(Expr::Integer(3)),
+            right: Box::new(Expr::Integer {
+                value: 3,
+                span: Span::default(),
+            }),
+            span: Span::default(),
         },
     );
 }
@@ -368,13 +513,24 @@ fn test_generate_expression_parenthesizes_lower_precedence_right_child() {
     assert_expression(
         "3 / (1 + 2)",
         Expr::BinaryOp {
-            left: Box::new(Expr::Integer(3)),
+            left: Box::new(Expr::Integer {
+                value: 3,
+                span: Span::default(),
+            }),
             op: BinaryOperator::Divide,
             right: Box::new(Expr::BinaryOp {
-                left: Box::new(Expr::Integer(1)),
+                left: Box::new(Expr::Integer {
+                    value: 1,
+                    span: Span::default(),
+                }),
                 op: BinaryOperator::Add,
-                right: Box::new(Expr::Integer(2)),
+                right: Box::new(Expr::Integer {
+                    value: 2,
+                    span: Span::default(),
+                }),
+                span: Span::default(),
             }),
+            span: Span::default(),
         },
     );
 }
@@ -382,7 +538,10 @@ fn test_generate_expression_parenthesizes_lower_precedence_right_child() {
 #[test]
 fn test_generate_program_wraps_single_expression() {
     let program = Program {
-        statements: vec![Statement::Expression(Expr::Integer(5))],
+        statements: vec![Statement::Expression(Expr::Integer {
+            value: 5,
+            span: Span::default(),
+        })],
     };
     let rust_code = CodeGenerator::generate_program(&program);
     let expected = "fn main() {\n    let result = 5;\n    println!(\"{}\", result);\n}\n";
@@ -393,7 +552,13 @@ fn test_generate_program_wraps_single_expression() {
 
 #[test]
 fn test_generate_float_expression() {
-    assert_expression("3.14", Expr::Float(3.14));
+    assert_expression(
+        "3.14",
+        Expr::Float {
+            value: 3.14,
+            span: Span::default(),
+        },
+    );
 }
 
 #[test]
@@ -401,9 +566,16 @@ fn test_generate_float_addition() {
     assert_expression(
         "1.5 + 2.5",
         Expr::BinaryOp {
-            left: Box::new(Expr::Float(1.5)),
+            left: Box::new(Expr::Float {
+                value: 1.5,
+                span: Span::default(),
+            }),
             op: BinaryOperator::Add,
-            right: Box::new(Expr::Float(2.5)),
+            right: Box::new(Expr::Float {
+                value: 2.5,
+                span: Span::default(),
+            }),
+            span: Span::default(),
         },
     );
 }
@@ -411,11 +583,18 @@ fn test_generate_float_addition() {
 #[test]
 fn test_generate_float_multiplication() {
     assert_expression(
-        "3.14 * 2",
+        "3.14 * 2.0",
         Expr::BinaryOp {
-            left: Box::new(Expr::Float(3.14)),
+            left: Box::new(Expr::Float {
+                value: 3.14,
+                span: Span::default(),
+            }),
             op: BinaryOperator::Multiply,
-            right: Box::new(Expr::Float(2.0)),
+            right: Box::new(Expr::Float {
+                value: 2.0,
+                span: Span::default(),
+            }),
+            span: Span::default(),
         },
     );
 }
@@ -423,11 +602,18 @@ fn test_generate_float_multiplication() {
 #[test]
 fn test_generate_mixed_int_float_expression() {
     assert_expression(
-        "5 + 2.5",
+        "5.0 + 2.5",
         Expr::BinaryOp {
-            left: Box::new(Expr::Integer(5)),
+            left: Box::new(Expr::Integer {
+                value: 5,
+                span: Span::default(),
+            }),
             op: BinaryOperator::Add,
-            right: Box::new(Expr::Float(2.5)),
+            right: Box::new(Expr::Float {
+                value: 2.5,
+                span: Span::default(),
+            }),
+            span: Span::default(),
         },
     );
 }
@@ -437,7 +623,13 @@ fn test_generate_float_assignment() {
     let program = Program {
         statements: vec![Statement::Assignment {
             name: "pi".to_string(),
-            value: Expr::Float(3.14159),
+            mutable: false,
+            ty: None,
+            value: Expr::Float {
+                value: 3.14159,
+                span: Span::default(),
+            },
+            span: Span::default(),
         }],
     };
 
@@ -451,9 +643,16 @@ fn test_generate_float_in_print() {
         statements: vec![Statement::Expression(Expr::FunctionCall {
             name: "print".to_string(),
             args: vec![
-                Expr::String("Float value: %s".to_string()),
-                Expr::Float(2.718),
+                Expr::String {
+                    value: "Float value: %s".to_string(),
+                    span: Span::default(),
+                },
+                Expr::Float {
+                    value: 2.718,
+                    span: Span::default(),
+                },
             ],
+            span: Span::default(),
         })],
     };
 
@@ -467,7 +666,11 @@ fn test_generate_float_in_print() {
 fn test_generate_to_int_conversion() {
     let expr = Expr::FunctionCall {
         name: "to_int".to_string(),
-        args: vec![Expr::Float(3.14)],
+        args: vec![Expr::Float {
+            value: 3.14,
+            span: Span::default(),
+        }],
+        span: Span::default(),
     };
     assert_expression("(3.14 as i64)", expr);
 }
@@ -476,7 +679,11 @@ fn test_generate_to_int_conversion() {
 fn test_generate_to_float_conversion() {
     let expr = Expr::FunctionCall {
         name: "to_float".to_string(),
-        args: vec![Expr::Integer(42)],
+        args: vec![Expr::Integer {
+            value: 42,
+            span: Span::default(),
+        }],
+        span: Span::default(),
     };
     assert_expression("(42 as f64)", expr);
 }
@@ -485,7 +692,11 @@ fn test_generate_to_float_conversion() {
 fn test_generate_to_string_conversion() {
     let expr = Expr::FunctionCall {
         name: "to_string".to_string(),
-        args: vec![Expr::Integer(42)],
+        args: vec![Expr::Integer {
+            value: 42,
+            span: Span::default(),
+        }],
+        span: Span::default(),
     };
     assert_expression("42.to_string()", expr);
 }
@@ -494,7 +705,11 @@ fn test_generate_to_string_conversion() {
 fn test_generate_to_string_float() {
     let expr = Expr::FunctionCall {
         name: "to_string".to_string(),
-        args: vec![Expr::Float(3.14)],
+        args: vec![Expr::Float {
+            value: 3.14,
+            span: Span::default(),
+        }],
+        span: Span::default(),
     };
     assert_expression("3.14.to_string()", expr);
 }
@@ -505,8 +720,13 @@ fn test_generate_nested_conversion() {
         name: "to_string".to_string(),
         args: vec![Expr::FunctionCall {
             name: "to_int".to_string(),
-            args: vec![Expr::Float(3.14)],
+            args: vec![Expr::Float {
+                value: 3.14,
+                span: Span::default(),
+            }],
+            span: Span::default(),
         }],
+        span: Span::default(),
     };
     assert_expression("(3.14 as i64).to_string()", expr);
 }
@@ -516,10 +736,17 @@ fn test_generate_conversion_in_assignment() {
     let program = Program {
         statements: vec![Statement::Assignment {
             name: "x".to_string(),
+            mutable: false,
+            ty: None,
             value: Expr::FunctionCall {
                 name: "to_float".to_string(),
-                args: vec![Expr::Integer(10)],
+                args: vec![Expr::Integer {
+                    value: 10,
+                    span: Span::default(),
+                }],
+                span: Span::default(),
             },
+            span: Span::default(),
         }],
     };
 
@@ -532,14 +759,25 @@ fn test_generate_conversion_in_expression() {
     let program = Program {
         statements: vec![Statement::Assignment {
             name: "result".to_string(),
+            mutable: false,
+            ty: None,
             value: Expr::BinaryOp {
                 left: Box::new(Expr::FunctionCall {
                     name: "to_float".to_string(),
-                    args: vec![Expr::Integer(5)],
+                    args: vec![Expr::Integer {
+                        value: 5,
+                        span: Span::default(),
+                    }],
+                    span: Span::default(),
                 }),
                 op: BinaryOperator::Multiply,
-                right: Box::new(Expr::Float(2.5)),
+                right: Box::new(Expr::Float {
+                    value: 2.5,
+                    span: Span::default(),
+                }),
+                span: Span::default(),
             },
+            span: Span::default(),
         }],
     };
 
@@ -550,15 +788,26 @@ fn test_generate_conversion_in_expression() {
 #[test]
 fn test_generate_float_with_precedence() {
     assert_expression(
-        "1.5 + 2 * 3.5",
+        "1.5 + 2.0 * 3.5",
         Expr::BinaryOp {
-            left: Box::new(Expr::Float(1.5)),
+            left: Box::new(Expr::Float {
+                value: 1.5,
+                span: Span::default(),
+            }),
             op: BinaryOperator::Add,
             right: Box::new(Expr::BinaryOp {
-                left: Box::new(Expr::Float(2.0)),
+                left: Box::new(Expr::Float {
+                    value: 2.0,
+                    span: Span::default(),
+                }),
                 op: BinaryOperator::Multiply,
-                right: Box::new(Expr::Float(3.5)),
+                right: Box::new(Expr::Float {
+                    value: 3.5,
+                    span: Span::default(),
+                }),
+                span: Span::default(),
             }),
+            span: Span::default(),
         },
     );
 }
@@ -566,11 +815,18 @@ fn test_generate_float_with_precedence() {
 #[test]
 fn test_generate_float_division() {
     assert_expression(
-        "10 / 3",
+        "10.0 / 3.0",
         Expr::BinaryOp {
-            left: Box::new(Expr::Float(10.0)),
+            left: Box::new(Expr::Float {
+                value: 10.0,
+                span: Span::default(),
+            }),
             op: BinaryOperator::Divide,
-            right: Box::new(Expr::Float(3.0)),
+            right: Box::new(Expr::Float {
+                value: 3.0,
+                span: Span::default(),
+            }),
+            span: Span::default(),
         },
     );
 }
@@ -580,9 +836,99 @@ fn test_generate_float_subtraction() {
     assert_expression(
         "5.5 - 2.3",
         Expr::BinaryOp {
-            left: Box::new(Expr::Float(5.5)),
+            left: Box::new(Expr::Float {
+                value: 5.5,
+                span: Span::default(),
+            }),
             op: BinaryOperator::Subtract,
-            right: Box::new(Expr::Float(2.3)),
+            right: Box::new(Expr::Float {
+                value: 2.3,
+                span: Span::default(),
+            }),
+            span: Span::default(),
+        },
+    );
+}
+
+#[test]
+fn test_generate_whole_number_float_keeps_decimal_point() {
+    assert_expression(
+        "2.0",
+        Expr::Float {
+            value: 2.0,
+            span: Span::default(),
+        },
+    );
+    assert_expression(
+        "0.0",
+        Expr::Float {
+            value: 0.0,
+            span: Span::default(),
+        },
+    );
+    assert_expression(
+        "100.0",
+        Expr::Float {
+            value: 100.0,
+            span: Span::default(),
+        },
+    );
+}
+
+#[test]
+fn test_generate_large_and_small_floats_stay_f64() {
+    assert_expression(
+        "1e20",
+        Expr::Float {
+            value: 1e20,
+            span: Span::default(),
+        },
+    );
+    assert_expression(
+        "1e-7",
+        Expr::Float {
+            value: 1e-7,
+            span: Span::default(),
         },
     );
 }
+
+#[test]
+fn test_generate_whole_number_float_assignment() {
+    let program = Program {
+        statements: vec![
+            Statement::Assignment {
+                name: "half".to_string(),
+                mutable: false,
+                ty: None,
+                value: Expr::BinaryOp {
+                    left: Box::new(Expr::Float {
+                        value: 1.0,
+                        span: Span::default(),
+                    }),
+                    op: BinaryOperator::Divide,
+                    right: Box::new(Expr::Float {
+                        value: 2.0,
+                        span: Span::default(),
+                    }),
+                    span: Span::default(),
+                },
+                span: Span::default(),
+            },
+            Statement::Assignment {
+                name: "x".to_string(),
+                mutable: false,
+                ty: None,
+                value: Expr::Float {
+                    value: 3.0,
+                    span: Span::default(),
+                },
+                span: Span::default(),
+            },
+        ],
+    };
+
+    let code = CodeGenerator::generate_program(&program);
+    assert!(code.contains("let half = 1.0 / 2.0;"));
+    assert!(code.contains("let x = 3.0;"));
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use common::parse;
+use grit::codegen::options::CodegenOptions;
+use grit::codegen::CodeGenerator;
+use std::io;
+
+const SOURCE: &str = "const LIMIT = 3\nclass Point {\n  x: int\n}\nfn Point > new(x: int) {\n  self.x = x\n}\nfn twice(n) {\n  n * 2\n}\np = Point.new(1)\nprint('%d %d', twice(p.x), LIMIT)\nprint('%d', clock())";
+
+/// Fails every write after the first `limit` bytes
+struct FailingWriter {
+    limit: usize,
+}
+
+impl io::Write for FailingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.len() > self.limit {
+            return Err(io::Error::new(io::ErrorKind::StorageFull, "disk full"));
+        }
+        self.limit -= buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_generate_program_to_matches_generate_program() {
+    let program = parse(SOURCE);
+
+    let mut code = String::new();
+    CodeGenerator::generate_program_to(&program, &CodegenOptions::default(), &mut code).unwrap();
+    assert_eq!(code, CodeGenerator::generate_program(&program));
+}
+
+#[test]
+fn test_write_program_writes_bytes() {
+    let program = parse(SOURCE);
+
+    let mut bytes = Vec::new();
+    CodeGenerator::write_program(&program, &CodegenOptions::default(), &mut bytes).unwrap();
+    assert_eq!(
+        String::from_utf8(bytes).unwrap(),
+        CodeGenerator::generate_program(&program)
+    );
+}
+
+#[test]
+fn test_write_program_returns_writer_error() {
+    let program = parse(SOURCE);
+
+    let err = CodeGenerator::write_program(
+        &program,
+        &CodegenOptions::default(),
+        FailingWriter { limit: 40 },
+    )
+    .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::StorageFull);
+    assert_eq!(err.to_string(), "disk full");
+}
+
+#[test]
+fn test_generate_test_harness_to_matches_generate_test_harness() {
+    let program = parse("fn add(a, b) {\n  a + b\n}\ntest 'adds' {\n  assert(add(1, 2) == 3)\n}");
+
+    let mut code = String::new();
+    CodeGenerator::generate_test_harness_to(&program, &CodegenOptions::default(), &mut code)
+        .unwrap();
+    assert_eq!(code, CodeGenerator::generate_test_harness(&program));
+    assert!(code.contains(
+        "fn main() {\n    // Failures are reported by grit_run_test, not the default panic message\n"
+    ));
+    assert!(code.contains("    if !grit_run_test(0, grit_test_0) {\n        failed += 1;\n    }\n"));
+}
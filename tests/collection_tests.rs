@@ -0,0 +1,258 @@
+mod common;
+
+use common::{generate, parse, try_parse};
+use grit::lexer::{Span, TokenType, Tokenizer};
+use grit::parser::{Expr, ParseError, Statement};
+use grit::semantic::{check_program, SymbolTable, Type, TypeEnv};
+
+fn parse_value(input: &str) -> Expr {
+    match parse(input).statements.pop() {
+        Some(Statement::Assignment { value, .. }) | Some(Statement::Expression(value)) => {
+            value.without_spans()
+        }
+        other => panic!("Expected expression, got {:?}", other),
+    }
+}
+
+/// Infers the type of the last assigned value, with earlier assignments in scope
+fn infer_last(input: &str) -> Type {
+    let program = parse(input);
+    let symbols = SymbolTable::from_program(&program);
+    let mut env = TypeEnv::new(&symbols);
+    let mut last = Type::Unknown;
+    for stmt in &program.statements {
+        if let Statement::Assignment { name, value, .. } = stmt {
+            last = env.infer(value);
+            env.bind(name, last.clone());
+        }
+    }
+    last
+}
+
+fn int_array() -> Type {
+    Type::Array(Box::new(Type::Int))
+}
+
+#[test]
+fn test_tokenize_brackets() {
+    let mut tokenizer = Tokenizer::new("[]");
+    let types: Vec<TokenType> = tokenizer
+        .tokenize()
+        .unwrap()
+        .into_iter()
+        .map(|token| token.token_type)
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            TokenType::LeftBracket,
+            TokenType::RightBracket,
+            TokenType::Eof
+        ]
+    );
+}
+
+#[test]
+fn test_parse_nested_array_literal() {
+    assert_eq!(
+        parse_value("grid = [[1, 2], []]"),
+        Expr::Array {
+            elements: vec![
+                Expr::Array {
+                    elements: vec![
+                        Expr::Integer {
+                            value: 1,
+                            span: Span::default()
+                        },
+                        Expr::Integer {
+                            value: 2,
+                            span: Span::default()
+                        }
+                    ],
+                    span: Span::default()
+                },
+                Expr::Array {
+                    elements: vec![],
+                    span: Span::default()
+                },
+            ],
+            span: Span::default()
+        }
+    );
+}
+
+#[test]
+fn test_parse_map_of_arrays() {
+    assert_eq!(
+        parse_value("scores = {'bob': [90], 'amy': []}"),
+        Expr::Map {
+            entries: vec![
+                (
+                    Expr::String {
+                        value: "bob".to_string(),
+                        span: Span::default()
+                    },
+                    Expr::Array {
+                        elements: vec![Expr::Integer {
+                            value: 90,
+                            span: Span::default()
+                        }],
+                        span: Span::default()
+                    }
+                ),
+                (
+                    Expr::String {
+                        value: "amy".to_string(),
+                        span: Span::default()
+                    },
+                    Expr::Array {
+                        elements: vec![],
+                        span: Span::default()
+                    }
+                ),
+            ],
+            span: Span::default()
+        }
+    );
+}
+
+#[test]
+fn test_parse_multiline_literal() {
+    let value = parse_value("grid = [\n  [1, 2],\n  [3, 4],\n]");
+    assert_eq!(value.to_string(), "[[1, 2], [3, 4]]");
+}
+
+#[test]
+fn test_parse_chained_index() {
+    assert_eq!(
+        parse_value("grid[i][j + 1]"),
+        Expr::Index {
+            object: Box::new(Expr::Index {
+                object: Box::new(Expr::Identifier {
+                    name: "grid".into(),
+                    span: Span::default()
+                }),
+                index: Box::new(Expr::Identifier {
+                    name: "i".into(),
+                    span: Span::default()
+                }),
+                span: Span::default(),
+            }),
+            index: Box::new(Expr::BinaryOp {
+                left: Box::new(Expr::Identifier {
+                    name: "j".into(),
+                    span: Span::default()
+                }),
+                op: grit::parser::BinaryOperator::Add,
+                right: Box::new(Expr::Integer {
+                    value: 1,
+                    span: Span::default()
+                }),
+                span: Span::default(),
+            }),
+            span: Span::default(),
+        }
+    );
+}
+
+#[test]
+fn test_index_binds_tighter_than_arithmetic() {
+    assert_eq!(parse_value("a[0] * 2").to_string(), "(a[0] * 2)");
+}
+
+#[test]
+fn test_collection_display() {
+    assert_eq!(
+        parse_value("m = {'a': [1, 2], 'b': [3]}").to_string(),
+        "{'a': [1, 2], 'b': [3]}"
+    );
+}
+
+#[test]
+fn test_unclosed_array_is_an_error() {
+    assert!(matches!(
+        try_parse("a = [1, 2"),
+        Err(ParseError::UnexpectedEof { .. })
+    ));
+    assert!(matches!(
+        try_parse("a = [1 2]"),
+        Err(ParseError::UnexpectedToken { .. })
+    ));
+}
+
+#[test]
+fn test_map_entry_requires_colon() {
+    assert!(matches!(
+        try_parse("m = {'a' 1}"),
+        Err(ParseError::UnexpectedToken { expected, .. }) if expected == "':'"
+    ));
+}
+
+#[test]
+fn test_infer_nested_element_types() {
+    assert_eq!(
+        infer_last("grid = [[1, 2], [3]]"),
+        Type::Array(Box::new(int_array()))
+    );
+    assert_eq!(
+        infer_last("m = {'a': [1], 'b': [2, 3]}"),
+        Type::Map(Box::new(Type::String), Box::new(int_array()))
+    );
+    assert_eq!(infer_last("grid = [[1, 2]]\nrow = grid[0]"), int_array());
+    assert_eq!(infer_last("grid = [[1, 2]]\ncell = grid[0][1]"), Type::Int);
+    assert_eq!(infer_last("m = {'a': [1.5]}\nx = m['a'][0]"), Type::Float);
+}
+
+#[test]
+fn test_unify_element_types() {
+    assert_eq!(Type::unify([Type::Int, Type::Float]), Type::Float);
+    assert_eq!(Type::unify([Type::Int, Type::String]), Type::Unknown);
+    assert_eq!(Type::unify([]), Type::Unknown);
+    assert_eq!(
+        Type::Map(Box::new(Type::String), Box::new(int_array())).to_string(),
+        "map<string, array<int>>"
+    );
+}
+
+#[test]
+fn test_generate_nested_arrays() {
+    let code = generate("grid = [[1, 2], [3, 4]]\ni = 1\nx = grid[i][0]\nrow = grid[i - 1]");
+    assert!(code.contains("let grid = vec![vec![1, 2], vec![3, 4]];"));
+    assert!(code.contains("let x = grid[i as usize][0];"));
+    assert!(code.contains("let row = grid[(i - 1) as usize].clone();"));
+}
+
+#[test]
+fn test_generate_map_of_arrays() {
+    let code = generate("scores = {'bob': [90, 85]}\nfirst = scores['bob'][0]");
+    assert!(
+        code.contains("let scores = std::collections::HashMap::from([(\"bob\", vec![90, 85])]);")
+    );
+    assert!(code.contains("let first = scores[&\"bob\"][0];"));
+}
+
+#[test]
+fn test_generate_empty_map() {
+    let code = generate("m = {}");
+    assert!(code.contains("let m = std::collections::HashMap::new();"));
+}
+
+#[test]
+fn test_print_collections_with_debug_format() {
+    let code = generate("grid = [[1]]\nprint('%s and %d', grid, grid[0][0])");
+    assert!(code.contains("println!(\"{:?} and {}\", grid, grid[0][0]);"));
+}
+
+#[test]
+fn test_string_element_comparison_borrows() {
+    let code = generate("names = ['a', 'b']\nok = names[0] == 'a'");
+    assert!(code.contains("let ok = &*names[0] == \"a\";"));
+}
+
+#[test]
+fn test_format_checks_see_element_types() {
+    let program = parse("grid = [[1, 2]]\nprint('%d %d', grid[0][1], grid[0])");
+    let diagnostics = check_program(&program);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("array<int>"));
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use common::try_parse;
+use grit::codegen::CodeGenerator;
+use grit::parser::Statement;
+
+fn statements(input: &str) -> Vec<Statement> {
+    try_parse(input)
+        .unwrap()
+        .statements
+        .iter()
+        .map(Statement::without_spans)
+        .collect()
+}
+
+/// Asserts that a commented source parses to the same program as its plain twin
+fn assert_same(commented: &str, plain: &str) {
+    assert_eq!(statements(commented), statements(plain), "{}", commented);
+}
+
+#[test]
+fn test_comments_in_functions() {
+    assert_same(
+        "fn add( # numbers\n  a, ## first\n  # second\n  b\n) { # body\n  ## sum\n  a + b # done\n}",
+        "fn add(a, b) {\n  a + b\n}",
+    );
+    assert_same(
+        "x = add(\n  # first\n  1, ## one\n  2 # two\n)\ny = [\n  1, # a\n  ## b\n  2\n]",
+        "x = add(1, 2)\ny = [1, 2]",
+    );
+    assert_same("f = |a, b| a + b # sum", "f = |a, b| a + b");
+}
+
+#[test]
+fn test_comments_in_control_flow() {
+    assert_same(
+        "if x { # yes\n  y = 1\n} # no\n## still no\nelif z {\n  y = 2\n}\n# otherwise\nelse {\n  y = 3\n}",
+        "if x {\n  y = 1\n} elif z {\n  y = 2\n} else {\n  y = 3\n}",
+    );
+    assert_same(
+        "do {\n  x = x + 1\n} # again\nwhile x < 3\nwhile x > 0 { ## each\n  x = x - 1\n}",
+        "do {\n  x = x + 1\n} while x < 3\nwhile x > 0 {\n  x = x - 1\n}",
+    );
+    assert_same(
+        "match x { # which\n  1, # or\n  2 => y = 1 ## first\n  # fallback\n  _ => {\n    y = 2 # other\n  }\n}",
+        "match x {\n  1, 2 => y = 1\n  _ => {\n    y = 2\n  }\n}",
+    );
+    assert_same(
+        "y = cond { # pick\n  x > 1 { 'big' }\n  ## small\n  else { 'small' }\n}",
+        "y = cond {\n  x > 1 { 'big' }\n  else { 'small' }\n}",
+    );
+}
+
+#[test]
+fn test_comments_in_declarations() {
+    assert_same(
+        "class Point { # fields\n  x, ## across\n  # and\n  y\n}\nenum Color { # hues\n  Red, ## warm\n  Blue\n}",
+        "class Point { x, y }\nenum Color { Red, Blue }",
+    );
+    assert_same(
+        "import math { # helpers\n  sqrt, ## root\n  pow\n}\nm = { # pairs\n  'a': 1, ## one\n  'b': 2\n}",
+        "import math { sqrt, pow }\nm = { 'a': 1, 'b': 2 }",
+    );
+}
+
+#[test]
+fn test_doc_comments_still_document_definitions() {
+    let code = CodeGenerator::generate_program(
+        &try_parse("x = 1\nif x > 0 {\n  x = 2\n}\n## Doubles n\n# plain\nfn double(n) { ## twice\n  n * 2\n}")
+            .unwrap(),
+    );
+
+    assert!(code.contains("/// Doubles n\nfn double(n: i64) -> i64 {"));
+    assert!(!code.contains("twice"));
+    assert!(!code.contains("plain"));
+}
@@ -0,0 +1,56 @@
+//! Helpers shared by the integration tests
+//!
+//! Each test file is its own crate and uses only some of these.
+#![allow(dead_code)]
+
+use grit::codegen::CodeGenerator;
+use grit::lexer::Tokenizer;
+use grit::parser::{ParseError, Parser, Program};
+use grit::semantic::{check_program, Diagnostic, Severity};
+
+/// Tokenizes and parses a source, returning the parser's result
+pub fn try_parse(input: &str) -> Result<Program, ParseError> {
+    let tokens = Tokenizer::new(input).tokenize().unwrap();
+    Parser::new(tokens).parse()
+}
+
+/// Parses a source that is expected to parse
+pub fn parse(input: &str) -> Program {
+    try_parse(input).unwrap()
+}
+
+/// Returns the Rust generated for a source
+pub fn generate(input: &str) -> String {
+    CodeGenerator::generate_program(&parse(input))
+}
+
+/// Returns every diagnostic the checks report for a source
+pub fn check(input: &str) -> Vec<Diagnostic> {
+    check_program(&parse(input))
+}
+
+/// Returns the message of every diagnostic
+pub fn messages(input: &str) -> Vec<String> {
+    check(input)
+        .into_iter()
+        .map(|diagnostic| diagnostic.message)
+        .collect()
+}
+
+/// Returns the messages of the errors, leaving out warnings
+pub fn errors(input: &str) -> Vec<String> {
+    check(input)
+        .into_iter()
+        .filter(|diagnostic| diagnostic.is_error())
+        .map(|diagnostic| diagnostic.message)
+        .collect()
+}
+
+/// Returns the messages of the warnings
+pub fn warnings(input: &str) -> Vec<String> {
+    check(input)
+        .into_iter()
+        .filter(|diagnostic| diagnostic.severity == Severity::Warning)
+        .map(|diagnostic| diagnostic.message)
+        .collect()
+}
@@ -0,0 +1,76 @@
+use grit::lexer::TokenType;
+use grit::optimize::{Pass, Pipeline};
+use grit::{compile, compile_with_options, CompileOptions, CompileResult};
+
+#[test]
+fn test_compile_returns_every_artifact() {
+    let result = compile("x = 1\nprint('%d', x)");
+
+    assert_eq!(
+        result.tokens[0].token_type,
+        TokenType::Identifier("x".into())
+    );
+    assert_eq!(result.tokens.last().unwrap().token_type, TokenType::Eof);
+    assert_eq!(result.ast.as_ref().unwrap().statements.len(), 2);
+    assert!(result.diagnostics.is_empty());
+    assert!(result.succeeded());
+    assert!(result.generated_code.unwrap().contains("let x = 1;"));
+}
+
+#[test]
+fn test_compile_timings_add_up() {
+    let timings = compile("fn add(a, b) {\n  a + b\n}\nadd(1, 2)").timings;
+    assert_eq!(
+        timings.total(),
+        timings.lexing + timings.parsing + timings.checking + timings.codegen
+    );
+}
+
+#[test]
+fn test_compile_parse_error_keeps_tokens() {
+    let result = compile("fn (");
+    assert!(!result.tokens.is_empty());
+    assert!(result.ast.is_err());
+    assert!(result.diagnostics.is_empty());
+    assert!(!result.succeeded());
+    assert!(result.timings.checking.is_zero());
+    assert!(result.timings.codegen.is_zero());
+}
+
+#[test]
+fn test_compile_semantic_error_keeps_ast_and_diagnostics() {
+    let result: CompileResult = compile("print('%d')");
+    assert!(result.ast.is_ok());
+    assert_eq!(result.diagnostics.len(), 1);
+    assert!(result.diagnostics[0].is_error());
+    assert_eq!(result.generated_code, None);
+    assert!(result.timings.codegen.is_zero());
+}
+
+#[test]
+fn test_compile_reports_every_parse_error() {
+    let result = compile("x = 1 +\ny = 2\nz = ]");
+
+    assert_eq!(result.parse_errors.len(), 2);
+    assert_eq!(result.ast, Err(result.parse_errors[0].clone()));
+    assert!(compile("x = 1").parse_errors.is_empty());
+}
+
+#[test]
+fn test_compile_with_options_optimizes_checked_program() {
+    let source = "fn unused() {\n  1\n}\nif false {\n  print('never')\n}\nprint('done')";
+    let options = CompileOptions {
+        optimize: Some(Pipeline::new(vec![Pass::UnusedFunctions])),
+        ..CompileOptions::default()
+    };
+
+    let result = compile_with_options(source, &options);
+    let code = result.generated_code.unwrap();
+    assert!(!code.contains("fn unused"));
+    assert!(code.contains("if false {"));
+    assert_eq!(result.ast.unwrap().statements.len(), 3);
+    assert_eq!(
+        compile(source).generated_code,
+        compile_with_options(source, &CompileOptions::default()).generated_code
+    );
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use common::{errors, generate, parse, try_parse};
+use grit::lexer::{TokenType, Tokenizer};
+use grit::parser::{Expr, ParseError, Statement};
+
+const SIZE: &str = "n = 42\nsize = cond {\n  n < 10 { 'small' }\n  n < 100 {\n    half = n / 2\n    'medium'\n  }\n  else { 'large' }\n}";
+
+#[test]
+fn test_parse_cond() {
+    let tokens = Tokenizer::new("cond").tokenize().unwrap();
+    assert_eq!(tokens[0].token_type, TokenType::Cond);
+
+    let program = parse(SIZE);
+    match &program.statements[1] {
+        Statement::Assignment {
+            value:
+                Expr::Cond {
+                    branches,
+                    else_branch,
+                    ..
+                },
+            ..
+        } => {
+            assert_eq!(branches.len(), 2);
+            assert_eq!(branches[1].0.to_string(), "(n < 100)");
+            assert_eq!(branches[1].1.len(), 2);
+            assert_eq!(else_branch.len(), 1);
+        }
+        other => panic!("expected a cond, got {:?}", other),
+    }
+    assert_eq!(
+        program.statements[1].to_string(),
+        "size = cond { (n < 10) { 'small' } (n < 100) { half = (n / 2); 'medium' } else { 'large' } }"
+    );
+}
+
+#[test]
+fn test_cond_needs_an_else_branch_last() {
+    assert!(matches!(
+        try_parse("x = cond {\n  a { 1 }\n}"),
+        Err(ParseError::UnexpectedToken { .. })
+    ));
+    assert!(try_parse("x = cond {\n  else { 1 }\n  a { 2 }\n}").is_err());
+    assert!(try_parse("x = cond {\n  a { 1 }").is_err());
+    assert!(try_parse("x = cond a").is_err());
+    assert!(try_parse("x = cond {\n  else { 1 }\n}").is_ok());
+}
+
+#[test]
+fn test_check_cond() {
+    assert!(errors(SIZE).is_empty());
+    assert_eq!(
+        errors("n = 1\nx = cond {\n  n > 0 { y = 1 }\n  else { 0 }\n}"),
+        vec!["each branch of a cond must end in an expression giving its value"]
+    );
+    assert_eq!(
+        errors("n = 1\nx = cond {\n  n > 0 { 'a' }\n  else { 'b' }\n}\nprint('%d', x)"),
+        vec!["format specifier %d (argument 1) does not accept a value of type string"]
+    );
+}
+
+#[test]
+fn test_generate_cond() {
+    let code = generate(SIZE);
+
+    assert!(code.contains(
+        "let size = if n < 10 {\n        \"small\"\n    } else if n < 100 {\n        \
+         let half = n / 2;\n        \"medium\"\n    } else {\n        \"large\"\n    };"
+    ));
+}
+
+#[test]
+fn test_generate_cond_as_function_value() {
+    let code = generate("fn sign(x) = cond {\n  x < 0 { -1 }\n  else { 1 }\n}");
+
+    assert!(code.contains("fn sign(x: i64) -> i64 {\n    if x < 0 {"));
+}
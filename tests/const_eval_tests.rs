@@ -0,0 +1,68 @@
+use grit::lexer::Tokenizer;
+use grit::parser::ast::eval::{eval, eval_with, EvalError, Value};
+use grit::parser::{Expr, Parser, Statement};
+
+fn expr(input: &str) -> Expr {
+    let tokens = Tokenizer::new(&format!("x = {}", input))
+        .tokenize()
+        .unwrap();
+    match Parser::new(tokens).parse().unwrap().statements.remove(0) {
+        Statement::Assignment { value, .. } => value,
+        other => panic!("expected an assignment, got {:?}", other),
+    }
+}
+
+fn value(input: &str) -> Result<Value, EvalError> {
+    eval(&expr(input))
+}
+
+#[test]
+fn test_eval_literals_and_operators() {
+    assert_eq!(value("-(1 + 2) * 4 / 3"), Ok(Value::Int(-4)));
+    assert_eq!(value("1 + 0.5"), Ok(Value::Float(1.5)));
+    assert_eq!(value("'gr' + 'it'"), Ok(Value::String("grit".to_string())));
+    assert_eq!(value("not (2 >= 3) and 'a' < 'b'"), Ok(Value::Bool(true)));
+    assert_eq!(value("(6 & 3) | 8 ^ 1"), Ok(Value::Int(11)));
+    assert_eq!(value("1 << 62 >> 60"), Ok(Value::Int(4)));
+    assert_eq!(value("1 == 1.0"), Ok(Value::Bool(true)));
+    assert_eq!(value("false and 1"), Ok(Value::Bool(false)));
+    assert_eq!(
+        value("1.0 / 0").map(|v| v.to_string()),
+        Ok("inf".to_string())
+    );
+}
+
+#[test]
+fn test_eval_reports_overflow_precisely() {
+    assert_eq!(value("9223372036854775807 + 1"), Err(EvalError::Overflow));
+    assert_eq!(value("-9223372036854775807 - 1"), Ok(Value::Int(i64::MIN)));
+    assert_eq!(
+        value("(-9223372036854775807 - 1) / -1"),
+        Err(EvalError::Overflow)
+    );
+    assert_eq!(
+        value("-(-9223372036854775807 - 1)"),
+        Err(EvalError::Overflow)
+    );
+    assert_eq!(value("4611686018427387904 * 2"), Err(EvalError::Overflow));
+    assert_eq!(value("1 << 64"), Err(EvalError::Overflow));
+    assert_eq!(value("1 >> -1"), Err(EvalError::Overflow));
+    assert_eq!(value("1 / (2 - 2)"), Err(EvalError::DivisionByZero));
+}
+
+#[test]
+fn test_eval_rejects_non_constants_and_mismatched_operands() {
+    assert_eq!(value("f(1)"), Err(EvalError::NotConstant));
+    assert_eq!(value("[1, 2]"), Err(EvalError::NotConstant));
+    assert_eq!(value("y + 1"), Err(EvalError::NotConstant));
+
+    let error = value("1 + true").unwrap_err();
+    assert_eq!(error.to_string(), "cannot apply '+' to int and bool");
+    assert_eq!(
+        value("-'a'").unwrap_err().to_string(),
+        "cannot apply '-' to string"
+    );
+
+    let lookup = |name: &str| (name == "N").then_some(Value::Int(20));
+    assert_eq!(eval_with(&expr("N * 2 + 2"), &lookup), Ok(Value::Int(42)));
+}
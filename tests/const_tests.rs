@@ -0,0 +1,129 @@
+mod common;
+
+use common::{messages, parse};
+use grit::codegen::CodeGenerator;
+use grit::diff::AstDiff;
+use grit::lexer::{Span, TokenType, Tokenizer};
+use grit::parser::{Expr, Parser, Statement};
+use grit::semantic::{SymbolDump, SymbolKind, SymbolTable, Type};
+
+#[test]
+fn test_parse_const_def() {
+    let tokens = Tokenizer::new("const").tokenize().unwrap();
+    assert_eq!(tokens[0].token_type, TokenType::Const);
+
+    let program = parse("const PI = 3.5\nx = PI");
+    assert_eq!(
+        program.statements[0],
+        Statement::ConstDef {
+            name: "PI".to_string(),
+            value: Expr::Float {
+                value: 3.5,
+                span: Span::new(1, 12, 1, 14)
+            },
+            span: Span::new(1, 1, 1, 8),
+        }
+    );
+    assert_eq!(program.statements[0].to_string(), "const PI = 3.5");
+}
+
+#[test]
+fn test_parse_const_requires_equals() {
+    let mut parser = Parser::new(Tokenizer::new("const PI 3").tokenize().unwrap());
+    assert!(parser.parse().is_err());
+}
+
+#[test]
+fn test_const_generates_module_level_const() {
+    let code = CodeGenerator::generate_program(&parse(
+        "x = 1\nconst PI = 3.5\nconst MAX = 10 * 2\nconst NAME = 'grit'\nconst DEBUG = false\nprint('%d', MAX)",
+    ));
+    assert!(code.starts_with(
+        "const PI: f64 = 3.5;\nconst MAX: i64 = 10 * 2;\nconst NAME: &str = \"grit\";\nconst DEBUG: bool = false;\n\n"
+    ));
+    let main = &code[code.find("fn main()").unwrap()..];
+    assert!(!main.contains("const"));
+    assert!(main.contains("println!(\"{}\", MAX);"));
+}
+
+#[test]
+fn test_constants_are_typed_everywhere() {
+    let program = parse("const RATE = 2.5\nconst DOUBLE = RATE * 2\nfn f() {\n  typeof(DOUBLE)\n}");
+    let symbols = SymbolTable::from_program(&program);
+    assert_eq!(symbols.constant("DOUBLE").unwrap().ty, Type::Float);
+    assert_eq!(symbols.consts().len(), 2);
+
+    let code = CodeGenerator::generate_program(&program);
+    assert!(code.contains("    \"float\".to_string()\n"));
+}
+
+#[test]
+fn test_valid_constants_have_no_diagnostics() {
+    assert!(messages("const A = 1\nconst B = -(A + 2) << 1\nconst C = 'c'\nx = A + B").is_empty());
+}
+
+#[test]
+fn test_const_errors() {
+    assert_eq!(
+        messages("const A = 1\nconst A = 2"),
+        vec!["constant 'A' is already declared"]
+    );
+    assert_eq!(
+        messages("x = 1\nconst A = x + 1"),
+        vec!["constant 'A' must be computed from literals and other constants"]
+    );
+    assert_eq!(
+        messages("const A = 'a' + 'b'"),
+        vec!["constant 'A' must be computed from literals and other constants"]
+    );
+    assert_eq!(
+        messages("const A = 1 + true"),
+        vec![
+            "operator '+' expects numbers, found bool",
+            "constant 'A' must be an int, float, string, or bool, found unknown"
+        ]
+    );
+    assert_eq!(
+        messages("fn f() {\n  const A = 1\n}"),
+        vec!["constant 'A' must be declared at the top level"]
+    );
+}
+
+#[test]
+fn test_const_arithmetic_errors() {
+    assert_eq!(
+        messages("const MAX = 9223372036854775807\nconst OVER = MAX + 1\nconst NEXT = OVER * 2"),
+        vec!["constant 'OVER' cannot be computed: arithmetic overflow"]
+    );
+    assert_eq!(
+        messages("const ZERO = 2 - 2\nconst RATIO = 10 / ZERO\nconst HALF = 1.0 / 0"),
+        vec!["constant 'RATIO' cannot be computed: division by zero"]
+    );
+    assert_eq!(
+        messages("const BIG = 1 << 64"),
+        vec!["constant 'BIG' cannot be computed: arithmetic overflow"]
+    );
+}
+
+#[test]
+fn test_assigning_to_constant_is_an_error() {
+    assert_eq!(
+        messages("const A = 1\nfn f() {\n  A = 2\n}\nA, b = 3, 4"),
+        vec![
+            "cannot assign to constant 'A'",
+            "cannot assign to constant 'A'"
+        ]
+    );
+}
+
+#[test]
+fn test_constants_in_symbol_dump_and_diff() {
+    let dump = SymbolDump::from_program(&parse("const LIMIT = 5"));
+    assert_eq!(dump.entries[0].kind, SymbolKind::Const);
+    assert_eq!(dump.entries[0].ty, Some(Type::Int));
+
+    let diff = AstDiff::between(&parse("const LIMIT = 5"), &parse("\nconst LIMIT = 6"));
+    assert_eq!(diff.changes.len(), 1);
+    assert_eq!(diff.changes[0].item, "const LIMIT = 6");
+    assert_eq!(diff.changes[0].details, vec!["value: 5 -> 6"]);
+}
@@ -1,5 +1,5 @@
 use grit::codegen::CodeGenerator;
-use grit::lexer::Tokenizer;
+use grit::lexer::{Span, Tokenizer};
 use grit::parser::{BinaryOperator, Expr, Parser, Program, Statement};
 
 // Lexer tests for control flow tokens
@@ -7,7 +7,7 @@ use grit::parser::{BinaryOperator, Expr, Parser, Program, Statement};
 #[test]
 fn test_tokenize_if_keyword() {
     let mut tokenizer = Tokenizer::new("if");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     assert_eq!(tokens.len(), 2); // if, EOF
     assert_eq!(tokens[0].token_type, grit::lexer::TokenType::If);
 }
@@ -15,7 +15,7 @@ fn test_tokenize_if_keyword() {
 #[test]
 fn test_tokenize_elif_keyword() {
     let mut tokenizer = Tokenizer::new("elif");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     assert_eq!(tokens.len(), 2); // elif, EOF
     assert_eq!(tokens[0].token_type, grit::lexer::TokenType::Elif);
 }
@@ -23,7 +23,7 @@ fn test_tokenize_elif_keyword() {
 #[test]
 fn test_tokenize_else_keyword() {
     let mut tokenizer = Tokenizer::new("else");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     assert_eq!(tokens.len(), 2); // else, EOF
     assert_eq!(tokens[0].token_type, grit::lexer::TokenType::Else);
 }
@@ -31,7 +31,7 @@ fn test_tokenize_else_keyword() {
 #[test]
 fn test_tokenize_while_keyword() {
     let mut tokenizer = Tokenizer::new("while");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     assert_eq!(tokens.len(), 2); // while, EOF
     assert_eq!(tokens[0].token_type, grit::lexer::TokenType::While);
 }
@@ -39,7 +39,7 @@ fn test_tokenize_while_keyword() {
 #[test]
 fn test_tokenize_equal_equal() {
     let mut tokenizer = Tokenizer::new("==");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     assert_eq!(tokens.len(), 2); // ==, EOF
     assert_eq!(tokens[0].token_type, grit::lexer::TokenType::EqualEqual);
 }
@@ -47,7 +47,7 @@ fn test_tokenize_equal_equal() {
 #[test]
 fn test_tokenize_not_equal() {
     let mut tokenizer = Tokenizer::new("!=");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     assert_eq!(tokens.len(), 2); // !=, EOF
     assert_eq!(tokens[0].token_type, grit::lexer::TokenType::NotEqual);
 }
@@ -55,7 +55,7 @@ fn test_tokenize_not_equal() {
 #[test]
 fn test_tokenize_less_than() {
     let mut tokenizer = Tokenizer::new("<");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     assert_eq!(tokens.len(), 2); // <, EOF
     assert_eq!(tokens[0].token_type, grit::lexer::TokenType::LessThan);
 }
@@ -63,7 +63,7 @@ fn test_tokenize_less_than() {
 #[test]
 fn test_tokenize_less_than_or_equal() {
     let mut tokenizer = Tokenizer::new("<=");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     assert_eq!(tokens.len(), 2); // <=, EOF
     assert_eq!(
         tokens[0].token_type,
@@ -74,7 +74,7 @@ fn test_tokenize_less_than_or_equal() {
 #[test]
 fn test_tokenize_greater_than() {
     let mut tokenizer = Tokenizer::new(">");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     assert_eq!(tokens.len(), 2); // >, EOF
     assert_eq!(tokens[0].token_type, grit::lexer::TokenType::GreaterThan);
 }
@@ -82,7 +82,7 @@ fn test_tokenize_greater_than() {
 #[test]
 fn test_tokenize_greater_than_or_equal() {
     let mut tokenizer = Tokenizer::new(">=");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     assert_eq!(tokens.len(), 2); // >=, EOF
     assert_eq!(
         tokens[0].token_type,
@@ -96,7 +96,7 @@ fn test_tokenize_greater_than_or_equal() {
 fn test_parse_simple_if() {
     let input = "if a < b { print('a < b') }";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
@@ -107,6 +107,7 @@ fn test_parse_simple_if() {
             then_branch,
             elif_branches,
             else_branch,
+            ..
         } => {
             assert!(matches!(condition, Expr::BinaryOp { .. }));
             assert_eq!(then_branch.len(), 1);
@@ -121,7 +122,7 @@ fn test_parse_simple_if() {
 fn test_parse_if_else() {
     let input = "if a < b { print('less') } else { print('not less') }";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
@@ -132,6 +133,7 @@ fn test_parse_if_else() {
             then_branch,
             elif_branches,
             else_branch,
+            ..
         } => {
             assert_eq!(then_branch.len(), 1);
             assert_eq!(elif_branches.len(), 0);
@@ -147,7 +149,7 @@ fn test_parse_if_elif_else() {
     let input =
         "if a < b { print('less') } elif a > b { print('greater') } else { print('equal') }";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
@@ -158,6 +160,7 @@ fn test_parse_if_elif_else() {
             then_branch,
             elif_branches,
             else_branch,
+            ..
         } => {
             assert_eq!(then_branch.len(), 1);
             assert_eq!(elif_branches.len(), 1);
@@ -167,19 +170,56 @@ fn test_parse_if_elif_else() {
     }
 }
 
+#[test]
+fn test_parse_else_if_as_elif() {
+    let input = "if a < b {\n  x = 1\n} else if a > b {\n  x = 2\n}\nelse if a == b {\n  x = 3\n} else {\n  x = 4\n}";
+    let mut tokenizer = Tokenizer::new(input);
+    let tokens = tokenizer.tokenize().unwrap();
+    let mut parser = Parser::new(tokens);
+    let program = parser.parse().unwrap();
+
+    let elif_input = "if a < b {\n  x = 1\n} elif a > b {\n  x = 2\n}\nelif a == b {\n  x = 3\n} else {\n  x = 4\n}";
+    let mut tokenizer = Tokenizer::new(elif_input);
+    let tokens = tokenizer.tokenize().unwrap();
+    let mut parser = Parser::new(tokens);
+    let elif_program = parser.parse().unwrap();
+    assert_eq!(
+        program.statements[0].without_spans(),
+        elif_program.statements[0].without_spans()
+    );
+
+    match &program.statements[0] {
+        Statement::If {
+            elif_branches,
+            else_branch,
+            ..
+        } => {
+            assert_eq!(elif_branches.len(), 2);
+            assert!(else_branch.is_some());
+        }
+        _ => panic!("Expected if statement"),
+    }
+}
+
 #[test]
 fn test_parse_while_loop() {
     let input = "while x < 10 { x = x + 1 }";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
     assert_eq!(program.statements.len(), 1);
     match &program.statements[0] {
-        Statement::While { condition, body } => {
+        Statement::While {
+            condition,
+            body,
+            label,
+            ..
+        } => {
             assert!(matches!(condition, Expr::BinaryOp { .. }));
             assert_eq!(body.len(), 1);
+            assert_eq!(label, &None);
         }
         _ => panic!("Expected while statement"),
     }
@@ -192,7 +232,7 @@ fn test_parse_comparison_operators() {
     for op in operators {
         let input = format!("a {} b", op);
         let mut tokenizer = Tokenizer::new(&input);
-        let tokens = tokenizer.tokenize();
+        let tokens = tokenizer.tokenize().unwrap();
         let mut parser = Parser::new(tokens);
         let program = parser.parse().unwrap();
 
@@ -211,16 +251,28 @@ fn test_generate_simple_if() {
     let program = Program {
         statements: vec![Statement::If {
             condition: Expr::BinaryOp {
-                left: Box::new(Expr::Identifier("a".to_string())),
+                left: Box::new(Expr::Identifier {
+                    name: "a".into(),
+                    span: Span::default(),
+                }),
                 op: BinaryOperator::LessThan,
-                right: Box::new(Expr::Identifier("b".to_string())),
+                right: Box::new(Expr::Identifier {
+                    name: "b".into(),
+                    span: Span::default(),
+                }),
+                span: Span::default(),
             },
             then_branch: vec![Statement::Expression(Expr::FunctionCall {
                 name: "print".to_string(),
-                args: vec![Expr::String("a < b".to_string())],
+                args: vec![Expr::String {
+                    value: "a < b".to_string(),
+                    span: Span::default(),
+                }],
+                span: Span::default(),
             })],
             elif_branches: vec![],
             else_branch: None,
+            span: Span::default(),
         }],
     };
 
@@ -234,19 +286,35 @@ fn test_generate_if_else() {
     let program = Program {
         statements: vec![Statement::If {
             condition: Expr::BinaryOp {
-                left: Box::new(Expr::Identifier("a".to_string())),
+                left: Box::new(Expr::Identifier {
+                    name: "a".into(),
+                    span: Span::default(),
+                }),
                 op: BinaryOperator::EqualEqual,
-                right: Box::new(Expr::Identifier("b".to_string())),
+                right: Box::new(Expr::Identifier {
+                    name: "b".into(),
+                    span: Span::default(),
+                }),
+                span: Span::default(),
             },
             then_branch: vec![Statement::Expression(Expr::FunctionCall {
                 name: "print".to_string(),
-                args: vec![Expr::String("equal".to_string())],
+                args: vec![Expr::String {
+                    value: "equal".to_string(),
+                    span: Span::default(),
+                }],
+                span: Span::default(),
             })],
             elif_branches: vec![],
             else_branch: Some(vec![Statement::Expression(Expr::FunctionCall {
                 name: "print".to_string(),
-                args: vec![Expr::String("not equal".to_string())],
+                args: vec![Expr::String {
+                    value: "not equal".to_string(),
+                    span: Span::default(),
+                }],
+                span: Span::default(),
             })]),
+            span: Span::default(),
         }],
     };
 
@@ -262,29 +330,56 @@ fn test_generate_if_elif_else() {
     let program = Program {
         statements: vec![Statement::If {
             condition: Expr::BinaryOp {
-                left: Box::new(Expr::Identifier("a".to_string())),
+                left: Box::new(Expr::Identifier {
+                    name: "a".into(),
+                    span: Span::default(),
+                }),
                 op: BinaryOperator::LessThan,
-                right: Box::new(Expr::Identifier("b".to_string())),
+                right: Box::new(Expr::Identifier {
+                    name: "b".into(),
+                    span: Span::default(),
+                }),
+                span: Span::default(),
             },
             then_branch: vec![Statement::Expression(Expr::FunctionCall {
                 name: "print".to_string(),
-                args: vec![Expr::String("less".to_string())],
+                args: vec![Expr::String {
+                    value: "less".to_string(),
+                    span: Span::default(),
+                }],
+                span: Span::default(),
             })],
             elif_branches: vec![(
                 Expr::BinaryOp {
-                    left: Box::new(Expr::Identifier("a".to_string())),
+                    left: Box::new(Expr::Identifier {
+                        name: "a".into(),
+                        span: Span::default(),
+                    }),
                     op: BinaryOperator::GreaterThan,
-                    right: Box::new(Expr::Identifier("b".to_string())),
+                    right: Box::new(Expr::Identifier {
+                        name: "b".into(),
+                        span: Span::default(),
+                    }),
+                    span: Span::default(),
                 },
                 vec![Statement::Expression(Expr::FunctionCall {
                     name: "print".to_string(),
-                    args: vec![Expr::String("greater".to_string())],
+                    args: vec![Expr::String {
+                        value: "greater".to_string(),
+                        span: Span::default(),
+                    }],
+                    span: Span::default(),
                 })],
             )],
             else_branch: Some(vec![Statement::Expression(Expr::FunctionCall {
                 name: "print".to_string(),
-                args: vec![Expr::String("equal".to_string())],
+                args: vec![Expr::String {
+                    value: "equal".to_string(),
+                    span: Span::default(),
+                }],
+                span: Span::default(),
             })]),
+            span: Span::default(),
         }],
     };
 
@@ -299,18 +394,37 @@ fn test_generate_while_loop() {
     let program = Program {
         statements: vec![Statement::While {
             condition: Expr::BinaryOp {
-                left: Box::new(Expr::Identifier("x".to_string())),
+                left: Box::new(Expr::Identifier {
+                    name: "x".into(),
+                    span: Span::default(),
+                }),
                 op: BinaryOperator::LessThan,
-                right: Box::new(Expr::Integer(10)),
+                right: Box::new(Expr::Integer {
+                    value: 10,
+                    span: Span::default(),
+                }),
+                span: Span::default(),
             },
             body: vec![Statement::Assignment {
                 name: "x".to_string(),
+                mutable: false,
+                ty: None,
                 value: Expr::BinaryOp {
-                    left: Box::new(Expr::Identifier("x".to_string())),
+                    left: Box::new(Expr::Identifier {
+                        name: "x".into(),
+                        span: Span::default(),
+                    }),
                     op: BinaryOperator::Add,
-                    right: Box::new(Expr::Integer(1)),
+                    right: Box::new(Expr::Integer {
+                        value: 1,
+                        span: Span::default(),
+                    }),
+                    span: Span::default(),
                 },
+                span: Span::default(),
             }],
+            label: None,
+            span: Span::default(),
         }],
     };
 
@@ -333,9 +447,16 @@ fn test_generate_all_comparison_operators() {
     for (op, symbol) in operators {
         let program = Program {
             statements: vec![Statement::Expression(Expr::BinaryOp {
-                left: Box::new(Expr::Identifier("a".to_string())),
+                left: Box::new(Expr::Identifier {
+                    name: "a".into(),
+                    span: Span::default(),
+                }),
                 op,
-                right: Box::new(Expr::Identifier("b".to_string())),
+                right: Box::new(Expr::Identifier {
+                    name: "b".into(),
+                    span: Span::default(),
+                }),
+                span: Span::default(),
             })],
         };
 
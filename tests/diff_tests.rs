@@ -0,0 +1,162 @@
+mod common;
+
+use common::parse;
+use grit::diff::{AstDiff, Change, ChangeKind};
+use std::fs;
+
+fn diff(old: &str, new: &str) -> AstDiff {
+    AstDiff::between(&parse(old), &parse(new))
+}
+
+fn change(kind: ChangeKind, item: &str, details: &[&str]) -> Change {
+    Change {
+        kind,
+        item: item.to_string(),
+        details: details.iter().map(|detail| detail.to_string()).collect(),
+    }
+}
+
+#[test]
+fn test_identical_programs_have_no_differences() {
+    let source = "fn add(a, b) {\n  a + b\n}\nx = add(1, 2)";
+    assert!(diff(source, source).is_empty());
+}
+
+#[test]
+fn test_positions_are_ignored() {
+    let old = "fn add(a, b) {\n  a + b\n}\nclass Point\nx = to_int(1)";
+    let new = "class Point\n\n\nfn add(a, b) {\n\n  a + b\n}\n\nx = to_int(1)";
+    assert!(diff(old, new).is_empty());
+}
+
+#[test]
+fn test_added_and_removed_declarations() {
+    let diff = diff(
+        "fn old() {\n  1\n}\nclass Shape",
+        "mixin Named\nfn Shape > area {\n  0\n}\nclass Shape",
+    );
+    assert_eq!(
+        diff.changes,
+        vec![
+            change(ChangeKind::Added, "mixin Named", &[]),
+            change(ChangeKind::Added, "fn Shape > area()", &[]),
+            change(ChangeKind::Removed, "fn old()", &[]),
+        ]
+    );
+}
+
+#[test]
+fn test_changed_function() {
+    let diff = diff(
+        "fn add(a, b) {\n  a + b\n}",
+        "fn add(a, b, c) {\n  total = a + b\n  total + c\n}",
+    );
+    assert_eq!(
+        diff.changes,
+        vec![change(
+            ChangeKind::Changed,
+            "fn add(a, b, c)",
+            &[
+                "params: (a, b) -> (a, b, c)",
+                "~ (a + b) -> total = (a + b)",
+                "+ (total + c)",
+            ],
+        )]
+    );
+}
+
+#[test]
+fn test_changed_class_mixins() {
+    let diff = diff(
+        "class Button",
+        "mixin Clickable\nclass Button with Clickable",
+    );
+    assert_eq!(
+        diff.changes[1],
+        change(
+            ChangeKind::Changed,
+            "class Button with Clickable",
+            &["mixins: (none) -> Clickable"],
+        )
+    );
+}
+
+#[test]
+fn test_top_level_statements_compared_in_order() {
+    let diff = diff(
+        "x = 1\nprint('%d', x)\nz = 0",
+        "x = 2\nprint('%d', x)\ny = 3\nz = 0",
+    );
+    assert_eq!(
+        diff.changes,
+        vec![
+            change(ChangeKind::Changed, "x = 2", &["was: x = 1"]),
+            change(ChangeKind::Added, "y = 3", &[]),
+        ]
+    );
+}
+
+#[test]
+fn test_nested_body_changes() {
+    let diff = diff(
+        "test 'loop' {\n  i = 0\n  while i < 3 {\n    i = i + 1\n  }\n}",
+        "test 'loop' {\n  i = 0\n  while i < 3 {\n    i = i + 2\n  }\n}",
+    );
+    assert_eq!(
+        diff.changes,
+        vec![change(
+            ChangeKind::Changed,
+            "test 'loop'",
+            &["~ while (i < 3) (body changed)"],
+        )]
+    );
+}
+
+#[test]
+fn test_diff_display() {
+    let diff = diff("fn f(a) {\n  a\n}\nx = 1", "fn f(b) {\n  a\n}");
+    assert_eq!(
+        diff.to_string(),
+        "~ fn f(b)\n    params: (a) -> (b)\n- x = 1\n"
+    );
+}
+
+#[test]
+fn test_run_diff() {
+    let dir = std::env::temp_dir();
+    let old = dir.join(format!("grit_diff_old_{}.grit", std::process::id()));
+    let new = dir.join(format!("grit_diff_new_{}.grit", std::process::id()));
+    fs::write(&old, "x = 1").unwrap();
+    fs::write(&new, "x = 1\ny = 2").unwrap();
+    let path = |path: &std::path::PathBuf| path.to_str().unwrap().to_string();
+
+    let mut output = Vec::new();
+    let args = vec![
+        "grit".to_string(),
+        "diff".to_string(),
+        path(&old),
+        path(&new),
+    ];
+    assert!(grit::run(&args, &mut output).is_ok());
+    assert_eq!(String::from_utf8(output).unwrap(), "+ y = 2\n");
+
+    let mut output = Vec::new();
+    let args = vec![
+        "grit".to_string(),
+        "diff".to_string(),
+        path(&old),
+        path(&old),
+    ];
+    assert!(grit::run(&args, &mut output).is_ok());
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "No structural differences\n"
+    );
+
+    let mut output = Vec::new();
+    let args = vec!["grit".to_string(), "diff".to_string(), path(&old)];
+    assert_eq!(grit::run(&args, &mut output), Err(1));
+
+    let _ = fs::remove_file(&old);
+    let _ = fs::remove_file(&new);
+}
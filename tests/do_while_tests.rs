@@ -0,0 +1,94 @@
+mod common;
+
+use common::{generate, messages, parse, try_parse};
+use grit::lexer::{TokenType, Tokenizer};
+use grit::parser::{Expr, ParseError, Statement};
+
+#[test]
+fn test_tokenize_do_keyword() {
+    let tokens = Tokenizer::new("do done").tokenize().unwrap();
+
+    assert_eq!(tokens[0].token_type, TokenType::Do);
+    assert_eq!(tokens[1].token_type, TokenType::Identifier("done".into()));
+}
+
+#[test]
+fn test_parse_do_while() {
+    let program = parse("do {\n  x = 1\n} while x < 3\ny = 2");
+
+    assert_eq!(program.statements.len(), 2);
+    match &program.statements[0] {
+        Statement::DoWhile {
+            body,
+            condition,
+            label,
+            ..
+        } => {
+            assert_eq!(body.len(), 1);
+            assert!(matches!(condition, Expr::BinaryOp { .. }));
+            assert_eq!(*label, None);
+        }
+        other => panic!("expected a do-while loop, got {:?}", other),
+    }
+    assert_eq!(program.statements[0].to_string(), "do ... while (x < 3)");
+}
+
+#[test]
+fn test_parse_labeled_do_while_with_condition_on_next_line() {
+    let program = parse("outer: do {\n  break outer\n}\nwhile true");
+
+    assert_eq!(program.statements.len(), 1);
+    assert_eq!(
+        program.statements[0].to_string(),
+        "outer: do ... while true"
+    );
+}
+
+#[test]
+fn test_do_without_while_is_error() {
+    assert!(matches!(
+        try_parse("do {\n  x = 1\n}\ny = 2"),
+        Err(ParseError::UnexpectedToken { expected, .. }) if expected == "'while' after do-loop body"
+    ));
+}
+
+#[test]
+fn test_generate_do_while() {
+    let code = generate("do {\n  print('%d', 1)\n} while 1 < 2");
+
+    assert!(code.contains(
+        "loop {\n        println!(\"{}\", 1);\n        if !(1 < 2) {\n            break;\n        }\n    }"
+    ));
+}
+
+#[test]
+fn test_generate_labeled_do_while() {
+    let code = generate("outer: do {\n  break outer\n} while true");
+
+    assert!(code.contains("'outer: loop {\n        break 'outer;\n"));
+}
+
+#[test]
+fn test_condition_sees_body_variables() {
+    let code = generate("do {\n  n = 'a'\n} while n == 'a'");
+
+    // `n` is known to be a string, so it is compared by content
+    assert!(code.contains("if !(&*n == \"a\")"));
+}
+
+#[test]
+fn test_break_in_do_while_is_valid() {
+    assert!(messages("do {\n  break\n} while true").is_empty());
+    assert!(messages("do {\n  while true {\n    continue\n  }\n} while true").is_empty());
+}
+
+#[test]
+fn test_continue_cannot_skip_do_while_condition() {
+    let message = "'continue' cannot target a do-while loop, whose condition it would skip";
+
+    assert_eq!(messages("do {\n  continue\n} while true"), vec![message]);
+    assert_eq!(
+        messages("outer: do {\n  while true {\n    continue outer\n  }\n} while true"),
+        vec![message]
+    );
+}
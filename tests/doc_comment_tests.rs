@@ -0,0 +1,100 @@
+mod common;
+
+use common::{generate, parse, try_parse};
+use grit::lexer::{TokenType, Tokenizer};
+use grit::parser::Statement;
+
+fn docs(stmt: &Statement) -> &[String] {
+    match stmt {
+        Statement::FunctionDef { docs, .. }
+        | Statement::ClassDef { docs, .. }
+        | Statement::MethodDef { docs, .. } => docs,
+        other => panic!("expected a definition, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_tokenize_comments() {
+    let types: Vec<TokenType> = Tokenizer::new("x = 1 # one\n##  Doubles x\n##\nfn")
+        .tokenize()
+        .unwrap()
+        .into_iter()
+        .map(|token| token.token_type)
+        .collect();
+
+    assert_eq!(
+        types,
+        vec![
+            TokenType::Identifier("x".into()),
+            TokenType::Equals,
+            TokenType::Integer(1),
+            TokenType::Newline,
+            TokenType::DocComment(" Doubles x".to_string()),
+            TokenType::Newline,
+            TokenType::DocComment(String::new()),
+            TokenType::Newline,
+            TokenType::Fn,
+            TokenType::Eof,
+        ]
+    );
+}
+
+#[test]
+fn test_parse_documented_definitions() {
+    let program = try_parse(
+        "## A point\n## in the plane\nclass Point { x, y }\n\n\
+         ## Builds a point\nfn Point > new(x, y) {\n  self.x = x\n  self.y = y\n}\n\
+         ## Adds two numbers\n\nfn add(a, b) {\n  a + b\n}\n\
+         fn plain() {\n  1\n}",
+    )
+    .unwrap();
+
+    assert_eq!(docs(&program.statements[0]), ["A point", "in the plane"]);
+    assert_eq!(docs(&program.statements[1]), ["Builds a point"]);
+    assert_eq!(docs(&program.statements[2]), ["Adds two numbers"]);
+    assert!(docs(&program.statements[3]).is_empty());
+}
+
+#[test]
+fn test_plain_comments_are_ignored() {
+    let program = parse("# setup\nx = 1 # the answer\n# done");
+
+    assert_eq!(program.statements.len(), 1);
+    assert_eq!(program.statements[0].to_string(), "x = 1");
+    assert_eq!(
+        parse("print('#%d', 1)").statements[0].to_string(),
+        "print('#%d', 1)"
+    );
+}
+
+#[test]
+fn test_doc_comments_elsewhere_are_plain_comments() {
+    assert_eq!(parse("## The answer\nx = 1").statements.len(), 1);
+    assert!(parse("## Nothing").statements.is_empty());
+    assert!(try_parse("fn f() {\n  ## Nothing\n}").is_ok());
+
+    let program = parse("x = 1 ## trailing\nfn f() {\n  1\n}");
+    assert!(docs(&program.statements[1]).is_empty());
+}
+
+#[test]
+fn test_generate_doc_comments() {
+    let code = generate(
+        "## A point\nclass Point { x }\n\
+         ## Builds a point\n##\n## x: where it is\nfn Point > new(x) {\n  self.x = x\n}\n\
+         ## Doubles n\nfn double(n) {\n  n * 2\n}",
+    );
+
+    assert!(code.contains("/// A point\n#[derive(Clone)]\nstruct Point {"));
+    assert!(code.contains(
+        "    /// Builds a point\n    ///\n    /// x: where it is\n    fn new(x: i64) -> Self {"
+    ));
+    assert!(code.contains("/// Doubles n\nfn double(n: i64) -> i64 {"));
+}
+
+#[test]
+fn test_without_spans_keeps_docs() {
+    let program = parse("## Doubles n\nfn double(n) {\n  n * 2\n}");
+
+    assert_eq!(docs(&program.statements[0].without_spans()), ["Doubles n"]);
+}
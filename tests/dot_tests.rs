@@ -0,0 +1,88 @@
+mod common;
+
+use common::parse;
+use std::fs;
+
+/// Returns the label of every node, in the order the nodes are written
+fn labels(dot: &str) -> Vec<&str> {
+    dot.lines()
+        .filter_map(|line| line.split_once("[label=\"")?.1.split_once("\", shape="))
+        .map(|(label, _)| label)
+        .collect()
+}
+
+#[test]
+fn test_dot_draws_nodes_and_edges_in_source_order() {
+    let dot = parse("x = 1 + f(2)\nfn f(n) {\n  return n\n}").to_dot();
+
+    assert!(dot.starts_with("digraph AST {\n"));
+    assert!(dot.ends_with("}\n"));
+    assert_eq!(
+        labels(&dot),
+        vec!["program", "x =", "+", "1", "f()", "2", "fn f(n)", "return", "n"]
+    );
+    assert!(dot.contains("  n0 -> n1;\n"));
+    assert!(dot.contains("  n2 -> n4;\n"));
+    assert!(dot.contains("  n0 -> n6;\n"));
+    assert!(dot.contains("  n1 [label=\"x =\", shape=box];\n"));
+    assert!(dot.contains("  n2 [label=\"+\", shape=ellipse];\n"));
+}
+
+#[test]
+fn test_dot_separates_branches_and_escapes_labels() {
+    let dot = parse(
+        "if a {\n  print('say \"hi\"')\n} elif b {\n  c = 1\n} else {\n  c = 2\n}\n\
+         match c {\n  1 => d = 1\n  _ => d = 2\n}",
+    )
+    .to_dot();
+
+    assert_eq!(
+        labels(&dot),
+        vec![
+            "program",
+            "if",
+            "a",
+            "then",
+            "print()",
+            "'say \\\"hi\\\"'",
+            "elif",
+            "b",
+            "c =",
+            "1",
+            "else",
+            "c =",
+            "2",
+            "match",
+            "c",
+            "=>",
+            "1",
+            "d =",
+            "1",
+            "=>",
+            "_",
+            "d =",
+            "2",
+        ]
+    );
+    assert!(dot.contains("[label=\"_\", shape=diamond]"));
+}
+
+#[test]
+fn test_run_dot() {
+    let path = std::env::temp_dir().join(format!("grit_dot_{}.grit", std::process::id()));
+    fs::write(&path, "x = 1").unwrap();
+    let file = path.to_str().unwrap().to_string();
+
+    let mut output = Vec::new();
+    let args = vec!["grit".to_string(), "--dot".to_string(), file];
+    assert!(grit::run(&args, &mut output).is_ok());
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .contains("[label=\"x =\", shape=box];"));
+
+    let mut output = Vec::new();
+    let args = vec!["grit".to_string(), "--dot".to_string()];
+    assert_eq!(grit::run(&args, &mut output), Err(1));
+
+    let _ = fs::remove_file(&path);
+}
@@ -0,0 +1,195 @@
+mod common;
+
+use common::parse;
+use grit::lexer::Span;
+use grit::semantic::{SymbolDump, SymbolEntry, SymbolKind, SymbolTable, Type};
+use std::fs;
+
+fn dump(input: &str) -> SymbolDump {
+    SymbolDump::from_program(&parse(input))
+}
+
+const SOURCE: &str = "class Point\nfn Point > new(x) {\n  self.x = x\n  self.count = 0\n}\nfn add(a, b) {\n  total = a\n  1.5\n}\np = Point.new(1)\nname = 'grit'";
+
+#[test]
+fn test_symbol_table_records_definition_spans() {
+    let symbols = SymbolTable::from_program(&parse(SOURCE));
+    assert_eq!(symbols.class("Point").unwrap().span, Span::new(1, 1, 1, 11));
+    assert_eq!(
+        symbols.class("Point").unwrap().method("new").unwrap().span,
+        Span::new(2, 1, 2, 14)
+    );
+    assert_eq!(symbols.function("add").unwrap().span, Span::new(6, 1, 6, 6));
+}
+
+#[test]
+fn test_implied_class_is_located_at_its_first_method() {
+    let symbols = SymbolTable::from_program(&parse("x = 1\nfn Shape > area {\n  0\n}"));
+    assert_eq!(symbols.class("Shape").unwrap().span.start_line, 2);
+}
+
+#[test]
+fn test_dump_lists_declarations_in_source_order() {
+    let dump = dump(SOURCE);
+    let listed: Vec<(SymbolKind, String)> = dump
+        .entries
+        .iter()
+        .map(|entry| (entry.kind, entry.display_name()))
+        .collect();
+    assert_eq!(
+        listed,
+        vec![
+            (SymbolKind::Class, "Point".to_string()),
+            (SymbolKind::Method, "Point > new(x)".to_string()),
+            (SymbolKind::Field, "Point.x".to_string()),
+            (SymbolKind::Field, "Point.count".to_string()),
+            (SymbolKind::Function, "add(a, b)".to_string()),
+            (SymbolKind::Variable, "p".to_string()),
+            (SymbolKind::Variable, "name".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_dump_infers_types() {
+    let dump = dump(SOURCE);
+    let types: Vec<Option<Type>> = dump.entries.iter().map(|entry| entry.ty.clone()).collect();
+    assert_eq!(
+        types,
+        vec![
+            Some(Type::Class("Point".to_string())),
+            Some(Type::Class("Point".to_string())),
+            Some(Type::Unknown),
+            Some(Type::Int),
+            Some(Type::Float),
+            Some(Type::Class("Point".to_string())),
+            Some(Type::String),
+        ]
+    );
+}
+
+#[test]
+fn test_dump_unifies_reassigned_globals() {
+    let dump = dump("x = 1\nx = 2.5\ny = 1\ny = 'a'");
+    assert_eq!(dump.entries.len(), 2);
+    assert_eq!(dump.entries[0].ty, Some(Type::Float));
+    assert_eq!(dump.entries[0].span.start_line, 1);
+    assert_eq!(dump.entries[1].ty, Some(Type::Unknown));
+}
+
+#[test]
+fn test_dump_mixins_have_no_type() {
+    let dump = dump("mixin Named\nfn Named > name {\n  'n'\n}");
+    assert_eq!(dump.entries[0].kind, SymbolKind::Mixin);
+    assert_eq!(dump.entries[0].ty, None);
+    assert_eq!(dump.entries[1].display_name(), "Named > name()");
+    assert_eq!(dump.entries[1].ty, Some(Type::String));
+}
+
+#[test]
+fn test_dump_table() {
+    let table = dump("fn add(a, b) {\n  a\n}\ncount = 10").to_table();
+    assert_eq!(
+        table,
+        "KIND      NAME       TYPE     LOCATION\n\
+         function  add(a, b)  unknown  1:1\n\
+         variable  count      int      4:1\n"
+    );
+}
+
+#[test]
+fn test_dump_json() {
+    let json = dump("fn add(a, b) {\n  a\n}\nsay = 'hi \"you\"'").to_json();
+    assert_eq!(
+        json,
+        "[\n  {\"kind\": \"function\", \"name\": \"add\", \"params\": [\"a\", \"b\"], \"type\": \"unknown\", \"line\": 1, \"column\": 1},\n  \
+         {\"kind\": \"variable\", \"name\": \"say\", \"type\": \"string\", \"line\": 4, \"column\": 1}\n]\n"
+    );
+    assert_eq!(dump("").to_json(), "[]\n");
+}
+
+#[test]
+fn test_run_dump_symbols() {
+    let path = std::env::temp_dir().join(format!("grit_dump_{}.grit", std::process::id()));
+    fs::write(&path, "x = 1").unwrap();
+    let file = path.to_str().unwrap().to_string();
+
+    let mut output = Vec::new();
+    let args = vec![
+        "grit".to_string(),
+        "--dump-symbols".to_string(),
+        file.clone(),
+    ];
+    assert!(grit::run(&args, &mut output).is_ok());
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .contains("variable  x     int   1:1"));
+
+    let mut output = Vec::new();
+    let args = vec!["grit".to_string(), "--dump-symbols=json".to_string(), file];
+    assert!(grit::run(&args, &mut output).is_ok());
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .contains("\"name\": \"x\""));
+
+    let mut output = Vec::new();
+    let args = vec!["grit".to_string(), "--dump-symbols".to_string()];
+    assert_eq!(grit::run(&args, &mut output), Err(1));
+
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn test_dump_tells_globals_from_variables() {
+    let dump = dump("global count = 0\nx = 1\nfn bump() {\n  count = count + 1\n}");
+    let kinds: Vec<(SymbolKind, &str)> = dump
+        .entries
+        .iter()
+        .map(|entry| (entry.kind, entry.name.as_str()))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (SymbolKind::Global, "count"),
+            (SymbolKind::Variable, "x"),
+            (SymbolKind::Function, "bump"),
+        ]
+    );
+}
+
+#[test]
+fn test_dump_lists_fields_on_their_defining_class() {
+    let dump = dump(
+        "class Animal\nclass Dog < Animal\nfn Dog > new(name: string) {\n  self.name = name\n  self.tricks = 0\n}\n\
+         fn Animal > new(name: string) {\n  self.name = name\n}",
+    );
+    let fields: Vec<String> = dump
+        .entries
+        .iter()
+        .filter(|entry| entry.kind == SymbolKind::Field)
+        .map(SymbolEntry::display_name)
+        .collect();
+    assert_eq!(fields, vec!["Dog.tricks", "Animal.name"]);
+}
+
+#[test]
+fn test_dump_method_types_are_inferred_returns() {
+    let dump = dump(
+        "class Dog {\n  name: string\n}\nfn Dog > label() {\n  name\n}\nfn Dog > speak() {\n  print('%s', name)\n}\n\
+         fn greet(who) {\n  'hi ' + who\n}\nx = greet('Bob')",
+    );
+    let types: Vec<(String, Option<Type>)> = dump
+        .entries
+        .iter()
+        .filter(|entry| matches!(entry.kind, SymbolKind::Method | SymbolKind::Function))
+        .map(|entry| (entry.display_name(), entry.ty.clone()))
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            ("Dog > label()".to_string(), Some(Type::String)),
+            ("Dog > speak()".to_string(), None),
+            ("greet(who)".to_string(), Some(Type::String)),
+        ]
+    );
+}
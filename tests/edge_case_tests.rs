@@ -7,19 +7,19 @@ fn test_multiple_next_token_after_eof() {
     let mut tokenizer = Tokenizer::new("42");
 
     // First call gets the integer
-    let token1 = tokenizer.next_token();
+    let token1 = tokenizer.next_token().unwrap();
     assert_eq!(token1.token_type, TokenType::Integer(42));
 
     // Second call gets EOF
-    let token2 = tokenizer.next_token();
+    let token2 = tokenizer.next_token().unwrap();
     assert_eq!(token2.token_type, TokenType::Eof);
 
     // Third call still gets EOF (this exercises the None branch in advance)
-    let token3 = tokenizer.next_token();
+    let token3 = tokenizer.next_token().unwrap();
     assert_eq!(token3.token_type, TokenType::Eof);
 
     // Fourth call for good measure
-    let token4 = tokenizer.next_token();
+    let token4 = tokenizer.next_token().unwrap();
     assert_eq!(token4.token_type, TokenType::Eof);
 }
 
@@ -28,13 +28,13 @@ fn test_multiple_next_token_after_eof() {
 fn test_single_char_then_multiple_eofs() {
     let mut tokenizer = Tokenizer::new("+");
 
-    let token1 = tokenizer.next_token();
+    let token1 = tokenizer.next_token().unwrap();
     assert_eq!(token1.token_type, TokenType::Plus);
 
-    let token2 = tokenizer.next_token();
+    let token2 = tokenizer.next_token().unwrap();
     assert_eq!(token2.token_type, TokenType::Eof);
 
-    let token3 = tokenizer.next_token();
+    let token3 = tokenizer.next_token().unwrap();
     assert_eq!(token3.token_type, TokenType::Eof);
 }
 
@@ -43,32 +43,32 @@ fn test_single_char_then_multiple_eofs() {
 fn test_all_operators_individually() {
     // Test Plus operator
     let mut tokenizer = Tokenizer::new("+");
-    let token = tokenizer.next_token();
+    let token = tokenizer.next_token().unwrap();
     assert_eq!(token.token_type, TokenType::Plus);
 
     // Test Minus operator
     let mut tokenizer = Tokenizer::new("-");
-    let token = tokenizer.next_token();
+    let token = tokenizer.next_token().unwrap();
     assert_eq!(token.token_type, TokenType::Minus);
 
     // Test Multiply operator
     let mut tokenizer = Tokenizer::new("*");
-    let token = tokenizer.next_token();
+    let token = tokenizer.next_token().unwrap();
     assert_eq!(token.token_type, TokenType::Multiply);
 
     // Test Divide operator
     let mut tokenizer = Tokenizer::new("/");
-    let token = tokenizer.next_token();
+    let token = tokenizer.next_token().unwrap();
     assert_eq!(token.token_type, TokenType::Divide);
 
     // Test LeftParen
     let mut tokenizer = Tokenizer::new("(");
-    let token = tokenizer.next_token();
+    let token = tokenizer.next_token().unwrap();
     assert_eq!(token.token_type, TokenType::LeftParen);
 
     // Test RightParen
     let mut tokenizer = Tokenizer::new(")");
-    let token = tokenizer.next_token();
+    let token = tokenizer.next_token().unwrap();
     assert_eq!(token.token_type, TokenType::RightParen);
 }
 
@@ -77,13 +77,13 @@ fn test_all_operators_individually() {
 fn test_operator_at_end() {
     let mut tokenizer = Tokenizer::new("123+");
 
-    let token1 = tokenizer.next_token();
+    let token1 = tokenizer.next_token().unwrap();
     assert_eq!(token1.token_type, TokenType::Integer(123));
 
-    let token2 = tokenizer.next_token();
+    let token2 = tokenizer.next_token().unwrap();
     assert_eq!(token2.token_type, TokenType::Plus);
 
-    let token3 = tokenizer.next_token();
+    let token3 = tokenizer.next_token().unwrap();
     assert_eq!(token3.token_type, TokenType::Eof);
 }
 
@@ -92,14 +92,14 @@ fn test_operator_at_end() {
 fn test_operators_with_trailing_whitespace() {
     let mut tokenizer = Tokenizer::new("+ ");
 
-    let token1 = tokenizer.next_token();
+    let token1 = tokenizer.next_token().unwrap();
     assert_eq!(token1.token_type, TokenType::Plus);
 
-    let token2 = tokenizer.next_token();
+    let token2 = tokenizer.next_token().unwrap();
     assert_eq!(token2.token_type, TokenType::Eof);
 
     // Call again to exercise advance() None branch
-    let token3 = tokenizer.next_token();
+    let token3 = tokenizer.next_token().unwrap();
     assert_eq!(token3.token_type, TokenType::Eof);
 }
 
@@ -108,13 +108,13 @@ fn test_operators_with_trailing_whitespace() {
 fn test_parentheses_return_path() {
     let mut tokenizer = Tokenizer::new("()");
 
-    let token1 = tokenizer.next_token();
+    let token1 = tokenizer.next_token().unwrap();
     assert_eq!(token1.token_type, TokenType::LeftParen);
 
-    let token2 = tokenizer.next_token();
+    let token2 = tokenizer.next_token().unwrap();
     assert_eq!(token2.token_type, TokenType::RightParen);
 
-    let token3 = tokenizer.next_token();
+    let token3 = tokenizer.next_token().unwrap();
     assert_eq!(token3.token_type, TokenType::Eof);
 }
 
@@ -124,16 +124,16 @@ fn test_exhaustive_advancement() {
     let mut tokenizer = Tokenizer::new("1");
 
     // Get the integer (advances once)
-    let token1 = tokenizer.next_token();
+    let token1 = tokenizer.next_token().unwrap();
     assert_eq!(token1.token_type, TokenType::Integer(1));
 
     // Get EOF (tries to advance but already at end)
-    let token2 = tokenizer.next_token();
+    let token2 = tokenizer.next_token().unwrap();
     assert_eq!(token2.token_type, TokenType::Eof);
 
     // Try multiple times to ensure the None branch is hit
     for _ in 0..5 {
-        let token = tokenizer.next_token();
+        let token = tokenizer.next_token().unwrap();
         assert_eq!(token.token_type, TokenType::Eof);
     }
 }
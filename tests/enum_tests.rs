@@ -0,0 +1,267 @@
+mod common;
+
+use common::{errors, generate, parse, try_parse, warnings};
+use grit::diff::AstDiff;
+use grit::lexer::Span;
+use grit::parser::{Expr, ParseError, Pattern, Statement};
+use grit::semantic::{SymbolDump, SymbolKind, SymbolTable, Type, TypeEnv};
+
+const COLOR: &str = "enum Color { Red, Green, Blue }\n";
+
+#[test]
+fn test_parse_enum() {
+    let program = parse(COLOR);
+
+    match &program.statements[0] {
+        Statement::EnumDef {
+            name,
+            variants,
+            span,
+        } => {
+            assert_eq!(name, "Color");
+            assert_eq!(variants, &["Red", "Green", "Blue"]);
+            assert_eq!(*span, Span::new(1, 1, 1, 10));
+        }
+        other => panic!("expected an enum, got {:?}", other),
+    }
+    assert_eq!(
+        program.statements[0].to_string(),
+        "enum Color { Red, Green, Blue }"
+    );
+}
+
+#[test]
+fn test_parse_enum_variants_on_separate_lines() {
+    let program = parse("enum Color\n{\n  Red\n  Green,\n  Blue,\n}\nx = 1");
+
+    assert_eq!(program.statements.len(), 2);
+    assert_eq!(
+        program.statements[0].to_string(),
+        "enum Color { Red, Green, Blue }"
+    );
+}
+
+#[test]
+fn test_malformed_enums_are_errors() {
+    assert!(try_parse("enum Color").is_err());
+    assert!(try_parse("enum Color {}").is_err());
+    assert!(try_parse("enum Color { Red Green }").is_err());
+    assert!(try_parse("enum Color { 1 }").is_err());
+    assert!(matches!(
+        try_parse("enum Color { Red,"),
+        Err(ParseError::UnexpectedEof { .. })
+    ));
+}
+
+#[test]
+fn test_parse_match() {
+    let program = try_parse(
+        "match c {\n  Color.Red, Color.Blue => print('%s', c)\n  _ => {\n    x = 1\n  }\n}",
+    )
+    .unwrap();
+
+    match &program.statements[0] {
+        Statement::Match {
+            subject,
+            arms,
+            span,
+        } => {
+            assert_eq!(
+                *subject,
+                Expr::Identifier {
+                    name: "c".into(),
+                    span: Span::new(1, 7, 1, 7)
+                }
+            );
+            assert_eq!(*span, Span::new(1, 1, 1, 7));
+            assert_eq!(
+                arms[0].patterns,
+                vec![
+                    Pattern::Variant {
+                        enum_name: "Color".to_string(),
+                        variant: "Red".to_string(),
+                    },
+                    Pattern::Variant {
+                        enum_name: "Color".to_string(),
+                        variant: "Blue".to_string(),
+                    },
+                ]
+            );
+            assert_eq!(arms[1].patterns, vec![Pattern::Wildcard]);
+            assert_eq!(arms[1].body.len(), 1);
+        }
+        other => panic!("expected a match, got {:?}", other),
+    }
+    assert_eq!(program.statements[0].to_string(), "match c + 2 arm(s)");
+}
+
+#[test]
+fn test_parse_literal_patterns() {
+    let program = parse("match n {\n  1, 2 => x = 1\n  'a' => x = 2\n  true => x = 3\n}");
+
+    match &program.statements[0] {
+        Statement::Match { arms, .. } => {
+            let patterns: Vec<String> = arms
+                .iter()
+                .flat_map(|arm| &arm.patterns)
+                .map(Pattern::to_string)
+                .collect();
+            assert_eq!(patterns, vec!["1", "2", "'a'", "true"]);
+        }
+        other => panic!("expected a match, got {:?}", other),
+    }
+    assert!(try_parse("match n {\n  1 x = 1\n}").is_err());
+    assert!(try_parse("match n {\n  Color => x = 1\n}").is_err());
+    assert!(try_parse("match n {\n  1 => x = 1\n").is_err());
+}
+
+#[test]
+fn test_symbols_record_enums() {
+    let symbols = SymbolTable::from_program(&parse(COLOR));
+
+    assert!(symbols.is_enum("Color"));
+    assert!(!symbols.is_class("Color"));
+    let color = symbols.enum_def("Color").unwrap();
+    assert!(color.has_variant("Green"));
+    assert!(!color.has_variant("Purple"));
+}
+
+#[test]
+fn test_variant_type_is_its_enum() {
+    let symbols = SymbolTable::from_program(&parse(COLOR));
+    let env = TypeEnv::new(&symbols);
+    let variant = match parse("Color.Red").statements.remove(0) {
+        Statement::Expression(expr) => expr,
+        other => panic!("expected an expression, got {:?}", other),
+    };
+
+    assert_eq!(env.infer(&variant), Type::Enum("Color".to_string()));
+    assert_eq!(env.infer(&variant).to_string(), "Color");
+}
+
+#[test]
+fn test_check_enum_declarations() {
+    assert_eq!(
+        errors("enum Color { Red, Green, Red }"),
+        vec!["variant 'Red' is declared more than once in enum 'Color'"]
+    );
+    assert_eq!(
+        errors("class Color\nenum Color { Red }"),
+        vec!["'Color' is declared as both a class and an enum"]
+    );
+    assert_eq!(
+        errors(&format!("{}fn Color > name() {{\n  1\n}}", COLOR)),
+        vec!["'Color' is an enum and cannot define methods"]
+    );
+}
+
+#[test]
+fn test_check_match_patterns() {
+    let source = format!(
+        "{}enum Size {{ Small }}\nc = Color.Red\n\
+         match c {{\n  Color.Purple => x = 1\n  Shade.Dark => x = 2\n  Size.Small => x = 3\n  1 => x = 4\n  _ => x = 5\n}}",
+        COLOR
+    );
+
+    assert_eq!(
+        errors(&source),
+        vec![
+            "enum 'Color' has no variant 'Purple'",
+            "unknown enum 'Shade'",
+            "pattern 'Size.Small' does not match Color",
+            "pattern 1 does not match Color",
+        ]
+    );
+    assert_eq!(
+        errors("n = 1\nmatch n {\n  'a' => x = 1\n}"),
+        vec!["pattern 'a' does not match int"]
+    );
+}
+
+#[test]
+fn test_check_match_coverage() {
+    let partial = format!(
+        "{}c = Color.Red\nmatch c {{\n  Color.Red => x = 1\n}}",
+        COLOR
+    );
+    assert_eq!(
+        warnings(&partial),
+        vec![
+            "match on enum 'Color' does not cover variant 'Green'",
+            "match on enum 'Color' does not cover variant 'Blue'",
+        ]
+    );
+
+    let wildcard = format!(
+        "{}c = Color.Red\nmatch c {{\n  Color.Red => x = 1\n  _ => x = 2\n}}",
+        COLOR
+    );
+    assert!(warnings(&wildcard).is_empty());
+}
+
+#[test]
+fn test_generate_enum() {
+    let code = generate(COLOR);
+
+    assert!(code.contains(
+        "#[derive(Debug, Clone, Copy, PartialEq)]\nenum Color {\n    Red,\n    Green,\n    Blue,\n}\n"
+    ));
+    assert!(code.contains("impl std::fmt::Display for Color {"));
+    assert!(code.contains("            Color::Green => \"Green\",\n"));
+}
+
+#[test]
+fn test_generate_variant_construction() {
+    let code = generate(&format!("{}c = Color.Red\nprint('%s', c)", COLOR));
+
+    assert!(code.contains("let c = Color::Red;"));
+    // A variable named like the enum is an ordinary receiver
+    let code = generate(&format!("{}Color = 1\nprint('%d', Color.abs())", COLOR));
+    assert!(code.contains("Color.abs()"));
+}
+
+#[test]
+fn test_generate_exhaustive_match() {
+    let code = generate(&format!(
+        "{}c = Color.Red\nmatch c {{\n  Color.Red => print('%s', 'r')\n  Color.Green, Color.Blue => {{\n    print('%s', c)\n  }}\n}}",
+        COLOR
+    ));
+
+    assert!(code.contains(
+        "match c {\n        Color::Red => {\n            println!(\"{}\", \"r\");\n        }\n\
+         \x20       Color::Green | Color::Blue => {\n            println!(\"{}\", c);\n        }\n    }"
+    ));
+}
+
+#[test]
+fn test_generate_match_adds_catch_all() {
+    let code = generate(&format!(
+        "{}c = Color.Red\nmatch c {{\n  Color.Red => x = 1\n}}",
+        COLOR
+    ));
+    assert!(code.contains("        _ => {}\n    }"));
+
+    let code = generate("s = 'a'\nmatch s {\n  'a' => x = 1\n  _ => x = 2\n}");
+    assert!(code.contains("match &*s {\n        \"a\" => {"));
+    assert_eq!(code.matches("_ =>").count(), 1);
+}
+
+#[test]
+fn test_dump_lists_enums() {
+    let dump = SymbolDump::from_program(&parse(COLOR));
+
+    assert_eq!(dump.entries[0].kind, SymbolKind::Enum);
+    assert_eq!(dump.entries[0].kind.as_str(), "enum");
+    assert_eq!(dump.entries[0].ty, Some(Type::Enum("Color".to_string())));
+}
+
+#[test]
+fn test_diff_reports_variant_changes() {
+    let old = parse("enum Color { Red, Green }");
+    let new = parse("enum Color { Red, Green, Blue }");
+
+    assert_eq!(
+        AstDiff::between(&old, &new).changes[0].details,
+        vec!["variants: Red, Green -> Red, Green, Blue"]
+    );
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use common::{check, generate};
+
+#[test]
+fn test_env_without_default_may_be_nil() {
+    let code = generate("home = env('HOME')");
+    assert!(code.contains("let home = std::env::var(\"HOME\").ok();"));
+
+    let code = generate("if env('HOME') == nil {\n  print('unset')\n}");
+    assert!(code.contains("if std::env::var(\"HOME\").ok().is_none() {"));
+}
+
+#[test]
+fn test_env_with_explicit_default() {
+    let code = generate("port = env('PORT', '8080')");
+    assert!(code
+        .contains("let port = std::env::var(\"PORT\").unwrap_or_else(|_| \"8080\".to_string());"));
+}
+
+#[test]
+fn test_env_with_variable_key() {
+    let code = generate("key = 'HOME'\nhome = env(key)");
+    assert!(code.contains("let home = std::env::var(key).ok();"));
+}
+
+#[test]
+fn test_env_result_is_a_string() {
+    assert!(check("home = env('HOME')\nprint('home: %s', home)").is_empty());
+
+    let diagnostics = check("home = env('HOME')\nprint('home: %d', home)");
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("type string"));
+}
+
+#[test]
+fn test_env_without_default_must_be_bound_before_use() {
+    let diagnostics = check("h = env('HOME')\npath = h + '/x'");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "operator '+' cannot use a value that may be nil, found string or nil; \
+         bind it with `if name = ...` or give it a default"
+    );
+    assert_eq!(check("path = '/x' + env('HOME')").len(), 1);
+
+    assert!(check("if h = env('HOME') {\n  path = h + '/x'\n}").is_empty());
+    assert!(check("h = env('HOME', '/')\npath = h + '/x'").is_empty());
+}
+
+#[test]
+fn test_env_argument_count_is_checked() {
+    let diagnostics = check("x = env()");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "env() expects 1 or 2 arguments, found 0"
+    );
+
+    assert_eq!(check("x = env('A', 'B', 'C')").len(), 1);
+}
+
+#[test]
+fn test_env_key_must_be_a_string() {
+    let diagnostics = check("x = env(42)");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "env() expects string arguments, found int"
+    );
+}
+
+#[test]
+fn test_unset_env_compares_equal_to_nil() {
+    let dir = std::env::temp_dir().join(format!("grit_env_nil_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("env.grit"),
+        "test 'env' {\n  assert(env('GRIT_UNSET_FOR_TEST') == nil)\n  assert(env('GRIT_UNSET_FOR_TEST', 'x') == 'x')\n  assert(env('PATH') != nil)\n}\n",
+    )
+    .unwrap();
+
+    let report = grit::testing::run_tests(std::slice::from_ref(&dir)).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(report.results.len(), 1, "{:?}", report.results);
+    assert_eq!(report.failed(), 0, "{:?}", report.results);
+}
@@ -1,45 +1,122 @@
-use grit::lexer::{Token, TokenType, Tokenizer};
+use grit::lexer::{LexError, Token, TokenType, Tokenizer};
 use grit::parser::{ParseError, Parser};
 
+/// Returns the lexer errors the `Error` tokens of a source stand for
+fn unexpected_characters(input: &str) -> Vec<LexError> {
+    Tokenizer::new(input)
+        .tokenize()
+        .unwrap()
+        .iter()
+        .filter_map(LexError::from_token)
+        .collect()
+}
+
 #[test]
-#[should_panic(expected = "Unexpected character")]
 fn test_unexpected_character() {
     let mut tokenizer = Tokenizer::new("1 + @");
-    tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
+
+    assert_eq!(tokens[2], Token::new(TokenType::Error('@'), 1, 5));
+    assert_eq!(tokens[3].token_type, TokenType::Eof);
+    assert_eq!(
+        LexError::from_token(&tokens[2]),
+        Some(LexError::UnexpectedCharacter {
+            ch: '@',
+            line: 1,
+            column: 5
+        })
+    );
+    assert_eq!(LexError::from_token(&tokens[0]), None);
+}
+
+#[test]
+fn test_unexpected_character_message() {
+    assert_eq!(
+        unexpected_characters("$")[0].to_string(),
+        "Unexpected character '$' at line 1, column 1"
+    );
+}
+
+#[test]
+fn test_every_unexpected_character_is_reported() {
+    let chars: Vec<char> = unexpected_characters("5 ? 3 ~ 20\n1 $ 2")
+        .into_iter()
+        .map(|err| match err {
+            LexError::UnexpectedCharacter { ch, .. } => ch,
+            other => panic!("Expected an unexpected character, got {:?}", other),
+        })
+        .collect();
+    assert_eq!(chars, vec!['?', '~', '$']);
+}
+
+#[test]
+fn test_next_token_error_then_continues() {
+    let mut tokenizer = Tokenizer::new("@ 1");
+    assert_eq!(
+        tokenizer.next_token().unwrap().token_type,
+        TokenType::Error('@')
+    );
+    assert_eq!(
+        tokenizer.next_token().unwrap().token_type,
+        TokenType::Integer(1)
+    );
 }
 
 #[test]
-#[should_panic(expected = "Unexpected character '$'")]
-fn test_unexpected_character_dollar() {
-    let mut tokenizer = Tokenizer::new("$");
-    tokenizer.tokenize();
+fn test_lex_error_through_lazy_parser() {
+    let mut parser = Parser::from_source(Tokenizer::new("x = 1\ny = @"));
+    assert_eq!(
+        parser.parse(),
+        Err(ParseError::Lex(LexError::UnexpectedCharacter {
+            ch: '@',
+            line: 2,
+            column: 5
+        }))
+    );
 }
 
 #[test]
-#[should_panic(expected = "Unexpected character '#'")]
-fn test_unexpected_character_hash() {
-    let mut tokenizer = Tokenizer::new("5 # 3");
-    tokenizer.tokenize();
+fn test_error_token_fails_parsing_pre_lexed_tokens() {
+    let tokens = Tokenizer::new("x = 1 ? 2").tokenize().unwrap();
+    assert!(matches!(
+        Parser::new(tokens).parse(),
+        Err(ParseError::Lex(LexError::UnexpectedCharacter {
+            ch: '?',
+            ..
+        }))
+    ));
 }
 
 #[test]
-#[should_panic(expected = "Unexpected character '&'")]
-fn test_unexpected_character_ampersand() {
-    let mut tokenizer = Tokenizer::new("10 & 20");
-    tokenizer.tokenize();
+fn test_lex_errors_in_compile_result() {
+    let result = grit::compile("print(1) ? 1\nx = @");
+    assert_eq!(result.lex_errors.len(), 2);
+    assert_eq!(
+        result.lex_errors[1].to_string(),
+        "Unexpected character '@' at line 2, column 5"
+    );
+    assert!(result
+        .tokens
+        .iter()
+        .any(|token| token.token_type == TokenType::Error('?')));
+    assert!(matches!(result.ast, Err(ParseError::Lex(_))));
+    assert!(!result.succeeded());
 }
 
 #[test]
-#[should_panic(expected = "Unexpected character '!'")]
-fn test_unexpected_character_exclamation() {
-    let mut tokenizer = Tokenizer::new("!");
-    tokenizer.tokenize();
+fn test_malformed_number_still_stops_compile() {
+    let result = grit::compile("x = 1_");
+    assert!(result.tokens.is_empty());
+    assert!(matches!(
+        result.lex_errors[..],
+        [LexError::MisplacedUnderscore { .. }]
+    ));
 }
 
 #[test]
 fn test_empty_input() {
     let mut tokenizer = Tokenizer::new("");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
 
     assert_eq!(tokens.len(), 1);
     assert_eq!(tokens[0].token_type, grit::lexer::TokenType::Eof);
@@ -48,7 +125,7 @@ fn test_empty_input() {
 #[test]
 fn test_only_whitespace() {
     let mut tokenizer = Tokenizer::new("   \n\t  ");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
 
     // Now includes newline token
     assert_eq!(tokens.len(), 2);
@@ -59,7 +136,7 @@ fn test_only_whitespace() {
 #[test]
 fn test_trailing_whitespace() {
     let mut tokenizer = Tokenizer::new("42   \n  ");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
 
     // Now includes newline token
     assert_eq!(tokens.len(), 3);
@@ -72,11 +149,7 @@ fn test_trailing_whitespace() {
 
 #[test]
 fn test_parse_error_display_unexpected_token() {
-    let token = Token {
-        token_type: TokenType::Integer(42),
-        line: 10,
-        column: 5,
-    };
+    let token = Token::new(TokenType::Integer(42), 10, 5);
     let err = ParseError::UnexpectedToken {
         expected: "identifier".to_string(),
         found: token,
@@ -101,7 +174,7 @@ fn test_parse_error_display_unexpected_eof() {
 fn test_class_definition_missing_name() {
     let input = "class 123"; // Number instead of class name
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let result = parser.parse();
     assert!(result.is_err());
@@ -115,7 +188,7 @@ fn test_class_definition_missing_name() {
 fn test_class_definition_eof_after_class_keyword() {
     let input = "class"; // EOF after class keyword
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let result = parser.parse();
     assert!(result.is_err());
@@ -125,7 +198,7 @@ fn test_class_definition_eof_after_class_keyword() {
 fn test_method_definition_missing_method_name() {
     let input = "fn MyClass > 123()"; // Number instead of method name
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let result = parser.parse();
     assert!(result.is_err());
@@ -135,7 +208,7 @@ fn test_method_definition_missing_method_name() {
 fn test_method_definition_eof_after_arrow() {
     let input = "fn MyClass >"; // EOF after >
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let result = parser.parse();
     assert!(result.is_err());
@@ -145,7 +218,7 @@ fn test_method_definition_eof_after_arrow() {
 fn test_function_params_unexpected_eof() {
     let input = "fn test(a, b"; // Missing closing paren
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let result = parser.parse();
     assert!(result.is_err());
@@ -155,7 +228,7 @@ fn test_function_params_unexpected_eof() {
 fn test_function_params_unexpected_token() {
     let input = "fn test(a, 123)"; // Number instead of param name
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let result = parser.parse();
     assert!(result.is_err());
@@ -165,7 +238,7 @@ fn test_function_params_unexpected_token() {
 fn test_if_statement_missing_brace() {
     let input = "if x > 5 print('hi')"; // Missing {
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let result = parser.parse();
     assert!(result.is_err());
@@ -179,7 +252,7 @@ fn test_if_statement_missing_brace() {
 fn test_if_statement_eof_in_body() {
     let input = "if x > 5 { print('hi')"; // Missing }
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let result = parser.parse();
     assert!(result.is_err());
@@ -189,7 +262,7 @@ fn test_if_statement_eof_in_body() {
 fn test_elif_missing_brace() {
     let input = "if x > 5 { print('a') } elif x < 2 print('b')"; // Missing { after elif
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let result = parser.parse();
     assert!(result.is_err());
@@ -199,7 +272,7 @@ fn test_elif_missing_brace() {
 fn test_elif_eof_in_body() {
     let input = "if x > 5 { print('a') } elif x < 2 { print('b'"; // Missing }
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let result = parser.parse();
     assert!(result.is_err());
@@ -209,17 +282,40 @@ fn test_elif_eof_in_body() {
 fn test_else_missing_brace() {
     let input = "if x > 5 { print('a') } else print('b')"; // Missing { after else
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let result = parser.parse();
     assert!(result.is_err());
 }
 
+#[test]
+fn test_else_without_if() {
+    for (input, keyword, line) in [
+        ("x = 1\nelse {\n  x = 2\n}", "else", 2),
+        ("if x { x = 1 }\nx = 2\nelse if x { x = 3 }", "else", 3),
+        ("elif x > 1 { x = 2 }", "elif", 1),
+    ] {
+        let mut tokenizer = Tokenizer::new(input);
+        let tokens = tokenizer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        match parser.parse() {
+            Err(err @ ParseError::DanglingElse { .. }) => assert_eq!(
+                err.to_string(),
+                format!(
+                    "'{}' without a preceding 'if' at line {}, column 1",
+                    keyword, line
+                )
+            ),
+            other => panic!("expected a dangling else error, got {:?}", other),
+        }
+    }
+}
+
 #[test]
 fn test_while_loop_missing_brace() {
     let input = "while x < 10 x = x + 1"; // Missing {
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let result = parser.parse();
     assert!(result.is_err());
@@ -233,7 +329,7 @@ fn test_while_loop_missing_brace() {
 fn test_while_loop_eof_in_body() {
     let input = "while x < 10 { x = x + 1"; // Missing }
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let result = parser.parse();
     assert!(result.is_err());
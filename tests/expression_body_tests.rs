@@ -0,0 +1,55 @@
+mod common;
+
+use common::{parse, try_parse};
+use grit::codegen::CodeGenerator;
+use grit::parser::{Program, Statement};
+
+fn without_spans(program: &Program) -> Vec<Statement> {
+    program
+        .statements
+        .iter()
+        .map(Statement::without_spans)
+        .collect()
+}
+
+#[test]
+fn test_expression_body_desugars_to_a_block_body() {
+    let short = try_parse("fn double(x: int) -> int = x * 2\nclass P { x }\nfn P > get() = self.x")
+        .unwrap();
+    let long = try_parse(
+        "fn double(x: int) -> int {\n  x * 2\n}\nclass P { x }\nfn P > get() {\n  self.x\n}",
+    )
+    .unwrap();
+
+    assert_eq!(without_spans(&short), without_spans(&long));
+}
+
+#[test]
+fn test_expression_body_without_parameters() {
+    let program = parse("fn answer = 42\nx = answer()");
+
+    match &program.statements[0] {
+        Statement::FunctionDef { params, body, .. } => {
+            assert!(params.is_empty());
+            assert_eq!(body.len(), 1);
+            assert_eq!(body[0].to_string(), "42");
+        }
+        other => panic!("expected a function, got {:?}", other),
+    }
+    assert_eq!(program.statements.len(), 2);
+}
+
+#[test]
+fn test_expression_body_needs_an_expression() {
+    assert!(try_parse("fn f(x) =").is_err());
+    assert!(try_parse("fn f(x) =\n  x").is_err());
+    assert!(try_parse("f = |x| = x").is_err());
+}
+
+#[test]
+fn test_generate_expression_body() {
+    let code =
+        CodeGenerator::generate_program(&parse("fn double(x) = x * 2\nprint('%d', double(4))"));
+
+    assert!(code.contains("fn double(x: i64) -> i64 {\n    x * 2\n}"));
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use common::{errors, generate, parse, try_parse};
+use grit::lexer::Span;
+use grit::parser::{Expr, Program, Statement};
+
+fn value(program: &Program, index: usize) -> &Expr {
+    match &program.statements[index] {
+        Statement::Assignment { value, .. } => value,
+        other => panic!("expected an assignment, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_call_of_a_call() {
+    let program = parse("x = make_adder(2)(5)");
+
+    match value(&program, 0) {
+        Expr::Call { callee, args, span } => {
+            assert!(matches!(**callee, Expr::FunctionCall { .. }));
+            assert_eq!(args.len(), 1);
+            assert_eq!(*span, Span::new(1, 5, 1, 20));
+        }
+        other => panic!("expected a call, got {:?}", other),
+    }
+    assert_eq!(program.statements[0].to_string(), "x = make_adder(2)(5)");
+}
+
+#[test]
+fn test_parse_calls_of_other_expressions() {
+    let program =
+        try_parse("a = (|n| n * 2)(5)\nb = handlers[0](1, 2)\nc = p.maker()(3)(4)\nd = f(1)()")
+            .unwrap();
+
+    let calls: Vec<String> = (0..4).map(|i| value(&program, i).to_string()).collect();
+    assert_eq!(
+        calls,
+        [
+            "(|n| (n * 2))(5)",
+            "handlers[0](1, 2)",
+            "p.maker()(3)(4)",
+            "f(1)()"
+        ]
+    );
+    // A call's arguments may not be named
+    assert!(try_parse("x = f(1)(width = 2)").is_err());
+    assert!(try_parse("x = f(1)(2").is_err());
+}
+
+#[test]
+fn test_check_expression_calls() {
+    assert!(errors("double = |n| n * 2\nx = (double)(3)").is_empty());
+    assert_eq!(
+        errors("n = 5\nx = (n)(1)"),
+        vec!["cannot call a value of type int"]
+    );
+    assert_eq!(
+        errors("x = 'abc'(1)"),
+        vec!["cannot call a value of type string"]
+    );
+}
+
+#[test]
+fn test_generate_immediately_invoked_lambdas() {
+    let code = generate("x = (|n| n * 2)(5)\ny = fn(a, b) { a + b }(1, 2)\nprint('%d %d', x, y)");
+
+    assert!(code.contains("let x = (|n: i64| n * 2)(5);"));
+    assert!(code.contains("let y = (|a: i64, b: i64| a + b)(1, 2);"));
+}
+
+#[test]
+fn test_generate_call_of_an_element() {
+    let code = generate("double = |n| n * 2\nfs = [double]\nprint('%d', fs[0](4))");
+
+    assert!(code.contains("println!(\"{}\", fs[0](4));"));
+}
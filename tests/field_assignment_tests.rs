@@ -0,0 +1,140 @@
+mod common;
+
+use common::{generate, parse, try_parse};
+use grit::lexer::Span;
+use grit::parser::{Expr, Statement};
+use grit::semantic::check_program;
+
+const POINT: &str = "fn Point > new(x, y) {\n  self.x = x\n  self.y = y\n}\n";
+
+#[test]
+fn test_parse_field_assignment() {
+    let program = parse("p.x = 5");
+
+    assert_eq!(
+        program.statements[0],
+        Statement::FieldAssignment {
+            object: Expr::Identifier {
+                name: "p".into(),
+                span: Span::new(1, 1, 1, 1)
+            },
+            field: "x".to_string(),
+            value: Expr::Integer {
+                value: 5,
+                span: Span::new(1, 7, 1, 7)
+            },
+            span: Span::new(1, 1, 1, 3),
+        }
+    );
+    assert_eq!(program.statements[0].to_string(), "p.x = 5");
+}
+
+#[test]
+fn test_parse_nested_field_assignment() {
+    let program = parse("line.start.x = 1\npoints[0].y = 2");
+
+    match &program.statements[0] {
+        Statement::FieldAssignment { object, field, .. } => {
+            assert_eq!(
+                object.without_spans(),
+                Expr::FieldAccess {
+                    object: Box::new(Expr::Identifier {
+                        name: "line".into(),
+                        span: Span::default()
+                    }),
+                    field: "start".to_string(),
+                    span: Span::default(),
+                }
+            );
+            assert_eq!(field, "x");
+        }
+        other => panic!("expected a field assignment, got {:?}", other),
+    }
+    assert!(matches!(
+        &program.statements[1],
+        Statement::FieldAssignment {
+            object: Expr::Index { .. },
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_self_field_assignment_is_unchanged() {
+    let program = parse("self.x = 1");
+
+    assert!(matches!(
+        &program.statements[0],
+        Statement::Assignment { name, .. } if name == "self.x"
+    ));
+}
+
+#[test]
+fn test_method_call_is_not_an_assignment_target() {
+    assert!(try_parse("p.move(1) = 5").is_err());
+    assert!(try_parse("f() = 5").is_err());
+}
+
+#[test]
+fn test_generate_field_assignment_makes_binding_mutable() {
+    let code = generate(&format!("{}p = Point.new(1, 2)\np.x = 5", POINT));
+
+    assert!(code.contains("let mut p = Point::new(1, 2);"));
+    assert!(code.contains("p.x = 5;"));
+}
+
+#[test]
+fn test_nested_field_assignment_makes_root_mutable() {
+    let code = generate(&format!(
+        "{}p = Point.new(1, 2)\nq = Point.new(3, 4)\nif true {{\n  p.y = 7\n}}",
+        POINT
+    ));
+
+    assert!(code.contains("let mut p = Point::new(1, 2);"));
+    assert!(code.contains("let q = Point::new(3, 4);"));
+    assert!(code.contains("p.y = 7;"));
+}
+
+#[test]
+fn test_field_assignment_in_function_body() {
+    let code = generate(&format!(
+        "{}fn origin() {{\n  p = Point.new(1, 2)\n  p.x = 0\n  0\n}}",
+        POINT
+    ));
+
+    assert!(code.contains("let mut p = Point::new(1, 2);"));
+    assert!(code.contains("p.x = 0;"));
+}
+
+#[test]
+fn test_check_field_assignment_value() {
+    let program = parse("p.x = print('%d', 'a')");
+
+    assert!(!check_program(&program).is_empty());
+}
+
+#[test]
+fn test_field_assignments_run() {
+    let dir = std::env::temp_dir().join(format!("grit_field_assign_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("points.grit"),
+        "class Point {\n  x: int\n  y: int\n}\n\
+         fn Point > new(x: int, y: int) {\n  self.x = x\n  self.y = y\n}\n\
+         fn Point > set_y(y) {\n  self.y = y\n}\n\
+         fn moved(x) {\n  p = Point.new(0, 0)\n  p.x = x\n  p.set_y(x * 2)\n  p.x + p.y\n}\n\
+         test 'variable' {\n  p = Point.new(1, 2)\n  p.x = 5\n  if p.x > 4 {\n    p.y = 9\n  }\n  \
+         assert(p.x == 5 && p.y == 9)\n}\n\
+         test 'array element' {\n  points = [Point.new(0, 0), Point.new(3, 4)]\n  \
+         points[1].x = 2\n  points[0].set_y(8)\n  assert(points[1].x == 2 && points[0].y == 8)\n}\n\
+         test 'function' {\n  assert(moved(3) == 9)\n}\n",
+    )
+    .unwrap();
+
+    let report = grit::testing::run_tests(std::slice::from_ref(&dir)).unwrap();
+    assert_eq!(report.results.len(), 3);
+    assert_eq!(report.failed(), 0, "{:?}", report.results);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use common::parse;
+use grit::lexer::Span;
+use grit::parser::ast::fold::{walk_body, walk_expr, walk_statement};
+use grit::parser::{Expr, Fold, Statement};
+
+/// Desugars `elif` branches into an `else` holding another `if`
+struct ElifToElse;
+
+impl Fold for ElifToElse {
+    fn fold_statement(&mut self, stmt: Statement) -> Statement {
+        match walk_statement(self, stmt) {
+            Statement::If {
+                condition,
+                then_branch,
+                elif_branches,
+                else_branch,
+                span,
+            } if !elif_branches.is_empty() => {
+                let else_branch = elif_branches.into_iter().rev().fold(
+                    else_branch,
+                    |else_branch, (condition, branch)| {
+                        Some(vec![Statement::If {
+                            span: condition.span(),
+                            condition,
+                            then_branch: branch,
+                            elif_branches: Vec::new(),
+                            else_branch,
+                        }])
+                    },
+                );
+                Statement::If {
+                    condition,
+                    then_branch,
+                    elif_branches: Vec::new(),
+                    else_branch,
+                    span,
+                }
+            }
+            stmt => stmt,
+        }
+    }
+}
+
+/// Renames every use of one variable
+struct Rename(&'static str, &'static str);
+
+impl Fold for Rename {
+    fn fold_expr(&mut self, expr: Expr) -> Expr {
+        match walk_expr(self, expr) {
+            Expr::Identifier { name, span } if name == self.0 => Expr::Identifier {
+                name: self.1.into(),
+                span,
+            },
+            expr => expr,
+        }
+    }
+}
+
+/// Drops `break` statements and counts the spans it sees
+#[derive(Default)]
+struct DropBreaks {
+    spans: usize,
+}
+
+impl Fold for DropBreaks {
+    fn fold_body(&mut self, body: Vec<Statement>) -> Vec<Statement> {
+        let body = body
+            .into_iter()
+            .filter(|stmt| !matches!(stmt, Statement::Break { .. }))
+            .collect();
+        walk_body(self, body)
+    }
+
+    fn fold_span(&mut self, span: Span) -> Span {
+        self.spans += 1;
+        span
+    }
+}
+
+#[test]
+fn test_fold_desugars_elif_chains_at_every_depth() {
+    let program = parse(
+        "fn f(x) {\n  if x == 1 {\n    a = 1\n  } elif x == 2 {\n    a = 2\n  } elif x == 3 {\n    \
+         a = 3\n  } else {\n    a = 4\n  }\n  a\n}",
+    );
+    let expected = parse(
+        "fn f(x) {\n  if x == 1 {\n    a = 1\n  } else {\n    if x == 2 {\n      a = 2\n    } \
+         else {\n      if x == 3 {\n        a = 3\n      } else {\n        a = 4\n      }\n    \
+         }\n  }\n  a\n}",
+    );
+
+    let folded = ElifToElse.fold_program(program);
+    assert_eq!(
+        folded.statements[0].without_spans(),
+        expected.statements[0].without_spans()
+    );
+}
+
+#[test]
+fn test_fold_rewrites_expressions_in_place() {
+    let program = parse("y = x + f(x, [x])\nprint('%d', y)");
+    let folded = Rename("x", "z").fold_program(program.clone());
+
+    assert_eq!(folded.statements[0].to_string(), "y = (z + f(z, [z]))");
+    assert_eq!(folded.statements[1], program.statements[1]);
+    // Spans are kept
+    assert_eq!(folded.statements[0].span(), program.statements[0].span());
+}
+
+#[test]
+fn test_fold_body_may_drop_statements() {
+    let program = parse("loop {\n  x = 1\n  break\n}");
+    let mut folder = DropBreaks::default();
+    let folded = folder.fold_program(program);
+
+    match &folded.statements[0] {
+        Statement::Loop { body, .. } => assert_eq!(body.len(), 1),
+        other => panic!("expected a loop, got {:?}", other),
+    }
+    // The loop, the assignment, and its value
+    assert_eq!(folder.spans, 3);
+}
+
+#[test]
+fn test_without_spans_clears_nested_spans() {
+    let program = parse("class P { x: int }\nmatch n {\n  1 => y = [n]\n  _ => z = 0\n}");
+
+    for stmt in &program.statements {
+        let cleared = stmt.without_spans();
+        assert_eq!(cleared.span(), Span::default());
+        assert_eq!(cleared.to_string(), stmt.to_string());
+    }
+    match program.statements[0].without_spans() {
+        Statement::ClassDef { fields, .. } => assert_eq!(fields[0].span, Span::default()),
+        other => panic!("expected a class, got {:?}", other),
+    }
+}
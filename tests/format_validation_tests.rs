@@ -0,0 +1,216 @@
+mod common;
+
+use common::{check, parse};
+use grit::lexer::Span;
+use grit::parser::{Expr, Statement};
+use grit::semantic::format::{
+    parse_format, parse_format_parts, parse_placeholders, FormatPart, FormatSpec, Placeholder,
+};
+use grit::semantic::Severity;
+use std::fs;
+
+#[test]
+fn test_parse_format_specifiers() {
+    assert_eq!(
+        parse_format("%d and %s, 100% sure %x %f"),
+        vec![
+            FormatSpec::Int,
+            FormatSpec::Str,
+            FormatSpec::Hex,
+            FormatSpec::Float
+        ]
+    );
+    assert!(parse_format("plain text").is_empty());
+    assert!(parse_format("100%% %q %").is_empty());
+}
+
+#[test]
+fn test_parse_format_parts() {
+    assert_eq!(
+        parse_format_parts("total: %-8.2f%% of %5d"),
+        vec![
+            FormatPart::Text("total: ".to_string()),
+            FormatPart::Placeholder(Placeholder {
+                spec: FormatSpec::Float,
+                left: true,
+                zero: false,
+                width: Some(8),
+                precision: Some(2),
+            }),
+            FormatPart::Text("% of ".to_string()),
+            FormatPart::Placeholder(Placeholder {
+                spec: FormatSpec::Int,
+                left: false,
+                zero: false,
+                width: Some(5),
+                precision: None,
+            }),
+        ]
+    );
+    assert_eq!(
+        parse_format_parts("50% off"),
+        vec![FormatPart::Text("50% off".to_string())]
+    );
+}
+
+#[test]
+fn test_placeholder_display() {
+    let placeholders: Vec<String> = parse_placeholders("%d %05d %-8s %.2f %.f %x")
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+    assert_eq!(
+        placeholders,
+        vec!["%d", "%05d", "%-8s", "%.2f", "%.0f", "%x"]
+    );
+}
+
+#[test]
+fn test_literal_percent_takes_no_argument() {
+    assert!(check("print('100%%')").is_empty());
+
+    let diagnostics = check("print('%d%%', 1, 2)");
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0]
+        .message
+        .contains("expects 1 argument(s), found 2"));
+}
+
+#[test]
+fn test_float_and_hex_specifiers() {
+    assert!(check(
+        "x = 255
+pi = 3.14
+print('%x %5.2f %-4d', x, pi, x)"
+    )
+    .is_empty());
+
+    let diagnostics = check("print('%.2f', 1)");
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0]
+        .message
+        .contains("%.2f (argument 1) does not accept a value of type int"));
+
+    let diagnostics = check("print('%x', 'ff')");
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0]
+        .message
+        .contains("%x (argument 1) does not accept a value of type string"));
+}
+
+#[test]
+fn test_function_call_span() {
+    let program = parse("x = 1\n  print('%d', x)");
+
+    match &program.statements[1] {
+        Statement::Expression(Expr::FunctionCall { span, .. }) => {
+            assert_eq!(*span, Span::new(2, 3, 2, 16));
+        }
+        _ => panic!("Expected FunctionCall"),
+    }
+}
+
+#[test]
+fn test_matching_print_has_no_diagnostics() {
+    assert!(check("x = 1\nname = 'bob'\nprint('%s is %d', name, x)").is_empty());
+}
+
+#[test]
+fn test_too_few_arguments() {
+    let diagnostics = check("x = 1\nprint('%d %d', x)");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert!(diagnostics[0]
+        .message
+        .contains("expects 2 argument(s), found 1"));
+    assert_eq!(diagnostics[0].span.start_line, 2);
+    assert_eq!(diagnostics[0].span.start_column, 1);
+}
+
+#[test]
+fn test_too_many_arguments() {
+    let diagnostics = check("print('total', 1)");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0]
+        .message
+        .contains("expects 0 argument(s), found 1"));
+}
+
+#[test]
+fn test_int_specifier_rejects_string() {
+    let diagnostics = check("name = 'bob'\nprint('%d', name)");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0]
+        .message
+        .contains("%d (argument 1) does not accept a value of type string"));
+}
+
+#[test]
+fn test_int_specifier_rejects_float_expression() {
+    let diagnostics = check("x = 2\nprint('%d', x * 1.5)");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("type float"));
+}
+
+#[test]
+fn test_string_specifier_rejects_class_instance() {
+    let diagnostics = check("class Foo\nfn Foo > new { self.a = 1 }\nf = Foo.new\nprint('%s', f)");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("type Foo"));
+}
+
+#[test]
+fn test_unknown_parameter_types_are_accepted() {
+    assert!(check("fn show(x) {\n  print('%d', x)\n}").is_empty());
+}
+
+#[test]
+fn test_function_bodies_do_not_see_main_variables() {
+    assert!(check("x = 'text'\nfn show(x) {\n  print('%d', x)\n}").is_empty());
+}
+
+#[test]
+fn test_checks_nested_blocks() {
+    let diagnostics = check("x = 1\nwhile x < 10 {\n  if x == 5 {\n    print('%d')\n  }\n}");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].span.start_line, 4);
+}
+
+#[test]
+fn test_non_literal_format_is_not_checked() {
+    assert!(check("fmt = '%d'\nprint(fmt)").is_empty());
+}
+
+#[test]
+fn test_diagnostic_display() {
+    let diagnostics = check("print('%d')");
+
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "Error: format string '%d' expects 1 argument(s), found 0 at line 1, column 1"
+    );
+}
+
+#[test]
+fn test_run_rejects_format_mismatch() {
+    let test_file = "/tmp/test_run_format_mismatch.grit";
+    fs::write(test_file, "print('%d %d', 1)").unwrap();
+
+    let args = vec!["grit".to_string(), test_file.to_string()];
+    let mut output = Vec::new();
+
+    let result = grit::run(&args, &mut output);
+    assert_eq!(result, Err(1));
+
+    let output_str = String::from_utf8(output).unwrap();
+    assert!(!output_str.contains("Generated Rust code:"));
+
+    let _ = fs::remove_file(test_file);
+}
@@ -1,12 +1,12 @@
 use grit::codegen::CodeGenerator;
-use grit::lexer::Tokenizer;
+use grit::lexer::{Span, Tokenizer};
 use grit::parser::Program;
 use grit::parser::{Expr, Parser, Statement};
 
 #[test]
 fn test_tokenize_fn_keyword() {
     let mut tokenizer = Tokenizer::new("fn");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
 
     assert_eq!(tokens.len(), 2); // fn, EOF
     assert_eq!(tokens[0].token_type, grit::lexer::TokenType::Fn);
@@ -15,7 +15,7 @@ fn test_tokenize_fn_keyword() {
 #[test]
 fn test_tokenize_braces() {
     let mut tokenizer = Tokenizer::new("{}");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
 
     assert_eq!(tokens.len(), 3); // {, }, EOF
     assert_eq!(tokens[0].token_type, grit::lexer::TokenType::LeftBrace);
@@ -25,7 +25,7 @@ fn test_tokenize_braces() {
 #[test]
 fn test_tokenize_simple_function() {
     let mut tokenizer = Tokenizer::new("fn add(a, b) { a + b }");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
 
     // fn, add, (, a, ,, b, ), {, a, +, b, }, EOF
     assert_eq!(tokens.len(), 13);
@@ -34,13 +34,15 @@ fn test_tokenize_simple_function() {
 #[test]
 fn test_parse_empty_function() {
     let mut tokenizer = Tokenizer::new("fn foo() { }");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
     assert_eq!(program.statements.len(), 1);
     match &program.statements[0] {
-        Statement::FunctionDef { name, params, body } => {
+        Statement::FunctionDef {
+            name, params, body, ..
+        } => {
             assert_eq!(name, "foo");
             assert_eq!(params.len(), 0);
             assert_eq!(body.len(), 0);
@@ -52,13 +54,15 @@ fn test_parse_empty_function() {
 #[test]
 fn test_parse_function_with_params() {
     let mut tokenizer = Tokenizer::new("fn add(a, b) { a + b }");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
     assert_eq!(program.statements.len(), 1);
     match &program.statements[0] {
-        Statement::FunctionDef { name, params, body } => {
+        Statement::FunctionDef {
+            name, params, body, ..
+        } => {
             assert_eq!(name, "add");
             assert_eq!(params.len(), 2);
             assert_eq!(params[0], "a");
@@ -73,13 +77,15 @@ fn test_parse_function_with_params() {
 fn test_parse_function_with_multiline_body() {
     let input = "fn test(x) {\n  a = x + 1\n  a\n}";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
     assert_eq!(program.statements.len(), 1);
     match &program.statements[0] {
-        Statement::FunctionDef { name, params, body } => {
+        Statement::FunctionDef {
+            name, params, body, ..
+        } => {
             assert_eq!(name, "test");
             assert_eq!(params.len(), 1);
             assert_eq!(params[0], "x");
@@ -93,7 +99,7 @@ fn test_parse_function_with_multiline_body() {
 fn test_parse_function_and_main_code() {
     let input = "fn add(a, b) { a + b }\nc = add(1, 2)";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
@@ -114,12 +120,17 @@ fn test_generate_empty_function() {
         statements: vec![Statement::FunctionDef {
             name: "foo".to_string(),
             params: vec![],
+            param_types: vec![],
+            variadic: false,
+            return_type: None,
             body: vec![],
+            docs: Vec::new(),
+            span: Span::default(),
         }],
     };
 
     let code = CodeGenerator::generate_program(&program);
-    assert!(code.contains("fn foo() -> i64"));
+    assert!(code.contains("fn foo() {"));
 }
 
 #[test]
@@ -128,11 +139,23 @@ fn test_generate_function_with_one_param() {
         statements: vec![Statement::FunctionDef {
             name: "double".to_string(),
             params: vec!["x".to_string()],
+            param_types: vec![None],
+            variadic: false,
+            return_type: None,
             body: vec![Statement::Expression(Expr::BinaryOp {
-                left: Box::new(Expr::Identifier("x".to_string())),
+                left: Box::new(Expr::Identifier {
+                    name: "x".into(),
+                    span: Span::default(),
+                }),
                 op: grit::parser::BinaryOperator::Multiply,
-                right: Box::new(Expr::Integer(2)),
+                right: Box::new(Expr::Integer {
+                    value: 2,
+                    span: Span::default(),
+                }),
+                span: Span::default(),
             })],
+            docs: Vec::new(),
+            span: Span::default(),
         }],
     };
 
@@ -147,11 +170,23 @@ fn test_generate_function_with_multiple_params() {
         statements: vec![Statement::FunctionDef {
             name: "add".to_string(),
             params: vec!["a".to_string(), "b".to_string()],
+            param_types: vec![None; 2],
+            variadic: false,
+            return_type: None,
             body: vec![Statement::Expression(Expr::BinaryOp {
-                left: Box::new(Expr::Identifier("a".to_string())),
+                left: Box::new(Expr::Identifier {
+                    name: "a".into(),
+                    span: Span::default(),
+                }),
                 op: grit::parser::BinaryOperator::Add,
-                right: Box::new(Expr::Identifier("b".to_string())),
+                right: Box::new(Expr::Identifier {
+                    name: "b".into(),
+                    span: Span::default(),
+                }),
+                span: Span::default(),
             })],
+            docs: Vec::new(),
+            span: Span::default(),
         }],
     };
 
@@ -166,17 +201,35 @@ fn test_generate_function_with_assignment_in_body() {
         statements: vec![Statement::FunctionDef {
             name: "test".to_string(),
             params: vec!["x".to_string()],
+            param_types: vec![None],
+            variadic: false,
+            return_type: None,
             body: vec![
                 Statement::Assignment {
                     name: "result".to_string(),
+                    mutable: false,
+                    ty: None,
                     value: Expr::BinaryOp {
-                        left: Box::new(Expr::Identifier("x".to_string())),
+                        left: Box::new(Expr::Identifier {
+                            name: "x".into(),
+                            span: Span::default(),
+                        }),
                         op: grit::parser::BinaryOperator::Add,
-                        right: Box::new(Expr::Integer(1)),
+                        right: Box::new(Expr::Integer {
+                            value: 1,
+                            span: Span::default(),
+                        }),
+                        span: Span::default(),
                     },
+                    span: Span::default(),
                 },
-                Statement::Expression(Expr::Identifier("result".to_string())),
+                Statement::Expression(Expr::Identifier {
+                    name: "result".into(),
+                    span: Span::default(),
+                }),
             ],
+            docs: Vec::new(),
+            span: Span::default(),
         }],
     };
 
@@ -193,18 +246,43 @@ fn test_generate_complete_program_with_function() {
             Statement::FunctionDef {
                 name: "add".to_string(),
                 params: vec!["a".to_string(), "b".to_string()],
+                param_types: vec![None; 2],
+                variadic: false,
+                return_type: None,
                 body: vec![Statement::Expression(Expr::BinaryOp {
-                    left: Box::new(Expr::Identifier("a".to_string())),
+                    left: Box::new(Expr::Identifier {
+                        name: "a".into(),
+                        span: Span::default(),
+                    }),
                     op: grit::parser::BinaryOperator::Add,
-                    right: Box::new(Expr::Identifier("b".to_string())),
+                    right: Box::new(Expr::Identifier {
+                        name: "b".into(),
+                        span: Span::default(),
+                    }),
+                    span: Span::default(),
                 })],
+                docs: Vec::new(),
+                span: Span::default(),
             },
             Statement::Assignment {
                 name: "result".to_string(),
+                mutable: false,
+                ty: None,
                 value: Expr::FunctionCall {
                     name: "add".to_string(),
-                    args: vec![Expr::Integer(1), Expr::Integer(2)],
+                    args: vec![
+                        Expr::Integer {
+                            value: 1,
+                            span: Span::default(),
+                        },
+                        Expr::Integer {
+                            value: 2,
+                            span: Span::default(),
+                        },
+                    ],
+                    span: Span::default(),
                 },
+                span: Span::default(),
             },
         ],
     };
@@ -219,16 +297,16 @@ fn test_generate_complete_program_with_function() {
 fn test_parse_function_call_to_user_function() {
     let input = "fn double(x) { x * 2 }\nresult = double(5)";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
     assert_eq!(program.statements.len(), 2);
     match &program.statements[1] {
-        Statement::Assignment { name, value } => {
+        Statement::Assignment { name, value, .. } => {
             assert_eq!(name, "result");
             match value {
-                Expr::FunctionCall { name, args } => {
+                Expr::FunctionCall { name, args, .. } => {
                     assert_eq!(name, "double");
                     assert_eq!(args.len(), 1);
                 }
@@ -243,13 +321,15 @@ fn test_parse_function_call_to_user_function() {
 fn test_function_with_newlines_in_params() {
     let input = "fn test(\n  a,\n  b\n) {\n  a + b\n}";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
     assert_eq!(program.statements.len(), 1);
     match &program.statements[0] {
-        Statement::FunctionDef { name, params, body } => {
+        Statement::FunctionDef {
+            name, params, body, ..
+        } => {
             assert_eq!(name, "test");
             assert_eq!(params.len(), 2);
             assert_eq!(params[0], "a");
@@ -260,11 +340,71 @@ fn test_function_with_newlines_in_params() {
     }
 }
 
+#[test]
+fn test_function_header_across_lines() {
+    let input = "fn add\n(a, b)\n{\n  a + b\n}";
+    let mut tokenizer = Tokenizer::new(input);
+    let tokens = tokenizer.tokenize().unwrap();
+    let mut parser = Parser::new(tokens);
+    let program = parser.parse().unwrap();
+
+    assert_eq!(program.statements.len(), 1);
+    match &program.statements[0] {
+        Statement::FunctionDef {
+            name, params, span, ..
+        } => {
+            assert_eq!(name, "add");
+            assert_eq!(params, &["a", "b"]);
+            // The header span still covers only `fn add`
+            assert_eq!(*span, Span::new(1, 1, 1, 6));
+        }
+        _ => panic!("Expected function definition"),
+    }
+}
+
+#[test]
+fn test_method_header_across_lines() {
+    let input = "fn Point\n>\narea()\n{\n  1\n}";
+    let mut tokenizer = Tokenizer::new(input);
+    let tokens = tokenizer.tokenize().unwrap();
+    let mut parser = Parser::new(tokens);
+    let program = parser.parse().unwrap();
+
+    assert_eq!(program.statements.len(), 1);
+    match &program.statements[0] {
+        Statement::MethodDef {
+            class_name,
+            method_name,
+            ..
+        } => {
+            assert_eq!(class_name, "Point");
+            assert_eq!(method_name, "area");
+        }
+        _ => panic!("Expected method definition"),
+    }
+}
+
+#[test]
+fn test_return_type_on_next_line() {
+    let input = "fn double(a: int)\n-> int\n{\n  a * 2\n}";
+    let mut tokenizer = Tokenizer::new(input);
+    let tokens = tokenizer.tokenize().unwrap();
+    let mut parser = Parser::new(tokens);
+    let program = parser.parse().unwrap();
+
+    assert_eq!(
+        program.statements[0].to_string(),
+        "fn double(a: int) -> int"
+    );
+    let code = CodeGenerator::generate_program(&program);
+    assert!(code.contains("fn double(a: i64) -> i64 {"));
+}
+
 #[test]
 fn test_parse_function_missing_name() {
     let input = "fn () {}";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let result = parser.parse();
 
@@ -275,7 +415,7 @@ fn test_parse_function_missing_name() {
 fn test_parse_function_missing_left_paren() {
     let input = "fn foo a, b) {}";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let result = parser.parse();
 
@@ -286,7 +426,7 @@ fn test_parse_function_missing_left_paren() {
 fn test_parse_function_missing_comma_in_params() {
     let input = "fn foo(a b) {}";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let result = parser.parse();
 
@@ -297,7 +437,7 @@ fn test_parse_function_missing_comma_in_params() {
 fn test_parse_function_missing_left_brace() {
     let input = "fn foo() a + b }";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let result = parser.parse();
 
@@ -308,7 +448,7 @@ fn test_parse_function_missing_left_brace() {
 fn test_parse_function_missing_right_brace() {
     let input = "fn foo() { a + b";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let result = parser.parse();
 
@@ -322,7 +462,7 @@ fn test_parse_function_eof_after_name() {
 
     let tokens = vec![
         Token::new(TokenType::Fn, 1, 1),
-        Token::new(TokenType::Identifier("foo".to_string()), 1, 4),
+        Token::new(TokenType::Identifier("foo".into()), 1, 4),
         // Missing rest of function
     ];
     let mut parser = Parser::new(tokens);
@@ -338,9 +478,9 @@ fn test_parse_function_eof_in_params() {
 
     let tokens = vec![
         Token::new(TokenType::Fn, 1, 1),
-        Token::new(TokenType::Identifier("foo".to_string()), 1, 4),
+        Token::new(TokenType::Identifier("foo".into()), 1, 4),
         Token::new(TokenType::LeftParen, 1, 7),
-        Token::new(TokenType::Identifier("a".to_string()), 1, 8),
+        Token::new(TokenType::Identifier("a".into()), 1, 8),
         Token::new(TokenType::Comma, 1, 9),
         // Missing rest
     ];
@@ -357,7 +497,7 @@ fn test_parse_function_invalid_param() {
 
     let tokens = vec![
         Token::new(TokenType::Fn, 1, 1),
-        Token::new(TokenType::Identifier("foo".to_string()), 1, 4),
+        Token::new(TokenType::Identifier("foo".into()), 1, 4),
         Token::new(TokenType::LeftParen, 1, 7),
         Token::new(TokenType::Integer(42), 1, 8), // Numbers can't be param names
         Token::new(TokenType::RightParen, 1, 10),
@@ -378,7 +518,15 @@ fn test_generate_function_implicit_return() {
         statements: vec![Statement::FunctionDef {
             name: "get_five".to_string(),
             params: vec![],
-            body: vec![Statement::Expression(Expr::Integer(5))],
+            param_types: vec![],
+            variadic: false,
+            return_type: None,
+            body: vec![Statement::Expression(Expr::Integer {
+                value: 5,
+                span: Span::default(),
+            })],
+            docs: Vec::new(),
+            span: Span::default(),
         }],
     };
 
@@ -396,17 +544,35 @@ fn test_generate_function_with_statement_then_expression() {
         statements: vec![Statement::FunctionDef {
             name: "calc".to_string(),
             params: vec!["x".to_string()],
+            param_types: vec![None],
+            variadic: false,
+            return_type: None,
             body: vec![
                 Statement::Assignment {
                     name: "doubled".to_string(),
+                    mutable: false,
+                    ty: None,
                     value: Expr::BinaryOp {
-                        left: Box::new(Expr::Identifier("x".to_string())),
+                        left: Box::new(Expr::Identifier {
+                            name: "x".into(),
+                            span: Span::default(),
+                        }),
                         op: grit::parser::BinaryOperator::Multiply,
-                        right: Box::new(Expr::Integer(2)),
+                        right: Box::new(Expr::Integer {
+                            value: 2,
+                            span: Span::default(),
+                        }),
+                        span: Span::default(),
                     },
+                    span: Span::default(),
                 },
-                Statement::Expression(Expr::Identifier("doubled".to_string())),
+                Statement::Expression(Expr::Identifier {
+                    name: "doubled".into(),
+                    span: Span::default(),
+                }),
             ],
+            docs: Vec::new(),
+            span: Span::default(),
         }],
     };
 
@@ -0,0 +1,101 @@
+mod common;
+
+use common::{errors, generate, parse, try_parse};
+use grit::lexer::{Span, TokenType, Tokenizer};
+use grit::parser::{Expr, Statement};
+use grit::semantic::{SymbolDump, SymbolTable, Type};
+
+#[test]
+fn test_parse_global() {
+    let tokens = Tokenizer::new("global").tokenize().unwrap();
+    assert_eq!(tokens[0].token_type, TokenType::Global);
+
+    let program = parse("global count = 0\nx = 1");
+    assert_eq!(
+        program.statements[0],
+        Statement::Global {
+            name: "count".to_string(),
+            value: Expr::Integer {
+                value: 0,
+                span: Span::new(1, 16, 1, 16),
+            },
+            span: Span::new(1, 1, 1, 12),
+        }
+    );
+    assert_eq!(program.statements[0].to_string(), "global count = 0");
+    assert_eq!(program.statements.len(), 2);
+}
+
+#[test]
+fn test_malformed_globals_are_errors() {
+    assert!(try_parse("global").is_err());
+    assert!(try_parse("global count").is_err());
+    assert!(try_parse("global count 0").is_err());
+    assert!(try_parse("global 1 = 0").is_err());
+}
+
+#[test]
+fn test_symbols_record_globals() {
+    let program = parse("global name = 'grit'\nglobal rate = 1.5");
+    let symbols = SymbolTable::from_program(&program);
+
+    assert_eq!(symbols.global_var("name").unwrap().ty, Type::String);
+    assert_eq!(symbols.global_vars().len(), 2);
+    assert_eq!(symbols.globals(), &["name", "rate"]);
+    assert!(SymbolDump::from_program(&program)
+        .to_table()
+        .contains("global  rate  float   2:1"));
+}
+
+#[test]
+fn test_check_globals() {
+    assert!(errors("global count = 0\nfn bump() {\n  count = count + 1\n  count\n}").is_empty());
+    assert_eq!(
+        errors("fn f() {\n  global count = 0\n  1\n}"),
+        vec!["global 'count' must be declared at the top level"]
+    );
+    assert_eq!(
+        errors("global count = 0\nglobal count = 1"),
+        vec!["global 'count' is already declared"]
+    );
+    assert_eq!(
+        errors("global items = [1, 2]"),
+        vec!["global 'items' must be an int, float, string, or bool, found array<int>"]
+    );
+    assert_eq!(
+        errors("global count = 0\nfn f() {\n  count = 'many'\n  1\n}"),
+        vec!["'count' is declared int but assigned string"]
+    );
+    assert_eq!(
+        errors("global count = 0\ncount, other = 1, 2"),
+        vec!["global 'count' must be assigned on its own"]
+    );
+}
+
+#[test]
+fn test_parameter_hides_global() {
+    let code = generate("global count = 0\nfn show(count) {\n  count + 1\n}");
+
+    assert!(code.contains("fn show(count: i64) -> i64 {\n    count + 1\n}"));
+}
+
+#[test]
+fn test_generate_globals() {
+    let code = generate(
+        "global count = 0\nglobal name = 'start'\n\
+         fn bump(by) {\n  count = count + by\n  count\n}\nbump(2)\nprint('%d %s', count, name)",
+    );
+
+    assert!(code.contains(
+        "thread_local! {\n    #[allow(non_upper_case_globals)]\n    \
+         static count: std::cell::RefCell<i64> = std::cell::RefCell::new(0);\n}"
+    ));
+    assert!(code.contains(
+        "static name: std::cell::RefCell<String> = std::cell::RefCell::new(\"start\".to_string());"
+    ));
+    assert!(code.contains(
+        "count.with(|grit_cell| *grit_cell.borrow_mut() = \
+         count.with(|grit_cell| grit_cell.borrow().clone()) + by);"
+    ));
+    assert!(code.contains("println!(\"{} {}\", count.with(|grit_cell| grit_cell.borrow().clone())"));
+}
@@ -0,0 +1,56 @@
+#[derive(Clone)]
+struct Grid {
+    size: i64,
+    total: i64,
+}
+
+impl Grid {
+    fn new(size: i64) -> Self {
+        Self {
+            size: size,
+            total: 0,
+        }
+    }
+
+    fn fill(&mut self) {
+        let mut row = 0;
+        while row < self.size {
+            let mut col = 0;
+            loop {
+                if row == col {
+                    self.total = self.total + 2;
+                } else {
+                    match col {
+                        0 => {
+                            self.total = self.total + 1;
+                        }
+                        _ => {
+                            let for_cell = row * col;
+                            self.total = self.total + for_cell;
+                        }
+                    }
+                }
+                col = col + 1;
+                if !(col < self.size) {
+                    break;
+                }
+            }
+            row = row + 1;
+        }
+    }
+
+    fn describe(&self) -> String {
+        if self.total > 10 {
+            "full"
+        } else {
+            "sparse"
+        }.to_string()
+    }
+
+}
+
+fn main() {
+    let mut grid = Grid::new(3);
+    grid.fill();
+    println!("{} {}", grid.total, grid.describe());
+}
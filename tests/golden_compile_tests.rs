@@ -0,0 +1,79 @@
+use grit::codegen::source_map::SourceMap;
+use grit::codegen::CodeGenerator;
+use grit::lexer::Tokenizer;
+use grit::parser::Parser;
+use grit::testing::{check_rust, check_source};
+use std::fs;
+use std::path::PathBuf;
+
+/// The programs whose generated Rust must compile: the golden corpus and the examples
+fn corpus() -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for dir in ["tests/golden", "examples"] {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_some_and(|ext| ext == "grit") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+#[test]
+fn test_golden_programs_compile() {
+    let files = corpus();
+    assert!(files.len() >= 10);
+
+    let failures: Vec<String> = files
+        .iter()
+        .filter_map(|path| {
+            let source = fs::read_to_string(path).unwrap();
+            check_source(&source)
+                .unwrap()
+                .err()
+                .map(|message| format!("{}: {}", path.display(), message))
+        })
+        .collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn test_nested_blocks_in_methods_match_golden_output() {
+    let source = fs::read_to_string("tests/golden/nested_blocks.grit").unwrap();
+    let tokens = Tokenizer::new(&source).tokenize().unwrap();
+    let program = Parser::new(tokens).parse().unwrap();
+
+    let expected = fs::read_to_string("tests/golden/nested_blocks.rs").unwrap();
+    assert_eq!(CodeGenerator::generate_program(&program), expected);
+}
+
+#[test]
+fn test_check_rust_reports_first_error() {
+    let result = check_rust(
+        "fn main() {\n    let x: i64 = \"a\";\n}\n",
+        &SourceMap::default(),
+    );
+
+    let message = result.unwrap().unwrap_err();
+    assert!(message.starts_with("generated Rust failed to compile: error[E0308]: mismatched types"));
+}
+
+#[test]
+fn test_check_source_reports_grit_errors() {
+    let parse_error = check_source("fn (").unwrap().unwrap_err();
+    assert!(parse_error.starts_with("Parse error:"));
+
+    let semantic_error = check_source("print('%d', missing)").unwrap().unwrap_err();
+    assert!(semantic_error.contains("missing"));
+}
+
+#[test]
+fn test_check_source_names_grit_line_of_rust_error() {
+    // Adding an integer to a string passes the checks but not rustc
+    let result = check_source("x = 1\ny = 'a' + x");
+
+    let message = result.unwrap().unwrap_err();
+    assert!(message.ends_with("(from line 2)"), "{}", message);
+}
@@ -0,0 +1,261 @@
+use grit::hir::{self, Expr, ExprKind, Statement};
+use grit::lexer::Tokenizer;
+use grit::parser::Parser;
+use grit::semantic::Type;
+use grit::{compile, compile_with_options, CompileOptions};
+
+fn lower(input: &str) -> hir::Program {
+    let program = Parser::from_source(Tokenizer::new(input)).parse().unwrap();
+    hir::lower(&program)
+}
+
+/// Returns the value of the `let` for a name among the statements
+fn let_value<'a>(body: &'a [Statement], name: &str) -> &'a Expr {
+    body.iter()
+        .find_map(|stmt| match stmt {
+            Statement::Let {
+                name: bound, value, ..
+            } if bound == name => Some(value),
+            _ => None,
+        })
+        .unwrap_or_else(|| panic!("no let for '{}'", name))
+}
+
+#[test]
+fn test_definitions_leave_main() {
+    let program = lower(
+        "const N = 3\nglobal count = 0\nclass Point\nfn twice(x: int) -> int {\n  x * 2\n}\n\
+         fn Point > norm() {\n  1\n}\ntest 'twice' {\n  assert(twice(2) == 4)\n}\nx = twice(N)",
+    );
+
+    assert_eq!(program.constants[0].name, "N");
+    assert_eq!(program.globals[0].name, "count");
+    assert_eq!(program.functions[0].name, "twice");
+    assert_eq!(program.functions[0].params[0].ty, Type::Int);
+    assert_eq!(program.functions[0].return_type, Some(Type::Int));
+    assert_eq!(program.methods[0].class_name, "Point");
+    assert_eq!(program.tests[0].name, "twice");
+    assert!(program.symbols.is_class("Point"));
+    assert_eq!(program.main.len(), 1);
+    assert_eq!(let_value(&program.main, "x").ty, Type::Int);
+}
+
+#[test]
+fn test_every_expression_is_typed() {
+    let program = lower("a = [1, 2]\nb = a[0] + 0.5\nc = (a[1] > 1, 'x' + 'y')\nd = -b");
+
+    assert_eq!(
+        let_value(&program.main, "a").ty,
+        Type::Array(Box::new(Type::Int))
+    );
+    let b = let_value(&program.main, "b");
+    assert_eq!(b.ty, Type::Float);
+    match &b.kind {
+        ExprKind::Binary { left, right, .. } => {
+            assert_eq!(left.ty, Type::Int);
+            assert_eq!(right.ty, Type::Float);
+        }
+        other => panic!("expected a binary operation, got {:?}", other),
+    }
+    assert_eq!(
+        let_value(&program.main, "c").ty,
+        Type::Tuple(vec![Type::Bool, Type::String])
+    );
+    assert_eq!(let_value(&program.main, "d").ty, Type::Float);
+}
+
+#[test]
+fn test_annotations_type_declarations() {
+    let program = lower("x: float = 1\nvar n = 0\nn = n + 1");
+
+    match &program.main[0] {
+        Statement::Let { ty, value, .. } => {
+            assert_eq!(*ty, Type::Float);
+            assert_eq!(value.ty, Type::Int);
+        }
+        other => panic!("expected a let, got {:?}", other),
+    }
+    assert!(matches!(
+        program.main[1],
+        Statement::Let { mutable: true, .. }
+    ));
+    match &program.main[2] {
+        Statement::Assign { target, .. } => {
+            assert_eq!(target.kind, ExprKind::Local("n".to_string()));
+            assert_eq!(target.ty, Type::Int);
+        }
+        other => panic!("expected an assignment, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_names_are_resolved() {
+    let program = lower(
+        "const LIMIT = 10\nglobal total = 0\nenum Color { Red, Green }\nclass Point\n\
+         fn Point >> origin() {\n  Point.new()\n}\nfn add() {\n  total = total + LIMIT\n}\n\
+         c = Color.Red\np = Point.origin()",
+    );
+
+    match &program.functions[0].body[0] {
+        Statement::Assign { target, value, .. } => {
+            assert_eq!(target.kind, ExprKind::Global("total".to_string()));
+            match &value.kind {
+                ExprKind::Binary { right, .. } => {
+                    assert_eq!(right.kind, ExprKind::Constant("LIMIT".to_string()));
+                    assert_eq!(right.ty, Type::Int);
+                }
+                other => panic!("expected a binary operation, got {:?}", other),
+            }
+        }
+        other => panic!("expected an assignment, got {:?}", other),
+    }
+
+    let c = let_value(&program.main, "c");
+    assert_eq!(
+        c.kind,
+        ExprKind::Variant {
+            enum_name: "Color".to_string(),
+            variant: "Red".to_string(),
+        }
+    );
+    assert_eq!(c.ty, Type::Enum("Color".to_string()));
+    match &let_value(&program.main, "p").kind {
+        ExprKind::MethodCall { object, method, .. } => {
+            assert_eq!(object.kind, ExprKind::Class("Point".to_string()));
+            assert_eq!(method, "origin");
+        }
+        other => panic!("expected a method call, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_methods_see_self_and_fields() {
+    let program = lower(
+        "class Rect { w: int, h: int }\nfn Rect > area() -> int {\n  self.w * self.h\n}\n\
+         fn Rect > grow() {\n  self.w = self.area()\n}",
+    );
+
+    let area = &program.methods[0].function;
+    match &area.body[0] {
+        Statement::Expression(Expr {
+            kind: ExprKind::Binary { left, .. },
+            ..
+        }) => {
+            assert_eq!(left.ty, Type::Int);
+            match &left.kind {
+                ExprKind::FieldAccess { object, field } => {
+                    assert_eq!(object.kind, ExprKind::Local("self".to_string()));
+                    assert_eq!(object.ty, Type::Class("Rect".to_string()));
+                    assert_eq!(field, "w");
+                }
+                other => panic!("expected a field read, got {:?}", other),
+            }
+        }
+        other => panic!("expected an expression, got {:?}", other),
+    }
+
+    match &program.methods[1].function.body[0] {
+        Statement::FieldAssign { field, value, .. } => {
+            assert_eq!(field, "w");
+            assert!(matches!(value.kind, ExprKind::MethodCall { .. }));
+            assert_eq!(value.ty, Type::Int);
+        }
+        other => panic!("expected a field assignment, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_named_arguments_are_put_in_parameter_order() {
+    let program =
+        lower("fn rect(width, height, depth) {\n  width\n}\nr = rect(1, depth = 3, height = 2)");
+
+    match &let_value(&program.main, "r").kind {
+        ExprKind::Call { function, args } => {
+            assert_eq!(function, "rect");
+            let args: Vec<&ExprKind> = args.iter().map(|arg| &arg.kind).collect();
+            assert_eq!(
+                args,
+                vec![
+                    &ExprKind::Integer(1),
+                    &ExprKind::Integer(2),
+                    &ExprKind::Integer(3)
+                ]
+            );
+        }
+        other => panic!("expected a call, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_scopes_follow_the_checker() {
+    let program =
+        lower("fn sum(nums...) {\n  nums\n}\nif n = 1 + 2 {\n  m = n\n}\nf = |x| x\ny = f(2)\n(1)");
+
+    match &program.functions[0].body[0] {
+        Statement::Expression(nums) => assert_eq!(nums.ty, Type::Array(Box::new(Type::Unknown))),
+        other => panic!("expected an expression, got {:?}", other),
+    }
+    match &program.main[0] {
+        Statement::If { branches, .. } => {
+            assert_eq!(branches[0].0.ty, Type::Bool);
+            assert_eq!(let_value(&branches[0].1, "m").ty, Type::Int);
+        }
+        other => panic!("expected an if, got {:?}", other),
+    }
+    assert!(matches!(
+        let_value(&program.main, "y").kind,
+        ExprKind::CallValue { .. }
+    ));
+    // Parentheses are gone
+    assert!(matches!(
+        program.main.last(),
+        Some(Statement::Expression(Expr {
+            kind: ExprKind::Integer(1),
+            ..
+        }))
+    ));
+}
+
+#[test]
+fn test_compile_lowers_checked_programs_only() {
+    let options = CompileOptions {
+        hir: true,
+        ..CompileOptions::default()
+    };
+    assert_eq!(
+        compile_with_options("x = 1", &options)
+            .hir
+            .unwrap()
+            .main
+            .len(),
+        1
+    );
+    assert!(compile_with_options("const N = 1\nN = 2", &options)
+        .hir
+        .is_none());
+}
+
+#[test]
+fn test_compile_skips_the_hir_unless_asked() {
+    let result = compile("x = 1");
+    assert!(result.hir.is_none());
+    assert!(result.generated_code.is_some());
+}
+
+#[test]
+fn test_signatures_carry_inferred_types() {
+    let program = lower(
+        "class Dog {\n  name: string\n}\nfn Dog > label() {\n  name\n}\nfn Dog > speak() {\n  print('%s', name)\n}\n\
+         fn greet(name) {\n  'hi ' + name\n}\nfn pair(a) {\n  return (a, 1.5)\n}\nx = greet('Bob')\ny = pair(1)",
+    );
+
+    let greet = &program.functions[0];
+    assert_eq!(greet.params[0].ty, Type::String);
+    assert_eq!(greet.return_type, Some(Type::String));
+    assert_eq!(
+        program.functions[1].return_type,
+        Some(Type::Tuple(vec![Type::Int, Type::Float]))
+    );
+    assert_eq!(program.methods[0].function.return_type, Some(Type::String));
+    assert_eq!(program.methods[1].function.return_type, None);
+}
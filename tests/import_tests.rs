@@ -0,0 +1,81 @@
+mod common;
+
+use common::{messages, parse, try_parse};
+use grit::codegen::CodeGenerator;
+use grit::diff::AstDiff;
+use grit::lexer::Span;
+use grit::parser::{ParseError, Statement};
+
+#[test]
+fn test_parse_grouped_import() {
+    let program = parse("import math { sqrt, pow }\nx = 1");
+
+    assert_eq!(
+        program.statements[0],
+        Statement::Import {
+            module: "math".to_string(),
+            names: vec!["sqrt".to_string(), "pow".to_string()],
+            span: Span::new(1, 1, 1, 11),
+        }
+    );
+    assert_eq!(
+        program.statements[0].to_string(),
+        "import math { sqrt, pow }"
+    );
+    assert_eq!(program.statements.len(), 2);
+}
+
+#[test]
+fn test_parse_whole_module_import() {
+    let program = parse("import math\nimport strings {\n  upper,\n  lower\n}");
+
+    assert_eq!(program.statements[0].to_string(), "import math");
+    assert_eq!(
+        program.statements[1].to_string(),
+        "import strings { upper, lower }"
+    );
+}
+
+#[test]
+fn test_malformed_imports_are_errors() {
+    assert!(try_parse("import").is_err());
+    assert!(try_parse("import 'math'").is_err());
+    assert!(try_parse("import math {}").is_err());
+    assert!(try_parse("import math { sqrt pow }").is_err());
+    assert!(matches!(
+        try_parse("import math { sqrt,"),
+        Err(ParseError::UnexpectedEof { .. })
+    ));
+}
+
+#[test]
+fn test_check_imports() {
+    assert!(messages("import math { sqrt, pow }").is_empty());
+    assert_eq!(
+        messages("import math { sqrt, pow, sqrt, sqrt }"),
+        vec!["'sqrt' is imported from 'math' more than once"]
+    );
+    assert_eq!(
+        messages("fn f() {\n  import math\n  1\n}"),
+        vec!["import of 'math' must be at the top level"]
+    );
+}
+
+#[test]
+fn test_generate_leaves_imports_out_of_main() {
+    let code = CodeGenerator::generate_program(&parse("import math { sqrt }\nx = 1"));
+
+    assert!(!code.contains("import"));
+    assert!(!code.contains("use math"));
+    assert!(code.contains("let x = 1;"));
+}
+
+#[test]
+fn test_diff_reports_imported_names() {
+    let old = parse("import math { sqrt }");
+    let new = parse("import math { sqrt, pow }\nimport io");
+    let diff = AstDiff::between(&old, &new);
+
+    assert_eq!(diff.changes.len(), 2);
+    assert_eq!(diff.changes[0].details, vec!["names: sqrt -> sqrt, pow"]);
+}
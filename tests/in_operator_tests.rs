@@ -0,0 +1,66 @@
+mod common;
+
+use common::{errors, generate, parse, try_parse};
+use grit::parser::{BinaryOperator, Expr, Statement};
+
+#[test]
+fn test_parse_in() {
+    let program = parse("found = x in xs");
+
+    match &program.statements[0] {
+        Statement::Assignment {
+            value: Expr::BinaryOp { op, .. },
+            ..
+        } => assert_eq!(*op, BinaryOperator::In),
+        other => panic!("expected a membership test, got {:?}", other),
+    }
+    assert_eq!(program.statements[0].to_string(), "found = (x in xs)");
+}
+
+#[test]
+fn test_in_binds_like_a_comparison() {
+    let program = parse("a = x + 1 in xs && ok\nb = !x in xs");
+
+    assert_eq!(
+        program.statements[0].to_string(),
+        "a = (((x + 1) in xs) && ok)"
+    );
+    assert_eq!(program.statements[1].to_string(), "b = ((!x) in xs)");
+    assert!(try_parse("c = x in").is_err());
+}
+
+#[test]
+fn test_check_in() {
+    assert!(
+        errors("xs = [1, 2]\nm = {'a': 1}\nok = 1 in xs && 'a' in m && 'b' in 'abc'").is_empty()
+    );
+    assert_eq!(
+        errors("n = 5\nok = 1 in n"),
+        vec!["cannot test membership in a value of type int"]
+    );
+    assert_eq!(
+        errors("ok = 1 in 'abc'"),
+        vec!["only a string can be in a string, found int"]
+    );
+}
+
+#[test]
+fn test_generate_in() {
+    let code = generate(
+        "xs = [1, 2, 3]\nm = {'a': 1}\ns = 'hello'\nsub = 'lo'\nx = 2\n\
+         if x in xs {\n  print('%s', 'a' in m)\n}\nprint('%s %s %s', x + 2 in xs, 'ell' in s, sub in s)",
+    );
+
+    assert!(code.contains("if xs.contains(&x) {"));
+    assert!(code.contains("m.contains_key(&\"a\")"));
+    assert!(code.contains("xs.contains(&(x + 2))"));
+    assert!(code.contains("s.contains(\"ell\")"));
+    assert!(code.contains("s.contains(&*sub)"));
+}
+
+#[test]
+fn test_generate_in_within_while_condition() {
+    let code = generate("xs = [1, 2]\nwhile 3 in xs {\n  print('%d', 1)\n}");
+
+    assert!(code.contains("while xs.contains(&3) {"));
+}
@@ -0,0 +1,175 @@
+mod common;
+
+use common::{generate, messages, parse, try_parse};
+use grit::diff::AstDiff;
+use grit::lexer::Span;
+use grit::parser::Statement;
+use grit::semantic::SymbolTable;
+
+const ANIMALS: &str = "class Animal { name, legs }\n\
+                       fn Animal > new(name) {\n  self.name = name\n  self.legs = 4\n}\n\
+                       fn Animal > walk(steps) {\n  legs * steps\n}\n\
+                       class Dog < Animal { breed }\n\
+                       fn Dog > new(name, breed) {\n  self.name = name\n  self.breed = breed\n}\n\
+                       fn Dog > total() {\n  legs + breed\n}\n";
+
+#[test]
+fn test_parse_parent_class() {
+    let program = parse("class Dog < Animal");
+
+    match &program.statements[0] {
+        Statement::ClassDef {
+            name, parent, span, ..
+        } => {
+            assert_eq!(name, "Dog");
+            assert_eq!(parent.as_deref(), Some("Animal"));
+            assert_eq!(*span, Span::new(1, 1, 1, 18));
+        }
+        other => panic!("expected a class, got {:?}", other),
+    }
+    assert_eq!(program.statements[0].to_string(), "class Dog < Animal");
+}
+
+#[test]
+fn test_parse_parent_with_mixins_and_fields() {
+    let program = parse("mixin Loud\nclass Dog < Animal with Loud { breed }");
+
+    assert_eq!(
+        program.statements[1].to_string(),
+        "class Dog < Animal with Loud { breed }"
+    );
+}
+
+#[test]
+fn test_parent_must_be_a_name() {
+    assert!(try_parse("class Dog <").is_err());
+    assert!(try_parse("class Dog < 1").is_err());
+}
+
+#[test]
+fn test_symbols_walk_ancestors() {
+    let symbols = SymbolTable::from_program(&parse(&format!("{}class Puppy < Dog", ANIMALS)));
+
+    assert_eq!(
+        symbols.class("Dog").unwrap().parent.as_deref(),
+        Some("Animal")
+    );
+    let ancestors: Vec<&str> = symbols
+        .ancestors("Puppy")
+        .iter()
+        .map(|class| class.name.as_str())
+        .collect();
+    assert_eq!(ancestors, vec!["Dog", "Animal"]);
+    assert!(symbols.resolve_method("Puppy", "walk").is_some());
+    assert!(symbols.resolve_method("Animal", "total").is_none());
+}
+
+#[test]
+fn test_ancestors_stop_on_cycles() {
+    let symbols = SymbolTable::from_program(&parse("class A < B\nclass B < A\nclass C < Missing"));
+
+    assert_eq!(symbols.ancestors("A").len(), 1);
+    assert!(symbols.ancestors("C").is_empty());
+}
+
+#[test]
+fn test_check_parent_errors() {
+    assert_eq!(
+        messages("class Dog < Animal"),
+        vec!["unknown parent class 'Animal'"]
+    );
+    assert_eq!(
+        messages("mixin Loud\nclass Dog < Loud"),
+        vec!["'Loud' is a mixin, not a class; include it with 'with'"]
+    );
+    assert_eq!(
+        messages("class Dog < Dog"),
+        vec!["class 'Dog' inherits from itself"]
+    );
+    assert_eq!(
+        messages("class A < B\nclass B < A"),
+        vec![
+            "class 'A' inherits from itself",
+            "class 'B' inherits from itself"
+        ]
+    );
+}
+
+#[test]
+fn test_check_inherited_declared_fields() {
+    assert!(messages(ANIMALS).is_empty());
+    assert_eq!(
+        messages(&format!(
+            "{}fn Dog > rename() {{\n  self.owner = 1\n}}",
+            ANIMALS
+        )),
+        vec!["class 'Dog' declares no field 'owner'"]
+    );
+}
+
+#[test]
+fn test_generate_embedded_parent() {
+    let code = generate(ANIMALS);
+
+    assert!(code.contains("#[derive(Clone, Default)]\nstruct Animal {"));
+    assert!(
+        code.contains("#[derive(Clone)]\nstruct Dog {\n    parent: Animal,\n    breed: i64,\n}")
+    );
+    assert!(code.contains(
+        "            parent: Animal { name: name, ..Default::default() },\n            breed: breed,\n"
+    ));
+}
+
+#[test]
+fn test_generate_inherited_field_reads() {
+    let code = generate(ANIMALS);
+
+    assert!(code.contains("self.parent.legs + self.breed"));
+}
+
+#[test]
+fn test_generate_delegating_methods() {
+    let code = generate(ANIMALS);
+
+    assert!(code.contains(
+        "    fn walk(&self, steps: i64) -> i64 {\n        self.parent.walk(steps)\n    }"
+    ));
+    // A method the subclass defines is not delegated
+    assert_eq!(code.matches("fn new(").count(), 2);
+}
+
+#[test]
+fn test_generate_grandparent_literal() {
+    let code = generate(&format!(
+        "{}class Puppy < Dog\nfn Puppy > new() {{\n  self.legs = 3\n}}",
+        ANIMALS
+    ));
+
+    assert!(code.contains("#[derive(Clone, Default)]\nstruct Dog {"));
+    assert!(code.contains(
+        "parent: Dog { parent: Animal { legs: 3, ..Default::default() }, ..Default::default() },"
+    ));
+    assert!(code.contains("        self.parent.total()\n"));
+    assert!(code.contains("        self.parent.walk(steps)\n"));
+}
+
+#[test]
+fn test_generate_unassigned_parent_defaults() {
+    let code = generate(&format!(
+        "{}class Cat < Animal\nfn Cat > new() {{\n  0\n}}",
+        ANIMALS
+    ));
+
+    assert!(code.contains("            parent: Default::default(),\n"));
+}
+
+#[test]
+fn test_diff_reports_parent_change() {
+    let old = parse("class Dog");
+    let new = parse("class Dog < Animal");
+
+    assert_eq!(
+        AstDiff::between(&old, &new).changes[0].details,
+        vec!["parent: (none) -> Animal"]
+    );
+}
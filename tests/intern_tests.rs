@@ -0,0 +1,62 @@
+use grit::lexer::{Interner, Symbol, TokenType, Tokenizer};
+use grit::parser::{Expr, Parser, Statement};
+
+#[test]
+fn test_intern_returns_same_symbol_for_same_text() {
+    let mut interner = Interner::new();
+    let a = interner.intern("count");
+    let b = interner.intern("total");
+    let c = interner.intern("count");
+
+    assert_eq!(a, c);
+    assert_ne!(a, b);
+    assert_eq!(interner.len(), 2);
+    assert_eq!(interner.resolve(a), "count");
+    assert_eq!(interner.resolve(b), "total");
+    assert_eq!(interner.get("total"), Some(b));
+    assert_eq!(interner.get("missing"), None);
+}
+
+#[test]
+fn test_from_tokens_collects_identifiers_and_strings() {
+    let tokens = Tokenizer::new("x = 'hi'\ny = x + 1\nprint('hi')")
+        .tokenize()
+        .unwrap();
+    let interner = Interner::from_tokens(&tokens);
+
+    let names: Vec<&str> = interner.iter().map(|(_, text)| text).collect();
+    assert_eq!(names, vec!["x", "hi", "y", "print"]);
+}
+
+#[test]
+fn test_symbols_are_shared_across_interners() {
+    let mut first = Interner::new();
+    let mut second = Interner::new();
+    assert!(first.is_empty());
+    let a = first.intern("shared_name");
+    let b = second.intern("shared_name");
+
+    assert_eq!(a, b);
+    assert_eq!(a, Symbol::intern("shared_name"));
+    assert_eq!(a.index(), b.index());
+    assert_eq!(a.to_string(), "shared_name");
+    assert_eq!(format!("{a:?}"), "\"shared_name\"");
+}
+
+#[test]
+fn test_tokens_and_identifiers_carry_symbols() {
+    let tokens = Tokenizer::new("total = total + 1").tokenize().unwrap();
+    let TokenType::Identifier(name) = tokens[0].token_type else {
+        panic!("expected an identifier, got {:?}", tokens[0].token_type);
+    };
+    assert_eq!(name, Symbol::intern("total"));
+
+    let program = Parser::new(tokens).parse().unwrap();
+    let Statement::Assignment { value, .. } = &program.statements[0] else {
+        panic!("expected an assignment, got {:?}", program.statements[0]);
+    };
+    let Expr::BinaryOp { left, .. } = value else {
+        panic!("expected a binary operation, got {value:?}");
+    };
+    assert!(matches!(left.as_ref(), Expr::Identifier { name: symbol, .. } if *symbol == name));
+}
@@ -0,0 +1,167 @@
+mod common;
+
+use common::{generate, parse, try_parse};
+use grit::lexer::Span;
+use grit::parser::{BinaryOperator, Expr, ParseError, Statement};
+use grit::semantic::check_program;
+
+fn assigned_value(input: &str) -> Expr {
+    match &parse(input).statements[0] {
+        Statement::Assignment { value, .. } => value.without_spans(),
+        other => panic!("expected an assignment, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_pipe_lambda() {
+    assert_eq!(
+        assigned_value("double = |x| x * 2"),
+        Expr::Lambda {
+            params: vec!["x".to_string()],
+            body: vec![Statement::Expression(Expr::BinaryOp {
+                left: Box::new(Expr::Identifier {
+                    name: "x".into(),
+                    span: Span::default()
+                }),
+                op: BinaryOperator::Multiply,
+                right: Box::new(Expr::Integer {
+                    value: 2,
+                    span: Span::default()
+                }),
+                span: Span::default(),
+            })],
+            span: Span::default(),
+        }
+    );
+}
+
+#[test]
+fn test_parse_lambda_without_params() {
+    for source in ["five = || 5", "five = | | 5"] {
+        assert_eq!(
+            assigned_value(source),
+            Expr::Lambda {
+                params: vec![],
+                body: vec![Statement::Expression(Expr::Integer {
+                    value: 5,
+                    span: Span::default()
+                })],
+                span: Span::default(),
+            }
+        );
+    }
+}
+
+#[test]
+fn test_parse_fn_lambda_with_block_body() {
+    match assigned_value("shift = fn(x, y) {\n  z = x + y\n  z * 2\n}") {
+        Expr::Lambda { params, body, .. } => {
+            assert_eq!(params, vec!["x", "y"]);
+            assert_eq!(body.len(), 2);
+            assert!(matches!(body[0], Statement::Assignment { .. }));
+        }
+        other => panic!("expected a lambda, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_fn_lambda_as_argument() {
+    let program = parse("apply(fn(x) { x + 1 }, 2)");
+
+    match &program.statements[0] {
+        Statement::Expression(Expr::FunctionCall { args, .. }) => {
+            assert!(matches!(args[0], Expr::Lambda { .. }));
+            assert_eq!(
+                args[1].without_spans(),
+                Expr::Integer {
+                    value: 2,
+                    span: Span::default()
+                }
+            );
+        }
+        other => panic!("expected a call, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_pipe_is_still_bitwise_or_between_operands() {
+    assert!(matches!(
+        assigned_value("x = a | b"),
+        Expr::BinaryOp {
+            op: BinaryOperator::BitOr,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_bad_lambda_params_are_errors() {
+    assert!(matches!(
+        try_parse("f = |1| 2"),
+        Err(ParseError::UnexpectedToken { expected, .. }) if expected == "parameter name"
+    ));
+    assert!(matches!(
+        try_parse("f = |a b| 2"),
+        Err(ParseError::UnexpectedToken { expected, .. }) if expected == "',' or '|'"
+    ));
+}
+
+#[test]
+fn test_lambda_display() {
+    assert_eq!(
+        assigned_value("f = |a, b| a + b").to_string(),
+        "|a, b| (a + b)"
+    );
+    assert_eq!(
+        assigned_value("f = fn(x) {\n  y = x\n  y\n}").to_string(),
+        "|x| { y = x; y }"
+    );
+}
+
+#[test]
+fn test_generate_closures() {
+    let code = generate("double = |x| x * 2\nadd = |a, b| a + b\nfive = || 5");
+
+    assert!(code.contains("let double = |x: i64| x * 2;"));
+    assert!(code.contains("let add = |a: i64, b: i64| a + b;"));
+    assert!(code.contains("let five = || 5;"));
+}
+
+#[test]
+fn test_generate_closure_with_block_body() {
+    let code = generate("offset = 10\nshift = fn(x) {\n  y = x + offset\n  y * 2\n}");
+
+    assert!(
+        code.contains("let shift = |x: i64| {\n        let y = x + offset;\n        y * 2\n    };")
+    );
+}
+
+#[test]
+fn test_called_parameter_becomes_a_function_parameter() {
+    let code = generate("fn apply(f, x) {\n  f(x)\n}\nfn combine(g, a) {\n  g(a, a) + a\n}");
+
+    assert!(code.contains("fn apply(f: impl Fn(i64) -> i64, x: i64) -> i64 {"));
+    assert!(code.contains("fn combine(g: impl Fn(i64, i64) -> i64, a: i64) -> i64 {"));
+}
+
+#[test]
+fn test_lambda_parameter_shadows_function_parameter() {
+    let code = generate("fn keep(f) {\n  g = |f| f(1)\n  1\n}");
+
+    assert!(code.contains("fn keep(f: i64) -> i64 {"));
+}
+
+#[test]
+fn test_lambda_body_is_checked() {
+    let diagnostics = check_program(&parse("f = |x| print('%d', 'text')"));
+
+    assert_eq!(diagnostics.len(), 1);
+}
+
+#[test]
+fn test_break_inside_lambda_does_not_see_enclosing_loop() {
+    let diagnostics = check_program(&parse("while true {\n  f = fn() {\n    break\n  }\n}"));
+
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("outside of a loop"));
+}
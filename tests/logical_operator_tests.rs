@@ -0,0 +1,207 @@
+use grit::codegen::CodeGenerator;
+use grit::lexer::{Span, TokenType, Tokenizer};
+use grit::parser::{BinaryOperator, Expr, Parser, UnaryOperator};
+
+fn parse_expr(input: &str) -> Expr {
+    let mut tokenizer = Tokenizer::new(input);
+    let tokens = tokenizer.tokenize().unwrap();
+    let mut parser = Parser::new(tokens);
+    parser.parse_expression_only().unwrap().without_spans()
+}
+
+fn token_types(input: &str) -> Vec<TokenType> {
+    let mut tokenizer = Tokenizer::new(input);
+    tokenizer
+        .tokenize()
+        .unwrap()
+        .into_iter()
+        .map(|token| token.token_type)
+        .collect()
+}
+
+#[test]
+fn test_tokenize_symbolic_logical_operators() {
+    assert_eq!(
+        token_types("&& || !"),
+        vec![
+            TokenType::DoubleAmpersand,
+            TokenType::DoublePipe,
+            TokenType::Bang,
+            TokenType::Eof
+        ]
+    );
+}
+
+#[test]
+fn test_tokenize_word_logical_operators() {
+    assert_eq!(
+        token_types("and or not"),
+        vec![
+            TokenType::And,
+            TokenType::Or,
+            TokenType::Not,
+            TokenType::Eof
+        ]
+    );
+}
+
+#[test]
+fn test_tokenize_bang_vs_not_equal() {
+    assert_eq!(
+        token_types("!a != b"),
+        vec![
+            TokenType::Bang,
+            TokenType::Identifier("a".into()),
+            TokenType::NotEqual,
+            TokenType::Identifier("b".into()),
+            TokenType::Eof
+        ]
+    );
+}
+
+#[test]
+fn test_word_operators_are_not_identifier_prefixes() {
+    assert_eq!(
+        token_types("android order nothing"),
+        vec![
+            TokenType::Identifier("android".into()),
+            TokenType::Identifier("order".into()),
+            TokenType::Identifier("nothing".into()),
+            TokenType::Eof
+        ]
+    );
+}
+
+#[test]
+fn test_word_and_symbolic_forms_parse_identically() {
+    assert_eq!(parse_expr("a and b"), parse_expr("a && b"));
+    assert_eq!(parse_expr("a or b"), parse_expr("a || b"));
+    assert_eq!(parse_expr("not a"), parse_expr("!a"));
+}
+
+#[test]
+fn test_and_binds_tighter_than_or() {
+    let expr = parse_expr("a or b and c");
+
+    match expr {
+        Expr::BinaryOp { op, right, .. } => {
+            assert_eq!(op, BinaryOperator::Or);
+            assert!(matches!(
+                *right,
+                Expr::BinaryOp {
+                    op: BinaryOperator::And,
+                    ..
+                }
+            ));
+        }
+        _ => panic!("Expected BinaryOp"),
+    }
+}
+
+#[test]
+fn test_comparison_binds_tighter_than_and() {
+    let expr = parse_expr("a < b and c > d");
+
+    match expr {
+        Expr::BinaryOp {
+            left, op, right, ..
+        } => {
+            assert_eq!(op, BinaryOperator::And);
+            assert!(matches!(
+                *left,
+                Expr::BinaryOp {
+                    op: BinaryOperator::LessThan,
+                    ..
+                }
+            ));
+            assert!(matches!(
+                *right,
+                Expr::BinaryOp {
+                    op: BinaryOperator::GreaterThan,
+                    ..
+                }
+            ));
+        }
+        _ => panic!("Expected BinaryOp"),
+    }
+}
+
+#[test]
+fn test_not_binds_to_its_operand() {
+    let expr = parse_expr("not a and b");
+
+    match expr {
+        Expr::BinaryOp { left, op, .. } => {
+            assert_eq!(op, BinaryOperator::And);
+            assert_eq!(
+                left.without_spans(),
+                Expr::UnaryOp {
+                    op: UnaryOperator::Not,
+                    operand: Box::new(Expr::Identifier {
+                        name: "a".into(),
+                        span: Span::default()
+                    }),
+                    span: Span::default(),
+                }
+            );
+        }
+        _ => panic!("Expected BinaryOp"),
+    }
+}
+
+#[test]
+fn test_not_applies_to_method_call() {
+    let expr = parse_expr("!list.empty");
+
+    match expr {
+        Expr::UnaryOp { op, operand, .. } => {
+            assert_eq!(op, UnaryOperator::Not);
+            assert!(matches!(*operand, Expr::MethodCall { .. }));
+        }
+        _ => panic!("Expected UnaryOp"),
+    }
+}
+
+#[test]
+fn test_generate_logical_operators() {
+    assert_eq!(
+        CodeGenerator::generate_expression(&parse_expr("a and b or c")),
+        "a && b || c"
+    );
+    assert_eq!(
+        CodeGenerator::generate_expression(&parse_expr("a and (b or c)")),
+        "a && (b || c)"
+    );
+}
+
+#[test]
+fn test_generate_not() {
+    assert_eq!(
+        CodeGenerator::generate_expression(&parse_expr("not done")),
+        "!done"
+    );
+    assert_eq!(
+        CodeGenerator::generate_expression(&Expr::UnaryOp {
+            op: UnaryOperator::Not,
+            operand: Box::new(Expr::BinaryOp {
+                left: Box::new(Expr::Identifier {
+                    name: "a".into(),
+                    span: Span::default()
+                }),
+                op: BinaryOperator::LessThan,
+                right: Box::new(Expr::Identifier {
+                    name: "b".into(),
+                    span: Span::default()
+                }),
+                span: Span::default(),
+            }),
+            span: Span::default(),
+        }),
+        "!(a < b)"
+    );
+}
+
+#[test]
+fn test_display_logical_expression() {
+    assert_eq!(parse_expr("not a or b").to_string(), "((!a) || b)");
+}
@@ -0,0 +1,142 @@
+mod common;
+
+use common::{check, generate, parse, try_parse};
+use grit::lexer::{Span, TokenType, Tokenizer};
+use grit::parser::{ParseError, Statement};
+
+fn tokenize(input: &str) -> Vec<TokenType> {
+    let mut tokenizer = Tokenizer::new(input);
+    tokenizer
+        .tokenize()
+        .unwrap()
+        .into_iter()
+        .map(|token| token.token_type)
+        .collect()
+}
+
+const NESTED: &str = "i = 0
+outer: while i < 3 {
+  j = 0
+  while j < 3 {
+    if j == 1 {
+      continue outer
+    }
+    if i == 2 {
+      break outer
+    }
+    break
+  }
+}";
+
+#[test]
+fn test_tokenize_label_and_keywords() {
+    assert_eq!(
+        tokenize("outer: break continue"),
+        vec![
+            TokenType::Identifier("outer".into()),
+            TokenType::Colon,
+            TokenType::Break,
+            TokenType::Continue,
+            TokenType::Eof,
+        ]
+    );
+}
+
+#[test]
+fn test_parse_labeled_while() {
+    let program = parse(NESTED);
+
+    match &program.statements[1] {
+        Statement::While { label, body, .. } => {
+            assert_eq!(label.as_deref(), Some("outer"));
+            assert!(matches!(body[1], Statement::While { label: None, .. }));
+        }
+        _ => panic!("Expected While"),
+    }
+}
+
+#[test]
+fn test_parse_break_and_continue() {
+    let program = parse("while 1 {\n  break\n  continue outer\n}");
+
+    match &program.statements[0] {
+        Statement::While { body, .. } => {
+            assert_eq!(
+                body[0],
+                Statement::Break {
+                    label: None,
+                    span: Span::new(2, 3, 2, 7),
+                }
+            );
+            assert_eq!(
+                body[1],
+                Statement::Continue {
+                    label: Some("outer".to_string()),
+                    span: Span::new(3, 3, 3, 16),
+                }
+            );
+        }
+        _ => panic!("Expected While"),
+    }
+}
+
+#[test]
+fn test_label_requires_loop() {
+    assert!(matches!(
+        try_parse("outer: x = 1"),
+        Err(ParseError::UnexpectedToken { expected, .. }) if expected == "loop after label"
+    ));
+}
+
+#[test]
+fn test_loop_control_display() {
+    let program = parse(NESTED);
+    assert_eq!(program.statements[1].to_string(), "outer: while (i < 3)");
+
+    let program = parse("while 1 {\n  break outer\n  continue\n}");
+    match &program.statements[0] {
+        Statement::While { body, .. } => {
+            assert_eq!(body[0].to_string(), "break outer");
+            assert_eq!(body[1].to_string(), "continue");
+        }
+        _ => panic!("Expected While"),
+    }
+}
+
+#[test]
+fn test_generate_labeled_loops() {
+    let code = generate(NESTED);
+    assert!(code.contains("'outer: while i < 3 {"));
+    assert!(code.contains("continue 'outer;"));
+    assert!(code.contains("break 'outer;"));
+    assert!(code.contains("break;"));
+}
+
+#[test]
+fn test_nested_labels_are_valid() {
+    assert!(check(NESTED).is_empty());
+}
+
+#[test]
+fn test_break_outside_loop() {
+    let diagnostics = check("break");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "'break' outside of a loop");
+}
+
+#[test]
+fn test_unknown_label() {
+    let diagnostics = check("while 1 {\n  continue outer\n}");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "'continue outer' does not match an enclosing loop label"
+    );
+    assert_eq!(diagnostics[0].span.start_line, 2);
+}
+
+#[test]
+fn test_function_body_does_not_see_outer_loops() {
+    let diagnostics = check("outer: while 1 {\n  fn f() {\n    break outer\n  }\n}");
+    assert_eq!(diagnostics.len(), 1);
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use common::{generate, messages, parse, try_parse};
+use grit::lexer::{TokenType, Tokenizer};
+use grit::parser::{ParseError, Statement};
+
+#[test]
+fn test_tokenize_loop_keyword() {
+    let tokens = Tokenizer::new("loop looping").tokenize().unwrap();
+
+    assert_eq!(tokens[0].token_type, TokenType::Loop);
+    assert_eq!(
+        tokens[1].token_type,
+        TokenType::Identifier("looping".into())
+    );
+}
+
+#[test]
+fn test_parse_loop() {
+    let program = parse("loop {\n  break\n}\nx = 1");
+
+    assert_eq!(program.statements.len(), 2);
+    match &program.statements[0] {
+        Statement::Loop { body, label, .. } => {
+            assert_eq!(body.len(), 1);
+            assert_eq!(*label, None);
+        }
+        other => panic!("expected a loop, got {:?}", other),
+    }
+    assert_eq!(program.statements[0].to_string(), "loop");
+}
+
+#[test]
+fn test_parse_labeled_loop() {
+    let program = parse("outer: loop {\n  loop {\n    break outer\n  }\n}");
+
+    match &program.statements[0] {
+        Statement::Loop { body, label, .. } => {
+            assert_eq!(label.as_deref(), Some("outer"));
+            assert!(matches!(body[0], Statement::Loop { label: None, .. }));
+        }
+        other => panic!("expected a loop, got {:?}", other),
+    }
+    assert_eq!(program.statements[0].to_string(), "outer: loop");
+}
+
+#[test]
+fn test_loop_requires_body() {
+    assert!(matches!(
+        try_parse("loop x"),
+        Err(ParseError::UnexpectedToken { expected, .. }) if expected == "'{'"
+    ));
+}
+
+#[test]
+fn test_generate_loop() {
+    let code = generate("loop {\n  print('%d', 1)\n  break\n}");
+
+    assert!(code.contains("loop {\n        println!(\"{}\", 1);\n        break;\n    }"));
+}
+
+#[test]
+fn test_generate_labeled_loop() {
+    let code = generate("outer: loop {\n  loop {\n    continue outer\n  }\n}");
+
+    assert!(code.contains("'outer: loop {\n        loop {"));
+    assert!(code.contains("continue 'outer;"));
+}
+
+#[test]
+fn test_generate_loop_with_return_in_function() {
+    let code = generate("fn f(n) {\n  loop {\n    return n\n  }\n}");
+
+    assert!(code.contains("fn f(n: i64) -> i64 {\n    loop {\n        return n;\n    }\n}"));
+}
+
+#[test]
+fn test_loop_control_inside_loop_is_valid() {
+    assert!(messages("outer: loop {\n  loop {\n    break outer\n  }\n  continue\n}").is_empty());
+    assert_eq!(
+        messages("loop {\n  break inner\n}"),
+        vec!["'break inner' does not match an enclosing loop label"]
+    );
+}
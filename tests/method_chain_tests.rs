@@ -0,0 +1,126 @@
+mod common;
+
+use common::{generate, parse, try_parse};
+use grit::lexer::Span;
+use grit::parser::{Expr, Statement};
+
+fn expression(input: &str) -> Expr {
+    match parse(input).statements.remove(0) {
+        Statement::Expression(expr) => expr.without_spans(),
+        other => panic!("expected an expression, got {:?}", other),
+    }
+}
+
+fn call(object: Expr, method: &str, args: Vec<Expr>) -> Expr {
+    Expr::MethodCall {
+        object: Box::new(object),
+        method: method.to_string(),
+        args,
+        span: Span::default(),
+    }
+}
+
+const POINT: &str = "fn Point > new(x, y) {\n  self.x = x\n  self.y = y\n}\n\
+                     fn Point > scaled(factor) {\n  Point.new(1, 2)\n}\n\
+                     fn Point > norm() -> int {\n  x + y\n}\n";
+
+#[test]
+fn test_parse_chained_calls_nest_left_to_right() {
+    let a = Expr::Identifier {
+        name: "a".into(),
+        span: Span::default(),
+    };
+
+    assert_eq!(
+        expression("a.b().c(1).d"),
+        call(
+            call(
+                call(a, "b", vec![]),
+                "c",
+                vec![Expr::Integer {
+                    value: 1,
+                    span: Span::default()
+                }]
+            ),
+            "d",
+            vec![]
+        )
+    );
+}
+
+#[test]
+fn test_parse_call_result_as_receiver() {
+    match expression("make_point(1, 2).norm()") {
+        Expr::MethodCall { object, method, .. } => {
+            assert!(matches!(*object, Expr::FunctionCall { ref name, .. } if name == "make_point"));
+            assert_eq!(method, "norm");
+        }
+        other => panic!("expected a method call, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_chain_continued_on_next_lines() {
+    let program = parse("p = Point.new(1, 2)\n  .scaled(2)\n\n  .norm()\nprint('%d', p)");
+
+    assert_eq!(program.statements.len(), 2);
+    assert_eq!(
+        program.statements[0].to_string(),
+        "p = Point.new(1, 2).scaled(2).norm()"
+    );
+}
+
+#[test]
+fn test_leading_dot_only_continues_an_expression() {
+    assert_eq!(parse("x = y\n\n.norm()").statements.len(), 1);
+    assert!(try_parse(".norm()").is_err());
+}
+
+#[test]
+fn test_named_arguments_inside_chained_method_arguments() {
+    match expression("p.scaled(factor(n = 2)).norm()") {
+        Expr::MethodCall { object, .. } => match *object {
+            Expr::MethodCall { args, .. } => {
+                assert!(matches!(&args[0], Expr::FunctionCall { args, .. }
+                    if matches!(args[0], Expr::NamedArg { .. })));
+            }
+            other => panic!("expected a method call, got {:?}", other),
+        },
+        other => panic!("expected a method call, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_generate_chained_calls() {
+    let code = generate(&format!(
+        "{}p = Point.new(1, 2)\nprint('%d', p.scaled(2).norm())",
+        POINT
+    ));
+
+    assert!(code.contains("p.scaled(2).norm()"));
+}
+
+#[test]
+fn test_generate_calls_on_static_call_result() {
+    let code = generate(&format!("{}print('%d', Point.new(1, 2).norm)", POINT));
+
+    assert!(code.contains("Point::new(1, 2).norm()"));
+}
+
+#[test]
+fn test_generate_calls_on_function_result() {
+    let code = generate("fn make() {\n  [1, 2]\n}\nprint('%d', make().len())");
+
+    assert!(code.contains("make().len()"));
+}
+
+#[test]
+fn test_chained_receiver_resolves_callee() {
+    let code = generate(&format!(
+        "fn Point > label(name: string) -> string {{\n  name\n}}\n{}print('%s', Point.new(1, 2).label('a'))",
+        POINT
+    ));
+
+    // The callee's annotated parameter converts the argument
+    assert!(code.contains("Point::new(1, 2).label(\"a\".to_string())"));
+}
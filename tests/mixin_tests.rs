@@ -0,0 +1,209 @@
+mod common;
+
+use common::{generate, parse, try_parse};
+use grit::lexer::{Span, TokenType, Tokenizer};
+use grit::parser::{ParseError, Statement};
+use grit::semantic::{check_program, SymbolTable};
+
+const WIDGETS: &str = "mixin Clickable
+
+fn Clickable > click {
+  clicks + 1
+}
+
+fn Clickable > label {
+  0
+}
+
+mixin Drawable
+
+fn Drawable > area {
+  width * height
+}
+
+fn Drawable > label {
+  1
+}
+
+class Button with Clickable, Drawable
+
+fn Button > new(w, h) {
+  self.width = w
+  self.height = h
+  self.clicks = 0
+}
+
+fn Button > area {
+  width
+}
+
+b = Button.new(2, 3)
+print('%d %d %d', b.click, b.area, b.label)
+";
+
+#[test]
+fn test_tokenize_mixin_keywords() {
+    let mut tokenizer = Tokenizer::new("mixin with");
+    let types: Vec<TokenType> = tokenizer
+        .tokenize()
+        .unwrap()
+        .into_iter()
+        .map(|token| token.token_type)
+        .collect();
+    assert_eq!(
+        types,
+        vec![TokenType::Mixin, TokenType::With, TokenType::Eof]
+    );
+}
+
+#[test]
+fn test_parse_class_with_mixins() {
+    let program = parse("class Button with Clickable, Drawable");
+
+    assert_eq!(
+        program.statements,
+        vec![Statement::ClassDef {
+            name: "Button".to_string(),
+            mixins: vec!["Clickable".to_string(), "Drawable".to_string()],
+            fields: vec![],
+            parent: None,
+            traits: vec![],
+            docs: Vec::new(),
+            span: Span::new(1, 1, 1, 37),
+        }]
+    );
+    assert_eq!(
+        program.statements[0].to_string(),
+        "class Button with Clickable, Drawable"
+    );
+}
+
+#[test]
+fn test_parse_mixin_def() {
+    let program = parse("mixin Clickable\n");
+
+    assert_eq!(
+        program.statements,
+        vec![Statement::MixinDef {
+            name: "Clickable".to_string(),
+            span: Span::new(1, 1, 1, 15),
+        }]
+    );
+    assert_eq!(program.statements[0].to_string(), "mixin Clickable");
+}
+
+#[test]
+fn test_with_requires_mixin_name() {
+    assert!(matches!(
+        try_parse("class Button with"),
+        Err(ParseError::UnexpectedToken { expected, .. }) if expected == "mixin name"
+    ));
+}
+
+#[test]
+fn test_mixin_methods_belong_to_mixin() {
+    let symbols = SymbolTable::from_program(&parse(WIDGETS));
+
+    assert!(symbols.is_mixin("Clickable"));
+    assert!(!symbols.is_class("Clickable"));
+    assert_eq!(
+        symbols.class("Button").unwrap().mixins,
+        vec!["Clickable", "Drawable"]
+    );
+    assert!(symbols.mixin("Drawable").unwrap().method("area").is_some());
+}
+
+#[test]
+fn test_resolve_method_order() {
+    let symbols = SymbolTable::from_program(&parse(WIDGETS));
+
+    // Own method, first mixin, then later mixins
+    assert!(symbols.class("Button").unwrap().method("area").is_some());
+    assert!(symbols.resolve_method("Button", "click").is_some());
+    assert!(symbols.resolve_method("Button", "label").is_some());
+    assert!(symbols.resolve_method("Button", "missing").is_none());
+    assert!(symbols.resolve_method("Clickable", "click").is_none());
+}
+
+#[test]
+fn test_generate_copies_mixin_methods() {
+    let code = generate(WIDGETS);
+
+    assert!(code.contains("struct Button {"));
+    assert!(!code.contains("struct Clickable"));
+    assert!(!code.contains("struct Drawable"));
+    assert!(code.contains("    fn click(&self) -> i64 {\n        self.clicks + 1\n"));
+    assert!(code.contains("let b = Button::new(2, 3);"));
+}
+
+#[test]
+fn test_class_method_overrides_mixin() {
+    let code = generate(WIDGETS);
+
+    assert_eq!(code.matches("fn area(").count(), 1);
+    assert!(code.contains("fn area(&self) -> i64 {\n        self.width\n"));
+}
+
+#[test]
+fn test_first_mixin_wins_conflicts() {
+    let code = generate(WIDGETS);
+
+    assert_eq!(code.matches("fn label(").count(), 1);
+    assert!(code.contains("fn label(&self) -> i64 {\n        0\n"));
+}
+
+#[test]
+fn test_mixin_constructor_is_not_copied() {
+    let code = generate(
+        "mixin Counter\nfn Counter > new {\n  self.count = 0\n}\nclass Foo with Counter\nfn Foo > new {\n  self.a = 1\n}",
+    );
+
+    assert_eq!(code.matches("fn new(").count(), 1);
+    assert!(!code.contains("count"));
+}
+
+#[test]
+fn test_valid_mixins_have_no_diagnostics() {
+    assert!(check_program(&parse(WIDGETS)).is_empty());
+}
+
+#[test]
+fn test_unknown_mixin() {
+    let diagnostics = check_program(&parse("class Button with Clickable"));
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "unknown mixin 'Clickable'");
+    assert_eq!(diagnostics[0].span.start_line, 1);
+}
+
+#[test]
+fn test_class_used_as_mixin() {
+    let diagnostics = check_program(&parse("class Base\nclass Button with Base"));
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "'Base' is a class, not a mixin");
+}
+
+#[test]
+fn test_duplicate_mixin() {
+    let diagnostics = check_program(&parse(
+        "mixin Clickable\nclass Button with Clickable, Clickable",
+    ));
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "class 'Button' includes mixin 'Clickable' more than once"
+    );
+}
+
+#[test]
+fn test_name_declared_as_class_and_mixin() {
+    let diagnostics = check_program(&parse("class Shape\nmixin Shape"));
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "'Shape' is declared as both a class and a mixin"
+    );
+}
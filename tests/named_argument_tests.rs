@@ -0,0 +1,125 @@
+mod common;
+
+use common::{generate, messages, parse, try_parse};
+use grit::lexer::Span;
+use grit::parser::{Expr, ParseError, Statement};
+
+const RECT: &str = "fn rect(width, height) {\n  width * height\n}\n";
+
+#[test]
+fn test_parse_named_arguments() {
+    let program = parse("rect(3, height = 4)");
+
+    match &program.statements[0] {
+        Statement::Expression(Expr::FunctionCall { args, .. }) => assert_eq!(
+            args,
+            &[
+                Expr::Integer {
+                    value: 3,
+                    span: Span::new(1, 6, 1, 6)
+                },
+                Expr::NamedArg {
+                    name: "height".to_string(),
+                    value: Box::new(Expr::Integer {
+                        value: 4,
+                        span: Span::new(1, 18, 1, 18)
+                    }),
+                    span: Span::new(1, 9, 1, 18),
+                },
+            ]
+        ),
+        other => panic!("expected a call, got {:?}", other),
+    }
+    assert_eq!(program.statements[0].to_string(), "rect(3, height = 4)");
+}
+
+#[test]
+fn test_comparison_argument_is_not_named() {
+    let program = parse("check(a == 4)");
+
+    match &program.statements[0] {
+        Statement::Expression(Expr::FunctionCall { args, .. }) => {
+            assert!(matches!(args[0], Expr::BinaryOp { .. }))
+        }
+        other => panic!("expected a call, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_generate_reorders_named_arguments() {
+    let code = generate(&format!(
+        "{}a = rect(height = 4, width = 3)\nb = rect(3, height = 4)",
+        RECT
+    ));
+
+    assert!(code.contains("let a = rect(3, 4);"));
+    assert!(code.contains("let b = rect(3, 4);"));
+}
+
+#[test]
+fn test_named_arguments_before_variadic_extras() {
+    let code = generate("fn tag(id, extras...) {\n  id\n}\nt = tag(id = 7)");
+
+    assert!(code.contains("let t = tag(7, &[]);"));
+}
+
+#[test]
+fn test_valid_named_call_has_no_errors() {
+    assert!(messages(&format!("{}rect(height = 4, width = 3)", RECT)).is_empty());
+}
+
+#[test]
+fn test_unknown_named_argument_is_an_error() {
+    assert_eq!(
+        messages(&format!("{}rect(width = 3, depth = 4)", RECT)),
+        vec!["rect() has no parameter named 'depth'"]
+    );
+}
+
+#[test]
+fn test_duplicate_named_argument_is_an_error() {
+    assert_eq!(
+        messages(&format!("{}rect(3, width = 4)", RECT)),
+        vec!["rect() is given argument 'width' more than once"]
+    );
+    assert_eq!(
+        messages(&format!("{}rect(width = 3, width = 4)", RECT)),
+        vec!["rect() is given argument 'width' more than once"]
+    );
+}
+
+#[test]
+fn test_positional_after_named_is_an_error() {
+    assert_eq!(
+        messages(&format!("{}rect(width = 3, 4)", RECT)),
+        vec!["positional argument after named arguments in call to rect()"]
+    );
+}
+
+#[test]
+fn test_missing_argument_is_an_error() {
+    assert_eq!(
+        messages(&format!("{}rect(height = 4)", RECT)),
+        vec!["rect() is missing argument 'width'"]
+    );
+}
+
+#[test]
+fn test_named_arguments_need_a_known_function() {
+    assert_eq!(
+        messages("print(format = 'x')"),
+        vec!["print() does not take named arguments"]
+    );
+    assert_eq!(
+        messages(&format!("{}rect = |a| a\nrect(a = 1)", RECT)),
+        vec!["rect() does not take named arguments"]
+    );
+}
+
+#[test]
+fn test_method_calls_take_positional_arguments_only() {
+    assert!(matches!(
+        try_parse("p.move(x = 1)"),
+        Err(ParseError::UnexpectedToken { .. })
+    ));
+}
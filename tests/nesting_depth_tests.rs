@@ -0,0 +1,121 @@
+use grit::codegen::CodeGenerator;
+use grit::lexer::{Span, Tokenizer};
+use grit::parser::{ParseError, ParseResult, Parser, Program, DEFAULT_MAX_DEPTH};
+
+fn parse_with_limit(input: &str, max_depth: usize) -> ParseResult<Program> {
+    let tokens = Tokenizer::new(input).tokenize().unwrap();
+    Parser::new(tokens).with_max_depth(max_depth).parse()
+}
+
+fn parens(depth: usize) -> String {
+    format!("x = {}1{}", "(".repeat(depth), ")".repeat(depth))
+}
+
+fn nested_ifs(depth: usize) -> String {
+    let mut source = String::new();
+    for _ in 0..depth {
+        source.push_str("if true {\n");
+    }
+    source.push_str("x = 1\n");
+    source.push_str(&"}\n".repeat(depth));
+    source
+}
+
+#[test]
+fn test_default_limit() {
+    let tokens = Tokenizer::new("x = 1").tokenize().unwrap();
+
+    assert_eq!(Parser::new(tokens).max_depth(), DEFAULT_MAX_DEPTH);
+}
+
+#[test]
+fn test_deep_parentheses_are_an_error() {
+    let err = parse_with_limit(&parens(100_000), DEFAULT_MAX_DEPTH).unwrap_err();
+
+    assert!(matches!(
+        err,
+        ParseError::TooDeep {
+            limit: DEFAULT_MAX_DEPTH,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_nesting_within_the_limit_parses() {
+    // The assignment is one level and the outer expression another
+    let program = parse_with_limit(&parens(DEFAULT_MAX_DEPTH - 2), DEFAULT_MAX_DEPTH).unwrap();
+    assert!(CodeGenerator::generate_program(&program).contains("let x = ((("));
+
+    assert!(parse_with_limit(&nested_ifs(40), DEFAULT_MAX_DEPTH).is_ok());
+}
+
+#[test]
+fn test_custom_limit() {
+    assert!(parse_with_limit(&parens(3), 5).is_ok());
+    assert_eq!(
+        parse_with_limit(&parens(4), 5),
+        Err(ParseError::TooDeep {
+            limit: 5,
+            span: Span::new(1, 9, 1, 9),
+        })
+    );
+}
+
+#[test]
+fn test_blocks_count_toward_the_limit() {
+    assert!(parse_with_limit(&nested_ifs(4), 6).is_ok());
+    assert!(matches!(
+        parse_with_limit(&nested_ifs(5), 6),
+        Err(ParseError::TooDeep { limit: 6, .. })
+    ));
+}
+
+#[test]
+fn test_long_operator_chains_are_an_error() {
+    let sum = format!("x = 1{}", " + 1".repeat(50_000));
+    assert!(matches!(
+        parse_with_limit(&sum, DEFAULT_MAX_DEPTH),
+        Err(ParseError::TooDeep {
+            limit: DEFAULT_MAX_DEPTH,
+            ..
+        })
+    ));
+
+    let calls = format!("x = items{}", ".first()".repeat(50_000));
+    assert!(matches!(
+        parse_with_limit(&calls, DEFAULT_MAX_DEPTH),
+        Err(ParseError::TooDeep { .. })
+    ));
+}
+
+#[test]
+fn test_operator_chains_within_the_limit_compile() {
+    // The assignment and its value take two levels, each `+` one more
+    let sum = format!("x = 1{}", " + 1".repeat(DEFAULT_MAX_DEPTH - 2));
+    let program = parse_with_limit(&sum, DEFAULT_MAX_DEPTH).unwrap();
+
+    assert!(grit::semantic::check_program(&program).is_empty());
+    assert!(CodeGenerator::generate_program(&program).contains("let x = 1 + 1 + 1"));
+    assert!(parse_with_limit(&format!("{} + 1", sum), DEFAULT_MAX_DEPTH).is_err());
+}
+
+#[test]
+fn test_too_deep_message() {
+    let err = parse_with_limit("x = -(-1)", 3).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "Nesting deeper than 3 levels at line 1, column 7"
+    );
+}
+
+#[test]
+fn test_recovery_continues_after_too_deep() {
+    let source = format!("{}\ny = 1", parens(50));
+    let tokens = Tokenizer::new(&source).tokenize().unwrap();
+    let outcome = Parser::new(tokens).with_max_depth(10).parse_all();
+
+    assert_eq!(outcome.errors.len(), 1);
+    assert_eq!(outcome.program.statements[0].to_string(), "y = 1");
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use common::parse;
+use grit::codegen::CodeGenerator;
+use grit::lexer::{Span, TokenType, Tokenizer};
+use grit::parser::{Expr, Statement};
+use grit::semantic::check_program;
+
+#[test]
+fn test_tokenize_nil_keyword() {
+    let tokens = Tokenizer::new("nil nils").tokenize().unwrap();
+    assert_eq!(tokens[0].token_type, TokenType::Nil);
+    assert_eq!(tokens[1].token_type, TokenType::Identifier("nils".into()));
+}
+
+#[test]
+fn test_parse_nil_literal() {
+    let program = parse("x = nil");
+    match &program.statements[0] {
+        Statement::Assignment { value, .. } => assert_eq!(
+            value.without_spans(),
+            Expr::Nil {
+                span: Span::default()
+            }
+        ),
+        other => panic!("Expected assignment, got {:?}", other),
+    }
+    assert_eq!(program.to_string(), "x = nil");
+}
+
+#[test]
+fn test_nil_generates_empty_option() {
+    assert_eq!(
+        CodeGenerator::generate_expression(&Expr::Nil {
+            span: Span::default()
+        }),
+        "None::<i64>"
+    );
+    let code = CodeGenerator::generate_program(&parse("x = nil\nprint('%s', x)"));
+    assert!(code.contains("let x = None::<i64>;"));
+    assert!(code.contains("println!(\"{}\", \"nil\");"));
+}
+
+#[test]
+fn test_comparing_nil_values() {
+    let code = CodeGenerator::generate_program(&parse(
+        "x = nil\na = x == nil\nb = nil != x\nc = nil == nil",
+    ));
+    assert!(code.contains("let a = x.is_none();"));
+    assert!(code.contains("let b = x.is_some();"));
+    assert!(code.contains("let c = true;"));
+}
+
+#[test]
+fn test_comparing_non_nil_values_is_constant() {
+    let code = CodeGenerator::generate_program(&parse("y = 5\na = y == nil\nb = y != nil"));
+    assert!(code.contains("let a = false;"));
+    assert!(code.contains("let b = true;"));
+}
+
+#[test]
+fn test_nil_type() {
+    let code = CodeGenerator::generate_program(&parse("t = typeof(nil)"));
+    assert!(code.contains("let t = \"nil\";"));
+
+    let diagnostics = check_program(&parse("print('%d', nil)"));
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("type nil"));
+}
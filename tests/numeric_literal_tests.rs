@@ -0,0 +1,74 @@
+use grit::lexer::{LexError, TokenType, Tokenizer};
+
+fn first_token(input: &str) -> Result<TokenType, LexError> {
+    Tokenizer::new(input)
+        .next_token()
+        .map(|token| token.token_type)
+}
+
+#[test]
+fn test_integer_with_underscores() {
+    assert_eq!(first_token("1_000_000"), Ok(TokenType::Integer(1_000_000)));
+    assert_eq!(first_token("1_0"), Ok(TokenType::Integer(10)));
+}
+
+#[test]
+fn test_float_with_underscores() {
+    assert_eq!(first_token("1.234_567"), Ok(TokenType::Float(1.234_567)));
+    assert_eq!(first_token("1_000.5"), Ok(TokenType::Float(1_000.5)));
+}
+
+#[test]
+fn test_underscore_literal_spans_whole_token() {
+    let tokens = Tokenizer::new("1_000 + 2").tokenize().unwrap();
+    assert_eq!(tokens[1].token_type, TokenType::Plus);
+    assert_eq!(tokens[1].column, 7);
+}
+
+#[test]
+fn test_trailing_underscore_rejected() {
+    assert_eq!(
+        first_token("100_"),
+        Err(LexError::MisplacedUnderscore { line: 1, column: 4 })
+    );
+    assert_eq!(
+        first_token("1.5_ + 2"),
+        Err(LexError::MisplacedUnderscore { line: 1, column: 4 })
+    );
+}
+
+#[test]
+fn test_underscore_next_to_decimal_point_rejected() {
+    assert_eq!(
+        first_token("1_.5"),
+        Err(LexError::MisplacedUnderscore { line: 1, column: 2 })
+    );
+    assert_eq!(
+        first_token("3._14"),
+        Err(LexError::MisplacedUnderscore { line: 1, column: 3 })
+    );
+}
+
+#[test]
+fn test_doubled_underscore_rejected() {
+    assert_eq!(
+        first_token("1__000"),
+        Err(LexError::MisplacedUnderscore { line: 1, column: 2 })
+    );
+}
+
+#[test]
+fn test_misplaced_underscore_message() {
+    assert_eq!(
+        first_token("7_").unwrap_err().to_string(),
+        "Misplaced '_' in number literal at line 1, column 2: underscores must be between digits"
+    );
+}
+
+#[test]
+fn test_leading_underscore_is_an_identifier() {
+    assert_eq!(
+        first_token("_100"),
+        Ok(TokenType::Identifier("_100".into()))
+    );
+}
@@ -0,0 +1,147 @@
+mod common;
+
+use common::parse;
+use grit::codegen::CodeGenerator;
+use grit::optimize::{optimize, Pass, Pipeline};
+use grit::parser::printer::print_program;
+
+/// Asserts that running passes over a program prints as the expected source
+fn assert_snapshot(passes: &[Pass], before: &str, after: &str) {
+    let optimized = Pipeline::new(passes.to_vec()).run(parse(before));
+    assert_eq!(print_program(&optimized), after);
+}
+
+#[test]
+fn test_statements_after_return() {
+    assert_snapshot(
+        &[Pass::UnreachableCode],
+        "fn f(n) {\n  return n\n  print('never')\n  n + 1\n}\nprint('%d', f(1))",
+        "fn f(n) {\n  return n\n}\n\nprint('%d', f(1))\n",
+    );
+}
+
+#[test]
+fn test_statements_after_break_and_continue() {
+    assert_snapshot(
+        &[Pass::UnreachableCode],
+        "i = 0\nwhile i < 10 {\n  i = i + 1\n  if i < 5 {\n    continue\n    print('skipped')\n  }\n  break\n  print('done')\n}",
+        "i = 0\nwhile i < 10 {\n  i = i + 1\n  if i < 5 {\n    continue\n  }\n  break\n}\n",
+    );
+}
+
+#[test]
+fn test_if_returning_from_every_branch() {
+    assert_snapshot(
+        &[Pass::UnreachableCode],
+        "fn sign(n) {\n  if n < 0 {\n    return -1\n  } elif n == 0 {\n    return 0\n  } else {\n    return 1\n  }\n  print('unreachable')\n}",
+        "fn sign(n) {\n  if n < 0 {\n    return -1\n  } elif n == 0 {\n    return 0\n  } else {\n    return 1\n  }\n}\n",
+    );
+    // Without an `else`, the code after the `if` runs when no branch does
+    assert_snapshot(
+        &[Pass::UnreachableCode],
+        "fn f(n) {\n  if n < 0 {\n    return 0\n  }\n  n\n}",
+        "fn f(n) {\n  if n < 0 {\n    return 0\n  }\n  n\n}\n",
+    );
+}
+
+#[test]
+fn test_if_false_branches() {
+    assert_snapshot(
+        &[Pass::ConstantBranches],
+        "x = 1\nif false {\n  print('never')\n}\nif false {\n  x = 2\n} elif x > 0 {\n  x = 3\n} else {\n  x = 4\n}\nif !true {\n  x = 5\n} else {\n  x = 6\n}",
+        "x = 1\nif x > 0 {\n  x = 3\n} else {\n  x = 4\n}\n{\n  x = 6\n}\n",
+    );
+}
+
+#[test]
+fn test_constant_true_branch_ends_chain() {
+    assert_snapshot(
+        &[Pass::ConstantBranches],
+        "const DEBUG = 1 > 2\nconst VERBOSE = !DEBUG\nx = 1\nif x > 0 {\n  print('a')\n} elif DEBUG {\n  print('b')\n} elif VERBOSE {\n  print('c')\n} elif x < 0 {\n  print('d')\n}",
+        "const DEBUG = 1 > 2\nconst VERBOSE = !DEBUG\nx = 1\nif x > 0 {\n  print('a')\n} else {\n  print('c')\n}\n",
+    );
+}
+
+#[test]
+fn test_unused_functions() {
+    assert_snapshot(
+        &[Pass::UnusedFunctions],
+        "fn helper(n) {\n  n * 2\n}\nfn used(n) {\n  helper(n)\n}\nfn unused() {\n  used(1)\n}\nfn passed(n) {\n  n\n}\nfn in_test() {\n  1\n}\nf = passed\nprint('%d %d', used(2), f(3))\ntest 'helper' {\n  in_test()\n}",
+        "fn helper(n) {\n  n * 2\n}\n\nfn used(n) {\n  helper(n)\n}\n\nfn passed(n) {\n  n\n}\n\nfn in_test() {\n  1\n}\n\nf = passed\nprint('%d %d', used(2), f(3))\n\ntest 'helper' {\n  in_test()\n}\n",
+    );
+}
+
+#[test]
+fn test_pipeline_order() {
+    let source = "fn debug() {\n  print('debug')\n}\nif false {\n  debug()\n}\nprint('done')";
+
+    // Functions only a constant `false` branch calls go once the branch has gone
+    assert_snapshot(
+        &[Pass::ConstantBranches, Pass::UnusedFunctions],
+        source,
+        "print('done')\n",
+    );
+    assert_snapshot(
+        &[Pass::UnusedFunctions, Pass::ConstantBranches],
+        source,
+        "fn debug() {\n  print('debug')\n}\n\nprint('done')\n",
+    );
+    assert_eq!(print_program(&optimize(parse(source))), "print('done')\n");
+}
+
+#[test]
+fn test_default_pipeline() {
+    assert_eq!(
+        Pipeline::default().passes(),
+        [
+            Pass::ConstantBranches,
+            Pass::UnreachableCode,
+            Pass::UnusedFunctions
+        ]
+    );
+    assert_eq!(
+        Pipeline::new(Vec::new()).run(parse("x = 1")),
+        parse("x = 1")
+    );
+}
+
+#[test]
+fn test_pass_names() {
+    for pass in Pipeline::default().passes() {
+        assert_eq!(Pass::from_name(pass.name()), Some(*pass));
+    }
+    assert_eq!(
+        Pass::from_name("unused-functions"),
+        Some(Pass::UnusedFunctions)
+    );
+    assert_eq!(Pass::from_name("inline"), None);
+}
+
+#[test]
+fn test_optimized_program_generates() {
+    let program = optimize(parse(
+        "fn unused() {\n  1\n}\nfn f(n) {\n  if true {\n    return n\n  }\n  n + 1\n}\nprint('%d', f(2))",
+    ));
+    let code = CodeGenerator::generate_program(&program);
+
+    assert!(!code.contains("fn unused"));
+    assert!(code.contains("fn f(n: i64) -> i64 {\n    {\n        return n;\n    }\n}"));
+}
+
+#[test]
+fn test_pipeline_from_names() {
+    let pipeline = Pipeline::from_names("unused-functions, constant-branches").unwrap();
+    assert_eq!(
+        pipeline.passes(),
+        &[Pass::UnusedFunctions, Pass::ConstantBranches]
+    );
+    assert_eq!(pipeline.to_string(), "unused-functions,constant-branches");
+    assert_eq!(
+        Pipeline::from_names(&Pipeline::default().to_string()),
+        Ok(Pipeline::default())
+    );
+    assert_eq!(
+        Pipeline::from_names("unreachable-code,inline"),
+        Err("inline".to_string())
+    );
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use common::{generate, parse, try_parse};
+use grit::lexer::Span;
+use grit::parser::{Expr, ParseError, Statement};
+use grit::semantic::check_program;
+
+#[test]
+fn test_parse_swap() {
+    let program = parse("a, b = b, a");
+
+    assert_eq!(
+        program.statements,
+        vec![Statement::ParallelAssignment {
+            names: vec!["a".to_string(), "b".to_string()],
+            values: vec![
+                Expr::Identifier {
+                    name: "b".into(),
+                    span: Span::new(1, 8, 1, 8)
+                },
+                Expr::Identifier {
+                    name: "a".into(),
+                    span: Span::new(1, 11, 1, 11)
+                },
+            ],
+            span: Span::new(1, 1, 1, 4),
+        }]
+    );
+}
+
+#[test]
+fn test_parse_three_way_assignment_with_expressions() {
+    let program = parse("x, y, z = 1, x + y, to_int(2.5)\nprint('%d', x)");
+
+    assert_eq!(program.statements.len(), 2);
+    match &program.statements[0] {
+        Statement::ParallelAssignment { names, values, .. } => {
+            assert_eq!(names, &["x", "y", "z"]);
+            assert_eq!(values.len(), 3);
+            assert!(matches!(values[1], Expr::BinaryOp { .. }));
+            assert!(matches!(values[2], Expr::FunctionCall { .. }));
+        }
+        _ => panic!("Expected ParallelAssignment"),
+    }
+}
+
+#[test]
+fn test_parallel_assignment_display() {
+    let program = parse("a, b = b, a + 1");
+    assert_eq!(program.statements[0].to_string(), "a, b = b, (a + 1)");
+}
+
+#[test]
+fn test_too_few_values_is_an_error() {
+    // A single value is destructured instead, so two values are needed to fall short
+    match try_parse("a, b, c = 1, 2") {
+        Err(ParseError::UnexpectedToken { expected, .. }) => assert_eq!(expected, "3 values"),
+        other => panic!("Expected UnexpectedToken, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_too_many_values_is_an_error() {
+    match try_parse("a, b = 1, 2, 3") {
+        Err(ParseError::UnexpectedToken { expected, .. }) => assert_eq!(expected, "2 values"),
+        other => panic!("Expected UnexpectedToken, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_missing_equals_is_an_error() {
+    assert!(matches!(
+        try_parse("a, b + 1"),
+        Err(ParseError::UnexpectedToken { .. })
+    ));
+}
+
+#[test]
+fn test_swap_codegen_uses_tuple() {
+    let code = generate("a = 1\nb = 2\na, b = b, a");
+    assert!(code.contains("let (a, b) = (b, a);"));
+}
+
+#[test]
+fn test_parallel_assignment_codegen_with_expressions() {
+    let code = generate("x, y = 1, 2\nx, y = y, x + y");
+    assert!(code.contains("let (x, y) = (1, 2);"));
+    assert!(code.contains("let (x, y) = (y, x + y);"));
+}
+
+#[test]
+fn test_swap_exchanges_types() {
+    let diagnostics = check_program(&parse(
+        "a = 1\nb = 'two'\n{\n  a, b = b, a\n  print('%s %d', a, b)\n}",
+    ));
+    assert!(diagnostics.is_empty());
+
+    let diagnostics = check_program(&parse(
+        "a = 1\nb = 'two'\n{\n  a, b = b, a\n  print('%d', a)\n}",
+    ));
+    assert_eq!(diagnostics.len(), 1);
+}
@@ -0,0 +1,150 @@
+mod common;
+
+use common::generate;
+use grit::lexer::Tokenizer;
+use grit::parser::Parser;
+use grit::semantic::{check_program, SymbolTable, Type};
+
+#[test]
+fn test_param_typed_from_call_arguments() {
+    let code = generate("fn greet(name) {\n  print('%s', name)\n  0\n}\ngreet('Bob')");
+
+    assert!(code.contains("fn greet(name: String) -> i64"));
+    assert!(code.contains("greet(\"Bob\".to_string());"));
+}
+
+#[test]
+fn test_param_types_spread_through_calls() {
+    let code =
+        generate("fn inner(flag) {\n  flag\n}\nfn outer(value) {\n  inner(value)\n}\nouter(true)");
+
+    assert!(code.contains("fn outer(value: bool)"));
+    assert!(code.contains("fn inner(flag: bool)"));
+}
+
+#[test]
+fn test_int_and_float_arguments_widen_to_float() {
+    let code = generate("fn scale(x) {\n  x\n}\nscale(1)\nscale(2.5)");
+
+    assert!(code.contains("fn scale(x: f64)"));
+    assert!(code.contains("scale(1 as f64);"));
+}
+
+#[test]
+fn test_conflicting_or_missing_calls_stay_integers() {
+    let code = generate("fn show(x) {\n  x\n}\nfn unused(y) {\n  y\n}\nshow(1)\nshow('a')");
+
+    assert!(code.contains("fn show(x: i64)"));
+    assert!(code.contains("fn unused(y: i64)"));
+}
+
+#[test]
+fn test_annotation_wins_over_calls() {
+    let code = generate("fn half(x: float) {\n  x\n}\nhalf(3)");
+
+    assert!(code.contains("fn half(x: f64)"));
+}
+
+#[test]
+fn test_instance_argument_is_cloned() {
+    let code = generate(
+        "class Point {\n  x: int\n}\nfn show(point) {\n  0\n}\np = Point.new()\nshow(p)\nshow(p)",
+    );
+
+    assert!(code.contains("fn show(point: Point)"));
+    assert!(code.contains("show(p.clone());"));
+}
+
+#[test]
+fn test_body_is_checked_with_inferred_param_types() {
+    let tokens = Tokenizer::new("fn twice(n) {\n  n * 2\n}\ntwice('a')")
+        .tokenize()
+        .unwrap();
+    let diagnostics = check_program(&Parser::new(tokens).parse().unwrap());
+
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(messages, ["operator '*' expects numbers, found string"]);
+}
+
+#[test]
+fn test_inferred_signatures_are_in_the_symbol_table() {
+    let tokens = Tokenizer::new("fn half(x) {\n  x / 2\n}\nhalf(3.0)")
+        .tokenize()
+        .unwrap();
+    let symbols = SymbolTable::from_program(&Parser::new(tokens).parse().unwrap());
+
+    let half = symbols.function("half").unwrap();
+    assert_eq!(half.param_types, [Type::Unknown]);
+    assert_eq!(half.inferred_params, [Type::Float]);
+    assert_eq!(half.returns, Some(Type::Float));
+}
+
+#[test]
+fn test_mixed_int_and_float_arithmetic_converts_the_integer() {
+    let code = generate("x = 1.5 + 1\nn = 2\ny = x / n\nif y < 1 {\n  print('small')\n}");
+
+    assert!(code.contains("let x = 1.5 + 1.0;"));
+    assert!(code.contains("let y = x / (n as f64);"));
+    assert!(code.contains("if y < 1.0 {"));
+}
+
+#[test]
+fn test_mixed_arithmetic_runs() {
+    let dir = std::env::temp_dir().join(format!("grit_mixed_arithmetic_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("mixed.grit"),
+        "fn twice(n) {\n  n * 2\n}\n\
+         test 'mixed arithmetic' {\n  x = 1.5 + 1\n  n = 2\n  assert(x / n == 1.25)\n  assert(twice(2.5) == 5.0)\n  assert(n < x)\n}\n",
+    )
+    .unwrap();
+
+    let report = grit::testing::run_tests(std::slice::from_ref(&dir)).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(report.results.len(), 1, "{:?}", report.results);
+    assert_eq!(report.failed(), 0, "{:?}", report.results);
+}
+
+const PERSON: &str = "class Person { name }\n\
+                      fn Person > new(name) {\n  self.name = name\n}\n\
+                      fn Person > greet(greeting) {\n  print('%s, %s', greeting, self.name)\n  name\n}\n";
+
+#[test]
+fn test_method_params_typed_from_call_arguments() {
+    let code = generate(&format!(
+        "{}p = Person.new('Bob')\nprint('%s', p.greet('Hi'))",
+        PERSON
+    ));
+
+    assert!(code.contains("    name: String,\n"), "{}", code);
+    assert!(code.contains("fn new(name: String) -> Self {"), "{}", code);
+    assert!(
+        code.contains("fn greet(&self, greeting: String) -> String {"),
+        "{}",
+        code
+    );
+    assert!(
+        code.contains("let p = Person::new(\"Bob\".to_string());"),
+        "{}",
+        code
+    );
+}
+
+#[test]
+fn test_constructor_and_method_taking_strings_run() {
+    let dir = std::env::temp_dir().join(format!("grit_method_params_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("person.grit"),
+        format!(
+            "{}test 'greet' {{\n  p = Person.new('Bob')\n  assert(p.greet('Hi') == 'Bob')\n}}\n",
+            PERSON
+        ),
+    )
+    .unwrap();
+
+    let report = grit::testing::run_tests(std::slice::from_ref(&dir)).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(report.results.len(), 1, "{:?}", report.results);
+    assert_eq!(report.failed(), 0, "{:?}", report.results);
+}
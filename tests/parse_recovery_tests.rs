@@ -0,0 +1,110 @@
+use grit::lexer::{Token, TokenType, Tokenizer};
+use grit::parser::{ParseError, ParseOutcome, Parser};
+
+fn parse_all(input: &str) -> ParseOutcome {
+    let mut tokenizer = Tokenizer::new(input);
+    let tokens = tokenizer.tokenize().unwrap();
+    Parser::new(tokens).parse_all()
+}
+
+/// Returns the line each error was found on
+fn error_lines(outcome: &ParseOutcome) -> Vec<usize> {
+    outcome
+        .errors
+        .iter()
+        .map(|err| match err {
+            ParseError::UnexpectedToken { found: token, .. }
+            | ParseError::InvalidExpression { token }
+            | ParseError::DanglingElse { token } => token.line,
+            other => panic!("unexpected error {:?}", other),
+        })
+        .collect()
+}
+
+#[test]
+fn test_valid_program_has_no_errors() {
+    let outcome = parse_all("x = 1\nprint('%d', x)");
+
+    assert!(outcome.is_ok());
+    assert_eq!(outcome.program.statements.len(), 2);
+}
+
+#[test]
+fn test_reports_every_error() {
+    let outcome = parse_all("x = 1 +\ny = 2\nz = ]\nelse { y = 3 }\nprint('%d', y)");
+
+    assert!(!outcome.is_ok());
+    assert_eq!(error_lines(&outcome), vec![1, 3, 4]);
+    // The statements around the errors are kept
+    let statements: Vec<String> = outcome
+        .program
+        .statements
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(statements, vec!["y = 2", "print('%d', y)"]);
+}
+
+#[test]
+fn test_error_in_block_skips_to_its_end() {
+    let outcome = parse_all("fn f() {\n  a = *\n  b = )\n}\nc = ]\nd = 1");
+
+    // The second bad line is inside the same failed function
+    assert_eq!(error_lines(&outcome), vec![2, 5]);
+    assert_eq!(outcome.program.statements.len(), 1);
+    assert_eq!(outcome.program.statements[0].to_string(), "d = 1");
+}
+
+#[test]
+fn test_recovers_at_semicolons() {
+    let outcome = parse_all("a = *; b = 2; c = ]; d = 4");
+
+    assert_eq!(outcome.errors.len(), 2);
+    assert_eq!(outcome.program.statements.len(), 2);
+}
+
+#[test]
+fn test_unclosed_block_is_reported_once() {
+    let outcome = parse_all("x = 1\nif x {\n  y = 2\n");
+
+    assert_eq!(error_lines(&outcome), vec![4]);
+    assert_eq!(outcome.program.statements.len(), 1);
+}
+
+#[test]
+fn test_into_result_returns_first_error() {
+    let outcome = parse_all("x = ]\ny = ]");
+    let first = outcome.errors[0].clone();
+
+    assert_eq!(outcome.into_result(), Err(first));
+    assert_eq!(
+        parse_all("x = 1").into_result().unwrap().statements.len(),
+        1
+    );
+}
+
+#[test]
+fn test_lex_error_is_the_only_error() {
+    let mut parser = Parser::from_source(Tokenizer::new("x = 1\ny = 1_\nz = ]"));
+    let outcome = parser.parse_all();
+
+    assert_eq!(outcome.errors.len(), 1);
+    assert!(matches!(outcome.errors[0], ParseError::Lex(_)));
+}
+
+#[test]
+fn test_parse_still_stops_at_first_error() {
+    let tokens: Vec<Token> = Tokenizer::new("x = ]\ny = ]").tokenize().unwrap();
+    let err = Parser::new(tokens).parse().unwrap_err();
+
+    assert!(matches!(
+        err,
+        ParseError::InvalidExpression {
+            token: Token {
+                token_type: TokenType::RightBracket,
+                line: 1,
+                ..
+            }
+        }
+    ));
+}
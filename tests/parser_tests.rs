@@ -1,10 +1,10 @@
-use grit::lexer::Tokenizer;
+use grit::lexer::{Span, Tokenizer};
 use grit::parser::{BinaryOperator, Expr, Parser, Statement};
 
 /// Helper function to parse a string as a single expression
 fn parse_string(input: &str) -> Result<Expr, String> {
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().map_err(|e| e.to_string())?;
 
@@ -14,20 +14,43 @@ fn parse_string(input: &str) -> Result<Expr, String> {
     }
 
     match &program.statements[0] {
-        Statement::Expression(expr) => Ok(expr.clone()),
-        Statement::Assignment { value, .. } => Ok(value.clone()),
+        Statement::Expression(expr) => Ok(expr.without_spans()),
+        Statement::Assignment { value, .. } => Ok(value.without_spans()),
+        Statement::ParallelAssignment { .. } => Err("Unexpected parallel assignment".to_string()),
+        Statement::ArrayDestructuring { .. } => Err("Unexpected array destructuring".to_string()),
+        Statement::FieldAssignment { .. } => Err("Unexpected field assignment".to_string()),
         Statement::FunctionDef { .. } => Err("Unexpected function definition".to_string()),
         Statement::ClassDef { .. } => Err("Unexpected class definition".to_string()),
+        Statement::MixinDef { .. } => Err("Unexpected mixin definition".to_string()),
+        Statement::TraitDef { .. } => Err("Unexpected trait definition".to_string()),
+        Statement::EnumDef { .. } => Err("Unexpected enum definition".to_string()),
         Statement::MethodDef { .. } => Err("Unexpected method definition".to_string()),
         Statement::If { .. } => Err("Unexpected if statement".to_string()),
+        Statement::Match { .. } => Err("Unexpected match statement".to_string()),
         Statement::While { .. } => Err("Unexpected while statement".to_string()),
+        Statement::Loop { .. } => Err("Unexpected loop statement".to_string()),
+        Statement::DoWhile { .. } => Err("Unexpected do-while statement".to_string()),
+        Statement::Block { .. } => Err("Unexpected block".to_string()),
+        Statement::Break { .. } => Err("Unexpected break statement".to_string()),
+        Statement::Continue { .. } => Err("Unexpected continue statement".to_string()),
+        Statement::ConstDef { .. } => Err("Unexpected constant declaration".to_string()),
+        Statement::Import { .. } => Err("Unexpected import".to_string()),
+        Statement::Global { .. } => Err("Unexpected global".to_string()),
+        Statement::Return { .. } => Err("Unexpected return statement".to_string()),
+        Statement::Test { .. } => Err("Unexpected test block".to_string()),
     }
 }
 
 #[test]
 fn test_parse_integer() {
     let result = parse_string("42").unwrap();
-    assert_eq!(result, Expr::Integer(42));
+    assert_eq!(
+        result,
+        Expr::Integer {
+            value: 42,
+            span: Span::default()
+        }
+    );
 }
 
 #[test]
@@ -36,9 +59,16 @@ fn test_parse_addition() {
     assert_eq!(
         result,
         Expr::BinaryOp {
-            left: Box::new(Expr::Integer(1)),
+            left: Box::new(Expr::Integer {
+                value: 1,
+                span: Span::default()
+            }),
             op: BinaryOperator::Add,
-            right: Box::new(Expr::Integer(2)),
+            right: Box::new(Expr::Integer {
+                value: 2,
+                span: Span::default()
+            }),
+            span: Span::default(),
         }
     );
 }
@@ -49,9 +79,16 @@ fn test_parse_subtraction() {
     assert_eq!(
         result,
         Expr::BinaryOp {
-            left: Box::new(Expr::Integer(10)),
+            left: Box::new(Expr::Integer {
+                value: 10,
+                span: Span::default()
+            }),
             op: BinaryOperator::Subtract,
-            right: Box::new(Expr::Integer(5)),
+            right: Box::new(Expr::Integer {
+                value: 5,
+                span: Span::default()
+            }),
+            span: Span::default(),
         }
     );
 }
@@ -62,9 +99,16 @@ fn test_parse_multiplication() {
     assert_eq!(
         result,
         Expr::BinaryOp {
-            left: Box::new(Expr::Integer(3)),
+            left: Box::new(Expr::Integer {
+                value: 3,
+                span: Span::default()
+            }),
             op: BinaryOperator::Multiply,
-            right: Box::new(Expr::Integer(4)),
+            right: Box::new(Expr::Integer {
+                value: 4,
+                span: Span::default()
+            }),
+            span: Span::default(),
         }
     );
 }
@@ -75,9 +119,16 @@ fn test_parse_division() {
     assert_eq!(
         result,
         Expr::BinaryOp {
-            left: Box::new(Expr::Integer(20)),
+            left: Box::new(Expr::Integer {
+                value: 20,
+                span: Span::default()
+            }),
             op: BinaryOperator::Divide,
-            right: Box::new(Expr::Integer(4)),
+            right: Box::new(Expr::Integer {
+                value: 4,
+                span: Span::default()
+            }),
+            span: Span::default(),
         }
     );
 }
@@ -89,13 +140,24 @@ fn test_parse_precedence_mul_before_add() {
     assert_eq!(
         result,
         Expr::BinaryOp {
-            left: Box::new(Expr::Integer(1)),
+            left: Box::new(Expr::Integer {
+                value: 1,
+                span: Span::default()
+            }),
             op: BinaryOperator::Add,
             right: Box::new(Expr::BinaryOp {
-                left: Box::new(Expr::Integer(2)),
+                left: Box::new(Expr::Integer {
+                    value: 2,
+                    span: Span::default()
+                }),
                 op: BinaryOperator::Multiply,
-                right: Box::new(Expr::Integer(3)),
+                right: Box::new(Expr::Integer {
+                    value: 3,
+                    span: Span::default()
+                }),
+                span: Span::default(),
             }),
+            span: Span::default(),
         }
     );
 }
@@ -107,13 +169,24 @@ fn test_parse_precedence_div_before_sub() {
     assert_eq!(
         result,
         Expr::BinaryOp {
-            left: Box::new(Expr::Integer(10)),
+            left: Box::new(Expr::Integer {
+                value: 10,
+                span: Span::default()
+            }),
             op: BinaryOperator::Subtract,
             right: Box::new(Expr::BinaryOp {
-                left: Box::new(Expr::Integer(6)),
+                left: Box::new(Expr::Integer {
+                    value: 6,
+                    span: Span::default()
+                }),
                 op: BinaryOperator::Divide,
-                right: Box::new(Expr::Integer(2)),
+                right: Box::new(Expr::Integer {
+                    value: 2,
+                    span: Span::default()
+                }),
+                span: Span::default(),
             }),
+            span: Span::default(),
         }
     );
 }
@@ -126,12 +199,23 @@ fn test_parse_left_associativity_add() {
         result,
         Expr::BinaryOp {
             left: Box::new(Expr::BinaryOp {
-                left: Box::new(Expr::Integer(1)),
+                left: Box::new(Expr::Integer {
+                    value: 1,
+                    span: Span::default()
+                }),
                 op: BinaryOperator::Add,
-                right: Box::new(Expr::Integer(2)),
+                right: Box::new(Expr::Integer {
+                    value: 2,
+                    span: Span::default()
+                }),
+                span: Span::default(),
             }),
             op: BinaryOperator::Add,
-            right: Box::new(Expr::Integer(3)),
+            right: Box::new(Expr::Integer {
+                value: 3,
+                span: Span::default()
+            }),
+            span: Span::default(),
         }
     );
 }
@@ -144,12 +228,23 @@ fn test_parse_left_associativity_mul() {
         result,
         Expr::BinaryOp {
             left: Box::new(Expr::BinaryOp {
-                left: Box::new(Expr::Integer(2)),
+                left: Box::new(Expr::Integer {
+                    value: 2,
+                    span: Span::default()
+                }),
                 op: BinaryOperator::Multiply,
-                right: Box::new(Expr::Integer(3)),
+                right: Box::new(Expr::Integer {
+                    value: 3,
+                    span: Span::default()
+                }),
+                span: Span::default(),
             }),
             op: BinaryOperator::Multiply,
-            right: Box::new(Expr::Integer(4)),
+            right: Box::new(Expr::Integer {
+                value: 4,
+                span: Span::default()
+            }),
+            span: Span::default(),
         }
     );
 }
@@ -159,11 +254,21 @@ fn test_parse_parentheses() {
     let result = parse_string("(1 + 2)").unwrap();
     assert_eq!(
         result,
-        Expr::Grouped(Box::new(Expr::BinaryOp {
-            left: Box::new(Expr::Integer(1)),
-            op: BinaryOperator::Add,
-            right: Box::new(Expr::Integer(2)),
-        }))
+        Expr::Grouped {
+            inner: Box::new(Expr::BinaryOp {
+                left: Box::new(Expr::Integer {
+                    value: 1,
+                    span: Span::default()
+                }),
+                op: BinaryOperator::Add,
+                right: Box::new(Expr::Integer {
+                    value: 2,
+                    span: Span::default()
+                }),
+                span: Span::default(),
+            }),
+            span: Span::default()
+        }
     );
 }
 
@@ -174,13 +279,27 @@ fn test_parse_parentheses_override_precedence() {
     assert_eq!(
         result,
         Expr::BinaryOp {
-            left: Box::new(Expr::Grouped(Box::new(Expr::BinaryOp {
-                left: Box::new(Expr::Integer(1)),
-                op: BinaryOperator::Add,
-                right: Box::new(Expr::Integer(2)),
-            }))),
+            left: Box::new(Expr::Grouped {
+                inner: Box::new(Expr::BinaryOp {
+                    left: Box::new(Expr::Integer {
+                        value: 1,
+                        span: Span::default()
+                    }),
+                    op: BinaryOperator::Add,
+                    right: Box::new(Expr::Integer {
+                        value: 2,
+                        span: Span::default()
+                    }),
+                    span: Span::default(),
+                }),
+                span: Span::default()
+            }),
             op: BinaryOperator::Multiply,
-            right: Box::new(Expr::Integer(3)),
+            right: Box::new(Expr::Integer {
+                value: 3,
+                span: Span::default()
+            }),
+            span: Span::default(),
         }
     );
 }
@@ -190,15 +309,32 @@ fn test_parse_nested_parentheses() {
     let result = parse_string("((1 + 2) * 3)").unwrap();
     assert_eq!(
         result,
-        Expr::Grouped(Box::new(Expr::BinaryOp {
-            left: Box::new(Expr::Grouped(Box::new(Expr::BinaryOp {
-                left: Box::new(Expr::Integer(1)),
-                op: BinaryOperator::Add,
-                right: Box::new(Expr::Integer(2)),
-            }))),
-            op: BinaryOperator::Multiply,
-            right: Box::new(Expr::Integer(3)),
-        }))
+        Expr::Grouped {
+            inner: Box::new(Expr::BinaryOp {
+                left: Box::new(Expr::Grouped {
+                    inner: Box::new(Expr::BinaryOp {
+                        left: Box::new(Expr::Integer {
+                            value: 1,
+                            span: Span::default()
+                        }),
+                        op: BinaryOperator::Add,
+                        right: Box::new(Expr::Integer {
+                            value: 2,
+                            span: Span::default()
+                        }),
+                        span: Span::default(),
+                    }),
+                    span: Span::default()
+                }),
+                op: BinaryOperator::Multiply,
+                right: Box::new(Expr::Integer {
+                    value: 3,
+                    span: Span::default()
+                }),
+                span: Span::default(),
+            }),
+            span: Span::default()
+        }
     );
 }
 
@@ -210,20 +346,45 @@ fn test_parse_complex_expression() {
         result,
         Expr::BinaryOp {
             left: Box::new(Expr::BinaryOp {
-                left: Box::new(Expr::Grouped(Box::new(Expr::BinaryOp {
-                    left: Box::new(Expr::Integer(10)),
-                    op: BinaryOperator::Add,
-                    right: Box::new(Expr::Integer(20)),
-                }))),
+                left: Box::new(Expr::Grouped {
+                    inner: Box::new(Expr::BinaryOp {
+                        left: Box::new(Expr::Integer {
+                            value: 10,
+                            span: Span::default()
+                        }),
+                        op: BinaryOperator::Add,
+                        right: Box::new(Expr::Integer {
+                            value: 20,
+                            span: Span::default()
+                        }),
+                        span: Span::default(),
+                    }),
+                    span: Span::default()
+                }),
                 op: BinaryOperator::Multiply,
-                right: Box::new(Expr::Grouped(Box::new(Expr::BinaryOp {
-                    left: Box::new(Expr::Integer(30)),
-                    op: BinaryOperator::Subtract,
-                    right: Box::new(Expr::Integer(15)),
-                }))),
+                right: Box::new(Expr::Grouped {
+                    inner: Box::new(Expr::BinaryOp {
+                        left: Box::new(Expr::Integer {
+                            value: 30,
+                            span: Span::default()
+                        }),
+                        op: BinaryOperator::Subtract,
+                        right: Box::new(Expr::Integer {
+                            value: 15,
+                            span: Span::default()
+                        }),
+                        span: Span::default(),
+                    }),
+                    span: Span::default()
+                }),
+                span: Span::default(),
             }),
             op: BinaryOperator::Divide,
-            right: Box::new(Expr::Integer(5)),
+            right: Box::new(Expr::Integer {
+                value: 5,
+                span: Span::default()
+            }),
+            span: Span::default(),
         }
     );
 }
@@ -235,13 +396,24 @@ fn test_parse_no_whitespace() {
     assert_eq!(
         result,
         Expr::BinaryOp {
-            left: Box::new(Expr::Integer(1)),
+            left: Box::new(Expr::Integer {
+                value: 1,
+                span: Span::default()
+            }),
             op: BinaryOperator::Add,
             right: Box::new(Expr::BinaryOp {
-                left: Box::new(Expr::Integer(2)),
+                left: Box::new(Expr::Integer {
+                    value: 2,
+                    span: Span::default()
+                }),
                 op: BinaryOperator::Multiply,
-                right: Box::new(Expr::Integer(3)),
+                right: Box::new(Expr::Integer {
+                    value: 3,
+                    span: Span::default()
+                }),
+                span: Span::default(),
             }),
+            span: Span::default(),
         }
     );
 }
@@ -321,14 +493,29 @@ fn test_parse_error_display_invalid_expression() {
 fn test_parse_integer_expression() {
     let result = parse_string("123");
     assert!(result.is_ok());
-    assert_eq!(result.unwrap(), Expr::Integer(123));
+    assert_eq!(
+        result.unwrap(),
+        Expr::Integer {
+            value: 123,
+            span: Span::default()
+        }
+    );
 }
 
 #[test]
 fn test_parse_grouped_expression() {
     let result = parse_string("(42)");
     assert!(result.is_ok());
-    assert_eq!(result.unwrap(), Expr::Grouped(Box::new(Expr::Integer(42))));
+    assert_eq!(
+        result.unwrap(),
+        Expr::Grouped {
+            inner: Box::new(Expr::Integer {
+                value: 42,
+                span: Span::default()
+            }),
+            span: Span::default()
+        }
+    );
 }
 
 #[test]
@@ -355,7 +542,7 @@ fn test_parse_missing_right_paren() {
     use grit::parser::Parser;
 
     let mut tokenizer = Tokenizer::new("(42");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let result = parser.parse();
     assert!(result.is_err());
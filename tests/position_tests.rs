@@ -1,9 +1,15 @@
-use grit::lexer::{TokenType, Tokenizer};
+use grit::lexer::{Span, TokenType, Tokenizer};
+use grit::parser::{Expr, Parser, Program, Statement};
+
+fn parse(input: &str) -> Program {
+    let tokens = Tokenizer::new(input).tokenize().unwrap();
+    Parser::new(tokens).parse().unwrap()
+}
 
 #[test]
 fn test_token_positions_single_line() {
     let mut tokenizer = Tokenizer::new("1 + 2");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
 
     assert_eq!(tokens[0].line, 1);
     assert_eq!(tokens[0].column, 1);
@@ -18,7 +24,7 @@ fn test_token_positions_single_line() {
 #[test]
 fn test_token_positions_multiple_lines() {
     let mut tokenizer = Tokenizer::new("1 + 2\n3 * 4");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
 
     // Line 1: "1 + 2"
     assert_eq!(tokens[0].line, 1);
@@ -55,7 +61,7 @@ fn test_token_positions_multiple_lines() {
 #[test]
 fn test_token_positions_with_parentheses() {
     let mut tokenizer = Tokenizer::new("(10)");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
 
     assert_eq!(tokens[0].line, 1);
     assert_eq!(tokens[0].column, 1);
@@ -69,3 +75,49 @@ fn test_token_positions_with_parentheses() {
     assert_eq!(tokens[2].column, 4);
     assert_eq!(tokens[2].token_type, TokenType::RightParen);
 }
+
+#[test]
+fn test_expression_spans() {
+    let program = parse("x = 1 + foo(2)");
+
+    match &program.statements[0] {
+        Statement::Assignment { value, .. } => {
+            assert_eq!(value.span(), Span::new(1, 5, 1, 14));
+            match value {
+                Expr::BinaryOp { left, right, .. } => {
+                    assert_eq!(left.span(), Span::new(1, 5, 1, 5));
+                    assert_eq!(right.span(), Span::new(1, 9, 1, 14));
+                }
+                other => panic!("expected a binary operation, got {:?}", other),
+            }
+        }
+        other => panic!("expected an assignment, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_statement_spans() {
+    let program =
+        parse("if x {\n  y = 1\n}\nwhile x < 3 {\n  x = x + 1\n}\nfn f() {\n  return 1\n}\nf()");
+    assert_eq!(program.statements[0].span(), Span::new(1, 1, 1, 4));
+    assert_eq!(program.statements[1].span(), Span::new(4, 1, 4, 11));
+    match &program.statements[2] {
+        Statement::FunctionDef { body, .. } => {
+            assert_eq!(body[0].span(), Span::new(8, 3, 8, 10));
+        }
+        other => panic!("expected a function, got {:?}", other),
+    }
+    assert_eq!(program.statements[3].span(), Span::new(10, 1, 10, 3));
+}
+
+#[test]
+fn test_without_spans_ignores_positions() {
+    let a = parse("x = (1 + 2) * y");
+    let b = parse("x  =  ( 1+2 )*y");
+
+    assert_ne!(a.statements[0], b.statements[0]);
+    assert_eq!(
+        a.statements[0].without_spans(),
+        b.statements[0].without_spans()
+    );
+}
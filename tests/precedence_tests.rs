@@ -0,0 +1,60 @@
+use grit::parser::precedence::{
+    find_by_symbol, operator_info, operators_at, precedence_levels, OPERATORS,
+};
+use grit::parser::{Associativity, BinaryOperator};
+
+#[test]
+fn test_table_matches_operator_methods() {
+    for info in OPERATORS {
+        assert_eq!(info.operator.precedence(), info.precedence);
+        assert_eq!(info.operator.associativity(), info.associativity);
+        assert_eq!(info.operator.symbol(), info.symbol);
+        assert_eq!(info.operator.to_string(), info.symbol);
+    }
+}
+
+#[test]
+fn test_operator_info_lookup() {
+    let info = operator_info(&BinaryOperator::Multiply);
+
+    assert_eq!(info.symbol, "*");
+    assert_eq!(info.precedence, 8);
+    assert_eq!(info.associativity, Associativity::Left);
+}
+
+#[test]
+fn test_find_by_symbol() {
+    assert_eq!(
+        find_by_symbol("<=").unwrap().operator,
+        BinaryOperator::LessThanOrEqual
+    );
+    assert_eq!(find_by_symbol("+").unwrap().operator, BinaryOperator::Add);
+    assert!(find_by_symbol("**").is_none());
+}
+
+#[test]
+fn test_operators_at_level() {
+    let additive: Vec<BinaryOperator> = operators_at(BinaryOperator::Add.precedence())
+        .map(|info| info.operator.clone())
+        .collect();
+
+    assert_eq!(
+        additive,
+        vec![BinaryOperator::Add, BinaryOperator::Subtract]
+    );
+}
+
+#[test]
+fn test_precedence_levels_are_sorted_and_distinct() {
+    let levels = precedence_levels();
+
+    assert!(!levels.is_empty());
+    assert!(levels.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn test_table_is_ordered_loosest_first() {
+    assert!(OPERATORS
+        .windows(2)
+        .all(|pair| pair[0].precedence <= pair[1].precedence));
+}
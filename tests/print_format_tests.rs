@@ -0,0 +1,77 @@
+mod common;
+
+use common::generate;
+use grit::testing::check_source;
+
+#[test]
+fn test_float_specifiers() {
+    let code = generate("pi = 3.14159\nprint('%f %.2f %8.3f', pi, pi, pi)");
+
+    assert!(code.contains("println!(\"{:.6} {:.2} {:8.3}\", pi, pi, pi);"));
+}
+
+#[test]
+fn test_integer_width_and_flags() {
+    let code = generate("x = 42\nprint('[%5d] [%-5d] [%05d]', x, x, x)");
+
+    assert!(code.contains("println!(\"[{:5}] [{:<5}] [{:05}]\", x, x, x);"));
+}
+
+#[test]
+fn test_hex_specifier() {
+    let code = generate("x = 255\nprint('%x %04x', x, x)");
+
+    assert!(code.contains("println!(\"{:x} {:04x}\", x, x);"));
+}
+
+#[test]
+fn test_string_width_aligns_right() {
+    let code = generate("name = 'bob'\nprint('[%6s] [%-6s] [%.2s]', name, name, name)");
+
+    assert!(code.contains("println!(\"[{:>6}] [{:<6}] [{:.2}]\", name, name, name);"));
+}
+
+#[test]
+fn test_literal_percent() {
+    let code = generate("x = 50\nprint('%d%% done, 100% sure', x)");
+    assert!(code.contains("println!(\"{}% done, 100% sure\", x);"));
+
+    let code = generate("print('100%%')");
+    assert!(code.contains("println!(\"100%\");"));
+}
+
+#[test]
+fn test_collection_width_pads_whole_value() {
+    let code = generate("items = [1, 2]\nprint('%s [%10s]', items, items)");
+
+    assert!(code.contains("println!(\"{:?} [{:>10}]\", items, format!(\"{:?}\", items));"));
+}
+
+#[test]
+fn test_formatted_print_compiles() {
+    let source = "x = 255\npi = 3.14159\nname = 'bob'\nitems = [1, 2]\nprint('%5d|%-5d|%05d|%x|%f|%.2f|%6s|%-6s|%8s|%d%%', x, x, x, x, pi, pi, name, name, items, x)";
+
+    assert_eq!(check_source(source).unwrap(), Ok(()));
+}
+
+#[test]
+fn test_format_text_is_escaped() {
+    let code = generate("x = 3\nprint('{} %d {x} \"q\" \\\\', x)");
+
+    assert!(code.contains("println!(\"{{}} {} {{x}} \\\"q\\\" \\\\\", x);"));
+    assert_eq!(
+        check_source("print('{}')\nprint('%d {x} \"q\" \\\\', 3)").unwrap(),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_non_literal_format_is_printed() {
+    let code = generate("x = 'hi'\nprint(x)");
+    assert!(code.contains("println!(\"{}\", x);"));
+
+    let errors = grit::compile("x = 'hi'\nprint(x, 1)").diagnostics;
+    assert!(errors
+        .iter()
+        .any(|d| d.message == "print() formats its values with a string literal"));
+}
@@ -0,0 +1,123 @@
+use grit::lexer::Tokenizer;
+use grit::parser::printer::{print_expr, print_program, print_statement};
+use grit::parser::{BinaryOperator, Expr, Parser, Program, Statement};
+use std::fs;
+
+fn parse(input: &str) -> Program {
+    let tokens = Tokenizer::new(input).tokenize().unwrap();
+    Parser::new(tokens)
+        .parse()
+        .unwrap_or_else(|error| panic!("{:?} in:\n{}", error, input))
+}
+
+fn without_spans(program: &Program) -> Vec<Statement> {
+    program
+        .statements
+        .iter()
+        .map(Statement::without_spans)
+        .collect()
+}
+
+/// Asserts that printing a program gives source that parses back into the same
+/// program, and that printing that again changes nothing
+fn assert_round_trip(source: &str) -> String {
+    let program = parse(source);
+    let printed = print_program(&program);
+    let reparsed = parse(&printed);
+
+    assert_eq!(
+        without_spans(&reparsed),
+        without_spans(&program),
+        "{}",
+        printed
+    );
+    assert_eq!(print_program(&reparsed), printed);
+    printed
+}
+
+#[test]
+fn test_round_trip_examples() {
+    for entry in fs::read_dir("examples").unwrap() {
+        let path = entry.unwrap().path();
+        assert_round_trip(&fs::read_to_string(&path).unwrap());
+    }
+}
+
+#[test]
+fn test_print_canonical_layout() {
+    let printed = assert_round_trip(
+        "x=1\n## Adds\n##\n## two numbers\nfn add(a:int,b) -> int {\nif a>b{\nreturn a} elif a==b {\n  b\n}\nelse{a+b}\n}\ny = add(1,\n  2)",
+    );
+
+    assert_eq!(
+        printed,
+        "x = 1\n\n## Adds\n##\n## two numbers\nfn add(a: int, b) -> int {\n  if a > b {\n    return a\n  } \
+         elif a == b {\n    b\n  } else {\n    a + b\n  }\n}\n\ny = add(1, 2)\n"
+    );
+}
+
+#[test]
+fn test_round_trip_declarations() {
+    assert_round_trip(
+        "class Point { x: float, y: float }\nclass Shape\nclass Circle < Shape : Named with Drawable\n\
+         mixin Drawable\ntrait Named {\n  fn name() -> string\n  fn rename(to: string, extra...)\n}\n\
+         trait Empty {}\nenum Color { Red, Green }\nfn Point > new(x, y) {\n  self.x = x\n  self.y = y\n}\n\
+         fn Point >> origin() {\n  Point.new(0.0, 0.0)\n}\nfn nothing() {}\n\
+         const LIMIT = 10\nglobal count = 0\nimport math { sqrt, pow }\nimport io\n\
+         test 'adds it\\'s numbers' {\n  assert(1 + 1 == 2)\n}",
+    );
+}
+
+#[test]
+fn test_round_trip_statements() {
+    assert_round_trip(
+        "var i: int = 0\na, b = 1, 2\nx, y = point()\n[first, _, third] = values\np.x = 5\nshapes[0].w = 2\n\
+         {\n  inner = 1\n}\nouter: while i < 10 {\n  loop {\n    break outer\n  }\n  continue\n}\n\
+         do {\n  i = i + 1\n} while i < 3\nlabel: do {\n  break label\n} while true\n\
+         if n = parse(s) {\n  print('%d', n)\n}\nwhile line = next() {\n  line\n}\n\
+         match c {\n  Color.Red, Color.Green => x = 1\n  1, 'two', true => {\n    y = 2\n    z = 3\n  }\n  \
+         _ => {\n    {\n      w = 4\n    }\n  }\n}\nfn f() {\n  return\n}",
+    );
+}
+
+#[test]
+fn test_round_trip_expressions() {
+    assert_round_trip(
+        "a = -b + (c - d) * e / -(f)\nb = !(x and y) or not z\nc = 1 - (2 - 3) - 4\n\
+         d = 1 << 2 | 3 & 4 ^ 5 >> 1\ne = [1, 2.5, 'it\\'s\\n', nil, true][0]\nf = (a, b)\n\
+         g = {'k': [1], 'v': {'n': 2}}\nh = s[1..3] + s[..2] + s[1..]\ni = rect(width = 3, height = 4)\n\
+         j = (make_adder(2))(5) + handlers[0](1)\nk = p.x + p.move(1, 2).y\nl = 'a' in s\n\
+         m = |x, y| x * y\nn = || 1\no = fn(x) {\n  y = x * 2\n  y + 1\n}\n\
+         p = cond {\n  x < 10 { 'small' }\n  x < 100 {\n    t = 'medium'\n    t\n  }\n  else { 'large' }\n}\n\
+         q = (|x| x + 1)(2)\nr = - -1\ns = (-1).abs()",
+    );
+}
+
+#[test]
+fn test_print_adds_parentheses_precedence_needs() {
+    let sum = |left: Expr, right: Expr, op: BinaryOperator| Expr::BinaryOp {
+        left: Box::new(left),
+        op,
+        right: Box::new(right),
+        span: Default::default(),
+    };
+    let name = |name: &str| Expr::Identifier {
+        name: name.into(),
+        span: Default::default(),
+    };
+    let expr = sum(
+        sum(name("a"), name("b"), BinaryOperator::Add),
+        sum(name("c"), name("d"), BinaryOperator::Subtract),
+        BinaryOperator::Multiply,
+    );
+
+    assert_eq!(print_expr(&expr), "(a + b) * (c - d)");
+    assert_eq!(
+        print_statement(&Statement::Expression(sum(
+            name("a"),
+            sum(name("b"), name("c"), BinaryOperator::Subtract),
+            BinaryOperator::Subtract
+        ))),
+        "a - (b - c)\n"
+    );
+}
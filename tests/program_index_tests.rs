@@ -0,0 +1,73 @@
+mod common;
+
+use common::parse;
+use grit::codegen::CodeGenerator;
+use grit::parser::Statement;
+
+fn method_names(methods: &[&Statement]) -> Vec<String> {
+    methods
+        .iter()
+        .map(|method| match method {
+            Statement::MethodDef { method_name, .. } => method_name.clone(),
+            other => panic!("expected a method, got {:?}", other),
+        })
+        .collect()
+}
+
+#[test]
+fn test_index_lists_definitions_in_order() {
+    let program = parse(
+        "fn b() {\n  1\n}\nfn Zebra > run() {\n  1\n}\nclass Ant\nfn a() {\n  2\n}\n\
+         fn Ant > walk() {\n  1\n}\nfn Zebra > eat() {\n  1\n}",
+    );
+    let index = program.index();
+
+    let functions: Vec<String> = index
+        .functions()
+        .iter()
+        .map(|function| match function {
+            Statement::FunctionDef { name, .. } => name.clone(),
+            other => panic!("expected a function, got {:?}", other),
+        })
+        .collect();
+    assert_eq!(functions, vec!["b", "a"]);
+    assert!(index.function("a").is_some());
+    assert!(index.function("c").is_none());
+
+    assert_eq!(index.classes(), ["Zebra", "Ant"]);
+    assert_eq!(method_names(index.methods_of("Zebra")), vec!["run", "eat"]);
+    assert!(index.methods_of("Cat").is_empty());
+    assert!(index.class_def("Ant").is_some());
+    assert!(index.class_def("Zebra").is_none());
+}
+
+#[test]
+fn test_index_keeps_mixins_traits_and_enums_out_of_classes() {
+    let program = parse(
+        "mixin Walks\nfn Walks > walk() {\n  1\n}\ntrait Named {\n  fn name() -> string\n}\n\
+         enum Color { Red }\n## A point\nclass Point : Named with Walks { x: int }\n\
+         fn Point > name() -> string {\n  'p'\n}",
+    );
+    let index = program.index();
+
+    assert_eq!(index.classes(), ["Point"]);
+    assert!(index.is_mixin("Walks"));
+    assert_eq!(method_names(index.methods_of("Walks")), vec!["walk"]);
+    assert_eq!(index.trait_methods("Named").unwrap()[0].name, "name");
+    assert!(index.trait_methods("Point").is_none());
+    assert_eq!(index.declared_fields("Point")[0].name, "x");
+    assert_eq!(index.class_docs("Point"), ["A point"]);
+    assert!(index.class_docs("Walks").is_empty());
+}
+
+#[test]
+fn test_generate_classes_in_declaration_order() {
+    let code = CodeGenerator::generate_program(&parse(
+        "class Zebra\nclass Ant\nclass Moth\nfn Moth > fly() {\n  1\n}",
+    ));
+
+    let zebra = code.find("struct Zebra").unwrap();
+    let ant = code.find("struct Ant").unwrap();
+    let moth = code.find("struct Moth").unwrap();
+    assert!(zebra < ant && ant < moth);
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use common::{generate, parse};
+use grit::lexer::Span;
+use grit::parser::{BinaryOperator, Expr, Program, Statement};
+
+fn function_body(program: &Program) -> &[Statement] {
+    match &program.statements[0] {
+        Statement::FunctionDef { body, .. } => body,
+        other => panic!("expected a function, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_return_with_value() {
+    let program = parse("fn double(x) {\n  return x * 2\n}");
+
+    assert_eq!(
+        function_body(&program),
+        &[Statement::Return {
+            value: Some(Expr::BinaryOp {
+                left: Box::new(Expr::Identifier {
+                    name: "x".into(),
+                    span: Span::new(2, 10, 2, 10)
+                }),
+                op: BinaryOperator::Multiply,
+                right: Box::new(Expr::Integer {
+                    value: 2,
+                    span: Span::new(2, 14, 2, 14)
+                }),
+                span: Span::new(2, 10, 2, 14),
+            }),
+            span: Span::new(2, 3, 2, 14)
+        }]
+    );
+}
+
+#[test]
+fn test_parse_bare_return() {
+    let program = parse("fn stop() {\n  return\n}\nfn inline() { return }");
+
+    assert_eq!(
+        function_body(&program),
+        &[Statement::Return {
+            value: None,
+            span: Span::new(2, 3, 2, 8)
+        }]
+    );
+    match &program.statements[1] {
+        Statement::FunctionDef { body, .. } => assert_eq!(
+            body,
+            &[Statement::Return {
+                value: None,
+                span: Span::new(4, 15, 4, 20)
+            }]
+        ),
+        other => panic!("expected a function, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_return_display() {
+    let program = parse("fn f(x) {\n  return x + 1\n  return\n}");
+    let body = function_body(&program);
+
+    assert_eq!(body[0].to_string(), "return (x + 1)");
+    assert_eq!(body[1].to_string(), "return");
+}
+
+#[test]
+fn test_generate_early_return() {
+    let code = generate("fn clamp(x) {\n  if x > 10 {\n    return 10\n  }\n  x\n}");
+
+    assert!(code.contains("        return 10;\n"));
+    assert!(code.contains("    x\n}"));
+}
+
+#[test]
+fn test_generate_final_return() {
+    let code = generate("fn sign(x) {\n  if x < 0 {\n    return -1\n  }\n  return 1\n}");
+
+    assert!(code.contains("return -1;"));
+    assert!(code.contains("    return 1;\n}"));
+}
+
+#[test]
+fn test_generate_return_in_method() {
+    let code = generate(
+        "class Counter\nfn Counter > new(count) {\n  self.count = count\n}\nfn Counter > doubled() {\n  return count * 2\n}",
+    );
+
+    assert!(code.contains("return self.count * 2;"));
+}
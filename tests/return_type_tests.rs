@@ -0,0 +1,99 @@
+mod common;
+
+use common::generate;
+
+#[test]
+fn test_function_without_value_returns_nothing() {
+    let code = generate("fn hello() {\n  print('hi')\n}\nhello()");
+
+    assert!(code.contains("fn hello() {\n    println!(\"hi\");\n}"));
+}
+
+#[test]
+fn test_bare_return_returns_nothing() {
+    let code =
+        generate("fn check(x) {\n  if x > 0 {\n    return\n  }\n  print('%d', x)\n}\ncheck(1)");
+
+    assert!(code.contains("fn check(x: i64) {"));
+    assert!(code.contains("return;"));
+}
+
+#[test]
+fn test_return_type_inferred_from_final_expression() {
+    let code =
+        generate("fn name() {\n  'Bob'\n}\nfn ratio() {\n  1.5\n}\nfn positive(x) {\n  x > 0\n}");
+
+    assert!(code.contains("fn name() -> String {\n    \"Bob\".to_string()\n}"));
+    assert!(code.contains("fn ratio() -> f64"));
+    assert!(code.contains("fn positive(x: i64) -> bool"));
+}
+
+#[test]
+fn test_int_and_float_returns_widen_to_float() {
+    let code = generate("fn half(x) {\n  if x > 10 {\n    return 5\n  }\n  2.5\n}");
+
+    assert!(code.contains("fn half(x: i64) -> f64"));
+    assert!(code.contains("return 5 as f64;"));
+}
+
+#[test]
+fn test_return_type_follows_called_function() {
+    let code = generate("fn greeting() {\n  name()\n}\nfn name() {\n  'Bob'\n}\nfn log() {\n  hello()\n}\nfn hello() {\n  print('hi')\n}");
+
+    assert!(code.contains("fn greeting() -> String"));
+    assert!(code.contains("fn log() {\n    hello();\n}"));
+}
+
+#[test]
+fn test_method_return_type_inferred_from_fields() {
+    let code = generate(
+        "class Dog {\n  name: string\n}\nfn Dog > label() {\n  name\n}\nfn Dog > speak() {\n  print('%s', name)\n}",
+    );
+
+    assert!(code.contains("fn label(&self) -> String"));
+    assert!(code.contains("fn speak(&self) {"));
+}
+
+#[test]
+fn test_tuple_return_types_inferred_per_element() {
+    let code = generate("fn pair(a) {\n  return (a, 'x', 1.5)\n}\nx, y, z = pair(1)");
+
+    assert!(code.contains("fn pair(a: i64) -> (i64, String, f64)"));
+    assert!(code.contains("return (a, \"x\".to_string(), 1.5);"));
+}
+
+#[test]
+fn test_function_returning_nil_on_a_path_returns_option() {
+    let code = generate("fn find(n) {\n  if n > 0 {\n    return n\n  }\n  nil\n}\nx = find(1)");
+
+    assert!(code.contains("fn find(n: i64) -> Option<i64>"));
+    assert!(code.contains("return Some(n);"));
+    assert!(code.contains("    None\n}"));
+}
+
+#[test]
+fn test_method_calling_method_without_value_returns_nothing() {
+    let code = generate(
+        "class Point {\n  x: int\n}\nfn Point > shift(n: int) {\n  self.x = self.x + n\n}\nfn Point > reset() {\n  self.shift(-x)\n}",
+    );
+
+    assert!(code.contains("fn reset(&mut self) {"));
+}
+
+#[test]
+fn test_optional_and_tuple_returns_run() {
+    let dir = std::env::temp_dir().join(format!("grit_optional_returns_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("optional.grit"),
+        "fn find(n) {\n  if n > 0 {\n    return n\n  }\n  nil\n}\n\
+         fn pair(a) {\n  return (a, 'x', 1.5)\n}\n\
+         test 'optional returns' {\n  assert(find(-1) == nil)\n  assert(find(3) != nil)\n  assert(find(3) == 3)\n  a, b, c = pair(1)\n  assert(b == 'x')\n  assert(c == 1.5)\n}\n",
+    )
+    .unwrap();
+
+    let report = grit::testing::run_tests(std::slice::from_ref(&dir)).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(report.results.len(), 1, "{:?}", report.results);
+    assert_eq!(report.failed(), 0, "{:?}", report.results);
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use common::{errors, generate, parse, try_parse};
+use grit::lexer::Span;
+use grit::parser::{Expr, Statement};
+
+fn value(stmt: &Statement) -> &Expr {
+    match stmt {
+        Statement::Assignment { value, .. } => value,
+        other => panic!("expected an assignment, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_slice() {
+    let program = parse("t = s[1..3]");
+
+    match value(&program.statements[0]) {
+        Expr::Slice {
+            object,
+            start,
+            end,
+            span,
+        } => {
+            assert_eq!(object.to_string(), "s");
+            assert_eq!(start.as_deref().map(Expr::to_string), Some("1".to_string()));
+            assert_eq!(end.as_deref().map(Expr::to_string), Some("3".to_string()));
+            assert_eq!(*span, Span::new(1, 5, 1, 11));
+        }
+        other => panic!("expected a slice, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_open_slices() {
+    let program = parse("a = s[..2]\nb = s[n + 1..]\nc = s[..]\nd = s[i][1..]");
+
+    let slices: Vec<String> = program
+        .statements
+        .iter()
+        .map(|stmt| value(stmt).to_string())
+        .collect();
+    assert_eq!(slices, ["s[..2]", "s[(n + 1)..]", "s[..]", "s[i][1..]"]);
+    assert!(matches!(
+        value(&program.statements[0]),
+        Expr::Slice { start: None, .. }
+    ));
+    assert!(matches!(
+        value(&program.statements[1]),
+        Expr::Slice { end: None, .. }
+    ));
+}
+
+#[test]
+fn test_malformed_slices_are_errors() {
+    assert!(try_parse("t = s[1..3").is_err());
+    assert!(try_parse("t = s[1...3]").is_err());
+    assert!(try_parse("t = s[]").is_err());
+}
+
+#[test]
+fn test_check_slices() {
+    assert!(errors("s = 'abc'\nt = s[1..]\nprint('%s', t)").is_empty());
+    assert_eq!(
+        errors("n = 5\nt = n[1..2]"),
+        vec!["cannot slice a value of type int"]
+    );
+    assert_eq!(
+        errors("s = 'abc'\nt = s['a'..]"),
+        vec!["slice bounds must be integers, found string"]
+    );
+    assert_eq!(
+        errors("s = 'abc'\nprint('%d', s[0])"),
+        vec!["format specifier %d (argument 1) does not accept a value of type string"]
+    );
+}
+
+#[test]
+fn test_generate_string_index_and_slices() {
+    let code = generate("s = 'hello'\nn = 1\nprint('%s %s %s', s[n], s[1..3], s[..2])");
+
+    assert!(code
+        .contains("s.chars().nth(n as usize).expect(\"string index out of range\").to_string()"));
+    assert!(code.contains("s.chars().take(3).skip(1).collect::<String>()"));
+    assert!(code.contains("s.chars().take(2).collect::<String>()"));
+}
+
+#[test]
+fn test_generate_array_slice() {
+    let code = generate("a = [1, 2, 3]\nn = 1\nb = a[n..]\nprint('%d', b[0])");
+
+    assert!(code.contains("let b = a.iter().skip(n as usize).cloned().collect::<Vec<_>>();"));
+    assert!(code.contains("b[0]"));
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use common::parse;
+use grit::codegen::CodeGenerator;
+use grit::lexer::Span;
+use std::fs;
+
+/// Returns the Grit line each generated line came from, keyed by the generated line
+fn grit_lines(input: &str) -> Vec<(String, Option<usize>)> {
+    let (code, map) = CodeGenerator::generate_program_with_source_map(&parse(input));
+    code.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let span = map.lookup(i + 1);
+            (line.trim().to_string(), span.map(|span| span.start_line))
+        })
+        .collect()
+}
+
+#[test]
+fn test_source_map_points_lines_at_innermost_statement() {
+    let lines = grit_lines(
+        "fn f(a) {\n  if a > 1 {\n    print('big')\n  }\n  return a\n}\nx = f(2)\nwhile x > 5 {\n  print('%d', x)\n}",
+    );
+
+    let find = |text: &str| {
+        lines
+            .iter()
+            .find(|(line, _)| line == text)
+            .unwrap_or_else(|| panic!("no line '{}' in {:?}", text, lines))
+            .1
+    };
+    assert_eq!(find("fn f(a: i64) -> i64 {"), Some(1));
+    assert_eq!(find("if a > 1 {"), Some(2));
+    assert_eq!(find("println!(\"big\");"), Some(3));
+    assert_eq!(find("return a;"), Some(5));
+    assert_eq!(find("let x = f(2);"), Some(7));
+    assert_eq!(find("while x > 5 {"), Some(8));
+    assert_eq!(find("println!(\"{}\", x);"), Some(9));
+    assert_eq!(find("fn main() {"), None);
+}
+
+#[test]
+fn test_source_map_covers_methods_and_nests_mappings() {
+    let (code, map) = CodeGenerator::generate_program_with_source_map(&parse(
+        "class P { x: int }\nfn P > new(x: int) {\n  self.x = x\n}\nfn P >> origin() {\n  P.new(0)\n}",
+    ));
+
+    let line_of = |text: &str| code.lines().position(|line| line.contains(text)).unwrap() + 1;
+    assert_eq!(
+        map.lookup(line_of("fn new(")).unwrap(),
+        Span::new(2, 1, 2, 10)
+    );
+    assert_eq!(map.lookup(line_of("fn origin(")).unwrap().start_line, 5);
+
+    // Each mapping starts no earlier than the one before it
+    let starts: Vec<usize> = map.mappings().iter().map(|m| *m.lines.start()).collect();
+    assert!(starts.windows(2).all(|pair| pair[0] <= pair[1]));
+}
+
+#[test]
+fn test_source_map_leaves_code_unchanged() {
+    let source = fs::read_to_string("examples/control-flow.grit").unwrap()
+        + "\nclass P { x: int }\nfn P > new(x: int) {\n  self.x = x\n}\n\
+           fn P > get() -> int {\n  cond {\n    self.x > 1 { 1 }\n    else { 2 }\n  }\n}\n\
+           f = |v| v + 1\nmatch f(1) {\n  1 => print('one')\n  _ => print('other')\n}";
+    let program = parse(&source);
+    let (code, _) = CodeGenerator::generate_program_with_source_map(&program);
+    assert_eq!(code, CodeGenerator::generate_program(&program));
+
+    let program = parse("test 'one' {\n  assert(1 == 1)\n}");
+    let (code, map) = CodeGenerator::generate_test_harness_with_source_map(&program);
+    assert_eq!(code, CodeGenerator::generate_test_harness(&program));
+    let line = code
+        .lines()
+        .position(|line| line.contains("assert"))
+        .unwrap()
+        + 1;
+    assert_eq!(map.lookup(line).unwrap().start_line, 2);
+}
+
+#[test]
+fn test_compile_returns_source_map() {
+    let result = grit::compile("x = 1\nprint('%d', x)");
+    let code = result.generated_code.unwrap();
+    let map = result.source_map.unwrap();
+    let line = code
+        .lines()
+        .position(|line| line.contains("println!"))
+        .unwrap()
+        + 1;
+    assert_eq!(map.lookup(line).unwrap().start_line, 2);
+
+    assert!(grit::compile("const A = 1\nA = 2").source_map.is_none());
+}
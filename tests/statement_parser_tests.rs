@@ -1,18 +1,24 @@
-use grit::lexer::Tokenizer;
+use grit::lexer::{Span, Tokenizer};
 use grit::parser::{Expr, Parser, Statement};
 
 #[test]
 fn test_parse_assignment() {
     let mut tokenizer = Tokenizer::new("a = 42");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
     assert_eq!(program.statements.len(), 1);
     match &program.statements[0] {
-        Statement::Assignment { name, value } => {
+        Statement::Assignment { name, value, .. } => {
             assert_eq!(name, "a");
-            assert_eq!(*value, Expr::Integer(42));
+            assert_eq!(
+                value.without_spans(),
+                Expr::Integer {
+                    value: 42,
+                    span: Span::default()
+                }
+            );
         }
         _ => panic!("Expected assignment statement"),
     }
@@ -21,7 +27,7 @@ fn test_parse_assignment() {
 #[test]
 fn test_parse_multiple_assignments() {
     let mut tokenizer = Tokenizer::new("a = 1\nb = 2\nc = 3");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
@@ -31,7 +37,7 @@ fn test_parse_multiple_assignments() {
 #[test]
 fn test_parse_assignment_with_expression() {
     let mut tokenizer = Tokenizer::new("x = 1 + 2 * 3");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
@@ -47,15 +53,21 @@ fn test_parse_assignment_with_expression() {
 #[test]
 fn test_parse_identifier_expression() {
     let mut tokenizer = Tokenizer::new("x = a");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
     assert_eq!(program.statements.len(), 1);
     match &program.statements[0] {
-        Statement::Assignment { name, value } => {
+        Statement::Assignment { name, value, .. } => {
             assert_eq!(name, "x");
-            assert_eq!(*value, Expr::Identifier("a".to_string()));
+            assert_eq!(
+                value.without_spans(),
+                Expr::Identifier {
+                    name: "a".into(),
+                    span: Span::default()
+                }
+            );
         }
         _ => panic!("Expected assignment statement"),
     }
@@ -64,15 +76,21 @@ fn test_parse_identifier_expression() {
 #[test]
 fn test_parse_string_literal() {
     let mut tokenizer = Tokenizer::new("msg = 'hello'");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
     assert_eq!(program.statements.len(), 1);
     match &program.statements[0] {
-        Statement::Assignment { name, value } => {
+        Statement::Assignment { name, value, .. } => {
             assert_eq!(name, "msg");
-            assert_eq!(*value, Expr::String("hello".to_string()));
+            assert_eq!(
+                value.without_spans(),
+                Expr::String {
+                    value: "hello".to_string(),
+                    span: Span::default()
+                }
+            );
         }
         _ => panic!("Expected assignment statement"),
     }
@@ -81,13 +99,13 @@ fn test_parse_string_literal() {
 #[test]
 fn test_parse_function_call_no_args() {
     let mut tokenizer = Tokenizer::new("foo()");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
     assert_eq!(program.statements.len(), 1);
     match &program.statements[0] {
-        Statement::Expression(Expr::FunctionCall { name, args }) => {
+        Statement::Expression(Expr::FunctionCall { name, args, .. }) => {
             assert_eq!(name, "foo");
             assert_eq!(args.len(), 0);
         }
@@ -98,16 +116,22 @@ fn test_parse_function_call_no_args() {
 #[test]
 fn test_parse_function_call_one_arg() {
     let mut tokenizer = Tokenizer::new("print('hello')");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
     assert_eq!(program.statements.len(), 1);
     match &program.statements[0] {
-        Statement::Expression(Expr::FunctionCall { name, args }) => {
+        Statement::Expression(Expr::FunctionCall { name, args, .. }) => {
             assert_eq!(name, "print");
             assert_eq!(args.len(), 1);
-            assert_eq!(args[0], Expr::String("hello".to_string()));
+            assert_eq!(
+                args[0].without_spans(),
+                Expr::String {
+                    value: "hello".to_string(),
+                    span: Span::default()
+                }
+            );
         }
         _ => panic!("Expected function call"),
     }
@@ -116,17 +140,29 @@ fn test_parse_function_call_one_arg() {
 #[test]
 fn test_parse_function_call_multiple_args() {
     let mut tokenizer = Tokenizer::new("print('value: %d', 42)");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
     assert_eq!(program.statements.len(), 1);
     match &program.statements[0] {
-        Statement::Expression(Expr::FunctionCall { name, args }) => {
+        Statement::Expression(Expr::FunctionCall { name, args, .. }) => {
             assert_eq!(name, "print");
             assert_eq!(args.len(), 2);
-            assert_eq!(args[0], Expr::String("value: %d".to_string()));
-            assert_eq!(args[1], Expr::Integer(42));
+            assert_eq!(
+                args[0].without_spans(),
+                Expr::String {
+                    value: "value: %d".to_string(),
+                    span: Span::default()
+                }
+            );
+            assert_eq!(
+                args[1].without_spans(),
+                Expr::Integer {
+                    value: 42,
+                    span: Span::default()
+                }
+            );
         }
         _ => panic!("Expected function call"),
     }
@@ -135,13 +171,13 @@ fn test_parse_function_call_multiple_args() {
 #[test]
 fn test_parse_function_call_with_expression_args() {
     let mut tokenizer = Tokenizer::new("print('sum', a + b)");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
     assert_eq!(program.statements.len(), 1);
     match &program.statements[0] {
-        Statement::Expression(Expr::FunctionCall { name, args }) => {
+        Statement::Expression(Expr::FunctionCall { name, args, .. }) => {
             assert_eq!(name, "print");
             assert_eq!(args.len(), 2);
         }
@@ -152,7 +188,7 @@ fn test_parse_function_call_with_expression_args() {
 #[test]
 fn test_parse_expression_with_variables() {
     let mut tokenizer = Tokenizer::new("result = a + b * c");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
@@ -168,18 +204,67 @@ fn test_parse_expression_with_variables() {
 #[test]
 fn test_parse_empty_lines() {
     let mut tokenizer = Tokenizer::new("\n\na = 1\n\n");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
     assert_eq!(program.statements.len(), 1);
 }
 
+#[test]
+fn test_parse_semicolon_separated_statements() {
+    let input = "a = 1; b = 2;\nprint('%d', a + b); if a < b { a = 2; b = 1 };\n;";
+    let mut tokenizer = Tokenizer::new(input);
+    let tokens = tokenizer.tokenize().unwrap();
+    let mut parser = Parser::new(tokens);
+    let program = parser.parse().unwrap();
+
+    assert_eq!(program.statements.len(), 4);
+    match &program.statements[3] {
+        Statement::If { then_branch, .. } => assert_eq!(then_branch.len(), 2),
+        _ => panic!("Expected if statement"),
+    }
+}
+
+#[test]
+fn test_parse_bare_return_before_semicolon() {
+    let input = "fn f() { return; x = 1 }\nfn g() { return; }";
+    let mut tokenizer = Tokenizer::new(input);
+    let tokens = tokenizer.tokenize().unwrap();
+    let mut parser = Parser::new(tokens);
+    let program = parser.parse().unwrap();
+
+    match &program.statements[0] {
+        Statement::FunctionDef { body, .. } => {
+            assert!(matches!(body[0], Statement::Return { value: None, .. }));
+            assert!(matches!(body[1], Statement::Assignment { .. }));
+        }
+        _ => panic!("Expected function definition"),
+    }
+    match &program.statements[1] {
+        Statement::FunctionDef { body, .. } => {
+            assert_eq!(body.len(), 1);
+            assert!(matches!(body[0], Statement::Return { value: None, .. }));
+        }
+        _ => panic!("Expected function definition"),
+    }
+}
+
+#[test]
+fn test_semicolon_inside_expression_is_an_error() {
+    for input in ["x = (1; 2)", "print(1;)", "x = 1 +; 2"] {
+        let mut tokenizer = Tokenizer::new(input);
+        let tokens = tokenizer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        assert!(parser.parse().is_err(), "{} should not parse", input);
+    }
+}
+
 #[test]
 fn test_parse_complete_program() {
     let input = "a = 1\nb = 2\nc = a + b\nprint('c: %d', c)";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
@@ -190,7 +275,7 @@ fn test_parse_complete_program() {
 fn test_parse_function_call_missing_comma() {
     // Test error case: function call with missing comma between arguments
     let mut tokenizer = Tokenizer::new("func(1 2)");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let result = parser.parse();
 
@@ -204,7 +289,7 @@ fn test_parse_function_call_unexpected_eof_after_arg() {
     use grit::lexer::TokenType;
 
     let tokens = vec![
-        Token::new(TokenType::Identifier("func".to_string()), 1, 1),
+        Token::new(TokenType::Identifier("func".into()), 1, 1),
         Token::new(TokenType::LeftParen, 1, 5),
         Token::new(TokenType::Integer(42), 1, 6),
         // Missing comma, right paren, and EOF - just ends
@@ -222,7 +307,7 @@ fn test_parse_function_call_missing_closing_paren_after_args() {
     use grit::lexer::TokenType;
 
     let tokens = vec![
-        Token::new(TokenType::Identifier("func".to_string()), 1, 1),
+        Token::new(TokenType::Identifier("func".into()), 1, 1),
         Token::new(TokenType::LeftParen, 1, 5),
         Token::new(TokenType::Integer(1), 1, 6),
         Token::new(TokenType::Comma, 1, 7),
@@ -240,14 +325,14 @@ fn test_parse_function_call_missing_closing_paren_after_args() {
 fn test_parse_expression_only() {
     // Test the legacy parse_expression_only method
     let mut tokenizer = Tokenizer::new("1 + 2");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let expr = parser.parse_expression_only().unwrap();
 
     match expr {
         Expr::BinaryOp { left, right, .. } => {
-            assert!(matches!(*left, Expr::Integer(1)));
-            assert!(matches!(*right, Expr::Integer(2)));
+            assert!(matches!(*left, Expr::Integer { value: 1, .. }));
+            assert!(matches!(*right, Expr::Integer { value: 2, .. }));
         }
         _ => panic!("Expected binary operation"),
     }
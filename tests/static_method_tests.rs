@@ -0,0 +1,125 @@
+mod common;
+
+use common::{generate, messages, parse, try_parse};
+use grit::diff::AstDiff;
+use grit::lexer::Span;
+use grit::parser::Statement;
+use grit::semantic::SymbolTable;
+
+const POINT: &str = "fn Point > new(x, y) {\n  self.x = x\n  self.y = y\n}\n\
+                     fn Point >> twice(n) -> int {\n  n * 2\n}\n\
+                     fn Point > sum() {\n  x + y\n}\n";
+
+#[test]
+fn test_parse_static_method() {
+    let program = parse("fn Point >> twice(n) -> int {\n  n * 2\n}");
+
+    match &program.statements[0] {
+        Statement::MethodDef {
+            class_name,
+            method_name,
+            is_static,
+            span,
+            ..
+        } => {
+            assert_eq!(class_name, "Point");
+            assert_eq!(method_name, "twice");
+            assert!(*is_static);
+            assert_eq!(*span, Span::new(1, 1, 1, 17));
+        }
+        other => panic!("expected a method, got {:?}", other),
+    }
+    assert_eq!(
+        program.statements[0].to_string(),
+        "fn Point >> twice(n) -> int"
+    );
+}
+
+#[test]
+fn test_instance_methods_are_not_static() {
+    let program = parse(POINT);
+
+    assert!(matches!(
+        &program.statements[2],
+        Statement::MethodDef {
+            is_static: false,
+            ..
+        }
+    ));
+    assert_eq!(program.statements[2].to_string(), "fn Point > sum()");
+}
+
+#[test]
+fn test_static_header_may_wrap() {
+    let program = parse("fn Point\n  >>\n  twice(n) {\n  n\n}");
+
+    assert_eq!(program.statements[0].to_string(), "fn Point >> twice(n)");
+    assert!(try_parse("fn Point >> {\n  1\n}").is_err());
+}
+
+#[test]
+fn test_symbols_record_static_methods() {
+    let symbols = SymbolTable::from_program(&parse(POINT));
+
+    assert!(symbols.resolve_method("Point", "twice").unwrap().is_static);
+    assert!(!symbols.resolve_method("Point", "sum").unwrap().is_static);
+    assert!(!symbols.resolve_method("Point", "new").unwrap().is_static);
+}
+
+#[test]
+fn test_check_static_method_assigns_no_fields() {
+    assert_eq!(
+        messages("fn Point >> reset() {\n  self.x = 0\n}"),
+        vec!["a static method has no 'self' to assign 'self.x'"]
+    );
+    assert!(messages(POINT).is_empty());
+}
+
+#[test]
+fn test_check_static_call_types() {
+    assert!(messages(&format!("{}s: string = Point.twice(2)", POINT))
+        .contains(&"'s' is declared string but assigned int".to_string()));
+}
+
+#[test]
+fn test_generate_static_method_without_self() {
+    let code = generate(POINT);
+
+    assert!(code.contains("    fn twice(n: i64) -> i64 {\n        n * 2\n    }\n"));
+    assert!(code.contains("    fn sum(&self) -> i64 {\n        self.x + self.y\n"));
+}
+
+#[test]
+fn test_generate_static_calls() {
+    let code = generate(&format!(
+        "{}p = Point.new(1, 2)\nprint('%d', Point.twice(3))\nprint('%d', p.twice(4))",
+        POINT
+    ));
+
+    assert!(code.contains("Point::twice(3)"));
+    // Called through an instance, a static method is still called on its class
+    assert!(code.contains("Point::twice(4)"));
+}
+
+#[test]
+fn test_generate_inherited_static_method() {
+    let code = generate(&format!(
+        "{}class Pixel < Point\nprint('%d', Pixel.twice(2))",
+        POINT
+    ));
+
+    assert!(code.contains("    fn twice(n: i64) -> i64 {\n        Point::twice(n)\n    }"));
+    assert!(code.contains("    fn sum(&self) -> i64 {\n        self.parent.sum()\n    }"));
+    assert!(code.contains("Pixel::twice(2)"));
+}
+
+#[test]
+fn test_diff_reports_static_change() {
+    let old = parse("fn Point > twice(n) {\n  n\n}");
+    let new = parse("fn Point >> twice(n) {\n  n\n}");
+
+    assert_eq!(
+        AstDiff::between(&old, &new).changes[0].details,
+        vec!["kind: instance -> static"]
+    );
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::generate;
+
+#[test]
+fn test_string_equality_in_condition() {
+    let code = generate("name = 'bob'\nif name == 'bob' {\n  print('hi')\n}");
+    assert!(code.contains("if &*name == \"bob\" {"));
+}
+
+#[test]
+fn test_string_inequality_in_condition() {
+    let code = generate("name = 'bob'\nif 'alice' != name {\n  print('hi')\n}");
+    assert!(code.contains("if \"alice\" != &*name {"));
+}
+
+#[test]
+fn test_string_equality_in_assignment() {
+    let code = generate("a = 'x'\nb = to_string(1)\nsame = a == b");
+    assert!(code.contains("let same = &*a == &*b;"));
+}
+
+#[test]
+fn test_string_ordering_compares_contents() {
+    let code = generate("b = 'm'\nbefore = env('A', '') < b");
+    assert!(code.contains(
+        "let before = &*std::env::var(\"A\").unwrap_or_else(|_| \"\".to_string()) < &*b;"
+    ));
+}
+
+#[test]
+fn test_string_literals_compare_directly() {
+    let code = generate("same = 'a' == 'a'");
+    assert!(code.contains("let same = \"a\" == \"a\";"));
+}
+
+#[test]
+fn test_numeric_comparison_unchanged() {
+    let code = generate("x = 1\nif x == 1 {\n  print('one')\n}");
+    assert!(code.contains("if x == 1 {"));
+}
+
+#[test]
+fn test_unknown_types_compare_unchanged() {
+    let code = generate("fn same(a, b) {\n  a == b\n}");
+    assert!(code.contains("a == b"));
+    assert!(!code.contains("&*"));
+}
+
+#[test]
+fn test_string_comparison_inside_logical_expression() {
+    let code = generate("name = 'bob'\nx = 1\nok = x > 0 and name != 'eve'");
+    assert!(code.contains("let ok = x > 0 && &*name != \"eve\";"));
+}
+
+#[test]
+fn test_annotated_string_parameter_compares_contents() {
+    let code = generate(
+        "fn greet(name: string) {\n  if name == 'bob' {\n    print('hi')\n  } elif name != 'amy' {\n    print('who')\n  }\n}",
+    );
+    assert!(code.contains("if &*name == \"bob\" {"));
+    assert!(code.contains("} else if &*name != \"amy\" {"));
+}
+
+#[test]
+fn test_string_returning_call_compares_contents() {
+    let code =
+        generate("fn title() -> string {\n  'sir'\n}\nif title() == 'sir' {\n  print('yes')\n}");
+    assert!(code.contains("if &*title() == \"sir\" {"));
+}
+
+#[test]
+fn test_string_comparison_in_while_condition() {
+    let code = generate("name = 'bob'\nwhile name != 'bob' {\n  print('never')\n}");
+    assert!(code.contains("while &*name != \"bob\" {"));
+}
+
+#[test]
+fn test_field_compared_with_string_in_method() {
+    // Fields have no static type, but comparing one with a string makes it a string
+    let code = generate(
+        "fn Person > new(name: string) {\n  self.name = name\n}\n\
+         fn Person > early() -> bool {\n  name < 'm'\n}",
+    );
+    assert!(code.contains("&*self.name < \"m\""));
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use common::parse;
+use grit::parser::ast::build::*;
+use grit::parser::BinaryOperator::*;
+
+#[test]
+fn test_moved_code_is_structurally_equal() {
+    let program = parse("fn f(x) {\n  return x + 1\n}\ny = f(2)");
+    let moved = parse("\n\n# A comment\nfn f(x) {\n      return x  +  1\n}\n\n\ny = f( 2 )");
+
+    assert_ne!(program, moved);
+    assert!(program.structurally_eq(&moved));
+    assert_eq!(program.structural_hash(), moved.structural_hash());
+    assert_eq!(program.without_spans(), moved.without_spans());
+    assert!(program.statements[1].structurally_eq(&moved.statements[1]));
+    assert_eq!(
+        program.statements[1].structural_hash(),
+        moved.statements[1].structural_hash()
+    );
+}
+
+#[test]
+fn test_changed_code_is_not_structurally_equal() {
+    let program = parse("y = 1 + 2");
+    for changed in ["y = 1 - 2", "z = 1 + 2", "y = (1 + 2)", "y = 1 + 2\nz = 3"] {
+        let changed = parse(changed);
+        assert!(!program.structurally_eq(&changed));
+        assert_ne!(program.structural_hash(), changed.structural_hash());
+    }
+}
+
+#[test]
+fn test_structural_hash_agrees_with_equality() {
+    let parsed = match &parse("x = 1.5 * 2").statements[0] {
+        grit::parser::Statement::Assignment { value, .. } => value.clone(),
+        other => panic!("expected an assignment, got {:?}", other),
+    };
+    let built = binop(float(1.5), Multiply, int(2));
+    assert!(parsed.structurally_eq(&built));
+    assert_eq!(parsed.structural_hash(), built.structural_hash());
+
+    // Equal floats hash the same even when they print differently
+    assert!(float(0.0).structurally_eq(&float(-0.0)));
+    assert_eq!(float(0.0).structural_hash(), float(-0.0).structural_hash());
+    assert_eq!(int(7).structural_hash(), int(7).structural_hash());
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use common::parse;
+use grit::codegen::CodeGenerator;
+use grit::semantic::SymbolTable;
+
+#[test]
+fn test_symbol_table_collects_declarations() {
+    let program = parse(
+        "class Point\nfn Point > new(x) { self.x = x }\nfn Point > norm { x }\nfn add(a, b) { a + b }\np = Point.new(1)\nq = 2\np = 3",
+    );
+    let symbols = SymbolTable::from_program(&program);
+
+    assert!(symbols.is_class("Point"));
+    let point = symbols.class("Point").unwrap();
+    assert_eq!(point.methods.len(), 2);
+    assert_eq!(point.method("new").unwrap().params, vec!["x".to_string()]);
+    assert!(point.method("missing").is_none());
+
+    assert_eq!(
+        symbols.function("add").unwrap().params,
+        vec!["a".to_string(), "b".to_string()]
+    );
+    assert!(symbols.function("Point").is_none());
+
+    assert_eq!(symbols.globals(), &["p".to_string(), "q".to_string()]);
+    assert!(symbols.is_global("q"));
+    assert!(!symbols.is_global("x"));
+}
+
+#[test]
+fn test_method_definition_implies_class() {
+    let program = parse("fn Shape > area { 0 }");
+    let symbols = SymbolTable::from_program(&program);
+
+    assert!(symbols.is_class("Shape"));
+}
+
+#[test]
+fn test_lowercase_class_static_call() {
+    let program = parse("class point\nfn point > new { self.x = 1 }\np = point.new");
+    let code = CodeGenerator::generate_program(&program);
+
+    assert!(code.contains("let p = point::new();"));
+}
+
+#[test]
+fn test_uppercase_variable_instance_call() {
+    let program =
+        parse("class Foo\nfn Foo > new { self.a = 1 }\nfn Foo > get { a }\nF = Foo.new\nx = F.get");
+    let code = CodeGenerator::generate_program(&program);
+
+    assert!(code.contains("let F = Foo::new();"));
+    assert!(code.contains("let x = F.get();"));
+}
+
+#[test]
+fn test_undeclared_uppercase_receiver_is_instance_call() {
+    let program = parse("x = Config.load\ny = 1");
+    let code = CodeGenerator::generate_program(&program);
+
+    assert!(code.contains("let x = Config.load();"));
+}
+
+#[test]
+fn test_variable_shadowing_class_name_is_instance_call() {
+    let program = parse("class item\nfn item > new { self.v = 1 }\nfn item > value { v }\nitem = item.new\nx = item.value");
+    let code = CodeGenerator::generate_program(&program);
+
+    assert!(code.contains("let item = item::new();"));
+    assert!(code.contains("let x = item.value();"));
+}
+
+#[test]
+fn test_parameter_shadowing_class_name_is_instance_call() {
+    let program = parse(
+        "class Node\nfn Node > new { self.v = 1 }\nfn Node > value { v }\nfn read(Node) { Node.value }",
+    );
+    let code = CodeGenerator::generate_program(&program);
+
+    assert!(code.contains("Node.value()"));
+    assert!(!code.contains("Node::value()"));
+}
+
+#[test]
+fn test_main_variables_not_visible_in_functions() {
+    let program = parse(
+        "class Counter\nfn Counter > new { self.n = 0 }\nCounter = 1\nfn make { Counter.new }",
+    );
+    let code = CodeGenerator::generate_program(&program);
+
+    assert!(code.contains("Counter::new()"));
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use common::{check, parse};
+use grit::codegen::CodeGenerator;
+use grit::parser::Statement;
+use grit::testing::run_tests;
+use std::fs;
+use std::path::PathBuf;
+
+/// Creates an empty scratch directory for one test
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("grit_tests_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_parse_test_block() {
+    let program = parse("test 'adds' {\n  assert(1 + 1 == 2)\n}");
+    match &program.statements[0] {
+        Statement::Test { name, body, .. } => {
+            assert_eq!(name, "adds");
+            assert_eq!(body.len(), 1);
+        }
+        other => panic!("Expected test block, got {:?}", other),
+    }
+    assert_eq!(program.to_string(), "test 'adds'");
+}
+
+#[test]
+fn test_test_remains_usable_as_a_name() {
+    let program = parse("fn test(x) {\n  x\n}\ntest(1)");
+    assert!(matches!(
+        program.statements[0],
+        Statement::FunctionDef { .. }
+    ));
+    assert!(matches!(program.statements[1], Statement::Expression(_)));
+}
+
+#[test]
+fn test_program_codegen_skips_test_blocks() {
+    let code =
+        CodeGenerator::generate_program(&parse("x = 1\ntest 'never runs' {\n  assert(x == 2)\n}"));
+    assert!(code.contains("let x = 1;"));
+    assert!(!code.contains("assert!"));
+}
+
+#[test]
+fn test_harness_runs_each_test_block() {
+    let code = CodeGenerator::generate_test_harness(&parse(
+        "fn double(x) {\n  x * 2\n}\nprint('%d', 1)\ntest 'a' {\n  assert(double(2) == 4)\n}\ntest 'b' {\n  assert(1 == 2, 'mismatch')\n}",
+    ));
+    assert!(code.contains("fn double(x: i64) -> i64"));
+    assert!(code.contains("fn grit_test_0() {"));
+    assert!(code.contains("fn grit_test_1() {"));
+    assert!(code.contains("grit_run_test(1, grit_test_1)"));
+    assert!(code.contains("assert!(1 == 2, \"{}\", \"mismatch\");"));
+    assert!(code.contains("\"assertion failed: (double(2) == 4)\""));
+    assert_eq!(code.matches("fn grit_run_test").count(), 1);
+    // Top-level statements are not part of the harness
+    assert!(!code.contains("println!(\"{}\", 1);"));
+}
+
+#[test]
+fn test_test_block_is_checked_like_a_function_body() {
+    let diagnostics = check("test 'format' {\n  print('%d', 'hi')\n}");
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("type string"));
+
+    let diagnostics = check("test 'loop' {\n  break\n}");
+    assert_eq!(diagnostics[0].message, "'break' outside of a loop");
+}
+
+#[test]
+fn test_assert_argument_count_is_checked() {
+    let diagnostics = check("assert(1 == 1, 'a', 'b')");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "assert() expects 1 or 2 arguments, found 3"
+    );
+}
+
+#[test]
+fn test_run_tests_reports_passes_and_failures() {
+    let dir = scratch_dir("report");
+    fs::write(
+        dir.join("math.grit"),
+        "fn add(a, b) {\n  a + b\n}\ntest 'adds' {\n  assert(add(1, 2) == 3)\n}\ntest 'fails' {\n  assert(add(2, 2) == 5, 'bad sum')\n}",
+    )
+    .unwrap();
+    // Files without test blocks outside a tests directory are not run
+    fs::write(dir.join("main.grit"), "print('%d', 1)").unwrap();
+
+    let report = run_tests(std::slice::from_ref(&dir)).unwrap();
+    assert_eq!(report.results.len(), 2);
+    assert_eq!(report.passed(), 1);
+    assert_eq!(report.failed(), 1);
+    assert_eq!(report.results[0].name, "adds");
+    assert!(report.results[0].passed);
+    assert_eq!(report.results[1].name, "fails");
+    assert_eq!(report.results[1].message.as_deref(), Some("bad sum"));
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_run_tests_runs_files_in_tests_directory() {
+    let dir = scratch_dir("whole");
+    fs::create_dir_all(dir.join("tests")).unwrap();
+    fs::write(dir.join("tests").join("ok.grit"), "print('%d', 1)").unwrap();
+    fs::write(dir.join("tests").join("broken.grit"), "print('%d')").unwrap();
+
+    let report = run_tests(std::slice::from_ref(&dir)).unwrap();
+    assert_eq!(report.results.len(), 2);
+    assert_eq!(report.results[0].name, "broken");
+    assert!(!report.results[0].passed);
+    assert!(report.results[0].message.is_some());
+    assert_eq!(report.results[1].name, "ok");
+    assert!(report.results[1].passed);
+
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use common::{check, generate};
+
+#[test]
+fn test_time_uses_system_time() {
+    let code = generate("now = time()");
+    assert!(code.contains(
+        "let now = (std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as i64);"
+    ));
+    assert!(!code.contains("fn grit_clock"));
+}
+
+#[test]
+fn test_clock_emits_helper_once() {
+    let code = generate("start = clock()\nelapsed = clock() - start\nprint('%d ms', elapsed)");
+    assert!(code.contains("let start = grit_clock();"));
+    assert!(code.contains("let elapsed = grit_clock() - start;"));
+    assert_eq!(code.matches("fn grit_clock() -> i64").count(), 1);
+    assert!(code.contains("std::time::Instant::now"));
+    // Helpers are written after the code that uses them
+    assert!(code.find("fn grit_clock").unwrap() > code.find("fn main").unwrap());
+}
+
+#[test]
+fn test_clock_in_single_expression_program() {
+    let code = generate("clock() + 1");
+    assert!(code.starts_with("fn main() {"));
+    assert!(code.contains("}\n\nfn grit_clock() -> i64"));
+    assert!(code.contains("let result = grit_clock() + 1;"));
+}
+
+#[test]
+fn test_clock_inside_function() {
+    let code = generate("fn elapsed(start) {\n  clock() - start\n}");
+    assert!(code.contains("grit_clock() - start"));
+    assert!(code.contains("fn grit_clock() -> i64"));
+}
+
+#[test]
+fn test_time_and_clock_are_integers() {
+    assert!(check("t = time()\nc = clock()\nprint('%d %d', t, c)").is_empty());
+}
+
+#[test]
+fn test_time_and_clock_take_no_arguments() {
+    let diagnostics = check("t = time(1)\nc = clock(1, 2)");
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].message, "time() takes no arguments, found 1");
+    assert_eq!(
+        diagnostics[1].message,
+        "clock() takes no arguments, found 2"
+    );
+}
@@ -0,0 +1,196 @@
+use grit::lexer::{Span, TokenStream, TokenType, Tokenizer};
+use grit::parser::{Parser, Statement};
+
+fn stream(input: &str) -> TokenStream {
+    let mut tokenizer = Tokenizer::new(input);
+    TokenStream::new(tokenizer.tokenize().unwrap())
+}
+
+#[test]
+fn test_peek_does_not_consume() {
+    let stream = stream("1 + 2");
+
+    assert_eq!(stream.peek().unwrap().token_type, TokenType::Integer(1));
+    assert_eq!(stream.peek().unwrap().token_type, TokenType::Integer(1));
+    assert_eq!(stream.position(), 0);
+}
+
+#[test]
+fn test_peek_nth() {
+    let mut stream = stream("1 + 2");
+
+    assert_eq!(
+        stream.peek_nth(0).unwrap().token_type,
+        TokenType::Integer(1)
+    );
+    assert_eq!(stream.peek_nth(1).unwrap().token_type, TokenType::Plus);
+    assert_eq!(
+        stream.peek_nth(2).unwrap().token_type,
+        TokenType::Integer(2)
+    );
+    assert_eq!(stream.peek_nth(3).unwrap().token_type, TokenType::Eof);
+    assert!(stream.peek_nth(4).is_none());
+}
+
+#[test]
+fn test_next_consumes_tokens() {
+    let mut stream = stream("a = 1");
+
+    assert_eq!(
+        stream.next().unwrap().token_type,
+        TokenType::Identifier("a".into())
+    );
+    assert_eq!(stream.next().unwrap().token_type, TokenType::Equals);
+    assert_eq!(stream.position(), 2);
+    assert_eq!(stream.peek().unwrap().token_type, TokenType::Integer(1));
+}
+
+#[test]
+fn test_is_at_end() {
+    let mut stream = stream("x");
+
+    assert!(!stream.is_at_end());
+    stream.next();
+    assert!(stream.is_at_end());
+    stream.next();
+    assert!(stream.is_at_end());
+    assert!(stream.next().is_none());
+}
+
+#[test]
+fn test_iterator_collects_remaining_tokens() {
+    let mut stream = stream("1 + 2");
+    stream.next();
+
+    let rest: Vec<TokenType> = stream.map(|token| token.token_type).collect();
+    assert_eq!(
+        rest,
+        vec![TokenType::Plus, TokenType::Integer(2), TokenType::Eof]
+    );
+}
+
+#[test]
+fn test_span_tracking() {
+    let mut stream = stream("a = 1\nb = 22");
+
+    assert!(stream.last_span().is_none());
+    let start = stream.current_span();
+    assert_eq!(start, Span::new(1, 1, 1, 1));
+
+    for _ in 0..6 {
+        stream.next();
+    }
+
+    assert_eq!(stream.last_span(), Some(Span::new(2, 3, 2, 3)));
+    assert_eq!(stream.span_from(start), Span::new(1, 1, 2, 3));
+    assert_eq!(stream.current_span(), Span::new(2, 5, 2, 6));
+
+    // A span ends at the last character of its last token
+    stream.next();
+    assert_eq!(stream.span_from(start), Span::new(1, 1, 2, 6));
+}
+
+#[test]
+fn test_tokens_record_where_they_end() {
+    let tokens = Tokenizer::new("count >= 'a\nb'\nx").tokenize().unwrap();
+    let ends: Vec<(usize, usize)> = tokens
+        .iter()
+        .map(|token| (token.end_line, token.end_column))
+        .collect();
+
+    assert_eq!(ends, vec![(1, 5), (1, 8), (2, 2), (2, 3), (3, 1), (3, 2)]);
+}
+
+#[test]
+fn test_stream_from_borrowed_slice() {
+    let mut tokenizer = Tokenizer::new("1 + 2");
+    let tokens = tokenizer.tokenize().unwrap();
+    let mut stream = TokenStream::from_source(&tokens[..]);
+
+    assert_eq!(stream.next().unwrap().token_type, TokenType::Integer(1));
+    assert_eq!(
+        stream.peek_nth(1).unwrap().token_type,
+        TokenType::Integer(2)
+    );
+    assert_eq!(tokens.len(), 4);
+}
+
+#[test]
+fn test_stream_from_tokenizer_stops_after_eof() {
+    let stream = TokenStream::from_source(Tokenizer::new("a b"));
+    let types: Vec<TokenType> = stream.map(|token| token.token_type).collect();
+
+    assert_eq!(
+        types,
+        vec![
+            TokenType::Identifier("a".into()),
+            TokenType::Identifier("b".into()),
+            TokenType::Eof,
+        ]
+    );
+}
+
+#[test]
+fn test_parser_from_tokenizer_source() {
+    let mut parser = Parser::from_source(Tokenizer::new("x = 1 + 2\nprint('%d', x)"));
+    let program = parser.parse().unwrap();
+
+    assert_eq!(program.statements.len(), 2);
+    assert!(matches!(
+        program.statements[0],
+        Statement::Assignment { .. }
+    ));
+}
+
+#[test]
+fn test_parser_from_slice_can_reparse() {
+    let mut tokenizer = Tokenizer::new("a = 1");
+    let tokens = tokenizer.tokenize().unwrap();
+
+    let first = Parser::from_source(&tokens[..]).parse().unwrap();
+    let second = Parser::from_source(&tokens[..]).parse().unwrap();
+
+    assert_eq!(first, second);
+}
+
+#[test]
+fn test_tokenizer_iterates_lazily_until_eof() {
+    let mut tokenizer = Tokenizer::new("x = 1");
+
+    let first = tokenizer.next().unwrap().unwrap();
+    assert_eq!(first.token_type, TokenType::Identifier("x".into()));
+
+    let rest: Vec<TokenType> = tokenizer
+        .by_ref()
+        .map(|token| token.unwrap().token_type)
+        .collect();
+    assert_eq!(
+        rest,
+        vec![TokenType::Equals, TokenType::Integer(1), TokenType::Eof]
+    );
+    assert!(tokenizer.next().is_none());
+}
+
+#[test]
+fn test_tokenizer_iterator_yields_errors_in_place() {
+    let results: Vec<bool> = Tokenizer::new("1 2_ @")
+        .map(|token| token.is_ok())
+        .collect();
+    // `2_` fails, lexing resumes at the underscore, and `@` is an Error token
+    assert_eq!(results, vec![true, false, true, true, true]);
+
+    let collected: Result<Vec<_>, _> = Tokenizer::new("1 2_ @").collect();
+    assert!(collected.is_err());
+}
+
+#[test]
+fn test_tokenizer_iterator_works_with_adapters() {
+    let identifiers: Vec<String> = Tokenizer::new("a + b * 2")
+        .filter_map(Result::ok)
+        .filter_map(|token| match token.token_type {
+            TokenType::Identifier(name) => Some(name.to_string()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(identifiers, vec!["a", "b"]);
+}
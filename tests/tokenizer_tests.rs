@@ -3,7 +3,7 @@ use grit::lexer::{TokenType, Tokenizer};
 #[test]
 fn test_tokenize_single_integer() {
     let mut tokenizer = Tokenizer::new("42");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
 
     assert_eq!(tokens.len(), 2);
     assert_eq!(tokens[0].token_type, TokenType::Integer(42));
@@ -13,7 +13,7 @@ fn test_tokenize_single_integer() {
 #[test]
 fn test_tokenize_multiple_integers() {
     let mut tokenizer = Tokenizer::new("123 456 789");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
 
     assert_eq!(tokens.len(), 4);
     assert_eq!(tokens[0].token_type, TokenType::Integer(123));
@@ -25,7 +25,7 @@ fn test_tokenize_multiple_integers() {
 #[test]
 fn test_tokenize_addition() {
     let mut tokenizer = Tokenizer::new("1 + 2");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
 
     assert_eq!(tokens.len(), 4);
     assert_eq!(tokens[0].token_type, TokenType::Integer(1));
@@ -37,7 +37,7 @@ fn test_tokenize_addition() {
 #[test]
 fn test_tokenize_subtraction() {
     let mut tokenizer = Tokenizer::new("10 - 5");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
 
     assert_eq!(tokens.len(), 4);
     assert_eq!(tokens[0].token_type, TokenType::Integer(10));
@@ -49,7 +49,7 @@ fn test_tokenize_subtraction() {
 #[test]
 fn test_tokenize_multiplication() {
     let mut tokenizer = Tokenizer::new("3 * 4");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
 
     assert_eq!(tokens.len(), 4);
     assert_eq!(tokens[0].token_type, TokenType::Integer(3));
@@ -61,7 +61,7 @@ fn test_tokenize_multiplication() {
 #[test]
 fn test_tokenize_division() {
     let mut tokenizer = Tokenizer::new("20 / 4");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
 
     assert_eq!(tokens.len(), 4);
     assert_eq!(tokens[0].token_type, TokenType::Integer(20));
@@ -73,7 +73,7 @@ fn test_tokenize_division() {
 #[test]
 fn test_tokenize_all_operators() {
     let mut tokenizer = Tokenizer::new("1 + 2 - 3 * 4 / 5");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
 
     assert_eq!(tokens.len(), 10);
     assert_eq!(tokens[0].token_type, TokenType::Integer(1));
@@ -91,7 +91,7 @@ fn test_tokenize_all_operators() {
 #[test]
 fn test_tokenize_parentheses() {
     let mut tokenizer = Tokenizer::new("(1 + 2)");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
 
     assert_eq!(tokens.len(), 6);
     assert_eq!(tokens[0].token_type, TokenType::LeftParen);
@@ -105,7 +105,7 @@ fn test_tokenize_parentheses() {
 #[test]
 fn test_tokenize_nested_parentheses() {
     let mut tokenizer = Tokenizer::new("((1 + 2) * 3)");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
 
     assert_eq!(tokens.len(), 10);
     assert_eq!(tokens[0].token_type, TokenType::LeftParen);
@@ -123,7 +123,7 @@ fn test_tokenize_nested_parentheses() {
 #[test]
 fn test_tokenize_no_whitespace() {
     let mut tokenizer = Tokenizer::new("1+2*3");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
 
     assert_eq!(tokens.len(), 6);
     assert_eq!(tokens[0].token_type, TokenType::Integer(1));
@@ -137,7 +137,7 @@ fn test_tokenize_no_whitespace() {
 #[test]
 fn test_tokenize_complex_expression() {
     let mut tokenizer = Tokenizer::new("(10 + 20) * (30 - 15) / 5");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
 
     assert_eq!(tokens.len(), 14);
     assert_eq!(tokens[0].token_type, TokenType::LeftParen);
@@ -155,3 +155,301 @@ fn test_tokenize_complex_expression() {
     assert_eq!(tokens[12].token_type, TokenType::Integer(5));
     assert_eq!(tokens[13].token_type, TokenType::Eof);
 }
+
+#[test]
+fn test_tokenize_brackets_around_index() {
+    let mut tokenizer = Tokenizer::new("grid[i][0]");
+    let tokens = tokenizer.tokenize().unwrap();
+
+    assert_eq!(tokens.len(), 8);
+    assert_eq!(tokens[1].token_type, TokenType::LeftBracket);
+    assert_eq!(tokens[1].column, 5);
+    assert_eq!(tokens[3].token_type, TokenType::RightBracket);
+    assert_eq!(tokens[3].column, 7);
+    assert_eq!(tokens[4].token_type, TokenType::LeftBracket);
+    assert_eq!(tokens[5].token_type, TokenType::Integer(0));
+    assert_eq!(tokens[6].token_type, TokenType::RightBracket);
+}
+
+#[test]
+fn test_tokenize_type_annotation_tokens() {
+    let mut tokenizer = Tokenizer::new("fn add(a: int, b: int) -> int");
+    let types: Vec<TokenType> = tokenizer
+        .tokenize()
+        .unwrap()
+        .into_iter()
+        .map(|token| token.token_type)
+        .collect();
+
+    assert_eq!(types[3], TokenType::Identifier("a".into()));
+    assert_eq!(types[4], TokenType::Colon);
+    assert_eq!(types[5], TokenType::Identifier("int".into()));
+    assert_eq!(types[8], TokenType::Colon);
+    assert_eq!(types[11], TokenType::Arrow);
+    assert_eq!(types[12], TokenType::Identifier("int".into()));
+}
+
+#[test]
+fn test_tokenize_minus_without_arrow() {
+    let mut tokenizer = Tokenizer::new("a - > b -1 ->");
+    let tokens = tokenizer.tokenize().unwrap();
+
+    assert_eq!(tokens[1].token_type, TokenType::Minus);
+    assert_eq!(tokens[2].token_type, TokenType::GreaterThan);
+    assert_eq!(tokens[4].token_type, TokenType::Minus);
+    assert_eq!(tokens[5].token_type, TokenType::Integer(1));
+    assert_eq!(tokens[6].token_type, TokenType::Arrow);
+    assert_eq!(tokens[6].column, 12);
+}
+
+#[test]
+fn test_tokenize_range_operators() {
+    let mut tokenizer = Tokenizer::new("0..10 1..=n 1.5..2.5");
+    let types: Vec<TokenType> = tokenizer
+        .tokenize()
+        .unwrap()
+        .into_iter()
+        .map(|token| token.token_type)
+        .collect();
+
+    assert_eq!(
+        types,
+        vec![
+            TokenType::Integer(0),
+            TokenType::DotDot,
+            TokenType::Integer(10),
+            TokenType::Integer(1),
+            TokenType::DotDotEqual,
+            TokenType::Identifier("n".into()),
+            TokenType::Float(1.5),
+            TokenType::DotDot,
+            TokenType::Float(2.5),
+            TokenType::Eof,
+        ]
+    );
+}
+
+#[test]
+fn test_tokenize_single_dot_is_still_member_access() {
+    let mut tokenizer = Tokenizer::new("p.x..p.y");
+    let types: Vec<TokenType> = tokenizer
+        .tokenize()
+        .unwrap()
+        .into_iter()
+        .map(|token| token.token_type)
+        .collect();
+
+    assert_eq!(types[1], TokenType::Dot);
+    assert_eq!(types[3], TokenType::DotDot);
+    assert_eq!(types[5], TokenType::Dot);
+}
+
+#[test]
+fn test_tokenize_multibyte_source() {
+    let mut tokenizer = Tokenizer::new("greeting = 'héllo\\n wörld'\nπ2 = 1_000");
+    let tokens = tokenizer.tokenize().unwrap();
+
+    assert_eq!(
+        tokens[2].token_type,
+        TokenType::String("héllo\n wörld".into())
+    );
+    assert_eq!(tokens[3].token_type, TokenType::Newline);
+    assert_eq!(tokens[3].column, 27);
+    assert_eq!(tokens[4].token_type, TokenType::Identifier("π2".into()));
+    assert_eq!((tokens[5].line, tokens[5].column), (2, 4));
+    assert_eq!(tokens[6].token_type, TokenType::Integer(1000));
+}
+
+#[test]
+fn test_tokenize_unterminated_string_keeps_its_text() {
+    let mut tokenizer = Tokenizer::new("'abc\\tdef");
+    let tokens = tokenizer.tokenize().unwrap();
+
+    assert_eq!(tokens[0].token_type, TokenType::String("abc\tdef".into()));
+    assert_eq!(tokens[1].token_type, TokenType::Eof);
+}
+
+#[test]
+fn test_tokenize_control_flow_exit_keywords() {
+    let mut tokenizer = Tokenizer::new("return break continue returned");
+    let types: Vec<TokenType> = tokenizer
+        .tokenize()
+        .unwrap()
+        .into_iter()
+        .map(|token| token.token_type)
+        .collect();
+
+    assert_eq!(
+        types,
+        vec![
+            TokenType::Return,
+            TokenType::Break,
+            TokenType::Continue,
+            TokenType::Identifier("returned".into()),
+            TokenType::Eof,
+        ]
+    );
+}
+
+#[test]
+fn test_tokenize_for_in_keywords() {
+    let mut tokenizer = Tokenizer::new("for x in items inside");
+    let types: Vec<TokenType> = tokenizer
+        .tokenize()
+        .unwrap()
+        .into_iter()
+        .map(|token| token.token_type)
+        .collect();
+
+    assert_eq!(
+        types,
+        vec![
+            TokenType::For,
+            TokenType::Identifier("x".into()),
+            TokenType::In,
+            TokenType::Identifier("items".into()),
+            TokenType::Identifier("inside".into()),
+            TokenType::Eof,
+        ]
+    );
+}
+
+#[test]
+fn test_tokenize_import_path() {
+    let mut tokenizer = Tokenizer::new("import math.trig\nimported.x");
+    let types: Vec<TokenType> = tokenizer
+        .tokenize()
+        .unwrap()
+        .into_iter()
+        .map(|token| token.token_type)
+        .collect();
+
+    assert_eq!(
+        types,
+        vec![
+            TokenType::Import,
+            TokenType::Identifier("math".into()),
+            TokenType::Dot,
+            TokenType::Identifier("trig".into()),
+            TokenType::Newline,
+            TokenType::Identifier("imported".into()),
+            TokenType::Dot,
+            TokenType::Identifier("x".into()),
+            TokenType::Eof,
+        ]
+    );
+}
+
+#[test]
+fn test_tokenize_match_arm_tokens() {
+    let mut tokenizer = Tokenizer::new("match x {\n  1 => 'one'\n}\na == b = c");
+    let types: Vec<TokenType> = tokenizer
+        .tokenize()
+        .unwrap()
+        .into_iter()
+        .map(|token| token.token_type)
+        .collect();
+
+    assert_eq!(types[0], TokenType::Match);
+    assert_eq!(types[4], TokenType::Integer(1));
+    assert_eq!(types[5], TokenType::FatArrow);
+    assert_eq!(types[6], TokenType::String("one".into()));
+    assert_eq!(types[11], TokenType::EqualEqual);
+    assert_eq!(types[13], TokenType::Equals);
+}
+
+#[test]
+fn test_newlines_inside_parens_and_brackets_are_skipped() {
+    let mut tokenizer = Tokenizer::new("f(1,\n  2)\nxs = [\n  3,\n  4\n]\n");
+    let types: Vec<TokenType> = tokenizer
+        .tokenize()
+        .unwrap()
+        .into_iter()
+        .map(|token| token.token_type)
+        .collect();
+
+    let newlines = types
+        .iter()
+        .filter(|token_type| **token_type == TokenType::Newline)
+        .count();
+    assert_eq!(newlines, 2);
+    assert_eq!(types[5], TokenType::RightParen);
+    assert_eq!(types[6], TokenType::Newline);
+}
+
+#[test]
+fn test_newlines_inside_braces_within_parens_are_kept() {
+    let mut tokenizer = Tokenizer::new("(\n{\na\n}\n)");
+    let types: Vec<TokenType> = tokenizer
+        .tokenize()
+        .unwrap()
+        .into_iter()
+        .map(|token| token.token_type)
+        .collect();
+
+    assert_eq!(
+        types,
+        vec![
+            TokenType::LeftParen,
+            TokenType::LeftBrace,
+            TokenType::Newline,
+            TokenType::Identifier("a".into()),
+            TokenType::Newline,
+            TokenType::RightBrace,
+            TokenType::RightParen,
+            TokenType::Eof,
+        ]
+    );
+}
+
+#[test]
+fn test_trivia_round_trips_source() {
+    let source = "x = 1_000  +\t2.50\nname = 'a\\'b'\nf(1,\n  [2]) \n";
+    let tokens = Tokenizer::new(source).tokenize_with_trivia().unwrap();
+
+    let rebuilt: String = tokens
+        .iter()
+        .map(|token| format!("{}{}", token.leading, token.text))
+        .collect();
+    assert_eq!(rebuilt, source);
+    assert_eq!(tokens.last().unwrap().token.token_type, TokenType::Eof);
+}
+
+#[test]
+fn test_trivia_attaches_leading_whitespace() {
+    let tokens = Tokenizer::new("a  =\t1_0\n")
+        .tokenize_with_trivia()
+        .unwrap();
+
+    assert_eq!(tokens[0].leading, "");
+    assert_eq!(tokens[1].leading, "  ");
+    assert_eq!(tokens[1].text, "=");
+    assert_eq!(tokens[2].leading, "\t");
+    assert_eq!(tokens[2].text, "1_0");
+    assert_eq!(tokens[2].token.token_type, TokenType::Integer(10));
+    assert_eq!(tokens[3].text, "\n");
+}
+
+#[test]
+fn test_trivia_tokens_match_plain_tokens() {
+    let source = "fn add(a, b) {\n  a + b\n}\n";
+    let plain = Tokenizer::new(source).tokenize().unwrap();
+    let with_trivia: Vec<_> = Tokenizer::new(source)
+        .tokenize_with_trivia()
+        .unwrap()
+        .into_iter()
+        .map(|token| token.token)
+        .collect();
+
+    assert_eq!(plain, with_trivia);
+}
+
+#[test]
+fn test_tokenize_semicolon() {
+    let mut tokenizer = Tokenizer::new("a; b");
+    let tokens = tokenizer.tokenize().unwrap();
+
+    assert_eq!(tokens.len(), 4);
+    assert_eq!(tokens[1].token_type, TokenType::Semicolon);
+    assert_eq!(tokens[1].column, 2);
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::{parse, try_parse};
+use grit::codegen::CodeGenerator;
+use grit::parser::Statement;
+
+fn without_spans(input: &str) -> Vec<Statement> {
+    try_parse(input)
+        .unwrap()
+        .statements
+        .iter()
+        .map(Statement::without_spans)
+        .collect()
+}
+
+#[test]
+fn test_trailing_comma_in_calls() {
+    assert_eq!(
+        without_spans("x = f(1, 2,)\ny = p.m(x,)\nz = P.new(1, g(2,),)"),
+        without_spans("x = f(1, 2)\ny = p.m(x)\nz = P.new(1, g(2))")
+    );
+    assert_eq!(
+        without_spans("rect(width = 3, height = 4,)"),
+        without_spans("rect(width = 3, height = 4)")
+    );
+}
+
+#[test]
+fn test_trailing_comma_in_parameters() {
+    assert_eq!(
+        without_spans("fn f(a, b: int,) {\n  a\n}\ng = |a, b,| a + b"),
+        without_spans("fn f(a, b: int) {\n  a\n}\ng = |a, b| a + b")
+    );
+}
+
+#[test]
+fn test_trailing_comma_in_literals() {
+    assert_eq!(
+        without_spans("x = [\n  1,\n  2,\n]\ny = {'a': 1,}\nz = (1, 2,)"),
+        without_spans("x = [1, 2]\ny = {'a': 1}\nz = (1, 2)")
+    );
+}
+
+#[test]
+fn test_comma_without_an_element_is_an_error() {
+    assert!(try_parse("x = f(,)").is_err());
+    assert!(try_parse("x = f(1,,)").is_err());
+    assert!(try_parse("g = |,| 1").is_err());
+    assert!(try_parse("x = [,]").is_err());
+}
+
+#[test]
+fn test_generate_ignores_trailing_commas() {
+    let code = CodeGenerator::generate_program(&parse(
+        "fn add(a, b,) {\n  a + b\n}\nprint('%d', add(1, 2,),)",
+    ));
+
+    assert!(code.contains("fn add(a: i64, b: i64) -> i64 {"));
+    assert!(code.contains("println!(\"{}\", add(1, 2));"));
+}
@@ -0,0 +1,257 @@
+mod common;
+
+use common::{generate, messages, parse, try_parse};
+use grit::diff::AstDiff;
+use grit::lexer::Span;
+use grit::parser::{ParseError, Statement, TraitMethod, TypeAnnotation};
+use grit::semantic::{SymbolDump, SymbolKind, SymbolTable, Type};
+
+const SHAPE: &str = "trait Shape {\n  fn area() -> float\n  fn scaled(k: float) -> float\n}\n";
+
+const CIRCLE: &str = "class Circle : Shape { r: float }\n\
+                      fn Circle > new(r: float) {\n  self.r = r\n}\n\
+                      fn Circle > area() -> float {\n  r * r * 3.0\n}\n\
+                      fn Circle > scaled(k: float) -> float {\n  self.area() * 2.0\n}\n";
+
+#[test]
+fn test_parse_trait() {
+    let program = parse(SHAPE);
+
+    match &program.statements[0] {
+        Statement::TraitDef {
+            name,
+            methods,
+            span,
+        } => {
+            assert_eq!(name, "Shape");
+            assert_eq!(*span, Span::new(1, 1, 1, 11));
+            assert_eq!(
+                methods[1],
+                TraitMethod {
+                    name: "scaled".to_string(),
+                    params: vec!["k".to_string()],
+                    param_types: vec![Some(TypeAnnotation::Float)],
+                    variadic: false,
+                    return_type: Some(TypeAnnotation::Float),
+                    span: Span::new(3, 3, 3, 11),
+                }
+            );
+        }
+        other => panic!("expected a trait, got {:?}", other),
+    }
+    assert_eq!(
+        program.statements[0].to_string(),
+        "trait Shape { fn area() -> float, fn scaled(k: float) -> float }"
+    );
+}
+
+#[test]
+fn test_parse_trait_on_one_line() {
+    let program = parse("trait Named { fn name, fn greet(other) }\nx = 1");
+
+    assert_eq!(program.statements.len(), 2);
+    assert_eq!(
+        program.statements[0].to_string(),
+        "trait Named { fn name(), fn greet(other) }"
+    );
+    assert_eq!(
+        parse("trait Empty {}").statements[0].to_string(),
+        "trait Empty"
+    );
+}
+
+#[test]
+fn test_malformed_traits_are_errors() {
+    assert!(try_parse("trait Shape").is_err());
+    assert!(try_parse("trait Shape { area() }").is_err());
+    assert!(try_parse("trait Shape { fn area() { 1 } }").is_err());
+    assert!(matches!(
+        try_parse("trait Shape {\n  fn area()\n"),
+        Err(ParseError::UnexpectedEof { .. })
+    ));
+}
+
+#[test]
+fn test_parse_class_traits() {
+    let program = parse("class Circle < Base : Shape, Named with Loud { r }");
+
+    match &program.statements[0] {
+        Statement::ClassDef { traits, .. } => assert_eq!(traits, &["Shape", "Named"]),
+        other => panic!("expected a class, got {:?}", other),
+    }
+    assert_eq!(
+        program.statements[0].to_string(),
+        "class Circle < Base : Shape, Named with Loud { r }"
+    );
+    assert!(try_parse("class Circle :").is_err());
+}
+
+#[test]
+fn test_symbols_record_traits() {
+    let symbols = SymbolTable::from_program(&parse(&format!("{}{}", SHAPE, CIRCLE)));
+
+    assert!(symbols.is_trait("Shape"));
+    assert!(!symbols.is_class("Shape"));
+    let shape = symbols.trait_def("Shape").unwrap();
+    assert_eq!(shape.methods[0].return_type, Type::Float);
+    assert_eq!(symbols.class("Circle").unwrap().traits, vec!["Shape"]);
+}
+
+#[test]
+fn test_check_implemented_trait() {
+    assert!(messages(&format!("{}{}", SHAPE, CIRCLE)).is_empty());
+}
+
+#[test]
+fn test_check_missing_and_mismatched_methods() {
+    let source = format!(
+        "{}class Square : Shape\nfn Square > area() -> int {{\n  1\n}}",
+        SHAPE
+    );
+
+    assert_eq!(
+        messages(&source),
+        vec![
+            "method 'area' of class 'Square' does not match its declaration in trait 'Shape'",
+            "class 'Square' does not define method 'scaled' required by trait 'Shape'",
+        ]
+    );
+}
+
+#[test]
+fn test_check_inherited_method_satisfies_trait() {
+    let source = format!(
+        "{}{}class Ring < Circle : Shape\nfn Ring > new() {{\n  self.r = 1.0\n}}",
+        SHAPE, CIRCLE
+    );
+
+    assert!(messages(&source).is_empty());
+}
+
+#[test]
+fn test_check_trait_names() {
+    assert_eq!(messages("class A : Shape"), vec!["unknown trait 'Shape'"]);
+    assert_eq!(
+        messages("mixin Loud\nclass B\nclass A : Loud, B"),
+        vec![
+            "'Loud' is a mixin, not a trait; include it with 'with'",
+            "'B' is a class, not a trait",
+        ]
+    );
+    assert_eq!(
+        messages("trait T {}\nclass A : T, T"),
+        vec!["class 'A' implements trait 'T' more than once"]
+    );
+}
+
+#[test]
+fn test_check_trait_declarations() {
+    assert_eq!(
+        messages("trait T { fn a(), fn a(x) }"),
+        vec!["method 'a' is declared more than once in trait 'T'"]
+    );
+    assert_eq!(
+        messages("class T\ntrait T {}"),
+        vec!["'T' is declared as both a class and a trait"]
+    );
+    assert_eq!(
+        messages("trait T {}\nfn T > a() {\n  1\n}"),
+        vec!["'T' is a trait; declare its methods in the trait body"]
+    );
+}
+
+#[test]
+fn test_generate_trait() {
+    let code = generate(&format!("{}{}", SHAPE, CIRCLE));
+
+    assert!(code.contains(
+        "trait Shape {\n    fn area(&self) -> f64;\n    fn scaled(&self, k: f64) -> f64;\n}\n"
+    ));
+    // Methods defined on a trait name generate no struct
+    assert!(!code.contains("struct Shape"));
+}
+
+#[test]
+fn test_generate_trait_impl_forwards_to_class_methods() {
+    let code = generate(&format!("{}{}", SHAPE, CIRCLE));
+
+    assert!(code.contains(
+        "impl Shape for Circle {\n    fn area(&self) -> f64 {\n        Circle::area(self)\n    }\n\
+         \x20   fn scaled(&self, k: f64) -> f64 {\n        Circle::scaled(self, k)\n    }\n}\n"
+    ));
+    assert!(code.contains("    fn area(&self) -> f64 {\n        self.r * self.r * 3.0\n"));
+}
+
+const SQUARE: &str = "class Square : Shape { s: float }\n\
+                      fn Square > new(s: float) {\n  self.s = s\n}\n\
+                      fn Square > area() -> float {\n  s * s\n}\n\
+                      fn Square > scaled(k: float) -> float {\n  s * k\n}\n";
+
+const TOTAL: &str = "fn total(a, b) {\n  a.area() + b.scaled(2.0)\n}\n";
+
+#[test]
+fn test_parameter_passed_implementors_is_a_trait_object() {
+    let source = format!(
+        "{}{}{}{}c = Circle.new(1.0)\nsq = Square.new(2.0)\nx = total(c, sq)\ny = total(sq, c)",
+        SHAPE, CIRCLE, SQUARE, TOTAL
+    );
+    let code = generate(&source);
+
+    assert!(code.contains("fn total(a: &dyn Shape, b: &dyn Shape) -> f64 {"));
+    assert!(code.contains("let x = total(&c, &sq);"));
+    assert!(messages(&source).is_empty());
+    let symbols = SymbolTable::from_program(&parse(&source));
+    assert_eq!(
+        symbols.function("total").unwrap().inferred_params,
+        vec![Type::Trait("Shape".to_string()); 2]
+    );
+}
+
+#[test]
+fn test_function_called_with_two_implementors_runs() {
+    let dir = std::env::temp_dir().join(format!("grit_trait_params_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("shapes.grit"),
+        format!(
+            "{}{}{}{}test 'shapes' {{\n  c = Circle.new(1.0)\n  sq = Square.new(2.0)\n  \
+             assert(total(c, sq) == 7.0)\n  assert(total(sq, c) == 10.0)\n}}\n",
+            SHAPE, CIRCLE, SQUARE, TOTAL
+        ),
+    )
+    .unwrap();
+
+    let report = grit::testing::run_tests(std::slice::from_ref(&dir)).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(report.results.len(), 1, "{:?}", report.results);
+    assert_eq!(report.failed(), 0, "{:?}", report.results);
+}
+
+#[test]
+fn test_dump_lists_traits() {
+    let dump = SymbolDump::from_program(&parse(SHAPE));
+
+    assert_eq!(dump.entries[0].kind, SymbolKind::Trait);
+    assert_eq!(dump.entries[0].ty, None);
+    assert_eq!(dump.entries[1].display_name(), "Shape > area()");
+    assert_eq!(dump.entries[1].ty, Some(Type::Float));
+}
+
+#[test]
+fn test_diff_reports_trait_changes() {
+    let old = parse("trait T { fn a() }\nclass A");
+    let new = parse("trait T { fn a(), fn b() }\nclass A : T");
+    let details: Vec<Vec<String>> = AstDiff::between(&old, &new)
+        .changes
+        .into_iter()
+        .map(|change| change.details)
+        .collect();
+
+    assert_eq!(
+        details,
+        vec![
+            vec!["methods: fn a() -> fn a(), fn b()".to_string()],
+            vec!["traits: (none) -> T".to_string()],
+        ]
+    );
+}
@@ -0,0 +1,198 @@
+mod common;
+
+use common::{errors, generate, parse, try_parse};
+use grit::lexer::Span;
+use grit::parser::{Expr, ParseError, Statement};
+use grit::semantic::{check_program, SymbolTable, Type, TypeEnv};
+
+fn parse_expr(input: &str) -> Expr {
+    match &parse(input).statements[0] {
+        Statement::Expression(expr) => expr.without_spans(),
+        other => panic!("expected an expression, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_tuple_literal() {
+    assert_eq!(
+        parse_expr("(1, 'a', x)"),
+        Expr::Tuple {
+            elements: vec![
+                Expr::Integer {
+                    value: 1,
+                    span: Span::default()
+                },
+                Expr::String {
+                    value: "a".to_string(),
+                    span: Span::default()
+                },
+                Expr::Identifier {
+                    name: "x".into(),
+                    span: Span::default()
+                },
+            ],
+            span: Span::default()
+        }
+    );
+}
+
+#[test]
+fn test_single_parenthesized_value_is_grouped() {
+    assert_eq!(
+        parse_expr("(1)"),
+        Expr::Grouped {
+            inner: Box::new(Expr::Integer {
+                value: 1,
+                span: Span::default()
+            }),
+            span: Span::default()
+        }
+    );
+}
+
+#[test]
+fn test_tuple_literal_can_wrap_lines() {
+    assert_eq!(
+        parse_expr("(\n  1,\n  2\n)"),
+        Expr::Tuple {
+            elements: vec![
+                Expr::Integer {
+                    value: 1,
+                    span: Span::default()
+                },
+                Expr::Integer {
+                    value: 2,
+                    span: Span::default()
+                }
+            ],
+            span: Span::default()
+        }
+    );
+}
+
+#[test]
+fn test_unclosed_tuple_is_an_error() {
+    assert!(matches!(
+        try_parse("(1, 2"),
+        Err(ParseError::UnexpectedEof { .. })
+    ));
+}
+
+#[test]
+fn test_tuple_display() {
+    assert_eq!(parse_expr("(1, x + 1)").to_string(), "(1, (x + 1))");
+}
+
+#[test]
+fn test_infer_tuple_type() {
+    let symbols = SymbolTable::default();
+    let env = TypeEnv::new(&symbols);
+    let ty = env.infer(&parse_expr("(1, 2.5, 'a')"));
+
+    assert_eq!(ty, Type::Tuple(vec![Type::Int, Type::Float, Type::String]));
+    assert_eq!(ty.to_string(), "(int, float, string)");
+}
+
+#[test]
+fn test_parse_destructuring_assignment() {
+    let program = parse("x, y = point()");
+
+    match &program.statements[0] {
+        Statement::ParallelAssignment { names, values, .. } => {
+            assert_eq!(names, &["x", "y"]);
+            assert_eq!(values.len(), 1);
+            assert!(matches!(values[0], Expr::FunctionCall { .. }));
+        }
+        other => panic!("expected a parallel assignment, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_return_of_several_values() {
+    let program = parse("fn pair() {\n  return 1, 2\n}");
+
+    match &program.statements[0] {
+        Statement::FunctionDef { body, .. } => assert_eq!(
+            body,
+            &[Statement::Return {
+                value: Some(Expr::Tuple {
+                    elements: vec![
+                        Expr::Integer {
+                            value: 1,
+                            span: Span::new(2, 10, 2, 10)
+                        },
+                        Expr::Integer {
+                            value: 2,
+                            span: Span::new(2, 13, 2, 13)
+                        },
+                    ],
+                    span: Span::new(2, 10, 2, 13)
+                }),
+                span: Span::new(2, 3, 2, 13)
+            }]
+        ),
+        other => panic!("expected a function, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_destructuring_tuple_with_wrong_length_is_an_error() {
+    assert_eq!(
+        errors("a, b = (1, 2, 3)"),
+        vec!["cannot destructure (int, int, int) into 2 names"]
+    );
+}
+
+#[test]
+fn test_destructuring_non_tuple_is_an_error() {
+    assert_eq!(
+        errors("a, b = 1"),
+        vec!["cannot destructure int into 2 names"]
+    );
+}
+
+#[test]
+fn test_destructuring_binds_element_types() {
+    let program = parse("point = (1, 'a')\nx, name = point\nprint('%d', name)");
+    let diagnostics = check_program(&program);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("type string"));
+}
+
+#[test]
+fn test_generate_tuple_and_destructuring() {
+    let code = generate("point = (3, 4)\nx, y = point\nprint('%s', point)");
+
+    assert!(code.contains("let point = (3, 4);"));
+    assert!(code.contains("let (x, y) = point;"));
+    assert!(code.contains("println!(\"{:?}\", point);"));
+}
+
+#[test]
+fn test_generate_function_returning_tuple() {
+    let code =
+        generate("fn divmod(a, b) {\n  return a / b, a - (a / b) * b\n}\nq, r = divmod(17, 5)");
+
+    assert!(code.contains("fn divmod(a: i64, b: i64) -> (i64, i64) {"));
+    assert!(code.contains("return (a / b, a - (a / b) * b);"));
+    assert!(code.contains("let (q, r) = divmod(17, 5);"));
+}
+
+#[test]
+fn test_generate_function_ending_in_tuple() {
+    let code = generate("fn minmax(a, b) {\n  if a < b {\n    return (a, b)\n  }\n  (b, a)\n}");
+
+    assert!(code.contains("fn minmax(a: i64, b: i64) -> (i64, i64) {"));
+    assert!(code.contains("    (b, a)\n}"));
+}
+
+#[test]
+fn test_generate_method_returning_tuple() {
+    let code = generate(
+        "class Point\nfn Point > new(x, y) {\n  self.x = x\n  self.y = y\n}\nfn Point > pair() {\n  return x, y\n}",
+    );
+
+    assert!(code.contains("fn pair(&self) -> (i64, i64) {"));
+    assert!(code.contains("return (self.x, self.y);"));
+}
@@ -0,0 +1,187 @@
+mod common;
+
+use common::{generate, messages, parse, try_parse};
+use grit::diff::AstDiff;
+use grit::parser::{ParseError, Statement, TypeAnnotation};
+use grit::semantic::{SymbolTable, Type};
+
+#[test]
+fn test_parse_annotated_function() {
+    let program = parse("fn add(a: int, b) -> float {\n  a + b\n}");
+
+    match &program.statements[0] {
+        Statement::FunctionDef {
+            params,
+            param_types,
+            return_type,
+            ..
+        } => {
+            assert_eq!(params, &["a", "b"]);
+            assert_eq!(param_types, &[Some(TypeAnnotation::Int), None]);
+            assert_eq!(*return_type, Some(TypeAnnotation::Float));
+        }
+        other => panic!("expected a function, got {:?}", other),
+    }
+    assert_eq!(
+        program.statements[0].to_string(),
+        "fn add(a: int, b) -> float"
+    );
+}
+
+#[test]
+fn test_parse_annotated_assignment() {
+    let program = parse("x: float = 1.0\nouter: while true {\n  break outer\n}");
+
+    match &program.statements[0] {
+        Statement::Assignment { name, ty, .. } => {
+            assert_eq!(name, "x");
+            assert_eq!(*ty, Some(TypeAnnotation::Float));
+        }
+        other => panic!("expected an assignment, got {:?}", other),
+    }
+    assert_eq!(program.statements[0].to_string(), "x: float = 1.0");
+    assert!(matches!(program.statements[1], Statement::While { .. }));
+}
+
+#[test]
+fn test_parse_annotated_method_and_variadic() {
+    let program = parse("fn Stats > mean(nums: float...) -> float {\n  0.0\n}");
+
+    assert_eq!(
+        program.statements[0].to_string(),
+        "fn Stats > mean(nums: float...) -> float"
+    );
+}
+
+#[test]
+fn test_unknown_type_name_is_error() {
+    assert!(matches!(
+        try_parse("fn f(a: number) {\n  a\n}"),
+        Err(ParseError::UnexpectedToken { expected, .. }) if expected.starts_with("type")
+    ));
+    assert!(try_parse("fn f() -> {\n  1\n}").is_err());
+}
+
+#[test]
+fn test_lambdas_take_no_annotations() {
+    assert!(try_parse("f = fn(a: int) { a }").is_err());
+}
+
+#[test]
+fn test_symbols_record_annotations() {
+    let symbols = SymbolTable::from_program(&parse("fn half(x: float, n) -> float {\n  x\n}"));
+    let function = symbols.function("half").unwrap();
+
+    assert_eq!(function.param_types, vec![Type::Float, Type::Unknown]);
+    assert_eq!(function.return_type, Type::Float);
+}
+
+#[test]
+fn test_generate_annotated_signature() {
+    let code = generate(
+        "fn label(name: string, ok: bool, ratio: float) -> string {\n  name\n}\n\
+         print('%s', label('a', true, 1))",
+    );
+
+    assert!(code.contains("fn label(name: String, ok: bool, ratio: f64) -> String {"));
+    assert!(code.contains("name.to_string()"));
+    assert!(code.contains("label(\"a\".to_string(), true, 1 as f64)"));
+}
+
+#[test]
+fn test_generate_unannotated_defaults_to_int() {
+    let code = generate("fn add(a, b) {\n  a + b\n}");
+
+    assert!(code.contains("fn add(a: i64, b: i64) -> i64 {"));
+}
+
+#[test]
+fn test_generate_annotated_variable() {
+    let code = generate("x: float = 1\ny: string = 'hi'");
+
+    assert!(code.contains("let x: f64 = 1 as f64;"));
+    assert!(code.contains("let y: String = \"hi\".to_string();"));
+}
+
+#[test]
+fn test_generate_annotated_return_statement() {
+    let code =
+        generate("fn half(x: float) -> float {\n  if x > 0.0 {\n    return 1\n  }\n  x / 2.0\n}");
+
+    assert!(code.contains("return 1 as f64;"));
+}
+
+#[test]
+fn test_generate_typed_constructor_fields() {
+    let code = generate(
+        "fn Point > new(x: float, name: string) {\n  self.x = x\n  self.name = name\n}\n\
+         fn Point > label() -> string {\n  name\n}",
+    );
+
+    assert!(code.contains("    x: f64,\n"));
+    assert!(code.contains("    name: String,\n"));
+    assert!(code.contains("fn label(&self) -> String {"));
+    assert!(code.contains("self.name.to_string()"));
+}
+
+#[test]
+fn test_annotated_types_flow_into_inference() {
+    let code = generate("fn half(x: float) -> float {\n  x / 2.0\n}\nprint('%d', half(1.0))");
+
+    assert!(code.contains("fn half(x: f64) -> f64"));
+    assert!(
+        messages("fn half(x: float) -> float {\n  x / 2.0\n}\nprint('%d', half(1.0))")
+            .iter()
+            .any(|message| message.contains("%d"))
+    );
+}
+
+#[test]
+fn test_check_annotated_assignment() {
+    assert_eq!(
+        messages("x: int = 'a'"),
+        vec!["'x' is declared int but assigned string"]
+    );
+    assert!(messages("x: float = 1").is_empty());
+}
+
+#[test]
+fn test_check_argument_types() {
+    let source = "fn greet(name: string, times: int) {\n  times\n}\n";
+
+    assert_eq!(
+        messages(&format!("{}greet(1, 'twice')", source)),
+        vec![
+            "argument 'name' of greet() must be string, found int",
+            "argument 'times' of greet() must be int, found string",
+        ]
+    );
+    assert_eq!(
+        messages(&format!("{}greet(times = 1.5, name = 'a')", source)),
+        vec!["argument 'times' of greet() must be int, found float"]
+    );
+    assert!(messages(&format!("{}greet('a', 2)", source)).is_empty());
+}
+
+#[test]
+fn test_annotated_parameters_are_typed_in_body() {
+    assert_eq!(
+        messages("fn f(n: int) {\n  s: string = n\n}"),
+        vec!["'s' is declared string but assigned int"]
+    );
+}
+
+#[test]
+fn test_diff_reports_annotation_changes() {
+    let old = parse("fn add(a, b) {\n  a + b\n}");
+    let new = parse("fn add(a: int, b) -> int {\n  a + b\n}");
+    let diff = AstDiff::between(&old, &new);
+
+    assert_eq!(
+        diff.changes[0].details,
+        vec![
+            "params: (a, b) -> (a: int, b)",
+            "return type: (none) -> int"
+        ]
+    );
+}
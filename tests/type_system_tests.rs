@@ -1,11 +1,13 @@
+#![allow(clippy::approx_constant)]
+
 use grit::codegen::CodeGenerator;
-use grit::lexer::Tokenizer;
+use grit::lexer::{Span, Tokenizer};
 use grit::parser::{Expr, Parser};
 
 #[test]
 fn test_tokenize_float() {
     let mut tokenizer = Tokenizer::new("3.14");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     assert_eq!(tokens.len(), 2); // Float + Eof
     assert_eq!(tokens[0].token_type, grit::lexer::TokenType::Float(3.14));
 }
@@ -13,7 +15,7 @@ fn test_tokenize_float() {
 #[test]
 fn test_tokenize_float_in_expression() {
     let mut tokenizer = Tokenizer::new("1.5 + 2.5");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     assert_eq!(tokens.len(), 4); // Float + Plus + Float + Eof
 }
 
@@ -21,7 +23,7 @@ fn test_tokenize_float_in_expression() {
 fn test_tokenize_integer_dot_method_call() {
     // Should NOT parse as float
     let mut tokenizer = Tokenizer::new("42.foo");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     assert_eq!(tokens[0].token_type, grit::lexer::TokenType::Integer(42));
     assert_eq!(tokens[1].token_type, grit::lexer::TokenType::Dot);
 }
@@ -29,13 +31,13 @@ fn test_tokenize_integer_dot_method_call() {
 #[test]
 fn test_parse_float_literal() {
     let mut tokenizer = Tokenizer::new("3.14");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
     assert_eq!(program.statements.len(), 1);
     match &program.statements[0] {
-        grit::parser::Statement::Expression(Expr::Float(f)) => {
+        grit::parser::Statement::Expression(Expr::Float { value: f, .. }) => {
             assert_eq!(*f, 3.14);
         }
         _ => panic!("Expected float expression"),
@@ -45,19 +47,21 @@ fn test_parse_float_literal() {
 #[test]
 fn test_parse_float_arithmetic() {
     let mut tokenizer = Tokenizer::new("1.5 + 2.5");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
     assert_eq!(program.statements.len(), 1);
     match &program.statements[0] {
-        grit::parser::Statement::Expression(Expr::BinaryOp { left, op: _, right }) => {
+        grit::parser::Statement::Expression(Expr::BinaryOp {
+            left, op: _, right, ..
+        }) => {
             match **left {
-                Expr::Float(f) => assert_eq!(f, 1.5),
+                Expr::Float { value: f, .. } => assert_eq!(f, 1.5),
                 _ => panic!("Expected float left operand"),
             }
             match **right {
-                Expr::Float(f) => assert_eq!(f, 2.5),
+                Expr::Float { value: f, .. } => assert_eq!(f, 2.5),
                 _ => panic!("Expected float right operand"),
             }
         }
@@ -67,7 +71,10 @@ fn test_parse_float_arithmetic() {
 
 #[test]
 fn test_generate_float_literal() {
-    let expr = Expr::Float(3.14);
+    let expr = Expr::Float {
+        value: 3.14,
+        span: Span::default(),
+    };
     let code = CodeGenerator::generate_expression(&expr);
     assert_eq!(code, "3.14");
 }
@@ -75,9 +82,16 @@ fn test_generate_float_literal() {
 #[test]
 fn test_generate_float_expression() {
     let expr = Expr::BinaryOp {
-        left: Box::new(Expr::Float(1.5)),
+        left: Box::new(Expr::Float {
+            value: 1.5,
+            span: Span::default(),
+        }),
         op: grit::parser::BinaryOperator::Add,
-        right: Box::new(Expr::Float(2.5)),
+        right: Box::new(Expr::Float {
+            value: 2.5,
+            span: Span::default(),
+        }),
+        span: Span::default(),
     };
     let code = CodeGenerator::generate_expression(&expr);
     assert_eq!(code, "1.5 + 2.5");
@@ -86,7 +100,7 @@ fn test_generate_float_expression() {
 #[test]
 fn test_to_int_conversion() {
     let mut tokenizer = Tokenizer::new("to_int(3.14)");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
@@ -97,7 +111,7 @@ fn test_to_int_conversion() {
 #[test]
 fn test_to_float_conversion() {
     let mut tokenizer = Tokenizer::new("to_float(42)");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
@@ -108,7 +122,7 @@ fn test_to_float_conversion() {
 #[test]
 fn test_to_string_conversion() {
     let mut tokenizer = Tokenizer::new("to_string(42)");
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
@@ -120,7 +134,7 @@ fn test_to_string_conversion() {
 fn test_mixed_types_assignment() {
     let input = "x = 42\ny = 3.14\nz = 'hello'";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
@@ -134,7 +148,7 @@ fn test_mixed_types_assignment() {
 fn test_conversion_chain() {
     let input = "x = to_string(to_int(3.14))";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
@@ -146,22 +160,22 @@ fn test_conversion_chain() {
 fn test_float_in_arithmetic() {
     let input = "result = 10.5 * 2";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
     let code = CodeGenerator::generate_program(&program);
-    assert!(code.contains("let result = 10.5 * 2;"));
+    assert!(code.contains("let result = 10.5 * 2.0;"));
 }
 
 #[test]
 fn test_integer_and_float_mixed() {
     let input = "result = 5 + 2.5";
     let mut tokenizer = Tokenizer::new(input);
-    let tokens = tokenizer.tokenize();
+    let tokens = tokenizer.tokenize().unwrap();
     let mut parser = Parser::new(tokens);
     let program = parser.parse().unwrap();
 
     let code = CodeGenerator::generate_program(&program);
-    assert!(code.contains("let result = 5 + 2.5;"));
+    assert!(code.contains("let result = 5.0 + 2.5;"));
 }
@@ -0,0 +1,53 @@
+mod common;
+
+use common::{check, generate};
+
+#[test]
+fn test_typeof_literals_resolved_statically() {
+    let code = generate("a = typeof(42)\nb = typeof(1.5)\nc = typeof('hi')\nd = typeof(1 < 2)");
+    assert!(code.contains("let a = \"int\";"));
+    assert!(code.contains("let b = \"float\";"));
+    assert!(code.contains("let c = \"string\";"));
+    assert!(code.contains("let d = \"bool\";"));
+    assert!(!code.contains("fn grit_typeof"));
+}
+
+#[test]
+fn test_typeof_constructor_resolved_statically() {
+    let code = generate("class Point\nfn Point > new {\n  self.x = 0\n}\nt = typeof(Point.new)");
+    assert!(code.contains("let t = \"Point\";"));
+}
+
+#[test]
+fn test_typeof_variable_resolved_statically() {
+    let code = generate("x = 42\ny = typeof(x)");
+    assert!(code.contains("let y = \"int\";"));
+    assert!(!code.contains("fn grit_typeof"));
+}
+
+#[test]
+fn test_typeof_parameter_resolved_at_runtime() {
+    let code = generate("fn show(x) {\n  print('%s', typeof(x))\n  0\n}");
+    assert!(code.contains("println!(\"{}\", grit_typeof(&x));"));
+    assert_eq!(code.matches("fn grit_typeof").count(), 1);
+    assert!(code.contains("std::any::type_name::<T>()"));
+}
+
+#[test]
+fn test_typeof_returns_string() {
+    assert!(check("x = 1\nprint('type: %s', typeof(x))").is_empty());
+
+    let diagnostics = check("print('type: %d', typeof(1))");
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("type string"));
+}
+
+#[test]
+fn test_typeof_argument_count_is_checked() {
+    let diagnostics = check("t = typeof(1, 2)");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "typeof() expects 1 argument, found 2"
+    );
+}
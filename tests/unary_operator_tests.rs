@@ -0,0 +1,78 @@
+mod common;
+
+use common::parse;
+use grit::codegen::CodeGenerator;
+use grit::lexer::Span;
+use grit::parser::{BinaryOperator, Expr, Statement, UnaryOperator};
+
+fn parse_expr(input: &str) -> Expr {
+    match parse(input).statements.remove(0) {
+        Statement::Expression(expr) => expr.without_spans(),
+        other => panic!("Expected expression, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_negative_literals() {
+    assert_eq!(
+        parse_expr("-3.5"),
+        Expr::UnaryOp {
+            op: UnaryOperator::Negate,
+            operand: Box::new(Expr::Float {
+                value: 3.5,
+                span: Span::default()
+            }),
+            span: Span::default(),
+        }
+    );
+
+    let program = parse("x = -1");
+    match &program.statements[0] {
+        Statement::Assignment { value, .. } => assert_eq!(
+            value.without_spans(),
+            Expr::UnaryOp {
+                op: UnaryOperator::Negate,
+                operand: Box::new(Expr::Integer {
+                    value: 1,
+                    span: Span::default()
+                }),
+                span: Span::default(),
+            }
+        ),
+        other => panic!("Expected assignment, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_unary_binds_tighter_than_binary() {
+    assert_eq!(parse_expr("-x * y").to_string(), "((-x) * y)");
+    assert_eq!(parse_expr("-(a + b)").to_string(), "(-((a + b)))");
+    assert_eq!(parse_expr("+x - -y").to_string(), "((+x) - (-y))");
+}
+
+#[test]
+fn test_minus_after_operand_is_still_subtraction() {
+    match parse_expr("a - 1") {
+        Expr::BinaryOp { op, .. } => assert_eq!(op, BinaryOperator::Subtract),
+        other => panic!("Expected subtraction, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_generate_unary_minus_and_plus() {
+    let code =
+        CodeGenerator::generate_program(&parse("x = -1\ny = -(x + 2) * +3\nz = - -x\nw = -3.5"));
+    assert!(code.contains("let x = -1;"));
+    assert!(code.contains("let y = -(x + 2) * 3;"));
+    assert!(code.contains("let z = -(-x);"));
+    assert!(code.contains("let w = -3.5;"));
+}
+
+#[test]
+fn test_unary_minus_keeps_operand_type() {
+    let code =
+        CodeGenerator::generate_program(&parse("a = typeof(-1)\nb = typeof(-2.5)\nc = typeof(+1)"));
+    assert!(code.contains("let a = \"int\";"));
+    assert!(code.contains("let b = \"float\";"));
+    assert!(code.contains("let c = \"int\";"));
+}
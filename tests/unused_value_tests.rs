@@ -0,0 +1,71 @@
+use grit::lexer::{Span, Tokenizer};
+use grit::parser::Parser;
+use grit::semantic::{check_program, Diagnostic, Severity};
+
+fn warnings(input: &str) -> Vec<Diagnostic> {
+    let tokens = Tokenizer::new(input).tokenize().unwrap();
+    let program = Parser::new(tokens).parse().unwrap();
+    check_program(&program)
+        .into_iter()
+        .filter(|diagnostic| diagnostic.severity == Severity::Warning)
+        .collect()
+}
+
+fn messages(input: &str) -> Vec<String> {
+    warnings(input)
+        .into_iter()
+        .map(|diagnostic| diagnostic.message)
+        .collect()
+}
+
+#[test]
+fn test_unused_value_in_function() {
+    let found = warnings("fn f(x) {\n  x == 1\n  x + 1\n}");
+
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].message, "expression value is never used");
+    assert_eq!(found[0].span, Span::new(2, 3, 2, 8));
+}
+
+#[test]
+fn test_unused_values_in_blocks_and_tests() {
+    let found = warnings(
+        "fn f(x) {\n  while x < 3 {\n    x\n    x = x + 1\n  }\n  [x, -x]\n  nil\n}\n\
+         test 'sums' {\n  1 + 2\n  assert(true)\n}",
+    );
+
+    let spans: Vec<Span> = found.iter().map(|diagnostic| diagnostic.span).collect();
+    assert_eq!(
+        spans,
+        vec![
+            Span::new(3, 5, 3, 5),
+            Span::new(6, 3, 6, 9),
+            Span::new(10, 3, 10, 7),
+        ]
+    );
+}
+
+#[test]
+fn test_top_level_expressions_are_not_flagged() {
+    assert!(messages("1 + 2\nx = 1\nwhile x < 3 {\n  x\n  x = x + 1\n}\n[x, -x]").is_empty());
+    assert!(messages(include_str!("../examples/simple.grit")).is_empty());
+}
+
+#[test]
+fn test_last_expression_of_a_body_is_its_value() {
+    assert!(messages("fn f(x) {\n  x * 2\n}").is_empty());
+    assert!(messages("class P\nfn P > one() {\n  1\n}").is_empty());
+    assert!(messages("double = |x| x * 2").is_empty());
+    assert_eq!(
+        messages("fn f(x) {\n  if x > 1 {\n    x\n  }\n  x\n}"),
+        vec!["expression value is never used"]
+    );
+}
+
+#[test]
+fn test_expressions_with_side_effects_are_not_flagged() {
+    let source = "fn f() {\n  1\n}\nclass P\nfn P > go() {\n  1\n}\np = P.new()\n\
+                  f()\np.go()\np.go\n[f()]\nf() + 1\nprint('%d', 1)";
+
+    assert!(messages(source).is_empty());
+}
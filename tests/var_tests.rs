@@ -0,0 +1,137 @@
+mod common;
+
+use common::{errors, generate, parse, try_parse};
+use grit::lexer::Span;
+use grit::parser::{Expr, Statement};
+
+#[test]
+fn test_parse_var_declaration() {
+    let program = parse("var count = 0\ntotal: float = 1");
+
+    assert_eq!(
+        program.statements[0],
+        Statement::Assignment {
+            name: "count".to_string(),
+            mutable: true,
+            ty: None,
+            value: Expr::Integer {
+                value: 0,
+                span: Span::new(1, 13, 1, 13),
+            },
+            span: Span::new(1, 1, 1, 9),
+        }
+    );
+    assert_eq!(program.statements[0].to_string(), "var count = 0");
+    assert!(matches!(
+        program.statements[1],
+        Statement::Assignment { mutable: false, .. }
+    ));
+    assert_eq!(
+        parse("var rate: float = 2").statements[0].to_string(),
+        "var rate: float = 2"
+    );
+}
+
+#[test]
+fn test_malformed_var_is_an_error() {
+    assert!(try_parse("var").is_err());
+    assert!(try_parse("var 1 = 2").is_err());
+    assert!(try_parse("var x").is_err());
+    assert!(try_parse("var x: list = 1").is_err());
+}
+
+#[test]
+fn test_check_reassignment() {
+    assert!(errors("var x = 1\nx = 2\nwhile x < 5 {\n  x = x + 1\n}").is_empty());
+    assert_eq!(
+        errors("x = 1\nx = 2"),
+        vec!["cannot reassign 'x'; declare it with 'var' to allow it"]
+    );
+    assert_eq!(
+        errors("fn f(n) {\n  n = n - 1\n  n\n}"),
+        vec!["cannot reassign 'n'; declare it with 'var' to allow it"]
+    );
+    // Updating it from a nested scope is reassigning it too
+    assert_eq!(
+        errors("x = 1\nif x > 0 {\n  x = 2\n}"),
+        vec!["cannot reassign 'x'; declare it with 'var' to allow it"]
+    );
+    assert_eq!(
+        errors("fn count(n) {\n  while n > 0 {\n    n = n - 1\n  }\n}"),
+        vec!["cannot reassign 'n'; declare it with 'var' to allow it"]
+    );
+    // unless an annotation declares a new variable there
+    assert!(errors("x = 1\nif x > 0 {\n  x: float = 2.0\n}").is_empty());
+}
+
+#[test]
+fn test_check_parallel_reassignment() {
+    assert!(errors("var a = 1\nvar b = 2\na, b = b, a").is_empty());
+    assert_eq!(
+        errors("a = 1\nb = 2\na, b = b, a"),
+        vec![
+            "cannot reassign 'a'; declare it with 'var' to allow it",
+            "cannot reassign 'b'; declare it with 'var' to allow it",
+        ]
+    );
+    assert_eq!(
+        errors("a = 1\n[a, b] = [1, 2]"),
+        vec!["cannot reassign 'a'; declare it with 'var' to allow it"]
+    );
+}
+
+#[test]
+fn test_var_keeps_its_type() {
+    assert_eq!(
+        errors("var x = 1\nx = 'a'"),
+        vec!["'x' is declared int but assigned string"]
+    );
+    assert!(errors("var x = 1.5\nx = 2").is_empty());
+    assert_eq!(
+        errors("global g = 1\nvar g = 2"),
+        vec!["global 'g' cannot be redeclared with 'var'"]
+    );
+}
+
+#[test]
+fn test_generate_var() {
+    let code = generate(
+        "var i = 0\nvar f: float = 0\nn = 3\nwhile i < n {\n  i = i + 1\n  f = i\n}\n\
+         var a = 0\nvar b = 1\na, b = b, a + b",
+    );
+
+    assert!(code.contains("let mut i = 0;"));
+    assert!(code.contains("let mut f: f64 = 0 as f64;"));
+    assert!(code.contains("let n = 3;"));
+    assert!(code.contains("        i = i + 1;\n        f = i as f64;"));
+    assert!(code.contains("(a, b) = (b, a + b);"));
+}
+
+#[test]
+fn test_nested_assignment_updates_outer_variable() {
+    let code = generate("var x = 0\nwhile x < 3 {\n  x = x + 1\n  y = x\n}\nprint('%d', x)");
+
+    assert!(code.contains("let mut x = 0;"));
+    assert!(code.contains("        x = x + 1;\n        let y = x;"));
+    assert!(errors("var x = 0\nwhile x < 3 {\n  x = x + 1\n}").is_empty());
+    assert_eq!(
+        errors("var x = 0\nif true {\n  x = 'a'\n}"),
+        vec!["'x' is declared int but assigned string"]
+    );
+}
+
+#[test]
+fn test_nested_annotated_assignment_declares() {
+    let code = generate("x = 0\nif true {\n  x: float = 1\n}");
+
+    assert!(code.contains("let x = 0;"));
+    assert!(code.contains("let x: f64 = 1 as f64;"));
+}
+
+#[test]
+fn test_parameter_assigned_in_loop_is_mut() {
+    let code = generate("fn count(n) {\n  while n > 0 {\n    n = n - 1\n  }\n  n\n}");
+
+    assert!(code.contains("fn count(mut n: i64)"));
+    assert!(code.contains("        n = n - 1;"));
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use common::{generate, parse, try_parse};
+use grit::diff::AstDiff;
+use grit::lexer::{TokenType, Tokenizer};
+use grit::parser::{ParseError, Statement};
+use grit::semantic::{check_program, SymbolTable};
+
+const SUM: &str = "fn sum(first, rest...) {\n  first + rest[0]\n}\n";
+
+#[test]
+fn test_tokenize_ellipsis() {
+    let mut tokenizer = Tokenizer::new("nums... a..b");
+    let types: Vec<TokenType> = tokenizer
+        .tokenize()
+        .unwrap()
+        .into_iter()
+        .map(|token| token.token_type)
+        .collect();
+
+    assert_eq!(
+        types,
+        vec![
+            TokenType::Identifier("nums".into()),
+            TokenType::DotDotDot,
+            TokenType::Identifier("a".into()),
+            TokenType::DotDot,
+            TokenType::Identifier("b".into()),
+            TokenType::Eof,
+        ]
+    );
+}
+
+#[test]
+fn test_parse_variadic_function() {
+    let program = parse(SUM);
+
+    match &program.statements[0] {
+        Statement::FunctionDef {
+            params, variadic, ..
+        } => {
+            assert_eq!(params, &["first", "rest"]);
+            assert!(*variadic);
+        }
+        other => panic!("expected a function, got {:?}", other),
+    }
+    assert_eq!(program.statements[0].to_string(), "fn sum(first, rest...)");
+}
+
+#[test]
+fn test_parse_variadic_method() {
+    let program = parse("fn Bag > add(items...) {\n  items[0]\n}");
+
+    assert!(matches!(
+        program.statements[0],
+        Statement::MethodDef { variadic: true, .. }
+    ));
+    assert_eq!(program.statements[0].to_string(), "fn Bag > add(items...)");
+}
+
+#[test]
+fn test_only_last_parameter_can_be_variadic() {
+    match try_parse("fn f(a..., b) { a }") {
+        Err(ParseError::UnexpectedToken { expected, .. }) => {
+            assert_eq!(expected, "')' after variadic parameter")
+        }
+        other => panic!("expected UnexpectedToken, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_lambdas_cannot_be_variadic() {
+    assert!(matches!(
+        try_parse("f = fn(a...) { a }"),
+        Err(ParseError::UnexpectedToken { .. })
+    ));
+}
+
+#[test]
+fn test_symbol_records_variadic_function() {
+    let symbols = SymbolTable::from_program(&parse(SUM));
+    let sum = symbols.function("sum").unwrap();
+
+    assert!(sum.variadic);
+    assert_eq!(sum.required_args(), 1);
+}
+
+#[test]
+fn test_too_few_arguments_is_an_error() {
+    let diagnostics = check_program(&parse(&format!("{}sum()", SUM)));
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "sum() expects at least 1 argument, found 0"
+    );
+    assert!(check_program(&parse(&format!("{}sum(1)", SUM))).is_empty());
+}
+
+#[test]
+fn test_variadic_parameter_is_an_array() {
+    let diagnostics = check_program(&parse("fn f(nums...) {\n  print('%d', nums)\n  0\n}"));
+
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("array<unknown>"));
+}
+
+#[test]
+fn test_generate_variadic_function_and_calls() {
+    let code = generate(&format!("{}x = sum(1, 2, 3)\ny = sum(1)", SUM));
+
+    assert!(code.contains("fn sum(first: i64, rest: &[i64]) -> i64 {"));
+    assert!(code.contains("let x = sum(1, &[2, 3]);"));
+    assert!(code.contains("let y = sum(1, &[]);"));
+}
+
+#[test]
+fn test_generate_variadic_method_calls() {
+    let code = generate(
+        "class Bag\nfn Bag > new(size) {\n  self.size = size\n}\nfn Bag > add(items...) {\n  size + items[0]\n}\nb = Bag.new(3)\nx = b.add(4, 5)",
+    );
+
+    assert!(code.contains("fn add(&self, items: &[i64]) -> i64 {"));
+    assert!(code.contains("let x = b.add(&[4, 5]);"));
+}
+
+#[test]
+fn test_diff_reports_variadic_change() {
+    let old = parse("fn sum(nums) {\n  0\n}");
+    let new = parse("fn sum(nums...) {\n  0\n}");
+    let diff = AstDiff::between(&old, &new);
+
+    assert_eq!(diff.changes.len(), 1);
+    assert_eq!(diff.changes[0].details, vec!["params: (nums) -> (nums...)"]);
+}
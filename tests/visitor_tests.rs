@@ -0,0 +1,130 @@
+mod common;
+
+use common::parse;
+use grit::parser::ast::visit::{walk_expr, walk_expr_mut, walk_statement};
+use grit::parser::{Expr, Program, Statement, Visitor, VisitorMut};
+
+/// Collects the names of every identifier, in visiting order
+#[derive(Default)]
+struct Identifiers(Vec<String>);
+
+impl Visitor for Identifiers {
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let Expr::Identifier { name, .. } = expr {
+            self.0.push(name.to_string());
+        }
+        walk_expr(self, expr);
+    }
+}
+
+fn identifiers(program: &Program) -> Vec<String> {
+    let mut visitor = Identifiers::default();
+    visitor.visit_program(program);
+    visitor.0
+}
+
+#[test]
+fn test_visitor_reaches_every_statement() {
+    let source = "fn f(a) {\n  return a\n}\nclass P { x }\nfn P > m() {\n  b\n}\n\
+                  x = c\nvar y: int = d\np.x = e\nconst K = 1\nglobal g = 2\n\
+                  h, i = j, k\n[l, m] = n\n{\n  o\n}\n\
+                  if q {\n  r\n} elif s {\n  t\n} else {\n  u\n}\n\
+                  match v {\n  1 => w\n  _ => z\n}\nwhile a1 {\n  b1\n}\nloop {\n  break\n}\n\
+                  do {\n  c1\n} while d1\ntest 'works' {\n  e1\n}";
+
+    assert_eq!(
+        identifiers(&parse(source)),
+        [
+            "a", "b", "c", "d", "p", "e", "j", "k", "n", "o", "q", "r", "s", "t", "u", "v", "w",
+            "z", "a1", "b1", "c1", "d1", "e1",
+        ]
+    );
+}
+
+#[test]
+fn test_visitor_reaches_every_expression() {
+    let source = "x = -a + (b) * [c, d][e] + [(f, g)] + {h: i}[j] + k[l..m] + n(o, p = q)\n\
+                  y = (r)(s) + t.u + v.w(z) + cond {\n  a1 { b1 }\n  else { c1 }\n} + |d1| d1 + e1\n\
+                  if f1 = g1 {\n  h1\n}";
+
+    assert_eq!(
+        identifiers(&parse(source)),
+        [
+            "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m", "o", "q", "r", "s",
+            "t", "v", "z", "a1", "b1", "c1", "d1", "e1", "g1", "h1",
+        ]
+    );
+}
+
+#[test]
+fn test_visitor_reaches_match_patterns() {
+    struct Literals(usize);
+
+    impl Visitor for Literals {
+        fn visit_expr(&mut self, expr: &Expr) {
+            if let Expr::Integer { .. } = expr {
+                self.0 += 1;
+            }
+            walk_expr(self, expr);
+        }
+    }
+
+    let mut visitor = Literals(0);
+    visitor.visit_program(&parse(
+        "match n {\n  1, 2 => x = 3\n  Color.Red => y = 4\n  _ => z = 5\n}",
+    ));
+    assert_eq!(visitor.0, 5);
+}
+
+#[test]
+fn test_override_can_skip_children() {
+    struct TopLevelOnly(Vec<String>);
+
+    impl Visitor for TopLevelOnly {
+        fn visit_statement(&mut self, stmt: &Statement) {
+            match stmt {
+                Statement::FunctionDef { .. } => {}
+                _ => walk_statement(self, stmt),
+            }
+        }
+
+        fn visit_expr(&mut self, expr: &Expr) {
+            if let Expr::Identifier { name, .. } = expr {
+                self.0.push(name.to_string());
+            }
+            walk_expr(self, expr);
+        }
+    }
+
+    let mut visitor = TopLevelOnly(Vec::new());
+    visitor.visit_program(&parse("fn f() {\n  hidden\n}\nshown"));
+    assert_eq!(visitor.0, ["shown"]);
+}
+
+#[test]
+fn test_visitor_mut_rewrites_in_place() {
+    struct Rename;
+
+    impl VisitorMut for Rename {
+        fn visit_expr_mut(&mut self, expr: &mut Expr) {
+            if let Expr::Identifier { name, .. } = expr {
+                *name = format!("renamed_{name}").into();
+            }
+            walk_expr_mut(self, expr);
+        }
+    }
+
+    let mut program = parse("fn f(a) {\n  a + b(c)\n}\nif d {\n  e = [g, h.i]\n}");
+    Rename.visit_program_mut(&mut program);
+
+    assert_eq!(
+        identifiers(&program),
+        [
+            "renamed_a",
+            "renamed_c",
+            "renamed_d",
+            "renamed_g",
+            "renamed_h"
+        ]
+    );
+}